@@ -238,6 +238,8 @@ async fn main() -> anyhow::Result<()> {
 
     eth_sender_config.sender.operator_private_key =
         H256::from_str(key_without_prefix).expect("Cannot deserialize private key");
+    // The provided key is used even if the remote signer is configured.
+    eth_sender_config.sender.remote_signer_url = None;
 
     let mut storage = StorageProcessor::establish_connection().await?;
     let client = EthereumGateway::from_config(
@@ -1,8 +1,10 @@
 use web3::api::Eth;
 use web3::contract::Options;
-use web3::types::{Address, BlockId, U256};
+use web3::types::{Address, BlockId, H256, U256};
 use web3::Transport;
 
+use zksync_types::BlockNumber;
+
 use zksync_contracts::{
     zksync_contract, zksync_contract_v0, zksync_contract_v1, zksync_contract_v2, zksync_contract_v3,
 };
@@ -44,6 +46,21 @@ impl<T: Transport> ZkSyncDeployedContract<T> {
             .as_u32()
     }
 
+    /// Returns the hash of the `StoredBlockInfo` of the committed block, it's zero if the block
+    /// isn't committed. Only available since the contract version 4.
+    pub async fn get_stored_block_hash(&self, block_number: BlockNumber) -> anyhow::Result<H256> {
+        self.web3_contract
+            .query::<H256, Option<Address>, Option<BlockId>, U256>(
+                "storedBlockHashes",
+                U256::from(*block_number),
+                None,
+                Options::default(),
+                None,
+            )
+            .await
+            .map_err(|err| anyhow::format_err!("Can't query the stored block hash: {}", err))
+    }
+
     pub fn version0(eth: Eth<T>, address: Address) -> ZkSyncDeployedContract<T> {
         let abi = zksync_contract_v0();
         ZkSyncDeployedContract {
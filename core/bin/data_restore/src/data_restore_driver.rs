@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
 
 // External deps
 use web3::{
@@ -9,18 +10,21 @@ use web3::{
 // Workspace deps
 use zksync_contracts::governance_contract;
 use zksync_crypto::{
+    convert::FeConvert,
     params::{MIN_NFT_TOKEN_ID, NFT_STORAGE_ACCOUNT_ADDRESS, NFT_STORAGE_ACCOUNT_ID, NFT_TOKEN_ID},
     Fr,
 };
 use zksync_types::{
-    Account, AccountId, AccountMap, AccountUpdate, BlockNumber, SerialId, Token, TokenKind,
+    block::Block, Account, AccountId, AccountMap, AccountUpdate, BlockNumber, SerialId, Token,
+    TokenKind,
 };
 
 // Local deps
 use crate::{
-    contract::{get_genesis_account, ZkSyncDeployedContract},
+    contract::{get_genesis_account, ZkSyncContractVersion, ZkSyncDeployedContract},
     eth_tx_helpers::get_ethereum_transaction,
     events_state::EventsState,
+    progress::{stored_block_hash, RestoreProgress, SharedProgress},
     rollup_ops::RollupOpsBlock,
     storage_interactor::StorageInteractor,
     tree_state::TreeState,
@@ -81,6 +85,11 @@ pub struct DataRestoreDriver<T: Transport> {
     /// Serial id of the last priority operation processed by the driver. It's necessary to manually
     /// keep track of it since it's impossible to restore it from the contract.
     pub last_priority_op_serial_id: SerialId,
+    /// Whether the state roots of the restored blocks are checked against the ones
+    /// committed to the contract.
+    pub verify_state_roots: bool,
+    /// Progress of the restoring, shared with the progress server.
+    pub progress: SharedProgress,
 }
 
 impl<T: Transport> DataRestoreDriver<T> {
@@ -134,9 +143,19 @@ impl<T: Transport> DataRestoreDriver<T> {
             finite_mode,
             final_hash,
             last_priority_op_serial_id: 0,
+            verify_state_roots: false,
+            progress: Arc::new(RwLock::new(RestoreProgress::default())),
         }
     }
 
+    /// Enables the check of the state root of each restored block against the contract.
+    /// The mismatched blocks are reported in the progress, and fail the restoring in the
+    /// finite mode.
+    pub fn enable_state_roots_verification(&mut self) {
+        self.verify_state_roots = true;
+        self.progress.write().unwrap().state_roots_verification = true;
+    }
+
     /// Sets the 'genesis' state.
     /// Tree with inserted genesis account will be created.
     /// Used when restore driver is restarted.
@@ -322,6 +341,7 @@ impl<T: Transport> DataRestoreDriver<T> {
 
         transaction.commit().await;
 
+        self.update_progress(total_verified_blocks);
         vlog::info!(
             "State has been loaded\nProcessed {:?} blocks on contract\nRoot hash: {:?}\n",
             last_verified_block,
@@ -329,6 +349,9 @@ impl<T: Transport> DataRestoreDriver<T> {
         );
 
         let is_finished = self.finite_mode && (total_verified_blocks == *last_verified_block);
+        if is_finished {
+            self.assert_state_roots_match();
+        }
         // Save tree cache if necessary.
         if !is_cached {
             vlog::info!("Saving tree cache for future re-uses");
@@ -360,6 +383,7 @@ impl<T: Transport> DataRestoreDriver<T> {
                         self.zksync_contract.get_total_verified_blocks().await;
 
                     let last_verified_block = self.tree_state.block_number;
+                    self.update_progress(total_verified_blocks);
 
                     // We must update the Ethereum stats table to match the actual stored state
                     // to keep the `state_keeper` consistent with the `eth_sender`.
@@ -397,6 +421,7 @@ impl<T: Transport> DataRestoreDriver<T> {
                         if self.final_hash.is_some() && !final_hash_was_found {
                             panic!("Final hash was not met during the state restoring process");
                         }
+                        self.assert_state_roots_match();
                         break;
                     }
                 }
@@ -427,6 +452,7 @@ impl<T: Transport> DataRestoreDriver<T> {
             )
             .await
             .expect("Updating events state: cant update events state");
+        self.progress.write().unwrap().last_watched_eth_block = last_watched_eth_block_number;
         interactor
             .save_events_state(
                 &block_events,
@@ -468,6 +494,15 @@ impl<T: Transport> DataRestoreDriver<T> {
                     &mut self.last_priority_op_serial_id,
                 )
                 .expect("Updating tree state: cant update tree from operations");
+            if self.verify_state_roots {
+                self.check_state_root(
+                    &block,
+                    op_block
+                        .contract_version
+                        .expect("contract version must be set"),
+                )
+                .await;
+            }
             blocks.push(block);
             updates.push(acc_updates);
             count += 1;
@@ -503,6 +538,54 @@ impl<T: Transport> DataRestoreDriver<T> {
         vlog::debug!("Updated state");
     }
 
+    /// Checks that the hash of the restored block, which contains its state root, matches
+    /// the one stored by the contract when the block was committed.
+    async fn check_state_root(&self, block: &Block, contract_version: ZkSyncContractVersion) {
+        // The contract stores the hashes of the committed blocks since the version 4.
+        if i32::from(contract_version) < 4 {
+            return;
+        }
+
+        let stored_hash = self
+            .zksync_contract
+            .get_stored_block_hash(block.block_number)
+            .await
+            .expect("Can't check the state root of the restored block");
+        let restored_hash = stored_block_hash(block);
+        let matches = stored_hash == restored_hash;
+        if !matches {
+            vlog::error!(
+                "State root of the restored block {} doesn't match the contract: \
+                 stored block hash {:?}, restored block hash {:?}",
+                *block.block_number,
+                stored_hash,
+                restored_hash
+            );
+        }
+        self.progress
+            .write()
+            .unwrap()
+            .record_state_root_check(block.block_number, matches);
+    }
+
+    fn assert_state_roots_match(&self) {
+        let mismatches = &self.progress.read().unwrap().state_root_mismatches;
+        if !mismatches.is_empty() {
+            panic!(
+                "State roots of the restored blocks {:?} don't match the contract",
+                mismatches
+            );
+        }
+    }
+
+    fn update_progress(&self, total_verified_blocks: u32) {
+        let mut progress = self.progress.write().unwrap();
+        progress.last_watched_eth_block = self.events_state.last_watched_eth_block_number;
+        progress.last_restored_block = self.tree_state.block_number;
+        progress.root_hash = self.tree_state.root_hash().to_hex();
+        progress.total_verified_blocks = total_verified_blocks;
+    }
+
     /// Gets new operations blocks from events, updates rollup operations stored state.
     /// Returns new rollup operations blocks
     async fn update_operations_state(
@@ -5,6 +5,7 @@ pub mod eth_tx_helpers;
 pub mod events;
 pub mod events_state;
 pub mod inmemory_storage_interactor;
+pub mod progress;
 pub mod rollup_ops;
 pub mod storage_interactor;
 pub mod tree_state;
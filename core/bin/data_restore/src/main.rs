@@ -1,3 +1,5 @@
+use std::net::SocketAddr;
+
 use serde::Deserialize;
 use structopt::StructOpt;
 use web3::transports::Http;
@@ -10,8 +12,8 @@ use web3::Web3;
 use zksync_data_restore::contract::ZkSyncDeployedContract;
 use zksync_data_restore::{
     add_tokens_to_storage, data_restore_driver::DataRestoreDriver,
-    database_storage_interactor::DatabaseStorageInteractor, storage_interactor::StorageInteractor,
-    END_ETH_BLOCKS_OFFSET, ETH_BLOCKS_STEP,
+    database_storage_interactor::DatabaseStorageInteractor, progress::start_progress_server,
+    storage_interactor::StorageInteractor, END_ETH_BLOCKS_OFFSET, ETH_BLOCKS_STEP,
 };
 use zksync_types::network::Network;
 
@@ -45,6 +47,15 @@ struct Opt {
     /// Provides a path to the configuration file for data restore
     #[structopt(long = "config", name = "config")]
     config_path: Option<String>,
+
+    /// Checks the state root of each restored block against the one committed to the contract.
+    /// In `finite` mode, the restoring fails if any of them doesn't match
+    #[structopt(long)]
+    verify_state_roots: bool,
+
+    /// Serves the progress of the restoring on `/progress` at the provided address, e.g. `0.0.0.0:3090`
+    #[structopt(long)]
+    progress_addr: Option<SocketAddr>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -122,6 +133,13 @@ async fn main() {
         final_hash,
         contract,
     );
+    if opt.verify_state_roots {
+        driver.enable_state_roots_verification();
+    }
+    if let Some(progress_addr) = opt.progress_addr {
+        vlog::info!("Serving the restoring progress on {}", progress_addr);
+        start_progress_server(driver.progress.clone(), progress_addr);
+    }
 
     let mut interactor = StorageInteractor::Database(DatabaseStorageInteractor::new(storage));
     // If genesis is argument is present - there will be fetching contracts creation transactions to get first eth block and genesis acc address
@@ -0,0 +1,101 @@
+//! Progress of the data restore, exposed by the HTTP endpoint so the third parties
+//! verifying the network can watch the restoring state and the state roots check.
+
+use std::net::SocketAddr;
+use std::sync::{Arc, RwLock};
+use std::thread;
+
+use actix_web::{web, App, HttpResponse, HttpServer};
+use serde::Serialize;
+
+use zksync_types::{block::Block, BlockNumber, H256};
+
+/// Progress shared between the driver and the HTTP server.
+pub type SharedProgress = Arc<RwLock<RestoreProgress>>;
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreProgress {
+    /// The last Ethereum block which events were processed.
+    pub last_watched_eth_block: u64,
+    /// The last block which operations were applied to the tree.
+    pub last_restored_block: BlockNumber,
+    /// Root hash of the tree after the last restored block.
+    pub root_hash: String,
+    /// Amount of the blocks verified on the contract.
+    pub total_verified_blocks: u32,
+    /// Whether the state roots of the restored blocks are checked against the contract.
+    pub state_roots_verification: bool,
+    /// The last block which state root was checked against the contract.
+    pub last_checked_block: Option<BlockNumber>,
+    /// Blocks which state roots don't match the ones committed to the contract.
+    pub state_root_mismatches: Vec<BlockNumber>,
+}
+
+impl RestoreProgress {
+    /// Records the result of the state root check of the restored block.
+    pub fn record_state_root_check(&mut self, block_number: BlockNumber, matches: bool) {
+        self.last_checked_block = Some(block_number);
+        if !matches && !self.state_root_mismatches.contains(&block_number) {
+            self.state_root_mismatches.push(block_number);
+        }
+    }
+}
+
+/// Returns the hash of the block as it's stored by the contract: the keccak hash
+/// of the ABI-encoded `StoredBlockInfo`, which contains the state root of the block.
+pub fn stored_block_hash(block: &Block) -> H256 {
+    let stored_block_info = zksync_types::aggregated_operations::stored_block_info(block);
+    H256::from(tiny_keccak::keccak256(&ethabi::encode(&[
+        stored_block_info,
+    ])))
+}
+
+#[actix_web::get("/progress")]
+async fn restore_progress(data: web::Data<SharedProgress>) -> actix_web::Result<HttpResponse> {
+    let progress = data.read().unwrap().clone();
+    Ok(HttpResponse::Ok().json(progress))
+}
+
+/// Starts the HTTP server with the progress of the data restore in the separate thread.
+pub fn start_progress_server(progress: SharedProgress, bind_address: SocketAddr) {
+    thread::Builder::new()
+        .name("data-restore-progress".to_string())
+        .spawn(move || {
+            let actix_runtime = actix_rt::System::new();
+
+            actix_runtime.block_on(async move {
+                HttpServer::new(move || {
+                    App::new()
+                        .app_data(web::Data::new(progress.clone()))
+                        .service(restore_progress)
+                })
+                .bind(bind_address)
+                .expect("failed to bind the progress server")
+                .run()
+                .await
+                .expect("progress server crashed")
+            })
+        })
+        .expect("failed to start the progress server");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn state_root_checks() {
+        let mut progress = RestoreProgress::default();
+        progress.record_state_root_check(BlockNumber(1), true);
+        assert_eq!(progress.last_checked_block, Some(BlockNumber(1)));
+        assert!(progress.state_root_mismatches.is_empty());
+
+        // The block can be checked again after the restart of the data restore.
+        progress.record_state_root_check(BlockNumber(2), false);
+        progress.record_state_root_check(BlockNumber(2), false);
+        progress.record_state_root_check(BlockNumber(3), true);
+        assert_eq!(progress.last_checked_block, Some(BlockNumber(3)));
+        assert_eq!(progress.state_root_mismatches, vec![BlockNumber(2)]);
+    }
+}
@@ -1,23 +1,6 @@
-use jsonwebtoken::{encode as encode_token, EncodingKey, Header};
 use serde::{Deserialize, Serialize};
 use std::time;
-
-#[derive(Debug, Serialize, Deserialize)]
-struct PayloadAuthToken {
-    /// Subject (whom auth token refers to).
-    sub: String,
-    /// Expiration time (as UTC timestamp).
-    exp: usize,
-}
-
-impl PayloadAuthToken {
-    pub fn new(exp: usize) -> Self {
-        Self {
-            sub: "Authorization".to_string(),
-            exp,
-        }
-    }
-}
+use zksync_utils::auth::encode_auth_token;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AuthTokenGenerator {
@@ -35,13 +18,6 @@ impl AuthTokenGenerator {
 
     /// Encode JsonWebToken with shared secret
     pub fn encode(&self) -> jsonwebtoken::errors::Result<String> {
-        // Time (Unix Timestamp) until which the token will be valid
-        let exp = time::UNIX_EPOCH.elapsed().unwrap() + self.period_availability;
-
-        encode_token(
-            &Header::default(),
-            &PayloadAuthToken::new(exp.as_secs() as usize),
-            &EncodingKey::from_secret(self.secret.as_ref()),
-        )
+        encode_auth_token(&self.secret, self.period_availability)
     }
 }
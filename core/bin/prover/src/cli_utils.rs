@@ -3,7 +3,9 @@ use std::time::Duration;
 // External deps
 use structopt::StructOpt;
 // Workspace deps
-use zksync_config::configs::ProverConfig as EnvProverConfig;
+use zksync_config::configs::{
+    network_profile::apply_network_profile, NetworkProfile, ProverConfig as EnvProverConfig,
+};
 use zksync_utils::{get_env, parse_env};
 // Local deps
 use crate::{client, prover_work_cycle, ProverConfig, ProverImpl, ShutdownRequest};
@@ -27,6 +29,10 @@ struct Opt {
     /// Name of the worker. Must be unique!
     #[structopt(index = 1)]
     worker_name: String,
+    /// Network profile to load the configs with (`mainnet`, `testnet` or `local`),
+    /// overrides the `ZKSYNC_NETWORK_PROFILE` variable
+    #[structopt(long)]
+    network_profile: Option<NetworkProfile>,
 }
 
 pub async fn main_for_prover_impl<PROVER>(run_prometheus: bool)
@@ -35,6 +41,7 @@ where
 {
     let opt = Opt::from_args();
     let worker_name = opt.worker_name;
+    let network_profile = apply_network_profile(opt.network_profile);
 
     // used env
     let prover_options = EnvProverConfig::from_env();
@@ -45,6 +52,9 @@ where
     let _vlog_guard = vlog::init();
 
     vlog::info!("creating prover, worker name: {}", worker_name);
+    if let Some(network_profile) = network_profile {
+        vlog::info!("using the network profile <{}>", network_profile);
+    }
 
     // Create client.
 
@@ -142,6 +142,12 @@ impl crate::ApiClient for ApiClient {
             if response.status() == reqwest::StatusCode::UNAUTHORIZED {
                 return Err(Permanent(format_err!("authorization error")));
             }
+            if response.status() == reqwest::StatusCode::CONFLICT {
+                return Err(Permanent(format_err!(
+                    "the lease of the job {} is lost, it may be given to another prover",
+                    job_id
+                )));
+            }
 
             Ok(())
         };
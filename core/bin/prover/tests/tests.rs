@@ -65,6 +65,8 @@ impl Default for MockProverConfigs {
             witness_generator: zksync_config::configs::prover::WitnessGenerator {
                 prepare_data_interval: 5000,
                 witness_generators: 2,
+                shard_index: 0,
+                shards_count: 1,
             },
         };
 
@@ -6,26 +6,40 @@ use structopt::StructOpt;
 
 use serde::{Deserialize, Serialize};
 
+use zksync_api::api_server::config_reload::ConfigReloader;
 use zksync_api::fee_ticker::{run_updaters, FeeTicker, TickerInfo};
+use zksync_api::signature_checker::SignatureCheckerConfig;
 use zksync_core::{genesis_init, run_core, wait_for_tasks};
-use zksync_eth_client::EthereumGateway;
+use zksync_eth_client::{EthereumGateway, GasPriceOracle};
+use zksync_eth_signer::{OperatorSigner, PrivateKeySigner, RemoteSigner};
 use zksync_forced_exit_requests::run_forced_exit_requests_actors;
 use zksync_gateway_watcher::run_gateway_watcher_if_multiplexed;
-use zksync_witness_generator::run_prover_server;
+use zksync_witness_generator::{exit_proof_generator::run_exit_proof_generator, run_prover_server};
 
-use tokio::task::JoinHandle;
+use std::time::Duration;
+use tokio::{sync::watch, task::JoinHandle};
 use zksync_config::configs::api::{PrivateApiConfig, PrometheusConfig, TokenConfig};
+use zksync_config::configs::network_profile::apply_network_profile;
 use zksync_config::{
-    configs::api::{CommonApiConfig, JsonRpcConfig, ProverApiConfig, RestApiConfig, Web3Config},
+    configs::api::{CommonApiConfig, ProverApiConfig, RestApiConfig, Web3Config},
     ChainConfig, ContractsConfig, DBConfig, ETHClientConfig, ETHSenderConfig, ETHWatchConfig,
-    ForcedExitRequestsConfig, GatewayWatcherConfig, ProverConfig, TickerConfig, ZkSyncConfig,
+    ForcedExitRequestsConfig, GatewayWatcherConfig, NetworkProfile, ProverConfig, TickerConfig,
+    TokenHandlerConfig, ZkSyncConfig,
 };
+use zksync_core::network_stats_aggregator::run_network_stats_aggregator;
 use zksync_core::rejected_tx_cleaner::run_rejected_tx_cleaner;
+use zksync_core::state_pruner::run_state_pruner;
 use zksync_mempool::run_mempool_tx_handler;
 use zksync_prometheus_exporter::{run_operation_counter, run_prometheus_exporter};
 use zksync_storage::ConnectionPool;
+use zksync_token_db_cache::TokenDenylist;
+use zksync_types::{tx::PackedEthSignature, Address};
 
 const DEFAULT_CHANNEL_CAPACITY: usize = 32_768;
+/// How long the components are waited for in addition to the time they drain the requests.
+const COMPONENTS_STOP_MARGIN: Duration = Duration::from_secs(5);
+/// How long the database connections in use are waited for once the components are stopped.
+const DB_POOL_CLOSE_TIMEOUT: Duration = Duration::from_secs(10);
 
 #[derive(Debug, Clone, Copy)]
 pub enum ServerCommand {
@@ -40,6 +54,7 @@ pub enum Component {
     Web3Api,
     RpcApi,
     RpcWebSocketApi,
+    GrpcApi,
 
     // Core components
     Fetchers,
@@ -52,6 +67,9 @@ pub enum Component {
     Prometheus,
     PrometheusPeriodicMetrics,
     RejectedTaskCleaner,
+    StatePruner,
+    ExitProofGenerator,
+    NetworkStats,
 }
 
 impl FromStr for Component {
@@ -63,6 +81,7 @@ impl FromStr for Component {
             "web3-api" => Ok(Component::Web3Api),
             "rpc-api" => Ok(Component::RpcApi),
             "rpc-websocket-api" => Ok(Component::RpcWebSocketApi),
+            "grpc-api" => Ok(Component::GrpcApi),
             "eth-sender" => Ok(Component::EthSender),
             "witness-generator" => Ok(Component::WitnessGenerator),
             "forced-exit" => Ok(Component::ForcedExit),
@@ -70,6 +89,9 @@ impl FromStr for Component {
             "fetchers" => Ok(Component::Fetchers),
             "core" => Ok(Component::Core),
             "rejected-task-cleaner" => Ok(Component::RejectedTaskCleaner),
+            "state-pruner" => Ok(Component::StatePruner),
+            "exit-proof-generator" => Ok(Component::ExitProofGenerator),
+            "network-stats" => Ok(Component::NetworkStats),
             "prometheus-periodic-metrics" => Ok(Component::PrometheusPeriodicMetrics),
             other => Err(format!("{} is not a valid component name", other)),
         }
@@ -94,6 +116,7 @@ impl Default for ComponentsToRun {
             Component::RejectedTaskCleaner,
             Component::Fetchers,
             Component::PrometheusPeriodicMetrics,
+            Component::NetworkStats,
         ])
     }
 }
@@ -122,11 +145,17 @@ struct Opt {
         default_value = "rest-api,web3-api,rpc-api,rpc-websocket-api,eth-sender,witness-generator,forced-exit,prometheus,core,rejected-task-cleaner,fetchers,prometheus-periodic-metrics"
     )]
     components: ComponentsToRun,
+    /// Network profile to load the configs with (`mainnet`, `testnet` or `local`),
+    /// overrides the `ZKSYNC_NETWORK_PROFILE` variable
+    #[structopt(long)]
+    network_profile: Option<NetworkProfile>,
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let opt = Opt::from_args();
+    // All the components must load the configs of the same network.
+    let network_profile = apply_network_profile(opt.network_profile);
     let mut _vlog_guard = None;
     let server_mode = if opt.genesis {
         ServerCommand::Genesis
@@ -144,6 +173,9 @@ async fn main() -> anyhow::Result<()> {
 
     // It's a `ServerCommand::Launch`, perform the usual routine.
     vlog::info!("Running the zkSync server");
+    if let Some(network_profile) = network_profile {
+        vlog::info!("Using the network profile <{}>", network_profile);
+    }
 
     run_server(&opt.components).await;
 
@@ -154,8 +186,12 @@ async fn run_server(components: &ComponentsToRun) {
     let connection_pool = ConnectionPool::new(None);
     let read_only_connection_pool = ConnectionPool::new_readonly_pool(None);
     let (stop_signal_sender, mut stop_signal_receiver) = mpsc::channel(256);
+    // The components which are stopped gracefully hold the receivers until they are stopped.
+    let (shutdown_sender, shutdown_receiver) = watch::channel(false);
+    // The components of the API are tuned by reloading the config instead of restarting them.
+    let config_reloader = ConfigReloader::from_config(&ZkSyncConfig::from_env());
 
-    let mut tasks = vec![];
+    let mut tasks = vec![config_reloader.clone().run_on_sighup()];
 
     if components.0.contains(&Component::Web3Api) {
         // Run web3 api
@@ -168,14 +204,17 @@ async fn run_server(components: &ComponentsToRun) {
 
     if components.0.contains(&Component::Fetchers) {
         // Run price fetchers
-        let mut price_tasks = run_price_updaters(connection_pool.clone());
+        let mut price_tasks = run_price_updaters(connection_pool.clone(), &config_reloader);
         tasks.append(&mut price_tasks);
     }
 
     if components.0.iter().any(|c| {
         matches!(
             c,
-            Component::RpcWebSocketApi | Component::RpcApi | Component::RestApi
+            Component::RpcWebSocketApi
+                | Component::RpcApi
+                | Component::RestApi
+                | Component::GrpcApi
         )
     }) {
         // Create gateway
@@ -191,26 +230,39 @@ async fn run_server(components: &ComponentsToRun) {
             tasks.push(task);
         }
 
+        let common_config = CommonApiConfig::from_env();
+
         // Run signer
         let (sign_check_sender, sign_check_receiver) = mpsc::channel(DEFAULT_CHANNEL_CAPACITY);
         tasks.push(zksync_api::signature_checker::start_sign_checker(
-            eth_gateway,
+            eth_gateway.clone(),
             sign_check_receiver,
+            SignatureCheckerConfig::from_config(&common_config),
         ));
 
         let contracts_config = ContractsConfig::from_env();
-        let common_config = CommonApiConfig::from_env();
-        let token_config = TokenConfig::from_env();
         let chain_config = ChainConfig::from_env();
         let fee_ticker_config = TickerConfig::from_env();
-        let ticker_info = Box::new(TickerInfo::new(read_only_connection_pool.clone()));
+        let gas_price_oracle =
+            GasPriceOracle::from_config(&ETHSenderConfig::from_env().gas_price_oracle);
+        let ticker_info = Box::new(
+            TickerInfo::new(read_only_connection_pool.clone())
+                .with_gas_price_oracle(gas_price_oracle, eth_gateway.clone()),
+        );
 
         let ticker = FeeTicker::new_with_default_validator(
             ticker_info,
             fee_ticker_config,
             chain_config.max_blocks_to_aggregate(),
             read_only_connection_pool.clone(),
+            &config_reloader,
         );
+        // The denylist is shared by the mempools of the API servers.
+        let token_denylist = TokenDenylist::new(
+            connection_pool.clone(),
+            config_reloader.current().denylisted_tokens,
+        );
+        config_reloader.register_denylist(&token_denylist);
 
         if components.0.contains(&Component::RpcWebSocketApi) {
             let (mempool_tx_request_sender, mempool_tx_request_receiver) =
@@ -219,17 +271,17 @@ async fn run_server(components: &ComponentsToRun) {
                 connection_pool.clone(),
                 mempool_tx_request_receiver,
                 chain_config.state_keeper.block_chunk_sizes.clone(),
+                token_denylist.clone(),
             ));
             tasks.push(zksync_api::api_server::rpc_subscriptions::start_ws_server(
                 read_only_connection_pool.clone(),
                 sign_check_sender.clone(),
                 ticker.clone(),
-                &common_config,
-                &token_config,
-                &JsonRpcConfig::from_env(),
+                &ZkSyncConfig::from_env(),
                 chain_config.state_keeper.miniblock_iteration_interval(),
                 mempool_tx_request_sender,
                 eth_watch_config.confirmations_for_eth_event,
+                &config_reloader,
             ));
         }
 
@@ -240,16 +292,35 @@ async fn run_server(components: &ComponentsToRun) {
                 connection_pool.clone(),
                 mempool_tx_request_receiver,
                 chain_config.state_keeper.block_chunk_sizes.clone(),
+                token_denylist.clone(),
             ));
             tasks.push(zksync_api::api_server::rpc_server::start_rpc_server(
                 read_only_connection_pool.clone(),
                 sign_check_sender.clone(),
                 ticker.clone(),
-                &JsonRpcConfig::from_env(),
-                &common_config,
-                &token_config,
+                &ZkSyncConfig::from_env(),
                 mempool_tx_request_sender,
                 eth_watch_config.confirmations_for_eth_event,
+                &config_reloader,
+            ));
+        }
+
+        if components.0.contains(&Component::GrpcApi) {
+            let (mempool_tx_request_sender, mempool_tx_request_receiver) =
+                mpsc::channel(DEFAULT_CHANNEL_CAPACITY);
+            tasks.push(run_mempool_tx_handler(
+                connection_pool.clone(),
+                mempool_tx_request_receiver,
+                chain_config.state_keeper.block_chunk_sizes.clone(),
+                token_denylist.clone(),
+            ));
+            tasks.push(zksync_api::api_server::grpc::start_grpc_server(
+                read_only_connection_pool.clone(),
+                sign_check_sender.clone(),
+                ticker.clone(),
+                mempool_tx_request_sender,
+                &ZkSyncConfig::from_env(),
+                &config_reloader,
             ));
         }
 
@@ -260,6 +331,7 @@ async fn run_server(components: &ComponentsToRun) {
                 connection_pool.clone(),
                 mempool_tx_request_receiver,
                 chain_config.state_keeper.block_chunk_sizes,
+                token_denylist,
             ));
             let private_config = PrivateApiConfig::from_env();
             tasks.push(zksync_api::api_server::rest::start_server_thread_detached(
@@ -268,9 +340,12 @@ async fn run_server(components: &ComponentsToRun) {
                 RestApiConfig::from_env().bind_addr(),
                 contracts_config.contract_addr,
                 ticker,
+                eth_gateway,
                 sign_check_sender,
                 mempool_tx_request_sender,
                 private_config.url,
+                config_reloader.clone(),
+                shutdown_receiver.clone(),
             ));
         }
     }
@@ -316,7 +391,20 @@ async fn run_server(components: &ComponentsToRun) {
 
     if components.0.contains(&Component::RejectedTaskCleaner) {
         let config = DBConfig::from_env();
-        tasks.push(run_rejected_tx_cleaner(&config, connection_pool));
+        tasks.push(run_rejected_tx_cleaner(&config, connection_pool.clone()));
+    }
+
+    if components.0.contains(&Component::StatePruner) {
+        let config = DBConfig::from_env();
+        tasks.push(run_state_pruner(&config, connection_pool.clone()));
+    }
+
+    if components.0.contains(&Component::ExitProofGenerator) {
+        tasks.push(run_exit_proof_generator(connection_pool.clone()));
+    }
+
+    if components.0.contains(&Component::NetworkStats) {
+        tasks.push(run_network_stats_aggregator(connection_pool.clone()));
     }
 
     {
@@ -336,6 +424,25 @@ async fn run_server(components: &ComponentsToRun) {
             vlog::warn!("Stop signal received, shutting down");
         }
     };
+
+    // The API servers stop accepting the new requests and drain the in-flight ones,
+    // the submitted transactions are added to the mempool by the time they are stopped.
+    drop(shutdown_receiver);
+    let _ = shutdown_sender.send(true);
+    let shutdown_timeout = RestApiConfig::from_env().shutdown_timeout() + COMPONENTS_STOP_MARGIN;
+    if tokio::time::timeout(shutdown_timeout, shutdown_sender.closed())
+        .await
+        .is_err()
+    {
+        vlog::warn!(
+            "Some of the components are not stopped in {:?}",
+            shutdown_timeout
+        );
+    }
+
+    connection_pool.close(DB_POOL_CLOSE_TIMEOUT).await;
+    read_only_connection_pool.close(DB_POOL_CLOSE_TIMEOUT).await;
+    vlog::info!("zkSync server is stopped");
 }
 
 pub fn run_forced_exit(connection_pool: ConnectionPool) -> Vec<JoinHandle<()>> {
@@ -352,6 +459,10 @@ pub fn run_forced_exit(connection_pool: ConnectionPool) -> Vec<JoinHandle<()>> {
         connection_pool.clone(),
         mempool_tx_request_receiver,
         chain_config.state_keeper.block_chunk_sizes,
+        TokenDenylist::new(
+            connection_pool.clone(),
+            TokenHandlerConfig::from_env().denylisted_tokens,
+        ),
     );
     let forced_exit_task = run_forced_exit_requests_actors(
         connection_pool,
@@ -382,13 +493,57 @@ pub fn run_eth_sender(connection_pool: ConnectionPool) -> JoinHandle<()> {
         &eth_sender_config,
         contracts.contract_addr,
     );
+    let sender = &eth_sender_config.sender;
+    let mut reserve_signers: Vec<(Address, OperatorSigner)> = sender
+        .reserve_operator_private_keys
+        .iter()
+        .map(|private_key| {
+            let address = PackedEthSignature::address_from_private_key(private_key)
+                .expect("Invalid reserve operator private key");
+            (address, PrivateKeySigner::new(*private_key).into())
+        })
+        .collect();
+    if let Some(url) = &sender.remote_signer_url {
+        reserve_signers.extend(
+            sender
+                .reserve_operator_addresses
+                .iter()
+                .map(|address| (*address, RemoteSigner::new(url.clone(), *address).into())),
+        );
+    }
+    let reserve_operators = reserve_signers
+        .into_iter()
+        .map(|(address, signer)| {
+            let gateway = EthereumGateway::from_config_with_operator(
+                &eth_client_config,
+                signer,
+                address,
+                contracts.contract_addr,
+            );
+            (address, gateway)
+        })
+        .collect();
 
-    zksync_eth_sender::run_eth_sender(connection_pool, eth_gateway, eth_sender_config)
+    zksync_eth_sender::run_eth_sender(
+        connection_pool,
+        eth_gateway,
+        reserve_operators,
+        eth_sender_config,
+    )
 }
 
-pub fn run_price_updaters(connection_pool: ConnectionPool) -> Vec<JoinHandle<()>> {
+pub fn run_price_updaters(
+    connection_pool: ConnectionPool,
+    config_reloader: &ConfigReloader,
+) -> Vec<JoinHandle<()>> {
     let ticker_config = TickerConfig::from_env();
-    run_updaters(connection_pool, &ticker_config)
+    let eth_client_config = ETHClientConfig::from_env();
+    run_updaters(
+        connection_pool,
+        &ticker_config,
+        &eth_client_config.web3_url(),
+        config_reloader,
+    )
 }
 
 pub fn create_eth_gateway() -> EthereumGateway {
@@ -0,0 +1,83 @@
+use std::path::PathBuf;
+
+use structopt::StructOpt;
+use zksync_storage::StorageProcessor;
+use zksync_types::BlockNumber;
+
+use crate::snapshot::StateSnapshot;
+
+mod snapshot;
+
+#[derive(Debug, StructOpt)]
+enum Command {
+    /// Exports the state at the block to the file
+    Export {
+        /// Block to export the state at, the last verified block by default.
+        #[structopt(long)]
+        block: Option<u32>,
+        /// Path to the snapshot file.
+        #[structopt(long, parse(from_os_str))]
+        output: PathBuf,
+    },
+    /// Imports the state from the file into the empty database
+    Import {
+        /// Path to the snapshot file.
+        #[structopt(long, parse(from_os_str))]
+        input: PathBuf,
+    },
+}
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "zkSync state snapshot tool", author = "Matter Labs")]
+#[structopt(
+    about = "Tool to export the account tree and the tokens at the block and to bootstrap the database from them"
+)]
+struct Opt {
+    #[structopt(subcommand)]
+    command: Command,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let opt = Opt::from_args();
+    let mut storage = StorageProcessor::establish_connection().await?;
+
+    match opt.command {
+        Command::Export { block, output } => {
+            let block = match block {
+                Some(block) => BlockNumber(block),
+                None => {
+                    storage
+                        .chain()
+                        .block_schema()
+                        .get_last_verified_confirmed_block()
+                        .await?
+                }
+            };
+            println!("Exporting the state at the block {}", *block);
+            let snapshot = StateSnapshot::export(&mut storage, block).await?;
+            snapshot.write(&output)?;
+            println!(
+                "{} accounts and {} tokens are exported to {}",
+                snapshot.accounts.len(),
+                snapshot.tokens.len(),
+                output.display()
+            );
+        }
+        Command::Import { input } => {
+            let snapshot = StateSnapshot::read(&input)?;
+            println!(
+                "Importing the state at the block {}",
+                *snapshot.block.block_number
+            );
+            snapshot.import(&mut storage).await?;
+            println!(
+                "{} accounts and {} tokens are imported",
+                snapshot.accounts.len(),
+                snapshot.tokens.len()
+            );
+        }
+    }
+
+    Ok(())
+}
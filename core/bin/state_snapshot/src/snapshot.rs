@@ -0,0 +1,328 @@
+//! Snapshot of the zkSync state at some block: the accounts of the account tree
+//! and the token registry, along with the header of the block.
+//!
+//! The snapshot is imported into a fresh database the same way the data restore saves
+//! the restored blocks: the accounts are committed as the state update of the snapshot
+//! block, which is stored as committed and executed. So the server continues from the
+//! snapshot block without replaying the blocks before it, while the history of these
+//! blocks (the transactions, the state diffs and the proofs) isn't available.
+
+// Built-in uses
+use std::{fs, path::Path};
+
+// External uses
+use anyhow::{bail, ensure, format_err};
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+
+// Workspace uses
+use zksync_crypto::{
+    ff,
+    params::{NFT_STORAGE_ACCOUNT_ADDRESS, NFT_TOKEN_ID},
+};
+use zksync_state::state::ZkSyncState;
+use zksync_storage::StorageProcessor;
+use zksync_types::{
+    aggregated_operations::{BlocksCommitOperation, BlocksExecuteOperation},
+    block::Block,
+    Account, AccountId, AccountMap, AccountUpdate, AccountUpdates, BlockNumber, PubKeyHash, Token,
+    TokenKind,
+};
+
+/// Version of the snapshot format, the snapshots of other versions are rejected on import.
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateSnapshot {
+    pub version: u32,
+    /// Header of the snapshot block, its transactions aren't included.
+    pub block: Block,
+    /// The ERC20 tokens, the NFTs are restored from the accounts which minted them.
+    pub tokens: Vec<Token>,
+    pub accounts: Vec<(AccountId, Account)>,
+}
+
+impl StateSnapshot {
+    /// Loads the snapshot of the state after the committed block.
+    pub async fn export(
+        storage: &mut StorageProcessor<'_>,
+        block_number: BlockNumber,
+    ) -> anyhow::Result<Self> {
+        let mut transaction = storage.start_transaction().await?;
+
+        // The state before the last pruned block can't be restored from the state diffs.
+        let last_pruned_block = transaction
+            .pruning_schema()
+            .load_last_pruned_block()
+            .await?;
+        ensure!(
+            block_number >= last_pruned_block,
+            "The history of the block {} is pruned, the earliest block to export is {}",
+            *block_number,
+            *last_pruned_block
+        );
+
+        let mut block = transaction
+            .chain()
+            .block_schema()
+            .get_block(block_number)
+            .await?
+            .ok_or_else(|| format_err!("Block {} is not committed", *block_number))?;
+        block.block_transactions = Vec::new();
+
+        let (_, accounts) = transaction
+            .chain()
+            .state_schema()
+            .load_committed_state(Some(block_number))
+            .await?;
+        let mut accounts: Vec<_> = accounts.into_iter().collect();
+        accounts.sort_by_key(|(id, _)| *id);
+
+        let mut tokens: Vec<_> = transaction
+            .tokens_schema()
+            .load_tokens()
+            .await?
+            .into_iter()
+            .map(|(_, token)| token)
+            .collect();
+        tokens.sort_by_key(|token| token.id);
+        transaction.commit().await?;
+
+        let snapshot = Self {
+            version: SNAPSHOT_VERSION,
+            block,
+            tokens,
+            accounts,
+        };
+        snapshot.verify()?;
+        Ok(snapshot)
+    }
+
+    /// Checks that the snapshot can be imported and the root hash of its accounts matches the block.
+    pub fn verify(&self) -> anyhow::Result<()> {
+        ensure!(
+            self.version == SNAPSHOT_VERSION,
+            "Unsupported snapshot version {}, expected {}",
+            self.version,
+            SNAPSHOT_VERSION
+        );
+        let accounts: AccountMap = self.accounts.iter().cloned().collect();
+        let root_hash = ZkSyncState::from_acc_map(accounts).root_hash();
+        if root_hash != self.block.new_root_hash {
+            bail!(
+                "Root hash of the accounts 0x{} doesn't match the root hash of the block {} 0x{}",
+                ff::to_hex(&root_hash),
+                *self.block.block_number,
+                ff::to_hex(&self.block.new_root_hash)
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns the updates creating the accounts of the snapshot from scratch.
+    pub fn account_updates(&self) -> AccountUpdates {
+        let mut updates = Vec::new();
+        for (id, account) in &self.accounts {
+            let nonce = account.nonce;
+            updates.push((
+                *id,
+                AccountUpdate::Create {
+                    address: account.address,
+                    nonce,
+                },
+            ));
+            if account.pub_key_hash != PubKeyHash::default() {
+                updates.push((
+                    *id,
+                    AccountUpdate::ChangePubKeyHash {
+                        old_pub_key_hash: PubKeyHash::default(),
+                        new_pub_key_hash: account.pub_key_hash,
+                        old_nonce: nonce,
+                        new_nonce: nonce,
+                    },
+                ));
+            }
+            let mut balances: Vec<_> = account.get_nonzero_balances().into_iter().collect();
+            balances.sort_by_key(|(token, _)| *token);
+            for (token, balance) in balances {
+                updates.push((
+                    *id,
+                    AccountUpdate::UpdateBalance {
+                        old_nonce: nonce,
+                        new_nonce: nonce,
+                        balance_update: (token, BigUint::from(0u32), balance.0),
+                    },
+                ));
+            }
+            let mut nfts: Vec<_> = account.minted_nfts.values().cloned().collect();
+            nfts.sort_by_key(|nft| nft.id);
+            for nft in nfts {
+                updates.push((*id, AccountUpdate::MintNFT { token: nft, nonce }));
+            }
+        }
+        updates
+    }
+
+    /// Stores the snapshot into the database, which must not contain any blocks yet.
+    pub async fn import(&self, storage: &mut StorageProcessor<'_>) -> anyhow::Result<()> {
+        self.verify()?;
+        let block_number = self.block.block_number;
+        let mut transaction = storage.start_transaction().await?;
+
+        let (last_committed, accounts) = transaction
+            .chain()
+            .state_schema()
+            .load_committed_state(None)
+            .await?;
+        ensure!(
+            *last_committed == 0 && accounts.is_empty(),
+            "The database must be empty, but it contains the state of the block {}",
+            *last_committed
+        );
+
+        // The NFTs are stored along with their mints, but the special token isn't.
+        let special_token = Token::new(
+            NFT_TOKEN_ID,
+            *NFT_STORAGE_ACCOUNT_ADDRESS,
+            "SPECIAL",
+            18,
+            TokenKind::NFT,
+        );
+        for token in self.tokens.iter().chain(Some(&special_token)) {
+            transaction
+                .tokens_schema()
+                .store_token(token.clone())
+                .await?;
+        }
+        transaction
+            .chain()
+            .state_schema()
+            .commit_state_update(block_number, &self.account_updates(), 0)
+            .await?;
+
+        let commit_operation = BlocksCommitOperation {
+            last_committed_block: self.block.clone(),
+            blocks: vec![self.block.clone()],
+        };
+        let execute_operation = BlocksExecuteOperation {
+            blocks: vec![self.block.clone()],
+        };
+        transaction
+            .data_restore_schema()
+            .save_block_operations(commit_operation, execute_operation)
+            .await?;
+        transaction
+            .chain()
+            .block_schema()
+            .save_full_block(self.block.clone())
+            .await?;
+        transaction
+            .data_restore_schema()
+            .initialize_eth_stats(block_number, block_number, block_number)
+            .await?;
+
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    pub fn read(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let file = fs::File::open(path)
+            .map_err(|err| format_err!("Can't open {}: {}", path.display(), err))?;
+        Ok(serde_json::from_reader(std::io::BufReader::new(file))?)
+    }
+
+    pub fn write(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        let file = fs::File::create(path)
+            .map_err(|err| format_err!("Can't create {}: {}", path.display(), err))?;
+        serde_json::to_writer(std::io::BufWriter::new(file), self)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::{helpers::apply_updates, Address, Nonce, TokenId, H256, NFT};
+
+    fn snapshot() -> StateSnapshot {
+        let mut fee_account = Account::default_with_address(&Address::repeat_byte(1));
+        fee_account.set_balance(TokenId(0), BigUint::from(100u32));
+        fee_account.nonce = Nonce(3);
+
+        let mut creator = Account::default_with_address(&Address::repeat_byte(2));
+        creator.pub_key_hash =
+            PubKeyHash::from_hex("sync:0101010101010101010101010101010101010101").unwrap();
+        creator.set_balance(TokenId(0), BigUint::from(10u32));
+        creator.set_balance(TokenId(1), BigUint::from(1u32));
+        creator.minted_nfts.insert(
+            TokenId(65536),
+            NFT::new(
+                TokenId(65536),
+                0,
+                AccountId(1),
+                creator.address,
+                creator.address,
+                None,
+                H256::repeat_byte(3),
+            ),
+        );
+        let accounts = vec![(AccountId(0), fee_account), (AccountId(1), creator)];
+
+        let state = ZkSyncState::from_acc_map(accounts.iter().cloned().collect());
+        let block = Block::new(
+            BlockNumber(10),
+            state.root_hash(),
+            AccountId(0),
+            Vec::new(),
+            (0, 0),
+            10,
+            0u32.into(),
+            0u32.into(),
+            H256::zero(),
+            0,
+        );
+        StateSnapshot {
+            version: SNAPSHOT_VERSION,
+            block,
+            tokens: vec![Token::new(
+                TokenId(0),
+                Address::zero(),
+                "ETH",
+                18,
+                TokenKind::ERC20,
+            )],
+            accounts,
+        }
+    }
+
+    #[test]
+    fn account_updates() {
+        let snapshot = snapshot();
+        snapshot.verify().unwrap();
+
+        let mut accounts = AccountMap::default();
+        apply_updates(&mut accounts, snapshot.account_updates());
+        let expected: AccountMap = snapshot.accounts.iter().cloned().collect();
+        assert_eq!(accounts, expected);
+    }
+
+    #[test]
+    fn serialization() {
+        let snapshot = snapshot();
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let deserialized: StateSnapshot = serde_json::from_str(&json).unwrap();
+        deserialized.verify().unwrap();
+        assert_eq!(deserialized.accounts, snapshot.accounts);
+        assert_eq!(deserialized.tokens, snapshot.tokens);
+
+        // The snapshot with the modified state is rejected.
+        let mut modified = deserialized;
+        modified.accounts[0].1.nonce = Nonce(4);
+        assert!(modified.verify().is_err());
+        modified.version = SNAPSHOT_VERSION + 1;
+        assert!(modified.verify().is_err());
+    }
+}
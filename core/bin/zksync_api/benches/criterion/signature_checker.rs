@@ -0,0 +1,128 @@
+//! Compares the throughput of the signature checker verifying the burst of the submitted
+//! transactions by a single thread with the one of the whole thread pool.
+
+use chrono::Utc;
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use futures::channel::{mpsc, oneshot};
+use tokio::runtime::Runtime;
+
+use zksync_api::signature_checker::{
+    start_sign_checker, RequestData, SignatureCheckerConfig, TxRequest, VerifySignatureRequest,
+};
+use zksync_eth_client::{clients::mock::MockEthereum, EthereumGateway};
+use zksync_test_account::ZkSyncAccount;
+use zksync_types::{
+    tx::{EthSignData, TxEthSignature},
+    AccountId, Address, SignedZkSyncTx, Token, TokenId, TokenKind, ZkSyncTx,
+};
+
+const BURST_SIZE: usize = 256;
+
+fn token() -> Token {
+    Token::new(TokenId(0), Address::zero(), "ETH", 18, TokenKind::ERC20)
+}
+
+/// Generates the signed transfers of the different accounts.
+fn signed_transfers() -> Vec<(SignedZkSyncTx, Address)> {
+    let token = token();
+    (0..BURST_SIZE)
+        .map(|id| {
+            let account = ZkSyncAccount::rand();
+            account.set_account_id(Some(AccountId(id as u32)));
+            let (transfer, eth_signature) = account.sign_transfer(
+                token.id,
+                &token.symbol,
+                100u32.into(),
+                10u32.into(),
+                &Address::repeat_byte(1),
+                None,
+                true,
+                Default::default(),
+            );
+            let message = transfer
+                .get_ethereum_sign_message(&token.symbol, token.decimals)
+                .into_bytes();
+            let tx = SignedZkSyncTx {
+                tx: transfer.into(),
+                eth_sign_data: Some(EthSignData {
+                    signature: TxEthSignature::EthereumSignature(eth_signature.unwrap()),
+                    message,
+                }),
+                created_at: Utc::now(),
+            };
+            (tx, account.address)
+        })
+        .collect()
+}
+
+/// Creates the verification requests of the transactions. The transactions are re-created
+/// from JSON, as they are received by the API, so their `ZKSync` signatures are not cached.
+fn requests(txs: &[(SignedZkSyncTx, Address)]) -> Vec<RequestData> {
+    txs.iter()
+        .map(|(tx, sender)| {
+            let zksync_tx: ZkSyncTx =
+                serde_json::from_value(serde_json::to_value(&tx.tx).unwrap()).unwrap();
+            RequestData::Tx(TxRequest {
+                tx: SignedZkSyncTx {
+                    tx: zksync_tx,
+                    eth_sign_data: tx.eth_sign_data.clone(),
+                    created_at: Utc::now(),
+                },
+                sender: *sender,
+                token: token(),
+            })
+        })
+        .collect()
+}
+
+async fn verify_burst(
+    mut sender: mpsc::Sender<VerifySignatureRequest>,
+    requests: Vec<RequestData>,
+) {
+    let mut responses = Vec::with_capacity(requests.len());
+    for data in requests {
+        let (response, receiver) = oneshot::channel();
+        sender
+            .try_send(VerifySignatureRequest { data, response })
+            .unwrap();
+        responses.push(receiver);
+    }
+    for response in responses {
+        response.await.unwrap().expect("Incorrect signature");
+    }
+}
+
+fn bench_signature_checker(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let txs = signed_transfers();
+
+    let mut group = c.benchmark_group("signature_checker");
+    group.throughput(Throughput::Elements(BURST_SIZE as u64));
+    for (name, threads) in vec![("single_thread", 1), ("thread_pool", 0)] {
+        let config = SignatureCheckerConfig {
+            threads,
+            batch_size: 64,
+        };
+        let (sender, receiver) = mpsc::channel(BURST_SIZE);
+        {
+            let _guard = runtime.enter();
+            start_sign_checker(
+                EthereumGateway::Mock(MockEthereum::default()),
+                receiver,
+                config,
+            );
+        }
+
+        group.bench_function(name, |b| {
+            b.to_async(&runtime).iter_batched(
+                || requests(&txs),
+                |requests| verify_burst(sender.clone(), requests),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_signature_checker);
+criterion_main!(benches);
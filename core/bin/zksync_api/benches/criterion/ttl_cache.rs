@@ -0,0 +1,100 @@
+//! Compares the contention of the `TtlCache` against a cache guarded by a single `Mutex`.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use zksync_api::utils::ttl_cache::TtlCache;
+
+const TTL: Duration = Duration::from_secs(60);
+const KEYS: u32 = 256;
+const OPERATIONS_PER_THREAD: u32 = 10_000;
+
+/// Baseline implementation: the same TTL semantics behind a single lock.
+#[derive(Clone)]
+struct MutexTtlCache(Arc<Mutex<HashMap<u32, (u32, Instant)>>>);
+
+impl MutexTtlCache {
+    fn insert(&self, key: u32, value: u32) {
+        self.0.lock().unwrap().insert(key, (value, Instant::now()));
+    }
+
+    fn get(&self, key: &u32) -> Option<u32> {
+        let mut entries = self.0.lock().unwrap();
+        match entries.get(key) {
+            Some((value, inserted_at)) if inserted_at.elapsed() < TTL => Some(*value),
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+}
+
+/// Runs a read-heavy workload (one write per 16 reads) on several threads at once.
+fn run_workload<C, G, I>(cache: C, threads: usize, get: G, insert: I)
+where
+    C: Clone + Send + 'static,
+    G: Fn(&C, u32) -> Option<u32> + Copy + Send + 'static,
+    I: Fn(&C, u32, u32) + Copy + Send + 'static,
+{
+    let handles: Vec<_> = (0..threads)
+        .map(|_| {
+            let cache = cache.clone();
+            thread::spawn(move || {
+                for i in 0..OPERATIONS_PER_THREAD {
+                    let key = i % KEYS;
+                    if i % 16 == 0 {
+                        insert(&cache, key, i);
+                    } else {
+                        criterion::black_box(get(&cache, key));
+                    }
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
+
+fn bench_ttl_cache(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ttl_cache_contention");
+    for threads in [1usize, 4, 16].iter().copied() {
+        let dashmap_cache = TtlCache::new(TTL);
+        let mutex_cache = MutexTtlCache(Default::default());
+        for key in 0..KEYS {
+            dashmap_cache.insert(key, key);
+            mutex_cache.insert(key, key);
+        }
+
+        group.bench_with_input(BenchmarkId::new("dashmap", threads), &threads, |b, &n| {
+            b.iter(|| {
+                run_workload(
+                    dashmap_cache.clone(),
+                    n,
+                    |cache, key| cache.get(&key),
+                    |cache, key, value| cache.insert(key, value),
+                )
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("mutex", threads), &threads, |b, &n| {
+            b.iter(|| {
+                run_workload(
+                    mutex_cache.clone(),
+                    n,
+                    |cache, key| cache.get(&key),
+                    |cache, key, value| cache.insert(key, value),
+                )
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_ttl_cache);
+criterion_main!(benches);
@@ -0,0 +1,4 @@
+fn main() {
+    tonic_build::compile_protos("proto/zksync_api.proto")
+        .expect("Failed to compile the gRPC API protocol");
+}
@@ -0,0 +1,123 @@
+//! Reloading of the config parameters which can be changed without restarting the server.
+//!
+//! The config is reloaded from the env file on `SIGHUP` or by the admin API, and is sent
+//! to the components through the watch channel: the fee ticker and the tokens API pick
+//! the new liquidity volume, the REST API applies the new rate limit on the next request,
+//! and the tokens denylisted by the config are replaced in the registered denylists.
+
+// Built-in uses
+use std::sync::{Arc, Mutex};
+
+// External uses
+use tokio::{
+    signal::unix::{signal, SignalKind},
+    sync::watch,
+    task::JoinHandle,
+};
+
+// Workspace uses
+use zksync_config::{ReloadableConfig, ZkSyncConfig};
+use zksync_token_db_cache::TokenDenylist;
+
+#[derive(Debug, Clone)]
+pub struct ConfigReloader {
+    sender: Arc<watch::Sender<ReloadableConfig>>,
+    /// The updates are not sent to the channel without receivers, so one is always kept.
+    receiver: watch::Receiver<ReloadableConfig>,
+    /// Denylists whose config tokens are replaced on reload.
+    denylists: Arc<Mutex<Vec<TokenDenylist>>>,
+}
+
+impl ConfigReloader {
+    pub fn new(config: ReloadableConfig) -> Self {
+        let (sender, receiver) = watch::channel(config);
+        Self {
+            sender: Arc::new(sender),
+            receiver,
+            denylists: Default::default(),
+        }
+    }
+
+    pub fn from_config(config: &ZkSyncConfig) -> Self {
+        Self::new(ReloadableConfig::from_config(config))
+    }
+
+    /// Returns the receiver of the config updates, which always holds the current config.
+    pub fn subscribe(&self) -> watch::Receiver<ReloadableConfig> {
+        self.receiver.clone()
+    }
+
+    pub fn current(&self) -> ReloadableConfig {
+        self.receiver.borrow().clone()
+    }
+
+    /// Registers the denylist, so that its config tokens are replaced on reload.
+    pub fn register_denylist(&self, denylist: &TokenDenylist) {
+        self.denylists.lock().unwrap().push(denylist.clone());
+    }
+
+    /// Sends the config to the components.
+    pub fn update(&self, config: ReloadableConfig) {
+        if *self.receiver.borrow() == config {
+            return;
+        }
+        vlog::info!("Applying the reloaded config: {:?}", config);
+        for denylist in self.denylists.lock().unwrap().iter() {
+            denylist.set_config_tokens(config.denylisted_tokens.clone());
+        }
+        // The channel is never closed, since the receiver is kept.
+        let _ = self.sender.send(config);
+        metrics::increment_counter!("api.config_reloads");
+    }
+
+    /// Reloads the config from the env file and sends it to the components.
+    pub fn reload(&self) -> anyhow::Result<ReloadableConfig> {
+        let config = ReloadableConfig::reload()?;
+        self.update(config.clone());
+        Ok(config)
+    }
+
+    /// Reloads the config every time the process receives `SIGHUP`.
+    #[must_use]
+    pub fn run_on_sighup(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut hangups = signal(SignalKind::hangup()).expect("Can't listen for SIGHUP");
+            while hangups.recv().await.is_some() {
+                vlog::info!("SIGHUP received, reloading the config");
+                if let Err(err) = self.reload() {
+                    // The current config is kept until the env file is fixed.
+                    vlog::error!("Can't reload the config: {}", err);
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::Address;
+
+    #[test]
+    fn config_updates() {
+        let config = ReloadableConfig {
+            liquidity_volume: 100.0,
+            requests_per_second_per_ip: 10,
+            denylisted_tokens: vec![],
+        };
+        let reloader = ConfigReloader::new(config.clone());
+        let receiver = reloader.subscribe();
+        assert_eq!(*receiver.borrow(), config);
+
+        let updated = ReloadableConfig {
+            liquidity_volume: 200.0,
+            denylisted_tokens: vec![Address::repeat_byte(1)],
+            ..config
+        };
+        reloader.update(updated.clone());
+        assert_eq!(*receiver.borrow(), updated);
+        assert_eq!(reloader.current(), updated);
+        // The clones share the channel.
+        assert_eq!(*reloader.clone().subscribe().borrow(), updated);
+    }
+}
@@ -0,0 +1,414 @@
+//! gRPC API server.
+//!
+//! Exposes the transaction submission, the account states and the token prices
+//! via the strongly-typed protocol described in `proto/zksync_api.proto`.
+//! The requests are served by the same data structures as the REST API v0.2,
+//! so both APIs give the same answers.
+
+// Built-in uses
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+// External uses
+use futures::{channel::mpsc, future, stream, Stream, StreamExt};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::task::JoinHandle;
+use tonic::{transport::Server, Request, Response, Status};
+
+// Workspace uses
+use zksync_api_types::v02::account::{Account, AccountState};
+use zksync_config::ZkSyncConfig;
+use zksync_mempool::MempoolTransactionRequest;
+use zksync_storage::ConnectionPool;
+use zksync_types::{tx::TxEthSignatureVariant, TokenLike, ZkSyncTx};
+
+// Local uses
+use crate::{
+    api_server::{
+        config_reload::ConfigReloader,
+        rest::v02::{
+            account::ApiAccountData,
+            error::{Error, ErrorCode},
+            token::ApiTokenData,
+        },
+        tx_sender::TxSender,
+    },
+    fee_ticker::FeeTicker,
+    fiat_rates::FiatRates,
+    signature_checker::VerifySignatureRequest,
+};
+use proto::zk_sync_api_server::{ZkSyncApi, ZkSyncApiServer};
+
+#[allow(clippy::all)]
+pub mod proto {
+    tonic::include_proto!("zksync.api.v1");
+}
+
+/// How often the token prices are checked for the subscribed clients.
+const PRICE_STREAM_INTERVAL: Duration = Duration::from_secs(1);
+/// Number of the price updates buffered for a slow client.
+const PRICE_STREAM_CAPACITY: usize = 16;
+
+/// The token id and the currency the price is shown in.
+type PriceFeedKey = (u32, String);
+type PriceFeedUpdate = Result<proto::TokenPrice, Error>;
+
+/// Maps the REST API error to the gRPC status with the closest meaning.
+fn status(err: Error) -> Status {
+    match err.code {
+        ErrorCode::AccountNotFound | ErrorCode::TransactionNotFound | ErrorCode::TokenNotFound => {
+            Status::not_found(err.message)
+        }
+        ErrorCode::ExternalApiError
+        | ErrorCode::CommunicationCoreServer
+        | ErrorCode::ReadOnlyMode => Status::unavailable(err.message),
+        ErrorCode::UnreacheableError
+        | ErrorCode::CoreApiError
+        | ErrorCode::StorageError
+        | ErrorCode::InternalError
+        | ErrorCode::Other => Status::internal(err.message),
+        _ => Status::invalid_argument(err.message),
+    }
+}
+
+fn api_account(account: Account) -> proto::Account {
+    proto::Account {
+        account_id: *account.account_id,
+        address: format!("{:?}", account.address),
+        nonce: *account.nonce,
+        pub_key_hash: account.pub_key_hash.as_hex(),
+        last_update_in_block: *account.last_update_in_block,
+        balances: account
+            .balances
+            .into_iter()
+            .map(|(symbol, balance)| (symbol, balance.0.to_string()))
+            .collect(),
+    }
+}
+
+fn api_account_state(state: AccountState) -> proto::AccountState {
+    proto::AccountState {
+        committed: state.committed.map(api_account),
+        finalized: state.finalized.map(api_account),
+    }
+}
+
+/// The price feeds, each polling the price of a token once for all of its subscribers.
+#[derive(Clone, Default)]
+struct PriceFeeds(Arc<Mutex<HashMap<PriceFeedKey, broadcast::Sender<PriceFeedUpdate>>>>);
+
+impl PriceFeeds {
+    /// Subscribes to the feed of the price, starting the feed if it isn't running yet.
+    fn subscribe(
+        &self,
+        api: &GrpcApi,
+        price: &proto::TokenPrice,
+    ) -> broadcast::Receiver<PriceFeedUpdate> {
+        let key = (price.token_id, price.price_in.clone());
+        let mut feeds = self.0.lock().unwrap();
+        if let Some(sender) = feeds.get(&key) {
+            return sender.subscribe();
+        }
+        let (sender, receiver) = broadcast::channel(PRICE_STREAM_CAPACITY);
+        feeds.insert(key.clone(), sender.clone());
+        tokio::spawn(
+            self.clone()
+                .poll_prices(api.clone(), key, price.clone(), sender),
+        );
+        receiver
+    }
+
+    async fn poll_prices(
+        self,
+        api: GrpcApi,
+        key: PriceFeedKey,
+        mut last_price: proto::TokenPrice,
+        sender: broadcast::Sender<PriceFeedUpdate>,
+    ) {
+        metrics::increment_gauge!("api.grpc.price_feeds", 1.0);
+        let request = proto::GetTokenPriceRequest {
+            token: key.0.to_string(),
+            currency: key.1.clone(),
+        };
+        let mut timer = tokio::time::interval(PRICE_STREAM_INTERVAL);
+        // The first tick completes immediately, and the subscriber already has the price.
+        timer.tick().await;
+        loop {
+            timer.tick().await;
+            // The feed is removed under the lock, so nobody subscribes to the stopped feed.
+            {
+                let mut feeds = self.0.lock().unwrap();
+                if sender.receiver_count() == 0 {
+                    feeds.remove(&key);
+                    break;
+                }
+            }
+            let update = match api.token_price(&request).await {
+                Ok(price) if price == last_price => continue,
+                Ok(price) => {
+                    last_price = price.clone();
+                    Ok(price)
+                }
+                Err(err) => Err(err),
+            };
+            // Everybody may have unsubscribed meanwhile, it's checked on the next tick.
+            sender.send(update).ok();
+        }
+        metrics::decrement_gauge!("api.grpc.price_feeds", 1.0);
+    }
+}
+
+#[derive(Clone)]
+struct GrpcApi {
+    tx_sender: TxSender,
+    accounts: ApiAccountData,
+    tokens: ApiTokenData,
+    price_feeds: PriceFeeds,
+}
+
+impl GrpcApi {
+    async fn token_price(
+        &self,
+        request: &proto::GetTokenPriceRequest,
+    ) -> Result<proto::TokenPrice, Error> {
+        let token_like = TokenLike::parse(&request.token);
+        let price = self
+            .tokens
+            .token_price_in(token_like.clone(), &request.currency)
+            .await?;
+        let token = self.tokens.token(token_like).await?;
+        Ok(proto::TokenPrice {
+            token_id: *token.id,
+            token_symbol: token.symbol,
+            price_in: request.currency.clone(),
+            decimals: token.decimals.into(),
+            price: price.to_string(),
+        })
+    }
+}
+
+#[tonic::async_trait]
+impl ZkSyncApi for GrpcApi {
+    async fn submit_tx(
+        &self,
+        request: Request<proto::SubmitTxRequest>,
+    ) -> Result<Response<proto::SubmitTxResponse>, Status> {
+        let start = Instant::now();
+        let request = request.into_inner();
+        let tx: ZkSyncTx = serde_json::from_str(&request.tx).map_err(|err| {
+            Status::invalid_argument(format!("Cannot parse transaction: {}", err))
+        })?;
+        let signature = if request.signature.is_empty() {
+            TxEthSignatureVariant::default()
+        } else {
+            serde_json::from_str(&request.signature).map_err(|err| {
+                Status::invalid_argument(format!("Cannot parse signature: {}", err))
+            })?
+        };
+
+        let res = self
+            .tx_sender
+            .submit_tx(tx, signature, None)
+            .await
+            .map(|tx_hash| {
+                Response::new(proto::SubmitTxResponse {
+                    tx_hash: tx_hash.to_string(),
+                })
+            })
+            .map_err(|err| status(Error::from(err)));
+        metrics::histogram!("api", start.elapsed(), "type" => "grpc", "endpoint_name" => "submit_tx");
+        res
+    }
+
+    async fn get_account(
+        &self,
+        request: Request<proto::GetAccountRequest>,
+    ) -> Result<Response<proto::AccountState>, Status> {
+        let start = Instant::now();
+        let request = request.into_inner();
+        let res = async {
+            let address_or_id = self
+                .accounts
+                .parse_account_id_or_address(&request.account_id_or_address)?;
+            let address = self
+                .accounts
+                .get_address_by_address_or_id(address_or_id.clone())
+                .await?;
+            let account_id = self.accounts.get_id_by_address_or_id(address_or_id).await?;
+            self.accounts.account_full_info(address, account_id).await
+        }
+        .await
+        .map(|state| Response::new(api_account_state(state)))
+        .map_err(status);
+        metrics::histogram!("api", start.elapsed(), "type" => "grpc", "endpoint_name" => "get_account");
+        res
+    }
+
+    async fn get_token(
+        &self,
+        request: Request<proto::GetTokenRequest>,
+    ) -> Result<Response<proto::Token>, Status> {
+        let start = Instant::now();
+        let token_like = TokenLike::parse(&request.into_inner().token);
+        let res = self
+            .tokens
+            .api_token(token_like)
+            .await
+            .map(|token| {
+                Response::new(proto::Token {
+                    id: *token.id,
+                    address: format!("{:?}", token.address),
+                    symbol: token.symbol,
+                    decimals: token.decimals.into(),
+                    enabled_for_fees: token.enabled_for_fees,
+                })
+            })
+            .map_err(status);
+        metrics::histogram!("api", start.elapsed(), "type" => "grpc", "endpoint_name" => "get_token");
+        res
+    }
+
+    async fn get_token_price(
+        &self,
+        request: Request<proto::GetTokenPriceRequest>,
+    ) -> Result<Response<proto::TokenPrice>, Status> {
+        let start = Instant::now();
+        let res = self
+            .token_price(&request.into_inner())
+            .await
+            .map(Response::new)
+            .map_err(status);
+        metrics::histogram!("api", start.elapsed(), "type" => "grpc", "endpoint_name" => "get_token_price");
+        res
+    }
+
+    type SubscribeTokenPriceStream =
+        Pin<Box<dyn Stream<Item = Result<proto::TokenPrice, Status>> + Send>>;
+
+    async fn subscribe_token_price(
+        &self,
+        request: Request<proto::GetTokenPriceRequest>,
+    ) -> Result<Response<Self::SubscribeTokenPriceStream>, Status> {
+        let request = request.into_inner();
+        // The invalid requests are rejected right away rather than in the stream.
+        let price = self.token_price(&request).await.map_err(status)?;
+
+        let receiver = self.price_feeds.subscribe(self, &price);
+        let updates = stream::unfold(receiver, |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(update) => return Some((update.map_err(status), receiver)),
+                    // The price is only relevant until the next update, so the slow
+                    // client just skips the missed ones.
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        });
+        Ok(Response::new(Box::pin(
+            stream::once(future::ready(Ok(price))).chain(updates),
+        )))
+    }
+}
+
+/// Starts the gRPC API server.
+#[must_use]
+pub fn start_grpc_server(
+    connection_pool: ConnectionPool,
+    sign_verifier: mpsc::Sender<VerifySignatureRequest>,
+    fee_ticker: FeeTicker,
+    mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
+    config: &ZkSyncConfig,
+    config_reloader: &ConfigReloader,
+) -> JoinHandle<()> {
+    let tx_sender = TxSender::new(
+        connection_pool.clone(),
+        sign_verifier,
+        fee_ticker.clone(),
+        &config.api.common,
+        &config.api.token_config,
+        mempool_tx_sender,
+    );
+    let api = GrpcApi {
+        accounts: ApiAccountData::new(
+            connection_pool.clone(),
+            tx_sender.tokens.clone(),
+            config.eth_watch.confirmations_for_eth_event,
+        ),
+        tokens: ApiTokenData::new(
+            config,
+            connection_pool,
+            tx_sender.tokens.clone(),
+            fee_ticker,
+            FiatRates::from_config(&config.api.token_config),
+            config_reloader,
+        ),
+        tx_sender,
+        price_feeds: PriceFeeds::default(),
+    };
+    let bind_to = config.api.grpc.bind_addr();
+
+    tokio::spawn(async move {
+        Server::builder()
+            .add_service(ZkSyncApiServer::new(api))
+            .serve(bind_to)
+            .await
+            .expect("gRPC API server has crashed");
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rest::v02::error::InvalidDataError;
+    use crate::fee_ticker::PriceError;
+    use std::collections::BTreeMap;
+    use zksync_types::{AccountId, Address, BlockNumber, Nonce, PubKeyHash};
+    use zksync_utils::BigUintSerdeWrapper;
+
+    #[test]
+    fn error_statuses() {
+        let not_found = status(Error::from(PriceError::token_not_found("ETH")));
+        assert_eq!(not_found.code(), tonic::Code::NotFound);
+        let invalid = status(Error::from(InvalidDataError::InvalidCurrency));
+        assert_eq!(invalid.code(), tonic::Code::InvalidArgument);
+        assert_eq!(
+            invalid.message(),
+            InvalidDataError::InvalidCurrency.to_string()
+        );
+        let internal = status(Error::storage("connection refused"));
+        assert_eq!(internal.code(), tonic::Code::Internal);
+    }
+
+    #[test]
+    fn account_conversion() {
+        let mut balances = BTreeMap::new();
+        balances.insert("ETH".to_string(), BigUintSerdeWrapper(1_000_000u64.into()));
+        let account = Account {
+            account_id: AccountId(7),
+            address: Address::repeat_byte(0x12),
+            nonce: Nonce(3),
+            pub_key_hash: PubKeyHash::default(),
+            last_update_in_block: BlockNumber(2),
+            balances,
+            formatted_balances: None,
+            nfts: BTreeMap::new(),
+            minted_nfts: BTreeMap::new(),
+            account_type: None,
+        };
+        let state = api_account_state(AccountState {
+            committed: Some(account),
+            ..AccountState::default()
+        });
+        assert!(state.finalized.is_none());
+
+        let committed = state.committed.unwrap();
+        assert_eq!(committed.account_id, 7);
+        assert_eq!(committed.address, format!("0x{}", "12".repeat(20)));
+        assert_eq!(committed.nonce, 3);
+        assert_eq!(committed.last_update_in_block, 2);
+        assert_eq!(committed.balances["ETH"], "1000000");
+    }
+}
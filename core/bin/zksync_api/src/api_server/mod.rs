@@ -1,12 +1,16 @@
 //! API server handles endpoints for interaction with node.
 //!
 //! `mod rest` - api is used for block explorer.
+//! `mod grpc` - gRPC api (for request reply functions and price streams)
 //! `mod rpc_server` - JSON rpc via HTTP (for request reply functions)
 //! `mod rpc_subscriptions` - JSON rpc via WebSocket (for request reply functions and subscriptions)
 
+pub mod config_reload;
 mod event_notify;
 pub mod forced_exit_checker;
+pub mod grpc;
 mod helpers;
+pub(crate) mod read_only_mode;
 pub mod rest;
 pub mod rpc_server;
 pub mod rpc_subscriptions;
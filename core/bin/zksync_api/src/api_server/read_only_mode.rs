@@ -0,0 +1,104 @@
+//! Read-only mode of the API servers.
+//!
+//! While the mode is enabled, the transaction submissions are rejected and all the queries
+//! keep working, e.g. during the database migrations or the incidents. The mode is either
+//! forced by the config, or set by the admin API for all the API servers at once: it's
+//! stored in the database and reloaded once it gets older than `READ_ONLY_MODE_REFRESH_INTERVAL`.
+
+// Built-in uses
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+// External uses
+use tokio::sync::RwLock;
+
+// Workspace uses
+use zksync_config::configs::api::CommonApiConfig;
+use zksync_storage::ConnectionPool;
+
+// Local uses
+use super::tx_sender::SubmitError;
+
+/// How long the loaded mode is used before being reloaded from the database.
+const READ_ONLY_MODE_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone)]
+pub struct ReadOnlyMode {
+    pool: ConnectionPool,
+    /// The mode forced by the config.
+    forced: bool,
+    retry_after: Duration,
+    /// The reason of the read-only mode if it's enabled by the admin API.
+    cache: Arc<RwLock<Option<(Option<String>, Instant)>>>,
+}
+
+impl ReadOnlyMode {
+    pub fn new(pool: ConnectionPool, config: &CommonApiConfig) -> Self {
+        Self {
+            pool,
+            forced: config.read_only,
+            retry_after: config.read_only_retry_after(),
+            cache: Default::default(),
+        }
+    }
+
+    /// How long the clients should wait before retrying the rejected submission.
+    pub fn retry_after(&self) -> Duration {
+        self.retry_after
+    }
+
+    /// Returns the reason of the read-only mode if it's enabled by the admin API.
+    async fn enabled_reason(&self) -> Option<String> {
+        if let Some((reason, loaded_at)) = self.cache.read().await.as_ref() {
+            if loaded_at.elapsed() < READ_ONLY_MODE_REFRESH_INTERVAL {
+                return reason.clone();
+            }
+        }
+
+        let reason = match self.load_enabled_reason().await {
+            Ok(reason) => reason,
+            Err(err) => {
+                // The submissions are not blocked by the database failures,
+                // the last known mode is used until the next attempt.
+                vlog::warn!("Can't load the read-only mode: {}", err);
+                self.cache
+                    .read()
+                    .await
+                    .as_ref()
+                    .and_then(|(reason, _)| reason.clone())
+            }
+        };
+        *self.cache.write().await = Some((reason.clone(), Instant::now()));
+        metrics::gauge!("api.read_only_mode", reason.is_some() as u8 as f64);
+        reason
+    }
+
+    async fn load_enabled_reason(&self) -> anyhow::Result<Option<String>> {
+        let mode = self
+            .pool
+            .access_storage()
+            .await?
+            .read_only_mode_schema()
+            .load_read_only_mode()
+            .await?;
+        Ok(mode
+            .filter(|mode| mode.enabled)
+            .map(|mode| mode.reason.unwrap_or_default()))
+    }
+
+    /// Returns an error if the transaction submissions are rejected.
+    pub async fn check(&self) -> Result<(), SubmitError> {
+        if self.forced {
+            return Err(SubmitError::ReadOnlyMode(
+                "the submissions are disabled by the config".to_string(),
+            ));
+        }
+        match self.enabled_reason().await {
+            Some(reason) if reason.is_empty() => Err(SubmitError::ReadOnlyMode(
+                "the submissions are temporarily disabled".to_string(),
+            )),
+            Some(reason) => Err(SubmitError::ReadOnlyMode(reason)),
+            None => Ok(()),
+        }
+    }
+}
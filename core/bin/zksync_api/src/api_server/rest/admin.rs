@@ -0,0 +1,1172 @@
+//! Admin part of the REST API, used to manage the API keys of the clients,
+//! the discounts of the transaction fees, the tokens accepted for paying fees,
+//! the metadata of the tokens, the denylist of the tokens, the read-only mode of the API servers,
+//! the reloadable parameters of the config of this server, the stuck Ethereum transactions
+//! of the Ethereum sender, the webhooks notified about the events by the event listener,
+//! and exposes the status of the provers.
+//!
+//! The endpoints require the bearer JWT signed by the admin secret.
+//! The tokens changed by the endpoints are invalidated in the token cache of this server,
+//! the caches of the other servers are refreshed once they expire.
+
+// Built-in uses
+use std::time::{Duration, Instant};
+
+// External uses
+use actix_web::{
+    dev::ServiceRequest,
+    error::{ErrorBadRequest, ErrorInternalServerError, ErrorNotFound},
+    web, HttpResponse, Scope,
+};
+use actix_web_httpauth::{
+    extractors::{
+        bearer::{BearerAuth, Config},
+        AuthenticationError,
+    },
+    middleware::HttpAuthentication,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// Workspace uses
+use zksync_api_types::v02::token::TokenMetadata;
+use zksync_config::{configs::api::AdminApiConfig, ReloadableConfig};
+use zksync_crypto::rand::{thread_rng, Rng};
+use zksync_storage::{
+    api_keys::records::{StoredApiKey, StoredApiKeyUsage},
+    prover::records::{StorageConnectedProver, StorageJobQueueDepth},
+    read_only_mode::records::StoredReadOnlyMode,
+    tokens::records::StoredDenylistedToken,
+    webhooks::records::{StoredWebhook, StoredWebhookDelivery},
+    ConnectionPool, StorageProcessor,
+};
+use zksync_token_db_cache::TokenDBCache;
+use zksync_types::{
+    ethereum::{ETHOperation, ETHTxAttempt, EthOpId},
+    event::webhook::WebhookFilter,
+    Address, BlockNumber, TokenId, TokenLike, H256, U256,
+};
+use zksync_utils::{auth::AuthTokenValidator, ratio_to_big_decimal, scaled_big_decimal_to_ratio};
+
+// Local uses
+use crate::api_server::config_reload::ConfigReloader;
+use crate::fee_ticker::discounts::{FeeDiscount, FEE_DISCOUNT_SUBSIDY_TYPE, FEE_TYPE_NAMES};
+
+/// Checks the JsonWebToken of the admin request against the secret.
+async fn validate_auth(
+    secret_auth: &str,
+    req: ServiceRequest,
+    credentials: BearerAuth,
+) -> actix_web::Result<ServiceRequest> {
+    let config = req.app_data::<Config>().cloned().unwrap_or_default();
+
+    AuthTokenValidator::new(secret_auth)
+        .validate_auth_token(credentials.token())
+        .map_err(|_| AuthenticationError::from(config))?;
+
+    Ok(req)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueApiKeyRequest {
+    pub requests_per_second: u32,
+    #[serde(default)]
+    pub description: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKeyInfo {
+    pub api_key: String,
+    pub requests_per_second: u32,
+    pub description: String,
+    pub created_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl From<StoredApiKey> for ApiKeyInfo {
+    fn from(key: StoredApiKey) -> Self {
+        Self {
+            api_key: key.api_key,
+            requests_per_second: key.requests_per_second as u32,
+            description: key.description,
+            created_at: key.created_at,
+            revoked_at: key.revoked_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKeyUsage {
+    pub day: NaiveDate,
+    pub requests: u64,
+}
+
+impl From<StoredApiKeyUsage> for ApiKeyUsage {
+    fn from(usage: StoredApiKeyUsage) -> Self {
+        Self {
+            day: usage.day,
+            requests: usage.requests as u64,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewFeeDiscountRequest {
+    pub token_id: Option<TokenId>,
+    pub tx_type: Option<String>,
+    pub address: Option<Address>,
+    pub discount_percent: u32,
+    #[serde(default)]
+    pub description: String,
+}
+
+impl NewFeeDiscountRequest {
+    fn validate(&self) -> Result<(), &'static str> {
+        if self.discount_percent == 0 || self.discount_percent > 100 {
+            return Err("the discount must be from 1 to 100 percent");
+        }
+        if let Some(tx_type) = &self.tx_type {
+            if !FEE_TYPE_NAMES.contains(&tx_type.as_str()) {
+                return Err("unknown transaction type");
+            }
+        }
+        Ok(())
+    }
+}
+
+fn validate_token_metadata(metadata: &TokenMetadata) -> Result<(), &'static str> {
+    let urls = [&metadata.logo_uri, &metadata.project_url];
+    for url in urls.iter().filter_map(|url| url.as_deref()) {
+        if !url.starts_with("https://") && !url.starts_with("http://") {
+            return Err("the URLs must use the http or https scheme");
+        }
+    }
+    if metadata.display_name.as_deref() == Some("") {
+        return Err("the display name must not be empty");
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DenylistTokenRequest {
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DenylistedToken {
+    pub token_id: TokenId,
+    pub reason: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<StoredDenylistedToken> for DenylistedToken {
+    fn from(token: StoredDenylistedToken) -> Self {
+        Self {
+            token_id: TokenId(token.token_id as u32),
+            reason: token.reason,
+            created_at: token.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadOnlyModeRequest {
+    pub enabled: bool,
+    /// Reason shown to the clients whose submissions are rejected.
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadOnlyModeInfo {
+    pub enabled: bool,
+    pub reason: Option<String>,
+    /// `None` if the mode was never set.
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl From<StoredReadOnlyMode> for ReadOnlyModeInfo {
+    fn from(mode: StoredReadOnlyMode) -> Self {
+        Self {
+            enabled: mode.enabled,
+            reason: mode.reason,
+            updated_at: Some(mode.updated_at),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeDiscountsTotal {
+    /// Sum of the discounted parts of the fees in USD.
+    pub total_usd: String,
+}
+
+/// Unconfirmed Ethereum operation along with the history of its transactions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthOperationInfo {
+    pub id: EthOpId,
+    pub op_type: String,
+    pub nonce: U256,
+    pub operator: Option<Address>,
+    pub last_deadline_block: u64,
+    pub last_used_gas_price: U256,
+    pub last_used_priority_fee: Option<U256>,
+    pub tx_hashes: Vec<H256>,
+    /// Sent transactions, the ones sent before the history was tracked aren't included.
+    pub attempts: Vec<ETHTxAttempt>,
+    /// Whether the replacement of the transaction is requested and not handled yet.
+    pub bump_requested: bool,
+}
+
+impl EthOperationInfo {
+    fn new(op: ETHOperation, attempts: Vec<ETHTxAttempt>, bump_requested: bool) -> Self {
+        Self {
+            id: op.id,
+            op_type: op.op_type.to_string(),
+            nonce: op.nonce,
+            operator: op.operator,
+            last_deadline_block: op.last_deadline_block,
+            last_used_gas_price: op.last_used_gas_price,
+            last_used_priority_fee: op.last_used_priority_fee,
+            tx_hashes: op.used_tx_hashes,
+            attempts,
+            bump_requested,
+        }
+    }
+}
+
+/// Maximum amount of the latest deliveries returned by the delivery log of the webhook.
+const WEBHOOK_DELIVERIES_LIMIT: u32 = 100;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisterWebhookRequest {
+    pub url: String,
+    /// Serialized `WebhookFilter`, the webhook is notified about all the transactions if not set.
+    #[serde(default)]
+    pub filter: Option<Value>,
+    #[serde(default)]
+    pub description: String,
+}
+
+impl RegisterWebhookRequest {
+    /// Returns the filter of the webhook to be stored.
+    fn validate(&self) -> Result<Value, &'static str> {
+        if !self.url.starts_with("https://") && !self.url.starts_with("http://") {
+            return Err("the URL must use the http or https scheme");
+        }
+        let filter = self
+            .filter
+            .clone()
+            .unwrap_or_else(|| Value::Object(Default::default()));
+        if serde_json::from_value::<WebhookFilter>(filter.clone()).is_err() {
+            return Err("invalid filter of the events");
+        }
+        Ok(filter)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookInfo {
+    pub id: i32,
+    pub url: String,
+    pub filter: Value,
+    pub description: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<StoredWebhook> for WebhookInfo {
+    fn from(webhook: StoredWebhook) -> Self {
+        Self {
+            id: webhook.id,
+            url: webhook.url,
+            filter: webhook.filter,
+            description: webhook.description,
+            created_at: webhook.created_at,
+        }
+    }
+}
+
+/// The registered webhook, its secret is only returned on the registration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisteredWebhook {
+    #[serde(flatten)]
+    pub webhook: WebhookInfo,
+    /// Secret of the HMAC-SHA256 signatures of the delivered payloads.
+    pub secret: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookDelivery {
+    pub id: i64,
+    pub event_id: u64,
+    /// Either `pending`, `delivered` or `failed`.
+    pub status: String,
+    pub attempts: u32,
+    pub last_response_code: Option<u16>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_attempt_at: Option<DateTime<Utc>>,
+    pub next_attempt_at: DateTime<Utc>,
+}
+
+impl From<StoredWebhookDelivery> for WebhookDelivery {
+    fn from(delivery: StoredWebhookDelivery) -> Self {
+        Self {
+            id: delivery.id,
+            event_id: delivery.event_id as u64,
+            status: delivery.status,
+            attempts: delivery.attempts as u32,
+            last_response_code: delivery.last_response_code.map(|code| code as u16),
+            last_error: delivery.last_error,
+            created_at: delivery.created_at,
+            last_attempt_at: delivery.last_attempt_at,
+            next_attempt_at: delivery.next_attempt_at,
+        }
+    }
+}
+
+/// Prover which has reached the prover server within the gone timeout.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProverInfo {
+    pub name: String,
+    pub last_seen_at: DateTime<Utc>,
+    /// The job leased by the prover, if there is one.
+    pub job_id: Option<i32>,
+    pub job_type: Option<String>,
+    pub first_block: Option<BlockNumber>,
+    pub last_block: Option<BlockNumber>,
+}
+
+impl From<StorageConnectedProver> for ProverInfo {
+    fn from(prover: StorageConnectedProver) -> Self {
+        Self {
+            name: prover.prover_name,
+            last_seen_at: prover.last_seen_at,
+            job_id: prover.job_id,
+            job_type: prover.job_type,
+            first_block: prover.first_block.map(|block| BlockNumber(block as u32)),
+            last_block: prover.last_block.map(|block| BlockNumber(block as u32)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProverJobQueueInfo {
+    pub job_type: String,
+    pub idle_jobs: u64,
+    pub in_progress_jobs: u64,
+}
+
+impl From<StorageJobQueueDepth> for ProverJobQueueInfo {
+    fn from(depth: StorageJobQueueDepth) -> Self {
+        Self {
+            job_type: depth.job_type,
+            idle_jobs: depth.idle_jobs as u64,
+            in_progress_jobs: depth.in_progress_jobs as u64,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProverStatus {
+    pub provers: Vec<ProverInfo>,
+    pub queue: Vec<ProverJobQueueInfo>,
+}
+
+/// Shared data between `/api/admin` endpoints.
+#[derive(Debug, Clone)]
+struct ApiAdminData {
+    pool: ConnectionPool,
+    tokens: TokenDBCache,
+    config_reloader: ConfigReloader,
+    /// The provers which haven't reached the prover server for this time are considered gone.
+    prover_gone_timeout: Duration,
+}
+
+impl ApiAdminData {
+    async fn access_storage(&self) -> actix_web::Result<StorageProcessor<'_>> {
+        self.pool.access_storage().await.map_err(|err| {
+            vlog::warn!("Failed to access storage: {}", err);
+            ErrorInternalServerError(err)
+        })
+    }
+}
+
+fn storage_error(err: anyhow::Error) -> actix_web::Error {
+    vlog::warn!("Storage layer error: {}", err);
+    ErrorInternalServerError("storage layer error")
+}
+
+/// Generates the new random API key.
+fn generate_api_key() -> String {
+    let bytes: [u8; 32] = thread_rng().gen();
+    hex::encode(bytes)
+}
+
+async fn list_api_keys(data: web::Data<ApiAdminData>) -> actix_web::Result<HttpResponse> {
+    let start = Instant::now();
+    let mut storage = data.access_storage().await?;
+    let keys = storage
+        .api_keys_schema()
+        .load_api_keys()
+        .await
+        .map_err(storage_error)?
+        .into_iter()
+        .map(ApiKeyInfo::from)
+        .collect::<Vec<_>>();
+
+    metrics::histogram!("api", start.elapsed(), "type" => "admin", "endpoint_name" => "list_api_keys");
+    Ok(HttpResponse::Ok().json(keys))
+}
+
+async fn issue_api_key(
+    data: web::Data<ApiAdminData>,
+    request: web::Json<IssueApiKeyRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let start = Instant::now();
+    if request.requests_per_second == 0 {
+        return Err(ErrorBadRequest("the quota of the API key must be positive"));
+    }
+
+    let mut storage = data.access_storage().await?;
+    let key = storage
+        .api_keys_schema()
+        .store_api_key(
+            &generate_api_key(),
+            request.requests_per_second,
+            &request.description,
+        )
+        .await
+        .map_err(storage_error)?;
+    vlog::info!(
+        "Issued API key with quota of {} requests per second: {}",
+        request.requests_per_second,
+        request.description
+    );
+
+    metrics::histogram!("api", start.elapsed(), "type" => "admin", "endpoint_name" => "issue_api_key");
+    Ok(HttpResponse::Ok().json(ApiKeyInfo::from(key)))
+}
+
+/// Revokes the API key. The rate limiter caches the quotas of the keys,
+/// so the revoked key may still be accepted for about a minute.
+async fn revoke_api_key(
+    data: web::Data<ApiAdminData>,
+    api_key: web::Path<String>,
+) -> actix_web::Result<HttpResponse> {
+    let start = Instant::now();
+    let mut storage = data.access_storage().await?;
+    let revoked = storage
+        .api_keys_schema()
+        .revoke_api_key(&api_key)
+        .await
+        .map_err(storage_error)?;
+
+    metrics::histogram!("api", start.elapsed(), "type" => "admin", "endpoint_name" => "revoke_api_key");
+    if revoked {
+        Ok(HttpResponse::Ok().finish())
+    } else {
+        Err(ErrorNotFound("there is no such active API key"))
+    }
+}
+
+async fn api_key_usage(
+    data: web::Data<ApiAdminData>,
+    api_key: web::Path<String>,
+) -> actix_web::Result<HttpResponse> {
+    let start = Instant::now();
+    let mut storage = data.access_storage().await?;
+    let usage = storage
+        .api_keys_schema()
+        .load_api_key_usage(&api_key)
+        .await
+        .map_err(storage_error)?
+        .into_iter()
+        .map(ApiKeyUsage::from)
+        .collect::<Vec<_>>();
+
+    metrics::histogram!("api", start.elapsed(), "type" => "admin", "endpoint_name" => "api_key_usage");
+    Ok(HttpResponse::Ok().json(usage))
+}
+
+async fn list_fee_discounts(data: web::Data<ApiAdminData>) -> actix_web::Result<HttpResponse> {
+    let start = Instant::now();
+    let mut storage = data.access_storage().await?;
+    let discounts = storage
+        .fee_discounts_schema()
+        .load_fee_discounts()
+        .await
+        .map_err(storage_error)?
+        .into_iter()
+        .map(FeeDiscount::from)
+        .collect::<Vec<_>>();
+
+    metrics::histogram!("api", start.elapsed(), "type" => "admin", "endpoint_name" => "list_fee_discounts");
+    Ok(HttpResponse::Ok().json(discounts))
+}
+
+/// Adds the fee discount. The fee ticker caches the discounts,
+/// so the new discount is applied in about 10 seconds.
+async fn add_fee_discount(
+    data: web::Data<ApiAdminData>,
+    request: web::Json<NewFeeDiscountRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let start = Instant::now();
+    request.validate().map_err(ErrorBadRequest)?;
+
+    let mut storage = data.access_storage().await?;
+    let discount = storage
+        .fee_discounts_schema()
+        .store_fee_discount(
+            request.token_id,
+            request.tx_type.as_deref(),
+            request.address,
+            request.discount_percent,
+            &request.description,
+        )
+        .await
+        .map_err(storage_error)?;
+    vlog::info!(
+        "Added fee discount of {}%: {}",
+        request.discount_percent,
+        request.description
+    );
+
+    metrics::histogram!("api", start.elapsed(), "type" => "admin", "endpoint_name" => "add_fee_discount");
+    Ok(HttpResponse::Ok().json(FeeDiscount::from(discount)))
+}
+
+async fn remove_fee_discount(
+    data: web::Data<ApiAdminData>,
+    id: web::Path<i64>,
+) -> actix_web::Result<HttpResponse> {
+    let start = Instant::now();
+    let mut storage = data.access_storage().await?;
+    let removed = storage
+        .fee_discounts_schema()
+        .remove_fee_discount(*id)
+        .await
+        .map_err(storage_error)?;
+
+    metrics::histogram!("api", start.elapsed(), "type" => "admin", "endpoint_name" => "remove_fee_discount");
+    if removed {
+        Ok(HttpResponse::Ok().finish())
+    } else {
+        Err(ErrorNotFound("there is no such fee discount"))
+    }
+}
+
+async fn fee_discounts_total(data: web::Data<ApiAdminData>) -> actix_web::Result<HttpResponse> {
+    let start = Instant::now();
+    let mut storage = data.access_storage().await?;
+    let total_scaled = storage
+        .misc_schema()
+        .get_total_used_subsidy_for_type(FEE_DISCOUNT_SUBSIDY_TYPE)
+        .await
+        .map_err(storage_error)?;
+    let total_usd = scaled_big_decimal_to_ratio(total_scaled).map_err(ErrorInternalServerError)?;
+
+    metrics::histogram!("api", start.elapsed(), "type" => "admin", "endpoint_name" => "fee_discounts_total");
+    Ok(HttpResponse::Ok().json(FeeDiscountsTotal {
+        total_usd: ratio_to_big_decimal(&total_usd, 6).to_string(),
+    }))
+}
+
+/// Sets the eligibility of the token for paying fees, `None` resets it
+/// to be decided by the market volume of the token.
+async fn set_fee_eligibility(
+    data: web::Data<ApiAdminData>,
+    token_id: TokenId,
+    enabled: Option<bool>,
+) -> actix_web::Result<HttpResponse> {
+    let mut storage = data.access_storage().await?;
+    let updated = storage
+        .tokens_schema()
+        .set_fee_eligibility_override(token_id, enabled)
+        .await
+        .map_err(storage_error)?;
+    if !updated {
+        return Err(ErrorNotFound("there is no such token"));
+    }
+    data.tokens.invalidate(token_id).await;
+    vlog::info!(
+        "Fee eligibility of the token {} is set to {}",
+        token_id,
+        match enabled {
+            Some(true) => "enabled",
+            Some(false) => "disabled",
+            None => "automatic",
+        }
+    );
+    Ok(HttpResponse::Ok().finish())
+}
+
+async fn enable_fee_token(
+    data: web::Data<ApiAdminData>,
+    token_id: web::Path<u32>,
+) -> actix_web::Result<HttpResponse> {
+    let start = Instant::now();
+    let response = set_fee_eligibility(data, TokenId(*token_id), Some(true)).await;
+    metrics::histogram!("api", start.elapsed(), "type" => "admin", "endpoint_name" => "enable_fee_token");
+    response
+}
+
+async fn disable_fee_token(
+    data: web::Data<ApiAdminData>,
+    token_id: web::Path<u32>,
+) -> actix_web::Result<HttpResponse> {
+    let start = Instant::now();
+    let response = set_fee_eligibility(data, TokenId(*token_id), Some(false)).await;
+    metrics::histogram!("api", start.elapsed(), "type" => "admin", "endpoint_name" => "disable_fee_token");
+    response
+}
+
+async fn reset_fee_token(
+    data: web::Data<ApiAdminData>,
+    token_id: web::Path<u32>,
+) -> actix_web::Result<HttpResponse> {
+    let start = Instant::now();
+    let response = set_fee_eligibility(data, TokenId(*token_id), None).await;
+    metrics::histogram!("api", start.elapsed(), "type" => "admin", "endpoint_name" => "reset_fee_token");
+    response
+}
+
+/// Sets the metadata of the token, the fields left unset are removed.
+async fn set_token_metadata(
+    data: web::Data<ApiAdminData>,
+    token_id: web::Path<u32>,
+    metadata: web::Json<TokenMetadata>,
+) -> actix_web::Result<HttpResponse> {
+    let start = Instant::now();
+    validate_token_metadata(&metadata).map_err(ErrorBadRequest)?;
+
+    let token_id = TokenId(*token_id);
+    let mut storage = data.access_storage().await?;
+    let token = storage
+        .tokens_schema()
+        .get_token(TokenLike::Id(token_id))
+        .await
+        .map_err(storage_error)?;
+    if token.is_none() {
+        return Err(ErrorNotFound("there is no such token"));
+    }
+    storage
+        .tokens_schema()
+        .store_token_metadata(token_id, &metadata)
+        .await
+        .map_err(storage_error)?;
+    data.tokens.invalidate(token_id).await;
+    vlog::info!("Metadata of the token {} is updated", token_id);
+
+    metrics::histogram!("api", start.elapsed(), "type" => "admin", "endpoint_name" => "set_token_metadata");
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Lists the tokens denylisted by the admin API, the ones denylisted by the config aren't included.
+async fn list_denylisted_tokens(data: web::Data<ApiAdminData>) -> actix_web::Result<HttpResponse> {
+    let start = Instant::now();
+    let mut storage = data.access_storage().await?;
+    let tokens = storage
+        .tokens_schema()
+        .load_token_denylist()
+        .await
+        .map_err(storage_error)?
+        .into_iter()
+        .map(DenylistedToken::from)
+        .collect::<Vec<_>>();
+
+    metrics::histogram!("api", start.elapsed(), "type" => "admin", "endpoint_name" => "list_denylisted_tokens");
+    Ok(HttpResponse::Ok().json(tokens))
+}
+
+/// Adds the token to the denylist. The API servers and the mempools cache the denylist,
+/// so the token is rejected in about 10 seconds.
+async fn denylist_token(
+    data: web::Data<ApiAdminData>,
+    token_id: web::Path<u32>,
+    request: web::Json<DenylistTokenRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let start = Instant::now();
+    let token_id = TokenId(*token_id);
+    if token_id == TokenId(0) {
+        return Err(ErrorBadRequest("ETH can't be denylisted"));
+    }
+
+    let mut storage = data.access_storage().await?;
+    let token = storage
+        .tokens_schema()
+        .get_token(TokenLike::Id(token_id))
+        .await
+        .map_err(storage_error)?;
+    if token.is_none() {
+        return Err(ErrorNotFound("there is no such token"));
+    }
+    storage
+        .tokens_schema()
+        .add_token_to_denylist(token_id, &request.reason)
+        .await
+        .map_err(storage_error)?;
+    data.tokens.invalidate(token_id).await;
+    vlog::info!("Token {} is denylisted: {}", token_id, request.reason);
+
+    metrics::histogram!("api", start.elapsed(), "type" => "admin", "endpoint_name" => "denylist_token");
+    Ok(HttpResponse::Ok().finish())
+}
+
+async fn remove_denylisted_token(
+    data: web::Data<ApiAdminData>,
+    token_id: web::Path<u32>,
+) -> actix_web::Result<HttpResponse> {
+    let start = Instant::now();
+    let token_id = TokenId(*token_id);
+    let mut storage = data.access_storage().await?;
+    let removed = storage
+        .tokens_schema()
+        .remove_token_from_denylist(token_id)
+        .await
+        .map_err(storage_error)?;
+
+    metrics::histogram!("api", start.elapsed(), "type" => "admin", "endpoint_name" => "remove_denylisted_token");
+    if removed {
+        data.tokens.invalidate(token_id).await;
+        vlog::info!("Token {} is removed from the denylist", token_id);
+        Ok(HttpResponse::Ok().finish())
+    } else {
+        Err(ErrorNotFound("the token is not denylisted"))
+    }
+}
+
+/// Returns the read-only mode set by the admin API, the mode forced by the config isn't included.
+async fn get_read_only_mode(data: web::Data<ApiAdminData>) -> actix_web::Result<HttpResponse> {
+    let start = Instant::now();
+    let mut storage = data.access_storage().await?;
+    let mode = storage
+        .read_only_mode_schema()
+        .load_read_only_mode()
+        .await
+        .map_err(storage_error)?
+        .map(ReadOnlyModeInfo::from)
+        .unwrap_or(ReadOnlyModeInfo {
+            enabled: false,
+            reason: None,
+            updated_at: None,
+        });
+
+    metrics::histogram!("api", start.elapsed(), "type" => "admin", "endpoint_name" => "get_read_only_mode");
+    Ok(HttpResponse::Ok().json(mode))
+}
+
+/// Enables or disables the read-only mode. The API servers cache the mode,
+/// so the submissions are rejected or accepted again in about 5 seconds.
+async fn set_read_only_mode(
+    data: web::Data<ApiAdminData>,
+    request: web::Json<ReadOnlyModeRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let start = Instant::now();
+    let mut storage = data.access_storage().await?;
+    let mode = storage
+        .read_only_mode_schema()
+        .set_read_only_mode(request.enabled, request.reason.as_deref())
+        .await
+        .map_err(storage_error)?;
+    vlog::info!(
+        "Read-only mode is {}: {}",
+        if mode.enabled { "enabled" } else { "disabled" },
+        mode.reason.as_deref().unwrap_or_default()
+    );
+
+    metrics::histogram!("api", start.elapsed(), "type" => "admin", "endpoint_name" => "set_read_only_mode");
+    Ok(HttpResponse::Ok().json(ReadOnlyModeInfo::from(mode)))
+}
+
+/// Returns the reloadable parameters of the config currently used by this server.
+async fn get_config(data: web::Data<ApiAdminData>) -> actix_web::Result<HttpResponse> {
+    let start = Instant::now();
+    let config = data.config_reloader.current();
+
+    metrics::histogram!("api", start.elapsed(), "type" => "admin", "endpoint_name" => "get_config");
+    Ok(HttpResponse::Ok().json(config))
+}
+
+/// Applies the parameters to this server until they're reloaded or it's restarted.
+async fn set_config(
+    data: web::Data<ApiAdminData>,
+    config: web::Json<ReloadableConfig>,
+) -> actix_web::Result<HttpResponse> {
+    let start = Instant::now();
+    config.validate().map_err(ErrorBadRequest)?;
+    data.config_reloader.update(config.into_inner());
+
+    metrics::histogram!("api", start.elapsed(), "type" => "admin", "endpoint_name" => "set_config");
+    Ok(HttpResponse::Ok().json(data.config_reloader.current()))
+}
+
+/// Reloads the parameters from the env file, the same as `SIGHUP` does.
+async fn reload_config(data: web::Data<ApiAdminData>) -> actix_web::Result<HttpResponse> {
+    let start = Instant::now();
+    let config = data.config_reloader.reload().map_err(|err| {
+        vlog::warn!("Can't reload the config: {}", err);
+        ErrorBadRequest(err)
+    })?;
+
+    metrics::histogram!("api", start.elapsed(), "type" => "admin", "endpoint_name" => "reload_config");
+    Ok(HttpResponse::Ok().json(config))
+}
+
+/// Returns the unconfirmed Ethereum operations in the order of sending.
+async fn list_eth_operations(data: web::Data<ApiAdminData>) -> actix_web::Result<HttpResponse> {
+    let start = Instant::now();
+    let mut storage = data.access_storage().await?;
+    let operations = storage
+        .ethereum_schema()
+        .load_unconfirmed_operations()
+        .await
+        .map_err(storage_error)?;
+    let bump_requests = storage
+        .ethereum_schema()
+        .load_tx_bump_requests()
+        .await
+        .map_err(storage_error)?;
+
+    let mut response = Vec::with_capacity(operations.len());
+    for op in operations {
+        let attempts = storage
+            .ethereum_schema()
+            .load_tx_attempts(op.id)
+            .await
+            .map_err(storage_error)?;
+        let bump_requested = bump_requests.contains(&op.id);
+        response.push(EthOperationInfo::new(op, attempts, bump_requested));
+    }
+
+    metrics::histogram!("api", start.elapsed(), "type" => "admin", "endpoint_name" => "list_eth_operations");
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Requests the replacement of the transaction of the unconfirmed Ethereum operation,
+/// the Ethereum sender sends it regardless of its resubmission policy.
+async fn bump_eth_operation(
+    data: web::Data<ApiAdminData>,
+    id: web::Path<EthOpId>,
+) -> actix_web::Result<HttpResponse> {
+    let start = Instant::now();
+    let mut storage = data.access_storage().await?;
+    let requested = storage
+        .ethereum_schema()
+        .request_tx_bump(*id)
+        .await
+        .map_err(storage_error)?;
+
+    metrics::histogram!("api", start.elapsed(), "type" => "admin", "endpoint_name" => "bump_eth_operation");
+    if requested {
+        vlog::info!(
+            "Replacement of the tx of ETH Operation <id: {}> is requested",
+            *id
+        );
+        Ok(HttpResponse::Ok().finish())
+    } else {
+        Err(ErrorNotFound(
+            "the operation is not found or is confirmed already",
+        ))
+    }
+}
+
+async fn list_webhooks(data: web::Data<ApiAdminData>) -> actix_web::Result<HttpResponse> {
+    let start = Instant::now();
+    let mut storage = data.access_storage().await?;
+    let webhooks = storage
+        .webhooks_schema()
+        .load_webhooks()
+        .await
+        .map_err(storage_error)?
+        .into_iter()
+        .map(WebhookInfo::from)
+        .collect::<Vec<_>>();
+
+    metrics::histogram!("api", start.elapsed(), "type" => "admin", "endpoint_name" => "list_webhooks");
+    Ok(HttpResponse::Ok().json(webhooks))
+}
+
+async fn register_webhook(
+    data: web::Data<ApiAdminData>,
+    request: web::Json<RegisterWebhookRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let start = Instant::now();
+    let filter = request.validate().map_err(ErrorBadRequest)?;
+
+    let mut storage = data.access_storage().await?;
+    let webhook = storage
+        .webhooks_schema()
+        .register_webhook(
+            &request.url,
+            &generate_api_key(),
+            &filter,
+            &request.description,
+        )
+        .await
+        .map_err(storage_error)?;
+    vlog::info!(
+        "Registered webhook {} with URL {}: {}",
+        webhook.id,
+        webhook.url,
+        webhook.description
+    );
+
+    metrics::histogram!("api", start.elapsed(), "type" => "admin", "endpoint_name" => "register_webhook");
+    Ok(HttpResponse::Ok().json(RegisteredWebhook {
+        secret: webhook.secret.clone(),
+        webhook: WebhookInfo::from(webhook),
+    }))
+}
+
+async fn remove_webhook(
+    data: web::Data<ApiAdminData>,
+    id: web::Path<i32>,
+) -> actix_web::Result<HttpResponse> {
+    let start = Instant::now();
+    let mut storage = data.access_storage().await?;
+    let removed = storage
+        .webhooks_schema()
+        .remove_webhook(*id)
+        .await
+        .map_err(storage_error)?;
+
+    metrics::histogram!("api", start.elapsed(), "type" => "admin", "endpoint_name" => "remove_webhook");
+    if removed {
+        Ok(HttpResponse::Ok().finish())
+    } else {
+        Err(ErrorNotFound("there is no such webhook"))
+    }
+}
+
+async fn webhook_deliveries(
+    data: web::Data<ApiAdminData>,
+    id: web::Path<i32>,
+) -> actix_web::Result<HttpResponse> {
+    let start = Instant::now();
+    let mut storage = data.access_storage().await?;
+    let deliveries = storage
+        .webhooks_schema()
+        .load_deliveries(*id, WEBHOOK_DELIVERIES_LIMIT)
+        .await
+        .map_err(storage_error)?
+        .into_iter()
+        .map(WebhookDelivery::from)
+        .collect::<Vec<_>>();
+
+    metrics::histogram!("api", start.elapsed(), "type" => "admin", "endpoint_name" => "webhook_deliveries");
+    Ok(HttpResponse::Ok().json(deliveries))
+}
+
+async fn prover_status(data: web::Data<ApiAdminData>) -> actix_web::Result<HttpResponse> {
+    let start = Instant::now();
+    let mut storage = data.access_storage().await?;
+    let provers = storage
+        .prover_schema()
+        .load_connected_provers(data.prover_gone_timeout)
+        .await
+        .map_err(storage_error)?
+        .into_iter()
+        .map(ProverInfo::from)
+        .collect();
+    let queue = storage
+        .prover_schema()
+        .load_job_queue_depth()
+        .await
+        .map_err(storage_error)?
+        .into_iter()
+        .map(ProverJobQueueInfo::from)
+        .collect();
+
+    metrics::histogram!("api", start.elapsed(), "type" => "admin", "endpoint_name" => "prover_status");
+    Ok(HttpResponse::Ok().json(ProverStatus { provers, queue }))
+}
+
+pub(crate) fn api_scope(
+    pool: ConnectionPool,
+    tokens: TokenDBCache,
+    config: &AdminApiConfig,
+    config_reloader: ConfigReloader,
+    prover_gone_timeout: Duration,
+) -> Scope {
+    let secret_auth = config.secret_auth.clone();
+    let auth = HttpAuthentication::bearer(move |req, credentials| {
+        let secret_auth = secret_auth.clone();
+        async move { validate_auth(&secret_auth, req, credentials).await }
+    });
+
+    let data = web::Data::new(ApiAdminData {
+        pool,
+        tokens,
+        config_reloader,
+        prover_gone_timeout,
+    });
+
+    // The wrapped scopes have another type, so they're mounted as services.
+    web::scope("/api/admin")
+        .service(
+            web::scope("api_keys")
+                .wrap(auth.clone())
+                .app_data(data.clone())
+                .route("", web::get().to(list_api_keys))
+                .route("", web::post().to(issue_api_key))
+                .route("{api_key}", web::delete().to(revoke_api_key))
+                .route("{api_key}/usage", web::get().to(api_key_usage)),
+        )
+        .service(
+            web::scope("fee_discounts")
+                .wrap(auth.clone())
+                .app_data(data.clone())
+                .route("", web::get().to(list_fee_discounts))
+                .route("", web::post().to(add_fee_discount))
+                .route("total", web::get().to(fee_discounts_total))
+                .route("{id}", web::delete().to(remove_fee_discount)),
+        )
+        .service(
+            web::scope("fee_tokens")
+                .wrap(auth.clone())
+                .app_data(data.clone())
+                .route("{token_id}/enable", web::post().to(enable_fee_token))
+                .route("{token_id}/disable", web::post().to(disable_fee_token))
+                .route("{token_id}/reset", web::post().to(reset_fee_token)),
+        )
+        .service(
+            web::scope("token_metadata")
+                .wrap(auth.clone())
+                .app_data(data.clone())
+                .route("{token_id}", web::put().to(set_token_metadata)),
+        )
+        .service(
+            web::scope("token_denylist")
+                .wrap(auth.clone())
+                .app_data(data.clone())
+                .route("", web::get().to(list_denylisted_tokens))
+                .route("{token_id}", web::put().to(denylist_token))
+                .route("{token_id}", web::delete().to(remove_denylisted_token)),
+        )
+        .service(
+            web::scope("read_only_mode")
+                .wrap(auth.clone())
+                .app_data(data.clone())
+                .route("", web::get().to(get_read_only_mode))
+                .route("", web::put().to(set_read_only_mode)),
+        )
+        .service(
+            web::scope("config")
+                .wrap(auth.clone())
+                .app_data(data.clone())
+                .route("", web::get().to(get_config))
+                .route("", web::put().to(set_config))
+                .route("reload", web::post().to(reload_config)),
+        )
+        .service(
+            web::scope("eth_operations")
+                .wrap(auth.clone())
+                .app_data(data.clone())
+                .route("", web::get().to(list_eth_operations))
+                .route("{id}/bump", web::post().to(bump_eth_operation)),
+        )
+        .service(
+            web::scope("webhooks")
+                .wrap(auth.clone())
+                .app_data(data.clone())
+                .route("", web::get().to(list_webhooks))
+                .route("", web::post().to(register_webhook))
+                .route("{id}", web::delete().to(remove_webhook))
+                .route("{id}/deliveries", web::get().to(webhook_deliveries)),
+        )
+        .service(
+            web::scope("prover_status")
+                .wrap(auth)
+                .app_data(data)
+                .route("", web::get().to(prover_status)),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn fee_discount_request_validation() {
+        let request = |discount_percent, tx_type: Option<&str>| NewFeeDiscountRequest {
+            token_id: Some(TokenId(1)),
+            tx_type: tx_type.map(str::to_string),
+            address: None,
+            discount_percent,
+            description: String::new(),
+        };
+
+        assert!(request(50, None).validate().is_ok());
+        assert!(request(100, Some("ChangePubKey")).validate().is_ok());
+        assert!(request(0, None).validate().is_err());
+        assert!(request(101, None).validate().is_err());
+        assert!(request(50, Some("Deposit")).validate().is_err());
+    }
+
+    #[test]
+    fn token_metadata_validation() {
+        let metadata = |logo_uri: Option<&str>, display_name: Option<&str>| TokenMetadata {
+            logo_uri: logo_uri.map(str::to_string),
+            project_url: Some("https://example.com".to_string()),
+            display_name: display_name.map(str::to_string),
+        };
+
+        assert!(validate_token_metadata(&TokenMetadata::default()).is_ok());
+        assert!(
+            validate_token_metadata(&metadata(Some("https://example.com/logo.png"), None)).is_ok()
+        );
+        assert!(validate_token_metadata(&metadata(None, Some("Test token"))).is_ok());
+        assert!(validate_token_metadata(&metadata(Some("javascript:alert(1)"), None)).is_err());
+        assert!(validate_token_metadata(&metadata(None, Some(""))).is_err());
+    }
+
+    #[test]
+    fn webhook_request_validation() {
+        let request = |url: &str, filter: Option<Value>| RegisterWebhookRequest {
+            url: url.to_string(),
+            filter,
+            description: String::new(),
+        };
+
+        let filter = serde_json::json!({ "types": ["Deposit"], "status": "finalized" });
+        assert_eq!(
+            request("https://example.com", Some(filter.clone())).validate(),
+            Ok(filter)
+        );
+        assert_eq!(
+            request("http://example.com", None).validate(),
+            Ok(serde_json::json!({}))
+        );
+        assert!(request("ftp://example.com", None).validate().is_err());
+        assert!(request(
+            "https://example.com",
+            Some(serde_json::json!({ "types": ["Unknown"] }))
+        )
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn api_keys_are_random() {
+        let key = generate_api_key();
+        assert_eq!(key.len(), 64);
+        assert_ne!(key, generate_api_key());
+    }
+}
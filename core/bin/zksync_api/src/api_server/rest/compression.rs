@@ -0,0 +1,175 @@
+//! Compression policy of the REST API responses.
+//!
+//! The responses are compressed by the `Compress` middleware with gzip or brotli depending on
+//! the `Accept-Encoding` of the client. The compression of the small responses doesn't pay off,
+//! and the responses like the images are compressed already, so this middleware marks the
+//! responses which should be sent as is with the `identity` content encoding before they reach
+//! the `Compress` middleware. The streamed responses, i.e. the exports of the transactions,
+//! have no known size and are compressed if their content type is allowed.
+
+// Built-in uses
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+
+// External uses
+use actix_web::{
+    dev::{BodySize, MessageBody, Service, ServiceRequest, ServiceResponse, Transform},
+    http::{
+        header::{CONTENT_ENCODING, CONTENT_TYPE},
+        HeaderValue,
+    },
+    Error,
+};
+use futures::future::{ok, Ready};
+
+// Workspace uses
+use zksync_config::configs::api::RestApiConfig;
+
+/// Decides which responses are compressed, must be wrapped before the `Compress` middleware.
+#[derive(Debug, Clone)]
+pub struct CompressionPolicy {
+    inner: Rc<PolicyConfig>,
+}
+
+#[derive(Debug)]
+struct PolicyConfig {
+    enabled: bool,
+    min_size: usize,
+    content_types: Vec<String>,
+}
+
+impl CompressionPolicy {
+    pub fn new(config: &RestApiConfig) -> Self {
+        Self {
+            inner: Rc::new(PolicyConfig {
+                enabled: config.compression_enabled,
+                min_size: config.compression_min_size,
+                content_types: config
+                    .compression_content_types
+                    .iter()
+                    .map(|content_type| content_type.trim().to_ascii_lowercase())
+                    .collect(),
+            }),
+        }
+    }
+
+    fn should_compress(&self, content_type: Option<&str>, size: BodySize) -> bool {
+        if !self.inner.enabled {
+            return false;
+        }
+        let large = match size {
+            BodySize::None | BodySize::Empty => false,
+            BodySize::Sized(size) => size as usize >= self.inner.min_size,
+            BodySize::Stream => true,
+        };
+        // The parameters of the content type, e.g. the charset, don't matter.
+        let media_type = content_type
+            .and_then(|content_type| content_type.split(';').next())
+            .map(|media_type| media_type.trim().to_ascii_lowercase());
+        large
+            && media_type.map_or(false, |media_type| {
+                self.inner.content_types.contains(&media_type)
+            })
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for CompressionPolicy
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = CompressionPolicyMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(CompressionPolicyMiddleware {
+            service: Rc::new(service),
+            policy: self.clone(),
+        })
+    }
+}
+
+pub struct CompressionPolicyMiddleware<S> {
+    service: Rc<S>,
+    policy: CompressionPolicy,
+}
+
+impl<S, B> Service<ServiceRequest> for CompressionPolicyMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    #[allow(clippy::type_complexity)]
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    fn poll_ready(&self, ctx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(ctx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let policy = self.policy.clone();
+        Box::pin(async move {
+            let mut response = service.call(req).await?;
+            // The responses with the content encoding set aren't touched by `Compress`.
+            if !response.headers().contains_key(CONTENT_ENCODING) {
+                let content_type = response
+                    .headers()
+                    .get(CONTENT_TYPE)
+                    .and_then(|value| value.to_str().ok());
+                let size = response.response().body().size();
+                if !policy.should_compress(content_type, size) {
+                    response
+                        .headers_mut()
+                        .insert(CONTENT_ENCODING, HeaderValue::from_static("identity"));
+                }
+            }
+            Ok(response)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(enabled: bool) -> CompressionPolicy {
+        CompressionPolicy {
+            inner: Rc::new(PolicyConfig {
+                enabled,
+                min_size: 1024,
+                content_types: vec!["application/json".to_owned()],
+            }),
+        }
+    }
+
+    #[test]
+    fn compression_policy() {
+        let disabled = policy(false);
+        let policy = policy(true);
+
+        assert!(policy.should_compress(Some("application/json"), BodySize::Sized(1024)));
+        assert!(policy.should_compress(
+            Some("Application/JSON; charset=utf-8"),
+            BodySize::Sized(4096)
+        ));
+        assert!(policy.should_compress(Some("application/json"), BodySize::Stream));
+        // The response is too small.
+        assert!(!policy.should_compress(Some("application/json"), BodySize::Sized(1023)));
+        assert!(!policy.should_compress(Some("application/json"), BodySize::Empty));
+        // The content type isn't allowed.
+        assert!(!policy.should_compress(Some("image/png"), BodySize::Sized(4096)));
+        assert!(!policy.should_compress(None, BodySize::Sized(4096)));
+
+        assert!(!disabled.should_compress(Some("application/json"), BodySize::Sized(4096)));
+    }
+}
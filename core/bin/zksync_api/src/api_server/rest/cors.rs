@@ -0,0 +1,60 @@
+//! CORS policies of the REST API.
+//!
+//! The public scopes accept the cross-origin requests from the configured origins without the
+//! credentials. The admin scope is the only one accepting the credentialed requests, and only
+//! from the explicitly listed origins, since the browsers don't send the credentials to the
+//! wildcard origin anyway.
+
+// External uses
+use actix_cors::Cors;
+
+// Workspace uses
+use zksync_config::configs::api::{AdminApiConfig, RestApiConfig};
+
+// Local uses
+use super::v02::response_signer::{SIGNATURE_HEADER, SIGNATURE_KEY_ID_HEADER};
+
+/// Origin which allows the cross-origin requests from any origin.
+const ANY_ORIGIN: &str = "*";
+
+fn configured_origins(origins: &[String]) -> impl Iterator<Item = &str> {
+    origins
+        .iter()
+        .map(|origin| origin.trim())
+        .filter(|origin| !origin.is_empty())
+}
+
+fn base_cors(config: &RestApiConfig) -> Cors {
+    Cors::default()
+        .allowed_methods(
+            config
+                .cors_allowed_methods
+                .iter()
+                .map(|method| method.trim()),
+        )
+        .allow_any_header()
+        .max_age(config.cors_max_age_sec)
+}
+
+/// CORS policy of the public scopes, the browser clients may read the signatures of the responses.
+pub fn public_cors(config: &RestApiConfig) -> Cors {
+    let cors = base_cors(config).expose_headers(vec![SIGNATURE_HEADER, SIGNATURE_KEY_ID_HEADER]);
+    let mut origins = configured_origins(&config.cors_allowed_origins);
+    if origins.any(|origin| origin == ANY_ORIGIN) {
+        return cors.allow_any_origin().send_wildcard();
+    }
+    configured_origins(&config.cors_allowed_origins).fold(cors, Cors::allowed_origin)
+}
+
+/// CORS policy of the admin scope, which accepts the credentialed requests.
+pub fn admin_cors(config: &RestApiConfig, admin_config: &AdminApiConfig) -> Cors {
+    configured_origins(&admin_config.cors_allowed_origins)
+        .filter(|origin| {
+            if *origin == ANY_ORIGIN {
+                vlog::warn!("Wildcard origin isn't allowed for the admin API, it's ignored");
+            }
+            *origin != ANY_ORIGIN
+        })
+        .fold(base_cors(config), Cors::allowed_origin)
+        .supports_credentials()
+}
@@ -90,6 +90,7 @@ pub enum SumbitErrorCode {
     Internal = 110,
     CommunicationCoreServer = 111,
     Other = 112,
+    ReadOnlyMode = 113,
 }
 
 impl SumbitErrorCode {
@@ -106,6 +107,7 @@ impl SumbitErrorCode {
             SubmitError::Other(_) => Self::Other,
             SubmitError::Toggle2FA(_) => Self::Other,
             SubmitError::PriceError(_) => Self::Other,
+            SubmitError::ReadOnlyMode(_) => Self::ReadOnlyMode,
         }
     }
 
@@ -118,10 +120,12 @@ impl From<SubmitError> for ApiError {
     fn from(inner: SubmitError) -> Self {
         let internal_code = SumbitErrorCode::from_err(&inner).as_code();
 
-        if let SubmitError::Internal(err) = &inner {
-            ApiError::internal(err)
-        } else {
-            ApiError::bad_request(inner)
+        match &inner {
+            SubmitError::Internal(err) => ApiError::internal(err),
+            SubmitError::ReadOnlyMode(_) => {
+                ApiError::with_code(StatusCode::SERVICE_UNAVAILABLE, inner)
+            }
+            _ => ApiError::bad_request(inner),
         }
         .code(internal_code)
     }
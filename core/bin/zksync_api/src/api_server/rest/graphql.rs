@@ -0,0 +1,406 @@
+//! GraphQL part of the REST API, used by the explorers to compose the account,
+//! its transactions and the token metadata in a single query.
+//!
+//! The resolvers are a thin layer over the storage accessors, the nested fields
+//! are loaded only if they are requested.
+
+// Built-in uses
+use std::str::FromStr;
+use std::time::Instant;
+
+// External uses
+use actix_web::{web, HttpResponse, Scope};
+use async_graphql::{
+    Context, EmptyMutation, EmptySubscription, Enum, Json, Object, Result, Schema, SimpleObject,
+};
+use chrono::{DateTime, Utc};
+
+// Workspace uses
+use zksync_api_types::v02::{
+    account::AccountAddressOrId,
+    pagination::{AccountTxsRequest, ApiEither, PaginationDirection, PaginationQuery, MAX_LIMIT},
+    transaction::{Transaction, TxInBlockStatus},
+};
+use zksync_crypto::params::MIN_NFT_TOKEN_ID;
+use zksync_storage::{ConnectionPool, StorageProcessor};
+use zksync_token_db_cache::TokenDBCache;
+use zksync_types::{tx::TxHash, AccountId, Address, Token, TokenLike};
+
+/// Maximum depth of the query, the schema has no recursive types deeper than that.
+const MAX_QUERY_DEPTH: usize = 8;
+/// Maximum complexity of the query, limits the number of the resolved fields.
+const MAX_QUERY_COMPLEXITY: usize = 1000;
+/// Number of the account transactions returned by default.
+const DEFAULT_TXS_LIMIT: u32 = 10;
+
+pub type ApiSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Shared data between the resolvers.
+#[derive(Debug, Clone)]
+struct ApiGraphqlData {
+    pool: ConnectionPool,
+    tokens: TokenDBCache,
+}
+
+impl ApiGraphqlData {
+    async fn access_storage(&self) -> Result<StorageProcessor<'_>> {
+        Ok(self.pool.access_storage().await?)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+enum TxStatus {
+    Scheduled,
+    Queued,
+    Committed,
+    Finalized,
+    Rejected,
+}
+
+impl From<TxInBlockStatus> for TxStatus {
+    fn from(status: TxInBlockStatus) -> Self {
+        match status {
+            TxInBlockStatus::Scheduled => Self::Scheduled,
+            TxInBlockStatus::Queued => Self::Queued,
+            TxInBlockStatus::Committed => Self::Committed,
+            TxInBlockStatus::Finalized => Self::Finalized,
+            TxInBlockStatus::Rejected => Self::Rejected,
+        }
+    }
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+#[graphql(name = "Token")]
+struct TokenObject {
+    id: u32,
+    address: String,
+    symbol: String,
+    decimals: u32,
+}
+
+impl From<Token> for TokenObject {
+    fn from(token: Token) -> Self {
+        Self {
+            id: *token.id,
+            address: format!("{:?}", token.address),
+            symbol: token.symbol,
+            decimals: token.decimals.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+struct Balance {
+    token: TokenObject,
+    /// Amount in the smallest units of the token, as the decimal string.
+    amount: String,
+}
+
+#[derive(Debug, SimpleObject)]
+#[graphql(name = "Transaction")]
+struct TransactionObject {
+    tx_hash: String,
+    block_number: Option<u32>,
+    block_index: Option<u32>,
+    status: TxStatus,
+    fail_reason: Option<String>,
+    created_at: Option<DateTime<Utc>>,
+    /// The operation in the same format as in the REST API.
+    op: Json<serde_json::Value>,
+}
+
+impl From<Transaction> for TransactionObject {
+    fn from(tx: Transaction) -> Self {
+        Self {
+            tx_hash: tx.tx_hash.to_string(),
+            block_number: tx.block_number.map(|block_number| *block_number),
+            block_index: tx.block_index,
+            status: tx.status.into(),
+            fail_reason: tx.fail_reason,
+            created_at: tx.created_at,
+            op: Json(serde_json::to_value(tx.op).unwrap_or_default()),
+        }
+    }
+}
+
+/// The committed state of the account.
+struct AccountObject {
+    id: AccountId,
+    account: zksync_types::Account,
+}
+
+#[Object(name = "Account")]
+impl AccountObject {
+    async fn id(&self) -> u32 {
+        *self.id
+    }
+
+    async fn address(&self) -> String {
+        format!("{:?}", self.account.address)
+    }
+
+    async fn nonce(&self) -> u32 {
+        *self.account.nonce
+    }
+
+    async fn pub_key_hash(&self) -> String {
+        self.account.pub_key_hash.as_hex()
+    }
+
+    /// Non-zero balances of the account, NFTs are not included.
+    async fn balances(&self, ctx: &Context<'_>) -> Result<Vec<Balance>> {
+        let data = ctx.data::<ApiGraphqlData>()?;
+        let mut storage = data.access_storage().await?;
+        let mut nonzero_balances: Vec<_> =
+            self.account.get_nonzero_balances().into_iter().collect();
+        nonzero_balances.sort_by_key(|(token_id, _)| *token_id);
+
+        let mut balances = Vec::new();
+        for (token_id, amount) in nonzero_balances {
+            if token_id.0 >= MIN_NFT_TOKEN_ID {
+                continue;
+            }
+            if let Some(token) = data.tokens.get_token(&mut storage, token_id).await? {
+                balances.push(Balance {
+                    token: token.into(),
+                    amount: amount.0.to_string(),
+                });
+            }
+        }
+        Ok(balances)
+    }
+
+    /// Transactions of the account starting from the given one, the newest first.
+    async fn transactions(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = "latest")] from: String,
+        #[graphql(default_with = "DEFAULT_TXS_LIMIT")] limit: u32,
+    ) -> Result<Vec<TransactionObject>> {
+        if limit > MAX_LIMIT {
+            return Err(format!(
+                "Limit for pagination should be less than or equal to {}",
+                MAX_LIMIT
+            )
+            .into());
+        }
+        let tx_hash = ApiEither::<TxHash>::from_str(&from)?;
+        let query = PaginationQuery {
+            from: AccountTxsRequest {
+                address: self.account.address,
+                tx_hash,
+                token: None,
+                second_address: None,
+                filters: Default::default(),
+            },
+            limit,
+            direction: PaginationDirection::Older,
+        };
+
+        let data = ctx.data::<ApiGraphqlData>()?;
+        let mut storage = data.access_storage().await?;
+        let txs = storage
+            .chain()
+            .operations_ext_schema()
+            .get_account_transactions(&query)
+            .await?
+            .ok_or("Transaction is not found")?;
+        Ok(txs.into_iter().map(TransactionObject::from).collect())
+    }
+}
+
+fn parse_account_id_or_address(account_id_or_address: &str) -> Result<AccountAddressOrId> {
+    if let Ok(account_id) = u32::from_str(account_id_or_address) {
+        return Ok(AccountAddressOrId::Id(AccountId(account_id)));
+    }
+    let address = account_id_or_address
+        .strip_prefix("0x")
+        .unwrap_or(account_id_or_address);
+    Address::from_str(address)
+        .map(AccountAddressOrId::Address)
+        .map_err(|_| "Cannot parse account id or address".into())
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// The committed state of the account by its id or address.
+    async fn account(
+        &self,
+        ctx: &Context<'_>,
+        id_or_address: String,
+    ) -> Result<Option<AccountObject>> {
+        let data = ctx.data::<ApiGraphqlData>()?;
+        let mut storage = data.access_storage().await?;
+        let account_id = match parse_account_id_or_address(&id_or_address)? {
+            AccountAddressOrId::Id(account_id) => Some(account_id),
+            AccountAddressOrId::Address(address) => {
+                storage
+                    .chain()
+                    .account_schema()
+                    .account_id_by_address(address)
+                    .await?
+            }
+        };
+        let account_id = match account_id {
+            Some(account_id) => account_id,
+            None => return Ok(None),
+        };
+
+        let (_, committed) = storage
+            .chain()
+            .account_schema()
+            .last_committed_state_for_account(account_id)
+            .await?;
+        Ok(committed.map(|account| AccountObject {
+            id: account_id,
+            account,
+        }))
+    }
+
+    /// The token by its id, address or symbol.
+    async fn token(&self, ctx: &Context<'_>, token: String) -> Result<Option<TokenObject>> {
+        let data = ctx.data::<ApiGraphqlData>()?;
+        let mut storage = data.access_storage().await?;
+        let token = data
+            .tokens
+            .get_token(&mut storage, TokenLike::parse(&token))
+            .await?;
+        Ok(token.map(TokenObject::from))
+    }
+
+    /// The executed transaction by its hash.
+    async fn transaction(
+        &self,
+        ctx: &Context<'_>,
+        tx_hash: String,
+    ) -> Result<Option<TransactionObject>> {
+        let tx_hash = TxHash::from_str(&tx_hash).map_err(|_| "Cannot parse transaction hash")?;
+        let data = ctx.data::<ApiGraphqlData>()?;
+        let mut storage = data.access_storage().await?;
+        let tx_data = storage
+            .chain()
+            .operations_ext_schema()
+            .tx_data_api_v02(tx_hash.as_ref())
+            .await?;
+        Ok(tx_data.map(|tx_data| tx_data.tx.into()))
+    }
+}
+
+fn schema(pool: ConnectionPool, tokens: TokenDBCache) -> ApiSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(ApiGraphqlData { pool, tokens })
+        .limit_depth(MAX_QUERY_DEPTH)
+        .limit_complexity(MAX_QUERY_COMPLEXITY)
+        .finish()
+}
+
+async fn graphql(
+    schema: web::Data<ApiSchema>,
+    request: web::Json<async_graphql::Request>,
+) -> HttpResponse {
+    let start = Instant::now();
+    let response = schema.execute(request.into_inner()).await;
+    metrics::histogram!("api", start.elapsed(), "type" => "graphql", "endpoint_name" => "graphql");
+    HttpResponse::Ok().json(response)
+}
+
+pub(crate) fn api_scope(pool: ConnectionPool, tokens: TokenDBCache) -> Scope {
+    web::scope("/api/graphql")
+        .app_data(web::Data::new(schema(pool, tokens)))
+        .route("", web::post().to(graphql))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rest::v02::test_utils::TestServerConfig;
+    use zksync_types::BlockNumber;
+
+    #[test]
+    fn account_id_or_address() {
+        assert_eq!(
+            parse_account_id_or_address("42").unwrap(),
+            AccountAddressOrId::Id(AccountId(42))
+        );
+        let address = Address::repeat_byte(0x12);
+        assert_eq!(
+            parse_account_id_or_address(&format!("{:?}", address)).unwrap(),
+            AccountAddressOrId::Address(address)
+        );
+        assert!(parse_account_id_or_address("0x12").is_err());
+    }
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn graphql_queries() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+        let schema = schema(
+            cfg.pool.clone(),
+            TokenDBCache::new(cfg.config.api.token_config.invalidate_token_cache_period()),
+        );
+
+        let (tx_hash, account_id) = {
+            let mut storage = cfg.pool.access_storage().await?;
+            let transactions = storage
+                .chain()
+                .block_schema()
+                .get_block_transactions(BlockNumber(1))
+                .await?;
+            let tx = &transactions[1];
+            let op = tx.op.as_object().unwrap();
+            let account_id: AccountId = if op.contains_key("accountId") {
+                serde_json::from_value(op["accountId"].clone()).unwrap()
+            } else {
+                serde_json::from_value(op["creatorId"].clone()).unwrap()
+            };
+            (tx.tx_hash.clone(), account_id)
+        };
+
+        let query = format!(
+            r#"{{
+                account(idOrAddress: "{}") {{
+                    id
+                    balances {{ token {{ symbol decimals }} amount }}
+                    transactions(limit: 5) {{ txHash status }}
+                }}
+                token(token: "1") {{ id symbol }}
+                transaction(txHash: "{}") {{ txHash blockNumber }}
+            }}"#,
+            account_id, tx_hash
+        );
+        let response = schema.execute(query.as_str()).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json()?;
+
+        assert_eq!(data["account"]["id"], *account_id);
+        let txs = data["account"]["transactions"].as_array().unwrap();
+        assert!(!txs.is_empty() && txs.len() <= 5);
+        assert_eq!(data["token"]["id"], 1);
+        assert_eq!(data["transaction"]["blockNumber"], 1);
+
+        // The missing entities are returned as `null`.
+        let response = schema
+            .execute(
+                r#"{ account(idOrAddress: "4294967295") { id } token(token: "UNKNOWN") { id } }"#,
+            )
+            .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json()?;
+        assert!(data["account"].is_null());
+        assert!(data["token"].is_null());
+
+        // The invalid arguments are reported as the errors.
+        let response = schema
+            .execute(r#"{ account(idOrAddress: "invalid") { id } }"#)
+            .await;
+        assert_eq!(response.errors.len(), 1);
+
+        Ok(())
+    }
+}
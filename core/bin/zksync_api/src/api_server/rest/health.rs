@@ -0,0 +1,274 @@
+//! Liveness and readiness probes of the REST API server.
+//!
+//! Both endpoints probe the dependencies of the server: the database, the fee ticker and
+//! the Ethereum node, each of them within `PROBE_TIMEOUT`, and report the status of every
+//! dependency. The dependency which has never been up since the server start is reported
+//! as booting rather than down, so that the orchestrators can tell the server which is
+//! still starting from the one whose dependency has failed.
+//!
+//! `/healthz` fails only if the database is down, since the server can't serve anything
+//! without it and should be restarted. `/readyz` fails until all the dependencies are up,
+//! so that the traffic is routed to the server only once it can handle it.
+
+// Built-in uses
+use std::collections::{BTreeMap, HashSet};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+// External uses
+use actix_web::{http::StatusCode, web, HttpResponse};
+use serde::Serialize;
+
+// Workspace uses
+use zksync_eth_client::EthereumGateway;
+use zksync_storage::ConnectionPool;
+use zksync_types::{TokenId, TokenLike};
+
+// Local uses
+use crate::fee_ticker::{FeeTicker, TokenPriceRequestType};
+
+/// How long every dependency is waited for before it's reported as down.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+const DATABASE: &str = "database";
+const FEE_TICKER: &str = "feeTicker";
+const ETHEREUM: &str = "ethereum";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DependencyStatus {
+    Up,
+    /// The dependency has never been up since the server start.
+    Booting,
+    Down,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyHealth {
+    pub status: DependencyStatus,
+    pub latency_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthReport {
+    pub dependencies: BTreeMap<&'static str, DependencyHealth>,
+}
+
+impl HealthReport {
+    fn status(&self, dependency: &str) -> Option<DependencyStatus> {
+        self.dependencies
+            .get(dependency)
+            .map(|dependency| dependency.status)
+    }
+
+    fn is_alive(&self) -> bool {
+        self.status(DATABASE) == Some(DependencyStatus::Up)
+    }
+
+    fn is_ready(&self) -> bool {
+        self.dependencies
+            .values()
+            .all(|dependency| dependency.status == DependencyStatus::Up)
+    }
+
+    fn into_response(self, healthy: bool) -> HttpResponse {
+        let status = if healthy {
+            StatusCode::OK
+        } else {
+            StatusCode::SERVICE_UNAVAILABLE
+        };
+        HttpResponse::build(status).json(self)
+    }
+}
+
+/// Shared data between the health endpoints, the clones share the started dependencies.
+#[derive(Clone)]
+pub(crate) struct HealthChecker {
+    pool: ConnectionPool,
+    fee_ticker: FeeTicker,
+    eth_gateway: EthereumGateway,
+    /// Dependencies which have been up since the server start.
+    started: Arc<Mutex<HashSet<&'static str>>>,
+}
+
+impl HealthChecker {
+    pub(crate) fn new(
+        pool: ConnectionPool,
+        fee_ticker: FeeTicker,
+        eth_gateway: EthereumGateway,
+    ) -> Self {
+        Self {
+            pool,
+            fee_ticker,
+            eth_gateway,
+            started: Default::default(),
+        }
+    }
+
+    async fn probe<F>(&self, dependency: &'static str, probe: F) -> DependencyHealth
+    where
+        F: Future<Output = anyhow::Result<()>>,
+    {
+        let start = Instant::now();
+        let result = match tokio::time::timeout(PROBE_TIMEOUT, probe).await {
+            Ok(result) => result,
+            Err(_) => Err(anyhow::anyhow!("no response in {:?}", PROBE_TIMEOUT)),
+        };
+        let latency = start.elapsed();
+        metrics::histogram!("api.health.probe", latency, "dependency" => dependency);
+
+        let mut started = self.started.lock().unwrap();
+        let health = dependency_health(result, started.contains(dependency), latency);
+        if health.status == DependencyStatus::Up {
+            started.insert(dependency);
+        } else {
+            vlog::warn!(
+                "Health probe of the {} failed: {}",
+                dependency,
+                health.error.as_deref().unwrap_or_default()
+            );
+        }
+        health
+    }
+
+    async fn probe_database(&self) -> anyhow::Result<()> {
+        self.pool.access_storage().await?.ping().await
+    }
+
+    async fn probe_fee_ticker(&self) -> anyhow::Result<()> {
+        self.fee_ticker
+            .get_token_price(
+                TokenLike::Id(TokenId(0)),
+                TokenPriceRequestType::USDForOneToken,
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn probe_ethereum(&self) -> anyhow::Result<()> {
+        self.eth_gateway.block_number().await?;
+        Ok(())
+    }
+
+    async fn report(&self) -> HealthReport {
+        let (database, fee_ticker, ethereum) = futures::join!(
+            self.probe(DATABASE, self.probe_database()),
+            self.probe(FEE_TICKER, self.probe_fee_ticker()),
+            self.probe(ETHEREUM, self.probe_ethereum()),
+        );
+        let mut dependencies = BTreeMap::new();
+        dependencies.insert(DATABASE, database);
+        dependencies.insert(FEE_TICKER, fee_ticker);
+        dependencies.insert(ETHEREUM, ethereum);
+        HealthReport { dependencies }
+    }
+}
+
+fn dependency_health(
+    result: anyhow::Result<()>,
+    was_up: bool,
+    latency: Duration,
+) -> DependencyHealth {
+    let latency_ms = latency.as_millis() as u64;
+    match result {
+        Ok(()) => DependencyHealth {
+            status: DependencyStatus::Up,
+            latency_ms,
+            error: None,
+        },
+        Err(err) => DependencyHealth {
+            status: if was_up {
+                DependencyStatus::Down
+            } else {
+                DependencyStatus::Booting
+            },
+            latency_ms,
+            error: Some(err.to_string()),
+        },
+    }
+}
+
+pub(crate) async fn healthz(data: web::Data<HealthChecker>) -> HttpResponse {
+    let report = data.report().await;
+    let alive = report.is_alive();
+    report.into_response(alive)
+}
+
+pub(crate) async fn readyz(data: web::Data<HealthChecker>) -> HttpResponse {
+    let report = data.report().await;
+    let ready = report.is_ready();
+    report.into_response(ready)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(statuses: &[(&'static str, DependencyStatus)]) -> HealthReport {
+        let dependencies = statuses
+            .iter()
+            .map(|&(dependency, status)| {
+                let health = DependencyHealth {
+                    status,
+                    latency_ms: 1,
+                    error: None,
+                };
+                (dependency, health)
+            })
+            .collect();
+        HealthReport { dependencies }
+    }
+
+    #[test]
+    fn dependency_statuses() {
+        let latency = Duration::from_millis(5);
+        let up = dependency_health(Ok(()), false, latency);
+        assert_eq!(up.status, DependencyStatus::Up);
+        assert_eq!(up.latency_ms, 5);
+        assert_eq!(up.error, None);
+
+        let booting = dependency_health(Err(anyhow::anyhow!("refused")), false, latency);
+        assert_eq!(booting.status, DependencyStatus::Booting);
+        assert_eq!(booting.error.as_deref(), Some("refused"));
+        let down = dependency_health(Err(anyhow::anyhow!("refused")), true, latency);
+        assert_eq!(down.status, DependencyStatus::Down);
+    }
+
+    #[test]
+    fn liveness_and_readiness() {
+        let all_up = report(&[
+            (DATABASE, DependencyStatus::Up),
+            (FEE_TICKER, DependencyStatus::Up),
+            (ETHEREUM, DependencyStatus::Up),
+        ]);
+        assert!(all_up.is_alive());
+        assert!(all_up.is_ready());
+
+        let ethereum_booting = report(&[
+            (DATABASE, DependencyStatus::Up),
+            (FEE_TICKER, DependencyStatus::Up),
+            (ETHEREUM, DependencyStatus::Booting),
+        ]);
+        assert!(ethereum_booting.is_alive());
+        assert!(!ethereum_booting.is_ready());
+
+        let database_down = report(&[
+            (DATABASE, DependencyStatus::Down),
+            (FEE_TICKER, DependencyStatus::Up),
+            (ETHEREUM, DependencyStatus::Up),
+        ]);
+        assert!(!database_down.is_alive());
+        assert!(!database_down.is_ready());
+
+        let json = serde_json::to_value(&ethereum_booting).unwrap();
+        assert_eq!(
+            json["dependencies"]["ethereum"],
+            serde_json::json!({ "status": "booting", "latencyMs": 1 })
+        );
+    }
+}
@@ -1,8 +1,10 @@
-use actix_cors::Cors;
-use actix_web::{web, App, HttpResponse, HttpServer};
+use actix::Actor;
+use actix_web::{middleware::Compress, web, App, HttpResponse, HttpServer, Scope};
 use futures::channel::mpsc;
 use std::net::SocketAddr;
+use std::time::Duration;
 use zksync_storage::ConnectionPool;
+use zksync_token_db_cache::TokenDBCache;
 use zksync_types::{SequentialTxId, H160};
 
 use zksync_utils::panic_notify::{spawn_panic_handler, ThreadPanicNotify};
@@ -10,28 +12,101 @@ use zksync_utils::panic_notify::{spawn_panic_handler, ThreadPanicNotify};
 use self::v01::api_decl::ApiV01;
 use crate::signature_checker::VerifySignatureRequest;
 
-use super::tx_sender::TxSender;
+use super::{config_reload::ConfigReloader, tx_sender::TxSender};
 
 use crate::api_server::rest::network_status::SharedNetworkStatus;
 use crate::fee_ticker::FeeTicker;
-use tokio::task::JoinHandle;
+use tokio::{sync::watch, task::JoinHandle};
 use zksync_config::ZkSyncConfig;
+use zksync_eth_client::EthereumGateway;
 use zksync_mempool::MempoolTransactionRequest;
 
+mod admin;
+mod compression;
+mod cors;
 mod forced_exit_requests;
+mod graphql;
+mod health;
 mod helpers;
 pub mod network_status;
+mod rate_limit;
+mod request_id;
+mod request_metrics;
 mod v01;
 pub mod v02;
 
+/// How often the outdated rate limiting counters and idempotency keys are removed,
+/// and the usage of the API keys is saved.
+const MAINTENANCE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Wraps the scope with the middlewares applied to all the requests. The CORS policy is the
+/// outermost one, so the preflights aren't rate limited and the rejected requests carry the
+/// CORS headers as well.
+macro_rules! with_middlewares {
+    ($scope:expr, $cors:expr, $rate_limit:expr) => {
+        $scope
+            .wrap($rate_limit.clone())
+            .wrap(request_metrics::RequestMetrics)
+            // The rejected requests are traced as well.
+            .wrap(request_id::RequestTracing)
+            .wrap($cors)
+    };
+}
+
+#[allow(clippy::too_many_arguments)]
 async fn start_server(
     api_v01: ApiV01,
     fee_ticker: FeeTicker,
+    eth_gateway: EthereumGateway,
     sign_verifier: mpsc::Sender<VerifySignatureRequest>,
     bind_to: SocketAddr,
     mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
+    config_reloader: ConfigReloader,
+    mut shutdown: watch::Receiver<bool>,
 ) {
-    HttpServer::new(move || {
+    // The manager is shared between the workers, so the ticker is polled only once.
+    let price_subscriptions =
+        v02::price_subscription::PriceSubscriptionManager::new(fee_ticker.clone()).start();
+    let event_stream =
+        v02::event_stream::EventStreamManager::new(api_v01.connection_pool.clone()).start();
+    // The limits are shared between the workers as well.
+    // The usage of the API keys is saved, so the main database is used.
+    let rate_limit = rate_limit::RateLimit::new(
+        api_v01.main_database_connection_pool.clone(),
+        config_reloader.subscribe(),
+    );
+    // The responses of the submissions are stored in the main database as well.
+    let idempotency_keys = v02::idempotency::IdempotencyKeys::new(
+        api_v01.main_database_connection_pool.clone(),
+        api_v01.config.api.common.idempotency_key_window(),
+    );
+    actix_rt::spawn({
+        let rate_limit = rate_limit.clone();
+        let idempotency_keys = idempotency_keys.clone();
+        async move {
+            let mut timer = tokio::time::interval(MAINTENANCE_INTERVAL);
+            loop {
+                timer.tick().await;
+                rate_limit.evict_expired();
+                rate_limit.save_usage().await;
+                if let Err(err) = idempotency_keys.remove_expired().await {
+                    vlog::warn!("Can't remove the expired idempotency keys: {}", err);
+                }
+            }
+        }
+    });
+
+    // The dependencies which have been up are shared between the workers as well.
+    let health_checker = health::HealthChecker::new(
+        api_v01.connection_pool.clone(),
+        fee_ticker.clone(),
+        eth_gateway,
+    );
+
+    let metrics_endpoint = api_v01.config.api.rest.metrics_endpoint;
+    let shutdown_timeout = api_v01.config.api.rest.shutdown_timeout();
+
+    let server = HttpServer::new(move || {
         let api_v01 = api_v01.clone();
         // This api stores forced exit requests, it's necessary to use main database connection
         let forced_exit_requests_api_scope = forced_exit_requests::api_scope(
@@ -44,6 +119,23 @@ async fn start_server(
             &api_v01.config.forced_exit_requests,
             api_v01.config.contracts.forced_exit_addr,
         );
+        // The token cache is shared with the admin API, so the tokens changed by it are invalidated.
+        let token_db_cache = TokenDBCache::new(
+            api_v01
+                .config
+                .api
+                .token_config
+                .invalidate_token_cache_period(),
+        );
+        // The API keys are stored in the main database as well.
+        let admin_api_scope = admin::api_scope(
+            api_v01.main_database_connection_pool.clone(),
+            token_db_cache.clone(),
+            &api_v01.config.api.admin,
+            config_reloader.clone(),
+            api_v01.config.prover.core.gone_timeout(),
+        );
+        let graphql_api_scope = graphql::api_scope(api_v01.connection_pool.clone(), token_db_cache);
 
         let api_v02_scope = {
             let tx_sender = TxSender::new(
@@ -54,38 +146,99 @@ async fn start_server(
                 &api_v01.config.api.token_config,
                 mempool_tx_sender.clone(),
             );
-            v02::api_scope(tx_sender, &api_v01.config, api_v01.network_status.clone())
+            v02::api_scope(
+                tx_sender,
+                &api_v01.config,
+                api_v01.network_status.clone(),
+                price_subscriptions.clone(),
+                event_stream.clone(),
+                idempotency_keys.clone(),
+                &config_reloader,
+            )
         };
+        let root_scope =
+            root_scope(metrics_endpoint).app_data(web::Data::new(health_checker.clone()));
+        let compression_policy = compression::CompressionPolicy::new(&api_v01.config.api.rest);
+        // The admin scope has its own CORS policy accepting the credentialed requests,
+        // so the policies are applied to the scopes rather than to the whole application.
+        let rest_config = api_v01.config.api.rest.clone();
+        let public_cors = || cors::public_cors(&rest_config);
+        let admin_cors = cors::admin_cors(&rest_config, &api_v01.config.api.admin);
         App::new()
-            .wrap(
-                Cors::default()
-                    .send_wildcard()
-                    .max_age(3600)
-                    .allow_any_origin()
-                    .allow_any_header()
-                    .allow_any_method(),
-            )
-            .service(api_v01.into_scope())
-            .service(forced_exit_requests_api_scope)
-            .service(api_v02_scope)
-            // Endpoint needed for js isReachable
-            .route(
-                "/favicon.ico",
-                web::get().to(|| HttpResponse::Ok().finish()),
-            )
+            // `Compress` must wrap the policy which marks the responses that shouldn't be compressed,
+            // nothing is compressed if the compression is disabled.
+            .wrap(compression_policy)
+            .wrap(Compress::default())
+            .service(with_middlewares!(
+                api_v01.into_scope(),
+                public_cors(),
+                rate_limit
+            ))
+            .service(with_middlewares!(
+                forced_exit_requests_api_scope,
+                public_cors(),
+                rate_limit
+            ))
+            .service(with_middlewares!(admin_api_scope, admin_cors, rate_limit))
+            .service(with_middlewares!(
+                graphql_api_scope,
+                public_cors(),
+                rate_limit
+            ))
+            .service(with_middlewares!(api_v02_scope, public_cors(), rate_limit))
+            // The root scope matches any path, so it goes last.
+            .service(with_middlewares!(root_scope, public_cors(), rate_limit))
     })
     .workers(super::THREADS_PER_SERVER)
     .bind(bind_to)
     .unwrap()
-    .shutdown_timeout(60)
+    .shutdown_timeout(shutdown_timeout.as_secs())
     .keep_alive(10)
     .client_timeout(60000)
-    .run()
-    .await
-    .expect("REST API server has crashed");
+    // The server is stopped along with the other components rather than by the signals.
+    .disable_signals()
+    .run();
+
+    actix_rt::spawn({
+        let server = server.clone();
+        async move {
+            while !*shutdown.borrow() {
+                if shutdown.changed().await.is_err() {
+                    // The process is not stopped gracefully, so there is nothing to wait for.
+                    return;
+                }
+            }
+            vlog::info!("Stopping the REST API server, draining the in-flight requests");
+            // The server stops accepting the new connections and waits for the
+            // in-flight requests for `shutdown_timeout` at most.
+            server.stop(true).await;
+            vlog::info!("REST API server is stopped");
+        }
+    });
+    server.await.expect("REST API server has crashed");
+}
+
+/// Scope of the routes at the root of the server.
+fn root_scope(metrics_endpoint: bool) -> Scope {
+    web::scope("")
+        .route("/healthz", web::get().to(health::healthz))
+        .route("/readyz", web::get().to(health::readyz))
+        .configure(|cfg| {
+            if metrics_endpoint {
+                cfg.route("/metrics", web::get().to(request_metrics::metrics_endpoint));
+            }
+        })
+        // Endpoint needed for js isReachable
+        .route(
+            "/favicon.ico",
+            web::get().to(|| HttpResponse::Ok().finish()),
+        )
 }
 
 /// Start HTTP REST API
+///
+/// Once `true` is sent to the `shutdown` channel, the server stops accepting the new connections
+/// and drains the in-flight requests, the receiver is dropped when the server is stopped.
 #[allow(clippy::too_many_arguments)]
 #[must_use]
 pub fn start_server_thread_detached(
@@ -94,9 +247,12 @@ pub fn start_server_thread_detached(
     listen_addr: SocketAddr,
     contract_address: H160,
     fee_ticker: FeeTicker,
+    eth_gateway: EthereumGateway,
     sign_verifier: mpsc::Sender<VerifySignatureRequest>,
     mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
     core_address: String,
+    config_reloader: ConfigReloader,
+    shutdown: watch::Receiver<bool>,
 ) -> JoinHandle<()> {
     let (handler, panic_sender) = spawn_panic_handler();
 
@@ -130,9 +286,12 @@ pub fn start_server_thread_detached(
                 start_server(
                     api_v01,
                     fee_ticker,
+                    eth_gateway,
                     sign_verifier,
                     listen_addr,
                     mempool_tx_sender.clone(),
+                    config_reloader,
+                    shutdown,
                 )
                 .await;
             });
@@ -140,3 +299,85 @@ pub fn start_server_thread_detached(
         .expect("Api server thread");
     handler
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{
+        dev::ServiceResponse,
+        http::{header, HeaderValue, Method, StatusCode},
+        test::{self, TestRequest},
+    };
+    use zksync_config::{configs::api::RestApiConfig, ReloadableConfig};
+
+    fn rest_config() -> RestApiConfig {
+        RestApiConfig {
+            port: 3001,
+            url: "http://127.0.0.1:3001".into(),
+            pagination_cursor_secret: "secret".into(),
+            requests_per_second_per_ip: 1,
+            metrics_endpoint: false,
+            shutdown_timeout_sec: 5,
+            compression_enabled: false,
+            compression_min_size: 1024,
+            compression_content_types: Vec::new(),
+            cors_allowed_origins: vec!["*".into()],
+            cors_allowed_methods: vec!["GET".into(), "POST".into()],
+            cors_max_age_sec: 3600,
+            response_signing_key: None,
+        }
+    }
+
+    /// Checks that the rate limited requests and the root routes carry the CORS headers,
+    /// and the preflights aren't counted against the limits.
+    #[actix_rt::test]
+    async fn cors_wraps_rate_limit() {
+        let config = rest_config();
+        // The connections are established on demand, the test never reaches the database.
+        let url = "postgres://postgres@localhost/unreachable";
+        let (_config_sender, config_updates) = watch::channel(ReloadableConfig {
+            liquidity_volume: 0.0,
+            requests_per_second_per_ip: config.requests_per_second_per_ip,
+            denylisted_tokens: Vec::new(),
+        });
+        let rate_limit =
+            rate_limit::RateLimit::new(ConnectionPool::with_replica(url, url, 1), config_updates);
+        let app = test::init_service(App::new().service(with_middlewares!(
+            root_scope(false),
+            cors::public_cors(&config),
+            rate_limit
+        )))
+        .await;
+
+        let request = |method: Method| {
+            TestRequest::default()
+                .method(method)
+                .uri("/favicon.ico")
+                .insert_header((header::ORIGIN, "https://wallet.zksync.io"))
+                .insert_header(("CF-Connecting-IP", "127.0.0.1"))
+        };
+        fn allowed_origin<B>(response: &ServiceResponse<B>) -> Option<HeaderValue> {
+            response
+                .headers()
+                .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+                .cloned()
+        }
+
+        let response = test::call_service(&app, request(Method::GET).to_request()).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(allowed_origin(&response).unwrap(), "*");
+
+        // The preflight isn't counted, since CORS responds to it before the rate limit.
+        let preflight = request(Method::OPTIONS)
+            .insert_header((header::ACCESS_CONTROL_REQUEST_METHOD, "GET"))
+            .to_request();
+        let response = test::call_service(&app, preflight).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(allowed_origin(&response).is_some());
+
+        // The limit of the IP is exhausted, the browser still can read the rejection.
+        let response = test::call_service(&app, request(Method::GET).to_request()).await;
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(allowed_origin(&response).unwrap(), "*");
+    }
+}
@@ -0,0 +1,443 @@
+//! Rate limiting of the REST API requests.
+//!
+//! The requests are counted per client IP in the fixed one-second windows. The clients
+//! which need more requests can be given the API keys stored in the database, the requests
+//! with a known key are counted per key and limited by the quota of the key instead.
+//!
+//! The identity of the key is attached to the request, and the number of the requests made
+//! with each key is periodically saved to the database for the usage accounting.
+//!
+//! The quota of the key which isn't cached yet is loaded only if the request fits into the limit
+//! of its IP, so the requests with the random keys can't query the database more often than the
+//! clients without a key are allowed to make requests. The unknown keys are cached as well.
+//!
+//! The limit of the requests per IP is taken from the reloaded config, so it can be tuned
+//! without restarting the server.
+
+// Built-in uses
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+// External uses
+use actix_web::{
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    error::ErrorTooManyRequests,
+    Error, HttpMessage,
+};
+use dashmap::DashMap;
+use futures::future::{ok, Ready};
+use tokio::sync::watch;
+
+// Workspace uses
+use zksync_config::ReloadableConfig;
+use zksync_storage::ConnectionPool;
+
+// Local uses
+use crate::utils::{
+    clock::{Clock, SystemClock},
+    ttl_cache::TtlCache,
+};
+
+/// Header with the API key of the client.
+const API_KEY_HEADER: &str = "X-API-Key";
+/// Header with the IP of the client set by Cloudflare, users can never set it by themselves.
+const CLOUDFLARE_CONNECTING_IP_HEADER: &str = "CF-Connecting-IP";
+
+/// Duration of the window in which the requests are counted.
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(1);
+/// How long the quotas of the API keys are cached, so that the database
+/// is not queried on every request.
+const API_KEY_QUOTA_CACHE_TTL: Duration = Duration::from_secs(60);
+/// Maximum number of the cached quotas, the unknown keys aren't cached beyond it.
+const MAX_CACHED_API_KEYS: usize = 10_000;
+
+/// The API key of the client, inserted into the request extensions
+/// if the request has a valid key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ApiKeyIdentity(pub String);
+
+/// The request exceeds the limit of the client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TooManyRequests;
+
+/// The entity the requests are counted for.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum RateLimitedClient {
+    Ip(String),
+    ApiKey(String),
+}
+
+/// Counts the requests of the clients in the fixed windows.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    /// The start of the current window and the number of requests made in it.
+    windows: Arc<DashMap<RateLimitedClient, (Instant, u32)>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl RateLimiter {
+    pub fn new() -> Self {
+        Self::with_clock(Arc::new(SystemClock))
+    }
+
+    pub fn with_clock(clock: Arc<dyn Clock>) -> Self {
+        Self {
+            windows: Arc::new(DashMap::new()),
+            clock,
+        }
+    }
+
+    /// Registers the request of the client, returns `false` if the client
+    /// has already made `limit` requests in the current window.
+    fn try_acquire(&self, client: RateLimitedClient, limit: u32) -> bool {
+        let now = self.clock.now();
+        let mut window = self.windows.entry(client).or_insert((now, 0));
+        let (started_at, count) = window.value_mut();
+        if now.saturating_duration_since(*started_at) >= RATE_LIMIT_WINDOW {
+            *started_at = now;
+            *count = 0;
+        }
+
+        if *count >= limit {
+            false
+        } else {
+            *count += 1;
+            true
+        }
+    }
+
+    /// Removes the windows of the clients which haven't made any requests recently.
+    pub fn evict_expired(&self) {
+        let clock = &self.clock;
+        self.windows
+            .retain(|_, (started_at, _)| clock.elapsed(*started_at) < RATE_LIMIT_WINDOW);
+    }
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Middleware that rejects the requests exceeding the limit of the client
+/// with the `429 Too Many Requests` error.
+#[derive(Debug, Clone)]
+pub struct RateLimit {
+    pool: ConnectionPool,
+    config_updates: watch::Receiver<ReloadableConfig>,
+    limiter: RateLimiter,
+    /// Quotas of the API keys, `None` for the unknown keys.
+    api_key_quotas: TtlCache<String, Option<u32>>,
+    /// Number of the requests made with each API key since the last save.
+    api_key_usage: Arc<DashMap<String, u64>>,
+}
+
+impl RateLimit {
+    pub fn new(pool: ConnectionPool, config_updates: watch::Receiver<ReloadableConfig>) -> Self {
+        Self {
+            pool,
+            config_updates,
+            limiter: RateLimiter::new(),
+            api_key_quotas: TtlCache::new(API_KEY_QUOTA_CACHE_TTL),
+            api_key_usage: Arc::new(DashMap::new()),
+        }
+    }
+
+    fn requests_per_second_per_ip(&self) -> u32 {
+        self.config_updates.borrow().requests_per_second_per_ip
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.requests_per_second_per_ip() > 0
+    }
+
+    /// Removes the outdated request counters and API key quotas.
+    pub fn evict_expired(&self) {
+        self.limiter.evict_expired();
+        self.api_key_quotas.evict_expired();
+    }
+
+    fn record_usage(&self, identity: &ApiKeyIdentity) {
+        *self.api_key_usage.entry(identity.0.clone()).or_insert(0) += 1;
+    }
+
+    /// Saves the number of the requests made with each API key since the last save.
+    pub async fn save_usage(&self) {
+        let api_keys = self
+            .api_key_usage
+            .iter()
+            .map(|entry| entry.key().clone())
+            .collect::<Vec<_>>();
+        if api_keys.is_empty() {
+            return;
+        }
+
+        let mut storage = match self.pool.access_storage().await {
+            Ok(storage) => storage,
+            Err(err) => {
+                vlog::warn!("Can't save the API key usage: {}", err);
+                return;
+            }
+        };
+        for api_key in api_keys {
+            let (api_key, requests) = match self.api_key_usage.remove(&api_key) {
+                Some(usage) => usage,
+                None => continue,
+            };
+            let result = storage
+                .api_keys_schema()
+                .record_api_key_usage(&api_key, requests)
+                .await;
+            if let Err(err) = result {
+                vlog::warn!("Can't save the API key usage: {}", err);
+                // The requests will be saved next time.
+                *self.api_key_usage.entry(api_key).or_insert(0) += requests;
+            }
+        }
+    }
+
+    async fn load_api_key_quota(&self, api_key: &str) -> Option<u32> {
+        let quota = match self.pool.access_storage().await {
+            Ok(mut storage) => storage.api_keys_schema().get_api_key_quota(api_key).await,
+            Err(err) => Err(err.into()),
+        };
+        match quota {
+            Ok(quota) => {
+                // The flood of the random keys can't grow the cache unboundedly.
+                if quota.is_some() || self.api_key_quotas.len() < MAX_CACHED_API_KEYS {
+                    self.api_key_quotas.insert(api_key.to_string(), quota);
+                }
+                quota
+            }
+            // The client is limited as the one without a key until the database is available.
+            Err(err) => {
+                vlog::warn!("Can't load the API key quota: {}", err);
+                None
+            }
+        }
+    }
+
+    /// Registers the request of the IP, returns `false` if it exceeds the limit.
+    fn check_ip(&self, ip: Option<String>) -> bool {
+        match ip {
+            Some(ip) => self
+                .limiter
+                .try_acquire(RateLimitedClient::Ip(ip), self.requests_per_second_per_ip()),
+            // There is nothing to count the requests for.
+            None => true,
+        }
+    }
+
+    /// Registers the request, returns the identity of its API key if the key is valid.
+    /// The requests with the valid keys are limited by the quota of the key, the other
+    /// ones by the limit of the IP.
+    async fn admit(
+        &self,
+        ip: Option<String>,
+        api_key: Option<String>,
+    ) -> Result<Option<ApiKeyIdentity>, TooManyRequests> {
+        let enabled = self.is_enabled();
+        let mut ip_checked = false;
+        let quota = match &api_key {
+            Some(api_key) => match self.api_key_quotas.get(api_key) {
+                Some(quota) => quota,
+                None => {
+                    // The lookup of the key is paid by the limit of the IP.
+                    if enabled && !self.check_ip(ip.clone()) {
+                        return Err(TooManyRequests);
+                    }
+                    ip_checked = true;
+                    self.load_api_key_quota(api_key).await
+                }
+            },
+            None => None,
+        };
+
+        match (api_key, quota) {
+            (Some(api_key), Some(quota)) => {
+                let client = RateLimitedClient::ApiKey(api_key.clone());
+                if enabled && !self.limiter.try_acquire(client, quota) {
+                    return Err(TooManyRequests);
+                }
+                Ok(Some(ApiKeyIdentity(api_key)))
+            }
+            _ => {
+                if enabled && !ip_checked && !self.check_ip(ip) {
+                    return Err(TooManyRequests);
+                }
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// Returns the IP of the client, the one reported by Cloudflare takes precedence
+/// over the address of the peer, which is the proxy in that case.
+fn client_ip(req: &ServiceRequest) -> Option<String> {
+    req.headers()
+        .get(CLOUDFLARE_CONNECTING_IP_HEADER)
+        .and_then(|ip| ip.to_str().ok())
+        .map(str::to_owned)
+        .or_else(|| req.peer_addr().map(|addr| addr.ip().to_string()))
+}
+
+fn api_key(req: &ServiceRequest) -> Option<String> {
+    req.headers()
+        .get(API_KEY_HEADER)
+        .and_then(|api_key| api_key.to_str().ok())
+        .map(str::to_owned)
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RateLimit
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = RateLimitMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(RateLimitMiddleware {
+            service: Rc::new(service),
+            rate_limit: self.clone(),
+        })
+    }
+}
+
+pub struct RateLimitMiddleware<S> {
+    service: Rc<S>,
+    rate_limit: RateLimit,
+}
+
+impl<S, B> Service<ServiceRequest> for RateLimitMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    #[allow(clippy::type_complexity)]
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    fn poll_ready(&self, ctx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(ctx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let rate_limit = self.rate_limit.clone();
+        Box::pin(async move {
+            let identity = match rate_limit.admit(client_ip(&req), api_key(&req)).await {
+                Ok(identity) => identity,
+                Err(TooManyRequests) => {
+                    metrics::increment_counter!("api.rest.rate_limited_requests");
+                    return Err(ErrorTooManyRequests("Too many requests"));
+                }
+            };
+
+            if let Some(identity) = identity {
+                rate_limit.record_usage(&identity);
+                req.extensions_mut().insert(identity);
+            }
+            service.call(req).await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::clock::MockClock;
+
+    fn rate_limit(requests_per_second_per_ip: u32) -> (RateLimit, watch::Sender<ReloadableConfig>) {
+        // The connections are established on demand, the tests never reach the database.
+        let url = "postgres://postgres@localhost/unreachable";
+        let pool = ConnectionPool::with_replica(url, url, 1);
+        let (sender, receiver) = watch::channel(ReloadableConfig {
+            liquidity_volume: 0.0,
+            requests_per_second_per_ip,
+            denylisted_tokens: Vec::new(),
+        });
+        (RateLimit::new(pool, receiver), sender)
+    }
+
+    #[test]
+    fn requests_are_limited_per_window() {
+        let clock = MockClock::default();
+        let limiter = RateLimiter::with_clock(Arc::new(clock.clone()));
+        let client = RateLimitedClient::Ip("127.0.0.1".to_string());
+
+        for _ in 0..3 {
+            assert!(limiter.try_acquire(client.clone(), 3));
+        }
+        assert!(!limiter.try_acquire(client.clone(), 3));
+        // The other clients are counted separately.
+        assert!(limiter.try_acquire(RateLimitedClient::Ip("127.0.0.2".to_string()), 3));
+        assert!(limiter.try_acquire(RateLimitedClient::ApiKey("127.0.0.1".to_string()), 3));
+
+        clock.advance(RATE_LIMIT_WINDOW);
+        assert!(limiter.try_acquire(client.clone(), 3));
+
+        // Only the windows of the recent clients are kept.
+        clock.advance(RATE_LIMIT_WINDOW / 2);
+        assert!(limiter.try_acquire(RateLimitedClient::Ip("127.0.0.3".to_string()), 3));
+        clock.advance(RATE_LIMIT_WINDOW / 2);
+        limiter.evict_expired();
+        assert_eq!(limiter.windows.len(), 1);
+    }
+
+    /// Checks that the unknown API keys are checked against the limit of the IP
+    /// before their quotas are loaded.
+    #[tokio::test]
+    async fn unknown_api_keys_are_limited_per_ip() {
+        let (rate_limit, _config) = rate_limit(2);
+        let ip = || Some("127.0.0.1".to_string());
+        rate_limit
+            .api_key_quotas
+            .insert("valid".to_string(), Some(3));
+        rate_limit
+            .api_key_quotas
+            .insert("unknown".to_string(), None);
+
+        assert_eq!(
+            rate_limit.admit(ip(), Some("valid".to_string())).await,
+            Ok(Some(ApiKeyIdentity("valid".to_string())))
+        );
+        // The cached unknown key is counted as the request of the IP.
+        assert_eq!(
+            rate_limit.admit(ip(), Some("unknown".to_string())).await,
+            Ok(None)
+        );
+        assert_eq!(rate_limit.admit(ip(), None).await, Ok(None));
+        // The limit of the IP is exhausted, so the key isn't loaded from the database.
+        assert_eq!(
+            rate_limit.admit(ip(), Some("random".to_string())).await,
+            Err(TooManyRequests)
+        );
+        assert!(rate_limit
+            .api_key_quotas
+            .get(&"random".to_string())
+            .is_none());
+        // The valid keys are limited by their quotas.
+        for _ in 0..2 {
+            assert!(rate_limit
+                .admit(ip(), Some("valid".to_string()))
+                .await
+                .is_ok());
+        }
+        assert_eq!(
+            rate_limit.admit(ip(), Some("valid".to_string())).await,
+            Err(TooManyRequests)
+        );
+    }
+}
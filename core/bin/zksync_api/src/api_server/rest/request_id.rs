@@ -0,0 +1,172 @@
+//! Tracing of the REST API requests.
+//!
+//! Every request is given an ID, either the one set by the client in the `X-Request-Id` header
+//! or a random one, which is returned in the same header of the response. The request is handled
+//! within the `api_request` span with this ID, so the spans of the fee ticker and the storage
+//! calls made while handling it, as well as all the logged events, are attributed to the request.
+
+// Built-in uses
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+// External uses
+use actix_web::{
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    http::{HeaderName, HeaderValue},
+    Error, HttpMessage,
+};
+use futures::future::{ok, Ready};
+use tracing::Instrument;
+
+// Workspace uses
+use zksync_crypto::rand::{thread_rng, Rng};
+
+/// Header with the ID of the request.
+pub const REQUEST_ID_HEADER: &str = "X-Request-Id";
+/// Maximum length of the request ID set by the client.
+const MAX_REQUEST_ID_LENGTH: usize = 64;
+/// The requests handled longer than this are logged.
+const SLOW_REQUEST_THRESHOLD: Duration = Duration::from_secs(1);
+
+/// The ID of the request, inserted into the request extensions.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RequestId(pub String);
+
+impl RequestId {
+    fn random() -> Self {
+        let bytes: [u8; 16] = thread_rng().gen();
+        Self(hex::encode(bytes))
+    }
+
+    /// Returns the ID set by the client, if it's valid.
+    fn from_request(req: &ServiceRequest) -> Option<Self> {
+        let id = req.headers().get(REQUEST_ID_HEADER)?.to_str().ok()?;
+        let is_valid = !id.is_empty()
+            && id.len() <= MAX_REQUEST_ID_LENGTH
+            && id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.');
+        if is_valid {
+            Some(Self(id.to_string()))
+        } else {
+            None
+        }
+    }
+}
+
+/// Middleware that assigns the IDs to the requests and handles them within their spans.
+#[derive(Debug, Clone, Default)]
+pub struct RequestTracing;
+
+impl<S, B> Transform<S, ServiceRequest> for RequestTracing
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = RequestTracingMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(RequestTracingMiddleware {
+            service: Rc::new(service),
+        })
+    }
+}
+
+pub struct RequestTracingMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for RequestTracingMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    #[allow(clippy::type_complexity)]
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    fn poll_ready(&self, ctx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(ctx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let request_id = RequestId::from_request(&req).unwrap_or_else(RequestId::random);
+        let span = tracing::info_span!(
+            "api_request",
+            request_id = %request_id.0,
+            method = %req.method(),
+            path = %req.path(),
+        );
+        Box::pin(
+            async move {
+                let start = Instant::now();
+                let path = req.path().to_string();
+                req.extensions_mut().insert(request_id.clone());
+                let mut response = service.call(req).await?;
+
+                let elapsed = start.elapsed();
+                if elapsed >= SLOW_REQUEST_THRESHOLD {
+                    metrics::increment_counter!("api.rest.slow_requests");
+                    vlog::warn!(
+                        "Slow request {} to {} took {}ms",
+                        request_id.0,
+                        path,
+                        elapsed.as_millis()
+                    );
+                }
+                // The ID is validated or generated, so it's always a correct header value.
+                if let Ok(value) = HeaderValue::from_str(&request_id.0) {
+                    response
+                        .headers_mut()
+                        .insert(HeaderName::from_static("x-request-id"), value);
+                }
+                Ok(response)
+            }
+            .instrument(span),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+
+    #[test]
+    fn request_ids() {
+        let request_id = |header: Option<&str>| {
+            let mut req = TestRequest::default();
+            if let Some(header) = header {
+                req = req.insert_header((REQUEST_ID_HEADER, header));
+            }
+            RequestId::from_request(&req.to_srv_request())
+        };
+
+        assert_eq!(request_id(None), None);
+        assert_eq!(
+            request_id(Some("client-request.42_a")),
+            Some(RequestId("client-request.42_a".to_string()))
+        );
+        assert_eq!(request_id(Some("")), None);
+        assert_eq!(request_id(Some("with space")), None);
+        assert_eq!(
+            request_id(Some(&"a".repeat(MAX_REQUEST_ID_LENGTH + 1))),
+            None
+        );
+
+        let random = RequestId::random();
+        assert_eq!(random.0.len(), 32);
+        assert_ne!(random, RequestId::random());
+    }
+}
@@ -0,0 +1,109 @@
+//! Metrics of the REST API requests.
+//!
+//! The duration of every request is recorded along with its method, route pattern and
+//! response status. The unmatched requests share the same route label, so that the number
+//! of the label values is bounded by the number of the routes.
+
+// Built-in uses
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+use std::time::Instant;
+
+// External uses
+use actix_web::{
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    Error, HttpResponse,
+};
+use futures::future::{ok, Ready};
+
+/// Route label of the requests which don't match any route.
+const UNMATCHED_ROUTE: &str = "unmatched";
+
+/// Middleware that records the duration and the status of the requests.
+#[derive(Debug, Clone, Default)]
+pub struct RequestMetrics;
+
+impl<S, B> Transform<S, ServiceRequest> for RequestMetrics
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = RequestMetricsMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(RequestMetricsMiddleware {
+            service: Rc::new(service),
+        })
+    }
+}
+
+pub struct RequestMetricsMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for RequestMetricsMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    #[allow(clippy::type_complexity)]
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    fn poll_ready(&self, ctx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(ctx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        Box::pin(async move {
+            let start = Instant::now();
+            let method = req.method().to_string();
+            let response = service.call(req).await;
+
+            let (route, status) = match &response {
+                Ok(response) => (
+                    response.request().match_pattern(),
+                    response.status().as_u16(),
+                ),
+                Err(err) => (None, err.as_response_error().status_code().as_u16()),
+            };
+            let route = route.unwrap_or_else(|| UNMATCHED_ROUTE.to_string());
+            let status = status.to_string();
+            metrics::histogram!(
+                "api.rest.request",
+                start.elapsed(),
+                "method" => method.clone(),
+                "route" => route.clone(),
+                "status" => status.clone()
+            );
+            metrics::increment_counter!(
+                "api.rest.responses",
+                "method" => method,
+                "route" => route,
+                "status" => status
+            );
+            response
+        })
+    }
+}
+
+/// Metrics of the process in the Prometheus format.
+pub async fn metrics_endpoint() -> HttpResponse {
+    match zksync_prometheus_exporter::render_metrics() {
+        Some(metrics) => HttpResponse::Ok()
+            .content_type("text/plain; version=0.0.4")
+            .body(metrics),
+        // The recorder is not installed, so there are no metrics to show.
+        None => HttpResponse::NotFound().finish(),
+    }
+}
@@ -2,51 +2,229 @@
 
 // Built-in uses
 use std::collections::BTreeMap;
-use std::str::FromStr;
+use std::sync::Arc;
 use std::time::Instant;
 
 // External uses
-use actix_web::{web, Scope};
+use actix_web::{web, HttpRequest, HttpResponse, Scope};
+use num::{BigUint, Zero};
+use tokio::sync::Mutex;
 
 // Workspace uses
 use zksync_api_types::v02::{
-    account::{Account, AccountAddressOrId, AccountState, IncomingAccountTxsQuery},
+    account::{
+        Account, AccountAddressOrId, AccountCommittedAndPendingState, AccountProof, AccountState,
+        BalanceProof, IncomingAccountProofQuery, IncomingAccountTxsQuery, IncomingExitProofQuery,
+        UnconfirmedDeposit, UnconfirmedDeposits,
+    },
+    mempool::AccountQueuedTxs,
     pagination::{
-        parse_query, AccountTxsRequest, ApiEither, Paginated, PaginationQuery, PendingOpsRequest,
+        parse_query, AccountTxsFilters, AccountTxsRequest, ApiEither, Paginated, PaginationQuery,
+        PaginationQueryOrCursor, PendingOpsRequest,
     },
     transaction::{Transaction, TxHashSerializeWrapper},
+    FormattedAmountsQuery,
+};
+use zksync_crypto::{
+    circuit::account::CircuitAccount,
+    convert::FeConvert,
+    merkle_tree::parallel_smt::SparseMerkleTreeSerializableCacheBN256,
+    params::{account_tree_depth, MIN_NFT_TOKEN_ID, NFT_TOKEN_ID_VAL},
+    Engine, Fr,
 };
-use zksync_crypto::params::{MIN_NFT_TOKEN_ID, NFT_TOKEN_ID_VAL};
 use zksync_storage::{ConnectionPool, StorageProcessor};
 use zksync_token_db_cache::TokenDBCache;
-use zksync_types::{tx::TxHash, AccountId, Address, BlockNumber, SerialId, TokenLike};
+use zksync_types::{
+    exit_proof::ExitProofRequest, tx::TxHash, AccountId, AccountTree, Address, BlockNumber, Nonce,
+    SerialId, TokenId, TokenLike, ZkSyncPriorityOp, ZkSyncTx,
+};
+use zksync_utils::{format_units, BigUintSerdeWrapper};
 
 // Local uses
 use super::{
     error::{Error, InvalidDataError},
+    export::{AccountTxsExport, ExportFormat},
+    extractors::{PathParam, ValidPath},
     paginate_trait::Paginate,
-    response::ApiResult,
+    pagination_cursor::PaginationCursors,
+    response::{ApiResult, ExportableApiResult, FieldsQuery, SignedApiResult, SparseFields},
 };
 use crate::{api_server::helpers::get_depositing, api_try, fee_ticker::PriceError};
 
+/// Types of the transactions and priority operations the account transactions are filtered by.
+const TX_TYPES: &[&str] = &[
+    "Transfer",
+    "Withdraw",
+    "ChangePubKey",
+    "ForcedExit",
+    "MintNFT",
+    "WithdrawNFT",
+    "Swap",
+    "Close",
+    "Deposit",
+    "FullExit",
+];
+
+/// Subtracts the amount from the balance, the transactions that would fail because of
+/// the insufficient balance are expected to be rejected, so the balance doesn't go below zero.
+fn debit(balances: &mut BTreeMap<TokenId, BigUint>, token: TokenId, amount: BigUint) {
+    let balance = balances.entry(token).or_default();
+    *balance = if *balance > amount {
+        &*balance - amount
+    } else {
+        BigUint::zero()
+    };
+}
+
+fn credit(balances: &mut BTreeMap<TokenId, BigUint>, token: TokenId, amount: BigUint) {
+    *balances.entry(token).or_default() += amount;
+}
+
+/// Applies the pending transactions to the committed nonce and balances of the account.
+/// The returned nonce is the one following the consecutive nonces of the pending transactions.
+fn project_pending_txs<'a>(
+    account_id: AccountId,
+    address: Address,
+    nonce: Nonce,
+    mut balances: BTreeMap<TokenId, BigUint>,
+    txs: impl IntoIterator<Item = &'a ZkSyncTx>,
+) -> (Nonce, BTreeMap<TokenId, BigUint>) {
+    let mut pending_nonces = Vec::new();
+    for tx in txs {
+        if tx.account_id().ok() == Some(account_id) {
+            pending_nonces.push(tx.nonce());
+        }
+        match tx {
+            ZkSyncTx::Transfer(tx) => {
+                if tx.account_id == account_id {
+                    debit(&mut balances, tx.token, &tx.amount + &tx.fee);
+                }
+                if tx.to == address {
+                    credit(&mut balances, tx.token, tx.amount.clone());
+                }
+            }
+            ZkSyncTx::Withdraw(tx) if tx.account_id == account_id => {
+                debit(&mut balances, tx.token, &tx.amount + &tx.fee);
+            }
+            ZkSyncTx::ChangePubKey(tx) if tx.account_id == account_id => {
+                debit(&mut balances, tx.fee_token, tx.fee.clone());
+            }
+            ZkSyncTx::ForcedExit(tx) => {
+                // The whole balance of the target is withdrawn.
+                if tx.target == address {
+                    balances.remove(&tx.token);
+                }
+                if tx.initiator_account_id == account_id {
+                    debit(&mut balances, tx.token, tx.fee.clone());
+                }
+            }
+            ZkSyncTx::MintNFT(tx) if tx.creator_id == account_id => {
+                debit(&mut balances, tx.fee_token, tx.fee.clone());
+            }
+            ZkSyncTx::WithdrawNFT(tx) if tx.account_id == account_id => {
+                debit(&mut balances, tx.fee_token, tx.fee.clone());
+                debit(&mut balances, tx.token, BigUint::from(1u32));
+            }
+            ZkSyncTx::Swap(tx) => {
+                if tx.submitter_id == account_id {
+                    debit(&mut balances, tx.fee_token, tx.fee.clone());
+                }
+                let orders = [
+                    (&tx.orders.0, &tx.amounts.0, &tx.amounts.1),
+                    (&tx.orders.1, &tx.amounts.1, &tx.amounts.0),
+                ];
+                for (order, sold, bought) in &orders {
+                    if order.account_id == account_id {
+                        debit(&mut balances, order.token_sell, BigUint::clone(sold));
+                        // The nonce of the order is used only by the limit orders.
+                        if !order.amount.is_zero() && tx.submitter_id != account_id {
+                            pending_nonces.push(order.nonce);
+                        }
+                    }
+                    if order.recipient_address == address {
+                        credit(&mut balances, order.token_buy, BigUint::clone(bought));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pending_nonces.sort_unstable();
+    let mut effective_nonce = nonce;
+    for pending_nonce in pending_nonces {
+        if pending_nonce == effective_nonce {
+            effective_nonce = effective_nonce + 1;
+        }
+    }
+    balances.retain(|_, balance| !balance.is_zero());
+    (effective_nonce, balances)
+}
+
+/// Builds the Merkle proof of the account and optionally of its balance from the account tree.
+/// Returns `None` if the account doesn't exist in the tree.
+fn account_proof(
+    tree: &AccountTree,
+    block_number: BlockNumber,
+    account_id: AccountId,
+    token_id: Option<TokenId>,
+) -> Option<AccountProof> {
+    let account = tree.get(*account_id)?.clone();
+    let circuit_account = CircuitAccount::<Engine>::from(account.clone());
+    let account_path = tree
+        .merkle_path(*account_id)
+        .into_iter()
+        .map(|(hash, _)| hash)
+        .collect();
+    let balance = token_id.map(|token_id| BalanceProof {
+        token_id,
+        balance: account.get_balance(token_id).into(),
+        path: circuit_account
+            .subtree
+            .merkle_path(*token_id)
+            .into_iter()
+            .map(|(hash, _)| hash)
+            .collect(),
+    });
+    Some(AccountProof {
+        account_id,
+        block_number,
+        root_hash: tree.root_hash(),
+        address: account.address,
+        nonce: account.nonce,
+        pub_key_hash: account.pub_key_hash,
+        balance_tree_root: circuit_account.subtree.root_hash(),
+        account_path,
+        balance,
+    })
+}
+
 /// Shared data between `api/v02/accounts` endpoints.
 #[derive(Clone)]
-struct ApiAccountData {
+pub(crate) struct ApiAccountData {
     pool: ConnectionPool,
     tokens: TokenDBCache,
     confirmations_for_eth_event: u64,
+    /// Account tree of the last block requested for the proofs. The tree is built from the
+    /// whole state of the block, so it's reused by the requests for the same block.
+    account_tree: Arc<Mutex<Option<(BlockNumber, Arc<AccountTree>)>>>,
 }
 
 impl ApiAccountData {
-    fn new(pool: ConnectionPool, tokens: TokenDBCache, confirmations_for_eth_event: u64) -> Self {
+    pub(crate) fn new(
+        pool: ConnectionPool,
+        tokens: TokenDBCache,
+        confirmations_for_eth_event: u64,
+    ) -> Self {
         Self {
             pool,
             tokens,
             confirmations_for_eth_event,
+            account_tree: Arc::default(),
         }
     }
 
-    async fn get_id_by_address_or_id(
+    pub(crate) async fn get_id_by_address_or_id(
         &self,
         account_address_or_id: AccountAddressOrId,
     ) -> Result<Option<AccountId>, Error> {
@@ -65,7 +243,7 @@ impl ApiAccountData {
         }
     }
 
-    async fn get_address_by_address_or_id(
+    pub(crate) async fn get_address_by_address_or_id(
         &self,
         account_address_or_id: AccountAddressOrId,
     ) -> Result<Address, Error> {
@@ -84,25 +262,11 @@ impl ApiAccountData {
         }
     }
 
-    fn parse_account_id_or_address(
+    pub(crate) fn parse_account_id_or_address(
         &self,
         account_address_or_id: &str,
     ) -> Result<AccountAddressOrId, Error> {
-        if let Ok(account_id) = u32::from_str(account_address_or_id) {
-            Ok(AccountAddressOrId::Id(AccountId(account_id)))
-        } else {
-            let address_str = if let Some(address_str) = account_address_or_id.strip_prefix("0x") {
-                address_str
-            } else {
-                account_address_or_id
-            };
-
-            if let Ok(address) = Address::from_str(address_str) {
-                Ok(AccountAddressOrId::Address(address))
-            } else {
-                Err(Error::from(InvalidDataError::InvalidAccountIdOrAddress))
-            }
-        }
+        AccountAddressOrId::from_param(account_address_or_id)
     }
 
     async fn api_account(
@@ -163,6 +327,7 @@ impl ApiAccountData {
             pub_key_hash: account.pub_key_hash,
             last_update_in_block,
             balances,
+            formatted_balances: None,
             account_type,
             nfts,
             minted_nfts,
@@ -202,6 +367,211 @@ impl ApiAccountData {
         result
     }
 
+    /// Checks that the state at the block can be restored from the stored state diffs.
+    async fn check_state_block(
+        storage: &mut StorageProcessor<'_>,
+        block_number: BlockNumber,
+    ) -> Result<(), Error> {
+        let last_saved_block = storage
+            .chain()
+            .block_schema()
+            .get_last_saved_block()
+            .await
+            .map_err(Error::storage)?;
+        if block_number > last_saved_block {
+            return Err(Error::from(InvalidDataError::BlockNotFound));
+        }
+        let last_pruned_block = storage
+            .pruning_schema()
+            .load_last_pruned_block()
+            .await
+            .map_err(Error::storage)?;
+        if block_number < last_pruned_block {
+            return Err(Error::from(InvalidDataError::BlockHistoryPruned));
+        }
+        Ok(())
+    }
+
+    /// Returns the committed state of the account after the block, restored from its diffs.
+    async fn account_state_at_block(
+        &self,
+        account_id: AccountId,
+        block_number: BlockNumber,
+    ) -> Result<Option<Account>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let mut transaction = storage.start_transaction().await.map_err(Error::storage)?;
+        Self::check_state_block(&mut transaction, block_number).await?;
+
+        let state = transaction
+            .chain()
+            .account_schema()
+            .account_state_at_block(account_id, block_number)
+            .await
+            .map_err(Error::storage)?;
+        let result = if let Some((last_block, account)) = state {
+            Ok(Some(
+                self.api_account(account, account_id, last_block, &mut transaction)
+                    .await?,
+            ))
+        } else {
+            Ok(None)
+        };
+        transaction.commit().await.map_err(Error::storage)?;
+        result
+    }
+
+    /// Returns the account tree after the block, building it from the committed state
+    /// unless it's already built for the block.
+    async fn account_tree(&self, block_number: BlockNumber) -> Result<Arc<AccountTree>, Error> {
+        // The lock is held while the tree is built, so the concurrent requests for the same block
+        // wait for it instead of building their own trees.
+        let mut cached_tree = self.account_tree.lock().await;
+        if let Some((cached_block, tree)) = cached_tree.as_ref() {
+            if *cached_block == block_number {
+                return Ok(tree.clone());
+            }
+        }
+
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let mut transaction = storage.start_transaction().await.map_err(Error::storage)?;
+        Self::check_state_block(&mut transaction, block_number).await?;
+        let block = transaction
+            .chain()
+            .block_schema()
+            .get_storage_block(block_number)
+            .await
+            .map_err(Error::storage)?
+            .ok_or_else(|| Error::from(InvalidDataError::BlockNotFound))?;
+        let block_root_hash = Fr::from_bytes(&block.root_hash).map_err(Error::storage)?;
+        let (_, accounts) = transaction
+            .chain()
+            .state_schema()
+            .load_committed_state(Some(block_number))
+            .await
+            .map_err(Error::storage)?;
+        let tree_cache = transaction
+            .chain()
+            .tree_cache_schema_bincode()
+            .get_account_tree_cache_block(block_number)
+            .await
+            .map_err(Error::storage)?;
+        transaction.commit().await.map_err(Error::storage)?;
+
+        // Hashing the whole tree takes a while, so it's done outside of the async runtime.
+        let tree = tokio::task::spawn_blocking(move || {
+            let mut tree = AccountTree::new(account_tree_depth());
+            for (id, account) in accounts {
+                tree.insert(*id, account);
+            }
+            if let Some(tree_cache) = tree_cache {
+                tree.set_internals(SparseMerkleTreeSerializableCacheBN256::decode_bincode(
+                    &tree_cache,
+                ));
+            }
+            let _root_hash = tree.root_hash();
+            tree
+        })
+        .await
+        .map_err(|err| Error::from(anyhow::Error::from(err)))?;
+        if tree.root_hash() != block_root_hash {
+            return Err(Error::storage(format!(
+                "Root hash of the restored account tree doesn't match the block {}",
+                *block_number
+            )));
+        }
+
+        let tree = Arc::new(tree);
+        *cached_tree = Some((block_number, tree.clone()));
+        Ok(tree)
+    }
+
+    async fn account_proof(
+        &self,
+        account_id: AccountId,
+        token: Option<TokenLike>,
+        block_number: Option<BlockNumber>,
+    ) -> Result<Option<AccountProof>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let token_id = if let Some(token) = token {
+            Some(self.resolve_token_id(&mut storage, token).await?)
+        } else {
+            None
+        };
+        let block_number = if let Some(block_number) = block_number {
+            block_number
+        } else {
+            storage
+                .chain()
+                .block_schema()
+                .get_last_saved_block()
+                .await
+                .map_err(Error::storage)?
+        };
+        drop(storage);
+
+        let tree = self.account_tree(block_number).await?;
+        Ok(account_proof(&tree, block_number, account_id, token_id))
+    }
+
+    async fn resolve_token_id(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        token: TokenLike,
+    ) -> Result<TokenId, Error> {
+        let token = self
+            .tokens
+            .get_token(storage, token)
+            .await
+            .map_err(Error::storage)?
+            .ok_or_else(|| Error::from(PriceError::token_not_found("Token not found")))?;
+        Ok(token.id)
+    }
+
+    /// Requests the exit proof of the account balance at the last verified block,
+    /// the proof is generated asynchronously by the exit proof generator.
+    async fn request_exit_proof(
+        &self,
+        account_id: AccountId,
+        token: TokenLike,
+    ) -> Result<Option<ExitProofRequest>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let token_id = self.resolve_token_id(&mut storage, token).await?;
+        let last_verified_block = storage
+            .chain()
+            .block_schema()
+            .get_last_verified_confirmed_block()
+            .await
+            .map_err(Error::storage)?;
+
+        let mut transaction = storage.start_transaction().await.map_err(Error::storage)?;
+        transaction
+            .exit_proofs_schema()
+            .store_request(account_id, token_id, last_verified_block)
+            .await
+            .map_err(Error::storage)?;
+        let request = transaction
+            .exit_proofs_schema()
+            .load_request(account_id, token_id)
+            .await
+            .map_err(Error::storage)?;
+        transaction.commit().await.map_err(Error::storage)?;
+        Ok(request)
+    }
+
+    async fn exit_proof(
+        &self,
+        account_id: AccountId,
+        token: TokenLike,
+    ) -> Result<Option<ExitProofRequest>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let token_id = self.resolve_token_id(&mut storage, token).await?;
+        storage
+            .exit_proofs_schema()
+            .load_request(account_id, token_id)
+            .await
+            .map_err(Error::storage)
+    }
+
     async fn account_finalized_info(
         &self,
         account_id: AccountId,
@@ -231,7 +601,7 @@ impl ApiAccountData {
         result
     }
 
-    async fn account_full_info(
+    pub(crate) async fn account_full_info(
         &self,
         address: Address,
         account_id: Option<AccountId>,
@@ -290,37 +660,277 @@ impl ApiAccountData {
         })
     }
 
+    async fn account_committed_and_pending_info(
+        &self,
+        account_id: AccountId,
+    ) -> Result<Option<AccountCommittedAndPendingState>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let mut transaction = storage.start_transaction().await.map_err(Error::storage)?;
+
+        let (finalized_state, committed_state) = transaction
+            .chain()
+            .account_schema()
+            .last_committed_state_for_account(account_id)
+            .await
+            .map_err(Error::storage)?;
+        let committed_account = match committed_state {
+            Some(account) => account,
+            None => return Ok(None),
+        };
+
+        let finalized_block = BlockNumber(finalized_state.0 as u32);
+        let finalized = if let Some(account) = finalized_state.1 {
+            Some(
+                self.api_account(account, account_id, finalized_block, &mut transaction)
+                    .await?,
+            )
+        } else {
+            None
+        };
+        let last_block = transaction
+            .chain()
+            .account_schema()
+            .last_committed_block_with_update_for_acc(account_id, finalized_block)
+            .await
+            .map_err(Error::storage)?;
+        let committed = self
+            .api_account(
+                committed_account.clone(),
+                account_id,
+                last_block,
+                &mut transaction,
+            )
+            .await?;
+
+        let pending_txs = transaction
+            .chain()
+            .mempool_schema()
+            .get_account_pending_txs(account_id, committed.address)
+            .await
+            .map_err(Error::storage)?;
+        // NFTs are not included to the balances.
+        let balances = committed_account
+            .get_nonzero_balances()
+            .into_iter()
+            .filter(|(token_id, _)| token_id.0 < MIN_NFT_TOKEN_ID)
+            .map(|(token_id, balance)| (token_id, balance.0))
+            .collect();
+        let (effective_nonce, balances) = project_pending_txs(
+            account_id,
+            committed.address,
+            committed.nonce,
+            balances,
+            pending_txs.iter().map(|tx| &tx.tx),
+        );
+
+        let mut projected_balances = BTreeMap::new();
+        for (token_id, balance) in balances {
+            let token_symbol = self
+                .tokens
+                .token_symbol(&mut transaction, token_id)
+                .await
+                .map_err(Error::storage)?
+                .ok_or_else(|| Error::from(PriceError::token_not_found(token_id)))?;
+            projected_balances.insert(token_symbol, balance.into());
+        }
+        transaction.commit().await.map_err(Error::storage)?;
+
+        Ok(Some(AccountCommittedAndPendingState {
+            committed,
+            finalized,
+            pending_txs: pending_txs.iter().map(|tx| tx.hash()).collect(),
+            effective_nonce,
+            projected_balances,
+            formatted_projected_balances: None,
+        }))
+    }
+
+    /// Scales the balances by the decimals of their tokens.
+    async fn format_balances(
+        &self,
+        balances: &BTreeMap<String, BigUintSerdeWrapper>,
+    ) -> Result<BTreeMap<String, String>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let mut formatted = BTreeMap::new();
+        for (token_symbol, balance) in balances {
+            let token = self
+                .tokens
+                .get_token(&mut storage, token_symbol.as_str())
+                .await
+                .map_err(Error::storage)?
+                .ok_or_else(|| Error::from(PriceError::token_not_found(token_symbol)))?;
+            formatted.insert(
+                token_symbol.clone(),
+                format_units(&balance.0, token.decimals),
+            );
+        }
+        Ok(formatted)
+    }
+
+    async fn format_account(&self, account: &mut Account) -> Result<(), Error> {
+        account.formatted_balances = Some(self.format_balances(&account.balances).await?);
+        Ok(())
+    }
+
+    async fn format_account_state(&self, state: &mut AccountState) -> Result<(), Error> {
+        for account in state.committed.iter_mut().chain(state.finalized.iter_mut()) {
+            self.format_account(account).await?;
+        }
+        Ok(())
+    }
+
+    async fn format_pending_state(
+        &self,
+        state: &mut AccountCommittedAndPendingState,
+    ) -> Result<(), Error> {
+        self.format_account(&mut state.committed).await?;
+        if let Some(finalized) = &mut state.finalized {
+            self.format_account(finalized).await?;
+        }
+        state.formatted_projected_balances =
+            Some(self.format_balances(&state.projected_balances).await?);
+        Ok(())
+    }
+
+    /// Returns the deposits to the address waiting for the confirmations on Ethereum
+    /// with the number of the confirmations they have already.
+    async fn unconfirmed_deposits(&self, address: Address) -> Result<UnconfirmedDeposits, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let last_watched_eth_block = storage
+            .chain()
+            .mempool_schema()
+            .last_watched_eth_block()
+            .await
+            .map_err(Error::storage)?;
+        let ops = storage
+            .chain()
+            .mempool_schema()
+            .get_unconfirmed_deposits(address)
+            .await
+            .map_err(Error::storage)?;
+
+        let mut deposits = Vec::with_capacity(ops.len());
+        for op in ops {
+            let deposit = match op.data {
+                ZkSyncPriorityOp::Deposit(deposit) => deposit,
+                ZkSyncPriorityOp::FullExit(_) => continue,
+            };
+            let token_symbol = self
+                .tokens
+                .token_symbol(&mut storage, deposit.token)
+                .await
+                .map_err(Error::storage)?
+                .ok_or_else(|| Error::from(PriceError::token_not_found(deposit.token)))?;
+            let confirmations = last_watched_eth_block
+                .map_or(0, |eth_block| eth_block.saturating_sub(op.eth_block));
+            deposits.push(UnconfirmedDeposit {
+                serial_id: op.serial_id,
+                eth_hash: op.eth_hash,
+                eth_block: op.eth_block,
+                from: deposit.from,
+                token_id: deposit.token,
+                token_symbol,
+                amount: deposit.amount,
+                confirmations,
+            });
+        }
+
+        Ok(UnconfirmedDeposits {
+            address,
+            last_watched_eth_block,
+            required_confirmations: self.confirmations_for_eth_event,
+            deposits,
+        })
+    }
+
+    async fn account_queued_txs(&self, account_id: AccountId) -> Result<AccountQueuedTxs, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let mut transaction = storage.start_transaction().await.map_err(Error::storage)?;
+
+        let queue_size = transaction
+            .chain()
+            .mempool_schema()
+            .get_mempool_size()
+            .await
+            .map_err(Error::storage)?;
+        let txs = transaction
+            .chain()
+            .mempool_schema()
+            .get_account_queued_txs(account_id)
+            .await
+            .map_err(Error::storage)?;
+        transaction.commit().await.map_err(Error::storage)?;
+
+        Ok(AccountQueuedTxs {
+            account_id,
+            queue_size: queue_size.into(),
+            txs,
+        })
+    }
+
     async fn account_txs(
         &self,
         query: PaginationQuery<ApiEither<TxHash>>,
         address: Address,
         token_like: Option<TokenLike>,
         second_address: Option<Address>,
+        filters: AccountTxsFilters,
+        cursors: &PaginationCursors,
     ) -> Result<Paginated<Transaction, TxHashSerializeWrapper>, Error> {
         let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
-        let token = if let Some(token_like) = token_like {
-            Some(
-                self.tokens
-                    .get_token(&mut storage, token_like.clone())
-                    .await
-                    .map_err(Error::storage)?
-                    .ok_or_else(|| Error::from(PriceError::token_not_found(token_like)))?
-                    .id,
-            )
-        } else {
-            None
-        };
+        let token = self.account_txs_token(&mut storage, token_like).await?;
         let new_query = PaginationQuery {
             from: AccountTxsRequest {
                 tx_hash: query.from,
                 address,
                 token,
                 second_address,
+                filters,
             },
             limit: query.limit,
             direction: query.direction,
         };
-        storage.paginate_checked(&new_query).await
+        storage.paginate_with_cursor(new_query, cursors).await
+    }
+
+    async fn account_txs_token(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        token_like: Option<TokenLike>,
+    ) -> Result<Option<TokenId>, Error> {
+        if let Some(token_like) = token_like {
+            let token = self
+                .tokens
+                .get_token(storage, token_like.clone())
+                .await
+                .map_err(Error::storage)?
+                .ok_or_else(|| Error::from(PriceError::token_not_found(token_like)))?;
+            Ok(Some(token.id))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Streams the whole account history matching the filters in the export format.
+    async fn account_txs_export(
+        &self,
+        address: Address,
+        token_like: Option<TokenLike>,
+        second_address: Option<Address>,
+        filters: AccountTxsFilters,
+        format: ExportFormat,
+    ) -> Result<HttpResponse, Error> {
+        let token = {
+            let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+            self.account_txs_token(&mut storage, token_like).await?
+        };
+        let export = AccountTxsExport {
+            address,
+            token,
+            second_address,
+            filters,
+        };
+        Ok(export.stream(self.pool.clone(), format))
     }
 
     /// Pending deposits can be matched only with addresses,
@@ -349,70 +959,207 @@ impl ApiAccountData {
 
 async fn account_committed_info(
     data: web::Data<ApiAccountData>,
-    account_id_or_address: web::Path<String>,
-) -> ApiResult<Option<Account>> {
+    ValidPath(address_or_id): ValidPath<AccountAddressOrId>,
+    web::Query(fields): web::Query<FieldsQuery>,
+    web::Query(format): web::Query<FormattedAmountsQuery>,
+) -> SignedApiResult<SparseFields<Option<Account>>> {
+    let start = Instant::now();
+    let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
+    let mut account = match account_id {
+        Some(account_id) => api_try!(data.account_committed_info(account_id).await),
+        None => None,
+    };
+    if let (true, Some(account)) = (format.formatted, &mut account) {
+        api_try!(data.format_account(account).await);
+    }
+    let res = ApiResult::Ok(account).with_fields(&fields);
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_committed_info");
+    res.signed()
+}
+
+async fn account_finalized_info(
+    data: web::Data<ApiAccountData>,
+    ValidPath(address_or_id): ValidPath<AccountAddressOrId>,
+    web::Query(fields): web::Query<FieldsQuery>,
+    web::Query(format): web::Query<FormattedAmountsQuery>,
+) -> SignedApiResult<SparseFields<Option<Account>>> {
+    let start = Instant::now();
+    let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
+    let mut account = match account_id {
+        Some(account_id) => api_try!(data.account_finalized_info(account_id).await),
+        None => None,
+    };
+    if let (true, Some(account)) = (format.formatted, &mut account) {
+        api_try!(data.format_account(account).await);
+    }
+    let res = ApiResult::Ok(account).with_fields(&fields);
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_finalized_info");
+    res.signed()
+}
+
+async fn account_committed_and_pending_info(
+    data: web::Data<ApiAccountData>,
+    ValidPath(address_or_id): ValidPath<AccountAddressOrId>,
+    web::Query(format): web::Query<FormattedAmountsQuery>,
+) -> SignedApiResult<Option<AccountCommittedAndPendingState>> {
+    let start = Instant::now();
+    let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
+    let mut state = match account_id {
+        Some(account_id) => api_try!(data.account_committed_and_pending_info(account_id).await),
+        None => None,
+    };
+    if let (true, Some(state)) = (format.formatted, &mut state) {
+        api_try!(data.format_pending_state(state).await);
+    }
+    let res = ApiResult::Ok(state);
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_committed_and_pending_info");
+    res.signed()
+}
+
+async fn account_unconfirmed_deposits(
+    data: web::Data<ApiAccountData>,
+    ValidPath(address_or_id): ValidPath<AccountAddressOrId>,
+) -> ApiResult<UnconfirmedDeposits> {
+    let start = Instant::now();
+    let address = api_try!(data.get_address_by_address_or_id(address_or_id).await);
+    let res = data.unconfirmed_deposits(address).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_unconfirmed_deposits");
+    res
+}
+
+async fn account_queued_txs(
+    data: web::Data<ApiAccountData>,
+    ValidPath(address_or_id): ValidPath<AccountAddressOrId>,
+) -> ApiResult<Option<AccountQueuedTxs>> {
     let start = Instant::now();
-    let address_or_id = api_try!(data.parse_account_id_or_address(&account_id_or_address));
     let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
     let res = if let Some(account_id) = account_id {
-        data.account_committed_info(account_id).await.into()
+        data.account_queued_txs(account_id).await.map(Some).into()
     } else {
         ApiResult::Ok(None)
     };
-    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_committed_info");
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_queued_txs");
     res
 }
 
-async fn account_finalized_info(
+async fn account_state_at_block(
     data: web::Data<ApiAccountData>,
-    account_id_or_address: web::Path<String>,
-) -> ApiResult<Option<Account>> {
+    path: web::Path<(String, BlockNumber)>,
+) -> SignedApiResult<Option<Account>> {
     let start = Instant::now();
-    let address_or_id = api_try!(data.parse_account_id_or_address(&account_id_or_address));
+    let (account_id_or_address, block_number) = path.into_inner();
+    let address_or_id = api_try!(AccountAddressOrId::from_param(&account_id_or_address));
     let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
     let res = if let Some(account_id) = account_id {
-        data.account_finalized_info(account_id).await.into()
+        data.account_state_at_block(account_id, block_number)
+            .await
+            .into()
     } else {
         ApiResult::Ok(None)
     };
-    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_finalized_info");
-    res
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_state_at_block");
+    res.signed()
+}
+
+async fn account_proof(
+    data: web::Data<ApiAccountData>,
+    ValidPath(address_or_id): ValidPath<AccountAddressOrId>,
+    web::Query(query): web::Query<IncomingAccountProofQuery>,
+) -> SignedApiResult<Option<AccountProof>> {
+    let start = Instant::now();
+    let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
+    let token_like = query.token.map(|token| TokenLike::parse(&token));
+    let res = if let Some(account_id) = account_id {
+        data.account_proof(account_id, token_like, query.block)
+            .await
+            .into()
+    } else {
+        ApiResult::Ok(None)
+    };
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_proof");
+    res.signed()
+}
+
+async fn request_exit_proof(
+    data: web::Data<ApiAccountData>,
+    ValidPath(address_or_id): ValidPath<AccountAddressOrId>,
+    web::Query(query): web::Query<IncomingExitProofQuery>,
+) -> SignedApiResult<Option<ExitProofRequest>> {
+    let start = Instant::now();
+    let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
+    let res = if let Some(account_id) = account_id {
+        data.request_exit_proof(account_id, TokenLike::parse(&query.token))
+            .await
+            .into()
+    } else {
+        ApiResult::Ok(None)
+    };
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "request_exit_proof");
+    res.signed()
+}
+
+async fn exit_proof(
+    data: web::Data<ApiAccountData>,
+    ValidPath(address_or_id): ValidPath<AccountAddressOrId>,
+    web::Query(query): web::Query<IncomingExitProofQuery>,
+) -> SignedApiResult<Option<ExitProofRequest>> {
+    let start = Instant::now();
+    let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
+    let res = if let Some(account_id) = account_id {
+        data.exit_proof(account_id, TokenLike::parse(&query.token))
+            .await
+            .into()
+    } else {
+        ApiResult::Ok(None)
+    };
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "exit_proof");
+    res.signed()
 }
 
 async fn account_full_info(
     data: web::Data<ApiAccountData>,
-    account_id_or_address: web::Path<String>,
-) -> ApiResult<AccountState> {
+    ValidPath(address_or_id): ValidPath<AccountAddressOrId>,
+    web::Query(fields): web::Query<FieldsQuery>,
+    web::Query(format): web::Query<FormattedAmountsQuery>,
+) -> SignedApiResult<SparseFields<AccountState>> {
     let start = Instant::now();
-    let address_or_id = api_try!(data.parse_account_id_or_address(&account_id_or_address));
     let address = api_try!(
         data.get_address_by_address_or_id(address_or_id.clone())
             .await
     );
     let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
-    let res = data.account_full_info(address, account_id).await.into();
+    let mut state = api_try!(data.account_full_info(address, account_id).await);
+    if format.formatted {
+        api_try!(data.format_account_state(&mut state).await);
+    }
+    let res = ApiResult::Ok(state).with_fields(&fields);
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_full_info");
-    res
+    res.signed()
 }
 
+fn check_tx_type(tx_type: Option<&str>) -> Result<(), Error> {
+    match tx_type {
+        Some(tx_type) if !TX_TYPES.contains(&tx_type) => {
+            Err(Error::from(InvalidDataError::InvalidTxType))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// The transactions are exported instead of the paginated response if the request
+/// accepts CSV or NDJSON, see the `export` module.
 async fn account_txs(
+    req: HttpRequest,
     data: web::Data<ApiAccountData>,
-    account_id_or_address: web::Path<String>,
+    cursors: web::Data<PaginationCursors>,
+    ValidPath(address_or_id): ValidPath<AccountAddressOrId>,
     web::Query(query): web::Query<IncomingAccountTxsQuery>,
-) -> ApiResult<Paginated<Transaction, TxHashSerializeWrapper>> {
+) -> ExportableApiResult<Paginated<Transaction, TxHashSerializeWrapper>> {
     let start = Instant::now();
-    let pagination = api_try!(parse_query(PaginationQuery {
-        from: query.from,
-        limit: query.limit,
-        direction: query.direction
-    })
-    .map_err(Error::from));
-
-    let address_or_id = api_try!(data.parse_account_id_or_address(&account_id_or_address));
     let address = api_try!(data.get_address_by_address_or_id(address_or_id).await);
 
     let second_address = if let Some(second_account) = query.second_account {
-        let address_or_id = api_try!(data.parse_account_id_or_address(&second_account));
+        let address_or_id = api_try!(AccountAddressOrId::from_param(&second_account));
         Some(api_try!(
             data.get_address_by_address_or_id(address_or_id).await
         ))
@@ -421,23 +1168,52 @@ async fn account_txs(
     };
 
     let token_like = query.token.map(|token| TokenLike::parse(&token));
+    api_try!(check_tx_type(query.tx_type.as_deref()));
+    let filters = AccountTxsFilters {
+        tx_type: query.tx_type,
+        tx_direction: query.tx_direction,
+        status: query.status,
+        created_after: query.created_after,
+        created_before: query.created_before,
+    };
 
+    if let Some(format) = ExportFormat::from_request(&req) {
+        let response = api_try!(
+            data.account_txs_export(address, token_like, second_address, filters, format)
+                .await
+        );
+        metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_txs_export");
+        return ExportableApiResult::Export(response);
+    }
+
+    let pagination = api_try!(cursors.parse_query(PaginationQueryOrCursor {
+        from: query.from,
+        limit: query.limit,
+        direction: query.direction,
+        cursor: query.cursor,
+    }));
     let res = data
-        .account_txs(pagination, address, token_like, second_address)
+        .account_txs(
+            pagination,
+            address,
+            token_like,
+            second_address,
+            filters,
+            &cursors,
+        )
         .await
         .into();
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_txs");
-    res
+    ExportableApiResult::Json(res)
 }
 
 async fn account_pending_txs(
     data: web::Data<ApiAccountData>,
-    account_id_or_address: web::Path<String>,
+    ValidPath(address_or_id): ValidPath<AccountAddressOrId>,
     web::Query(query): web::Query<PaginationQuery<String>>,
 ) -> ApiResult<Paginated<Transaction, SerialId>> {
     let start = Instant::now();
     let query = api_try!(parse_query(query).map_err(Error::from));
-    let address_or_id = api_try!(data.parse_account_id_or_address(&account_id_or_address));
     let address = api_try!(
         data.get_address_by_address_or_id(address_or_id.clone())
             .await
@@ -468,6 +1244,34 @@ pub fn api_scope(
             "{account_id_or_address}/finalized",
             web::get().to(account_finalized_info),
         )
+        .route(
+            "{account_id_or_address}/committed_and_pending",
+            web::get().to(account_committed_and_pending_info),
+        )
+        .route(
+            "{account_id_or_address}/pending",
+            web::get().to(account_queued_txs),
+        )
+        .route(
+            "{account_id_or_address}/deposits/unconfirmed",
+            web::get().to(account_unconfirmed_deposits),
+        )
+        .route(
+            "{account_id_or_address}/state_at/{block_number}",
+            web::get().to(account_state_at_block),
+        )
+        .route(
+            "{account_id_or_address}/proof",
+            web::get().to(account_proof),
+        )
+        .route(
+            "{account_id_or_address}/exit_proof",
+            web::post().to(request_exit_proof),
+        )
+        .route(
+            "{account_id_or_address}/exit_proof",
+            web::get().to(exit_proof),
+        )
         .route("{account_id_or_address}", web::get().to(account_full_info))
         .route(
             "{account_id_or_address}/transactions",
@@ -483,20 +1287,26 @@ pub fn api_scope(
 mod tests {
     use super::*;
     use crate::api_server::rest::v02::{
+        error::ErrorCode,
         test_utils::{deserialize_response_result, TestServerConfig},
         SharedData,
     };
     use num::BigUint;
     use serde::Deserialize;
+    use std::str::FromStr;
     use zksync_api_client::rest::client::Client;
     use zksync_api_types::v02::{
-        account::{DepositingAccountBalances, DepositingFunds},
+        account::{AccountTxExport, DepositingAccountBalances, DepositingFunds},
         pagination::{PaginationDirection, PaginationQuery},
         transaction::{L1Transaction, TransactionData},
         ApiVersion,
     };
+    use zksync_crypto::{merkle_tree::hasher::Hasher, primitives::GetBits};
     use zksync_storage::StorageProcessor;
-    use zksync_types::{AccountId, Address, Deposit, PriorityOp, TokenId, ZkSyncPriorityOp, H256};
+    use zksync_types::{
+        tx::{ForcedExit, Transfer, Withdraw},
+        AccountId, Address, Deposit, PriorityOp, SignedZkSyncTx, TokenId, ZkSyncPriorityOp, H256,
+    };
 
     // While the values of the PendingOpsFlattenRequest's fields are never directly
     // used in the tests, we still need them to specify the JSON format of the `unconfirmed_ops` endpoint input in tests.
@@ -576,6 +1386,133 @@ mod tests {
         }
     }
 
+    fn transfer(
+        account_id: u32,
+        to: Address,
+        token: u32,
+        amount: u32,
+        fee: u32,
+        nonce: u32,
+    ) -> ZkSyncTx {
+        Transfer::new(
+            AccountId(account_id),
+            Address::random(),
+            to,
+            TokenId(token),
+            amount.into(),
+            fee.into(),
+            Nonce(nonce),
+            Default::default(),
+            None,
+        )
+        .into()
+    }
+
+    /// Folds the Merkle path from the leaf hash to the root hash.
+    fn fold_path(hasher: &impl Hasher<Fr>, leaf_hash: Fr, index: u32, path: &[Fr]) -> Fr {
+        path.iter()
+            .enumerate()
+            .fold(leaf_hash, |hash, (level, sibling)| {
+                if (index >> level) & 1 == 1 {
+                    hasher.compress(sibling, &hash, level)
+                } else {
+                    hasher.compress(&hash, sibling, level)
+                }
+            })
+    }
+
+    #[test]
+    fn account_merkle_proof() {
+        let mut tree = AccountTree::new(account_tree_depth());
+        let mut account = zksync_types::Account::default_with_address(&Address::random());
+        account.nonce = Nonce(3);
+        account.set_balance(TokenId(2), BigUint::from(100u32));
+        tree.insert(
+            1,
+            zksync_types::Account::default_with_address(&Address::random()),
+        );
+        tree.insert(5, account.clone());
+
+        let proof = account_proof(&tree, BlockNumber(1), AccountId(5), Some(TokenId(2))).unwrap();
+        assert_eq!(proof.root_hash, tree.root_hash());
+        assert_eq!(proof.nonce, account.nonce);
+        assert_eq!(proof.account_path.len(), account_tree_depth());
+        let leaf_hash = tree.hasher.hash_bits(account.get_bits_le());
+        assert_eq!(
+            fold_path(&tree.hasher, leaf_hash, 5, &proof.account_path),
+            proof.root_hash
+        );
+
+        let balance = proof.balance.unwrap();
+        assert_eq!(balance.balance.0, BigUint::from(100u32));
+        let circuit_account = CircuitAccount::<Engine>::from(account);
+        let balance_leaf = circuit_account.subtree.get(2).unwrap();
+        let balance_hash = circuit_account
+            .subtree
+            .hasher
+            .hash_bits(balance_leaf.get_bits_le());
+        assert_eq!(
+            fold_path(
+                &circuit_account.subtree.hasher,
+                balance_hash,
+                2,
+                &balance.path
+            ),
+            proof.balance_tree_root
+        );
+
+        // There is no proof for the accounts which don't exist.
+        assert!(account_proof(&tree, BlockNumber(1), AccountId(2), None).is_none());
+    }
+
+    #[test]
+    fn pending_txs_projection() {
+        let (account_id, address) = (AccountId(1), Address::random());
+        let balances = vec![
+            (TokenId(0), BigUint::from(1000u32)),
+            (TokenId(1), BigUint::from(50u32)),
+        ]
+        .into_iter()
+        .collect();
+        let withdraw = Withdraw::new(
+            account_id,
+            address,
+            Address::random(),
+            TokenId(0),
+            500u32.into(),
+            10u32.into(),
+            Nonce(7),
+            Default::default(),
+            None,
+        );
+        let forced_exit = ForcedExit::new(
+            AccountId(3),
+            address,
+            TokenId(1),
+            10u32.into(),
+            Nonce(0),
+            Default::default(),
+            None,
+        );
+        let txs = vec![
+            transfer(1, Address::random(), 0, 100, 10, 5),
+            ZkSyncTx::Withdraw(Box::new(withdraw)),
+            ZkSyncTx::ForcedExit(Box::new(forced_exit)),
+            transfer(2, address, 1, 30, 10, 0),
+        ];
+
+        let (nonce, balances) = project_pending_txs(account_id, address, Nonce(5), balances, &txs);
+        // The nonce 6 is missing in the pending transactions, so it's the one to use next.
+        assert_eq!(nonce, Nonce(6));
+        let expected_balances = vec![
+            (TokenId(0), BigUint::from(380u32)),
+            (TokenId(1), BigUint::from(30u32)),
+        ]
+        .into_iter()
+        .collect::<BTreeMap<_, _>>();
+        assert_eq!(balances, expected_balances);
+    }
+
     #[actix_rt::test]
     #[cfg_attr(
         not(feature = "api_test"),
@@ -611,6 +1548,58 @@ mod tests {
             .await?;
         let account_finalized_info: Option<Account> = deserialize_response_result(response)?;
 
+        // The pending transaction of the account is taken into account.
+        let pending_tx = transfer(
+            *account_id,
+            Address::random(),
+            0,
+            0,
+            0,
+            *account_committed_info_by_id.nonce,
+        );
+        server
+            .pool
+            .access_storage()
+            .await?
+            .chain()
+            .mempool_schema()
+            .insert_tx(&SignedZkSyncTx::from(pending_tx.clone()))
+            .await?;
+        let response = client
+            .account_info(&account_id.to_string(), "committed_and_pending")
+            .await?;
+        let pending_state: Option<AccountCommittedAndPendingState> =
+            deserialize_response_result(response)?;
+        let pending_state = pending_state.unwrap();
+        assert_eq!(pending_state.committed, account_committed_info_by_id);
+        assert_eq!(pending_state.finalized, account_finalized_info);
+        assert_eq!(pending_state.pending_txs, vec![pending_tx.hash()]);
+        assert_eq!(
+            pending_state.effective_nonce,
+            account_committed_info_by_id.nonce + 1
+        );
+        assert_eq!(
+            pending_state.projected_balances,
+            account_committed_info_by_id.balances
+        );
+
+        let response = client.account_queued_txs(&account_id.to_string()).await?;
+        let queued_txs: Option<AccountQueuedTxs> = deserialize_response_result(response)?;
+        let queued_txs = queued_txs.unwrap();
+        assert_eq!(queued_txs.account_id, account_id);
+        assert_eq!(queued_txs.txs.len(), 1);
+        assert_eq!(queued_txs.txs[0].tx_hash, pending_tx.hash());
+        assert_eq!(queued_txs.txs[0].nonce, account_committed_info_by_id.nonce);
+        assert!(queued_txs.txs[0].queue_position < queued_txs.queue_size);
+        server
+            .pool
+            .access_storage()
+            .await?
+            .chain()
+            .mempool_schema()
+            .remove_tx(pending_tx.hash().as_ref())
+            .await?;
+
         {
             let mut storage = server.pool.access_storage().await?;
             storage
@@ -682,6 +1671,58 @@ mod tests {
         assert_eq!(account_full_info.finalized, account_finalized_info);
         assert_eq!(account_full_info.depositing, expected_depositing);
 
+        // The state at the last block is the committed state.
+        let last_saved_block = server
+            .pool
+            .access_storage()
+            .await?
+            .chain()
+            .block_schema()
+            .get_last_saved_block()
+            .await?;
+        let response = client
+            .account_state_at_block(&account_id.to_string(), last_saved_block)
+            .await?;
+        let state_at_block: Option<Account> = deserialize_response_result(response)?;
+        let state_at_block = state_at_block.unwrap();
+        let committed = account_full_info.committed.unwrap();
+        assert_eq!(state_at_block.nonce, committed.nonce);
+        assert_eq!(state_at_block.balances, committed.balances);
+
+        let response = client
+            .account_state_at_block(&account_id.to_string(), last_saved_block + 1)
+            .await?;
+        let error = serde_json::from_value::<Error>(response.error.unwrap()).unwrap();
+        assert_eq!(error.code, ErrorCode::BlockNotFound);
+
+        // The test blocks have the dummy root hashes, so only the block validation is checked,
+        // the proofs themselves are checked by the unit test.
+        let query = IncomingAccountProofQuery {
+            token: Some("0".to_string()),
+            block: Some(last_saved_block + 1),
+        };
+        let response = client
+            .account_proof(&account_id.to_string(), &query)
+            .await?;
+        let error = serde_json::from_value::<Error>(response.error.unwrap()).unwrap();
+        assert_eq!(error.code, ErrorCode::BlockNotFound);
+
+        // The exit proof is only requested, since the generator isn't run by the test server.
+        let query = IncomingExitProofQuery {
+            token: "ETH".to_string(),
+        };
+        let response = client
+            .request_exit_proof(&account_id.to_string(), &query)
+            .await?;
+        let request: Option<ExitProofRequest> = deserialize_response_result(response)?;
+        let request = request.unwrap();
+        assert_eq!(request.account_id, account_id);
+        assert_eq!(request.token_id, TokenId(0));
+        assert!(request.proof_data.is_none());
+        let response = client.exit_proof(&account_id.to_string(), &query).await?;
+        let stored_request: Option<ExitProofRequest> = deserialize_response_result(response)?;
+        assert_eq!(stored_request.unwrap().block_number, request.block_number);
+
         let query = PaginationQuery {
             from: ApiEither::from(tx_hash),
             limit: 1,
@@ -691,6 +1732,32 @@ mod tests {
         let txs: Paginated<Transaction, TxHash> = deserialize_response_result(response)?;
         assert_eq!(txs.list[0].tx_hash, tx_hash);
 
+        let filters = AccountTxsFilters {
+            tx_type: Some("Unknown".to_string()),
+            ..Default::default()
+        };
+        let response = client
+            .account_txs_filtered(&query, &account_id.to_string(), &filters)
+            .await?;
+        let error = serde_json::from_value::<Error>(response.error.unwrap()).unwrap();
+        assert_eq!(error.code, ErrorCode::InvalidTxType);
+
+        // The whole history is exported without pagination.
+        let filters = AccountTxsFilters::default();
+        let csv = client
+            .account_txs_export(&account_id.to_string(), "text/csv", &filters)
+            .await?;
+        let ndjson = client
+            .account_txs_export(&account_id.to_string(), "application/x-ndjson", &filters)
+            .await?;
+        let txs: Vec<AccountTxExport> = ndjson
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert!(txs.iter().any(|tx| tx.tx_hash == tx_hash));
+        assert!(csv.starts_with("txHash,blockNumber,"));
+        assert_eq!(csv.lines().count(), txs.len() + 1);
+
         let query = PaginationQuery {
             from: ApiEither::from_str("latest").unwrap(),
             limit: 2,
@@ -701,6 +1768,20 @@ mod tests {
             .await?;
         let txs: Paginated<Transaction, SerialId> = deserialize_response_result(response)?;
         assert_eq!(txs.list.len(), 2);
+
+        // The total count doesn't depend on the page size.
+        let query = PaginationQuery {
+            from: ApiEither::from_str("latest").unwrap(),
+            limit: 1,
+            direction: PaginationDirection::Older,
+        };
+        let response = client
+            .account_pending_txs(&query, &account_id.to_string())
+            .await?;
+        let first_page: Paginated<Transaction, SerialId> = deserialize_response_result(response)?;
+        assert_eq!(first_page.list.len(), 1);
+        assert_eq!(first_page.pagination.count, txs.pagination.count);
+        assert!(txs.pagination.count >= 2);
         match &txs.list[0].op {
             TransactionData::L1(tx) => match tx {
                 L1Transaction::Deposit(deposit) => {
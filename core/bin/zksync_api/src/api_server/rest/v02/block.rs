@@ -10,7 +10,7 @@ use actix_web::{web, Scope};
 // Workspace uses
 use zksync_api_types::v02::{
     block::{BlockInfo, BlockStatus},
-    pagination::{parse_query, ApiEither, BlockAndTxHash, Paginated, PaginationQuery},
+    pagination::{ApiEither, BlockAndTxHash, Paginated, PaginationQuery, PaginationQueryOrCursor},
     transaction::{Transaction, TxData, TxHashSerializeWrapper},
 };
 use zksync_crypto::{convert::FeConvert, Fr};
@@ -20,8 +20,10 @@ use zksync_types::{tx::TxHash, BlockNumber, H256};
 // Local uses
 use super::{
     error::{Error, InvalidDataError},
+    extractors::{PathParam, ValidPath},
     paginate_trait::Paginate,
-    response::ApiResult,
+    pagination_cursor::PaginationCursors,
+    response::{ApiResult, FieldsQuery, SparseFields},
 };
 use crate::{api_try, utils::block_details_cache::BlockDetailsCache};
 
@@ -48,6 +50,29 @@ pub fn block_info_from_details(details: StorageBlockDetails) -> BlockInfo {
     }
 }
 
+/// Block is identified either by its number or by its position in the chain.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BlockPosition {
+    Number(BlockNumber),
+    LastCommitted,
+    LastFinalized,
+}
+
+impl PathParam for BlockPosition {
+    const NAME: &'static str = "block_position";
+
+    fn from_param(param: &str) -> Result<Self, Error> {
+        if let Ok(number) = u32::from_str(param) {
+            return Ok(Self::Number(BlockNumber(number)));
+        }
+        match param {
+            "lastCommitted" => Ok(Self::LastCommitted),
+            "lastFinalized" => Ok(Self::LastFinalized),
+            _ => Err(Error::from(InvalidDataError::InvalidBlockPosition)),
+        }
+    }
+}
+
 /// Shared data between `api/v0.2/blocks` endpoints.
 #[derive(Debug, Clone)]
 struct ApiBlockData {
@@ -81,37 +106,35 @@ impl ApiBlockData {
 
     async fn get_block_number_by_position(
         &self,
-        block_position: &str,
+        block_position: BlockPosition,
     ) -> Result<BlockNumber, Error> {
-        if let Ok(number) = u32::from_str(block_position) {
-            Ok(BlockNumber(number))
-        } else {
-            match block_position {
-                "lastCommitted" => self
-                    .get_last_committed_block_number()
-                    .await
-                    .map_err(Error::storage),
-                "lastFinalized" => self
-                    .get_last_finalized_block_number()
-                    .await
-                    .map_err(Error::storage),
-                _ => Err(Error::from(InvalidDataError::InvalidBlockPosition)),
-            }
+        match block_position {
+            BlockPosition::Number(number) => Ok(number),
+            BlockPosition::LastCommitted => self
+                .get_last_committed_block_number()
+                .await
+                .map_err(Error::storage),
+            BlockPosition::LastFinalized => self
+                .get_last_finalized_block_number()
+                .await
+                .map_err(Error::storage),
         }
     }
 
     async fn block_page(
         &self,
         query: PaginationQuery<ApiEither<BlockNumber>>,
+        cursors: &PaginationCursors,
     ) -> Result<Paginated<BlockInfo, BlockNumber>, Error> {
         let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
-        storage.paginate_checked(&query).await
+        storage.paginate_with_cursor(query, cursors).await
     }
 
     async fn transaction_page(
         &self,
         block_number: BlockNumber,
         query: PaginationQuery<ApiEither<TxHash>>,
+        cursors: &PaginationCursors,
     ) -> Result<Paginated<Transaction, TxHashSerializeWrapper>, Error> {
         let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
 
@@ -124,7 +147,7 @@ impl ApiBlockData {
             direction: query.direction,
         };
 
-        storage.paginate_checked(&new_query).await
+        storage.paginate_with_cursor(new_query, cursors).await
     }
 
     async fn tx_data(
@@ -164,37 +187,42 @@ impl ApiBlockData {
 
 async fn block_pagination(
     data: web::Data<ApiBlockData>,
-    web::Query(query): web::Query<PaginationQuery<String>>,
-) -> ApiResult<Paginated<BlockInfo, BlockNumber>> {
+    cursors: web::Data<PaginationCursors>,
+    web::Query(query): web::Query<PaginationQueryOrCursor>,
+    web::Query(fields): web::Query<FieldsQuery>,
+) -> ApiResult<SparseFields<Paginated<BlockInfo, BlockNumber>>> {
     let start = Instant::now();
-    let query = api_try!(parse_query(query).map_err(Error::from));
-    let res = data.block_page(query).await.into();
+    let query = api_try!(cursors.parse_query(query));
+    let res = ApiResult::from(data.block_page(query, &cursors).await).with_fields(&fields);
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "block_pagination");
     res
 }
 
-// TODO: take `block_position` as enum.
-// Currently actix path extractor doesn't work with enums: https://github.com/actix/actix-web/issues/318 (ZKS-628)
 async fn block_by_position(
     data: web::Data<ApiBlockData>,
-    block_position: web::Path<String>,
-) -> ApiResult<Option<BlockInfo>> {
+    ValidPath(block_position): ValidPath<BlockPosition>,
+    web::Query(fields): web::Query<FieldsQuery>,
+) -> ApiResult<SparseFields<Option<BlockInfo>>> {
     let start = Instant::now();
-    let block_number = api_try!(data.get_block_number_by_position(&block_position).await);
-    let res = data.block_info(block_number).await.into();
+    let block_number = api_try!(data.get_block_number_by_position(block_position).await);
+    let res = ApiResult::from(data.block_info(block_number).await).with_fields(&fields);
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "block_by_position");
     res
 }
 
 async fn block_transactions(
     data: web::Data<ApiBlockData>,
-    block_position: web::Path<String>,
-    web::Query(query): web::Query<PaginationQuery<String>>,
+    cursors: web::Data<PaginationCursors>,
+    ValidPath(block_position): ValidPath<BlockPosition>,
+    web::Query(query): web::Query<PaginationQueryOrCursor>,
 ) -> ApiResult<Paginated<Transaction, TxHashSerializeWrapper>> {
     let start = Instant::now();
-    let block_number = api_try!(data.get_block_number_by_position(&block_position).await);
-    let query = api_try!(parse_query(query).map_err(Error::from));
-    let res = data.transaction_page(block_number, query).await.into();
+    let block_number = api_try!(data.get_block_number_by_position(block_position).await);
+    let query = api_try!(cursors.parse_query(query));
+    let res = data
+        .transaction_page(block_number, query, &cursors)
+        .await
+        .into();
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "block_transactions");
     res
 }
@@ -230,10 +258,8 @@ pub fn api_scope(pool: ConnectionPool, cache: BlockDetailsCache) -> Scope {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::api_server::rest::v02::{
-        test_utils::{deserialize_response_result, TestServerConfig},
-        SharedData,
-    };
+    use crate::api_server::rest::v02::{test_utils::TestServerConfig, SharedData};
+    use zksync_api_client::rest::v02::typed::TypedClient;
     use zksync_api_types::v02::{
         pagination::PaginationDirection, transaction::TransactionData, ApiVersion,
     };
@@ -255,28 +281,41 @@ mod tests {
             |cfg: &TestServerConfig| api_scope(cfg.pool.clone(), BlockDetailsCache::new(10)),
             Some(shared_data),
         );
+        let client = TypedClient::from(client);
 
         let query = PaginationQuery {
             from: ApiEither::from(BlockNumber(1)),
             limit: 3,
             direction: PaginationDirection::Newer,
         };
+        let cursors = PaginationCursors::from_config(&cfg.config.api.rest);
         let expected_blocks: Paginated<BlockInfo, BlockNumber> = {
             let mut storage = cfg.pool.access_storage().await?;
+            let query = PaginationQuery {
+                from: ApiEither::from(BlockNumber(1)),
+                limit: 3,
+                direction: PaginationDirection::Newer,
+            };
             storage
-                .paginate_checked(&query)
+                .paginate_with_cursor(query, &cursors)
                 .await
                 .map_err(|err| anyhow::anyhow!(err.message))?
         };
 
-        let response = client.block_by_position("2").await?;
-        let block: BlockInfo = deserialize_response_result(response)?;
-        assert_eq!(block, expected_blocks.list[1]);
+        let block = client.block_by_position("2").await?;
+        assert_eq!(block.as_ref(), Some(&expected_blocks.list[1]));
 
-        let response = client.block_pagination(&query).await?;
-        let paginated: Paginated<BlockInfo, BlockNumber> = deserialize_response_result(response)?;
+        let paginated = client.block_pagination(&query).await?;
         assert_eq!(paginated, expected_blocks);
 
+        if let Some(cursor) = &paginated.pagination.next_cursor {
+            let next_paginated = client.block_pagination_by_cursor(cursor).await?;
+            assert_eq!(
+                next_paginated.list[0].block_number,
+                BlockNumber(*paginated.list[2].block_number + 1)
+            );
+        }
+
         let block_number = BlockNumber(3);
         let expected_txs = {
             let mut storage = cfg.pool.access_storage().await?;
@@ -296,10 +335,9 @@ mod tests {
             direction: PaginationDirection::Older,
         };
 
-        let response = client
+        let paginated = client
             .block_transactions(&query, &*block_number.to_string())
             .await?;
-        let paginated: Paginated<Transaction, TxHash> = deserialize_response_result(response)?;
         assert_eq!(paginated.pagination.count as usize, expected_txs.len());
         assert_eq!(paginated.pagination.limit, query.limit);
         assert_eq!(paginated.list.len(), query.limit as usize);
@@ -323,14 +361,14 @@ mod tests {
             if !expected_tx.success {
                 continue;
             }
-            let response = client
+            let tx = client
                 .transaction_in_block(
                     expected_tx.block_number as u32,
                     expected_tx.block_index.unwrap() as u32,
                 )
-                .await?;
-            let tx: Option<TxData> = deserialize_response_result(response)?;
-            let tx = tx.unwrap().tx;
+                .await?
+                .unwrap()
+                .tx;
             assert_eq!(tx.created_at, Some(expected_tx.created_at));
             assert_eq!(*tx.block_number.unwrap(), expected_tx.block_number as u32);
             assert_eq!(tx.fail_reason, expected_tx.fail_reason);
@@ -0,0 +1,234 @@
+//! CREATE2 part of API implementation.
+//!
+//! The smart wallets deployed with CREATE2 set their public key hash by the `ChangePubKey`
+//! with the CREATE2 auth data instead of the Ethereum signature: the address of the wallet
+//! must be derived from the creator address, the salt and the code hash along with the new
+//! public key hash. These endpoints derive the address and check the auth data the same way
+//! the server does, so the integrators don't have to reimplement it.
+
+// Built-in uses
+use std::time::Instant;
+
+// External uses
+use actix_web::{
+    web::{self, Json},
+    Scope,
+};
+
+// Workspace uses
+use zksync_api_types::v02::{
+    create2::{Create2Address, Create2AddressRequest, Create2Validation, Create2ValidationRequest},
+    transaction::TxHashSerializeWrapper,
+};
+use zksync_types::{
+    tx::{ChangePubKey, ChangePubKeyCREATE2Data, ChangePubKeyEthAuthData, TxEthSignatureVariant},
+    Account, AccountId, Address, PubKeyHash, ZkSyncTx,
+};
+
+// Local uses
+use super::{
+    error::{Error, InvalidDataError},
+    response::ApiResult,
+};
+use crate::{api_server::tx_sender::TxSender, api_try};
+
+/// Shared data between `api/v0.2/create2` endpoints.
+#[derive(Clone)]
+struct ApiCreate2Data {
+    tx_sender: TxSender,
+}
+
+impl ApiCreate2Data {
+    fn new(tx_sender: TxSender) -> Self {
+        Self { tx_sender }
+    }
+
+    async fn validate(
+        &self,
+        request: Create2ValidationRequest,
+    ) -> Result<Create2Validation, Error> {
+        let mut storage = self
+            .tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?;
+        let account_state = storage
+            .chain()
+            .account_schema()
+            .account_state_by_address(request.account)
+            .await
+            .map_err(Error::storage)?
+            .committed;
+
+        Ok(check_create2_data(
+            request.account,
+            &request.pub_key_hash,
+            &request.auth_data,
+            account_state,
+        ))
+    }
+
+    /// Submits the `ChangePubKey` with the CREATE2 auth data, the transaction is checked
+    /// before the submission so the invalid auth data is reported with the reason.
+    async fn submit(&self, tx: ChangePubKey) -> Result<TxHashSerializeWrapper, Error> {
+        let auth_data = match &tx.eth_auth_data {
+            Some(ChangePubKeyEthAuthData::CREATE2(auth_data)) => auth_data.clone(),
+            _ => {
+                return Err(invalid_auth_data(
+                    "ChangePubKey should have the CREATE2 auth data",
+                ))
+            }
+        };
+        let validation = self
+            .validate(Create2ValidationRequest {
+                account: tx.account,
+                pub_key_hash: tx.new_pk_hash,
+                auth_data,
+            })
+            .await?;
+        if let Some(reason) = validation.reason {
+            return Err(invalid_auth_data(reason));
+        }
+        if validation.account_id != Some(tx.account_id) {
+            return Err(invalid_auth_data(
+                "Account id of the ChangePubKey doesn't match the account",
+            ));
+        }
+
+        self.tx_sender
+            .submit_tx(
+                ZkSyncTx::ChangePubKey(Box::new(tx)),
+                TxEthSignatureVariant::Single(None),
+                None,
+            )
+            .await
+            .map(TxHashSerializeWrapper)
+            .map_err(Error::from)
+    }
+}
+
+fn invalid_auth_data(reason: impl Into<String>) -> Error {
+    Error::from(InvalidDataError::InvalidCreate2AuthData(reason.into()))
+}
+
+/// Checks the CREATE2 auth data of the `ChangePubKey` of the account with the committed state.
+fn check_create2_data(
+    account: Address,
+    pub_key_hash: &PubKeyHash,
+    auth_data: &ChangePubKeyCREATE2Data,
+    account_state: Option<(AccountId, Account)>,
+) -> Create2Validation {
+    let expected_address = auth_data.get_address(pub_key_hash);
+    let account_id = account_state.as_ref().map(|(id, _)| *id);
+
+    let reason = if expected_address != account {
+        Some("CREATE2 data doesn't derive the address of the account")
+    } else {
+        match &account_state {
+            None => Some("Account does not exist, the funds should be deposited to it first"),
+            // The CREATE2 auth data can only be used for the first `ChangePubKey` of the account.
+            Some((_, state)) if *state.nonce != 0 => {
+                Some("CREATE2 ChangePubKey is only allowed for the account with zero nonce")
+            }
+            Some(_) => None,
+        }
+    };
+
+    Create2Validation {
+        expected_address,
+        account_id,
+        valid: reason.is_none(),
+        reason: reason.map(String::from),
+    }
+}
+
+// Server implementation
+
+async fn create2_address(Json(body): Json<Create2AddressRequest>) -> ApiResult<Create2Address> {
+    let start = Instant::now();
+    let address = body.create2_data.get_address(&body.pub_key_hash);
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "create2_address");
+    ApiResult::Ok(Create2Address { address })
+}
+
+async fn validate_create2(
+    data: web::Data<ApiCreate2Data>,
+    Json(body): Json<Create2ValidationRequest>,
+) -> ApiResult<Create2Validation> {
+    let start = Instant::now();
+    let res = data.validate(body).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "validate_create2");
+    res
+}
+
+async fn submit_create2_change_pubkey(
+    data: web::Data<ApiCreate2Data>,
+    Json(body): Json<ChangePubKey>,
+) -> ApiResult<TxHashSerializeWrapper> {
+    let start = Instant::now();
+    let tx_hash = api_try!(data.submit(body).await);
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "submit_create2_change_pubkey");
+    ApiResult::Ok(tx_hash)
+}
+
+pub fn api_scope(tx_sender: TxSender) -> Scope {
+    let data = ApiCreate2Data::new(tx_sender);
+
+    web::scope("create2")
+        .app_data(web::Data::new(data))
+        .route("address", web::post().to(create2_address))
+        .route("validate", web::post().to(validate_create2))
+        .route(
+            "change_pubkey",
+            web::post().to(submit_create2_change_pubkey),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::{Nonce, H256};
+
+    fn create2_data() -> ChangePubKeyCREATE2Data {
+        ChangePubKeyCREATE2Data {
+            creator_address: Address::repeat_byte(0x11),
+            salt_arg: H256::repeat_byte(0x22),
+            code_hash: H256::repeat_byte(0x33),
+        }
+    }
+
+    #[test]
+    fn create2_data_validation() {
+        let pub_key_hash =
+            PubKeyHash::from_hex("sync:0102030405060708091011121314151617181920").unwrap();
+        let auth_data = create2_data();
+        let address = auth_data.get_address(&pub_key_hash);
+        let account_state = |nonce| {
+            let mut account = Account::default_with_address(&address);
+            account.nonce = Nonce(nonce);
+            Some((AccountId(7), account))
+        };
+
+        let validation = check_create2_data(address, &pub_key_hash, &auth_data, account_state(0));
+        assert_eq!(validation.expected_address, address);
+        assert_eq!(validation.account_id, Some(AccountId(7)));
+        assert!(validation.valid);
+        assert!(validation.reason.is_none());
+
+        // The address is derived from the new public key hash as well.
+        let other_hash =
+            PubKeyHash::from_hex("sync:2020202020202020202020202020202020202020").unwrap();
+        let validation = check_create2_data(address, &other_hash, &auth_data, account_state(0));
+        assert_ne!(validation.expected_address, address);
+        assert!(!validation.valid);
+
+        let validation = check_create2_data(address, &pub_key_hash, &auth_data, None);
+        assert_eq!(validation.account_id, None);
+        assert!(!validation.valid);
+
+        let validation = check_create2_data(address, &pub_key_hash, &auth_data, account_state(1));
+        assert!(!validation.valid);
+        assert!(validation.reason.is_some());
+    }
+}
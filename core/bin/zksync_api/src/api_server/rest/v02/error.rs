@@ -3,6 +3,7 @@ use std::fmt::{Display, Formatter};
 
 // External uses
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use thiserror::Error;
 
@@ -11,37 +12,165 @@ use zksync_api_types::v02::pagination::{UnknownFromParameter, MAX_LIMIT};
 use zksync_crypto::params::MIN_NFT_TOKEN_ID;
 
 // Local uses
+use super::{
+    idempotency::MAX_IDEMPOTENCY_KEY_LENGTH,
+    token::{MAX_PRICE_HISTORY_POINTS, MAX_TOKENS_BATCH_SIZE},
+};
 use crate::{api_server::tx_sender::SubmitError, fee_ticker::PriceError};
 
-#[derive(Serialize_repr, Debug, Deserialize_repr, Clone, PartialEq)]
-#[repr(u16)]
-pub enum ErrorCode {
+/// Declares the error codes along with their catalog, the doc comment of each code
+/// is its description returned by the `/errors` endpoint.
+macro_rules! error_codes {
+    ($($(#[doc = $doc:literal])+ $name:ident = $code:literal,)+) => {
+        #[derive(Serialize_repr, Debug, Deserialize_repr, Clone, Copy, PartialEq)]
+        #[repr(u16)]
+        pub enum ErrorCode {
+            $($(#[doc = $doc])+ $name = $code,)+
+        }
+
+        impl ErrorCode {
+            /// All the error codes in the ascending order.
+            pub const ALL: &'static [ErrorCode] = &[$(ErrorCode::$name,)+];
+
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(Self::$name => stringify!($name),)+
+                }
+            }
+
+            pub fn description(self) -> &'static str {
+                match self {
+                    $(Self::$name => concat!($($doc),+).trim(),)+
+                }
+            }
+        }
+    };
+}
+
+error_codes! {
+    /// The error which should never be returned.
     UnreacheableError = 0,
+    /// The request to the core API server has failed.
     CoreApiError = 100,
+    /// The price can't be shown in the token with the zero price.
     TokenZeroPriceError = 200,
+    /// The currency of the price can't be parsed.
     InvalidCurrency = 201,
+    /// The block position can't be parsed.
     InvalidBlockPosition = 202,
+    /// The account id or address can't be parsed.
     InvalidAccountIdOrAddress = 203,
+    /// The account is not found.
     AccountNotFound = 204,
+    /// The transaction is not found.
     TransactionNotFound = 205,
+    /// The pagination limit exceeds the maximum one.
     PaginationLimitTooBig = 206,
+    /// The query parameters can't be deserialized.
     QueryDeserializationError = 207,
+    /// The token id is not the id of an NFT.
     InvalidNFTTokenId = 208,
+    /// The price smoothing window is out of range.
+    InvalidSmoothingWindow = 209,
+    /// The price smoothing is not supported for the currency.
+    SmoothingNotSupported = 210,
+    /// The number of the requested tokens exceeds the maximum one.
+    TooManyTokens = 211,
+    /// The list of the token ids can't be parsed.
+    InvalidTokenIds = 212,
+    /// The price history range is empty or too big.
+    InvalidPriceHistoryRange = 213,
+    /// The list of the token includes can't be parsed.
+    InvalidTokenIncludes = 214,
+    /// The pagination cursor is invalid or expired.
+    InvalidPaginationCursor = 215,
+    /// Neither the pagination cursor nor the pagination parameters are provided.
+    MissingPaginationParameters = 216,
+    /// The fee history range is empty.
+    InvalidFeeHistoryRange = 217,
+    /// The idempotency key is empty or too long.
+    InvalidIdempotencyKey = 218,
+    /// The idempotency key is already used for another request.
+    IdempotencyKeyReused = 219,
+    /// The block is not committed yet.
+    BlockNotFound = 220,
+    /// The history of the block is pruned, so the state at the block is not available.
+    BlockHistoryPruned = 221,
+    /// The transaction type of the filter is unknown.
+    InvalidTxType = 222,
+    /// The priority operation id is neither the serial id nor the hash of the L1 transaction.
+    InvalidPriorityOpId = 223,
+    /// The ForcedExit of the account is not possible, the reason is in the message.
+    ForcedExitNotPossible = 224,
+    /// The balance of the ForcedExit target doesn't cover the fee of the ForcedExit.
+    ForcedExitNotViable = 225,
+    /// The ForcedExit request is not found.
+    ForcedExitRequestNotFound = 226,
+    /// The CREATE2 auth data of the `ChangePubKey` is invalid, the reason is in the message.
+    InvalidCreate2AuthData = 227,
+    /// The parameters of the order are invalid, the reason is in the message.
+    InvalidOrderParams = 228,
+    /// The path parameters can't be parsed.
+    InvalidPathParams = 229,
+    /// The request body can't be deserialized.
+    BodyDeserializationError = 230,
+    /// The finality is requested for the transaction other than withdrawal.
+    NotWithdrawal = 231,
+    /// The withdrawal is rejected, so its funds are not withdrawn.
+    WithdrawalRejected = 232,
+    /// The request with the same idempotency key is being submitted.
+    IdempotentRequestInProgress = 233,
+    /// The token is neither the token id, address nor symbol.
+    InvalidTokenLike = 234,
+    /// The database request has failed.
     StorageError = 300,
+    /// The token is not found.
     TokenNotFound = 500,
+    /// The request to the external price API has failed.
     ExternalApiError = 501,
+    /// The internal error of the transaction submission.
     InternalError = 600,
+    /// The `Close` transactions are disabled.
     AccountCloseDisabled = 601,
+    /// The parameters of the submission are invalid.
     InvalidParams = 602,
+    /// The fast processing is requested for the transaction other than withdrawal.
     UnsupportedFastProcessing = 603,
+    /// The transaction is incorrect.
     IncorrectTx = 604,
+    /// The transaction is rejected by the mempool, the reason is in the details.
     TxAddError = 605,
+    /// The token can't be used to pay the fees.
     InappropriateFeeToken = 606,
+    /// The request to the core server has failed.
     CommunicationCoreServer = 607,
+    /// The 2FA can't be toggled, the reason is in the details.
     Toggle2FAError = 608,
+    /// The submissions are rejected in the read-only mode, they may be retried later.
+    ReadOnlyMode = 609,
+    /// The unclassified error.
     Other = 60_000,
 }
 
+/// Entry of the error catalog.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorCodeInfo {
+    pub code: ErrorCode,
+    pub name: String,
+    pub description: String,
+}
+
+impl From<ErrorCode> for ErrorCodeInfo {
+    fn from(code: ErrorCode) -> Self {
+        Self {
+            code,
+            name: code.name().to_string(),
+            description: code.description().to_string(),
+        }
+    }
+}
+
 /// Error object in a response
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -49,6 +178,9 @@ pub struct Error {
     pub error_type: String,
     pub code: ErrorCode,
     pub message: String,
+    /// Machine-readable details of the error, if there are any.
+    #[serde(default)]
+    pub details: Option<Value>,
 }
 
 /// Trait that can be used to map custom errors to the object.
@@ -60,6 +192,10 @@ pub trait ApiError: std::fmt::Display {
     fn message(&self) -> String {
         self.to_string()
     }
+
+    fn details(&self) -> Option<Value> {
+        None
+    }
 }
 
 impl<T> From<T> for Error
@@ -71,6 +207,7 @@ where
             error_type: t.error_type(),
             code: t.code(),
             message: t.message(),
+            details: t.details(),
         }
     }
 }
@@ -95,7 +232,7 @@ pub enum InvalidDataError {
     InvalidAccountIdOrAddress,
     #[error("Account is not found")]
     AccountNotFound,
-    #[error("Cannot parse currency. There are only token_id, usd options")]
+    #[error("Cannot parse currency. There are only token_id, usd, eur, gbp, jpy options")]
     InvalidCurrency,
     #[error("Transaction is not found")]
     TransactionNotFound,
@@ -103,6 +240,66 @@ pub enum InvalidDataError {
     PaginationLimitTooBig,
     #[error("NFT token ID should be greater than or equal to {}", MIN_NFT_TOKEN_ID)]
     InvalidNFTTokenId,
+    #[error("Smoothing window should be positive and not exceed the stored price points")]
+    InvalidSmoothingWindow,
+    #[error("Smoothing is supported only for the usd currency")]
+    SmoothingNotSupported,
+    #[error("Number of tokens should not exceed {}", MAX_TOKENS_BATCH_SIZE)]
+    TooManyTokens,
+    #[error("Token ids should be a comma-separated list of numbers")]
+    InvalidTokenIds,
+    #[error(
+        "Price history range should be non-empty and contain at most {} intervals",
+        MAX_PRICE_HISTORY_POINTS
+    )]
+    InvalidPriceHistoryRange,
+    #[error("Token includes should be a comma-separated list of price, volume options")]
+    InvalidTokenIncludes,
+    #[error("Pagination cursor is invalid")]
+    InvalidPaginationCursor,
+    #[error("Either the cursor or the from, limit and direction pagination parameters should be provided")]
+    MissingPaginationParameters,
+    #[error("Fee history range should be non-empty")]
+    InvalidFeeHistoryRange,
+    #[error(
+        "Idempotency key should be a non-empty string of at most {} characters",
+        MAX_IDEMPOTENCY_KEY_LENGTH
+    )]
+    InvalidIdempotencyKey,
+    #[error("Idempotency key is already used for another request")]
+    IdempotencyKeyReused,
+    #[error("Request with the same idempotency key is being submitted")]
+    IdempotentRequestInProgress,
+    #[error("Block is not found")]
+    BlockNotFound,
+    #[error("History of the block is pruned")]
+    BlockHistoryPruned,
+    #[error("Unknown transaction type")]
+    InvalidTxType,
+    #[error("Cannot parse priority operation id. It should be either the serial id or the hash of the L1 transaction")]
+    InvalidPriorityOpId,
+    #[error("Cannot parse token. It should be either the token id, address or symbol")]
+    InvalidTokenLike,
+    #[error("ForcedExit is not possible: {0}")]
+    ForcedExitNotPossible(String),
+    #[error("Balance of the target account doesn't cover the ForcedExit fee")]
+    ForcedExitNotViable,
+    #[error("ForcedExit request is not found")]
+    ForcedExitRequestNotFound,
+    #[error("CREATE2 auth data is invalid: {0}")]
+    InvalidCreate2AuthData(String),
+    #[error("Order parameters are invalid: {0}")]
+    InvalidOrderParams(String),
+    #[error("Cannot parse path parameters: {0}")]
+    InvalidPathParams(String),
+    #[error("Cannot deserialize query parameters: {0}")]
+    QueryDeserializationError(String),
+    #[error("Cannot deserialize request body: {0}")]
+    BodyDeserializationError(String),
+    #[error("Transaction is not a withdrawal")]
+    NotWithdrawal,
+    #[error("Withdrawal is rejected")]
+    WithdrawalRejected,
 }
 
 impl ApiError for InvalidDataError {
@@ -120,6 +317,33 @@ impl ApiError for InvalidDataError {
             Self::TransactionNotFound => ErrorCode::TransactionNotFound,
             Self::PaginationLimitTooBig => ErrorCode::PaginationLimitTooBig,
             Self::InvalidNFTTokenId => ErrorCode::InvalidNFTTokenId,
+            Self::InvalidSmoothingWindow => ErrorCode::InvalidSmoothingWindow,
+            Self::SmoothingNotSupported => ErrorCode::SmoothingNotSupported,
+            Self::TooManyTokens => ErrorCode::TooManyTokens,
+            Self::InvalidTokenIds => ErrorCode::InvalidTokenIds,
+            Self::InvalidPriceHistoryRange => ErrorCode::InvalidPriceHistoryRange,
+            Self::InvalidTokenIncludes => ErrorCode::InvalidTokenIncludes,
+            Self::InvalidPaginationCursor => ErrorCode::InvalidPaginationCursor,
+            Self::MissingPaginationParameters => ErrorCode::MissingPaginationParameters,
+            Self::InvalidFeeHistoryRange => ErrorCode::InvalidFeeHistoryRange,
+            Self::InvalidIdempotencyKey => ErrorCode::InvalidIdempotencyKey,
+            Self::IdempotencyKeyReused => ErrorCode::IdempotencyKeyReused,
+            Self::IdempotentRequestInProgress => ErrorCode::IdempotentRequestInProgress,
+            Self::BlockNotFound => ErrorCode::BlockNotFound,
+            Self::BlockHistoryPruned => ErrorCode::BlockHistoryPruned,
+            Self::InvalidTxType => ErrorCode::InvalidTxType,
+            Self::InvalidPriorityOpId => ErrorCode::InvalidPriorityOpId,
+            Self::InvalidTokenLike => ErrorCode::InvalidTokenLike,
+            Self::ForcedExitNotPossible(_) => ErrorCode::ForcedExitNotPossible,
+            Self::ForcedExitNotViable => ErrorCode::ForcedExitNotViable,
+            Self::ForcedExitRequestNotFound => ErrorCode::ForcedExitRequestNotFound,
+            Self::InvalidCreate2AuthData(_) => ErrorCode::InvalidCreate2AuthData,
+            Self::InvalidOrderParams(_) => ErrorCode::InvalidOrderParams,
+            Self::InvalidPathParams(_) => ErrorCode::InvalidPathParams,
+            Self::QueryDeserializationError(_) => ErrorCode::QueryDeserializationError,
+            Self::BodyDeserializationError(_) => ErrorCode::BodyDeserializationError,
+            Self::NotWithdrawal => ErrorCode::NotWithdrawal,
+            Self::WithdrawalRejected => ErrorCode::WithdrawalRejected,
         }
     }
 }
@@ -200,10 +424,19 @@ impl ApiError for SubmitError {
             Self::MempoolCommunication(_) => ErrorCode::CommunicationCoreServer,
             Self::Internal(_) => ErrorCode::InternalError,
             Self::Toggle2FA(_) => ErrorCode::Toggle2FAError,
+            Self::ReadOnlyMode(_) => ErrorCode::ReadOnlyMode,
             Self::Other(_) => ErrorCode::Other,
             Self::PriceError(_) => ErrorCode::InternalError,
         }
     }
+
+    fn details(&self) -> Option<Value> {
+        match self {
+            Self::TxAdd(err) => Some(json!({ "reason": err })),
+            Self::Toggle2FA(err) => Some(json!({ "reason": err.to_string() })),
+            _ => None,
+        }
+    }
 }
 
 impl ApiError for PriceError {
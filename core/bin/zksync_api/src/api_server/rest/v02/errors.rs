@@ -0,0 +1,114 @@
+//! Error catalog part of API implementation.
+
+// Built-in uses
+use std::time::{Duration, Instant};
+
+// External uses
+use actix_web::{web, Scope};
+
+// Local uses
+use super::{
+    error::{ErrorCode, ErrorCodeInfo},
+    response::{ApiResult, CachedApiResult},
+};
+
+/// The catalog changes only with the server version.
+const ERROR_CODES_MAX_AGE: Duration = Duration::from_secs(60 * 60);
+
+// Server implementation
+
+async fn error_codes() -> CachedApiResult<Vec<ErrorCodeInfo>> {
+    let start = Instant::now();
+    let codes = ErrorCode::ALL
+        .iter()
+        .copied()
+        .map(ErrorCodeInfo::from)
+        .collect();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "error_codes");
+    ApiResult::Ok(codes).cached(ERROR_CODES_MAX_AGE)
+}
+
+pub fn api_scope() -> Scope {
+    web::scope("errors").route("", web::get().to(error_codes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::{
+        rest::v02::{
+            error::{Error, InvalidDataError},
+            test_utils::{deserialize_response_result, TestServerConfig},
+            SharedData,
+        },
+        tx_sender::SubmitError,
+    };
+    use serde_json::json;
+    use std::collections::HashSet;
+    use zksync_api_types::v02::ApiVersion;
+    use zksync_types::tx::error::TxAddError;
+
+    #[test]
+    fn error_codes_are_documented() {
+        let codes = ErrorCode::ALL
+            .iter()
+            .map(|code| *code as u16)
+            .collect::<Vec<_>>();
+        let mut sorted = codes.clone();
+        sorted.sort_unstable();
+        sorted.dedup();
+        assert_eq!(codes, sorted);
+
+        let names = ErrorCode::ALL
+            .iter()
+            .map(|code| code.name())
+            .collect::<HashSet<_>>();
+        assert_eq!(names.len(), ErrorCode::ALL.len());
+        for code in ErrorCode::ALL {
+            assert!(!code.description().is_empty());
+            assert!(!code.description().starts_with(' '));
+        }
+        assert_eq!(ErrorCode::ReadOnlyMode.name(), "ReadOnlyMode");
+    }
+
+    #[test]
+    fn error_details() {
+        let error = Error::from(SubmitError::TxAdd(TxAddError::NonceMismatch));
+        assert_eq!(error.code, ErrorCode::TxAddError);
+        assert_eq!(error.details, Some(json!({ "reason": "NonceMismatch" })));
+        let serialized = serde_json::to_value(&error).unwrap();
+        assert_eq!(serialized["code"], 605);
+        assert_eq!(serialized["details"]["reason"], "NonceMismatch");
+
+        let error = Error::from(InvalidDataError::AccountNotFound);
+        assert_eq!(error.details, None);
+    }
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn errors_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+        let (client, server) =
+            cfg.start_server(|_: &TestServerConfig| api_scope(), Some(shared_data));
+        let response = client.error_codes().await?;
+        let codes: Vec<ErrorCodeInfo> = deserialize_response_result(response)?;
+        assert_eq!(codes.len(), ErrorCode::ALL.len());
+        let not_found = codes
+            .iter()
+            .find(|info| info.code == ErrorCode::AccountNotFound)
+            .unwrap();
+        assert_eq!(not_found.name, "AccountNotFound");
+        assert_eq!(not_found.description, "The account is not found.");
+
+        server.stop().await;
+        Ok(())
+    }
+}
@@ -0,0 +1,241 @@
+// Built-in uses
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::str::FromStr;
+use std::time::Duration;
+// External uses
+use actix::prelude::*;
+use actix_web::web::Bytes;
+use futures::channel::{mpsc, oneshot};
+// Workspace uses
+use zksync_storage::ConnectionPool;
+use zksync_types::{
+    event::{block::BlockStatus, transaction::TransactionStatus, EventData, EventId, ZkSyncEvent},
+    tx::TxHash,
+};
+// Local uses
+use super::messages::{Subscribe, WaitForTx};
+
+/// How often the new events are fetched from the database.
+const EVENT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// How often the keep-alive comment is sent, so that the idle connections
+/// are not closed by the proxies.
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Name of the SSE event sent when the block is sealed.
+const BLOCK_SEALED_EVENT: &str = "block_sealed";
+/// Name of the SSE event sent when the transaction is executed in a block.
+const TX_EXECUTED_EVENT: &str = "tx_executed";
+
+/// Formats the event as the `text/event-stream` message.
+/// Returns `None` for the events that are not streamed to the clients.
+fn format_event(event: &ZkSyncEvent) -> Option<Bytes> {
+    let name = match &event.data {
+        EventData::Block(block) if block.status == BlockStatus::Committed => BLOCK_SEALED_EVENT,
+        EventData::Transaction(tx) if tx.status == TransactionStatus::Committed => {
+            TX_EXECUTED_EVENT
+        }
+        _ => return None,
+    };
+    let data = serde_json::to_string(event).unwrap();
+    Some(Bytes::from(format!(
+        "id: {}\nevent: {}\ndata: {}\n\n",
+        *event.id, name, data
+    )))
+}
+
+/// The actor that polls the database for the events created by the committer
+/// and streams the sealed blocks and executed transactions to the clients.
+/// It also notifies the requests waiting for the events of the transactions.
+pub struct EventStreamManager {
+    pool: ConnectionPool,
+    subscribers: Vec<mpsc::Sender<Bytes>>,
+    tx_waiters: HashMap<TxHash, Vec<oneshot::Sender<()>>>,
+    /// The id of the last streamed event. `None` until the first poll.
+    last_event_id: Option<EventId>,
+    /// Whether the events are being fetched right now.
+    polling: bool,
+}
+
+impl EventStreamManager {
+    pub fn new(pool: ConnectionPool) -> Self {
+        Self {
+            pool,
+            subscribers: Vec::new(),
+            tx_waiters: HashMap::new(),
+            last_event_id: None,
+            polling: false,
+        }
+    }
+
+    fn poll_events(&mut self, ctx: &mut <Self as Actor>::Context) {
+        if self.polling {
+            return;
+        }
+        self.polling = true;
+
+        let pool = self.pool.clone();
+        let last_event_id = self.last_event_id;
+        async move {
+            let mut storage = pool.access_storage().await?;
+            let last_event_id = match last_event_id {
+                Some(id) => id,
+                // Only the events happened after the start are streamed.
+                None => {
+                    let id = storage.event_schema().get_last_event_id().await?;
+                    return Ok((id.unwrap_or_default(), Vec::new()));
+                }
+            };
+            let events = storage
+                .event_schema()
+                .fetch_new_events(last_event_id)
+                .await?
+                .into_iter()
+                .map(ZkSyncEvent::try_from)
+                .collect::<Result<Vec<_>, _>>()?;
+            let last_event_id = events.last().map_or(last_event_id, |event| event.id);
+            Ok((last_event_id, events))
+        }
+        .into_actor(self)
+        .map(
+            |result: anyhow::Result<(EventId, Vec<ZkSyncEvent>)>, act, _| {
+                act.polling = false;
+                match result {
+                    Ok((last_event_id, events)) => {
+                        act.last_event_id = Some(last_event_id);
+                        act.notify_tx_waiters(&events);
+                        if act.subscribers.is_empty() {
+                            return;
+                        }
+                        for message in events.iter().filter_map(format_event) {
+                            act.broadcast(message);
+                        }
+                    }
+                    // The events will be fetched again on the next poll.
+                    Err(err) => vlog::warn!("Can't fetch the new events: {}", err),
+                }
+            },
+        )
+        .spawn(ctx);
+    }
+
+    fn notify_tx_waiters(&mut self, events: &[ZkSyncEvent]) {
+        for event in events {
+            let tx_hash = match &event.data {
+                EventData::Transaction(tx) => TxHash::from_str(&tx.tx_hash).ok(),
+                _ => None,
+            };
+            let waiters = tx_hash.and_then(|tx_hash| self.tx_waiters.remove(&tx_hash));
+            for waiter in waiters.into_iter().flatten() {
+                // The request could have timed out already.
+                waiter.send(()).ok();
+            }
+        }
+        // Forget the waiters of the requests that have timed out.
+        self.tx_waiters.retain(|_, waiters| {
+            waiters.retain(|waiter| !waiter.is_canceled());
+            !waiters.is_empty()
+        });
+        metrics::gauge!(
+            "api.v02.event_stream.tx_waiters",
+            self.tx_waiters.len() as f64
+        );
+    }
+
+    fn broadcast(&mut self, message: Bytes) {
+        let subscribers = std::mem::take(&mut self.subscribers);
+        self.subscribers = subscribers
+            .into_iter()
+            .filter_map(
+                |mut subscriber| match subscriber.try_send(message.clone()) {
+                    Ok(()) => Some(subscriber),
+                    // Unlike the prices, the skipped events can't be restored by the
+                    // next update, so the lagging client is disconnected instead.
+                    // It's expected to reconnect and load the missed data via the REST API.
+                    Err(err) if err.is_full() => {
+                        metrics::increment_counter!("api.v02.event_stream.dropped_subscribers");
+                        None
+                    }
+                    // The client has disconnected.
+                    Err(_) => None,
+                },
+            )
+            .collect();
+    }
+}
+
+impl Actor for EventStreamManager {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        self.poll_events(ctx);
+        ctx.run_interval(EVENT_POLL_INTERVAL, |act, ctx| act.poll_events(ctx));
+        ctx.run_interval(KEEP_ALIVE_INTERVAL, |act, _| {
+            act.broadcast(Bytes::from_static(b": keep-alive\n\n"))
+        });
+    }
+
+    fn stopped(&mut self, _ctx: &mut Self::Context) {
+        vlog::warn!("EventStreamManager actor has stopped");
+    }
+}
+
+impl Handler<Subscribe> for EventStreamManager {
+    type Result = ();
+
+    fn handle(&mut self, msg: Subscribe, _ctx: &mut Self::Context) {
+        self.subscribers.push(msg.0);
+    }
+}
+
+impl Handler<WaitForTx> for EventStreamManager {
+    type Result = ();
+
+    fn handle(&mut self, msg: WaitForTx, _ctx: &mut Self::Context) {
+        self.tx_waiters
+            .entry(msg.tx_hash)
+            .or_default()
+            .push(msg.notify);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::event::{
+        test_data::{get_block_event, get_transaction_event},
+        transaction::TransactionType,
+    };
+    use zksync_types::{AccountId, TokenId};
+
+    #[test]
+    fn only_sealed_blocks_and_executed_txs_are_streamed() {
+        let mut event = get_block_event(BlockStatus::Committed);
+        event.id = EventId(5);
+        let message = format_event(&event).unwrap();
+        let message = std::str::from_utf8(&message).unwrap();
+        assert!(message.starts_with("id: 5\nevent: block_sealed\ndata: {"));
+        assert!(message.ends_with("}\n\n"));
+
+        let event = get_transaction_event(
+            TransactionType::Transfer,
+            AccountId(1),
+            TokenId(0),
+            TransactionStatus::Committed,
+        );
+        let message = format_event(&event).unwrap();
+        assert!(std::str::from_utf8(&message)
+            .unwrap()
+            .contains("event: tx_executed\n"));
+
+        assert!(format_event(&get_block_event(BlockStatus::Finalized)).is_none());
+        assert!(format_event(&get_block_event(BlockStatus::Reverted)).is_none());
+        let event = get_transaction_event(
+            TransactionType::Transfer,
+            AccountId(1),
+            TokenId(0),
+            TransactionStatus::Rejected,
+        );
+        assert!(format_event(&event).is_none());
+    }
+}
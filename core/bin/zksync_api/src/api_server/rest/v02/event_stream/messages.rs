@@ -0,0 +1,23 @@
+// Built-in uses
+// External uses
+use actix::prelude::*;
+use actix_web::web::Bytes;
+use futures::channel::{mpsc, oneshot};
+// Workspace uses
+use zksync_types::tx::TxHash;
+// Local uses
+
+/// Registers the channel of the connected client, the formatted
+/// events are sent to it until the client disconnects.
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct Subscribe(pub mpsc::Sender<Bytes>);
+
+/// Registers the waiter notified once the next event of the transaction is received.
+/// The waiter is dropped if the receiver is closed before that.
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct WaitForTx {
+    pub tx_hash: TxHash,
+    pub notify: oneshot::Sender<()>,
+}
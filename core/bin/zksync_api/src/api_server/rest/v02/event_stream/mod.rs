@@ -0,0 +1,45 @@
+//! Server-sent events stream of the sealed blocks and executed transactions.
+//!
+//! The events are created by the committer and stored in the database, the
+//! clients receive them as soon as they are noticed by the server, so they
+//! don't have to poll the blocks endpoint.
+//! Every message has the `block_sealed` or `tx_executed` type and contains
+//! the JSON-serialized `ZkSyncEvent` in its data.
+//!
+//! The same events wake up the long-polling requests waiting for the status
+//! of the transactions to change.
+
+// Built-in uses
+use std::convert::Infallible;
+// External uses
+use actix::prelude::*;
+use actix_web::{web, HttpResponse, Scope};
+use futures::{channel::mpsc, StreamExt};
+// Workspace uses
+// Local uses
+pub use manager::EventStreamManager;
+use messages::Subscribe;
+pub use messages::WaitForTx;
+
+mod manager;
+mod messages;
+
+/// Number of the events that can be queued for the client. The client
+/// that doesn't keep up with the events is disconnected.
+const CLIENT_BUFFER_CAPACITY: usize = 256;
+
+async fn subscribe(manager: web::Data<Addr<EventStreamManager>>) -> HttpResponse {
+    let (sender, receiver) = mpsc::channel(CLIENT_BUFFER_CAPACITY);
+    manager.do_send(Subscribe(sender));
+
+    HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(("Cache-Control", "no-cache"))
+        .streaming(receiver.map(Ok::<_, Infallible>))
+}
+
+pub fn api_scope(manager: Addr<EventStreamManager>) -> Scope {
+    web::scope("events")
+        .app_data(web::Data::new(manager))
+        .route("", web::get().to(subscribe))
+}
@@ -0,0 +1,245 @@
+//! Export of the account transaction history as CSV or NDJSON, which is requested
+//! by the `Accept` header of the account transactions endpoint.
+//!
+//! The export includes the whole history matching the filters, so it isn't paginated.
+//! The transactions are fetched from the database cursor in batches and streamed to the
+//! client as they are fetched, the next batch is fetched only once the client has read
+//! the buffered ones, so neither the server memory nor the query depend on the history size.
+
+// Built-in uses
+use std::io;
+
+// External uses
+use actix_web::{
+    http::header::ACCEPT,
+    web::{Bytes, BytesMut},
+    HttpRequest, HttpResponse,
+};
+use futures::{channel::mpsc, SinkExt};
+use serde_json::Value;
+
+// Workspace uses
+use zksync_api_types::v02::{account::AccountTxExport, pagination::AccountTxsFilters};
+use zksync_storage::ConnectionPool;
+use zksync_types::{Address, TokenId};
+
+/// Amount of the transactions fetched from the database cursor at once.
+const EXPORT_BATCH_SIZE: u32 = 1000;
+/// Amount of the fetched batches buffered for the client.
+const EXPORT_BUFFER_CAPACITY: usize = 2;
+
+/// Columns of the CSV export, which are the fields of the exported transaction.
+const CSV_COLUMNS: &[&str] = &[
+    "txHash",
+    "blockNumber",
+    "createdAt",
+    "txType",
+    "from",
+    "to",
+    "token",
+    "amount",
+    "feeToken",
+    "fee",
+    "success",
+    "failReason",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Ndjson,
+}
+
+impl ExportFormat {
+    /// Returns the first export format accepted by the request,
+    /// `None` if the request accepts only the JSON response.
+    pub fn from_request(req: &HttpRequest) -> Option<Self> {
+        let accept = req.headers().get(ACCEPT)?.to_str().ok()?;
+        accept.split(',').find_map(|media_type| {
+            let media_type = media_type.split(';').next().unwrap_or_default();
+            match media_type.trim() {
+                "text/csv" => Some(Self::Csv),
+                "application/x-ndjson" | "application/ndjson" => Some(Self::Ndjson),
+                _ => None,
+            }
+        })
+    }
+
+    fn content_type(self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv; charset=utf-8",
+            Self::Ndjson => "application/x-ndjson",
+        }
+    }
+
+    fn header(self) -> Option<String> {
+        match self {
+            Self::Csv => Some(format!("{}\n", CSV_COLUMNS.join(","))),
+            Self::Ndjson => None,
+        }
+    }
+
+    fn format(self, tx: &AccountTxExport) -> String {
+        let value = serde_json::to_value(tx).expect("Should be correct serializable");
+        match self {
+            Self::Csv => {
+                let fields: Vec<_> = CSV_COLUMNS
+                    .iter()
+                    .map(|column| match &value[*column] {
+                        Value::Null => String::new(),
+                        Value::String(field) => csv_escape(field),
+                        field => field.to_string(),
+                    })
+                    .collect();
+                format!("{}\n", fields.join(","))
+            }
+            Self::Ndjson => format!("{}\n", value),
+        }
+    }
+}
+
+/// Quotes the CSV field if it contains the separators or the quotes.
+fn csv_escape(field: &str) -> String {
+    if field.contains(|c| matches!(c, ',' | '"' | '\n' | '\r')) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AccountTxsExport {
+    pub address: Address,
+    pub token: Option<TokenId>,
+    pub second_address: Option<Address>,
+    pub filters: AccountTxsFilters,
+}
+
+impl AccountTxsExport {
+    /// Starts streaming the account transactions from the database.
+    /// The error happened during the export aborts the response, so the client doesn't
+    /// confuse the truncated export with the complete one.
+    pub fn stream(self, pool: ConnectionPool, format: ExportFormat) -> HttpResponse {
+        let (mut sender, receiver) = mpsc::channel(EXPORT_BUFFER_CAPACITY);
+        tokio::spawn(async move {
+            if let Err(err) = self.export(&pool, format, &mut sender).await {
+                vlog::warn!("Can't export the account transactions: {}", err);
+                let _ = sender
+                    .send(Err(io::Error::new(io::ErrorKind::Other, err.to_string())))
+                    .await;
+            }
+        });
+
+        HttpResponse::Ok()
+            .content_type(format.content_type())
+            .streaming(receiver)
+    }
+
+    async fn export(
+        &self,
+        pool: &ConnectionPool,
+        format: ExportFormat,
+        sender: &mut mpsc::Sender<io::Result<Bytes>>,
+    ) -> anyhow::Result<()> {
+        let mut storage = pool.access_storage().await?;
+        let mut transaction = storage.start_transaction().await?;
+        transaction
+            .chain()
+            .operations_ext_schema()
+            .declare_account_txs_export(
+                self.address,
+                self.token,
+                self.second_address,
+                &self.filters,
+            )
+            .await?;
+
+        if let Some(header) = format.header() {
+            if sender.send(Ok(Bytes::from(header))).await.is_err() {
+                return Ok(());
+            }
+        }
+        loop {
+            let txs = transaction
+                .chain()
+                .operations_ext_schema()
+                .fetch_account_txs_export(EXPORT_BATCH_SIZE)
+                .await?;
+            if txs.is_empty() {
+                break;
+            }
+            let mut batch = BytesMut::new();
+            for tx in txs {
+                batch.extend_from_slice(format.format(&AccountTxExport::from(tx)).as_bytes());
+            }
+            // The client has disconnected.
+            if sender.send(Ok(batch.freeze())).await.is_err() {
+                break;
+            }
+        }
+        transaction.commit().await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+    use chrono::{TimeZone, Utc};
+    use zksync_types::{tx::TxHash, BlockNumber};
+
+    #[test]
+    fn export_format() {
+        let request = |accept: &str| {
+            let req = TestRequest::default()
+                .insert_header((ACCEPT, accept))
+                .to_http_request();
+            ExportFormat::from_request(&req)
+        };
+        assert_eq!(request("text/csv"), Some(ExportFormat::Csv));
+        assert_eq!(
+            request("application/json;q=0.9, application/x-ndjson"),
+            Some(ExportFormat::Ndjson)
+        );
+        assert_eq!(request("application/json"), None);
+        assert_eq!(
+            ExportFormat::from_request(&TestRequest::default().to_http_request()),
+            None
+        );
+    }
+
+    #[test]
+    fn format_tx() {
+        let tx = AccountTxExport {
+            tx_hash: TxHash::default(),
+            block_number: BlockNumber(5),
+            created_at: Utc.ymd(2022, 9, 1).and_hms(12, 0, 0),
+            tx_type: "Transfer".to_string(),
+            from: Address::zero(),
+            to: None,
+            token: Some("ETH".to_string()),
+            amount: Some("100".to_string()),
+            fee_token: Some("ETH".to_string()),
+            fee: Some("1".to_string()),
+            success: false,
+            fail_reason: Some("Nonce mismatch, \"expected\" 1".to_string()),
+        };
+
+        let header = ExportFormat::Csv.header().unwrap();
+        assert_eq!(header.trim_end().split(',').count(), CSV_COLUMNS.len());
+        assert_eq!(
+            ExportFormat::Csv.format(&tx),
+            format!(
+                "{},5,2022-09-01T12:00:00Z,Transfer,{:?},,ETH,100,ETH,1,false,\"Nonce mismatch, \"\"expected\"\" 1\"\n",
+                TxHash::default().to_string(),
+                Address::zero()
+            )
+        );
+
+        let line = ExportFormat::Ndjson.format(&tx);
+        assert!(line.ends_with('\n'));
+        let deserialized: AccountTxExport = serde_json::from_str(&line).unwrap();
+        assert_eq!(deserialized, tx);
+    }
+}
@@ -0,0 +1,181 @@
+//! Extractors of the v0.2 requests.
+//!
+//! The malformed inputs are rejected before the handlers are called, with the same error
+//! response as the handlers return: the path parameters are parsed by the `ValidPath`
+//! extractor, and the errors of the `Path`, `Query` and `Json` extractors of actix are
+//! converted to the v0.2 errors by the configs registered for the whole v0.2 scope.
+
+// Built-in uses
+use std::str::FromStr;
+
+// External uses
+use actix_web::{
+    dev::Payload,
+    error::{InternalError, JsonPayloadError, PathError, QueryPayloadError},
+    web, FromRequest, HttpRequest, Responder,
+};
+use futures::future::{ready, Ready};
+
+// Workspace uses
+use zksync_api_types::v02::{account::AccountAddressOrId, transaction::PriorityOpId};
+use zksync_types::{AccountId, Address, SerialId, TokenLike, H256};
+
+// Local uses
+use super::{
+    error::{Error, InvalidDataError},
+    response::ApiResult,
+};
+
+/// Path parameter which can be extracted by `ValidPath`.
+pub trait PathParam: Sized {
+    /// Name of the parameter in the route patterns.
+    const NAME: &'static str;
+
+    fn from_param(param: &str) -> Result<Self, Error>;
+}
+
+impl PathParam for TokenLike {
+    const NAME: &'static str = "token_like";
+
+    fn from_param(param: &str) -> Result<Self, Error> {
+        let token_like = TokenLike::parse(param);
+        // Anything which is neither the id nor the address is taken for the symbol,
+        // so the malformed ids and addresses are rejected here.
+        if let TokenLike::Symbol(symbol) = &token_like {
+            let digits = symbol.strip_prefix('-').unwrap_or(symbol);
+            if digits.bytes().all(|byte| byte.is_ascii_digit()) || symbol.starts_with("0x") {
+                return Err(Error::from(InvalidDataError::InvalidTokenLike));
+            }
+        }
+        Ok(token_like)
+    }
+}
+
+impl PathParam for AccountAddressOrId {
+    const NAME: &'static str = "account_id_or_address";
+
+    fn from_param(param: &str) -> Result<Self, Error> {
+        if let Ok(account_id) = u32::from_str(param) {
+            return Ok(AccountAddressOrId::Id(AccountId(account_id)));
+        }
+        let address = param.strip_prefix("0x").unwrap_or(param);
+        Address::from_str(address)
+            .map(AccountAddressOrId::Address)
+            .map_err(|_| Error::from(InvalidDataError::InvalidAccountIdOrAddress))
+    }
+}
+
+impl PathParam for PriorityOpId {
+    const NAME: &'static str = "id";
+
+    fn from_param(param: &str) -> Result<Self, Error> {
+        if let Ok(serial_id) = SerialId::from_str(param) {
+            return Ok(PriorityOpId::SerialId(serial_id));
+        }
+        let hash = param.strip_prefix("0x").unwrap_or(param);
+        H256::from_str(hash)
+            .map(PriorityOpId::EthHash)
+            .map_err(|_| Error::from(InvalidDataError::InvalidPriorityOpId))
+    }
+}
+
+/// Extracts the path parameter named `T::NAME`, the handler isn't called if it's invalid.
+/// The routes with several parameters use `web::Path` and parse the rest of them manually.
+#[derive(Debug)]
+pub struct ValidPath<T>(pub T);
+
+impl<T: PathParam> FromRequest for ValidPath<T> {
+    type Config = ();
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let result = match req.match_info().get(T::NAME) {
+            Some(param) => T::from_param(param).map(ValidPath),
+            None => Err(Error::from(InvalidDataError::InvalidPathParams(format!(
+                "Path parameter `{}` is missing",
+                T::NAME
+            )))),
+        };
+        ready(result.map_err(|err| api_error(req, err)))
+    }
+}
+
+/// Makes the actix error with the response of the v0.2 error.
+fn api_error(req: &HttpRequest, err: Error) -> actix_web::Error {
+    let message = err.message.clone();
+    let response = ApiResult::<()>::Error(err).respond_to(req);
+    InternalError::from_response(message, response).into()
+}
+
+pub fn path_config() -> web::PathConfig {
+    web::PathConfig::default().error_handler(|err: PathError, req| {
+        api_error(
+            req,
+            Error::from(InvalidDataError::InvalidPathParams(err.to_string())),
+        )
+    })
+}
+
+pub fn query_config() -> web::QueryConfig {
+    web::QueryConfig::default().error_handler(|err: QueryPayloadError, req| {
+        api_error(
+            req,
+            Error::from(InvalidDataError::QueryDeserializationError(err.to_string())),
+        )
+    })
+}
+
+pub fn json_config() -> web::JsonConfig {
+    web::JsonConfig::default().error_handler(|err: JsonPayloadError, req| {
+        api_error(
+            req,
+            Error::from(InvalidDataError::BodyDeserializationError(err.to_string())),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::TokenId;
+
+    #[test]
+    fn path_params() {
+        let address = Address::repeat_byte(0x12);
+        assert_eq!(
+            AccountAddressOrId::from_param("42").unwrap(),
+            AccountAddressOrId::Id(AccountId(42))
+        );
+        assert_eq!(
+            AccountAddressOrId::from_param(&format!("{:?}", address)).unwrap(),
+            AccountAddressOrId::Address(address)
+        );
+        assert_eq!(
+            AccountAddressOrId::from_param(&hex::encode(address)).unwrap(),
+            AccountAddressOrId::Address(address)
+        );
+        assert!(AccountAddressOrId::from_param("0x12").is_err());
+        assert!(AccountAddressOrId::from_param("-1").is_err());
+
+        assert_eq!(
+            TokenLike::from_param("ETH").unwrap(),
+            TokenLike::Symbol("ETH".to_string())
+        );
+        assert_eq!(
+            TokenLike::from_param("7").unwrap(),
+            TokenLike::Id(TokenId(7))
+        );
+        assert_eq!(
+            TokenLike::from_param(&format!("{:?}", address)).unwrap(),
+            TokenLike::Address(address)
+        );
+        for malformed in &["-1", "4294967296", "0x12", ""] {
+            assert!(
+                TokenLike::from_param(malformed).is_err(),
+                "token {}",
+                malformed
+            );
+        }
+    }
+}
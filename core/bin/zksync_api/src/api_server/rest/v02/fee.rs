@@ -8,15 +8,29 @@ use actix_web::{
     web::{self, Json},
     Scope,
 };
+use bigdecimal::BigDecimal;
+use chrono::Utc;
 
 // Workspace uses
-use zksync_api_types::v02::fee::{ApiFee, BatchFeeRequest, TxFeeRequest};
+use zksync_api_types::v02::{
+    fee::{
+        ApiFee, ApiFeeQuote, BatchFeeRequest, FeeHistoryQuery, FeeQuoteHistory, TxFeeRequest,
+        BATCH_FEE_QUOTE_TYPE,
+    },
+    FormattedAmountsQuery,
+};
+use zksync_types::{tx::error::TxAddError, Address, Token, TokenLike};
+use zksync_utils::biguint_to_big_decimal;
 
 // Local uses
-use super::{error::Error, response::ApiResult};
+use super::{
+    error::{Error, InvalidDataError},
+    response::ApiResult,
+};
 use crate::{
     api_server::tx_sender::{SubmitError, TxSender},
     api_try,
+    fee_ticker::PriceError,
 };
 
 /// Shared data between `api/v0.2/fee` endpoints.
@@ -25,15 +39,132 @@ struct ApiFeeData {
     tx_sender: TxSender,
 }
 
+/// Maximum number of the fee quotes returned by the `fee/history` endpoint.
+pub const MAX_FEE_QUOTES: u32 = 1000;
+
 impl ApiFeeData {
     fn new(tx_sender: TxSender) -> Self {
         Self { tx_sender }
     }
+
+    async fn token(&self, token_like: TokenLike) -> Result<Token, Error> {
+        let mut storage = self
+            .tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?;
+        self.tx_sender
+            .tokens
+            .get_token(&mut storage, token_like)
+            .await
+            .map_err(Error::storage)?
+            .ok_or_else(|| Error::from(PriceError::token_not_found("Token not found")))
+    }
+
+    /// Sets the fee scaled by the decimals of the token if the formatted amounts are requested.
+    async fn format_fee(
+        &self,
+        fee: ApiFee,
+        token_like: TokenLike,
+        format: FormattedAmountsQuery,
+    ) -> Result<ApiFee, Error> {
+        if !format.formatted {
+            return Ok(fee);
+        }
+        let token = self.token(token_like).await?;
+        Ok(fee.with_formatted(token.decimals))
+    }
+
+    async fn try_store_quote(
+        &self,
+        tx_type: &str,
+        token_like: TokenLike,
+        address: Address,
+        fee: &ApiFee,
+    ) -> Result<(), Error> {
+        let token = self.token(token_like).await?;
+        let mut storage = self
+            .tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?;
+        storage
+            .fee_quotes_schema()
+            .store_fee_quote(
+                tx_type,
+                token.id,
+                address,
+                biguint_to_big_decimal(fee.gas_fee.clone()),
+                biguint_to_big_decimal(fee.zkp_fee.clone()),
+                biguint_to_big_decimal(fee.total_fee.clone()),
+            )
+            .await
+            .map_err(Error::storage)
+    }
+
+    /// Stores the quoted fee, the quote is returned to the user even if it can't be stored.
+    async fn store_quote(
+        &self,
+        tx_type: &str,
+        token_like: TokenLike,
+        address: Address,
+        fee: &ApiFee,
+    ) {
+        if let Err(err) = self
+            .try_store_quote(tx_type, token_like, address, fee)
+            .await
+        {
+            vlog::warn!("Can't store the fee quote: {}", err.message);
+        }
+    }
+
+    /// Loads the fees quoted in the token. By default, the quotes for the last day are returned.
+    async fn fee_history(&self, query: FeeHistoryQuery) -> Result<FeeQuoteHistory, Error> {
+        let to = query.to.unwrap_or_else(Utc::now);
+        let from = query.from.unwrap_or_else(|| to - chrono::Duration::days(1));
+        if from > to {
+            return Err(Error::from(InvalidDataError::InvalidFeeHistoryRange));
+        }
+        let token = self.token(TokenLike::parse(&query.token)).await?;
+
+        let mut storage = self
+            .tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?;
+        let quotes = storage
+            .fee_quotes_schema()
+            .load_fee_quotes(token.id, query.tx_type.as_deref(), from, to, MAX_FEE_QUOTES)
+            .await
+            .map_err(Error::storage)?;
+        let to_biguint = |amount: BigDecimal| amount.to_bigint().unwrap().to_biguint().unwrap();
+        let quotes = quotes
+            .into_iter()
+            .map(|quote| ApiFeeQuote {
+                tx_type: quote.tx_type,
+                address: Address::from_slice(&quote.address),
+                gas_fee: to_biguint(quote.gas_fee),
+                zkp_fee: to_biguint(quote.zkp_fee),
+                total_fee: to_biguint(quote.total_fee),
+                quoted_at: quote.created_at,
+            })
+            .collect();
+
+        Ok(FeeQuoteHistory {
+            token_id: token.id,
+            token_symbol: token.symbol,
+            quotes,
+        })
+    }
 }
 
 async fn get_tx_fee(
     data: web::Data<ApiFeeData>,
     Json(body): Json<TxFeeRequest>,
+    web::Query(format): web::Query<FormattedAmountsQuery>,
 ) -> ApiResult<ApiFee> {
     let start = Instant::now();
     let token_allowed = api_try!(data
@@ -45,15 +176,18 @@ async fn get_tx_fee(
     if !token_allowed {
         return Error::from(SubmitError::InappropriateFeeToken).into();
     }
+    let tx_type = body.tx_type.name();
     // TODO implement subsidies for v02 api ZKS-888
-    let res = data
+    let fee: ApiFee = api_try!(data
         .tx_sender
         .ticker
-        .get_fee_from_ticker_in_wei(body.tx_type.into(), body.token_like, body.address)
+        .get_fee_from_ticker_in_wei(body.tx_type.into(), body.token_like.clone(), body.address)
         .await
         .map(|fee| fee.normal_fee.into())
-        .map_err(Error::from)
-        .into();
+        .map_err(Error::from));
+    data.store_quote(tx_type, body.token_like.clone(), body.address, &fee)
+        .await;
+    let res = data.format_fee(fee, body.token_like, format).await.into();
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "get_tx_fee");
     res
 }
@@ -61,8 +195,16 @@ async fn get_tx_fee(
 async fn get_batch_fee(
     data: web::Data<ApiFeeData>,
     Json(body): Json<BatchFeeRequest>,
+    web::Query(format): web::Query<FormattedAmountsQuery>,
 ) -> ApiResult<ApiFee> {
     let start = Instant::now();
+    // The batch is quoted within the same limits as it's submitted with.
+    if body.transactions.is_empty() {
+        return Error::from(SubmitError::TxAdd(TxAddError::EmptyBatch)).into();
+    }
+    if body.transactions.len() > data.tx_sender.max_number_of_transactions_per_batch {
+        return Error::from(SubmitError::TxAdd(TxAddError::BatchTooBig)).into();
+    }
     let token_allowed = api_try!(data
         .tx_sender
         .ticker
@@ -72,23 +214,41 @@ async fn get_batch_fee(
     if !token_allowed {
         return Error::from(SubmitError::InappropriateFeeToken).into();
     }
+    // The batch quote is stored for the address of its first transaction.
+    let address = body
+        .transactions
+        .first()
+        .map(|tx| tx.address)
+        .unwrap_or_default();
     let txs = body
         .transactions
         .into_iter()
         .map(|tx| (tx.tx_type.into(), tx.address))
         .collect();
-    let res = data
+    let fee: ApiFee = api_try!(data
         .tx_sender
         .ticker
-        .get_batch_from_ticker_in_wei(body.token_like, txs)
+        .get_batch_from_ticker_in_wei(body.token_like.clone(), txs)
         .await
         .map(|fee| fee.normal_fee.into())
-        .map_err(Error::from)
-        .into();
+        .map_err(Error::from));
+    data.store_quote(BATCH_FEE_QUOTE_TYPE, body.token_like.clone(), address, &fee)
+        .await;
+    let res = data.format_fee(fee, body.token_like, format).await.into();
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "get_batch_fee");
     res
 }
 
+async fn get_fee_history(
+    data: web::Data<ApiFeeData>,
+    web::Query(query): web::Query<FeeHistoryQuery>,
+) -> ApiResult<FeeQuoteHistory> {
+    let start = Instant::now();
+    let res = data.fee_history(query).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "get_fee_history");
+    res
+}
+
 pub fn api_scope(tx_sender: TxSender) -> Scope {
     let data = ApiFeeData::new(tx_sender);
 
@@ -96,12 +256,17 @@ pub fn api_scope(tx_sender: TxSender) -> Scope {
         .app_data(web::Data::new(data))
         .route("", web::post().to(get_tx_fee))
         .route("/batch", web::post().to(get_batch_fee))
+        .route("/history", web::get().to(get_fee_history))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::api_server::rest::v02::{
+        test_ticker::{
+            token_fixtures, ScriptedTickerInfo, FAILING_TOKEN, PRICED_TOKEN, TIMEOUT_TOKEN,
+            ZERO_PRICE_TOKEN,
+        },
         test_utils::{
             deserialize_response_result, dummy_fee_ticker, dummy_sign_verifier, TestServerConfig,
         },
@@ -113,10 +278,12 @@ mod tests {
     use num::rational::Ratio;
     use num::BigUint;
     use std::collections::HashMap;
+    use std::time::Duration;
     use zksync_api_types::v02::{
-        fee::{ApiTxFeeTypes, TxInBatchFeeRequest},
+        fee::{ApiTxFeeTypes, FeeHistoryQuery, TxInBatchFeeRequest},
         ApiVersion,
     };
+    use zksync_storage::tokens::memory::MemoryTokensStorage;
     use zksync_types::{
         tokens::{TokenLike, TokenMarketVolume},
         Address, Token, TokenId, TokenKind,
@@ -137,15 +304,10 @@ mod tests {
             api_version: ApiVersion::V02,
         };
 
-        let mut tokens = HashMap::new();
-        tokens.insert(
-            TokenLike::Id(TokenId(1)),
+        let tokens = vec![
             Token::new(TokenId(1), Default::default(), "", 18, TokenKind::ERC20),
-        );
-        tokens.insert(
-            TokenLike::Id(TokenId(2)),
             Token::new(TokenId(2), Default::default(), "", 18, TokenKind::ERC20),
-        );
+        ];
         let mut market = HashMap::new();
         market.insert(
             TokenId(2),
@@ -160,9 +322,11 @@ mod tests {
             (TokenLike::Id(TokenId(2)), 10000_u64.into()),
         ];
 
-        let cache = TokenInMemoryCache::new()
-            .with_tokens(tokens)
-            .with_market(market);
+        let cache = TokenInMemoryCache::from(
+            MemoryTokensStorage::new()
+                .with_tokens(tokens)
+                .with_market_volumes(market),
+        );
         let (client, server) = cfg.start_server(
             move |cfg: &TestServerConfig| {
                 api_scope(TxSender::new(
@@ -177,6 +341,7 @@ mod tests {
             Some(shared_data),
         );
 
+        let test_start = Utc::now();
         let tx_type = ApiTxFeeTypes::Withdraw;
         let address = Address::default();
         let not_allowed_token = TokenLike::Id(TokenId(1));
@@ -197,19 +362,131 @@ mod tests {
         assert_eq!(api_fee.gas_fee, BigUint::from(1u32));
         assert_eq!(api_fee.zkp_fee, BigUint::from(1u32));
         assert_eq!(api_fee.total_fee, BigUint::from(2u32));
+        // The formatted amounts are only returned on request.
+        assert!(api_fee.formatted.is_none());
 
         let tx = TxInBatchFeeRequest {
             tx_type: ApiTxFeeTypes::Withdraw,
             address: Address::default(),
         };
+        let too_big_batch_len =
+            cfg.config.api.common.max_number_of_transactions_per_batch as usize + 1;
+        let too_big_batch = vec![tx.clone(); too_big_batch_len];
         let txs = vec![tx.clone(), tx.clone(), tx];
 
+        let response = client.get_batch_fee(vec![], allowed_token.clone()).await?;
+        let expected_error = Error::from(SubmitError::TxAdd(TxAddError::EmptyBatch));
+        let error = serde_json::from_value::<Error>(response.error.unwrap()).unwrap();
+        assert_eq!(error, expected_error);
+
+        let response = client
+            .get_batch_fee(too_big_batch, allowed_token.clone())
+            .await?;
+        let expected_error = Error::from(SubmitError::TxAdd(TxAddError::BatchTooBig));
+        let error = serde_json::from_value::<Error>(response.error.unwrap()).unwrap();
+        assert_eq!(error, expected_error);
+
         let response = client.get_batch_fee(txs, allowed_token).await?;
         let api_batch_fee: ApiFee = deserialize_response_result(response)?;
         assert_eq!(api_batch_fee.gas_fee, BigUint::from(1u32));
         assert_eq!(api_batch_fee.zkp_fee, BigUint::from(1u32));
         assert_eq!(api_batch_fee.total_fee, BigUint::from(2u32));
 
+        // Both quotes are stored, the rejected request for the not allowed token is not.
+        let query = FeeHistoryQuery {
+            tx_type: None,
+            token: "2".to_string(),
+            from: Some(test_start),
+            to: None,
+        };
+        let response = client.fee_history(&query).await?;
+        let history: FeeQuoteHistory = deserialize_response_result(response)?;
+        assert_eq!(history.token_id, TokenId(2));
+        let tx_types: Vec<_> = history
+            .quotes
+            .iter()
+            .map(|quote| quote.tx_type.as_str())
+            .collect();
+        assert_eq!(tx_types, vec!["Withdraw", BATCH_FEE_QUOTE_TYPE]);
+        assert_eq!(history.quotes[1].total_fee, api_batch_fee.total_fee);
+
+        let query = FeeHistoryQuery {
+            tx_type: Some(BATCH_FEE_QUOTE_TYPE.to_string()),
+            ..query
+        };
+        let response = client.fee_history(&query).await?;
+        let history: FeeQuoteHistory = deserialize_response_result(response)?;
+        assert_eq!(history.quotes.len(), 1);
+
+        let query = FeeHistoryQuery {
+            from: Some(Utc::now()),
+            to: Some(test_start),
+            ..query
+        };
+        let response = client.fee_history(&query).await?;
+        let error = serde_json::from_value::<Error>(response.error.unwrap()).unwrap();
+        assert_eq!(error, Error::from(InvalidDataError::InvalidFeeHistoryRange));
+
+        server.stop().await;
+        Ok(())
+    }
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn fee_pricing_edge_cases() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        let (mempool_tx_request_sender, _mempool_tx_request_receiver) = mpsc::channel(100);
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+
+        let fixtures = token_fixtures();
+        let info = ScriptedTickerInfo::new(&fixtures).with_latency(Duration::from_millis(10));
+        let fee_ticker = info.fee_ticker(&fixtures);
+        let (client, server) = cfg.start_server(
+            move |cfg: &TestServerConfig| {
+                api_scope(TxSender::new(
+                    cfg.pool.clone(),
+                    dummy_sign_verifier(),
+                    fee_ticker.clone(),
+                    &cfg.config.api.common,
+                    &cfg.config.api.token_config,
+                    mempool_tx_request_sender.clone(),
+                ))
+            },
+            Some(shared_data),
+        );
+
+        let client = &client;
+        let fee = move |token_id: TokenId| {
+            client.get_txs_fee(
+                ApiTxFeeTypes::Withdraw,
+                Address::default(),
+                TokenLike::Id(token_id),
+            )
+        };
+
+        let response = fee(PRICED_TOKEN).await?;
+        let api_fee: ApiFee = deserialize_response_result(response)?;
+        assert!(api_fee.total_fee > BigUint::from(0u32));
+
+        // The fee can't be paid in the token with the zero price,
+        // or in the token which price is unavailable.
+        for token_id in &[ZERO_PRICE_TOKEN, FAILING_TOKEN, TIMEOUT_TOKEN] {
+            let response = fee(*token_id).await?;
+            assert!(response.error.is_some(), "token {}", token_id);
+        }
+
+        // Once the provider recovers, the fee is quoted.
+        info.set_price(FAILING_TOKEN, 10);
+        let response = fee(FAILING_TOKEN).await?;
+        let recovered_fee: ApiFee = deserialize_response_result(response)?;
+        assert_eq!(recovered_fee.total_fee, api_fee.total_fee);
+
         server.stop().await;
         Ok(())
     }
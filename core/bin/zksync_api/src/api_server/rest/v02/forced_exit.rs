@@ -0,0 +1,289 @@
+//! ForcedExit part of API implementation.
+//!
+//! Anyone may ask the operator to withdraw the funds of the account which has no signing key
+//! set to L1. The request is accepted only if the balance of the account covers the fee of the
+//! ForcedExit, and the accepted requests are batched and submitted by the automation service
+//! of the forced exit requests server when it's enabled.
+
+// Built-in uses
+use std::time::Instant;
+
+// External uses
+use actix_web::{
+    web::{self, Json},
+    Scope,
+};
+use bigdecimal::BigDecimal;
+
+// Workspace uses
+use zksync_api_types::v02::{
+    fee::ApiFee,
+    forced_exit::{
+        ApiForcedExitRequest, ForcedExitAutomationStatus, ForcedExitEstimation,
+        ForcedExitRequestBody,
+    },
+};
+use zksync_config::ZkSyncConfig;
+use zksync_storage::forced_exit_requests::records::{
+    ForcedExitAutomationStatus as StoredStatus, StoredForcedExitAutomationRequest,
+};
+use zksync_types::{
+    tx::TxHash, Address, PubKeyHash, Token, TokenId, TokenKind, TokenLike, TxFeeTypes,
+};
+use zksync_utils::biguint_to_big_decimal;
+
+// Local uses
+use super::{
+    error::{Error, InvalidDataError},
+    response::ApiResult,
+};
+use crate::{
+    api_server::{forced_exit_checker::ForcedExitAccountAgeChecker, tx_sender::TxSender},
+    fee_ticker::PriceError,
+};
+
+/// Shared data between `api/v0.2/forced_exit` endpoints.
+#[derive(Clone)]
+struct ApiForcedExitData {
+    tx_sender: TxSender,
+}
+
+impl ApiForcedExitData {
+    fn new(tx_sender: TxSender) -> Self {
+        Self { tx_sender }
+    }
+
+    async fn token(&self, token_like: TokenLike) -> Result<Token, Error> {
+        let mut storage = self
+            .tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?;
+        self.tx_sender
+            .tokens
+            .get_token(&mut storage, token_like)
+            .await
+            .map_err(Error::storage)?
+            .ok_or_else(|| Error::from(PriceError::token_not_found("Token not found")))
+    }
+
+    /// Estimates the ForcedExit of the token of the target account, fails if the ForcedExit
+    /// of the account isn't possible at all.
+    async fn estimate(&self, body: ForcedExitRequestBody) -> Result<ForcedExitEstimation, Error> {
+        let token = self.token(body.token).await?;
+        if token.kind == TokenKind::NFT {
+            return Err(not_possible("ForcedExit of the NFTs is not supported"));
+        }
+
+        let mut storage = self
+            .tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?;
+        let account = storage
+            .chain()
+            .account_schema()
+            .account_state_by_address(body.target)
+            .await
+            .map_err(Error::storage)?
+            .committed
+            .map(|(_, account)| account)
+            .ok_or_else(|| not_possible("Target account does not exist"))?;
+        if account.pub_key_hash != PubKeyHash::default() {
+            return Err(not_possible("Target account has the signing key set"));
+        }
+        let eligible = self
+            .tx_sender
+            .forced_exit_checker
+            .check_forced_exit(&mut storage, body.target)
+            .await?;
+        if !eligible {
+            return Err(not_possible(format!(
+                "Target account exists less than required minimum amount ({} hours)",
+                self.tx_sender
+                    .forced_exit_checker
+                    .forced_exit_minimum_account_age
+                    .num_hours()
+            )));
+        }
+        drop(storage);
+
+        let fee: ApiFee = self
+            .tx_sender
+            .ticker
+            .get_fee_from_ticker_in_wei(TxFeeTypes::Withdraw, TokenLike::Id(token.id), body.target)
+            .await
+            .map(|fee| fee.normal_fee.into())
+            .map_err(Error::from)?;
+        let balance = account.get_balance(token.id);
+        let viable = balance > fee.total_fee;
+
+        Ok(ForcedExitEstimation {
+            target: body.target,
+            token_id: token.id,
+            token_symbol: token.symbol,
+            balance,
+            fee,
+            viable,
+        })
+    }
+
+    /// Stores the request of the ForcedExit for the automation service. The pending request
+    /// for the same target and token is updated with the latest fee estimation.
+    async fn request(&self, body: ForcedExitRequestBody) -> Result<ApiForcedExitRequest, Error> {
+        let estimation = self.estimate(body).await?;
+        if !estimation.viable {
+            return Err(Error::from(InvalidDataError::ForcedExitNotViable));
+        }
+
+        let mut storage = self
+            .tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?;
+        let request = storage
+            .forced_exit_requests_schema()
+            .store_automation_request(
+                estimation.target,
+                estimation.token_id,
+                biguint_to_big_decimal(estimation.fee.total_fee),
+            )
+            .await
+            .map_err(Error::storage)?;
+        Ok(api_request(request))
+    }
+
+    async fn get_request(&self, id: i64) -> Result<ApiForcedExitRequest, Error> {
+        let mut storage = self
+            .tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?;
+        storage
+            .forced_exit_requests_schema()
+            .get_automation_request(id)
+            .await
+            .map_err(Error::storage)?
+            .map(api_request)
+            .ok_or_else(|| Error::from(InvalidDataError::ForcedExitRequestNotFound))
+    }
+}
+
+fn not_possible(reason: impl Into<String>) -> Error {
+    Error::from(InvalidDataError::ForcedExitNotPossible(reason.into()))
+}
+
+fn api_request(request: StoredForcedExitAutomationRequest) -> ApiForcedExitRequest {
+    let status = if request.status == StoredStatus::Submitted.as_str() {
+        ForcedExitAutomationStatus::Submitted
+    } else if request.status == StoredStatus::Failed.as_str() {
+        ForcedExitAutomationStatus::Failed
+    } else {
+        ForcedExitAutomationStatus::Pending
+    };
+    let to_biguint = |amount: BigDecimal| amount.to_bigint().unwrap().to_biguint().unwrap();
+
+    ApiForcedExitRequest {
+        id: request.id,
+        target: Address::from_slice(&request.target),
+        token_id: TokenId(request.token_id as u32),
+        estimated_fee: to_biguint(request.estimated_fee),
+        status,
+        tx_hash: request.tx_hash.as_deref().and_then(TxHash::from_slice),
+        error: request.error,
+        created_at: request.created_at,
+        processed_at: request.processed_at,
+    }
+}
+
+// Server implementation
+
+async fn estimate_forced_exit(
+    data: web::Data<ApiForcedExitData>,
+    Json(body): Json<ForcedExitRequestBody>,
+) -> ApiResult<ForcedExitEstimation> {
+    let start = Instant::now();
+    let res = data.estimate(body).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "estimate_forced_exit");
+    res
+}
+
+async fn request_forced_exit(
+    data: web::Data<ApiForcedExitData>,
+    Json(body): Json<ForcedExitRequestBody>,
+) -> ApiResult<ApiForcedExitRequest> {
+    let start = Instant::now();
+    let res = data.request(body).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "request_forced_exit");
+    res
+}
+
+async fn forced_exit_request(
+    data: web::Data<ApiForcedExitData>,
+    id: web::Path<i64>,
+) -> ApiResult<ApiForcedExitRequest> {
+    let start = Instant::now();
+    let res = data.get_request(*id).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "forced_exit_request");
+    res
+}
+
+pub fn api_scope(tx_sender: TxSender, zk_config: &ZkSyncConfig) -> Scope {
+    let data = ApiForcedExitData::new(tx_sender);
+
+    let scope = web::scope("forced_exit")
+        .app_data(web::Data::new(data))
+        .route("estimate", web::post().to(estimate_forced_exit));
+    // The requests are only accepted if there is the service submitting them.
+    if zk_config.forced_exit_requests.automation_enabled {
+        scope
+            .route("requests", web::post().to(request_forced_exit))
+            .route("requests/{id}", web::get().to(forced_exit_request))
+    } else {
+        scope
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn stored_request_conversion() {
+        let hash = TxHash::from_slice(&[0x12; 32]).unwrap();
+        let stored = StoredForcedExitAutomationRequest {
+            id: 1,
+            target: Address::repeat_byte(0x01).as_bytes().to_vec(),
+            token_id: 2,
+            estimated_fee: BigDecimal::from(1000),
+            status: StoredStatus::Submitted.as_str().to_owned(),
+            tx_hash: Some(hash.as_ref().to_vec()),
+            error: None,
+            created_at: Utc::now(),
+            processed_at: Some(Utc::now()),
+        };
+
+        let request = api_request(stored.clone());
+        assert_eq!(request.target, Address::repeat_byte(0x01));
+        assert_eq!(request.token_id, TokenId(2));
+        assert_eq!(request.estimated_fee, 1000u32.into());
+        assert_eq!(request.status, ForcedExitAutomationStatus::Submitted);
+        assert_eq!(request.tx_hash, Some(hash));
+
+        let failed = StoredForcedExitAutomationRequest {
+            status: StoredStatus::Failed.as_str().to_owned(),
+            tx_hash: None,
+            error: Some("Target account has the signing key set".to_owned()),
+            ..stored
+        };
+        let request = api_request(failed);
+        assert_eq!(request.status, ForcedExitAutomationStatus::Failed);
+        assert_eq!(request.tx_hash, None);
+        assert!(request.error.is_some());
+    }
+}
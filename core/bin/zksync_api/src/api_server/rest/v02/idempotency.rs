@@ -0,0 +1,166 @@
+//! Idempotency keys of the transaction submissions.
+//!
+//! The client may set the `Idempotency-Key` header on the submission request, so that
+//! the retried request returns the response of the first one instead of submitting the
+//! transaction again. The responses are stored in the database, so the retried request
+//! may be served by any of the API servers.
+//!
+//! The keys are scoped by the account signing the (first) transaction of the request, so
+//! the clients can't observe or block each other's keys. The key is reserved in the database
+//! before the request is submitted, so of the concurrent requests with the same key only one
+//! is submitted, while the others are rejected until its response is stored.
+//!
+//! Only the successful responses are stored: the failed submission doesn't change
+//! anything, so its key is released and it's safe to retry it with the same key.
+
+// Built-in uses
+use std::time::Duration;
+
+// External uses
+use actix_web::HttpRequest;
+use chrono::Utc;
+use serde::{de::DeserializeOwned, Serialize};
+
+// Workspace uses
+use zksync_storage::{idempotency_keys::records::StoredIdempotentResponse, ConnectionPool};
+use zksync_types::{tx::TxHash, AccountId};
+
+// Local uses
+use super::error::{Error, InvalidDataError};
+
+/// Header with the idempotency key of the request.
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+/// Maximum length of the idempotency key.
+pub const MAX_IDEMPOTENCY_KEY_LENGTH: usize = 255;
+
+#[derive(Debug, Clone)]
+pub struct IdempotencyKeys {
+    /// The responses are stored, so the main database is used.
+    pool: ConnectionPool,
+    window: chrono::Duration,
+}
+
+impl IdempotencyKeys {
+    pub fn new(pool: ConnectionPool, window: Duration) -> Self {
+        Self {
+            pool,
+            window: chrono::Duration::from_std(window).expect("Incorrect idempotency key window"),
+        }
+    }
+
+    /// Returns the idempotency key of the request, if it's set.
+    pub fn request_key(req: &HttpRequest) -> Result<Option<String>, Error> {
+        let key = match req.headers().get(IDEMPOTENCY_KEY_HEADER) {
+            Some(key) => key,
+            None => return Ok(None),
+        };
+        match key.to_str() {
+            Ok(key) if !key.is_empty() && key.len() <= MAX_IDEMPOTENCY_KEY_LENGTH => {
+                Ok(Some(key.to_string()))
+            }
+            _ => Err(InvalidDataError::InvalidIdempotencyKey.into()),
+        }
+    }
+
+    /// Submits the request only if its key of the sender is reserved by this call,
+    /// otherwise returns the response stored for the key. The key can't be reused for
+    /// another request until the stored response expires.
+    pub async fn submit<T, F>(
+        &self,
+        key: Option<String>,
+        sender: Option<AccountId>,
+        request_hash: TxHash,
+        submit: F,
+    ) -> Result<T, Error>
+    where
+        T: Serialize + DeserializeOwned,
+        F: std::future::Future<Output = Result<T, Error>>,
+    {
+        // The requests without the sender, e.g. the empty batches, are rejected on submission.
+        let (key, sender) = match key.zip(sender) {
+            Some(scoped_key) => scoped_key,
+            None => return submit.await,
+        };
+
+        let stored_after = Utc::now() - self.window;
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let reserved = storage
+            .idempotency_keys_schema()
+            .reserve_key(sender, &key, request_hash.as_ref(), stored_after)
+            .await
+            .map_err(Error::storage)?;
+        if !reserved {
+            let stored = storage
+                .idempotency_keys_schema()
+                .load_response(sender, &key, stored_after)
+                .await
+                .map_err(Error::storage)?;
+            return match stored {
+                Some(stored) if stored.request_hash != request_hash.as_ref() => {
+                    Err(InvalidDataError::IdempotencyKeyReused.into())
+                }
+                Some(StoredIdempotentResponse {
+                    response: Some(response),
+                    ..
+                }) => {
+                    metrics::increment_counter!("api.v02.idempotency_keys.repeated_requests");
+                    serde_json::from_value(response).map_err(Error::storage)
+                }
+                // The request with the same key is being submitted, or it has just failed
+                // and released the key.
+                _ => Err(InvalidDataError::IdempotentRequestInProgress.into()),
+            };
+        }
+        // The connection is not held while the request is submitted.
+        drop(storage);
+
+        let response = submit.await;
+        // The request is already submitted, so the failure to store the response
+        // is not reported to the client.
+        if let Err(err) = self.complete(sender, &key, &response).await {
+            vlog::warn!("Can't store the response for the idempotency key: {}", err);
+            metrics::increment_counter!("api.v02.idempotency_keys.store_fail");
+        }
+        response
+    }
+
+    /// Stores the response of the submitted request, or releases the key if the request
+    /// has failed.
+    async fn complete(
+        &self,
+        sender: AccountId,
+        key: &str,
+        response: &Result<impl Serialize, Error>,
+    ) -> anyhow::Result<()> {
+        let mut storage = self.pool.access_storage().await?;
+        match response {
+            Ok(response) => {
+                let response = serde_json::to_value(response)?;
+                storage
+                    .idempotency_keys_schema()
+                    .store_response(sender, key, response)
+                    .await?;
+            }
+            Err(_) => {
+                storage
+                    .idempotency_keys_schema()
+                    .release_key(sender, key)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes the expired responses from the database.
+    pub async fn remove_expired(&self) -> anyhow::Result<()> {
+        let removed = self
+            .pool
+            .access_storage()
+            .await?
+            .idempotency_keys_schema()
+            .remove_responses_before(Utc::now() - self.window)
+            .await?;
+        metrics::counter!("api.v02.idempotency_keys.removed", removed);
+        Ok(())
+    }
+}
@@ -0,0 +1,91 @@
+//! Mempool part of API implementation.
+
+// Built-in uses
+use std::time::Instant;
+
+// External uses
+use actix_web::{web, Scope};
+
+// Workspace uses
+use zksync_api_types::v02::mempool::MempoolStats;
+use zksync_storage::ConnectionPool;
+
+// Local uses
+use super::{error::Error, response::ApiResult};
+
+/// Shared data between `api/v0.2/mempool` endpoints.
+#[derive(Debug, Clone)]
+struct ApiMempoolData {
+    pool: ConnectionPool,
+}
+
+impl ApiMempoolData {
+    fn new(pool: ConnectionPool) -> Self {
+        Self { pool }
+    }
+
+    async fn mempool_stats(&self) -> Result<MempoolStats, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        storage
+            .chain()
+            .mempool_schema()
+            .get_mempool_stats()
+            .await
+            .map_err(Error::storage)
+    }
+}
+
+// Server implementation
+
+async fn get_mempool_stats(data: web::Data<ApiMempoolData>) -> ApiResult<MempoolStats> {
+    let start = Instant::now();
+    let res = data.mempool_stats().await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "get_mempool_stats");
+    res
+}
+
+pub fn api_scope(pool: ConnectionPool) -> Scope {
+    let data = ApiMempoolData::new(pool);
+
+    web::scope("mempool")
+        .app_data(web::Data::new(data))
+        .route("stats", web::get().to(get_mempool_stats))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rest::v02::{
+        test_utils::{deserialize_response_result, TestServerConfig},
+        SharedData,
+    };
+    use zksync_api_types::v02::ApiVersion;
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn mempool_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+        let (client, server) =
+            cfg.start_server(move |cfg| api_scope(cfg.pool.clone()), Some(shared_data));
+
+        let expected_stats = {
+            let mut storage = cfg.pool.access_storage().await?;
+            storage.chain().mempool_schema().get_mempool_stats().await?
+        };
+        let response = client.mempool_stats().await?;
+        let stats: MempoolStats = deserialize_response_result(response)?;
+        assert_eq!(stats, expected_stats);
+
+        server.stop().await;
+        Ok(())
+    }
+}
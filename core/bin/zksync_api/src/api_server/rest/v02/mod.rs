@@ -1,4 +1,5 @@
 // External uses
+use actix::Addr;
 use actix_web::{
     web::{self},
     Scope,
@@ -10,20 +11,48 @@ use zksync_config::ZkSyncConfig;
 use zksync_types::network::Network;
 
 // Local uses
-use crate::api_server::tx_sender::TxSender;
+use crate::{
+    api_server::{config_reload::ConfigReloader, tx_sender::TxSender},
+    fiat_rates::FiatRates,
+};
+use event_stream::EventStreamManager;
+use idempotency::IdempotencyKeys;
+use pagination_cursor::PaginationCursors;
+use price_subscription::PriceSubscriptionManager;
+use response_signer::ResponseSigner;
 
-mod account;
+pub(crate) mod account;
 mod block;
 mod config;
+mod create2;
 pub mod error;
+mod errors;
+pub(crate) mod event_stream;
+mod export;
+mod extractors;
 mod fee;
+mod forced_exit;
+pub(crate) mod idempotency;
+mod mempool;
+mod network_stats;
+mod nft;
+pub(crate) mod openapi;
+mod order;
 mod paginate_impl;
 mod paginate_trait;
+pub(crate) mod pagination_cursor;
+pub(crate) mod price_subscription;
+mod priority_op;
 mod response;
+pub(crate) mod response_signer;
+mod search;
 mod status;
 #[cfg(test)]
+pub mod test_ticker;
+#[cfg(test)]
 pub mod test_utils;
-mod token;
+pub(crate) mod token;
+mod token_rates;
 mod transaction;
 
 #[derive(Debug, Clone, Copy)]
@@ -36,13 +65,29 @@ pub(crate) fn api_scope(
     tx_sender: TxSender,
     zk_config: &ZkSyncConfig,
     network_status: SharedNetworkStatus,
+    price_subscriptions: Addr<PriceSubscriptionManager>,
+    event_stream: Addr<EventStreamManager>,
+    idempotency_keys: IdempotencyKeys,
+    config_reloader: &ConfigReloader,
 ) -> Scope {
     let data = SharedData {
         net: zk_config.chain.eth.network,
         api_version: ApiVersion::V02,
     };
-    web::scope("/api/v0.2")
-        .app_data(web::Data::new(data))
+    let mut scope = web::scope("/api/v0.2").app_data(web::Data::new(data));
+    if let Some(signer) = ResponseSigner::from_config(zk_config) {
+        vlog::info!("Responses are signed by the key {:?}", signer.key_id());
+        scope = scope.app_data(web::Data::new(signer));
+    }
+    scope
+        .app_data(web::Data::new(PaginationCursors::from_config(
+            &zk_config.api.rest,
+        )))
+        .app_data(web::Data::new(tx_sender.read_only_mode.clone()))
+        .app_data(extractors::path_config())
+        .app_data(extractors::query_config())
+        .app_data(extractors::json_config())
+        .route("openapi.json", web::get().to(openapi::openapi_spec))
         .service(account::api_scope(
             tx_sender.pool.clone(),
             tx_sender.tokens.clone(),
@@ -53,13 +98,33 @@ pub(crate) fn api_scope(
             tx_sender.blocks.clone(),
         ))
         .service(config::api_scope(zk_config))
+        .service(create2::api_scope(tx_sender.clone()))
+        .service(errors::api_scope())
+        .service(event_stream::api_scope(event_stream.clone()))
         .service(fee::api_scope(tx_sender.clone()))
-        .service(status::api_scope(network_status))
+        .service(forced_exit::api_scope(tx_sender.clone(), zk_config))
+        .service(mempool::api_scope(tx_sender.pool.clone()))
+        .service(network_stats::api_scope(tx_sender.pool.clone()))
+        .service(nft::api_scope(tx_sender.pool.clone()))
+        .service(order::api_scope(tx_sender.clone()))
+        .service(price_subscription::api_scope(price_subscriptions))
+        .service(priority_op::api_scope(tx_sender.pool.clone()))
+        .service(search::api_scope(
+            tx_sender.pool.clone(),
+            tx_sender.tokens.clone(),
+        ))
+        .service(status::api_scope(network_status, tx_sender.pool.clone()))
         .service(token::api_scope(
             zk_config,
             tx_sender.pool.clone(),
             tx_sender.tokens.clone(),
             tx_sender.ticker.clone(),
+            FiatRates::from_config(&zk_config.api.token_config),
+            config_reloader,
+        ))
+        .service(transaction::api_scope(
+            tx_sender,
+            event_stream,
+            idempotency_keys,
         ))
-        .service(transaction::api_scope(tx_sender))
 }
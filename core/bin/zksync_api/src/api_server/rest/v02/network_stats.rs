@@ -0,0 +1,105 @@
+//! Network statistics part of API implementation.
+
+// Built-in uses
+use std::time::Instant;
+
+// External uses
+use actix_web::{web, Scope};
+
+// Workspace uses
+use zksync_api_types::v02::status::NetworkStats;
+use zksync_storage::ConnectionPool;
+
+// Local uses
+use super::{error::Error, response::ApiResult};
+
+/// Shared data between `api/v0.2/networkStats` endpoints.
+#[derive(Debug, Clone)]
+struct ApiNetworkStatsData {
+    pool: ConnectionPool,
+}
+
+impl ApiNetworkStatsData {
+    fn new(pool: ConnectionPool) -> Self {
+        Self { pool }
+    }
+
+    /// Loads the statistics stored by the network stats aggregator,
+    /// `None` if they weren't aggregated yet.
+    async fn network_stats(&self) -> Result<Option<NetworkStats>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let stats = storage
+            .network_stats_schema()
+            .load_network_stats()
+            .await
+            .map_err(Error::storage)?;
+        Ok(stats.map(|(stats, tokens)| stats.into_api(tokens)))
+    }
+}
+
+// Server implementation
+
+async fn get_network_stats(
+    data: web::Data<ApiNetworkStatsData>,
+) -> ApiResult<Option<NetworkStats>> {
+    let start = Instant::now();
+    let res = data.network_stats().await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "get_network_stats");
+    res
+}
+
+pub fn api_scope(pool: ConnectionPool) -> Scope {
+    let data = ApiNetworkStatsData::new(pool);
+
+    web::scope("networkStats")
+        .app_data(web::Data::new(data))
+        .route("", web::get().to(get_network_stats))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rest::v02::{
+        test_utils::{deserialize_response_result, TestServerConfig},
+        SharedData,
+    };
+    use chrono::{Duration, Utc};
+    use zksync_api_types::v02::ApiVersion;
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn network_stats_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+        let (client, server) =
+            cfg.start_server(move |cfg| api_scope(cfg.pool.clone()), Some(shared_data));
+
+        let expected_stats = {
+            let mut storage = cfg.pool.access_storage().await?;
+            storage
+                .network_stats_schema()
+                .update_network_stats(Utc::now() - Duration::hours(24))
+                .await?;
+            let (stats, tokens) = storage
+                .network_stats_schema()
+                .load_network_stats()
+                .await?
+                .unwrap();
+            stats.into_api(tokens)
+        };
+        let response = client.network_stats().await?;
+        let stats: Option<NetworkStats> = deserialize_response_result(response)?;
+        assert_eq!(stats, Some(expected_stats));
+
+        server.stop().await;
+        Ok(())
+    }
+}
@@ -0,0 +1,243 @@
+//! NFT part of API implementation.
+
+// Built-in uses
+use std::time::Instant;
+
+// External uses
+use actix_web::{web, Scope};
+
+// Workspace uses
+use zksync_api_types::v02::token::{ApiNFT, NFTContentHashQuery, NFTContentHashVerification};
+use zksync_crypto::params::MIN_NFT_TOKEN_ID;
+use zksync_storage::ConnectionPool;
+use zksync_types::{tx::TxHash, AccountId, TokenId, H256};
+
+// Local uses
+use super::{
+    error::{Error, InvalidDataError},
+    response::ApiResult,
+};
+
+/// Shared data between `api/v0.2/nft` endpoints.
+#[derive(Clone)]
+pub(super) struct ApiNFTData {
+    pool: ConnectionPool,
+}
+
+impl ApiNFTData {
+    pub(super) fn new(pool: ConnectionPool) -> Self {
+        Self { pool }
+    }
+
+    fn check_nft_id(id: TokenId) -> Result<(), Error> {
+        if id.0 < MIN_NFT_TOKEN_ID {
+            Err(Error::from(InvalidDataError::InvalidNFTTokenId))
+        } else {
+            Ok(())
+        }
+    }
+
+    async fn nft(&self, id: TokenId) -> Result<Option<ApiNFT>, Error> {
+        Self::check_nft_id(id)?;
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        storage
+            .tokens_schema()
+            .get_nft_with_factories(id)
+            .await
+            .map_err(Error::storage)
+    }
+
+    async fn nft_owner(&self, id: TokenId) -> Result<Option<AccountId>, Error> {
+        Self::check_nft_id(id)?;
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        storage
+            .chain()
+            .account_schema()
+            .get_nft_owner(id)
+            .await
+            .map_err(Error::storage)
+    }
+
+    async fn nft_id_by_tx_hash(&self, tx_hash: TxHash) -> Result<Option<TokenId>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        storage
+            .chain()
+            .state_schema()
+            .get_nft_id_by_tx_hash(tx_hash)
+            .await
+            .map_err(Error::storage)
+    }
+
+    /// Compares the content hash with the one the NFT was minted with.
+    /// Returns `None` if there is no such NFT.
+    async fn verify_content_hash(
+        &self,
+        id: TokenId,
+        content_hash: H256,
+    ) -> Result<Option<NFTContentHashVerification>, Error> {
+        Self::check_nft_id(id)?;
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let nft = storage
+            .tokens_schema()
+            .get_nft(id)
+            .await
+            .map_err(Error::storage)?;
+        Ok(nft.map(|nft| NFTContentHashVerification {
+            id,
+            content_hash: nft.content_hash,
+            matches: nft.content_hash == content_hash,
+        }))
+    }
+}
+
+// Server implementation
+
+pub(super) async fn get_nft(
+    data: web::Data<ApiNFTData>,
+    id: web::Path<TokenId>,
+) -> ApiResult<Option<ApiNFT>> {
+    let start = Instant::now();
+    let res = data.nft(*id).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "get_nft");
+    res
+}
+
+pub(super) async fn get_nft_owner(
+    data: web::Data<ApiNFTData>,
+    id: web::Path<TokenId>,
+) -> ApiResult<Option<AccountId>> {
+    let start = Instant::now();
+    let res = data.nft_owner(*id).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "get_nft_owner");
+    res
+}
+
+pub(super) async fn get_nft_id_by_tx_hash(
+    data: web::Data<ApiNFTData>,
+    tx_hash: web::Path<TxHash>,
+) -> ApiResult<Option<TokenId>> {
+    let start = Instant::now();
+    let res = data.nft_id_by_tx_hash(*tx_hash).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "get_nft_id_by_tx_hash");
+    res
+}
+
+async fn verify_nft_content_hash(
+    data: web::Data<ApiNFTData>,
+    id: web::Path<TokenId>,
+    web::Query(query): web::Query<NFTContentHashQuery>,
+) -> ApiResult<Option<NFTContentHashVerification>> {
+    let start = Instant::now();
+    let res = data
+        .verify_content_hash(*id, query.content_hash)
+        .await
+        .into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "verify_nft_content_hash");
+    res
+}
+
+pub fn api_scope(pool: ConnectionPool) -> Scope {
+    let data = ApiNFTData::new(pool);
+
+    web::scope("nft")
+        .app_data(web::Data::new(data))
+        .route("{id}", web::get().to(get_nft))
+        .route("{id}/owner", web::get().to(get_nft_owner))
+        .route(
+            "{id}/verify_content_hash",
+            web::get().to(verify_nft_content_hash),
+        )
+        .route(
+            "id_by_tx_hash/{tx_hash}",
+            web::get().to(get_nft_id_by_tx_hash),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rest::v02::{
+        test_utils::{deserialize_response_result, TestServerConfig},
+        SharedData,
+    };
+    use zksync_api_types::v02::ApiVersion;
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn nft_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+        let (client, server) =
+            cfg.start_server(move |cfg| api_scope(cfg.pool.clone()), Some(shared_data));
+
+        let nft_id = TokenId(65542);
+        let expected_nft = {
+            let mut storage = cfg.pool.access_storage().await?;
+            storage
+                .tokens_schema()
+                .get_nft_with_factories(nft_id)
+                .await?
+                .unwrap()
+        };
+        let response = client.nft_info(nft_id).await?;
+        let nft: ApiNFT = deserialize_response_result(response)?;
+        assert_eq!(nft, expected_nft);
+
+        let response = client.nft_owner(nft_id).await?;
+        let owner_id: AccountId = deserialize_response_result(response)?;
+        let expected_owner_id = {
+            let mut storage = cfg.pool.access_storage().await?;
+            storage
+                .chain()
+                .account_schema()
+                .get_nft_owner(nft_id)
+                .await?
+                .unwrap()
+        };
+        assert_eq!(owner_id, expected_owner_id);
+
+        let response = client
+            .verify_nft_content_hash(nft_id, expected_nft.content_hash)
+            .await?;
+        let verification: Option<NFTContentHashVerification> =
+            deserialize_response_result(response)?;
+        assert_eq!(
+            verification,
+            Some(NFTContentHashVerification {
+                id: nft_id,
+                content_hash: expected_nft.content_hash,
+                matches: true,
+            })
+        );
+
+        let other_hash = H256::repeat_byte(0xab);
+        assert_ne!(other_hash, expected_nft.content_hash);
+        let response = client.verify_nft_content_hash(nft_id, other_hash).await?;
+        let verification: Option<NFTContentHashVerification> =
+            deserialize_response_result(response)?;
+        assert!(!verification.unwrap().matches);
+
+        // The unknown NFT can't be verified.
+        let response = client
+            .verify_nft_content_hash(TokenId(MIN_NFT_TOKEN_ID + 100_000), other_hash)
+            .await?;
+        let verification: Option<NFTContentHashVerification> =
+            deserialize_response_result(response)?;
+        assert!(verification.is_none());
+
+        // Fungible tokens are not NFTs.
+        let response = client.nft_info(TokenId(1)).await?;
+        assert!(response.error.is_some());
+
+        server.stop().await;
+        Ok(())
+    }
+}
@@ -0,0 +1,701 @@
+//! OpenAPI specification of the API v0.2.
+//!
+//! The schemas of the response types are inferred from the serialized examples of the
+//! actual types, so the field names and shapes always match the serde representation.
+//! The routes declared by the scopes are checked against the list of the documented
+//! endpoints in the tests.
+
+// Built-in uses
+use std::collections::HashMap;
+
+// External uses
+use actix_web::HttpResponse;
+use bigdecimal::BigDecimal;
+use chrono::{TimeZone, Utc};
+use num::BigUint;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+// Workspace uses
+use zksync_api_types::v02::{
+    pagination::{Paginated, PaginationDirection},
+    token::{ApiToken, TokenPrice, TokenRate},
+    ApiVersion, Request, Response, ResultStatus,
+};
+use zksync_types::{network::Network, Address, TokenId};
+
+// Local uses
+use super::error::{Error, ErrorCode, ErrorCodeInfo, InvalidDataError};
+
+const OPENAPI_VERSION: &str = "3.0.3";
+const API_PREFIX: &str = "/api/v0.2";
+
+/// Documented endpoint of the API.
+struct Endpoint {
+    method: &'static str,
+    /// Path relative to the API prefix, in the actix format.
+    path: &'static str,
+    summary: &'static str,
+    /// Name of the schema of the `result` field, if it's described.
+    result: Option<&'static str>,
+}
+
+const fn endpoint(
+    method: &'static str,
+    path: &'static str,
+    summary: &'static str,
+    result: Option<&'static str>,
+) -> Endpoint {
+    Endpoint {
+        method,
+        path,
+        summary,
+        result,
+    }
+}
+
+const ENDPOINTS: &[Endpoint] = &[
+    endpoint(
+        "get",
+        "/accounts/{account_id_or_address}",
+        "Full account info",
+        None,
+    ),
+    endpoint(
+        "get",
+        "/accounts/{account_id_or_address}/committed",
+        "Committed account state",
+        None,
+    ),
+    endpoint(
+        "get",
+        "/accounts/{account_id_or_address}/committed_and_pending",
+        "Committed account state with the pending transactions applied",
+        None,
+    ),
+    endpoint(
+        "get",
+        "/accounts/{account_id_or_address}/deposits/unconfirmed",
+        "Deposits to the account waiting for the confirmations on Ethereum",
+        None,
+    ),
+    endpoint(
+        "get",
+        "/accounts/{account_id_or_address}/exit_proof",
+        "Requested exit proof of the account balance",
+        None,
+    ),
+    endpoint(
+        "post",
+        "/accounts/{account_id_or_address}/exit_proof",
+        "Request the exit proof of the account balance",
+        None,
+    ),
+    endpoint(
+        "get",
+        "/accounts/{account_id_or_address}/finalized",
+        "Finalized account state",
+        None,
+    ),
+    endpoint(
+        "get",
+        "/accounts/{account_id_or_address}/pending",
+        "Account transactions awaiting in the mempool with their queue positions",
+        None,
+    ),
+    endpoint(
+        "get",
+        "/accounts/{account_id_or_address}/proof",
+        "Merkle proof of the account and its balance",
+        None,
+    ),
+    endpoint(
+        "get",
+        "/accounts/{account_id_or_address}/state_at/{block_number}",
+        "Account state at the block",
+        None,
+    ),
+    endpoint(
+        "get",
+        "/accounts/{account_id_or_address}/transactions",
+        "Account transactions, exported as CSV or NDJSON by the `Accept` header",
+        None,
+    ),
+    endpoint(
+        "get",
+        "/accounts/{account_id_or_address}/transactions/pending",
+        "Pending account transactions",
+        None,
+    ),
+    endpoint("get", "/blocks", "Blocks page", None),
+    endpoint("get", "/blocks/{block_position}", "Block info", None),
+    endpoint(
+        "get",
+        "/blocks/{block_position}/transactions",
+        "Block transactions",
+        None,
+    ),
+    endpoint(
+        "get",
+        "/blocks/{block_position}/transactions/{block_index}",
+        "Transaction by its index in the block",
+        None,
+    ),
+    endpoint("get", "/config", "Network configuration", None),
+    endpoint(
+        "post",
+        "/create2/address",
+        "Address of the CREATE2 smart wallet",
+        None,
+    ),
+    endpoint(
+        "post",
+        "/create2/validate",
+        "Validation of the CREATE2 ChangePubKey auth data",
+        None,
+    ),
+    endpoint(
+        "post",
+        "/create2/change_pubkey",
+        "Submission of the CREATE2 ChangePubKey",
+        None,
+    ),
+    endpoint(
+        "get",
+        "/errors",
+        "Catalog of the error codes",
+        Some("ErrorCatalog"),
+    ),
+    endpoint(
+        "get",
+        "/events",
+        "Stream of the sealed blocks and executed transactions (SSE)",
+        None,
+    ),
+    endpoint("post", "/fee", "Transaction fee", None),
+    endpoint("post", "/fee/batch", "Batch fee", None),
+    endpoint("get", "/fee/history", "History of the quoted fees", None),
+    endpoint(
+        "post",
+        "/forced_exit/estimate",
+        "Estimation of the ForcedExit of the account",
+        None,
+    ),
+    endpoint(
+        "post",
+        "/forced_exit/requests",
+        "Request of the ForcedExit submitted by the operator",
+        None,
+    ),
+    endpoint(
+        "get",
+        "/forced_exit/requests/{id}",
+        "Status of the ForcedExit request",
+        None,
+    ),
+    endpoint(
+        "get",
+        "/mempool/stats",
+        "Current state of the mempool queue",
+        None,
+    ),
+    endpoint("get", "/nft/{id}", "NFT info", None),
+    endpoint("get", "/nft/{id}/owner", "NFT owner", None),
+    endpoint(
+        "get",
+        "/nft/{id}/verify_content_hash",
+        "NFT content hash verification",
+        None,
+    ),
+    endpoint(
+        "get",
+        "/nft/id_by_tx_hash/{tx_hash}",
+        "NFT minted by the transaction",
+        None,
+    ),
+    endpoint(
+        "get",
+        "/networkStats",
+        "Network statistics over the last day",
+        None,
+    ),
+    endpoint("get", "/networkStatus", "Network status", None),
+    endpoint(
+        "get",
+        "/networkStatus/priceProviders",
+        "Health of the fee ticker price providers",
+        None,
+    ),
+    endpoint(
+        "post",
+        "/orders/validate",
+        "Validation of the signed order",
+        None,
+    ),
+    endpoint(
+        "post",
+        "/orders/quote",
+        "Quote of the swap with the order ratio including the fee",
+        None,
+    ),
+    endpoint(
+        "get",
+        "/orders/nonce/{account_id_or_address}",
+        "Nonce the next order of the account should be signed with",
+        None,
+    ),
+    endpoint(
+        "get",
+        "/priority_ops/{id}",
+        "Lifecycle of the priority operation by its serial id or L1 transaction hash",
+        None,
+    ),
+    endpoint(
+        "get",
+        "/search/{query}",
+        "Search of the transaction, account, block or token",
+        None,
+    ),
+    endpoint(
+        "get",
+        "/tokens",
+        "Tokens page or tokens by ids",
+        Some("PaginatedApiToken"),
+    ),
+    endpoint(
+        "post",
+        "/tokens/fee_acceptable_batch",
+        "Acceptability of the tokens for fees",
+        None,
+    ),
+    endpoint(
+        "get",
+        "/tokens/{token_like}",
+        "Token info",
+        Some("ApiToken"),
+    ),
+    endpoint(
+        "get",
+        "/tokens/{token_like}/priceIn/{currency}",
+        "Token price",
+        Some("TokenPrice"),
+    ),
+    endpoint(
+        "get",
+        "/tokens/{token_like}/rate/{quote_token}",
+        "Rate of the token pair",
+        Some("TokenRate"),
+    ),
+    endpoint(
+        "get",
+        "/tokens/{token_like}/price_history",
+        "Token price history",
+        None,
+    ),
+    endpoint(
+        "get",
+        "/tokens/{token_like}/available_currencies",
+        "Currencies of the token price",
+        None,
+    ),
+    endpoint(
+        "get",
+        "/tokens/{token_like}/transactions",
+        "Transactions of the token",
+        None,
+    ),
+    endpoint(
+        "get",
+        "/tokens/nft/{id}",
+        "NFT info, use `/nft/{id}` instead",
+        None,
+    ),
+    endpoint(
+        "get",
+        "/tokens/nft/{id}/owner",
+        "NFT owner, use `/nft/{id}/owner` instead",
+        None,
+    ),
+    endpoint(
+        "get",
+        "/tokens/nft_id_by_tx_hash/{tx_hash}",
+        "NFT minted by the transaction, use `/nft/id_by_tx_hash/{tx_hash}` instead",
+        None,
+    ),
+    endpoint("post", "/transactions", "Submit transaction", None),
+    endpoint("get", "/transactions/{tx_hash}", "Transaction status", None),
+    endpoint(
+        "get",
+        "/transactions/{tx_hash}/wait",
+        "Transaction status once it changes (long polling)",
+        None,
+    ),
+    endpoint(
+        "get",
+        "/transactions/{tx_hash}/data",
+        "Transaction data",
+        None,
+    ),
+    endpoint(
+        "get",
+        "/transactions/{tx_hash}/withdrawal",
+        "Withdrawal stage and estimated finalization time",
+        None,
+    ),
+    endpoint("post", "/transactions/batches", "Submit batch", None),
+    endpoint(
+        "get",
+        "/transactions/batches/{batch_hash}",
+        "Batch status",
+        None,
+    ),
+    endpoint("post", "/transactions/toggle2FA", "Toggle 2FA", None),
+    endpoint("get", "/ws", "Token price subscriptions (WebSocket)", None),
+];
+
+/// Infers the JSON schema of the serialized value.
+fn infer_schema(value: &Value) -> Value {
+    match value {
+        Value::Null => json!({ "nullable": true }),
+        Value::Bool(_) => json!({ "type": "boolean" }),
+        Value::Number(number) if number.is_f64() => json!({ "type": "number" }),
+        Value::Number(_) => json!({ "type": "integer" }),
+        Value::String(_) => json!({ "type": "string" }),
+        Value::Array(items) => json!({
+            "type": "array",
+            "items": items.first().map(infer_schema).unwrap_or_else(|| json!({})),
+        }),
+        Value::Object(fields) => {
+            let required = fields
+                .iter()
+                .filter(|(_, value)| !value.is_null())
+                .map(|(name, _)| Value::from(name.as_str()))
+                .collect::<Vec<_>>();
+            let properties = fields
+                .iter()
+                .map(|(name, value)| (name.clone(), infer_schema(value)))
+                .collect::<Map<_, _>>();
+            json!({
+                "type": "object",
+                "properties": properties,
+                "required": required,
+            })
+        }
+    }
+}
+
+fn schema_of(example: impl Serialize) -> Value {
+    infer_schema(&serde_json::to_value(example).unwrap())
+}
+
+/// Examples of the documented types. The optional fields are set,
+/// so that they are present in the schemas.
+fn component_schemas() -> Map<String, Value> {
+    let token = ApiToken {
+        id: TokenId(0),
+        address: Address::zero(),
+        symbol: "ETH".to_string(),
+        decimals: 18,
+        enabled_for_fees: true,
+        usd_price: Some(BigDecimal::from(1)),
+        market_volume: Some(BigDecimal::from(1)),
+        logo_uri: Some("https://example.com/eth.svg".to_string()),
+        project_url: Some("https://ethereum.org".to_string()),
+        display_name: Some("Ether".to_string()),
+    };
+    let request = Request {
+        network: Network::Mainnet,
+        api_version: ApiVersion::V02,
+        resource: format!("{}/tokens/ETH", API_PREFIX),
+        args: HashMap::new(),
+        timestamp: Utc.timestamp(0, 0),
+    };
+
+    let mut schemas = Map::new();
+    let mut request_schema = schema_of(&request);
+    // The arguments are the path and query parameters of the request.
+    request_schema["properties"]["args"]["additionalProperties"] = json!({ "type": "string" });
+    schemas.insert("Request".to_string(), request_schema);
+    // Either `result` or `error` is set, both are described separately.
+    let mut response = schema_of(Response {
+        request,
+        status: ResultStatus::Success,
+        error: None,
+        result: None,
+    });
+    response["properties"]["request"] = json!({ "$ref": "#/components/schemas/Request" });
+    response["properties"]["error"] = json!({
+        "nullable": true,
+        "allOf": [{ "$ref": "#/components/schemas/Error" }],
+    });
+    response["properties"]["status"]["enum"] = json!(["success", "error"]);
+    schemas.insert("Response".to_string(), response);
+    schemas.insert(
+        "Error".to_string(),
+        schema_of(Error::from(InvalidDataError::InvalidPaginationCursor)),
+    );
+    schemas.insert(
+        "ErrorCatalog".to_string(),
+        schema_of(vec![ErrorCodeInfo::from(ErrorCode::AccountNotFound)]),
+    );
+    schemas.insert("ApiToken".to_string(), schema_of(&token));
+    let mut tokens_page = Paginated::new(vec![token], TokenId(0), 1, PaginationDirection::Newer, 2);
+    tokens_page.pagination.next_cursor = Some("cursor".to_string());
+    schemas.insert("PaginatedApiToken".to_string(), schema_of(tokens_page));
+    schemas.insert(
+        "TokenPrice".to_string(),
+        schema_of(TokenPrice {
+            token_id: TokenId(0),
+            token_symbol: "ETH".to_string(),
+            price_in: "usd".to_string(),
+            decimals: 18,
+            price: BigDecimal::from(1),
+            smoothing_window: Some(1),
+        }),
+    );
+    schemas.insert(
+        "TokenRate".to_string(),
+        schema_of(TokenRate {
+            base_token_id: TokenId(0),
+            base_token_symbol: "ETH".to_string(),
+            quote_token_id: TokenId(1),
+            quote_token_symbol: "USDC".to_string(),
+            rate: BigDecimal::from(1),
+            amount: Some(BigUint::from(1u32).into()),
+            converted_amount: Some(BigUint::from(1u32).into()),
+            formatted_amount: Some("0.000000000000000001".to_string()),
+            formatted_converted_amount: Some("0.000001".to_string()),
+        }),
+    );
+    schemas
+}
+
+/// Returns the names of the path parameters, the actix and OpenAPI formats of them are the same.
+fn path_parameters(path: &str) -> Vec<&str> {
+    path.split('/')
+        .filter_map(|segment| segment.strip_prefix('{')?.strip_suffix('}'))
+        .collect()
+}
+
+fn operation(endpoint: &Endpoint) -> Value {
+    let response_schema = match endpoint.result {
+        Some(result) => json!({
+            "allOf": [
+                { "$ref": "#/components/schemas/Response" },
+                { "properties": { "result": { "$ref": format!("#/components/schemas/{}", result) } } },
+            ]
+        }),
+        None => json!({ "$ref": "#/components/schemas/Response" }),
+    };
+    let parameters = path_parameters(endpoint.path)
+        .into_iter()
+        .map(|name| {
+            json!({
+                "name": name,
+                "in": "path",
+                "required": true,
+                "schema": { "type": "string" },
+            })
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "summary": endpoint.summary,
+        "parameters": parameters,
+        "responses": {
+            "200": {
+                // Errors are returned with the `200` code as well, in the `error` field.
+                "description": "The result or the error of the request",
+                "content": { "application/json": { "schema": response_schema } },
+            }
+        }
+    })
+}
+
+/// Builds the OpenAPI document of the API v0.2.
+pub fn openapi_document() -> Value {
+    let mut paths = Map::new();
+    for endpoint in ENDPOINTS {
+        let path = paths
+            .entry(format!("{}{}", API_PREFIX, endpoint.path))
+            .or_insert_with(|| json!({}));
+        path[endpoint.method] = operation(endpoint);
+    }
+
+    json!({
+        "openapi": OPENAPI_VERSION,
+        "info": {
+            "title": "zkSync REST API",
+            "version": "0.2",
+        },
+        "paths": paths,
+        "components": { "schemas": component_schemas() },
+    })
+}
+
+// Server implementation
+
+pub async fn openapi_spec() -> HttpResponse {
+    HttpResponse::Ok().json(openapi_document())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    /// Sources of the scopes, each file declares one scope.
+    const SCOPE_SOURCES: &[&str] = &[
+        include_str!("account.rs"),
+        include_str!("block.rs"),
+        include_str!("config.rs"),
+        include_str!("create2.rs"),
+        include_str!("errors.rs"),
+        include_str!("event_stream/mod.rs"),
+        include_str!("fee.rs"),
+        include_str!("forced_exit.rs"),
+        include_str!("mempool.rs"),
+        include_str!("network_stats.rs"),
+        include_str!("nft.rs"),
+        include_str!("order.rs"),
+        include_str!("price_subscription/mod.rs"),
+        include_str!("priority_op.rs"),
+        include_str!("search.rs"),
+        include_str!("status.rs"),
+        include_str!("token.rs"),
+        include_str!("transaction.rs"),
+    ];
+
+    /// Returns the first string literal in the text and the rest of the text after it.
+    fn next_literal(text: &str) -> Option<(&str, &str)> {
+        let start = text.find('"')? + 1;
+        let len = text[start..].find('"')?;
+        Some((&text[start..start + len], &text[start + len + 1..]))
+    }
+
+    /// Extracts the `(method, path)` pairs of the routes declared in the scope source.
+    fn declared_routes(source: &str) -> Vec<(String, String)> {
+        let source = &source[..source.find("#[cfg(test)]").unwrap_or_else(|| source.len())];
+        let scope_start = source.find("web::scope(").expect("No scope declared");
+        let (scope, mut rest) = next_literal(&source[scope_start..]).unwrap();
+
+        let mut routes = Vec::new();
+        while let Some(route_start) = rest.find(".route(") {
+            let (path, after_path) = next_literal(&rest[route_start..]).unwrap();
+            let method_start = after_path.find("web::").unwrap() + "web::".len();
+            let method_len = after_path[method_start..].find('(').unwrap();
+            let method = &after_path[method_start..method_start + method_len];
+
+            let path = path.trim_start_matches('/');
+            let path = if path.is_empty() {
+                format!("{}/{}", API_PREFIX, scope)
+            } else {
+                format!("{}/{}/{}", API_PREFIX, scope, path)
+            };
+            routes.push((method.to_string(), path));
+            rest = after_path;
+        }
+        routes
+    }
+
+    #[test]
+    fn all_routes_are_documented() {
+        let document = openapi_document();
+        let routes = SCOPE_SOURCES
+            .iter()
+            .flat_map(|source| declared_routes(source))
+            .collect::<HashSet<_>>();
+        assert!(routes.contains(&("get".to_string(), "/api/v0.2/tokens".to_string())));
+
+        for (method, path) in &routes {
+            assert!(
+                document["paths"][path][method].is_object(),
+                "Route {} {} is not documented",
+                method,
+                path
+            );
+        }
+        // There are no documented endpoints which don't exist.
+        let documented = ENDPOINTS
+            .iter()
+            .map(|endpoint| {
+                (
+                    endpoint.method.to_string(),
+                    format!("{}{}", API_PREFIX, endpoint.path),
+                )
+            })
+            .collect::<HashSet<_>>();
+        assert_eq!(documented, routes);
+    }
+
+    #[test]
+    fn schema_references_are_resolved() {
+        let document = openapi_document();
+        assert_eq!(document["openapi"], OPENAPI_VERSION);
+
+        fn collect_refs(value: &Value, refs: &mut Vec<String>) {
+            match value {
+                Value::Object(fields) => {
+                    if let Some(Value::String(reference)) = fields.get("$ref") {
+                        refs.push(reference.clone());
+                    }
+                    fields.values().for_each(|value| collect_refs(value, refs));
+                }
+                Value::Array(items) => items.iter().for_each(|value| collect_refs(value, refs)),
+                _ => {}
+            }
+        }
+        let mut refs = Vec::new();
+        collect_refs(&document, &mut refs);
+        assert!(!refs.is_empty());
+        for reference in refs {
+            let name = reference
+                .strip_prefix("#/components/schemas/")
+                .expect("Unexpected reference");
+            assert!(
+                document["components"]["schemas"][name].is_object(),
+                "Unresolved reference {}",
+                reference
+            );
+        }
+    }
+
+    #[test]
+    fn schemas_match_serialized_types() {
+        let schemas = component_schemas();
+        let token = &schemas["ApiToken"];
+        assert_eq!(token["type"], "object");
+        assert_eq!(token["properties"]["enabledForFees"]["type"], "boolean");
+        assert_eq!(token["properties"]["decimals"]["type"], "integer");
+        assert_eq!(token["properties"]["usdPrice"]["type"], "string");
+
+        let page = &schemas["PaginatedApiToken"];
+        assert_eq!(
+            page["properties"]["list"]["items"]["properties"]["symbol"]["type"],
+            "string"
+        );
+        assert_eq!(
+            page["properties"]["pagination"]["properties"]["count"]["type"],
+            "integer"
+        );
+        assert!(page["properties"]["pagination"]["properties"]["nextCursor"].is_object());
+
+        let error = &schemas["Error"];
+        assert!(error["properties"]["errorType"].is_object());
+        assert_eq!(error["properties"]["code"]["type"], "integer");
+        assert!(error["properties"]["details"].is_object());
+        assert_eq!(
+            schemas["ErrorCatalog"]["items"]["properties"]["description"]["type"],
+            "string"
+        );
+
+        let response = &schemas["Response"];
+        for field in &["request", "status", "error", "result"] {
+            assert!(response["properties"][field].is_object());
+        }
+        assert_eq!(
+            path_parameters("/tokens/{token_like}/priceIn/{currency}"),
+            vec!["token_like", "currency"]
+        );
+    }
+}
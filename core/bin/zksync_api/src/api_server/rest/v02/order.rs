@@ -0,0 +1,396 @@
+//! Orders part of API implementation.
+//!
+//! The helpers for the DEX front-ends building the `Swap` transactions: the signed order is
+//! checked against the committed state of its account, the swap of the order is quoted along
+//! with the fee, and the nonce the next order of the account should be signed with is returned.
+
+// Built-in uses
+use std::time::Instant;
+
+// External uses
+use actix_web::{
+    web::{self, Json},
+    Scope,
+};
+use chrono::Utc;
+use num::{rational::Ratio, BigUint, Zero};
+
+// Workspace uses
+use zksync_api_types::v02::{
+    account::AccountAddressOrId,
+    fee::ApiFee,
+    order::{OrderNonceState, OrderQuote, OrderQuoteRequest, OrderValidation},
+};
+use zksync_types::{Account, Order, PubKeyHash, Token, TokenLike, TxFeeTypes};
+
+// Local uses
+use super::{
+    account::ApiAccountData,
+    error::{Error, InvalidDataError},
+    extractors::ValidPath,
+    response::ApiResult,
+};
+use crate::{
+    api_server::tx_sender::{SubmitError, TxSender},
+    api_try,
+    fee_ticker::PriceError,
+};
+
+/// Shared data between `api/v0.2/orders` endpoints.
+#[derive(Clone)]
+struct ApiOrderData {
+    tx_sender: TxSender,
+    account_data: ApiAccountData,
+}
+
+impl ApiOrderData {
+    fn new(tx_sender: TxSender) -> Self {
+        let account_data = ApiAccountData::new(tx_sender.pool.clone(), tx_sender.tokens.clone(), 0);
+        Self {
+            tx_sender,
+            account_data,
+        }
+    }
+
+    async fn token(&self, token_like: TokenLike) -> Result<Token, Error> {
+        let mut storage = self
+            .tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?;
+        self.tx_sender
+            .tokens
+            .get_token(&mut storage, token_like)
+            .await
+            .map_err(Error::storage)?
+            .ok_or_else(|| Error::from(PriceError::token_not_found("Token not found")))
+    }
+
+    async fn validate(&self, order: Order) -> Result<OrderValidation, Error> {
+        let mut storage = self
+            .tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?;
+        let account = storage
+            .chain()
+            .account_schema()
+            .last_committed_state_for_account(order.account_id)
+            .await
+            .map_err(Error::storage)?
+            .1;
+
+        let signer = order.verify_signature();
+        let reason = check_order(
+            &order,
+            signer,
+            account.as_ref(),
+            Utc::now().timestamp() as u64,
+        );
+        Ok(OrderValidation {
+            valid: reason.is_none(),
+            reason,
+            signer,
+            account_nonce: account.map(|account| account.nonce),
+        })
+    }
+
+    async fn quote(&self, request: OrderQuoteRequest) -> Result<OrderQuote, Error> {
+        let token_sell = self.token(request.token_sell).await?;
+        let token_buy = self.token(request.token_buy).await?;
+        let fee_token = self.token(request.fee_token).await?;
+        if token_sell.id == token_buy.id {
+            return Err(invalid_order("Order tokens should be different"));
+        }
+
+        let token_allowed = self
+            .tx_sender
+            .ticker
+            .token_allowed_for_fees(TokenLike::Id(fee_token.id))
+            .await
+            .map_err(Error::from)?;
+        if !token_allowed {
+            return Err(Error::from(SubmitError::InappropriateFeeToken));
+        }
+        let fee: ApiFee = self
+            .tx_sender
+            .ticker
+            .get_fee_from_ticker_in_wei(
+                TxFeeTypes::Swap,
+                TokenLike::Id(fee_token.id),
+                request.submitter,
+            )
+            .await
+            .map(|fee| fee.normal_fee.into())
+            .map_err(Error::from)?;
+
+        let (amount_buy, effective_rate) = quote_amounts(
+            &request.amount,
+            &request.ratio,
+            &fee.total_fee,
+            &fee_token,
+            &token_sell,
+            &token_buy,
+        )?;
+        Ok(OrderQuote {
+            token_sell: token_sell.id,
+            token_buy: token_buy.id,
+            amount_sell: request.amount,
+            amount_buy,
+            fee_token: fee_token.id,
+            fee,
+            effective_rate,
+        })
+    }
+
+    async fn nonce_state(
+        &self,
+        account_id_or_address: AccountAddressOrId,
+    ) -> Result<OrderNonceState, Error> {
+        let account_id = self
+            .account_data
+            .get_id_by_address_or_id(account_id_or_address)
+            .await?
+            .ok_or_else(|| Error::from(InvalidDataError::AccountNotFound))?;
+
+        let mut storage = self
+            .tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?;
+        let ((_, finalized), committed) = storage
+            .chain()
+            .account_schema()
+            .last_committed_state_for_account(account_id)
+            .await
+            .map_err(Error::storage)?;
+        let committed = committed.ok_or_else(|| Error::from(InvalidDataError::AccountNotFound))?;
+
+        Ok(OrderNonceState {
+            account_id,
+            committed_nonce: committed.nonce,
+            finalized_nonce: finalized.map(|account| account.nonce),
+        })
+    }
+}
+
+fn invalid_order(reason: impl Into<String>) -> Error {
+    Error::from(InvalidDataError::InvalidOrderParams(reason.into()))
+}
+
+/// Returns the reason why the order signed by `signer` can't be used in the `Swap` of the
+/// account in its committed state, if any.
+fn check_order(
+    order: &Order,
+    signer: Option<PubKeyHash>,
+    account: Option<&Account>,
+    now: u64,
+) -> Option<String> {
+    if let Err(err) = order.check_correctness() {
+        return Some(err.to_string());
+    }
+    if order.token_sell == order.token_buy {
+        return Some("Order tokens should be different".to_string());
+    }
+    if !order.time_range.is_valid(now) {
+        return Some("Order is not valid at the current time".to_string());
+    }
+    let signer = match signer {
+        Some(signer) => signer,
+        None => return Some("Order signature is incorrect".to_string()),
+    };
+    let account = match account {
+        Some(account) => account,
+        None => return Some("Account does not exist".to_string()),
+    };
+    if account.pub_key_hash != signer {
+        return Some("Order is not signed by the signing key of the account".to_string());
+    }
+    if order.nonce < account.nonce {
+        return Some("Order nonce is already used".to_string());
+    }
+    if order.nonce > account.nonce {
+        return Some("Order nonce is greater than the account nonce".to_string());
+    }
+    // The limit orders with the zero amount may be filled with any amount.
+    if !order.amount.is_zero() && account.get_balance(order.token_sell) < order.amount {
+        return Some("Balance of the sold token doesn't cover the order amount".to_string());
+    }
+    None
+}
+
+/// Returns the amount of the bought token and the effective rate of the swap of the `amount`
+/// with the ratio of the order.
+fn quote_amounts(
+    amount: &BigUint,
+    ratio: &(BigUint, BigUint),
+    fee: &BigUint,
+    fee_token: &Token,
+    token_sell: &Token,
+    token_buy: &Token,
+) -> Result<(BigUint, Ratio<BigUint>), Error> {
+    if amount.is_zero() {
+        return Err(invalid_order("Amount of the sold token should be positive"));
+    }
+    if ratio.0.is_zero() || ratio.1.is_zero() {
+        return Err(invalid_order(
+            "Both parts of the order ratio should be positive",
+        ));
+    }
+    let amount_buy = amount * &ratio.1 / &ratio.0;
+
+    let mut total_sell = amount.clone();
+    let mut net_buy = amount_buy.clone();
+    if fee_token.id == token_sell.id {
+        total_sell += fee;
+    } else if fee_token.id == token_buy.id {
+        net_buy = if net_buy > *fee {
+            net_buy - fee
+        } else {
+            BigUint::zero()
+        };
+    }
+    let effective_rate = Ratio::new(
+        net_buy * BigUint::from(10u32).pow(u32::from(token_sell.decimals)),
+        total_sell * BigUint::from(10u32).pow(u32::from(token_buy.decimals)),
+    );
+    Ok((amount_buy, effective_rate))
+}
+
+// Server implementation
+
+async fn validate_order(
+    data: web::Data<ApiOrderData>,
+    Json(body): Json<Order>,
+) -> ApiResult<OrderValidation> {
+    let start = Instant::now();
+    let res = data.validate(body).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "validate_order");
+    res
+}
+
+async fn quote_order(
+    data: web::Data<ApiOrderData>,
+    Json(body): Json<OrderQuoteRequest>,
+) -> ApiResult<OrderQuote> {
+    let start = Instant::now();
+    let res = data.quote(body).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "quote_order");
+    res
+}
+
+async fn order_nonce_state(
+    data: web::Data<ApiOrderData>,
+    ValidPath(account_id_or_address): ValidPath<AccountAddressOrId>,
+) -> ApiResult<OrderNonceState> {
+    let start = Instant::now();
+    let state = api_try!(data.nonce_state(account_id_or_address).await);
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "order_nonce_state");
+    ApiResult::Ok(state)
+}
+
+pub fn api_scope(tx_sender: TxSender) -> Scope {
+    let data = ApiOrderData::new(tx_sender);
+
+    web::scope("orders")
+        .app_data(web::Data::new(data))
+        .route("validate", web::post().to(validate_order))
+        .route("quote", web::post().to(quote_order))
+        .route(
+            "nonce/{account_id_or_address}",
+            web::get().to(order_nonce_state),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::ToPrimitive;
+    use zksync_test_account::ZkSyncAccount;
+    use zksync_types::{tx::TimeRange, AccountId, Address, Nonce, TokenId, TokenKind};
+
+    fn token(id: u32, decimals: u8) -> Token {
+        Token::new(
+            TokenId(id),
+            Address::repeat_byte(id as u8),
+            &format!("T{}", id),
+            decimals,
+            TokenKind::ERC20,
+        )
+    }
+
+    #[test]
+    fn order_checks() {
+        let zksync_account = ZkSyncAccount::rand();
+        zksync_account.set_account_id(Some(AccountId(1)));
+        let order = zksync_account.sign_order(
+            TokenId(1),
+            TokenId(2),
+            1u32.into(),
+            2u32.into(),
+            100u32.into(),
+            &zksync_account.address,
+            Some(Nonce(3)),
+            false,
+            TimeRange::new(0, u64::MAX),
+        );
+        let signer = order.verify_signature();
+        assert_eq!(signer, Some(zksync_account.pubkey_hash));
+
+        let mut account = Account::default_with_address(&zksync_account.address);
+        account.pub_key_hash = zksync_account.pubkey_hash;
+        account.nonce = Nonce(3);
+        account.set_balance(TokenId(1), 100u32.into());
+        assert_eq!(check_order(&order, signer, Some(&account), 1000), None);
+
+        assert!(check_order(&order, signer, None, 1000).is_some());
+        assert!(check_order(&order, None, Some(&account), 1000).is_some());
+
+        let mut used_nonce = account.clone();
+        used_nonce.nonce = Nonce(4);
+        assert!(check_order(&order, signer, Some(&used_nonce), 1000).is_some());
+
+        let mut low_balance = account.clone();
+        low_balance.set_balance(TokenId(1), 99u32.into());
+        assert!(check_order(&order, signer, Some(&low_balance), 1000).is_some());
+
+        let mut other_key = account;
+        other_key.pub_key_hash = PubKeyHash::default();
+        assert!(check_order(&order, signer, Some(&other_key), 1000).is_some());
+    }
+
+    #[test]
+    fn quoted_amounts() {
+        let sell = token(1, 18);
+        let buy = token(2, 6);
+        let other = token(3, 18);
+        let amount = BigUint::from(10u64.pow(18));
+        // One sold token for 2000 bought tokens.
+        let ratio = (
+            BigUint::from(10u64.pow(18)),
+            BigUint::from(2000 * 10u64.pow(6)),
+        );
+
+        let (amount_buy, rate) =
+            quote_amounts(&amount, &ratio, &BigUint::zero(), &other, &sell, &buy).unwrap();
+        assert_eq!(amount_buy, BigUint::from(2000 * 10u64.pow(6)));
+        assert_eq!(rate, Ratio::from_integer(2000u32.into()));
+
+        // The fee in the bought token decreases the received amount.
+        let fee = BigUint::from(10u64.pow(6));
+        let (_, rate) = quote_amounts(&amount, &ratio, &fee, &buy, &sell, &buy).unwrap();
+        assert_eq!(rate, Ratio::from_integer(1999u32.into()));
+
+        // The fee in the sold token increases the sold amount.
+        let fee = BigUint::from(10u64.pow(18));
+        let (_, rate) = quote_amounts(&amount, &ratio, &fee, &sell, &sell, &buy).unwrap();
+        assert_eq!(rate.to_integer().to_u32(), Some(1000));
+
+        assert!(quote_amounts(&BigUint::zero(), &ratio, &fee, &sell, &sell, &buy).is_err());
+        let zero_ratio = (BigUint::zero(), BigUint::from(1u32));
+        assert!(quote_amounts(&amount, &zero_ratio, &fee, &sell, &sell, &buy).is_err());
+    }
+}
@@ -8,13 +8,15 @@ use zksync_api_types::{
         block::BlockInfo,
         pagination::{
             AccountTxsRequest, ApiEither, BlockAndTxHash, Paginated, PaginationQuery,
-            PendingOpsRequest,
+            PendingOpsRequest, TokenTxsRequest, TokensRequest,
         },
         transaction::{Transaction, TxHashSerializeWrapper},
     },
     Either,
 };
-use zksync_storage::StorageProcessor;
+#[cfg(test)]
+use zksync_storage::tokens::memory::MemoryTokensStorage;
+use zksync_storage::{tokens::TokensStorage, StorageProcessor};
 use zksync_types::{BlockNumber, SerialId, Token, TokenId};
 
 // Local uses
@@ -31,6 +33,10 @@ impl Paginate<ApiEither<TokenId>> for StorageProcessor<'_> {
     type OutputObj = Token;
     type OutputId = TokenId;
 
+    fn cursor_key(token: &Token) -> Option<String> {
+        Some(token.id.to_string())
+    }
+
     async fn paginate(
         &mut self,
         query: &PaginationQuery<ApiEither<TokenId>>,
@@ -76,11 +82,112 @@ impl Paginate<ApiEither<TokenId>> for StorageProcessor<'_> {
     }
 }
 
+#[async_trait::async_trait]
+impl Paginate<TokensRequest> for StorageProcessor<'_> {
+    type OutputObj = Token;
+    type OutputId = TokenId;
+
+    fn cursor_key(token: &Token) -> Option<String> {
+        Some(token.id.to_string())
+    }
+
+    async fn paginate(
+        &mut self,
+        query: &PaginationQuery<TokensRequest>,
+    ) -> Result<Paginated<Token, TokenId>, Error> {
+        let mut transaction = self.start_transaction().await.map_err(Error::storage)?;
+        let paginated = paginate_tokens(&mut transaction, query).await?;
+        transaction.commit().await.map_err(Error::storage)?;
+        Ok(paginated)
+    }
+}
+
+#[cfg(test)]
+#[async_trait::async_trait]
+impl Paginate<TokensRequest> for MemoryTokensStorage {
+    type OutputObj = Token;
+    type OutputId = TokenId;
+
+    fn cursor_key(token: &Token) -> Option<String> {
+        Some(token.id.to_string())
+    }
+
+    async fn paginate(
+        &mut self,
+        query: &PaginationQuery<TokensRequest>,
+    ) -> Result<Paginated<Token, TokenId>, Error> {
+        paginate_tokens(self, query).await
+    }
+}
+
+/// Loads the page of the tokens from any token storage.
+async fn paginate_tokens<S: TokensStorage>(
+    storage: &mut S,
+    query: &PaginationQuery<TokensRequest>,
+) -> Result<Paginated<Token, TokenId>, Error> {
+    let filter = &query.from.filter;
+    let min_market_volume = &query.from.min_market_volume;
+    let denylisted_tokens = &query.from.denylisted_tokens;
+
+    let token_id = match query.from.token_id.inner {
+        Either::Left(token_id) => token_id,
+        Either::Right(_) => {
+            if let Some(token_id) = storage
+                .get_last_filtered_token_id(filter, min_market_volume, denylisted_tokens)
+                .await
+                .map_err(Error::storage)?
+            {
+                token_id
+            } else {
+                return Ok(Paginated::new(
+                    Vec::new(),
+                    Default::default(),
+                    query.limit,
+                    query.direction,
+                    0,
+                ));
+            }
+        }
+    };
+
+    let query = PaginationQuery {
+        from: TokensRequest {
+            token_id: ApiEither::from(token_id),
+            filter: filter.clone(),
+            min_market_volume: min_market_volume.clone(),
+            denylisted_tokens: denylisted_tokens.clone(),
+        },
+        limit: query.limit,
+        direction: query.direction,
+    };
+
+    let tokens = storage
+        .load_filtered_token_page(&query)
+        .await
+        .map_err(Error::storage)?;
+    let count = storage
+        .get_filtered_count(filter, min_market_volume, denylisted_tokens)
+        .await
+        .map_err(Error::storage)?;
+
+    Ok(Paginated::new(
+        tokens,
+        token_id,
+        query.limit,
+        query.direction,
+        count,
+    ))
+}
+
 #[async_trait::async_trait]
 impl Paginate<ApiEither<BlockNumber>> for StorageProcessor<'_> {
     type OutputObj = BlockInfo;
     type OutputId = BlockNumber;
 
+    fn cursor_key(block: &BlockInfo) -> Option<String> {
+        Some(block.block_number.to_string())
+    }
+
     async fn paginate(
         &mut self,
         query: &PaginationQuery<ApiEither<BlockNumber>>,
@@ -130,6 +237,10 @@ impl Paginate<BlockAndTxHash> for StorageProcessor<'_> {
     type OutputObj = Transaction;
     type OutputId = TxHashSerializeWrapper;
 
+    fn cursor_key(tx: &Transaction) -> Option<String> {
+        Some(tx.tx_hash.to_string())
+    }
+
     async fn paginate(
         &mut self,
         query: &PaginationQuery<BlockAndTxHash>,
@@ -199,6 +310,10 @@ impl Paginate<AccountTxsRequest> for StorageProcessor<'_> {
     type OutputObj = Transaction;
     type OutputId = TxHashSerializeWrapper;
 
+    fn cursor_key(tx: &Transaction) -> Option<String> {
+        Some(tx.tx_hash.to_string())
+    }
+
     async fn paginate(
         &mut self,
         query: &PaginationQuery<AccountTxsRequest>,
@@ -247,10 +362,11 @@ impl Paginate<AccountTxsRequest> for StorageProcessor<'_> {
         let count = transaction
             .chain()
             .operations_ext_schema()
-            .get_account_transactions_count(
+            .get_filtered_account_transactions_count(
                 query.from.address,
                 query.from.token,
                 query.from.second_address,
+                &query.from.filters,
             )
             .await
             .map_err(Error::storage)?;
@@ -267,6 +383,79 @@ impl Paginate<AccountTxsRequest> for StorageProcessor<'_> {
     }
 }
 
+#[async_trait::async_trait]
+impl Paginate<TokenTxsRequest> for StorageProcessor<'_> {
+    type OutputObj = Transaction;
+    type OutputId = TxHashSerializeWrapper;
+
+    fn cursor_key(tx: &Transaction) -> Option<String> {
+        Some(tx.tx_hash.to_string())
+    }
+
+    async fn paginate(
+        &mut self,
+        query: &PaginationQuery<TokenTxsRequest>,
+    ) -> Result<Paginated<Transaction, TxHashSerializeWrapper>, Error> {
+        let mut transaction = self.start_transaction().await.map_err(Error::storage)?;
+
+        let tx_hash = match query.from.tx_hash.inner {
+            Either::Left(tx_hash) => tx_hash,
+            Either::Right(_) => {
+                if let Some(tx_hash) = transaction
+                    .chain()
+                    .operations_ext_schema()
+                    .get_token_last_tx_hash(query.from.token)
+                    .await
+                    .map_err(Error::storage)?
+                {
+                    tx_hash
+                } else {
+                    return Ok(Paginated::new(
+                        Vec::new(),
+                        Default::default(),
+                        query.limit,
+                        query.direction,
+                        0,
+                    ));
+                }
+            }
+        };
+
+        let query = PaginationQuery {
+            from: TokenTxsRequest {
+                token: query.from.token,
+                tx_hash: ApiEither::from(tx_hash),
+            },
+            limit: query.limit,
+            direction: query.direction,
+        };
+
+        let txs = transaction
+            .chain()
+            .operations_ext_schema()
+            .get_token_transactions(&query)
+            .await
+            .map_err(Error::storage)?
+            .ok_or_else(|| Error::from(InvalidDataError::TransactionNotFound))?;
+        let count = transaction
+            .chain()
+            .operations_ext_schema()
+            .get_token_transactions_count(query.from.token)
+            .await
+            .map_err(Error::storage)?;
+
+        transaction.commit().await.map_err(Error::storage)?;
+
+        Ok(Paginated::new(
+            txs,
+            TxHashSerializeWrapper(tx_hash),
+            query.limit,
+            query.direction,
+            count,
+        ))
+    }
+}
+
 #[async_trait::async_trait]
 impl Paginate<PendingOpsRequest> for StorageProcessor<'_> {
     type OutputObj = Transaction;
@@ -305,7 +494,12 @@ impl Paginate<PendingOpsRequest> for StorageProcessor<'_> {
             .await
             .map_err(Error::storage)?;
 
-        let count = result.len() as u32;
+        let count = self
+            .chain()
+            .mempool_schema()
+            .get_pending_deposits_count(query.from.address)
+            .await
+            .map_err(Error::storage)?;
         let txs = result
             .into_iter()
             .map(|op| {
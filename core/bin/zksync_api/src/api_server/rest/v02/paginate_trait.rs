@@ -7,7 +7,10 @@ use serde::Serialize;
 use zksync_api_types::v02::pagination::{Paginated, PaginationQuery, MAX_LIMIT};
 
 // Local uses
-use super::error::{Error, InvalidDataError};
+use super::{
+    error::{Error, InvalidDataError},
+    pagination_cursor::PaginationCursors,
+};
 
 #[async_trait::async_trait]
 pub trait Paginate<I: Serialize + Send + Sync + 'static> {
@@ -29,4 +32,37 @@ pub trait Paginate<I: Serialize + Send + Sync + 'static> {
             self.paginate(query).await
         }
     }
+
+    /// Returns the key of the object in the format of the `from` query parameter.
+    /// The pages of the objects without such a key have no cursors.
+    fn cursor_key(_obj: &Self::OutputObj) -> Option<String> {
+        None
+    }
+
+    /// Loads the page along with the opaque cursor of the next one.
+    async fn paginate_with_cursor(
+        &mut self,
+        mut query: PaginationQuery<I>,
+        cursors: &PaginationCursors,
+    ) -> Result<Paginated<Self::OutputObj, Self::OutputId>, Error> {
+        if query.limit > MAX_LIMIT {
+            return Err(Error::from(InvalidDataError::PaginationLimitTooBig));
+        }
+        let limit = query.limit;
+        // The extra object is the first one of the next page, the `from` parameter is inclusive.
+        query.limit += 1;
+        let mut page = self.paginate(&query).await?;
+        page.pagination.limit = limit;
+        if page.list.len() > limit as usize {
+            let next = page.list.pop().unwrap();
+            page.pagination.next_cursor = <Self as Paginate<I>>::cursor_key(&next).map(|from| {
+                cursors.encode(&PaginationQuery {
+                    from,
+                    limit,
+                    direction: query.direction,
+                })
+            });
+        }
+        Ok(page)
+    }
 }
@@ -0,0 +1,177 @@
+//! Opaque pagination cursors.
+//!
+//! The cursor is the signed pagination query of the next page. The clients can't
+//! forge it and don't have to know the keys the objects are ordered by, so the
+//! ordering can be changed without breaking the pagination.
+
+// Built-in uses
+use std::str::FromStr;
+
+// External uses
+use serde::Serialize;
+use tiny_keccak::keccak256;
+
+// Workspace uses
+use zksync_api_types::v02::pagination::{
+    parse_query, ApiEither, PaginationQuery, PaginationQueryOrCursor,
+};
+use zksync_config::configs::api::RestApiConfig;
+
+// Local uses
+use super::error::{Error, InvalidDataError};
+
+/// Number of the signature bytes included into the cursor.
+const SIGNATURE_LEN: usize = 16;
+
+/// Signs the pagination cursors and checks the ones provided by the clients.
+#[derive(Debug, Clone)]
+pub struct PaginationCursors {
+    key: [u8; 32],
+}
+
+impl PaginationCursors {
+    pub fn new(secret: &str) -> Self {
+        Self {
+            key: keccak256(secret.as_bytes()),
+        }
+    }
+
+    pub fn from_config(config: &RestApiConfig) -> Self {
+        Self::new(&config.pagination_cursor_secret)
+    }
+
+    fn signature(&self, payload: &[u8]) -> [u8; 32] {
+        // Keccak is not prone to the length extension attacks,
+        // so the keyed hash is enough.
+        keccak256(&[&self.key[..], payload].concat())
+    }
+
+    pub fn encode(&self, query: &PaginationQuery<String>) -> String {
+        let payload = serde_json::to_vec(query).unwrap();
+        let signature = self.signature(&payload);
+        format!(
+            "{}.{}",
+            hex::encode(&payload),
+            hex::encode(&signature[..SIGNATURE_LEN])
+        )
+    }
+
+    pub fn decode(&self, cursor: &str) -> Result<PaginationQuery<String>, Error> {
+        let invalid_cursor = || Error::from(InvalidDataError::InvalidPaginationCursor);
+
+        let mut parts = cursor.splitn(2, '.');
+        let payload = parts.next().and_then(|part| hex::decode(part).ok());
+        let signature = parts.next().and_then(|part| hex::decode(part).ok());
+        let (payload, signature) = payload.zip(signature).ok_or_else(invalid_cursor)?;
+
+        let expected_signature = self.signature(&payload);
+        let is_valid = signature.len() == SIGNATURE_LEN
+            && signature
+                .iter()
+                .zip(&expected_signature[..SIGNATURE_LEN])
+                .fold(0, |acc, (a, b)| acc | (a ^ b))
+                == 0;
+        if !is_valid {
+            return Err(invalid_cursor());
+        }
+        serde_json::from_slice(&payload).map_err(|_| invalid_cursor())
+    }
+
+    /// Returns the pagination query given either explicitly or by the cursor.
+    pub fn resolve(
+        &self,
+        query: PaginationQueryOrCursor,
+    ) -> Result<PaginationQuery<String>, Error> {
+        if let Some(cursor) = query.cursor {
+            return self.decode(&cursor);
+        }
+        match (query.from, query.limit, query.direction) {
+            (Some(from), Some(limit), Some(direction)) => Ok(PaginationQuery {
+                from,
+                limit,
+                direction,
+            }),
+            _ => Err(Error::from(InvalidDataError::MissingPaginationParameters)),
+        }
+    }
+
+    /// Resolves the pagination query and parses its `from` parameter.
+    pub fn parse_query<T: FromStr + Serialize>(
+        &self,
+        query: PaginationQueryOrCursor,
+    ) -> Result<PaginationQuery<ApiEither<T>>, Error> {
+        let query = self.resolve(query)?;
+        parse_query(query).map_err(Error::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_api_types::v02::pagination::PaginationDirection;
+
+    #[test]
+    fn cursors_are_signed() {
+        let cursors = PaginationCursors::new("secret");
+        let query = PaginationQuery {
+            from: "42".to_string(),
+            limit: 10,
+            direction: PaginationDirection::Older,
+        };
+        let cursor = cursors.encode(&query);
+
+        let decoded = cursors.decode(&cursor).unwrap();
+        assert_eq!(decoded.from, query.from);
+        assert_eq!(decoded.limit, query.limit);
+        assert_eq!(decoded.direction, query.direction);
+
+        // The cursor signed with another secret is rejected.
+        assert!(PaginationCursors::new("other").decode(&cursor).is_err());
+
+        // The forged payload is rejected.
+        let (_, signature) = cursor.split_at(cursor.find('.').unwrap());
+        let forged_query = PaginationQuery {
+            from: "43".to_string(),
+            ..query
+        };
+        let forged_payload = hex::encode(serde_json::to_vec(&forged_query).unwrap());
+        assert!(cursors
+            .decode(&format!("{}{}", forged_payload, signature))
+            .is_err());
+
+        assert!(cursors.decode("").is_err());
+        assert!(cursors.decode("zz.zz").is_err());
+    }
+
+    #[test]
+    fn resolve_query() {
+        let cursors = PaginationCursors::new("secret");
+        let query = PaginationQuery {
+            from: "latest".to_string(),
+            limit: 5,
+            direction: PaginationDirection::Newer,
+        };
+        let cursor = cursors.encode(&query);
+
+        // The cursor takes precedence over the explicit parameters.
+        let resolved = cursors
+            .resolve(PaginationQueryOrCursor {
+                from: Some("1".to_string()),
+                ..PaginationQueryOrCursor::from_cursor(cursor)
+            })
+            .unwrap();
+        assert_eq!(resolved.from, "latest");
+
+        let resolved = cursors
+            .resolve(PaginationQueryOrCursor::from(query))
+            .unwrap();
+        assert_eq!(resolved.limit, 5);
+
+        let incomplete = PaginationQueryOrCursor {
+            from: Some("1".to_string()),
+            limit: Some(5),
+            ..Default::default()
+        };
+        assert!(cursors.resolve(incomplete).is_err());
+    }
+}
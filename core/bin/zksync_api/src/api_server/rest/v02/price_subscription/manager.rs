@@ -0,0 +1,294 @@
+// Built-in uses
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::sync::Arc;
+use std::time::Duration;
+// External uses
+use actix::prelude::*;
+use bigdecimal::{BigDecimal, Zero};
+use chrono::Utc;
+use futures::{stream, StreamExt};
+// Workspace uses
+use zksync_api_types::v02::token::TokenPriceUpdate;
+use zksync_types::{TokenId, TokenLike};
+// Local uses
+use super::messages::{PriceUpdate, RemoveSubscriber, Subscribe, Unsubscribe};
+use super::subscriber::PriceSubscriber;
+use crate::fee_ticker::{FeeTicker, TokenPriceRequestType};
+
+/// How often the prices of the subscribed tokens are requested from the ticker.
+const PRICE_UPDATE_INTERVAL: Duration = Duration::from_secs(10);
+/// Maximum number of token prices requested from the ticker at once.
+const PRICE_REQUEST_CONCURRENCY: usize = 10;
+
+/// Subscriptions of the clients to the price updates of the tokens.
+#[derive(Debug)]
+struct PriceTopics<S: Eq + Hash> {
+    subscribers: HashMap<TokenId, HashSet<S>>,
+    /// The last pushed update of every token, so that only the changes are pushed.
+    last_updates: HashMap<TokenId, Arc<TokenPriceUpdate>>,
+}
+
+impl<S: Eq + Hash + Clone> PriceTopics<S> {
+    fn new() -> Self {
+        Self {
+            subscribers: HashMap::new(),
+            last_updates: HashMap::new(),
+        }
+    }
+
+    /// Subscribes to the tokens and returns their already known prices.
+    fn subscribe(&mut self, subscriber: S, tokens: &[TokenId]) -> Vec<Arc<TokenPriceUpdate>> {
+        for token_id in tokens {
+            self.subscribers
+                .entry(*token_id)
+                .or_default()
+                .insert(subscriber.clone());
+        }
+        tokens
+            .iter()
+            .filter_map(|token_id| self.last_updates.get(token_id).cloned())
+            .collect()
+    }
+
+    fn unsubscribe(&mut self, subscriber: &S, tokens: &[TokenId]) {
+        for token_id in tokens {
+            let is_empty = match self.subscribers.get_mut(token_id) {
+                Some(subscribers) => {
+                    subscribers.remove(subscriber);
+                    subscribers.is_empty()
+                }
+                None => false,
+            };
+            // Nobody will receive the updates, so the topic is no longer tracked.
+            if is_empty {
+                self.subscribers.remove(token_id);
+                self.last_updates.remove(token_id);
+            }
+        }
+    }
+
+    fn remove(&mut self, subscriber: &S) {
+        let tokens = self.tokens();
+        self.unsubscribe(subscriber, &tokens);
+    }
+
+    fn tokens(&self) -> Vec<TokenId> {
+        self.subscribers.keys().copied().collect()
+    }
+
+    /// Records the new prices and returns the changed ones along with their subscribers.
+    fn update_prices(
+        &mut self,
+        updates: Vec<TokenPriceUpdate>,
+    ) -> Vec<(Arc<TokenPriceUpdate>, Vec<S>)> {
+        let mut changed = Vec::new();
+        for update in updates {
+            let subscribers = match self.subscribers.get(&update.token_id) {
+                Some(subscribers) => subscribers,
+                // The topic has been removed while the price was requested.
+                None => continue,
+            };
+            let is_changed = self
+                .last_updates
+                .get(&update.token_id)
+                .map_or(true, |last_update| last_update.price != update.price);
+            if is_changed {
+                let update = Arc::new(update);
+                self.last_updates.insert(update.token_id, update.clone());
+                changed.push((update, subscribers.iter().cloned().collect()));
+            }
+        }
+        changed
+    }
+}
+
+/// The actor that polls the fee ticker for the prices of the subscribed tokens
+/// and pushes the price changes to the subscribers.
+pub struct PriceSubscriptionManager {
+    fee_ticker: FeeTicker,
+    topics: PriceTopics<Addr<PriceSubscriber>>,
+    /// Whether the prices are being requested from the ticker right now.
+    updating: bool,
+}
+
+impl PriceSubscriptionManager {
+    pub fn new(fee_ticker: FeeTicker) -> Self {
+        Self {
+            fee_ticker,
+            topics: PriceTopics::new(),
+            updating: false,
+        }
+    }
+
+    fn request_prices(&mut self, ctx: &mut <Self as Actor>::Context) {
+        // Don't pile up the requests if the ticker is slow.
+        if self.updating || self.topics.subscribers.is_empty() {
+            return;
+        }
+        self.updating = true;
+
+        let fee_ticker = self.fee_ticker.clone();
+        let tokens = self.topics.tokens();
+        async move {
+            stream::iter(tokens)
+                .map(|token_id| {
+                    let fee_ticker = &fee_ticker;
+                    async move {
+                        let price = fee_ticker
+                            .get_token_price(
+                                TokenLike::Id(token_id),
+                                TokenPriceRequestType::USDForOneToken,
+                            )
+                            .await;
+                        (token_id, price)
+                    }
+                })
+                .buffer_unordered(PRICE_REQUEST_CONCURRENCY)
+                .collect::<Vec<_>>()
+                .await
+        }
+        .into_actor(self)
+        .map(|prices, act, _| {
+            act.updating = false;
+            let updated_at = Utc::now();
+            let updates = prices
+                .into_iter()
+                .filter_map(|(token_id, price)| match price {
+                    Ok(price) if !price.is_zero() => Some(TokenPriceUpdate {
+                        token_id,
+                        price,
+                        updated_at,
+                    }),
+                    Ok(_) => None,
+                    Err(err) => {
+                        vlog::warn!("Can't get the price of the token {}: {}", token_id, err);
+                        None
+                    }
+                })
+                .collect();
+            for (update, subscribers) in act.topics.update_prices(updates) {
+                for subscriber in subscribers {
+                    act.push(&subscriber, PriceUpdate(update.clone()));
+                }
+            }
+        })
+        .spawn(ctx);
+    }
+
+    fn push(&mut self, subscriber: &Addr<PriceSubscriber>, update: PriceUpdate) {
+        match subscriber.try_send(update) {
+            Ok(()) => {}
+            // The subscriber doesn't keep up with the updates. The price is only
+            // relevant until the next update, so it's fine to skip it.
+            Err(SendError::Full(_)) => {
+                metrics::increment_counter!("api.v02.price_subscription.skipped_updates");
+            }
+            // The subscriber has finished its work without notifying the manager.
+            Err(SendError::Closed(_)) => self.topics.remove(subscriber),
+        }
+    }
+}
+
+impl Actor for PriceSubscriptionManager {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        ctx.set_mailbox_capacity(1 << 16);
+        ctx.run_interval(PRICE_UPDATE_INTERVAL, |act, ctx| act.request_prices(ctx));
+    }
+
+    fn stopped(&mut self, _ctx: &mut Self::Context) {
+        vlog::warn!("PriceSubscriptionManager actor has stopped");
+    }
+}
+
+impl Handler<Subscribe> for PriceSubscriptionManager {
+    type Result = ();
+
+    fn handle(&mut self, msg: Subscribe, _ctx: &mut Self::Context) {
+        // Send the known prices right away instead of waiting for them to change.
+        let known_updates = self.topics.subscribe(msg.subscriber.clone(), &msg.tokens);
+        for update in known_updates {
+            self.push(&msg.subscriber, PriceUpdate(update));
+        }
+    }
+}
+
+impl Handler<Unsubscribe> for PriceSubscriptionManager {
+    type Result = ();
+
+    fn handle(&mut self, msg: Unsubscribe, _ctx: &mut Self::Context) {
+        self.topics.unsubscribe(&msg.subscriber, &msg.tokens);
+    }
+}
+
+impl Handler<RemoveSubscriber> for PriceSubscriptionManager {
+    type Result = ();
+
+    fn handle(&mut self, msg: RemoveSubscriber, _ctx: &mut Self::Context) {
+        self.topics.remove(&msg.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update(token_id: u32, price: u32) -> TokenPriceUpdate {
+        TokenPriceUpdate {
+            token_id: TokenId(token_id),
+            price: BigDecimal::from(price),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn only_changed_prices_are_pushed() {
+        let mut topics = PriceTopics::new();
+        assert!(topics.subscribe(1, &[TokenId(1), TokenId(2)]).is_empty());
+        topics.subscribe(2, &[TokenId(2)]);
+
+        let mut changed = topics.update_prices(vec![update(1, 10), update(2, 20), update(3, 30)]);
+        changed.sort_by_key(|(update, _)| update.token_id);
+        let changed: Vec<_> = changed
+            .into_iter()
+            .map(|(update, mut subscribers)| {
+                subscribers.sort_unstable();
+                (update.token_id, subscribers)
+            })
+            .collect();
+        // Nobody is subscribed to the third token.
+        assert_eq!(
+            changed,
+            vec![(TokenId(1), vec![1]), (TokenId(2), vec![1, 2])]
+        );
+
+        // Unchanged price is not pushed again.
+        let changed = topics.update_prices(vec![update(1, 10), update(2, 25)]);
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0].0.token_id, TokenId(2));
+
+        // The new subscriber receives the last known price.
+        let known = topics.subscribe(3, &[TokenId(1)]);
+        assert_eq!(known.len(), 1);
+        assert_eq!(known[0].price, BigDecimal::from(10));
+    }
+
+    #[test]
+    fn unused_topics_are_removed() {
+        let mut topics = PriceTopics::new();
+        topics.subscribe(1, &[TokenId(1), TokenId(2)]);
+        topics.subscribe(2, &[TokenId(2)]);
+        topics.update_prices(vec![update(1, 10), update(2, 20)]);
+
+        topics.remove(&1);
+        assert_eq!(topics.tokens(), vec![TokenId(2)]);
+        // The last price of the removed topic is forgotten.
+        assert!(topics.subscribe(2, &[TokenId(1)]).is_empty());
+
+        topics.unsubscribe(&2, &[TokenId(1), TokenId(2)]);
+        assert!(topics.tokens().is_empty());
+        assert!(topics.update_prices(vec![update(2, 30)]).is_empty());
+    }
+}
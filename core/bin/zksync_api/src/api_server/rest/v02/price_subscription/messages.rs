@@ -0,0 +1,32 @@
+// Built-in uses
+use std::sync::Arc;
+// External uses
+use actix::prelude::*;
+// Workspace uses
+use zksync_api_types::v02::token::TokenPriceUpdate;
+use zksync_types::TokenId;
+// Local uses
+use super::subscriber::PriceSubscriber;
+
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct Subscribe {
+    pub subscriber: Addr<PriceSubscriber>,
+    pub tokens: Vec<TokenId>,
+}
+
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct Unsubscribe {
+    pub subscriber: Addr<PriceSubscriber>,
+    pub tokens: Vec<TokenId>,
+}
+
+/// Removes the subscriber from all the topics.
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct RemoveSubscriber(pub Addr<PriceSubscriber>);
+
+#[derive(Debug, Clone, Message)]
+#[rtype(result = "()")]
+pub struct PriceUpdate(pub Arc<TokenPriceUpdate>);
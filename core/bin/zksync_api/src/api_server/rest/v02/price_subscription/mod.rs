@@ -0,0 +1,37 @@
+//! WebSocket channel pushing the token price updates to the subscribed clients.
+//!
+//! After connecting, the client sends the JSON-serialized `PriceSubscriptionRequest`
+//! to choose the tokens it's interested in, and receives the `TokenPriceUpdate`
+//! every time the USD price of one of them changes.
+
+// Built-in uses
+// External uses
+use actix::prelude::*;
+use actix_web::{web, Error, HttpRequest, HttpResponse, Scope};
+use actix_web_actors::ws;
+// Workspace uses
+// Local uses
+pub use manager::PriceSubscriptionManager;
+use subscriber::PriceSubscriber;
+
+mod manager;
+mod messages;
+mod subscriber;
+
+async fn subscribe(
+    req: HttpRequest,
+    stream: web::Payload,
+    manager: web::Data<Addr<PriceSubscriptionManager>>,
+) -> Result<HttpResponse, Error> {
+    ws::start(
+        PriceSubscriber::new(manager.get_ref().clone()),
+        &req,
+        stream,
+    )
+}
+
+pub fn api_scope(manager: Addr<PriceSubscriptionManager>) -> Scope {
+    web::scope("ws")
+        .app_data(web::Data::new(manager))
+        .route("", web::get().to(subscribe))
+}
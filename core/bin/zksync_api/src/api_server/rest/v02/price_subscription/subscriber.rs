@@ -0,0 +1,154 @@
+// Built-in uses
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+// External uses
+use actix::prelude::*;
+use actix_web_actors::ws;
+// Workspace uses
+use zksync_api_types::v02::token::PriceSubscriptionRequest;
+use zksync_types::TokenId;
+// Local uses
+use super::manager::PriceSubscriptionManager;
+use super::messages::{PriceUpdate, RemoveSubscriber, Subscribe, Unsubscribe};
+
+/// Maximum number of tokens a single client can be subscribed to.
+const MAX_SUBSCRIBED_TOKENS: usize = 100;
+/// Number of the updates that can be queued for the client. Once the queue is full,
+/// the new updates are skipped until the client catches up.
+const MAILBOX_CAPACITY: usize = 16;
+/// How often the client is pinged.
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
+/// The client that didn't respond within this time is disconnected.
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// The WebSocket actor. Created for each connected client.
+pub struct PriceSubscriber {
+    /// Tokens the client is subscribed to.
+    tokens: HashSet<TokenId>,
+    /// The last time the client has shown any sign of life.
+    last_heartbeat: Instant,
+    /// The address of the [`PriceSubscriptionManager`] for subscribing.
+    manager: Addr<PriceSubscriptionManager>,
+}
+
+impl PriceSubscriber {
+    pub fn new(manager: Addr<PriceSubscriptionManager>) -> Self {
+        Self {
+            tokens: HashSet::new(),
+            last_heartbeat: Instant::now(),
+            manager,
+        }
+    }
+
+    /// Removes the subscriber from the manager and stops the execution context.
+    /// Should be called instead of `ctx.stop()`.
+    fn shutdown(&mut self, ctx: &mut <Self as Actor>::Context) {
+        self.manager.do_send(RemoveSubscriber(ctx.address()));
+        ctx.stop();
+    }
+
+    /// Closes the connection with the given reason and stops the actor.
+    fn close(&mut self, ctx: &mut <Self as Actor>::Context, code: ws::CloseCode, reason: String) {
+        ctx.close(Some(ws::CloseReason {
+            code,
+            description: Some(reason),
+        }));
+        self.shutdown(ctx);
+    }
+
+    fn handle_request(
+        &mut self,
+        request: PriceSubscriptionRequest,
+        ctx: &mut <Self as Actor>::Context,
+    ) {
+        match request {
+            PriceSubscriptionRequest::Subscribe { tokens } => {
+                let tokens: Vec<_> = tokens
+                    .into_iter()
+                    .filter(|token_id| !self.tokens.contains(token_id))
+                    .collect::<HashSet<_>>()
+                    .into_iter()
+                    .collect();
+                if self.tokens.len() + tokens.len() > MAX_SUBSCRIBED_TOKENS {
+                    let reason = format!(
+                        "Can't subscribe to more than {} tokens",
+                        MAX_SUBSCRIBED_TOKENS
+                    );
+                    self.close(ctx, ws::CloseCode::Policy, reason);
+                    return;
+                }
+                self.tokens.extend(tokens.iter().copied());
+                self.manager.do_send(Subscribe {
+                    subscriber: ctx.address(),
+                    tokens,
+                });
+            }
+            PriceSubscriptionRequest::Unsubscribe { tokens } => {
+                for token_id in &tokens {
+                    self.tokens.remove(token_id);
+                }
+                self.manager.do_send(Unsubscribe {
+                    subscriber: ctx.address(),
+                    tokens,
+                });
+            }
+        }
+    }
+}
+
+impl Actor for PriceSubscriber {
+    type Context = ws::WebsocketContext<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        ctx.set_mailbox_capacity(MAILBOX_CAPACITY);
+        // A client that doesn't read from the socket won't answer the pings either,
+        // so it's disconnected instead of having the updates buffered forever.
+        ctx.run_interval(HEARTBEAT_INTERVAL, |act, ctx| {
+            if act.last_heartbeat.elapsed() > CLIENT_TIMEOUT {
+                act.close(ctx, ws::CloseCode::Away, "Client timed out".to_string());
+            } else {
+                ctx.ping(b"");
+            }
+        });
+    }
+}
+
+impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for PriceSubscriber {
+    fn handle(&mut self, msg: Result<ws::Message, ws::ProtocolError>, ctx: &mut Self::Context) {
+        self.last_heartbeat = Instant::now();
+        match msg {
+            Ok(ws::Message::Ping(msg)) => ctx.pong(&msg),
+            Ok(ws::Message::Text(text)) => match serde_json::from_str(&text) {
+                Ok(request) => self.handle_request(request, ctx),
+                // The client provided invalid JSON, give it the error message
+                // and close the connection.
+                Err(err) => self.close(ctx, ws::CloseCode::Policy, err.to_string()),
+            },
+            Ok(ws::Message::Close(reason)) => {
+                // Send back the close frame.
+                ctx.close(reason);
+                self.shutdown(ctx);
+            }
+            Err(err) => self.close(ctx, ws::CloseCode::Error, err.to_string()),
+            _ => {}
+        }
+    }
+
+    fn finished(&mut self, ctx: &mut Self::Context) {
+        // The client disconnected without sending the close frame.
+        self.shutdown(ctx);
+    }
+}
+
+impl Handler<PriceUpdate> for PriceSubscriber {
+    type Result = ();
+
+    fn handle(&mut self, msg: PriceUpdate, ctx: &mut Self::Context) {
+        // The update could have been sent before the client unsubscribed.
+        if !self.tokens.contains(&msg.0.token_id) {
+            return;
+        }
+        let json = serde_json::to_string(msg.0.as_ref()).unwrap();
+        ctx.text(json);
+    }
+}
@@ -0,0 +1,143 @@
+//! Priority operations part of API implementation.
+//!
+//! The lifecycle of the priority operation is tracked from the moment the Ethereum watcher
+//! sees it on L1 to the finalization of the block which includes it, so the deposits and
+//! the full exits can be followed end to end by either the serial id or the L1 transaction hash.
+
+// Built-in uses
+use std::time::Instant;
+
+// External uses
+use actix_web::{web, Scope};
+
+// Workspace uses
+use zksync_api_types::v02::transaction::{PriorityOpId, PriorityOpLifecycle};
+use zksync_storage::ConnectionPool;
+
+// Local uses
+use super::{error::Error, extractors::ValidPath, response::ApiResult};
+
+/// Shared data between `api/v0.2/priority_ops` endpoints.
+#[derive(Debug, Clone)]
+struct ApiPriorityOpData {
+    pool: ConnectionPool,
+}
+
+impl ApiPriorityOpData {
+    fn new(pool: ConnectionPool) -> Self {
+        Self { pool }
+    }
+
+    async fn priority_op(&self, id: PriorityOpId) -> Result<Option<PriorityOpLifecycle>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        storage
+            .chain()
+            .operations_ext_schema()
+            .get_priority_op_lifecycle(id)
+            .await
+            .map_err(Error::storage)
+    }
+}
+
+// Server implementation
+
+async fn priority_op(
+    data: web::Data<ApiPriorityOpData>,
+    ValidPath(id): ValidPath<PriorityOpId>,
+) -> ApiResult<Option<PriorityOpLifecycle>> {
+    let start = Instant::now();
+    let res = data.priority_op(id).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "priority_op");
+    res
+}
+
+pub fn api_scope(pool: ConnectionPool) -> Scope {
+    let data = ApiPriorityOpData::new(pool);
+
+    web::scope("priority_ops")
+        .app_data(web::Data::new(data))
+        .route("{id}", web::get().to(priority_op))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rest::v02::{
+        error::ErrorCode,
+        extractors::PathParam,
+        test_utils::{
+            deserialize_response_result, TestServerConfig, COMMITTED_OP_SERIAL_ID,
+            VERIFIED_OP_SERIAL_ID,
+        },
+        SharedData,
+    };
+    use zksync_api_types::v02::{transaction::PriorityOpStage, ApiVersion};
+    use zksync_storage::test_data::dummy_ethereum_tx_hash;
+    use zksync_types::{BlockNumber, H256};
+
+    #[test]
+    fn parse_priority_op_id() {
+        let hash = H256::repeat_byte(0x12);
+        assert_eq!(
+            PriorityOpId::from_param("42").unwrap(),
+            PriorityOpId::SerialId(42)
+        );
+        assert_eq!(
+            PriorityOpId::from_param(&format!("{:?}", hash)).unwrap(),
+            PriorityOpId::EthHash(hash)
+        );
+        assert_eq!(
+            PriorityOpId::from_param(&hex::encode(hash)).unwrap(),
+            PriorityOpId::EthHash(hash)
+        );
+        assert!(PriorityOpId::from_param("latest").is_err());
+    }
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn priority_ops_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+        let (client, server) =
+            cfg.start_server(move |cfg| api_scope(cfg.pool.clone()), Some(shared_data));
+
+        let response = client
+            .priority_op(&VERIFIED_OP_SERIAL_ID.to_string())
+            .await?;
+        let op: Option<PriorityOpLifecycle> = deserialize_response_result(response)?;
+        let op = op.unwrap();
+        assert_eq!(op.stage, PriorityOpStage::Finalized);
+        assert_eq!(op.op_type, "Deposit");
+        assert_eq!(op.rollup_block, Some(BlockNumber(2)));
+        assert!(op.finalized_at.is_some());
+
+        let eth_hash = dummy_ethereum_tx_hash(COMMITTED_OP_SERIAL_ID as i64);
+        let response = client.priority_op(&format!("{:?}", eth_hash)).await?;
+        let op: Option<PriorityOpLifecycle> = deserialize_response_result(response)?;
+        let op = op.unwrap();
+        assert_eq!(op.serial_id, COMMITTED_OP_SERIAL_ID);
+        assert_eq!(op.op_type, "FullExit");
+        assert_eq!(op.stage, PriorityOpStage::Committed);
+        assert!(op.committed_at.is_some());
+        assert!(op.finalized_at.is_none());
+
+        let response = client.priority_op("1000000").await?;
+        let op: Option<PriorityOpLifecycle> = deserialize_response_result(response)?;
+        assert!(op.is_none());
+
+        let response = client.priority_op("latest").await?;
+        let error = serde_json::from_value::<Error>(response.error.unwrap()).unwrap();
+        assert_eq!(error.code, ErrorCode::InvalidPriorityOpId);
+
+        server.stop().await;
+        Ok(())
+    }
+}
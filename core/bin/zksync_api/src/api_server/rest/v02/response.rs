@@ -1,18 +1,33 @@
 // Built-in uses
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::convert::From;
+use std::time::Duration;
 
 // External uses
-use actix_web::{web::Data, HttpRequest, HttpResponse, Responder};
+use actix_web::{
+    http::{
+        header::{CACHE_CONTROL, ETAG, IF_NONE_MATCH, RETRY_AFTER},
+        HeaderValue,
+    },
+    web::Data,
+    HttpRequest, HttpResponse, Responder,
+};
 use chrono::Utc;
 use qstring::QString;
-use serde::{Deserialize, Serialize};
+use serde::{ser, Deserialize, Serialize, Serializer};
+use serde_json::Value;
+use tiny_keccak::keccak256;
 
 // Workspace uses
 use zksync_api_types::v02::{Request, Response, ResultStatus};
 
 // Local uses
-use super::{error::Error, SharedData};
+use super::{
+    error::{Error, ErrorCode},
+    response_signer::ResponseSigner,
+    SharedData,
+};
+use crate::api_server::read_only_mode::ReadOnlyMode;
 
 // This struct is needed to wrap all api responses is `Response` struct by implementing `Responder` trait for it.
 // We can't use simple `Result`, because `actix-web` has already `Responder` implementation for it.
@@ -25,16 +40,26 @@ pub enum ApiResult<R: Serialize> {
 
 impl<R: Serialize> Responder for ApiResult<R> {
     fn respond_to(self, req: &HttpRequest) -> HttpResponse {
+        self.respond_signed(req, None)
+    }
+}
+
+impl<R: Serialize> ApiResult<R> {
+    fn respond_signed(self, req: &HttpRequest, signer: Option<&ResponseSigner>) -> HttpResponse {
         let data = req
             .app_data::<Data<SharedData>>()
             .expect("Wrong app data type");
         let mut args = HashMap::new();
-        for (name, value) in req.match_info().iter() {
-            args.insert(name.to_string(), value.to_string());
-        }
-        let query_string = QString::from(req.query_string());
-        for (name, value) in query_string {
-            args.insert(name, value);
+        // The signed responses don't echo the arguments, so the signed content isn't chosen
+        // by the client beyond the requested resource.
+        if signer.is_none() {
+            for (name, value) in req.match_info().iter() {
+                args.insert(name.to_string(), value.to_string());
+            }
+            let query_string = QString::from(req.query_string());
+            for (name, value) in query_string {
+                args.insert(name, value);
+            }
         }
 
         let request = Request {
@@ -45,6 +70,9 @@ impl<R: Serialize> Responder for ApiResult<R> {
             timestamp: Utc::now(),
         };
 
+        // The submissions rejected in the read-only mode can be retried later.
+        let unavailable =
+            matches!(&self, ApiResult::Error(err) if err.code == ErrorCode::ReadOnlyMode);
         let response = match self {
             ApiResult::Ok(res) => Response {
                 request,
@@ -62,9 +90,238 @@ impl<R: Serialize> Responder for ApiResult<R> {
 
         let body = serde_json::to_string(&response).expect("Should be correct serializable");
 
-        HttpResponse::Ok()
-            .content_type("application/json")
-            .body(body)
+        let mut builder = if unavailable {
+            let mut builder = HttpResponse::ServiceUnavailable();
+            if let Some(read_only_mode) = req.app_data::<Data<ReadOnlyMode>>() {
+                let retry_after = read_only_mode.retry_after().as_secs().to_string();
+                builder.insert_header((RETRY_AFTER, retry_after));
+            }
+            builder
+        } else {
+            HttpResponse::Ok()
+        };
+        if let Some(signer) = signer {
+            for header in signer.signature_headers(body.as_bytes()).iter().cloned() {
+                builder.insert_header(header);
+            }
+        }
+        builder.content_type("application/json").body(body)
+    }
+
+    /// Signs the response by the response signing key if it's configured.
+    pub fn signed(self) -> SignedApiResult<R> {
+        SignedApiResult(self)
+    }
+
+    /// Allows the clients to cache the successful response for `max_age`.
+    pub fn cached(self, max_age: Duration) -> CachedApiResult<R> {
+        CachedApiResult {
+            result: self,
+            max_age,
+        }
+    }
+
+    /// Trims the successful response to the fields requested by the `fields` query parameter.
+    pub fn with_fields(self, query: &FieldsQuery) -> ApiResult<SparseFields<R>> {
+        match self {
+            Self::Ok(value) => ApiResult::Ok(SparseFields {
+                value,
+                selection: query.fields.as_deref().and_then(FieldSelection::parse),
+            }),
+            Self::Error(err) => ApiResult::Error(err),
+        }
+    }
+}
+
+/// Query of the endpoints supporting the sparse fieldsets: `fields` is the comma-separated
+/// list of the fields of the result to return, the nested fields are separated by dots,
+/// e.g. `?fields=committed.balances,committed.nonce`. The fields of the arrays' items
+/// are selected the same way as the fields of the objects. The unknown fields are ignored.
+#[derive(Debug, Default, Deserialize)]
+pub struct FieldsQuery {
+    pub fields: Option<String>,
+}
+
+/// Tree of the selected fields, the field without the nested selection is returned as a whole.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FieldSelection(BTreeMap<String, Option<FieldSelection>>);
+
+impl FieldSelection {
+    /// Parses the list of the fields, returns `None` if no fields are selected.
+    pub fn parse(fields: &str) -> Option<Self> {
+        let mut selection = Self::default();
+        for path in fields.split(',') {
+            let path = path
+                .split('.')
+                .map(str::trim)
+                .filter(|field| !field.is_empty())
+                .collect::<Vec<_>>();
+            selection.insert(&path);
+        }
+        if selection.0.is_empty() {
+            None
+        } else {
+            Some(selection)
+        }
+    }
+
+    fn insert(&mut self, path: &[&str]) {
+        let (field, nested) = match path.split_first() {
+            Some(split) => split,
+            None => return,
+        };
+        if nested.is_empty() {
+            self.0.insert(field.to_string(), None);
+            return;
+        }
+        // The field already selected as a whole stays selected as a whole.
+        if let Some(selection) = self
+            .0
+            .entry(field.to_string())
+            .or_insert_with(|| Some(Self::default()))
+        {
+            selection.insert(nested);
+        }
+    }
+
+    pub fn apply(&self, value: Value) -> Value {
+        match value {
+            Value::Object(fields) => Value::Object(
+                fields
+                    .into_iter()
+                    .filter_map(|(field, value)| {
+                        let value = match self.0.get(&field)? {
+                            Some(selection) => selection.apply(value),
+                            None => value,
+                        };
+                        Some((field, value))
+                    })
+                    .collect(),
+            ),
+            Value::Array(items) => {
+                Value::Array(items.into_iter().map(|item| self.apply(item)).collect())
+            }
+            value => value,
+        }
+    }
+}
+
+/// Serializes the value trimmed to the selected fields, or the whole value if there is no selection.
+#[derive(Debug)]
+pub struct SparseFields<R> {
+    value: R,
+    selection: Option<FieldSelection>,
+}
+
+impl<R: Serialize> Serialize for SparseFields<R> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match &self.selection {
+            Some(selection) => {
+                let value = serde_json::to_value(&self.value).map_err(ser::Error::custom)?;
+                selection.apply(value).serialize(serializer)
+            }
+            None => self.value.serialize(serializer),
+        }
+    }
+}
+
+/// Api response with the `ETag` and `Cache-Control` headers, the requests with the
+/// matching `If-None-Match` header are answered with `304 Not Modified`.
+///
+/// The response includes the request metadata with the timestamp, so the entity tag
+/// is the hash of the result rather than of the whole response. The errors are not cached.
+#[derive(Debug)]
+pub struct CachedApiResult<R: Serialize> {
+    result: ApiResult<R>,
+    max_age: Duration,
+}
+
+fn entity_tag(result: &impl Serialize) -> String {
+    let result = serde_json::to_vec(result).expect("Should be correct serializable");
+    format!("\"{}\"", hex::encode(&keccak256(&result)[..16]))
+}
+
+fn is_not_modified(req: &HttpRequest, etag: &str) -> bool {
+    let if_none_match = match req.headers().get(IF_NONE_MATCH) {
+        Some(header) => header.to_str().unwrap_or_default(),
+        None => return false,
+    };
+    if_none_match.split(',').any(|tag| {
+        let tag = tag.trim();
+        // The weak comparison is used, same as the `If-None-Match` semantics require.
+        tag == "*" || tag.trim_start_matches("W/") == etag
+    })
+}
+
+impl<R: Serialize> Responder for CachedApiResult<R> {
+    fn respond_to(self, req: &HttpRequest) -> HttpResponse {
+        let etag = match &self.result {
+            ApiResult::Ok(res) => entity_tag(res),
+            ApiResult::Error(_) => return self.result.respond_to(req),
+        };
+
+        let mut response = if is_not_modified(req, &etag) {
+            HttpResponse::NotModified().finish()
+        } else {
+            self.result.respond_to(req)
+        };
+        let headers = response.headers_mut();
+        headers.insert(ETAG, HeaderValue::from_str(&etag).unwrap());
+        headers.insert(
+            CACHE_CONTROL,
+            HeaderValue::from_str(&format!("public, max-age={}", self.max_age.as_secs())).unwrap(),
+        );
+        response
+    }
+}
+
+impl<R: Serialize> From<Error> for CachedApiResult<R> {
+    fn from(err: Error) -> Self {
+        ApiResult::Error(err).cached(Duration::default())
+    }
+}
+
+/// Api response signed by the response signing key, the signature of the body and the address of
+/// the key are returned in the `X-Signature` and `X-Signature-Key-Id` headers. The arguments of the
+/// request are not echoed in the signed body. The response isn't signed if no key is configured.
+#[derive(Debug)]
+pub struct SignedApiResult<R: Serialize>(ApiResult<R>);
+
+impl<R: Serialize> Responder for SignedApiResult<R> {
+    fn respond_to(self, req: &HttpRequest) -> HttpResponse {
+        let signer = req.app_data::<Data<ResponseSigner>>();
+        self.0
+            .respond_signed(req, signer.map(|signer| signer.get_ref()))
+    }
+}
+
+impl<R: Serialize> From<Error> for SignedApiResult<R> {
+    fn from(err: Error) -> Self {
+        ApiResult::Error(err).signed()
+    }
+}
+
+/// Api response of the endpoint whose data can also be exported in the format requested
+/// by the `Accept` header, e.g. CSV. The exported data is streamed as is, without wrapping
+/// it into the `Response` struct.
+#[derive(Debug)]
+pub enum ExportableApiResult<R: Serialize> {
+    Json(ApiResult<R>),
+    Export(HttpResponse),
+}
+
+impl<R: Serialize> Responder for ExportableApiResult<R> {
+    fn respond_to(self, req: &HttpRequest) -> HttpResponse {
+        match self {
+            Self::Json(result) => result.respond_to(req),
+            Self::Export(response) => response,
+        }
+    }
+}
+
+impl<R: Serialize> From<Error> for ExportableApiResult<R> {
+    fn from(err: Error) -> Self {
+        Self::Json(ApiResult::Error(err))
     }
 }
 
@@ -89,8 +346,141 @@ macro_rules! api_try {
         match $e {
             Ok(res) => res,
             Err(err) => {
-                return ApiResult::from(err);
+                return From::from(err);
             }
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rest::v02::response_signer::{
+        SIGNATURE_HEADER, SIGNATURE_KEY_ID_HEADER,
+    };
+    use actix_web::{body::AnyBody, http::StatusCode, test::TestRequest};
+    use serde_json::json;
+    use zksync_api_types::v02::ApiVersion;
+    use zksync_types::{network::Network, tx::PackedEthSignature, H256};
+
+    fn request(if_none_match: Option<&str>) -> HttpRequest {
+        let request = TestRequest::default().app_data(Data::new(SharedData {
+            net: Network::Localhost,
+            api_version: ApiVersion::V02,
+        }));
+        match if_none_match {
+            Some(etag) => request.insert_header((IF_NONE_MATCH, etag)),
+            None => request,
+        }
+        .to_http_request()
+    }
+
+    #[test]
+    fn cached_responses() {
+        let max_age = Duration::from_secs(10);
+        let response = ApiResult::Ok(42).cached(max_age).respond_to(&request(None));
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CACHE_CONTROL], "public, max-age=10");
+        let etag = response.headers()[ETAG].to_str().unwrap().to_owned();
+
+        // The entity tag doesn't depend on the time of the request.
+        let response = ApiResult::Ok(42)
+            .cached(max_age)
+            .respond_to(&request(Some(&etag)));
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        let response = ApiResult::Ok(42)
+            .cached(max_age)
+            .respond_to(&request(Some(&format!("\"other\", W/{}", etag))));
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+        let response = ApiResult::Ok(43)
+            .cached(max_age)
+            .respond_to(&request(Some(&etag)));
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_ne!(response.headers()[ETAG], etag.as_str());
+
+        // The errors are not cached.
+        let response =
+            CachedApiResult::<u32>::from(Error::storage("error")).respond_to(&request(Some("*")));
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers().get(ETAG).is_none());
+    }
+
+    #[test]
+    fn signed_responses() {
+        let response = ApiResult::Ok(42).signed().respond_to(&request(None));
+        assert!(response.headers().get(SIGNATURE_HEADER).is_none());
+
+        let signer = ResponseSigner::new(H256::repeat_byte(0x11));
+        let request = TestRequest::with_uri("/accounts/1/committed?fields=balances")
+            .app_data(Data::new(SharedData {
+                net: Network::Localhost,
+                api_version: ApiVersion::V02,
+            }))
+            .app_data(Data::new(signer.clone()))
+            .to_http_request();
+        // The errors are signed as well, e.g. the absence of the account is the data to verify.
+        for response in vec![
+            ApiResult::Ok(42).signed().respond_to(&request),
+            SignedApiResult::<u32>::from(Error::storage("error")).respond_to(&request),
+        ] {
+            let headers = response.headers();
+            assert_eq!(
+                headers[SIGNATURE_KEY_ID_HEADER],
+                format!("{:?}", signer.key_id()).as_str()
+            );
+            let signature = headers[SIGNATURE_HEADER].to_str().unwrap();
+            let signature =
+                PackedEthSignature::deserialize_packed(&hex::decode(&signature[2..]).unwrap())
+                    .unwrap();
+            let body = match response.body() {
+                AnyBody::Bytes(body) => body.clone(),
+                _ => panic!("Unexpected response body"),
+            };
+            assert_eq!(
+                signature.signature_recover_signer(&body).unwrap(),
+                signer.key_id()
+            );
+            // The client-chosen arguments are not echoed in the signed body.
+            let body: Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(body["request"]["args"], json!({}));
+        }
+    }
+
+    #[test]
+    fn sparse_fields() {
+        let value = json!({
+            "accountId": 1,
+            "committed": { "nonce": 2, "balances": { "ETH": "10" }, "pubKeyHash": "sync:00" },
+            "list": [{ "id": 0, "symbol": "ETH" }, { "id": 1, "symbol": "DAI" }],
+        });
+        let sparse = |fields: &str| {
+            let query = FieldsQuery {
+                fields: Some(fields.to_owned()),
+            };
+            let result = ApiResult::Ok(value.clone()).with_fields(&query);
+            match result {
+                ApiResult::Ok(sparse) => serde_json::to_value(&sparse).unwrap(),
+                ApiResult::Error(_) => unreachable!(),
+            }
+        };
+
+        assert_eq!(
+            sparse("accountId, committed.nonce"),
+            json!({ "accountId": 1, "committed": { "nonce": 2 } })
+        );
+        // The fields of the array items are selected for each item.
+        assert_eq!(
+            sparse("list.symbol,unknown"),
+            json!({ "list": [{ "symbol": "ETH" }, { "symbol": "DAI" }] })
+        );
+        // The field selected as a whole isn't trimmed by its nested selection.
+        assert_eq!(
+            sparse("committed.nonce,committed"),
+            json!({ "committed": value["committed"].clone() })
+        );
+        // There is no selection if no fields are requested.
+        assert_eq!(sparse(" , "), value);
+        assert_eq!(FieldSelection::parse(""), None);
+    }
+}
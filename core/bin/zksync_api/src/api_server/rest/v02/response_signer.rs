@@ -0,0 +1,75 @@
+//! Signing of the responses by the dedicated response signing key.
+//!
+//! The light clients and the auditors querying the API through the untrusted proxies can check
+//! that the account balances and the exit proofs were returned by the server: the response body
+//! is signed as an Ethereum message, so the signer is recovered with `ecrecover` and compared
+//! with the key id, which is the address of the response signing key.
+//!
+//! The key is kept apart from the operator keys, so the server never signs the client-chosen
+//! content with a key controlling the funds on L1.
+
+// External uses
+use actix_web::http::{HeaderName, HeaderValue};
+
+// Workspace uses
+use zksync_config::ZkSyncConfig;
+use zksync_types::{tx::PackedEthSignature, Address, H256};
+
+/// Header with the signature of the response body.
+pub const SIGNATURE_HEADER: &str = "x-signature";
+/// Header with the address of the key the response is signed by.
+pub const SIGNATURE_KEY_ID_HEADER: &str = "x-signature-key-id";
+
+#[derive(Debug, Clone)]
+pub struct ResponseSigner {
+    private_key: H256,
+    key_id: Address,
+}
+
+impl ResponseSigner {
+    pub fn new(private_key: H256) -> Self {
+        let key_id = PackedEthSignature::address_from_private_key(&private_key)
+            .expect("Invalid key for signing the responses");
+        Self {
+            private_key,
+            key_id,
+        }
+    }
+
+    /// Returns the signer if the response signing key is configured.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the response signing key is one of the operator keys.
+    pub fn from_config(config: &ZkSyncConfig) -> Option<Self> {
+        let private_key = config.api.rest.response_signing_key?;
+        let sender = &config.eth_sender.sender;
+        assert!(
+            private_key != sender.operator_private_key
+                && !sender.reserve_operator_private_keys.contains(&private_key),
+            "`API_REST_RESPONSE_SIGNING_KEY` must not be an operator key"
+        );
+        Some(Self::new(private_key))
+    }
+
+    pub fn key_id(&self) -> Address {
+        self.key_id
+    }
+
+    /// Returns the headers with the signature of the response body and the key id.
+    pub fn signature_headers(&self, body: &[u8]) -> [(HeaderName, HeaderValue); 2] {
+        let signature = PackedEthSignature::sign(&self.private_key, body)
+            .expect("Signing the response must not fail");
+        let signature = format!("0x{}", hex::encode(signature.serialize_packed()));
+        [
+            (
+                HeaderName::from_static(SIGNATURE_HEADER),
+                HeaderValue::from_str(&signature).unwrap(),
+            ),
+            (
+                HeaderName::from_static(SIGNATURE_KEY_ID_HEADER),
+                HeaderValue::from_str(&format!("{:?}", self.key_id)).unwrap(),
+            ),
+        ]
+    }
+}
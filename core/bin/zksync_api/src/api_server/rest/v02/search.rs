@@ -0,0 +1,296 @@
+//! Explorer search part of API implementation.
+//!
+//! The search query is classified by its format first and then resolved via
+//! the storage lookups, the first found entity is returned.
+
+// Built-in uses
+use std::str::FromStr;
+use std::time::Instant;
+
+// External uses
+use actix_web::{web, Scope};
+
+// Workspace uses
+use zksync_api_types::v02::search::SearchResult;
+use zksync_storage::{ConnectionPool, StorageProcessor};
+use zksync_token_db_cache::TokenDBCache;
+use zksync_types::{tx::TxHash, Address, BlockNumber, TokenLike};
+
+// Local uses
+use super::{error::Error, response::ApiResult};
+
+/// The entities the search query may refer to, in the order of the lookups.
+#[derive(Debug, Clone, PartialEq)]
+enum SearchCandidate {
+    TxHash(TxHash),
+    /// Either an account or a token address.
+    Address(Address),
+    BlockNumber(BlockNumber),
+    TokenSymbol(String),
+}
+
+impl SearchCandidate {
+    fn parse(query: &str) -> Self {
+        let query = query.trim();
+        if let Ok(tx_hash) = TxHash::from_str(query) {
+            return Self::TxHash(tx_hash);
+        }
+        let maybe_address = query.strip_prefix("0x").unwrap_or(query);
+        // Don't interpret decimal block numbers as addresses, they have a different length anyway.
+        if maybe_address.len() == 40 {
+            if let Ok(address) = Address::from_str(maybe_address) {
+                return Self::Address(address);
+            }
+        }
+        if let Ok(block_number) = u32::from_str(query) {
+            return Self::BlockNumber(BlockNumber(block_number));
+        }
+        Self::TokenSymbol(query.to_string())
+    }
+}
+
+/// Shared data between `api/v0.2/search` endpoints.
+#[derive(Debug, Clone)]
+struct ApiSearchData {
+    pool: ConnectionPool,
+    tokens: TokenDBCache,
+}
+
+impl ApiSearchData {
+    fn new(pool: ConnectionPool, tokens: TokenDBCache) -> Self {
+        Self { pool, tokens }
+    }
+
+    async fn search(&self, query: &str) -> Result<Option<SearchResult>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        match SearchCandidate::parse(query) {
+            SearchCandidate::TxHash(tx_hash) => Self::find_tx(&mut storage, tx_hash).await,
+            SearchCandidate::Address(address) => {
+                let account_id = storage
+                    .chain()
+                    .account_schema()
+                    .account_id_by_address(address)
+                    .await
+                    .map_err(Error::storage)?;
+                if let Some(account_id) = account_id {
+                    return Ok(Some(SearchResult::Account {
+                        address,
+                        account_id,
+                    }));
+                }
+                self.find_token(&mut storage, TokenLike::Address(address))
+                    .await
+            }
+            SearchCandidate::BlockNumber(block_number) => {
+                let last_committed = storage
+                    .chain()
+                    .block_schema()
+                    .get_last_committed_block()
+                    .await
+                    .map_err(Error::storage)?;
+                if *block_number > 0 && block_number <= last_committed {
+                    Ok(Some(SearchResult::Block { block_number }))
+                } else {
+                    Ok(None)
+                }
+            }
+            SearchCandidate::TokenSymbol(symbol) => {
+                self.find_token(&mut storage, TokenLike::Symbol(symbol))
+                    .await
+            }
+        }
+    }
+
+    async fn find_tx(
+        storage: &mut StorageProcessor<'_>,
+        tx_hash: TxHash,
+    ) -> Result<Option<SearchResult>, Error> {
+        let is_executed = storage
+            .chain()
+            .operations_ext_schema()
+            .tx_receipt_api_v02(tx_hash.as_ref())
+            .await
+            .map_err(Error::storage)?
+            .is_some();
+        let found = is_executed
+            || storage
+                .chain()
+                .mempool_schema()
+                .get_pending_operation_by_hash(tx_hash.into())
+                .await
+                .map_err(Error::core_api)?
+                .is_some();
+        Ok(if found {
+            Some(SearchResult::Transaction { tx_hash })
+        } else {
+            None
+        })
+    }
+
+    async fn find_token(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        token_like: TokenLike,
+    ) -> Result<Option<SearchResult>, Error> {
+        let token = self
+            .tokens
+            .get_token(storage, token_like)
+            .await
+            .map_err(Error::storage)?;
+        Ok(token.map(|token| SearchResult::Token { token }))
+    }
+}
+
+// Server implementation
+
+async fn search(
+    data: web::Data<ApiSearchData>,
+    query: web::Path<String>,
+) -> ApiResult<Option<SearchResult>> {
+    let start = Instant::now();
+    let res = data.search(&query).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "search");
+    res
+}
+
+pub fn api_scope(pool: ConnectionPool, tokens: TokenDBCache) -> Scope {
+    let data = ApiSearchData::new(pool, tokens);
+
+    web::scope("search")
+        .app_data(web::Data::new(data))
+        .route("{query}", web::get().to(search))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rest::v02::{
+        test_utils::{deserialize_response_result, TestServerConfig},
+        SharedData,
+    };
+    use zksync_api_types::v02::ApiVersion;
+    use zksync_types::{AccountId, TokenId};
+
+    #[test]
+    fn search_candidates() {
+        let tx_hash = TxHash::from_str(&format!("0x{}", "ab".repeat(32))).unwrap();
+        assert_eq!(
+            SearchCandidate::parse(&format!("0x{}", "ab".repeat(32))),
+            SearchCandidate::TxHash(tx_hash)
+        );
+        assert_eq!(
+            SearchCandidate::parse(&format!("sync-tx:{}", "ab".repeat(32))),
+            SearchCandidate::TxHash(tx_hash)
+        );
+
+        let address = Address::repeat_byte(0x12);
+        assert_eq!(
+            SearchCandidate::parse(&format!("{:?}", address)),
+            SearchCandidate::Address(address)
+        );
+        assert_eq!(
+            SearchCandidate::parse(&"12".repeat(20)),
+            SearchCandidate::Address(address)
+        );
+
+        assert_eq!(
+            SearchCandidate::parse(" 42 "),
+            SearchCandidate::BlockNumber(BlockNumber(42))
+        );
+        assert_eq!(
+            SearchCandidate::parse("ETH"),
+            SearchCandidate::TokenSymbol("ETH".to_string())
+        );
+        // The hash with the wrong length is not a hash.
+        assert_eq!(
+            SearchCandidate::parse("0xabcd"),
+            SearchCandidate::TokenSymbol("0xabcd".to_string())
+        );
+    }
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn search_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+        let (client, server) = cfg.start_server(
+            move |cfg: &TestServerConfig| {
+                api_scope(
+                    cfg.pool.clone(),
+                    TokenDBCache::new(cfg.config.api.token_config.invalidate_token_cache_period()),
+                )
+            },
+            Some(shared_data),
+        );
+
+        let (tx_hash, address, account_id) = {
+            let mut storage = cfg.pool.access_storage().await?;
+            let transactions = storage
+                .chain()
+                .block_schema()
+                .get_block_transactions(BlockNumber(1))
+                .await?;
+            let tx = &transactions[1];
+            let op = tx.op.as_object().unwrap();
+            let account_id: AccountId = if op.contains_key("accountId") {
+                serde_json::from_value(op["accountId"].clone()).unwrap()
+            } else {
+                serde_json::from_value(op["creatorId"].clone()).unwrap()
+            };
+            let address = storage
+                .chain()
+                .account_schema()
+                .account_address_by_id(account_id)
+                .await?
+                .unwrap();
+            (TxHash::from_str(&tx.tx_hash).unwrap(), address, account_id)
+        };
+
+        let result: Option<SearchResult> =
+            deserialize_response_result(client.search(&tx_hash.to_string()).await?)?;
+        assert_eq!(result, Some(SearchResult::Transaction { tx_hash }));
+
+        let result: Option<SearchResult> =
+            deserialize_response_result(client.search(&format!("{:?}", address)).await?)?;
+        assert_eq!(
+            result,
+            Some(SearchResult::Account {
+                address,
+                account_id
+            })
+        );
+
+        let result: Option<SearchResult> = deserialize_response_result(client.search("1").await?)?;
+        assert_eq!(
+            result,
+            Some(SearchResult::Block {
+                block_number: BlockNumber(1)
+            })
+        );
+        let result: Option<SearchResult> =
+            deserialize_response_result(client.search(&u32::MAX.to_string()).await?)?;
+        assert!(result.is_none());
+
+        let result: Option<SearchResult> =
+            deserialize_response_result(client.search("phnx").await?)?;
+        match result {
+            Some(SearchResult::Token { token }) => assert_eq!(token.id, TokenId(1)),
+            other => panic!("Unexpected search result: {:?}", other),
+        }
+
+        let result: Option<SearchResult> =
+            deserialize_response_result(client.search("UNKNOWN").await?)?;
+        assert!(result.is_none());
+
+        server.stop().await;
+        Ok(())
+    }
+}
@@ -7,21 +7,36 @@ use std::time::Instant;
 use actix_web::{web, Scope};
 
 // Workspace uses
-use zksync_api_types::v02::status::NetworkStatus;
+use zksync_api_types::v02::status::{NetworkStatus, PriceProviderHealth};
+use zksync_storage::ConnectionPool;
 
 // Local uses
-use super::response::ApiResult;
-use crate::api_server::rest::network_status::SharedNetworkStatus;
+use super::{error::Error, response::ApiResult};
+use crate::{api_server::rest::network_status::SharedNetworkStatus, api_try};
 
 /// Shared data between `api/v0.2/networkStatus` endpoints.
 #[derive(Debug, Clone)]
 pub struct ApiStatusData {
     status: SharedNetworkStatus,
+    pool: ConnectionPool,
 }
 
 impl ApiStatusData {
-    pub fn new(status: SharedNetworkStatus) -> Self {
-        Self { status }
+    pub fn new(status: SharedNetworkStatus, pool: ConnectionPool) -> Self {
+        Self { status, pool }
+    }
+
+    async fn price_providers_health(&self) -> Result<Vec<PriceProviderHealth>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let providers = storage
+            .tokens_schema()
+            .load_price_providers_health()
+            .await
+            .map_err(Error::storage)?;
+        Ok(providers
+            .into_iter()
+            .map(PriceProviderHealth::from)
+            .collect())
     }
 }
 
@@ -42,12 +57,23 @@ async fn get_status(data: web::Data<ApiStatusData>) -> ApiResult<NetworkStatus>
     Ok(network_status).into()
 }
 
-pub fn api_scope(shared_status: SharedNetworkStatus) -> Scope {
-    let data = ApiStatusData::new(shared_status);
+/// Health of the price providers, as it was last reported by the fee ticker.
+async fn get_price_providers_health(
+    data: web::Data<ApiStatusData>,
+) -> ApiResult<Vec<PriceProviderHealth>> {
+    let start = Instant::now();
+    let providers = api_try!(data.price_providers_health().await);
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "get_price_providers_health");
+    Ok(providers).into()
+}
+
+pub fn api_scope(shared_status: SharedNetworkStatus, pool: ConnectionPool) -> Scope {
+    let data = ApiStatusData::new(shared_status, pool);
 
     web::scope("networkStatus")
         .app_data(web::Data::new(data))
         .route("", web::get().to(get_status))
+        .route("priceProviders", web::get().to(get_price_providers_health))
 }
 
 #[cfg(test)]
@@ -57,7 +83,9 @@ mod tests {
         test_utils::{deserialize_response_result, TestServerConfig},
         SharedData,
     };
+    use chrono::Utc;
     use zksync_api_types::v02::ApiVersion;
+    use zksync_storage::tokens::records::StoredPriceProviderHealth;
     use zksync_types::SequentialTxId;
 
     #[actix_rt::test]
@@ -77,7 +105,7 @@ mod tests {
         let (client, server) = cfg.start_server(
             {
                 let status = status.clone();
-                move |_| api_scope(status.clone())
+                move |cfg: &TestServerConfig| api_scope(status.clone(), cfg.pool.clone())
             },
             Some(shared_data),
         );
@@ -115,6 +143,26 @@ mod tests {
 
         assert_eq!(expected_status, status);
 
+        let stored_health = StoredPriceProviderHealth {
+            provider: "CoinGecko".to_string(),
+            priority: 0,
+            last_success_at: None,
+            last_failure_at: Some(Utc::now()),
+            last_error: Some("Api error: API request failed".to_string()),
+            consecutive_failures: 2,
+            updated_at: Utc::now(),
+        };
+        cfg.pool
+            .access_storage()
+            .await?
+            .tokens_schema()
+            .store_price_providers_health(&[stored_health.clone()])
+            .await?;
+        let response = client.price_providers_health().await?;
+        let providers: Vec<PriceProviderHealth> = deserialize_response_result(response)?;
+        assert_eq!(providers, vec![PriceProviderHealth::from(stored_health)]);
+        assert!(!providers[0].healthy);
+
         server.stop().await;
         Ok(())
     }
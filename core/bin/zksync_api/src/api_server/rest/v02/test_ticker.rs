@@ -0,0 +1,277 @@
+//! Scriptable fee ticker and token fixtures for the API tests.
+//!
+//! Unlike `dummy_fee_ticker`, the prices of `ScriptedTickerInfo` can be failing or slow,
+//! and can be changed while the test is running. Note that the fee ticker caches the
+//! received prices, so only the prices which weren't received yet can be changed.
+
+// Built-in uses
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+// External uses
+use anyhow::Error;
+use bigdecimal::BigDecimal;
+use chrono::Utc;
+use num::{rational::Ratio, BigUint};
+
+// Workspace uses
+use zksync_storage::tokens::memory::MemoryTokensStorage;
+use zksync_types::{
+    tokens::TokenMarketVolume, Address, Token, TokenId, TokenKind, TokenLike, TokenPrice,
+};
+use zksync_utils::big_decimal_to_ratio;
+
+// Local uses
+use super::test_utils::get_test_ticker_config;
+use crate::fee_ticker::{
+    ticker_info::{BlocksInFutureAggregatedOperations, FeeTickerInfo},
+    validator::{cache::TokenInMemoryCache, FeeTokenValidator},
+    FeeTicker, PriceError,
+};
+
+/// Token with the fixed price.
+pub const PRICED_TOKEN: TokenId = TokenId(1);
+/// Token with the zero price.
+pub const ZERO_PRICE_TOKEN: TokenId = TokenId(2);
+/// Token which price provider returns an error.
+pub const FAILING_TOKEN: TokenId = TokenId(3);
+/// Token which price provider doesn't respond in time.
+pub const TIMEOUT_TOKEN: TokenId = TokenId(4);
+
+/// Time after which the price provider is considered timed out.
+pub const PROVIDER_TIMEOUT: Duration = Duration::from_millis(50);
+
+/// Response of the price provider for the token.
+#[derive(Debug, Clone)]
+pub enum ScriptedPrice {
+    /// The price of the token in USD.
+    Price(BigDecimal),
+    /// The provider fails with the error.
+    Error(PriceError),
+    /// The provider fails after `PROVIDER_TIMEOUT`.
+    Timeout,
+}
+
+impl From<u64> for ScriptedPrice {
+    fn from(price: u64) -> Self {
+        Self::Price(BigDecimal::from(price))
+    }
+}
+
+/// Token along with its price and market volume.
+#[derive(Debug, Clone)]
+pub struct TokenFixture {
+    pub token: Token,
+    pub price: ScriptedPrice,
+    /// Tokens without the market volume aren't allowed for paying fees.
+    pub market_volume: Option<u32>,
+}
+
+impl TokenFixture {
+    pub fn new(id: TokenId, symbol: &str, price: impl Into<ScriptedPrice>) -> Self {
+        Self {
+            token: Token::new(
+                id,
+                Address::from_low_u64_be(u64::from(*id)),
+                symbol,
+                18,
+                TokenKind::ERC20,
+            ),
+            price: price.into(),
+            market_volume: Some(400),
+        }
+    }
+}
+
+/// ETH and the tokens for each of the pricing edge cases, all of them are allowed for fees.
+pub fn token_fixtures() -> Vec<TokenFixture> {
+    let mut eth = TokenFixture::new(TokenId(0), "ETH", 10);
+    eth.token.address = Address::zero();
+    vec![
+        eth,
+        TokenFixture::new(PRICED_TOKEN, "PRICED", 10),
+        TokenFixture::new(ZERO_PRICE_TOKEN, "ZERO", 0),
+        TokenFixture::new(
+            FAILING_TOKEN,
+            "FAILING",
+            ScriptedPrice::Error(PriceError::api_error("Price provider is unavailable")),
+        ),
+        TokenFixture::new(TIMEOUT_TOKEN, "TIMEOUT", ScriptedPrice::Timeout),
+    ]
+}
+
+/// Fee ticker info responding with the scripted prices of the token fixtures.
+#[derive(Debug, Clone)]
+pub struct ScriptedTickerInfo {
+    tokens: Arc<Vec<Token>>,
+    prices: Arc<RwLock<HashMap<TokenId, ScriptedPrice>>>,
+    latency: Duration,
+}
+
+impl ScriptedTickerInfo {
+    pub fn new(fixtures: &[TokenFixture]) -> Self {
+        Self {
+            tokens: Arc::new(
+                fixtures
+                    .iter()
+                    .map(|fixture| fixture.token.clone())
+                    .collect(),
+            ),
+            prices: Arc::new(RwLock::new(
+                fixtures
+                    .iter()
+                    .map(|fixture| (fixture.token.id, fixture.price.clone()))
+                    .collect(),
+            )),
+            latency: Duration::default(),
+        }
+    }
+
+    /// Delays every response of the price provider.
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    /// Changes the response of the price provider for the token,
+    /// the change is shared between the clones.
+    pub fn set_price(&self, token_id: TokenId, price: impl Into<ScriptedPrice>) {
+        self.prices.write().unwrap().insert(token_id, price.into());
+    }
+
+    /// Makes the fee ticker, the market volumes of the fixtures are used by its validator.
+    pub fn fee_ticker(&self, fixtures: &[TokenFixture]) -> FeeTicker {
+        let mut tokens = Vec::new();
+        let mut market = HashMap::new();
+        for fixture in fixtures {
+            let token = &fixture.token;
+            tokens.push(token.clone());
+            if let Some(market_volume) = fixture.market_volume {
+                let market_volume = TokenMarketVolume {
+                    market_volume: Ratio::from_integer(BigUint::from(market_volume)),
+                    last_updated: Utc::now(),
+                };
+                market.insert(token.id, market_volume);
+            }
+        }
+        let cache = TokenInMemoryCache::from(
+            MemoryTokensStorage::new()
+                .with_tokens(tokens)
+                .with_market_volumes(market),
+        );
+        let validator = FeeTokenValidator::new(
+            cache,
+            chrono::Duration::seconds(100),
+            BigDecimal::from(100),
+            Default::default(),
+        );
+        FeeTicker::new(Box::new(self.clone()), get_test_ticker_config(), validator)
+    }
+
+    fn find_token(&self, token: &TokenLike) -> Option<&Token> {
+        self.tokens.iter().find(|fixture| match token {
+            TokenLike::Id(id) => fixture.id == *id,
+            TokenLike::Address(address) => fixture.address == *address,
+            TokenLike::Symbol(symbol) => fixture.symbol.eq_ignore_ascii_case(symbol),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl FeeTickerInfo for ScriptedTickerInfo {
+    async fn is_account_new(&self, _address: Address) -> anyhow::Result<bool> {
+        Ok(false)
+    }
+
+    async fn blocks_in_future_aggregated_operations(
+        &self,
+    ) -> anyhow::Result<BlocksInFutureAggregatedOperations> {
+        Ok(BlocksInFutureAggregatedOperations {
+            blocks_to_commit: 1,
+            blocks_to_prove: 1,
+            blocks_to_execute: 1,
+        })
+    }
+
+    async fn remaining_chunks_in_pending_block(&self) -> anyhow::Result<Option<usize>> {
+        Ok(None)
+    }
+
+    async fn get_last_token_price(&self, token: TokenLike) -> Result<TokenPrice, PriceError> {
+        tokio::time::sleep(self.latency).await;
+        let token_id = self
+            .find_token(&token)
+            .map(|token| token.id)
+            .ok_or_else(|| PriceError::token_not_found(format!("{:?}", token)))?;
+        let price = self.prices.read().unwrap().get(&token_id).cloned();
+        match price {
+            Some(ScriptedPrice::Price(price)) => Ok(TokenPrice {
+                usd_price: big_decimal_to_ratio(&price).unwrap(),
+                last_updated: Utc::now(),
+            }),
+            Some(ScriptedPrice::Error(err)) => Err(err),
+            Some(ScriptedPrice::Timeout) => {
+                tokio::time::sleep(PROVIDER_TIMEOUT).await;
+                Err(PriceError::api_error("Price provider timed out"))
+            }
+            None => Err(PriceError::token_not_found(format!("{:?}", token))),
+        }
+    }
+
+    async fn get_gas_price_wei(&self) -> Result<BigUint, Error> {
+        Ok(BigUint::from(1u64))
+    }
+
+    async fn get_token(&self, token: TokenLike) -> Result<Token, Error> {
+        self.find_token(&token)
+            .cloned()
+            .ok_or_else(|| anyhow::format_err!("Token not found: {:?}", token))
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fee_ticker::TokenPriceRequestType;
+    use std::time::Instant;
+
+    #[tokio::test]
+    async fn scripted_prices() {
+        let fixtures = token_fixtures();
+        let info = ScriptedTickerInfo::new(&fixtures);
+        let ticker = &info.fee_ticker(&fixtures);
+        let price = move |token_id: TokenId| {
+            ticker.get_token_price(
+                TokenLike::Id(token_id),
+                TokenPriceRequestType::USDForOneToken,
+            )
+        };
+
+        assert_eq!(price(PRICED_TOKEN).await.unwrap(), BigDecimal::from(10));
+        assert_eq!(price(ZERO_PRICE_TOKEN).await.unwrap(), BigDecimal::from(0));
+        assert!(matches!(
+            price(FAILING_TOKEN).await,
+            Err(PriceError::ApiError(_))
+        ));
+        let start = Instant::now();
+        assert!(matches!(
+            price(TIMEOUT_TOKEN).await,
+            Err(PriceError::ApiError(_))
+        ));
+        assert!(start.elapsed() >= PROVIDER_TIMEOUT);
+        assert!(matches!(
+            price(TokenId(100)).await,
+            Err(PriceError::TokenNotFound(_))
+        ));
+
+        // The errors aren't cached, so the provider can recover.
+        info.set_price(FAILING_TOKEN, 5);
+        assert_eq!(price(FAILING_TOKEN).await.unwrap(), BigDecimal::from(5));
+    }
+}
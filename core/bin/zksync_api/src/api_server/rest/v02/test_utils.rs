@@ -3,6 +3,8 @@
 // Built-in uses
 use std::collections::HashMap;
 use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
 
 // External uses
 use actix_web::{web, App, Scope};
@@ -16,7 +18,7 @@ use serde::de::DeserializeOwned;
 use tokio::sync::Mutex;
 
 // Workspace uses
-use zksync_api_client::rest::client::Client;
+use zksync_api_client::rest::{client::Client, v02::deserialize_result};
 use zksync_api_types::v02::Response;
 use zksync_config::ZkSyncConfig;
 use zksync_crypto::rand::{Rng, SeedableRng, XorShiftRng};
@@ -46,12 +48,15 @@ use zksync_types::{
 use zksync_utils::{big_decimal_to_ratio, scaled_u64_to_ratio, UnsignedRatioSerializeAsDecimal};
 
 // Local uses
+use super::pagination_cursor::PaginationCursors;
 use crate::fee_ticker::{
+    gas_price_scaler::GasPriceScaleConfig,
     tests::TestToken,
     ticker_info::BlocksInFutureAggregatedOperations,
     validator::{cache::TokenInMemoryCache, FeeTokenValidator},
     {FeeTicker, FeeTickerInfo, GasOperationsCost, PriceError, TickerConfig},
 };
+use crate::fiat_rates::{FiatCurrency, FiatRates, FiatRatesAPI};
 use crate::signature_checker::{VerifiedTx, VerifySignatureRequest};
 use std::any::Any;
 
@@ -101,7 +106,8 @@ impl TestServerConfig {
         let this = self.clone();
 
         let server = actix_test::start(move || {
-            let app = App::new();
+            let cursors = PaginationCursors::from_config(&this.config.api.rest);
+            let app = App::new().app_data(web::Data::new(cursors));
             let shared_data = shared_data.clone();
             let app = if let Some(shared_data) = shared_data {
                 app.app_data(web::Data::new(shared_data))
@@ -499,6 +505,7 @@ impl TestServerConfig {
                     Some((id, op)),
                     100,
                     100u32.into(),
+                    None,
                     Default::default(),
                 )
                 .await?;
@@ -536,12 +543,12 @@ impl TestServerConfig {
 
                 // Get job id.
                 let stored_job_id = ProverSchema(&mut storage)
-                    .get_idle_prover_job_from_job_queue()
+                    .lease_prover_job("test_prover", Duration::from_secs(60))
                     .await?
                     .unwrap()
                     .job_id;
                 let stored_aggregated_job_id = ProverSchema(&mut storage)
-                    .get_idle_prover_job_from_job_queue()
+                    .lease_prover_job("test_prover", Duration::from_secs(60))
                     .await?
                     .unwrap()
                     .job_id;
@@ -585,6 +592,7 @@ impl TestServerConfig {
                         Some((id, op)),
                         100,
                         100u32.into(),
+                        None,
                         Default::default(),
                     )
                     .await?;
@@ -626,6 +634,7 @@ impl TestServerConfig {
                         Some((id, op)),
                         100,
                         100u32.into(),
+                        None,
                         Default::default(),
                     )
                     .await?;
@@ -798,20 +807,7 @@ pub fn dummy_full_exit_op(
 }
 
 pub fn deserialize_response_result<T: DeserializeOwned>(response: Response) -> anyhow::Result<T> {
-    match response.result {
-        Some(result) => {
-            let result = serde_json::from_value(result)?;
-            Ok(result)
-        }
-        None => {
-            if response.error.is_some() {
-                anyhow::bail!("Response returned error: {:?}", response);
-            } else {
-                let result = serde_json::from_str("null")?;
-                Ok(result)
-            }
-        }
-    }
+    Ok(deserialize_result(response)?)
 }
 
 pub fn dummy_sign_verifier() -> mpsc::Sender<VerifySignatureRequest> {
@@ -912,6 +908,11 @@ pub fn get_test_ticker_config() -> TickerConfig {
         scale_fee_coefficient: Ratio::new(BigUint::from(150u32), BigUint::from(100u32)),
         max_blocks_to_aggregate: 5,
         subsidy_cpk_price_usd: scaled_u64_to_ratio(SUBSIDY_CPK_PRICE_USD_SCALED),
+        gas_price_scale: GasPriceScaleConfig {
+            min_percent: 130,
+            max_percent: 130,
+            smoothing: Duration::from_secs(600),
+        },
     }
 }
 pub fn dummy_fee_ticker(
@@ -932,3 +933,22 @@ pub fn dummy_fee_ticker(
         validator,
     )
 }
+
+pub struct DummyFiatRatesAPI {
+    rates: HashMap<FiatCurrency, BigDecimal>,
+}
+
+#[async_trait::async_trait]
+impl FiatRatesAPI for DummyFiatRatesAPI {
+    async fn get_usd_rates(&self) -> Result<HashMap<FiatCurrency, BigDecimal>, PriceError> {
+        Ok(self.rates.clone())
+    }
+}
+
+pub fn dummy_fiat_rates(rates: &[(FiatCurrency, BigDecimal)]) -> FiatRates {
+    let rates = rates.iter().cloned().collect();
+    FiatRates::new(
+        Arc::new(DummyFiatRatesAPI { rates }),
+        Duration::from_secs(60),
+    )
+}
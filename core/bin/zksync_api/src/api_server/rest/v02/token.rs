@@ -1,68 +1,239 @@
 //! Tokens part of API implementation.
 
 // Built-in uses
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
-use std::time::Instant;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 // External uses
 use actix_web::{
-    web::{self},
+    dev::RequestHead,
+    guard,
+    web::{self, Json},
     Scope,
 };
 use bigdecimal::{BigDecimal, Zero};
+use chrono::Utc;
+use futures::{stream, StreamExt};
 use num::{rational::Ratio, BigUint, FromPrimitive};
+use tokio::sync::watch;
 
 // Workspace uses
 use zksync_api_types::v02::{
-    pagination::{parse_query, ApiEither, Paginated, PaginationQuery},
-    token::{ApiNFT, ApiToken, TokenPrice},
+    pagination::{
+        ApiEither, Paginated, PaginationQuery, PaginationQueryOrCursor, TokenTxsRequest,
+        TokensRequest,
+    },
+    token::{
+        ApiToken, AvailableCurrencies, FeeAcceptability, FeeAcceptableBatchRequest,
+        FeeUnacceptableReason, PriceHistoryQuery, TokenFilter, TokenIncludeQuery, TokenLookup,
+        TokenPrice, TokenPriceHistory, TokenPriceLookup, TokenPricePoint, TokenPriceQuery,
+        TokenRate, TokenRateQuery, TokensByIdsQuery, TokensQuery,
+    },
+    transaction::{Transaction, TxHashSerializeWrapper},
 };
-use zksync_config::ZkSyncConfig;
-use zksync_crypto::params::MIN_NFT_TOKEN_ID;
-use zksync_storage::{ConnectionPool, StorageProcessor};
-use zksync_token_db_cache::TokenDBCache;
-use zksync_types::{tx::TxHash, AccountId, Token, TokenId, TokenLike};
+use zksync_config::{ReloadableConfig, ZkSyncConfig};
+use zksync_storage::{tokens::TokensStorage, ConnectionPool, StorageProcessor};
+use zksync_token_db_cache::{TokenDBCache, TokenDenylist};
+use zksync_types::{tx::TxHash, Token, TokenId, TokenLike};
+use zksync_utils::{format_units, ratio_to_big_decimal};
 
 // Local uses
 use super::{
     error::{Error, InvalidDataError},
+    extractors::{PathParam, ValidPath},
+    nft::{self, ApiNFTData},
     paginate_trait::Paginate,
-    response::ApiResult,
+    pagination_cursor::PaginationCursors,
+    response::{ApiResult, CachedApiResult, FieldsQuery, SparseFields},
+    token_rates::{convert_amount, TokenRates},
 };
 use crate::{
+    api_server::config_reload::ConfigReloader,
     api_try,
-    fee_ticker::{FeeTicker, PriceError, TokenPriceRequestType},
+    fee_ticker::{FeeTicker, PriceError},
+    fiat_rates::{FiatCurrency, FiatRates},
+    utils::{
+        clock::{Clock, SystemClock},
+        ttl_cache::TtlCache,
+    },
 };
 
+/// Number of the most recent USD price points kept per token for smoothing.
+const PRICE_HISTORY_CAPACITY: usize = 100;
+/// Maximum number of tokens that can be requested in one batch request.
+pub const MAX_TOKENS_BATCH_SIZE: usize = 100;
+/// Maximum number of intervals returned by the `price_history` endpoint.
+pub const MAX_PRICE_HISTORY_POINTS: i64 = 1000;
+/// Default length of the price history interval, in seconds.
+const DEFAULT_PRICE_HISTORY_RESOLUTION: u32 = 60 * 60;
+/// Precision of the averaged prices in the price history.
+const PRICE_HISTORY_PRECISION: usize = 6;
+/// Maximum number of token prices requested from the ticker at once.
+const PRICE_CHECK_CONCURRENCY: usize = 10;
+/// Precision of the market volumes included into the tokens, same as they are stored with.
+const MARKET_VOLUME_PRECISION: usize = 6;
+
+/// Optional fields of `ApiToken` requested by the client.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct TokenIncludes {
+    price: bool,
+    volume: bool,
+}
+
 /// Shared data between `api/v0.2/tokens` endpoints.
 #[derive(Clone)]
-struct ApiTokenData {
-    min_market_volume: Ratio<BigUint>,
-    fee_ticker: FeeTicker,
+pub(crate) struct ApiTokenData {
+    /// The minimum market volume of the tokens is taken from the reloaded config.
+    config_updates: watch::Receiver<ReloadableConfig>,
+    /// The minimum market volume the server is started with, it's used if the reloaded one is invalid.
+    initial_min_market_volume: Ratio<BigUint>,
     tokens: TokenDBCache,
+    /// Denylisted tokens are hidden from the endpoints.
+    token_denylist: TokenDenylist,
+    token_rates: TokenRates,
+    /// Prices of the tokens by the currencies they were requested in.
+    prices: TtlCache<(TokenLike, String), BigDecimal>,
+    /// Tokens that every priced token can be crossed against.
+    reference_tokens: Vec<TokenLike>,
     pool: ConnectionPool,
+    fiat_rates: FiatRates,
+    /// How long the clients may cache the responses.
+    response_max_age: Duration,
 }
 
 impl ApiTokenData {
-    fn new(
+    pub(crate) fn new(
+        config: &ZkSyncConfig,
+        pool: ConnectionPool,
+        tokens: TokenDBCache,
+        fee_ticker: FeeTicker,
+        fiat_rates: FiatRates,
+        config_reloader: &ConfigReloader,
+    ) -> Self {
+        Self::with_clock(
+            config,
+            pool,
+            tokens,
+            fee_ticker,
+            fiat_rates,
+            config_reloader,
+            Arc::new(SystemClock),
+        )
+    }
+
+    /// Creates the data with the given clock used to expire the cached prices.
+    fn with_clock(
         config: &ZkSyncConfig,
         pool: ConnectionPool,
         tokens: TokenDBCache,
         fee_ticker: FeeTicker,
+        fiat_rates: FiatRates,
+        config_reloader: &ConfigReloader,
+        clock: Arc<dyn Clock>,
     ) -> Self {
+        let token_denylist =
+            TokenDenylist::new(pool.clone(), config_reloader.current().denylisted_tokens);
+        config_reloader.register_denylist(&token_denylist);
         Self {
-            min_market_volume: Ratio::from(
+            config_updates: config_reloader.subscribe(),
+            initial_min_market_volume: Ratio::from(
                 BigUint::from_f64(config.ticker.liquidity_volume)
                     .expect("TickerConfig::liquidity_volume must be positive"),
             ),
+            token_denylist,
             pool,
             tokens,
-            fee_ticker,
+            token_rates: TokenRates::new(
+                fee_ticker,
+                config.api.token_config.price_cache_ttl(),
+                PRICE_HISTORY_CAPACITY,
+                clock.clone(),
+            ),
+            prices: TtlCache::with_clock(config.api.token_config.price_cache_ttl(), clock),
+            reference_tokens: config
+                .ticker
+                .unconditionally_valid_tokens
+                .iter()
+                .copied()
+                .map(TokenLike::Address)
+                .collect(),
+            fiat_rates,
+            response_max_age: config.api.token_config.response_max_age(),
+        }
+    }
+}
+
+/// Fills the metadata of the tokens set by the admin.
+async fn include_token_metadata<'a>(
+    storage: &mut impl TokensStorage,
+    tokens: impl IntoIterator<Item = &'a mut ApiToken>,
+) -> Result<(), Error> {
+    let mut tokens: Vec<&mut ApiToken> = tokens.into_iter().collect();
+    let token_ids: Vec<TokenId> = tokens.iter().map(|token| token.id).collect();
+    let mut metadata = storage
+        .load_token_metadata(&token_ids)
+        .await
+        .map_err(Error::storage)?;
+    for token in tokens {
+        if let Some(metadata) = metadata.remove(&token.id) {
+            token.logo_uri = metadata.logo_uri;
+            token.project_url = metadata.project_url;
+            token.display_name = metadata.display_name;
         }
     }
+    Ok(())
+}
+
+/// Loads the page of the tokens along with their fee eligibility and metadata.
+async fn load_token_page<S>(
+    storage: &mut S,
+    query: PaginationQuery<TokensRequest>,
+    cursors: &PaginationCursors,
+) -> Result<Paginated<ApiToken, TokenId>, Error>
+where
+    S: TokensStorage + Paginate<TokensRequest, OutputObj = Token, OutputId = TokenId>,
+{
+    let min_market_volume = query.from.min_market_volume.clone();
+    let paginated_tokens = storage.paginate_with_cursor(query, cursors).await?;
+    let tokens_to_check: Vec<TokenId> =
+        paginated_tokens.list.iter().map(|token| token.id).collect();
+    let tokens_enabled_for_fees = storage
+        .filter_tokens_by_market_volume(tokens_to_check, &min_market_volume)
+        .await
+        .map_err(Error::storage)?;
+    let mut list: Vec<ApiToken> = paginated_tokens
+        .list
+        .into_iter()
+        .map(|token| {
+            let eligibility = tokens_enabled_for_fees.contains(&token.id);
+            ApiToken::from_token_and_eligibility(token, eligibility)
+        })
+        .collect();
+    include_token_metadata(storage, list.iter_mut()).await?;
+    Ok(Paginated {
+        list,
+        pagination: paginated_tokens.pagination,
+    })
 }
 
 impl ApiTokenData {
+    fn min_market_volume(&self) -> Ratio<BigUint> {
+        let liquidity_volume = self.config_updates.borrow().liquidity_volume;
+        match BigUint::from_f64(liquidity_volume) {
+            Some(volume) => Ratio::from(volume),
+            None => {
+                vlog::warn!(
+                    "The reloaded liquidity volume {} is invalid, {} is used instead",
+                    liquidity_volume,
+                    self.initial_min_market_volume
+                );
+                self.initial_min_market_volume.clone()
+            }
+        }
+    }
+
     async fn is_token_enabled_for_fees(
         &self,
         storage: &mut StorageProcessor<'_>,
@@ -70,7 +241,7 @@ impl ApiTokenData {
     ) -> Result<bool, Error> {
         let result = storage
             .tokens_schema()
-            .filter_tokens_by_market_volume(vec![token_id], &self.min_market_volume)
+            .filter_tokens_by_market_volume(vec![token_id], &self.min_market_volume())
             .await
             .map_err(Error::storage)?;
         Ok(!result.is_empty())
@@ -79,40 +250,61 @@ impl ApiTokenData {
     async fn token_page(
         &self,
         query: PaginationQuery<ApiEither<TokenId>>,
+        filter: TokenFilter,
+        cursors: &PaginationCursors,
     ) -> Result<Paginated<ApiToken, TokenId>, Error> {
+        let query = PaginationQuery {
+            from: TokensRequest {
+                token_id: query.from,
+                filter,
+                min_market_volume: self.min_market_volume(),
+                denylisted_tokens: self.denylisted_tokens().await?.iter().copied().collect(),
+            },
+            limit: query.limit,
+            direction: query.direction,
+        };
         let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
-        let paginated_tokens: Result<Paginated<Token, TokenId>, Error> =
-            storage.paginate_checked(&query).await;
-        match paginated_tokens {
-            Ok(paginated_tokens) => {
-                let tokens_to_check: Vec<TokenId> =
-                    paginated_tokens.list.iter().map(|token| token.id).collect();
-                let tokens_enabled_for_fees = storage
-                    .tokens_schema()
-                    .filter_tokens_by_market_volume(tokens_to_check, &self.min_market_volume)
-                    .await
-                    .map_err(Error::storage)?;
-                let list = paginated_tokens
-                    .list
-                    .into_iter()
-                    .map(|token| {
-                        let eligibility = tokens_enabled_for_fees.contains(&token.id);
-                        ApiToken::from_token_and_eligibility(token, eligibility)
-                    })
-                    .collect();
-                Ok(Paginated::new(
-                    list,
-                    paginated_tokens.pagination.from,
-                    paginated_tokens.pagination.limit,
-                    paginated_tokens.pagination.direction,
-                    paginated_tokens.pagination.count,
-                ))
-            }
-            Err(err) => Err(err),
+        load_token_page(&mut storage, query, cursors).await
+    }
+
+    async fn token_txs(
+        &self,
+        token_like: TokenLike,
+        query: PaginationQuery<ApiEither<TxHash>>,
+        cursors: &PaginationCursors,
+    ) -> Result<Paginated<Transaction, TxHashSerializeWrapper>, Error> {
+        let token = self.token(token_like).await?;
+        let query = PaginationQuery {
+            from: TokenTxsRequest {
+                token: token.id,
+                tx_hash: query.from,
+            },
+            limit: query.limit,
+            direction: query.direction,
+        };
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        storage.paginate_with_cursor(query, cursors).await
+    }
+
+    async fn denylisted_tokens(&self) -> Result<Arc<HashSet<TokenId>>, Error> {
+        self.token_denylist
+            .denylisted_tokens()
+            .await
+            .map_err(Error::storage)
+    }
+
+    /// Denylisted tokens are reported as not found.
+    pub(crate) async fn token(&self, token_like: TokenLike) -> Result<Token, Error> {
+        let token = self.find_token(token_like).await?;
+        if self.denylisted_tokens().await?.contains(&token.id) {
+            return Err(Error::from(PriceError::token_not_found(
+                "Token not found in storage",
+            )));
         }
+        Ok(token)
     }
 
-    async fn token(&self, token_like: TokenLike) -> Result<Token, Error> {
+    async fn find_token(&self, token_like: TokenLike) -> Result<Token, Error> {
         // Try to find the token in the cache first.
         if let Some(token) = self
             .tokens
@@ -140,49 +332,471 @@ impl ApiTokenData {
         }
     }
 
-    async fn api_token(&self, token_like: TokenLike) -> Result<ApiToken, Error> {
+    /// Finds the tokens in the order of the request with at most one storage lookup,
+    /// the unknown and denylisted tokens are `None`.
+    async fn find_tokens(&self, token_likes: &[TokenLike]) -> Result<Vec<Option<Token>>, Error> {
+        let denylisted_tokens = self.denylisted_tokens().await?;
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let tokens = self
+            .tokens
+            .get_tokens(&mut storage, token_likes)
+            .await
+            .map_err(Error::storage)?;
+        Ok(tokens
+            .into_iter()
+            .map(|token| token.filter(|token| !denylisted_tokens.contains(&token.id)))
+            .collect())
+    }
+
+    pub(crate) async fn api_token(&self, token_like: TokenLike) -> Result<ApiToken, Error> {
         let token = self.token(token_like).await?;
         let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
         let enabled_for_fees = self
             .is_token_enabled_for_fees(&mut storage, token.id)
             .await?;
-        Ok(ApiToken::from_token_and_eligibility(
-            token,
-            enabled_for_fees,
-        ))
+        let mut token = ApiToken::from_token_and_eligibility(token, enabled_for_fees);
+        include_token_metadata(&mut storage, Some(&mut token)).await?;
+        Ok(token)
+    }
+
+    /// Fills the optional fields of the tokens. The tokens without the known price or
+    /// market volume are left without the corresponding field.
+    async fn include_token_fields(
+        &self,
+        tokens: &mut [ApiToken],
+        includes: TokenIncludes,
+    ) -> Result<(), Error> {
+        if includes.volume {
+            let token_ids: Vec<TokenId> = tokens.iter().map(|token| token.id).collect();
+            let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+            let market_volumes = storage
+                .tokens_schema()
+                .load_token_market_volumes(&token_ids)
+                .await
+                .map_err(Error::storage)?;
+            for token in tokens.iter_mut() {
+                token.market_volume = market_volumes.get(&token.id).map(|volume| {
+                    ratio_to_big_decimal(&volume.market_volume, MARKET_VOLUME_PRECISION)
+                });
+            }
+        }
+        if includes.price {
+            let token_ids = tokens.iter().map(|token| token.id).collect();
+            let prices = self.token_prices_usd(token_ids).await;
+            for (token, price) in tokens.iter_mut().zip(prices) {
+                token.usd_price = price.ok();
+            }
+        }
+        Ok(())
     }
 
-    async fn token_price_usd(&self, token: TokenLike) -> Result<BigDecimal, Error> {
-        self.fee_ticker
-            .get_token_price(token, TokenPriceRequestType::USDForOneToken)
+    /// Loads the tokens with the given ids, keeping the order of the request.
+    /// All the distinct tokens are looked up at once.
+    pub(crate) async fn tokens_by_ids(&self, ids: Vec<TokenId>) -> Result<Vec<TokenLookup>, Error> {
+        if ids.len() > MAX_TOKENS_BATCH_SIZE {
+            return Err(Error::from(InvalidDataError::TooManyTokens));
+        }
+
+        let mut distinct_ids = ids.clone();
+        distinct_ids.sort_unstable();
+        distinct_ids.dedup();
+        let token_likes: Vec<TokenLike> = distinct_ids.iter().copied().map(TokenLike::Id).collect();
+        let found_tokens: HashMap<TokenId, Option<Token>> = distinct_ids
+            .into_iter()
+            .zip(self.find_tokens(&token_likes).await?)
+            .collect();
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+
+        let tokens_to_check = found_tokens
+            .iter()
+            .filter(|(_, token)| token.is_some())
+            .map(|(id, _)| *id)
+            .collect();
+        let tokens_enabled_for_fees = storage
+            .tokens_schema()
+            .filter_tokens_by_market_volume(tokens_to_check, &self.min_market_volume())
             .await
-            .map_err(Error::storage)
+            .map_err(Error::storage)?;
+
+        let mut lookups: Vec<TokenLookup> = ids
+            .into_iter()
+            .map(|id| match found_tokens.get(&id).cloned().flatten() {
+                Some(token) => TokenLookup {
+                    id,
+                    token: Some(ApiToken::from_token_and_eligibility(
+                        token,
+                        tokens_enabled_for_fees.contains(&id),
+                    )),
+                    error: None,
+                },
+                None => TokenLookup {
+                    id,
+                    token: None,
+                    error: Some(String::from("Token not found")),
+                },
+            })
+            .collect();
+        include_token_metadata(
+            &mut storage,
+            lookups
+                .iter_mut()
+                .filter_map(|lookup| lookup.token.as_mut()),
+        )
+        .await?;
+        Ok(lookups)
+    }
+
+    /// Loads all the tokens which can be used to pay fees, ordered by their ids.
+    pub(crate) async fn fee_tokens(&self) -> Result<Vec<ApiToken>, Error> {
+        let denylisted_tokens = self.denylisted_tokens().await?;
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let mut tokens = storage
+            .tokens_schema()
+            .load_tokens_by_market_volume(self.min_market_volume())
+            .await
+            .map_err(Error::storage)?;
+        // ETH always has enough market volume.
+        if !tokens.contains_key(&TokenId(0)) {
+            let eth = self
+                .tokens
+                .get_token(&mut storage, TokenLike::Id(TokenId(0)))
+                .await
+                .map_err(Error::storage)?;
+            tokens.extend(eth.map(|eth| (eth.id, eth)));
+        }
+
+        let mut list: Vec<ApiToken> = tokens
+            .into_iter()
+            .filter(|(id, _)| !denylisted_tokens.contains(id))
+            .map(|(_, token)| ApiToken::from_token_and_eligibility(token, true))
+            .collect();
+        list.sort_by_key(|token| token.id);
+        include_token_metadata(&mut storage, list.iter_mut()).await?;
+        Ok(list)
+    }
+
+    async fn token_price_usd(&self, token_id: TokenId) -> Result<BigDecimal, Error> {
+        self.token_rates
+            .usd_price(token_id)
+            .await
+            .map_err(Error::from)
+    }
+
+    /// Returns the USD prices of the list of tokens, in the order of the request.
+    async fn token_prices_usd(&self, tokens: Vec<TokenId>) -> Vec<Result<BigDecimal, Error>> {
+        self.token_rates
+            .usd_prices(tokens)
+            .await
+            .into_iter()
+            .map(|price| price.map_err(Error::from))
+            .collect()
+    }
+
+    /// Returns the USD prices of the tokens, in the order of the request. The unknown
+    /// and denylisted tokens are reported as not found without failing the whole batch.
+    pub(crate) async fn token_price_lookups(
+        &self,
+        tokens: Vec<TokenLike>,
+    ) -> Result<Vec<TokenPriceLookup>, Error> {
+        if tokens.len() > MAX_TOKENS_BATCH_SIZE {
+            return Err(Error::from(InvalidDataError::TooManyTokens));
+        }
+
+        let found_tokens: Vec<Result<Token, Error>> = self
+            .find_tokens(&tokens)
+            .await?
+            .into_iter()
+            .map(|token| {
+                token.ok_or_else(|| {
+                    Error::from(PriceError::token_not_found("Token not found in storage"))
+                })
+            })
+            .collect();
+        let token_ids = found_tokens
+            .iter()
+            .filter_map(|token| token.as_ref().ok())
+            .map(|token| token.id)
+            .collect();
+        let mut prices = self.token_prices_usd(token_ids).await.into_iter();
+
+        let lookups = tokens
+            .into_iter()
+            .zip(found_tokens)
+            .map(|(token, found)| {
+                let price = found.and_then(|_| prices.next().expect("price for every found token"));
+                match price {
+                    Ok(price) => TokenPriceLookup {
+                        token,
+                        price: Some(price),
+                        error: None,
+                    },
+                    Err(err) => TokenPriceLookup {
+                        token,
+                        price: None,
+                        error: Some(err.message),
+                    },
+                }
+            })
+            .collect();
+        Ok(lookups)
+    }
+
+    /// Returns the moving average of the token USD price over the `window` most
+    /// recent price points.
+    async fn smoothed_token_price_in(
+        &self,
+        token: TokenLike,
+        currency: &str,
+        window: usize,
+    ) -> Result<BigDecimal, Error> {
+        if currency != "usd" {
+            return Err(Error::from(InvalidDataError::SmoothingNotSupported));
+        }
+        // The price history is kept by the token id, whatever form the token is requested in.
+        let token = self.token(token).await?;
+        self.token_rates
+            .smoothed_usd_price(token.id, window)
+            .await
+            .map_err(Error::from)?
+            .ok_or_else(|| Error::from(InvalidDataError::InvalidSmoothingWindow))
+    }
+
+    async fn has_usd_price(&self, token_id: TokenId) -> bool {
+        matches!(self.token_price_usd(token_id).await, Ok(price) if !price.is_zero())
+    }
+
+    async fn available_currencies(&self, token_like: TokenLike) -> Result<Vec<String>, Error> {
+        let token = self.token(token_like).await?;
+        // Without the USD price there is nothing to convert through.
+        if !self.has_usd_price(token.id).await {
+            return Ok(Vec::new());
+        }
+
+        let mut currencies = vec![String::from("usd")];
+        for currency in FiatCurrency::ALL.iter() {
+            if self.fiat_rates.usd_rate(*currency).await.is_ok() {
+                currencies.push(currency.to_string());
+            }
+        }
+        for reference_token in &self.reference_tokens {
+            let reference_token = match self.token(reference_token.clone()).await {
+                Ok(reference_token) => reference_token,
+                Err(_) => continue,
+            };
+            if reference_token.id != token.id && self.has_usd_price(reference_token.id).await {
+                currencies.push(reference_token.id.to_string());
+            }
+        }
+        Ok(currencies)
+    }
+
+    /// Checks whether each of the tokens can be used to pay fees, and if not, explains why.
+    pub(crate) async fn fee_acceptable_batch(
+        &self,
+        tokens: Vec<TokenLike>,
+    ) -> Result<Vec<FeeAcceptability>, Error> {
+        if tokens.len() > MAX_TOKENS_BATCH_SIZE {
+            return Err(Error::from(InvalidDataError::TooManyTokens));
+        }
+
+        let found_tokens = self.find_tokens(&tokens).await?;
+        let resolved_tokens: Vec<(TokenLike, Option<Token>)> =
+            tokens.into_iter().zip(found_tokens).collect();
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+
+        let token_ids: Vec<TokenId> = resolved_tokens
+            .iter()
+            .filter_map(|(_, token)| token.as_ref().map(|token| token.id))
+            .collect();
+        let market_volumes = storage
+            .tokens_schema()
+            .load_token_market_volumes(&token_ids)
+            .await
+            .map_err(Error::storage)?;
+        drop(storage);
+
+        let results = stream::iter(resolved_tokens)
+            .map(|(token_like, token)| {
+                let market_volumes = &market_volumes;
+                async move {
+                    let token = match token {
+                        Some(token) => token,
+                        None => {
+                            return FeeAcceptability::unacceptable(
+                                token_like,
+                                FeeUnacceptableReason::NotFound,
+                            )
+                        }
+                    };
+                    // ETH and the unconditionally valid tokens are accepted regardless of
+                    // their market volume.
+                    let volume_required = token.id != TokenId(0)
+                        && !self
+                            .reference_tokens
+                            .contains(&TokenLike::Address(token.address));
+                    if volume_required {
+                        match market_volumes.get(&token.id) {
+                            None => {
+                                return FeeAcceptability::unacceptable(
+                                    token_like,
+                                    FeeUnacceptableReason::NoVolumeData,
+                                )
+                            }
+                            Some(volume) if volume.market_volume < self.min_market_volume() => {
+                                return FeeAcceptability::unacceptable(
+                                    token_like,
+                                    FeeUnacceptableReason::BelowThreshold,
+                                )
+                            }
+                            Some(_) => {}
+                        }
+                    }
+                    if !self.has_usd_price(token.id).await {
+                        return FeeAcceptability::unacceptable(
+                            token_like,
+                            FeeUnacceptableReason::NoLivePrice,
+                        );
+                    }
+                    FeeAcceptability::acceptable(token_like)
+                }
+            })
+            .buffered(PRICE_CHECK_CONCURRENCY)
+            .collect()
+            .await;
+        Ok(results)
+    }
+
+    /// Loads the USD price history of the token. By default, the hourly prices
+    /// for the last day are returned.
+    async fn price_history(
+        &self,
+        token_like: TokenLike,
+        query: PriceHistoryQuery,
+    ) -> Result<TokenPriceHistory, Error> {
+        let token = self.token(token_like).await?;
+
+        let to = query.to.unwrap_or_else(Utc::now);
+        let from = query.from.unwrap_or_else(|| to - chrono::Duration::days(1));
+        let resolution = query.resolution.unwrap_or(DEFAULT_PRICE_HISTORY_RESOLUTION);
+        if from > to
+            || resolution == 0
+            || (to - from).num_seconds() / i64::from(resolution) >= MAX_PRICE_HISTORY_POINTS
+        {
+            return Err(Error::from(InvalidDataError::InvalidPriceHistoryRange));
+        }
+
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let prices = storage
+            .tokens_schema()
+            .load_ticker_price_history(token.id, from, to, resolution)
+            .await
+            .map_err(Error::storage)?;
+        let points = prices
+            .into_iter()
+            .map(|price| TokenPricePoint {
+                time: price.last_updated,
+                price: ratio_to_big_decimal(&price.usd_price, PRICE_HISTORY_PRECISION),
+            })
+            .collect();
+
+        Ok(TokenPriceHistory {
+            token_id: token.id,
+            token_symbol: token.symbol,
+            resolution,
+            points,
+        })
+    }
+
+    /// Returns the price of the token in the currency. The prices are cached,
+    /// so the repeated requests don't reach the fee ticker.
+    pub(crate) async fn token_price_in(
+        &self,
+        first_token: TokenLike,
+        currency: &str,
+    ) -> Result<BigDecimal, Error> {
+        let cache_key = (first_token.to_lowercase(), currency.to_string());
+        if let Some(price) = self.prices.get(&cache_key) {
+            return Ok(price);
+        }
+
+        let price = self.convert_token_price(first_token, currency).await?;
+        self.prices.insert(cache_key, price.clone());
+        Ok(price)
+    }
+
+    /// Returns the rate of the token pair and converts the amount of the base token
+    /// to the quote token, if it's given.
+    async fn token_rate(
+        &self,
+        base_token: TokenLike,
+        quote_token: TokenLike,
+        amount: Option<BigUint>,
+        formatted: bool,
+    ) -> Result<TokenRate, Error> {
+        let base_token = self.token(base_token).await?;
+        let quote_token = self.token(quote_token).await?;
+        let rate = self
+            .token_rates
+            .rate(base_token.id, quote_token.id)
+            .await
+            .map_err(Error::from)?
+            .ok_or_else(|| Error::from(InvalidDataError::TokenZeroPriceError))?;
+        let converted_amount = match &amount {
+            Some(amount) => Some(
+                convert_amount(amount, &rate, base_token.decimals, quote_token.decimals)
+                    .map_err(Error::storage)?,
+            ),
+            None => None,
+        };
+
+        let (formatted_amount, formatted_converted_amount) = match (&amount, &converted_amount) {
+            (Some(amount), Some(converted_amount)) if formatted => (
+                Some(format_units(amount, base_token.decimals)),
+                Some(format_units(converted_amount, quote_token.decimals)),
+            ),
+            _ => (None, None),
+        };
+
+        Ok(TokenRate {
+            base_token_id: base_token.id,
+            base_token_symbol: base_token.symbol,
+            quote_token_id: quote_token.id,
+            quote_token_symbol: quote_token.symbol,
+            rate,
+            amount: amount.map(Into::into),
+            converted_amount: converted_amount.map(Into::into),
+            formatted_amount,
+            formatted_converted_amount,
+        })
     }
+
     // TODO: take `currency` as enum. (ZKS-628)
-    async fn token_price_in(
+    async fn convert_token_price(
         &self,
         first_token: TokenLike,
         currency: &str,
     ) -> Result<BigDecimal, Error> {
+        let first_token = self.token(first_token).await?;
         if let Ok(second_token_id) = u32::from_str(currency) {
-            let second_token = TokenLike::from(TokenId(second_token_id));
-            let first_usd_price = self.token_price_usd(first_token).await;
-            let second_usd_price = self.token_price_usd(second_token).await;
-            match (first_usd_price, second_usd_price) {
-                (Ok(first_usd_price), Ok(second_usd_price)) => {
-                    if second_usd_price.is_zero() {
-                        Err(Error::from(InvalidDataError::TokenZeroPriceError))
-                    } else {
-                        Ok(first_usd_price / second_usd_price)
-                    }
-                }
-                (Err(err), _) => Err(err),
-                (_, Err(err)) => Err(err),
-            }
+            self.token_rates
+                .rate(first_token.id, TokenId(second_token_id))
+                .await
+                .map_err(Error::from)?
+                .ok_or_else(|| Error::from(InvalidDataError::TokenZeroPriceError))
         } else {
             match currency {
-                "usd" => self.token_price_usd(first_token).await,
-                _ => Err(Error::from(InvalidDataError::InvalidCurrency)),
+                "usd" => self.token_price_usd(first_token.id).await,
+                _ => {
+                    let currency = FiatCurrency::from_str(currency)
+                        .map_err(|_| Error::from(InvalidDataError::InvalidCurrency))?;
+                    let usd_price = self.token_price_usd(first_token.id).await?;
+                    let usd_rate = self
+                        .fiat_rates
+                        .usd_rate(currency)
+                        .await
+                        .map_err(Error::from)?;
+                    Ok(usd_price * usd_rate)
+                }
             }
         }
     }
@@ -192,22 +806,92 @@ impl ApiTokenData {
 
 async fn token_pagination(
     data: web::Data<ApiTokenData>,
-    web::Query(query): web::Query<PaginationQuery<String>>,
-) -> ApiResult<Paginated<ApiToken, TokenId>> {
+    cursors: web::Data<PaginationCursors>,
+    web::Query(query): web::Query<TokensQuery>,
+    web::Query(fields): web::Query<FieldsQuery>,
+) -> CachedApiResult<SparseFields<Paginated<ApiToken, TokenId>>> {
     let start = Instant::now();
-    let query = api_try!(parse_query(query).map_err(Error::from));
-    let res = data.token_page(query).await.into();
+    let pagination = api_try!(cursors.parse_query(PaginationQueryOrCursor {
+        from: query.from,
+        limit: query.limit,
+        direction: query.direction,
+        cursor: query.cursor,
+    }));
+    let filter = TokenFilter {
+        enabled_for_fees: query.enabled_for_fees,
+        symbol_prefix: query.symbol_prefix,
+        sort: query.sort.unwrap_or_default(),
+    };
+    let includes = api_try!(parse_token_includes(query.include.as_deref()));
+    let mut page = api_try!(data.token_page(pagination, filter, &cursors).await);
+    let res = ApiResult::from(
+        data.include_token_fields(&mut page.list, includes)
+            .await
+            .map(|_| page),
+    )
+    .with_fields(&fields)
+    .cached(data.response_max_age);
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "token_pagination");
     res
 }
 
+fn parse_token_includes(include: Option<&str>) -> Result<TokenIncludes, Error> {
+    let mut includes = TokenIncludes::default();
+    for field in include.into_iter().flat_map(|include| include.split(',')) {
+        match field.trim() {
+            "price" => includes.price = true,
+            "volume" => includes.volume = true,
+            _ => return Err(Error::from(InvalidDataError::InvalidTokenIncludes)),
+        }
+    }
+    Ok(includes)
+}
+
+/// Checks whether the tokens are requested by their ids rather than by pages.
+fn has_ids_query(head: &RequestHead) -> bool {
+    head.uri.query().map_or(false, |query| {
+        query.split('&').any(|param| param.starts_with("ids="))
+    })
+}
+
+fn parse_token_ids(ids: &str) -> Result<Vec<TokenId>, Error> {
+    ids.split(',')
+        .map(|id| {
+            u32::from_str(id.trim())
+                .map(TokenId)
+                .map_err(|_| Error::from(InvalidDataError::InvalidTokenIds))
+        })
+        .collect()
+}
+
+async fn tokens_by_ids(
+    data: web::Data<ApiTokenData>,
+    web::Query(query): web::Query<TokensByIdsQuery>,
+    web::Query(fields): web::Query<FieldsQuery>,
+) -> CachedApiResult<SparseFields<Vec<TokenLookup>>> {
+    let start = Instant::now();
+    let ids = api_try!(parse_token_ids(&query.ids));
+    let res = ApiResult::from(data.tokens_by_ids(ids).await)
+        .with_fields(&fields)
+        .cached(data.response_max_age);
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "get_tokens_by_ids");
+    res
+}
+
 async fn token_info(
     data: web::Data<ApiTokenData>,
-    token_like_string: web::Path<String>,
-) -> ApiResult<ApiToken> {
+    ValidPath(token_like): ValidPath<TokenLike>,
+    web::Query(query): web::Query<TokenIncludeQuery>,
+) -> CachedApiResult<ApiToken> {
     let start = Instant::now();
-    let token_like = TokenLike::parse(&token_like_string);
-    let res = data.api_token(token_like).await.into();
+    let includes = api_try!(parse_token_includes(query.include.as_deref()));
+    let mut token = api_try!(data.api_token(token_like).await);
+    let res = ApiResult::from(
+        data.include_token_fields(std::slice::from_mut(&mut token), includes)
+            .await
+            .map(|_| token),
+    )
+    .cached(data.response_max_age);
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "token_info");
     res
 }
@@ -217,12 +901,19 @@ async fn token_info(
 async fn token_price(
     data: web::Data<ApiTokenData>,
     path: web::Path<(String, String)>,
-) -> ApiResult<TokenPrice> {
+    web::Query(query): web::Query<TokenPriceQuery>,
+) -> CachedApiResult<TokenPrice> {
     let start = Instant::now();
     let (token_like_string, currency) = path.into_inner();
     let first_token = TokenLike::parse(&token_like_string);
 
-    let price = api_try!(data.token_price_in(first_token.clone(), &currency).await);
+    let price = match query.smoothing {
+        Some(window) => api_try!(
+            data.smoothed_token_price_in(first_token.clone(), &currency, window)
+                .await
+        ),
+        None => api_try!(data.token_price_in(first_token.clone(), &currency).await),
+    };
     let token = api_try!(data.token(first_token).await);
 
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "get_token_price");
@@ -232,60 +923,85 @@ async fn token_price(
         price_in: currency.to_string(),
         decimals: token.decimals,
         price,
+        smoothing_window: query.smoothing,
     })
+    .cached(data.response_max_age)
 }
 
-async fn get_nft(
+async fn token_rate(
     data: web::Data<ApiTokenData>,
-    id: web::Path<TokenId>,
-) -> ApiResult<Option<ApiNFT>> {
+    path: web::Path<(String, String)>,
+    web::Query(query): web::Query<TokenRateQuery>,
+) -> CachedApiResult<TokenRate> {
     let start = Instant::now();
-    if id.0 < MIN_NFT_TOKEN_ID {
-        return Error::from(InvalidDataError::InvalidNFTTokenId).into();
-    }
-    let mut storage = api_try!(data.pool.access_storage().await.map_err(Error::storage));
-    let nft = api_try!(storage
-        .tokens_schema()
-        .get_nft_with_factories(*id)
-        .await
-        .map_err(Error::storage));
-    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "get_nft");
-    ApiResult::Ok(nft)
+    let (base_token, quote_token) = path.into_inner();
+    let base_token = api_try!(TokenLike::from_param(&base_token));
+    let quote_token = api_try!(TokenLike::from_param(&quote_token));
+
+    let res = ApiResult::from(
+        data.token_rate(
+            base_token,
+            quote_token,
+            query.amount.map(|amount| amount.0),
+            query.formatted,
+        )
+        .await,
+    );
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "token_rate");
+    res.cached(data.response_max_age)
 }
 
-async fn get_nft_owner(
+async fn token_price_history(
     data: web::Data<ApiTokenData>,
-    id: web::Path<TokenId>,
-) -> ApiResult<Option<AccountId>> {
+    ValidPath(token_like): ValidPath<TokenLike>,
+    web::Query(query): web::Query<PriceHistoryQuery>,
+) -> CachedApiResult<TokenPriceHistory> {
     let start = Instant::now();
-    if id.0 < MIN_NFT_TOKEN_ID {
-        return Error::from(InvalidDataError::InvalidNFTTokenId).into();
-    }
-    let mut storage = api_try!(data.pool.access_storage().await.map_err(Error::storage));
-    let owner_id = api_try!(storage
-        .chain()
-        .account_schema()
-        .get_nft_owner(*id)
-        .await
-        .map_err(Error::storage));
-    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "get_nft_owner");
-    ApiResult::Ok(owner_id)
+    let res =
+        ApiResult::from(data.price_history(token_like, query).await).cached(data.response_max_age);
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "get_token_price_history");
+    res
 }
 
-async fn get_nft_id_by_tx_hash(
+async fn token_available_currencies(
     data: web::Data<ApiTokenData>,
-    tx_hash: web::Path<TxHash>,
-) -> ApiResult<Option<TokenId>> {
+    ValidPath(token_like): ValidPath<TokenLike>,
+) -> CachedApiResult<AvailableCurrencies> {
     let start = Instant::now();
-    let mut storage = api_try!(data.pool.access_storage().await.map_err(Error::storage));
-    let nft_id = api_try!(storage
-        .chain()
-        .state_schema()
-        .get_nft_id_by_tx_hash(*tx_hash)
-        .await
-        .map_err(Error::storage));
-    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "get_nft_id_by_tx_hash");
-    ApiResult::Ok(nft_id)
+
+    let token = api_try!(data.token(token_like.clone()).await);
+    let currencies = api_try!(data.available_currencies(token_like).await);
+
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "token_available_currencies");
+    ApiResult::Ok(AvailableCurrencies {
+        token_id: token.id,
+        token_symbol: token.symbol,
+        currencies,
+    })
+    .cached(data.response_max_age)
+}
+
+async fn token_txs(
+    data: web::Data<ApiTokenData>,
+    cursors: web::Data<PaginationCursors>,
+    ValidPath(token_like): ValidPath<TokenLike>,
+    web::Query(query): web::Query<PaginationQueryOrCursor>,
+) -> ApiResult<Paginated<Transaction, TxHashSerializeWrapper>> {
+    let start = Instant::now();
+    let query = api_try!(cursors.parse_query(query));
+    let res = data.token_txs(token_like, query, &cursors).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "token_txs");
+    res
+}
+
+async fn fee_acceptable_batch(
+    data: web::Data<ApiTokenData>,
+    Json(body): Json<FeeAcceptableBatchRequest>,
+) -> ApiResult<Vec<FeeAcceptability>> {
+    let start = Instant::now();
+    let res = data.fee_acceptable_batch(body.tokens).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "fee_acceptable_batch");
+    res
 }
 
 pub fn api_scope(
@@ -293,34 +1009,80 @@ pub fn api_scope(
     pool: ConnectionPool,
     tokens_db: TokenDBCache,
     fee_ticker: FeeTicker,
+    fiat_rates: FiatRates,
+    config_reloader: &ConfigReloader,
 ) -> Scope {
-    let data = ApiTokenData::new(config, pool, tokens_db, fee_ticker);
+    let data = ApiTokenData::new(
+        config,
+        pool.clone(),
+        tokens_db,
+        fee_ticker,
+        fiat_rates,
+        config_reloader,
+    );
 
     web::scope("tokens")
         .app_data(web::Data::new(data))
+        .route(
+            "",
+            web::get()
+                .guard(guard::fn_guard(has_ids_query))
+                .to(tokens_by_ids),
+        )
         .route("", web::get().to(token_pagination))
+        .route("fee_acceptable_batch", web::post().to(fee_acceptable_batch))
         .route("{token_like}", web::get().to(token_info))
         .route(
             "{token_like}/priceIn/{currency}",
             web::get().to(token_price),
         )
-        .route("nft/{id}", web::get().to(get_nft))
-        .route("nft/{id}/owner", web::get().to(get_nft_owner))
+        .route("{token_like}/rate/{quote_token}", web::get().to(token_rate))
+        .route(
+            "{token_like}/price_history",
+            web::get().to(token_price_history),
+        )
+        .route(
+            "{token_like}/available_currencies",
+            web::get().to(token_available_currencies),
+        )
+        .route("{token_like}/transactions", web::get().to(token_txs))
+        // The NFT routes are served by the `nft` scope, these are kept for compatibility.
+        .app_data(web::Data::new(ApiNFTData::new(pool)))
+        .route("nft/{id}", web::get().to(nft::get_nft))
+        .route("nft/{id}/owner", web::get().to(nft::get_nft_owner))
         .route(
             "nft_id_by_tx_hash/{tx_hash}",
-            web::get().to(get_nft_id_by_tx_hash),
+            web::get().to(nft::get_nft_id_by_tx_hash),
         )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::api_server::rest::v02::{
-        test_utils::{deserialize_response_result, dummy_fee_ticker, TestServerConfig},
-        SharedData,
+    use crate::{
+        api_server::rest::v02::{
+            error::ErrorCode,
+            test_ticker::{
+                token_fixtures, ScriptedTickerInfo, FAILING_TOKEN, PRICED_TOKEN, TIMEOUT_TOKEN,
+                ZERO_PRICE_TOKEN,
+            },
+            test_utils::{
+                deserialize_response_result, dummy_fee_ticker, dummy_fiat_rates, TestServerConfig,
+            },
+            SharedData,
+        },
+        utils::clock::MockClock,
+    };
+    use chrono::TimeZone;
+    use zksync_api_client::rest::v02::typed::TypedClient;
+    use zksync_api_types::v02::token::{ApiNFT, TokenMetadata};
+    use zksync_api_types::v02::{
+        pagination::PaginationDirection, token::TokenSortOrder, ApiVersion,
+    };
+    use zksync_storage::tokens::memory::MemoryTokensStorage;
+    use zksync_types::{
+        tokens::TokenMarketVolume, tx::TxHash, AccountId, Address, BlockNumber, TokenKind, ZkSyncTx,
     };
-    use zksync_api_types::v02::{pagination::PaginationDirection, ApiVersion};
-    use zksync_types::{Address, BlockNumber, ZkSyncTx};
 
     async fn is_token_enabled_for_fees(
         storage: &mut StorageProcessor<'_>,
@@ -343,42 +1105,909 @@ mod tests {
         not(feature = "api_test"),
         ignore = "Use `zk test rust-api` command to perform this test"
     )]
-    async fn tokens_scope() -> anyhow::Result<()> {
+    async fn usd_price_cache_expiry() -> anyhow::Result<()> {
         let cfg = TestServerConfig::default();
-        cfg.fill_database().await?;
+        let prices = vec![(TokenLike::Id(TokenId(1)), 10_u64.into())];
+        let clock = MockClock::default();
+        let data = ApiTokenData::with_clock(
+            &cfg.config,
+            cfg.pool.clone(),
+            TokenDBCache::new(cfg.config.api.token_config.invalidate_token_cache_period()),
+            dummy_fee_ticker(&prices, None),
+            dummy_fiat_rates(&[]),
+            &ConfigReloader::from_config(&cfg.config),
+            Arc::new(clock.clone()),
+        );
 
-        let prices = vec![
-            (TokenLike::Id(TokenId(1)), 10_u64.into()),
-            (TokenLike::Symbol(String::from("PHNX")), 10_u64.into()),
-            (TokenLike::Id(TokenId(15)), 10_500_u64.into()),
-            (Address::default().into(), 1_u64.into()),
-        ];
+        let token = TokenId(1);
+        let price = data
+            .token_price_usd(token)
+            .await
+            .map_err(|err| anyhow::anyhow!(err.message))?;
+        assert_eq!(price, BigDecimal::from(10));
+        assert_eq!(data.token_rates.usd_prices.get(&token), Some(price.clone()));
 
-        let fee_ticker = dummy_fee_ticker(&prices, None);
+        // The cached price is served until its time-to-live has elapsed.
+        let ttl = cfg.config.api.token_config.price_cache_ttl();
+        clock.advance(ttl / 2);
+        data.token_price_usd(token)
+            .await
+            .map_err(|err| anyhow::anyhow!(err.message))?;
+        assert_eq!(data.token_rates.usd_price_history.len(&token), 1);
 
-        let shared_data = SharedData {
-            net: cfg.config.chain.eth.network,
-            api_version: ApiVersion::V02,
-        };
-        let (client, server) = cfg.start_server(
-            move |cfg| {
-                api_scope(
-                    &cfg.config,
-                    cfg.pool.clone(),
-                    TokenDBCache::new(cfg.config.api.token_config.invalidate_token_cache_period()),
-                    fee_ticker.clone(),
-                )
-            },
-            Some(shared_data),
-        );
+        // Once the price becomes stale, it's requested from the ticker again.
+        clock.advance(ttl / 2);
+        assert_eq!(data.token_rates.usd_prices.get(&token), None);
+        data.token_price_usd(token)
+            .await
+            .map_err(|err| anyhow::anyhow!(err.message))?;
+        assert_eq!(data.token_rates.usd_price_history.len(&token), 2);
 
-        let token_like = TokenLike::Id(TokenId(1));
-        let response = client.token_by_id(&token_like).await?;
-        let api_token: ApiToken = deserialize_response_result(response)?;
+        Ok(())
+    }
 
-        let expected_token = {
-            let mut storage = cfg.pool.access_storage().await?;
-            storage
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn smoothing_history_by_token_id() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+        let prices = vec![(TokenLike::Id(TokenId(1)), 10_u64.into())];
+        let clock = MockClock::default();
+        let data = ApiTokenData::with_clock(
+            &cfg.config,
+            cfg.pool.clone(),
+            TokenDBCache::new(cfg.config.api.token_config.invalidate_token_cache_period()),
+            dummy_fee_ticker(&prices, None),
+            dummy_fiat_rates(&[]),
+            &ConfigReloader::from_config(&cfg.config),
+            Arc::new(clock.clone()),
+        );
+        let token = {
+            let mut storage = cfg.pool.access_storage().await?;
+            storage
+                .tokens_schema()
+                .get_token(TokenLike::Id(TokenId(1)))
+                .await?
+                .unwrap()
+        };
+
+        // The same token requested in the different forms shares the price history.
+        let token_likes = vec![
+            TokenLike::Id(token.id),
+            TokenLike::Symbol(token.symbol.to_uppercase()),
+            TokenLike::Symbol(token.symbol.to_lowercase()),
+            TokenLike::Address(token.address),
+        ];
+        let ttl = cfg.config.api.token_config.price_cache_ttl();
+        for (i, token_like) in token_likes.iter().enumerate() {
+            let price = data
+                .smoothed_token_price_in(token_like.clone(), "usd", i + 1)
+                .await
+                .map_err(|err| anyhow::anyhow!(err.message))?;
+            assert_eq!(price, BigDecimal::from(10));
+            assert_eq!(data.token_rates.usd_price_history.len(&token.id), i + 1);
+            clock.advance(ttl);
+        }
+        // A window longer than the shared history is still rejected.
+        assert!(data
+            .smoothed_token_price_in(TokenLike::Id(token.id), "usd", token_likes.len() + 2)
+            .await
+            .is_err());
+
+        Ok(())
+    }
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn price_edge_cases() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        let fixtures = token_fixtures();
+        let info = ScriptedTickerInfo::new(&fixtures);
+        let data = ApiTokenData::new(
+            &cfg.config,
+            cfg.pool.clone(),
+            TokenDBCache::new(cfg.config.api.token_config.invalidate_token_cache_period()),
+            info.fee_ticker(&fixtures),
+            dummy_fiat_rates(&[(FiatCurrency::Eur, BigDecimal::from(2))]),
+            &ConfigReloader::from_config(&cfg.config),
+        );
+        let data = &data;
+        let price_in = move |token_id: TokenId, currency: &'static str| async move {
+            data.token_price_in(TokenLike::Id(token_id), currency).await
+        };
+        let price = move |token_id: TokenId, currency: &'static str| async move {
+            price_in(token_id, currency)
+                .await
+                .map_err(|err| anyhow::anyhow!(err.message))
+        };
+
+        assert_eq!(price(PRICED_TOKEN, "eur").await?, BigDecimal::from(20));
+        // The zero price is a valid price, but no token can be priced in such a token.
+        assert_eq!(price(ZERO_PRICE_TOKEN, "usd").await?, BigDecimal::from(0));
+        let err = price_in(PRICED_TOKEN, "2").await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::TokenZeroPriceError);
+
+        // The failures of the price provider are reported as such.
+        let err = price_in(FAILING_TOKEN, "usd").await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::ExternalApiError);
+        let err = price_in(TIMEOUT_TOKEN, "eur").await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::ExternalApiError);
+
+        // The failed prices aren't cached, the recovered provider is requested again.
+        info.set_price(TIMEOUT_TOKEN, 3);
+        assert_eq!(price(TIMEOUT_TOKEN, "eur").await?, BigDecimal::from(6));
+
+        Ok(())
+    }
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn currency_price_cache() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+        let prices = vec![
+            (TokenLike::Id(TokenId(1)), 10_u64.into()),
+            (TokenLike::Id(TokenId(2)), 5_u64.into()),
+        ];
+        let clock = MockClock::default();
+        let data = ApiTokenData::with_clock(
+            &cfg.config,
+            cfg.pool.clone(),
+            TokenDBCache::new(cfg.config.api.token_config.invalidate_token_cache_period()),
+            dummy_fee_ticker(&prices, None),
+            dummy_fiat_rates(&[]),
+            &ConfigReloader::from_config(&cfg.config),
+            Arc::new(clock.clone()),
+        );
+
+        let token = TokenLike::Id(TokenId(1));
+        let price = data
+            .token_price_in(token.clone(), "2")
+            .await
+            .map_err(|err| anyhow::anyhow!(err.message))?;
+        assert_eq!(price, BigDecimal::from(2));
+        let cache_key = (token.to_lowercase(), "2".to_string());
+        assert_eq!(data.prices.get(&cache_key), Some(price));
+        // The prices in the different currencies are cached separately.
+        assert_eq!(
+            data.prices.get(&(token.to_lowercase(), "usd".to_string())),
+            None
+        );
+
+        clock.advance(cfg.config.api.token_config.price_cache_ttl());
+        assert_eq!(data.prices.get(&cache_key), None);
+
+        Ok(())
+    }
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn reloaded_min_market_volume() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        let config_reloader = ConfigReloader::from_config(&cfg.config);
+        let data = ApiTokenData::new(
+            &cfg.config,
+            cfg.pool.clone(),
+            TokenDBCache::new(cfg.config.api.token_config.invalidate_token_cache_period()),
+            dummy_fee_ticker(&[], None),
+            dummy_fiat_rates(&[]),
+            &config_reloader,
+        );
+        assert_eq!(
+            data.min_market_volume(),
+            Ratio::from(BigUint::from_f64(cfg.config.ticker.liquidity_volume).unwrap())
+        );
+
+        config_reloader.update(ReloadableConfig {
+            liquidity_volume: cfg.config.ticker.liquidity_volume + 1000.0,
+            ..config_reloader.current()
+        });
+        assert_eq!(
+            data.min_market_volume(),
+            Ratio::from(BigUint::from_f64(cfg.config.ticker.liquidity_volume + 1000.0).unwrap())
+        );
+
+        // The invalid value doesn't panic the request, the initial one is used instead.
+        config_reloader.update(ReloadableConfig {
+            liquidity_volume: f64::NAN,
+            ..config_reloader.current()
+        });
+        assert_eq!(
+            data.min_market_volume(),
+            Ratio::from(BigUint::from_f64(cfg.config.ticker.liquidity_volume).unwrap())
+        );
+
+        Ok(())
+    }
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn available_currencies() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        // PHNX has a USD price, GNT doesn't.
+        let prices = vec![
+            (TokenLike::Id(TokenId(0)), 1_u64.into()),
+            (TokenLike::Id(TokenId(1)), 10_u64.into()),
+        ];
+        let fee_ticker = dummy_fee_ticker(&prices, None);
+        let fiat_rates = dummy_fiat_rates(&[]);
+
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+        let (client, server) = cfg.start_server(
+            move |cfg| {
+                api_scope(
+                    &cfg.config,
+                    cfg.pool.clone(),
+                    TokenDBCache::new(cfg.config.api.token_config.invalidate_token_cache_period()),
+                    fee_ticker.clone(),
+                    fiat_rates.clone(),
+                    &ConfigReloader::from_config(&cfg.config),
+                )
+            },
+            Some(shared_data),
+        );
+
+        let response = client
+            .token_available_currencies(&TokenLike::Id(TokenId(1)))
+            .await?;
+        let currencies: AvailableCurrencies = deserialize_response_result(response)?;
+        assert_eq!(currencies.token_id, TokenId(1));
+        assert_eq!(
+            currencies.currencies,
+            vec!["usd".to_string(), "0".to_string()]
+        );
+
+        let response = client
+            .token_available_currencies(&TokenLike::Id(TokenId(16)))
+            .await?;
+        let currencies: AvailableCurrencies = deserialize_response_result(response)?;
+        assert_eq!(currencies.token_id, TokenId(16));
+        assert!(currencies.currencies.is_empty());
+
+        server.stop().await;
+        Ok(())
+    }
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn price_history() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let start = Utc.timestamp(1_600_000_000, 0);
+        {
+            let mut storage = cfg.pool.access_storage().await?;
+            for (offset_secs, usd_price) in &[(0, 1u32), (600, 3), (3600, 5)] {
+                storage
+                    .tokens_schema()
+                    .store_ticker_price_snapshot(
+                        TokenId(1),
+                        zksync_types::TokenPrice {
+                            usd_price: Ratio::from_integer(BigUint::from(*usd_price)),
+                            last_updated: start + chrono::Duration::seconds(*offset_secs),
+                        },
+                    )
+                    .await?;
+            }
+        }
+
+        let fee_ticker = dummy_fee_ticker(&[], None);
+        let fiat_rates = dummy_fiat_rates(&[]);
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+        let (client, server) = cfg.start_server(
+            move |cfg| {
+                api_scope(
+                    &cfg.config,
+                    cfg.pool.clone(),
+                    TokenDBCache::new(cfg.config.api.token_config.invalidate_token_cache_period()),
+                    fee_ticker.clone(),
+                    fiat_rates.clone(),
+                    &ConfigReloader::from_config(&cfg.config),
+                )
+            },
+            Some(shared_data),
+        );
+
+        let token_like = TokenLike::Id(TokenId(1));
+        let query = PriceHistoryQuery {
+            from: Some(start),
+            to: Some(start + chrono::Duration::hours(2)),
+            resolution: Some(3600),
+        };
+        let response = client.token_price_history(&token_like, &query).await?;
+        let history: TokenPriceHistory = deserialize_response_result(response)?;
+        let expected_history = TokenPriceHistory {
+            token_id: TokenId(1),
+            token_symbol: String::from("PHNX"),
+            resolution: 3600,
+            points: vec![
+                TokenPricePoint {
+                    time: start,
+                    price: BigDecimal::from(2),
+                },
+                TokenPricePoint {
+                    time: start + chrono::Duration::hours(1),
+                    price: BigDecimal::from(5),
+                },
+            ],
+        };
+        assert_eq!(history, expected_history);
+
+        // Too many intervals are requested.
+        let query = PriceHistoryQuery {
+            resolution: Some(1),
+            ..query
+        };
+        let response = client.token_price_history(&token_like, &query).await?;
+        assert!(response.error.is_some());
+
+        server.stop().await;
+        Ok(())
+    }
+
+    #[test]
+    fn parse_token_ids_list() {
+        assert_eq!(
+            parse_token_ids("1, 16,1").unwrap(),
+            vec![TokenId(1), TokenId(16), TokenId(1)]
+        );
+        assert!(parse_token_ids("").is_err());
+        assert!(parse_token_ids("1,abc").is_err());
+        assert!(parse_token_ids("1,,2").is_err());
+    }
+
+    /// Checks the token pages on top of the in-memory storage, no database is required.
+    #[tokio::test]
+    async fn token_page_in_memory() -> anyhow::Result<()> {
+        let mut storage = MemoryTokensStorage::new();
+        for (id, symbol, market_volume) in &[(1, "ABC", 10u32), (2, "ABD", 1), (3, "XYZ", 20)] {
+            let token = Token::new(
+                TokenId(*id),
+                Address::from_low_u64_be(*id as u64),
+                symbol,
+                18,
+                TokenKind::ERC20,
+            );
+            storage.store_token(token).await?;
+            let market_volume = TokenMarketVolume {
+                market_volume: Ratio::from_integer(BigUint::from(*market_volume)),
+                last_updated: Utc::now(),
+            };
+            storage
+                .update_token_market_volume(TokenId(*id), market_volume)
+                .await?;
+        }
+        let metadata = TokenMetadata {
+            display_name: Some("Token ABC".to_string()),
+            ..Default::default()
+        };
+        storage.store_token_metadata(TokenId(1), &metadata).await?;
+
+        let cursors = PaginationCursors::new("secret");
+        let query = |filter: TokenFilter| PaginationQuery {
+            from: TokensRequest {
+                token_id: ApiEither::from_str("latest").unwrap(),
+                filter,
+                min_market_volume: Ratio::from_integer(BigUint::from(5u32)),
+                denylisted_tokens: vec![TokenId(3)],
+            },
+            limit: 2,
+            direction: PaginationDirection::Older,
+        };
+
+        // The denylisted token is skipped, the next page is available by the cursor.
+        let page = load_token_page(&mut storage, query(TokenFilter::default()), &cursors)
+            .await
+            .map_err(|err| anyhow::anyhow!(err.message))?;
+        let ids: Vec<TokenId> = page.list.iter().map(|token| token.id).collect();
+        assert_eq!(ids, vec![TokenId(2), TokenId(1)]);
+        assert_eq!(page.pagination.count, 3);
+        assert!(page.pagination.next_cursor.is_some());
+        assert!(!page.list[0].enabled_for_fees);
+        assert!(page.list[1].enabled_for_fees);
+        assert_eq!(page.list[1].display_name.as_deref(), Some("Token ABC"));
+
+        // The next page is loaded by the cursor.
+        let by_cursor = |cursor: String| {
+            cursors
+                .parse_query::<TokenId>(PaginationQueryOrCursor {
+                    from: None,
+                    limit: None,
+                    direction: None,
+                    cursor: Some(cursor),
+                })
+                .map_err(|err| anyhow::anyhow!(err.message))
+        };
+        let cursor = page.pagination.next_cursor.clone().unwrap();
+        let next = by_cursor(cursor.clone())?;
+        assert_eq!(next.limit, 2);
+        assert_eq!(next.direction, PaginationDirection::Older);
+        let next_query = PaginationQuery {
+            from: TokensRequest {
+                token_id: next.from,
+                ..query(TokenFilter::default()).from
+            },
+            limit: next.limit,
+            direction: next.direction,
+        };
+        let next_page = load_token_page(&mut storage, next_query, &cursors)
+            .await
+            .map_err(|err| anyhow::anyhow!(err.message))?;
+        let ids: Vec<TokenId> = next_page.list.iter().map(|token| token.id).collect();
+        assert_eq!(ids, vec![TokenId(0)]);
+        assert_eq!(next_page.pagination.next_cursor, None);
+        // ETH is always enabled for fees.
+        assert!(next_page.list[0].enabled_for_fees);
+
+        // The cursor can't be forged.
+        assert!(by_cursor(cursor.replace('.', "0.")).is_err());
+
+        let filter = TokenFilter {
+            symbol_prefix: Some("ab".to_string()),
+            sort: TokenSortOrder::MarketVolume,
+            ..Default::default()
+        };
+        let page = load_token_page(&mut storage, query(filter), &cursors)
+            .await
+            .map_err(|err| anyhow::anyhow!(err.message))?;
+        let ids: Vec<TokenId> = page.list.iter().map(|token| token.id).collect();
+        assert_eq!(ids, vec![TokenId(1), TokenId(2)]);
+        assert_eq!(page.pagination.next_cursor, None);
+
+        Ok(())
+    }
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn tokens_by_ids() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let fee_ticker = dummy_fee_ticker(&[], None);
+        let fiat_rates = dummy_fiat_rates(&[]);
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+        let (client, server) = cfg.start_server(
+            move |cfg| {
+                api_scope(
+                    &cfg.config,
+                    cfg.pool.clone(),
+                    TokenDBCache::new(cfg.config.api.token_config.invalidate_token_cache_period()),
+                    fee_ticker.clone(),
+                    fiat_rates.clone(),
+                    &ConfigReloader::from_config(&cfg.config),
+                )
+            },
+            Some(shared_data),
+        );
+
+        let ids = [TokenId(16), TokenId(1), TokenId(16), TokenId(40000)];
+        let response = client.tokens_by_ids(&ids).await?;
+        let lookups: Vec<TokenLookup> = deserialize_response_result(response)?;
+
+        let returned_ids: Vec<_> = lookups.iter().map(|lookup| lookup.id).collect();
+        assert_eq!(returned_ids, ids.to_vec());
+        assert_eq!(lookups[0], lookups[2]);
+        assert_eq!(lookups[0].token.as_ref().unwrap().symbol, "GNT");
+        assert_eq!(lookups[1].token.as_ref().unwrap().symbol, "PHNX");
+        assert!(lookups[3].token.is_none());
+        assert!(lookups[3].error.is_some());
+
+        // The pagination is still served when no ids are requested.
+        let query = PaginationQuery {
+            from: ApiEither::from(TokenId(1)),
+            limit: 1,
+            direction: PaginationDirection::Newer,
+        };
+        let response = client.token_pagination(&query).await?;
+        let pagination: Paginated<ApiToken, TokenId> = deserialize_response_result(response)?;
+        assert_eq!(pagination.list.len(), 1);
+
+        server.stop().await;
+        Ok(())
+    }
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn denylisted_tokens() -> anyhow::Result<()> {
+        let mut cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+        let denylisted = cfg
+            .pool
+            .access_storage()
+            .await?
+            .tokens_schema()
+            .get_token(TokenLike::Id(TokenId(1)))
+            .await?
+            .unwrap();
+        cfg.config.token_handler.denylisted_tokens = vec![denylisted.address];
+
+        let fee_ticker = dummy_fee_ticker(&[], None);
+        let fiat_rates = dummy_fiat_rates(&[]);
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+        let (client, server) = cfg.start_server(
+            move |cfg| {
+                api_scope(
+                    &cfg.config,
+                    cfg.pool.clone(),
+                    TokenDBCache::new(cfg.config.api.token_config.invalidate_token_cache_period()),
+                    fee_ticker.clone(),
+                    fiat_rates.clone(),
+                    &ConfigReloader::from_config(&cfg.config),
+                )
+            },
+            Some(shared_data),
+        );
+
+        // The denylisted token is reported as not found.
+        let response = client.token_by_id(&TokenLike::Id(TokenId(1))).await?;
+        assert!(response.error.is_some());
+        let response = client.tokens_by_ids(&[TokenId(1), TokenId(16)]).await?;
+        let lookups: Vec<TokenLookup> = deserialize_response_result(response)?;
+        assert!(lookups[0].token.is_none());
+        assert!(lookups[1].token.is_some());
+
+        let query = PaginationQuery {
+            from: ApiEither::from(TokenId(0)),
+            limit: 3,
+            direction: PaginationDirection::Newer,
+        };
+        let response = client.token_pagination(&query).await?;
+        let pagination: Paginated<ApiToken, TokenId> = deserialize_response_result(response)?;
+        assert_eq!(pagination.list.len(), 3);
+        assert!(pagination.list.iter().all(|token| token.id != TokenId(1)));
+
+        server.stop().await;
+        Ok(())
+    }
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn fee_acceptable_batch() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        // PHNX has enough market volume, GNT doesn't, and the token without
+        // the market volume data is stored separately.
+        let no_volume_token = Token::new(
+            TokenId(17),
+            Address::from_low_u64_be(17),
+            "NOVOL",
+            18,
+            TokenKind::ERC20,
+        );
+        {
+            let mut storage = cfg.pool.access_storage().await?;
+            storage
+                .tokens_schema()
+                .store_or_update_token(no_volume_token)
+                .await?;
+            for (token_id, volume) in &[(TokenId(1), 1_000_u32), (TokenId(16), 1)] {
+                storage
+                    .tokens_schema()
+                    .update_token_market_volume(
+                        *token_id,
+                        TokenMarketVolume {
+                            market_volume: Ratio::from(BigUint::from(*volume)),
+                            last_updated: chrono::Utc::now(),
+                        },
+                    )
+                    .await?;
+            }
+        }
+
+        // Only PHNX has a live price.
+        let prices = vec![(TokenLike::Id(TokenId(1)), 10_u64.into())];
+        let fee_ticker = dummy_fee_ticker(&prices, None);
+        let fiat_rates = dummy_fiat_rates(&[]);
+
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+        let (client, server) = cfg.start_server(
+            move |cfg| {
+                api_scope(
+                    &cfg.config,
+                    cfg.pool.clone(),
+                    TokenDBCache::new(cfg.config.api.token_config.invalidate_token_cache_period()),
+                    fee_ticker.clone(),
+                    fiat_rates.clone(),
+                    &ConfigReloader::from_config(&cfg.config),
+                )
+            },
+            Some(shared_data),
+        );
+
+        let tokens = vec![
+            TokenLike::Id(TokenId(1)),
+            TokenLike::Id(TokenId(16)),
+            TokenLike::Id(TokenId(17)),
+            TokenLike::Id(TokenId(0)),
+            TokenLike::Symbol(String::from("UNKNOWN")),
+        ];
+        let response = client.fee_acceptable_batch(tokens.clone()).await?;
+        let acceptability: Vec<FeeAcceptability> = deserialize_response_result(response)?;
+        let expected = vec![
+            FeeAcceptability::acceptable(tokens[0].clone()),
+            FeeAcceptability::unacceptable(
+                tokens[1].clone(),
+                FeeUnacceptableReason::BelowThreshold,
+            ),
+            FeeAcceptability::unacceptable(tokens[2].clone(), FeeUnacceptableReason::NoVolumeData),
+            // ETH doesn't require the market volume, but it has no price in this test.
+            FeeAcceptability::unacceptable(tokens[3].clone(), FeeUnacceptableReason::NoLivePrice),
+            FeeAcceptability::unacceptable(tokens[4].clone(), FeeUnacceptableReason::NotFound),
+        ];
+        assert_eq!(acceptability, expected);
+
+        let too_many_tokens = vec![TokenLike::Id(TokenId(1)); MAX_TOKENS_BATCH_SIZE + 1];
+        let response = client.fee_acceptable_batch(too_many_tokens).await?;
+        assert!(response.error.is_some());
+
+        server.stop().await;
+        Ok(())
+    }
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn fee_tokens_and_price_lookups() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+        // PHNX has enough market volume, GNT doesn't.
+        {
+            let mut storage = cfg.pool.access_storage().await?;
+            for (token_id, volume) in &[(TokenId(1), 1_000_u32), (TokenId(16), 1)] {
+                storage
+                    .tokens_schema()
+                    .update_token_market_volume(
+                        *token_id,
+                        TokenMarketVolume {
+                            market_volume: Ratio::from(BigUint::from(*volume)),
+                            last_updated: chrono::Utc::now(),
+                        },
+                    )
+                    .await?;
+            }
+        }
+
+        let prices = vec![(TokenLike::Id(TokenId(1)), 10_u64.into())];
+        let data = ApiTokenData::new(
+            &cfg.config,
+            cfg.pool.clone(),
+            TokenDBCache::new(cfg.config.api.token_config.invalidate_token_cache_period()),
+            dummy_fee_ticker(&prices, None),
+            dummy_fiat_rates(&[]),
+            &ConfigReloader::from_config(&cfg.config),
+        );
+
+        // ETH is always enabled for fees.
+        let fee_tokens = data
+            .fee_tokens()
+            .await
+            .map_err(|err| anyhow::anyhow!(err.message))?;
+        let fee_token_ids: Vec<_> = fee_tokens.iter().map(|token| token.id).collect();
+        assert_eq!(fee_token_ids, vec![TokenId(0), TokenId(1)]);
+        assert!(fee_tokens.iter().all(|token| token.enabled_for_fees));
+
+        let tokens = vec![
+            TokenLike::Id(TokenId(1)),
+            TokenLike::Symbol(String::from("UNKNOWN")),
+            TokenLike::Id(TokenId(1)),
+        ];
+        let lookups = data
+            .token_price_lookups(tokens.clone())
+            .await
+            .map_err(|err| anyhow::anyhow!(err.message))?;
+        let returned_tokens: Vec<_> = lookups.iter().map(|lookup| lookup.token.clone()).collect();
+        assert_eq!(returned_tokens, tokens);
+        assert_eq!(lookups[0].price, Some(BigDecimal::from(10)));
+        assert_eq!(lookups[0], lookups[2]);
+        assert!(lookups[1].price.is_none());
+        assert!(lookups[1].error.is_some());
+
+        let too_many_tokens = vec![TokenLike::Id(TokenId(1)); MAX_TOKENS_BATCH_SIZE + 1];
+        assert!(data.token_price_lookups(too_many_tokens).await.is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_token_includes_list() {
+        assert_eq!(
+            parse_token_includes(None).unwrap(),
+            TokenIncludes::default()
+        );
+        assert_eq!(
+            parse_token_includes(Some("volume, price")).unwrap(),
+            TokenIncludes {
+                price: true,
+                volume: true
+            }
+        );
+        assert!(parse_token_includes(Some("price,supply")).is_err());
+    }
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn token_includes() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+        {
+            let mut storage = cfg.pool.access_storage().await?;
+            storage
+                .tokens_schema()
+                .update_token_market_volume(
+                    TokenId(1),
+                    TokenMarketVolume {
+                        market_volume: Ratio::from(BigUint::from(1_000_u32)),
+                        last_updated: chrono::Utc::now(),
+                    },
+                )
+                .await?;
+        }
+
+        let prices = vec![(TokenLike::Id(TokenId(1)), 10_u64.into())];
+        let fee_ticker = dummy_fee_ticker(&prices, None);
+        let fiat_rates = dummy_fiat_rates(&[]);
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+        let (client, server) = cfg.start_server(
+            move |cfg| {
+                api_scope(
+                    &cfg.config,
+                    cfg.pool.clone(),
+                    TokenDBCache::new(cfg.config.api.token_config.invalidate_token_cache_period()),
+                    fee_ticker.clone(),
+                    fiat_rates.clone(),
+                    &ConfigReloader::from_config(&cfg.config),
+                )
+            },
+            Some(shared_data),
+        );
+
+        let token_like = TokenLike::Id(TokenId(1));
+        let response = client.token_by_id(&token_like).await?;
+        let token: ApiToken = deserialize_response_result(response)?;
+        assert_eq!(token.usd_price, None);
+        assert_eq!(token.market_volume, None);
+
+        let includes = TokenIncludeQuery {
+            include: Some(String::from("price,volume")),
+        };
+        let response = client
+            .token_by_id_with_includes(&token_like, &includes)
+            .await?;
+        let token: ApiToken = deserialize_response_result(response)?;
+        assert_eq!(token.usd_price, Some(BigDecimal::from(10)));
+        assert_eq!(token.market_volume, Some(BigDecimal::from(1000)));
+
+        let query = PaginationQuery {
+            from: ApiEither::from(TokenId(1)),
+            limit: 1,
+            direction: PaginationDirection::Newer,
+        };
+        let includes = TokenIncludeQuery {
+            include: Some(String::from("volume")),
+        };
+        let response = client
+            .token_pagination_filtered(&query, &Default::default(), &includes)
+            .await?;
+        let pagination: Paginated<ApiToken, TokenId> = deserialize_response_result(response)?;
+        assert_eq!(pagination.list[0].usd_price, None);
+        assert_eq!(
+            pagination.list[0].market_volume,
+            Some(BigDecimal::from(1000))
+        );
+
+        let includes = TokenIncludeQuery {
+            include: Some(String::from("supply")),
+        };
+        let response = client
+            .token_by_id_with_includes(&token_like, &includes)
+            .await?;
+        assert!(response.error.is_some());
+
+        server.stop().await;
+        Ok(())
+    }
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn tokens_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let prices = vec![
+            (TokenLike::Id(TokenId(1)), 10_u64.into()),
+            (TokenLike::Symbol(String::from("PHNX")), 10_u64.into()),
+            (TokenLike::Id(TokenId(15)), 10_500_u64.into()),
+            (Address::default().into(), 1_u64.into()),
+        ];
+
+        let fee_ticker = dummy_fee_ticker(&prices, None);
+        // There is no GBP rate.
+        let fiat_rates = dummy_fiat_rates(&[
+            (FiatCurrency::Eur, BigDecimal::from(2)),
+            (FiatCurrency::Jpy, BigDecimal::from(100)),
+        ]);
+
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+        let (client, server) = cfg.start_server(
+            move |cfg| {
+                api_scope(
+                    &cfg.config,
+                    cfg.pool.clone(),
+                    TokenDBCache::new(cfg.config.api.token_config.invalidate_token_cache_period()),
+                    fee_ticker.clone(),
+                    fiat_rates.clone(),
+                    &ConfigReloader::from_config(&cfg.config),
+                )
+            },
+            Some(shared_data),
+        );
+
+        let token_like = TokenLike::Id(TokenId(1));
+        let response = client.token_by_id(&token_like).await?;
+        let api_token: ApiToken = deserialize_response_result(response)?;
+
+        let expected_token = {
+            let mut storage = cfg.pool.access_storage().await?;
+            storage
                 .tokens_schema()
                 .get_token(token_like)
                 .await?
@@ -400,10 +2029,16 @@ mod tests {
         let response = client.token_pagination(&query).await?;
         let pagination: Paginated<ApiToken, TokenId> = deserialize_response_result(response)?;
 
+        let cursors = PaginationCursors::from_config(&cfg.config.api.rest);
         let expected_pagination = {
             let mut storage = cfg.pool.access_storage().await?;
+            let query = PaginationQuery {
+                from: ApiEither::from(TokenId(15)),
+                limit: 2,
+                direction: PaginationDirection::Older,
+            };
             let paginated_tokens: Paginated<Token, TokenId> = storage
-                .paginate_checked(&query)
+                .paginate_with_cursor(query, &cursors)
                 .await
                 .map_err(|err| anyhow::anyhow!(err.message))?;
             let mut list = Vec::new();
@@ -415,16 +2050,109 @@ mod tests {
                     enabled_for_fees,
                 ));
             }
-            Paginated::new(
+            Paginated {
                 list,
-                paginated_tokens.pagination.from,
-                paginated_tokens.pagination.limit,
-                paginated_tokens.pagination.direction,
-                paginated_tokens.pagination.count,
-            )
+                pagination: paginated_tokens.pagination,
+            }
         };
         assert_eq!(pagination, expected_pagination);
 
+        // The next page is loaded by the cursor.
+        let cursor = pagination.pagination.next_cursor.unwrap();
+        let response = client.token_pagination_by_cursor(&cursor).await?;
+        let next_pagination: Paginated<ApiToken, TokenId> = deserialize_response_result(response)?;
+        assert_eq!(next_pagination.pagination.limit, 2);
+        assert_eq!(
+            next_pagination.pagination.direction,
+            PaginationDirection::Older
+        );
+        assert!(next_pagination.list[0].id < pagination.list[1].id);
+
+        // The cursor can't be forged.
+        let forged_cursor = cursor.replace('.', "0.");
+        let response = client.token_pagination_by_cursor(&forged_cursor).await?;
+        assert!(response.error.is_some());
+
+        // The typed client loads all the pages.
+        let typed_client = TypedClient::from(client.clone());
+        let all_tokens = typed_client.all_tokens().await?;
+        assert_eq!(all_tokens.len(), pagination.pagination.count as usize);
+        assert!(all_tokens.windows(2).all(|pair| pair[0].id < pair[1].id));
+        let err = typed_client
+            .token_pagination_by_cursor(&forged_cursor)
+            .await
+            .unwrap_err();
+        assert!(err.api_error_code().is_some());
+
+        let token_like = TokenLike::Id(TokenId(0));
+        let query = PaginationQuery {
+            from: ApiEither::from_str("latest").unwrap(),
+            limit: 2,
+            direction: PaginationDirection::Older,
+        };
+        let response = client.token_txs(&query, &token_like).await?;
+        let txs: Paginated<Transaction, TxHashSerializeWrapper> =
+            deserialize_response_result(response)?;
+        let expected_txs = {
+            let mut storage = cfg.pool.access_storage().await?;
+            let query = PaginationQuery {
+                from: TokenTxsRequest {
+                    token: TokenId(0),
+                    tx_hash: query.from,
+                },
+                limit: query.limit,
+                direction: query.direction,
+            };
+            storage
+                .paginate_with_cursor(query, &cursors)
+                .await
+                .map_err(|err| anyhow::anyhow!(err.message))?
+        };
+        let tx_hashes = |txs: &Paginated<Transaction, TxHashSerializeWrapper>| {
+            txs.list.iter().map(|tx| tx.tx_hash).collect::<Vec<_>>()
+        };
+        assert_eq!(tx_hashes(&txs), tx_hashes(&expected_txs));
+        assert_eq!(txs.pagination, expected_txs.pagination);
+        assert_eq!(txs.list.len(), 2);
+        assert!(txs.pagination.count >= 2);
+
+        let query = PaginationQuery {
+            from: ApiEither::from(TokenId(0)),
+            limit: 100,
+            direction: PaginationDirection::Newer,
+        };
+        let filter = TokenFilter {
+            enabled_for_fees: Some(true),
+            ..Default::default()
+        };
+        let response = client
+            .token_pagination_filtered(&query, &filter, &Default::default())
+            .await?;
+        let pagination: Paginated<ApiToken, TokenId> = deserialize_response_result(response)?;
+        assert!(!pagination.list.is_empty());
+        assert!(pagination.list.iter().all(|token| token.enabled_for_fees));
+
+        // Starting from the latest token covers all of them regardless of the sort order.
+        let query = PaginationQuery {
+            from: ApiEither::from_str("latest").unwrap(),
+            limit: 100,
+            direction: PaginationDirection::Older,
+        };
+        let filter = TokenFilter {
+            symbol_prefix: Some(String::from("ph")),
+            sort: TokenSortOrder::MarketVolume,
+            ..Default::default()
+        };
+        let response = client
+            .token_pagination_filtered(&query, &filter, &Default::default())
+            .await?;
+        let pagination: Paginated<ApiToken, TokenId> = deserialize_response_result(response)?;
+        assert!(pagination
+            .list
+            .iter()
+            .all(|token| token.symbol.to_lowercase().starts_with("ph")));
+        assert!(pagination.list.iter().any(|token| token.symbol == "PHNX"));
+
         let token_like = TokenLike::Symbol(String::from("PHNX"));
         let token = {
             let mut storage = cfg.pool.access_storage().await?;
@@ -440,6 +2168,7 @@ mod tests {
             price_in: String::from("15"),
             decimals: token.decimals,
             price: BigDecimal::from_u32(10).unwrap() / BigDecimal::from_u32(10500).unwrap(),
+            smoothing_window: None,
         };
 
         let response = client.token_price(&token_like, "15").await?;
@@ -456,6 +2185,34 @@ mod tests {
         let response = client.token_price(&token_like, "333").await?;
         assert!(response.error.is_some());
 
+        expected_token_price.price_in = String::from("eur");
+        expected_token_price.price = BigDecimal::from_u32(20).unwrap();
+        let response = client.token_price(&token_like, "eur").await?;
+        let price_in_eur: TokenPrice = deserialize_response_result(response)?;
+        assert_eq!(price_in_eur, expected_token_price);
+
+        let response = client.token_price(&token_like, "gbp").await?;
+        assert!(response.error.is_some());
+        let response = client.token_price(&token_like, "cad").await?;
+        assert!(response.error.is_some());
+
+        // The only stored price point is the spot price.
+        let response = client
+            .token_price_with_smoothing(&token_like, "usd", 1)
+            .await?;
+        let smoothed_price: TokenPrice = deserialize_response_result(response)?;
+        assert_eq!(smoothed_price.price, expected_token_price.price);
+        assert_eq!(smoothed_price.smoothing_window, Some(1));
+
+        let response = client
+            .token_price_with_smoothing(&token_like, "usd", PRICE_HISTORY_CAPACITY + 1)
+            .await?;
+        assert!(response.error.is_some());
+        let response = client
+            .token_price_with_smoothing(&token_like, "15", 1)
+            .await?;
+        assert!(response.error.is_some());
+
         let nft_id = TokenId(65542);
         let response = client.nft_by_id(nft_id).await?;
         let nft: ApiNFT = deserialize_response_result(response)?;
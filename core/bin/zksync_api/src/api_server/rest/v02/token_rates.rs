@@ -0,0 +1,196 @@
+//! Cached USD prices of the tokens and the cross rates between them.
+//!
+//! The rate of the token pair is the ratio of the USD prices of the tokens, both prices are
+//! requested from the fee ticker at once if they aren't cached. The rates are cached by the
+//! pair, and the rate of the inverse pair is served from the same entry.
+//!
+//! The tokens are resolved by the caller, so that the prices and their history are kept
+//! by the token id regardless of the form the token is requested in.
+
+// Built-in uses
+use std::sync::Arc;
+use std::time::Duration;
+
+// External uses
+use bigdecimal::{BigDecimal, Zero};
+use num::{rational::Ratio, BigUint};
+
+// Workspace uses
+use zksync_types::{TokenId, TokenLike};
+use zksync_utils::big_decimal_to_ratio;
+
+// Local uses
+use crate::{
+    fee_ticker::{FeeTicker, PriceError, TokenPriceRequestType},
+    utils::{clock::Clock, price_history::PriceHistory, ttl_cache::TtlCache},
+};
+
+#[derive(Clone)]
+pub(crate) struct TokenRates {
+    fee_ticker: FeeTicker,
+    pub(super) usd_prices: TtlCache<TokenId, BigDecimal>,
+    pub(super) usd_price_history: PriceHistory<TokenId>,
+    /// Rates of the pairs by the base and the quote token.
+    rates: TtlCache<(TokenId, TokenId), BigDecimal>,
+}
+
+impl TokenRates {
+    pub(crate) fn new(
+        fee_ticker: FeeTicker,
+        ttl: Duration,
+        history_capacity: usize,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        Self {
+            fee_ticker,
+            usd_prices: TtlCache::with_clock(ttl, clock.clone()),
+            usd_price_history: PriceHistory::new(history_capacity),
+            rates: TtlCache::with_clock(ttl, clock),
+        }
+    }
+
+    pub(crate) async fn usd_price(&self, token: TokenId) -> Result<BigDecimal, PriceError> {
+        if let Some(price) = self.usd_prices.get(&token) {
+            return Ok(price);
+        }
+
+        let price = self
+            .fee_ticker
+            .get_token_price(TokenLike::Id(token), TokenPriceRequestType::USDForOneToken)
+            .await?;
+        self.usd_prices.insert(token, price.clone());
+        self.usd_price_history.push(token, price.clone());
+        Ok(price)
+    }
+
+    /// Returns the USD prices of the list of tokens, in the order of the request.
+    /// The prices missing in the cache are requested from the fee ticker at once.
+    pub(crate) async fn usd_prices(
+        &self,
+        tokens: Vec<TokenId>,
+    ) -> Vec<Result<BigDecimal, PriceError>> {
+        let mut prices: Vec<Option<Result<BigDecimal, PriceError>>> = tokens
+            .iter()
+            .map(|token| self.usd_prices.get(token).map(Ok))
+            .collect();
+
+        let missing: Vec<usize> = (0..prices.len()).filter(|&i| prices[i].is_none()).collect();
+        if !missing.is_empty() {
+            let missing_tokens = missing.iter().map(|&i| TokenLike::Id(tokens[i])).collect();
+            let loaded = self
+                .fee_ticker
+                .get_token_prices(missing_tokens, TokenPriceRequestType::USDForOneToken)
+                .await;
+            for (i, price) in missing.into_iter().zip(loaded) {
+                if let Ok(price) = &price {
+                    self.usd_prices.insert(tokens[i], price.clone());
+                    self.usd_price_history.push(tokens[i], price.clone());
+                }
+                prices[i] = Some(price);
+            }
+        }
+
+        prices
+            .into_iter()
+            .map(|price| price.expect("all the missing prices are loaded"))
+            .collect()
+    }
+
+    /// Returns the moving average of the token USD price over the `window` most recent
+    /// price points, `None` if the window is out of range.
+    pub(crate) async fn smoothed_usd_price(
+        &self,
+        token: TokenId,
+        window: usize,
+    ) -> Result<Option<BigDecimal>, PriceError> {
+        // Make sure that the latest price point is in the history.
+        self.usd_price(token).await?;
+        Ok(self.usd_price_history.simple_moving_average(&token, window))
+    }
+
+    /// Returns the price of one base token in the quote tokens, `None` if the price
+    /// of the quote token is zero.
+    pub(crate) async fn rate(
+        &self,
+        base_token: TokenId,
+        quote_token: TokenId,
+    ) -> Result<Option<BigDecimal>, PriceError> {
+        if base_token == quote_token {
+            return Ok(Some(BigDecimal::from(1)));
+        }
+        if let Some(rate) = self.rates.get(&(base_token, quote_token)) {
+            return Ok(Some(rate));
+        }
+        // The zero rates aren't cached, so the inverse rate is always defined.
+        if let Some(inverse_rate) = self.rates.get(&(quote_token, base_token)) {
+            return Ok(Some(BigDecimal::from(1) / inverse_rate));
+        }
+
+        let mut prices = self
+            .usd_prices(vec![base_token, quote_token])
+            .await
+            .into_iter();
+        let base_price = prices.next().expect("price of the base token")?;
+        let quote_price = prices.next().expect("price of the quote token")?;
+        if quote_price.is_zero() {
+            return Ok(None);
+        }
+
+        let rate = base_price / quote_price;
+        if !rate.is_zero() {
+            self.rates.insert((base_token, quote_token), rate.clone());
+        }
+        Ok(Some(rate))
+    }
+}
+
+/// Converts the amount of the base token to the quote token with the rate of the pair,
+/// the amounts are in the smallest units of the tokens. The result is rounded down.
+pub(crate) fn convert_amount(
+    amount: &BigUint,
+    rate: &BigDecimal,
+    base_decimals: u8,
+    quote_decimals: u8,
+) -> anyhow::Result<BigUint> {
+    let rate = big_decimal_to_ratio(rate)?;
+    let scale = |decimals: u8| BigUint::from(10u32).pow(u32::from(decimals));
+    let converted = Ratio::from(amount.clone())
+        * rate
+        * Ratio::new(scale(quote_decimals), scale(base_decimals));
+    Ok(converted.to_integer())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn amount_conversion() {
+        let convert = |amount: u64, rate: &str, base_decimals, quote_decimals| {
+            convert_amount(
+                &BigUint::from(amount),
+                &BigDecimal::from_str(rate).unwrap(),
+                base_decimals,
+                quote_decimals,
+            )
+            .unwrap()
+        };
+
+        // 1.5 of the base token with 18 decimals at the rate of 2000 is 3000 of the quote
+        // token with 6 decimals.
+        assert_eq!(
+            convert(1_500_000_000_000_000_000, "2000", 18, 6),
+            BigUint::from(3_000_000_000u64)
+        );
+        // 3000 of the quote token converted back with the inverse rate.
+        assert_eq!(
+            convert(3_000_000_000, "0.0005", 6, 18),
+            BigUint::from(1_500_000_000_000_000_000u64)
+        );
+        // The result is rounded down.
+        assert_eq!(convert(1, "0.5", 0, 0), BigUint::from(0u32));
+        assert_eq!(convert(3, "0.5", 0, 0), BigUint::from(1u32));
+        assert_eq!(convert(0, "2000", 18, 6), BigUint::from(0u32));
+    }
+}
@@ -1,37 +1,129 @@
 //! Transactions part of API implementation.
+//!
+//! Besides the receipts, the clients can wait for the status of the transaction
+//! with the long-polling `wait` endpoint. The request is woken up by the events
+//! of the transaction, so the new status is returned without polling the receipt.
+//!
+//! The submission endpoints accept the `Idempotency-Key` header, see the `idempotency` module.
+//!
+//! The finality of the withdrawal is tracked through the prove and execute pipeline, with the
+//! time of the finalization estimated by the recent cadence of the confirmed L1 operations.
 
 // Built-in uses
-use std::time::Instant;
+use std::time::{Duration, Instant};
 // External uses
+use actix::Addr;
 use actix_web::{
     web::{self, Json},
-    Scope,
+    HttpRequest, Scope,
 };
+use chrono::{DateTime, Utc};
+use futures::channel::oneshot;
 
 // Workspace uses
 use zksync_api_types::{
     v02::transaction::{
-        ApiTxBatch, IncomingTxBatch, L1Receipt, L1Transaction, Receipt, SubmitBatchResponse,
-        Toggle2FA, Toggle2FAResponse, Transaction, TransactionData, TxData, TxHashSerializeWrapper,
-        TxInBlockStatus,
+        ApiTxBatch, IncomingTxBatch, L1Receipt, L1Transaction, L2Transaction, Receipt,
+        SubmitBatchResponse, Toggle2FA, Toggle2FAResponse, Transaction, TransactionData, TxData,
+        TxHashSerializeWrapper, TxInBlockStatus, TxStatusWaitQuery, WithdrawalFinality,
+        WithdrawalStage,
     },
     TxWithSignature,
 };
-use zksync_types::{tx::TxHash, EthBlockId};
+use zksync_types::{
+    aggregated_operations::AggregatedActionType, tx::TxHash, BlockNumber, EthBlockId,
+};
 
 // Local uses
-use super::{error::Error, response::ApiResult};
+use super::{
+    error::{Error, InvalidDataError},
+    event_stream::{EventStreamManager, WaitForTx},
+    idempotency::IdempotencyKeys,
+    response::ApiResult,
+};
 use crate::api_server::tx_sender::{SubmitError, TxSender};
 
+/// How long the status of the transaction is waited for if the client doesn't set the timeout.
+const DEFAULT_WAIT_TIMEOUT: Duration = Duration::from_secs(30);
+/// The longest allowed wait, so that the requests are not dropped by the proxies.
+const MAX_WAIT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Number of the latest confirmed operations of each stage the cadence of the stage is estimated by.
+const CADENCE_WINDOW: u32 = 20;
+/// Stages of the L1 pipeline the block passes before the withdrawals of the block are finalized.
+const PIPELINE_STAGES: &[AggregatedActionType] = &[
+    AggregatedActionType::CommitBlocks,
+    AggregatedActionType::PublishProofBlocksOnchain,
+    AggregatedActionType::ExecuteBlocks,
+];
+
+/// Recent cadence of the stage of the L1 pipeline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct StageCadence {
+    last_block: BlockNumber,
+    last_at: DateTime<Utc>,
+    /// Average time between the blocks passing the stage.
+    block_interval: chrono::Duration,
+}
+
+impl StageCadence {
+    /// Estimates the cadence by the last blocks of the recent operations of the stage and their
+    /// creation time, the latest operation first. `None` if the operations don't cover several blocks.
+    fn new(operations: &[(BlockNumber, DateTime<Utc>)]) -> Option<Self> {
+        let (last_block, last_at) = *operations.first()?;
+        let (first_block, first_at) = *operations.last()?;
+        let blocks = last_block
+            .checked_sub(*first_block)
+            .filter(|blocks| *blocks > 0)?;
+        Some(Self {
+            last_block,
+            last_at,
+            block_interval: (last_at - first_at) / blocks as i32,
+        })
+    }
+
+    /// Estimated time when the block passes the stage.
+    fn estimated_at(&self, block_number: BlockNumber) -> DateTime<Utc> {
+        let blocks_left = block_number.saturating_sub(*self.last_block);
+        self.last_at + self.block_interval * blocks_left as i32
+    }
+}
+
+/// Estimates when the block passes all the stages, given the last block which passed each stage
+/// and the cadence of the stage. The stalled pipeline isn't expected to pass the block earlier than `now`.
+fn estimate_finalized_at(
+    block_number: BlockNumber,
+    stages: &[(BlockNumber, Option<StageCadence>)],
+    now: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    let mut estimate = now;
+    for (last_block, cadence) in stages {
+        if block_number > *last_block {
+            estimate = estimate.max(cadence.as_ref()?.estimated_at(block_number));
+        }
+    }
+    Some(estimate)
+}
+
 /// Shared data between `api/v0.2/transactions` endpoints.
 #[derive(Clone)]
 struct ApiTransactionData {
     tx_sender: TxSender,
+    event_stream: Addr<EventStreamManager>,
+    idempotency_keys: IdempotencyKeys,
 }
 
 impl ApiTransactionData {
-    fn new(tx_sender: TxSender) -> Self {
-        Self { tx_sender }
+    fn new(
+        tx_sender: TxSender,
+        event_stream: Addr<EventStreamManager>,
+        idempotency_keys: IdempotencyKeys,
+    ) -> Self {
+        Self {
+            tx_sender,
+            event_stream,
+            idempotency_keys,
+        }
     }
 
     async fn tx_status(&self, tx_hash: TxHash) -> Result<Option<Receipt>, Error> {
@@ -66,6 +158,7 @@ impl ApiTransactionData {
                 eth_block: EthBlockId(op.eth_block),
                 rollup_block: None,
                 id: op.serial_id,
+                l1_details: Default::default(),
             })))
         }
         // 3. No operation found, return nothing.
@@ -74,6 +167,42 @@ impl ApiTransactionData {
         }
     }
 
+    /// Returns the receipt of the transaction once its status is different from the known one,
+    /// or the current receipt after the timeout.
+    async fn wait_tx_status(
+        &self,
+        tx_hash: TxHash,
+        known_status: Option<TxInBlockStatus>,
+        timeout: Duration,
+    ) -> Result<Option<Receipt>, Error> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            // The waiter is registered before the receipt is loaded,
+            // so the event emitted in between is not missed.
+            let (notify, notified) = oneshot::channel();
+            self.event_stream.do_send(WaitForTx { tx_hash, notify });
+
+            let receipt = self.tx_status(tx_hash).await?;
+            let status = receipt.as_ref().map(Receipt::status);
+            let is_final = matches!(
+                status,
+                Some(TxInBlockStatus::Finalized) | Some(TxInBlockStatus::Rejected)
+            );
+            if status != known_status || is_final {
+                return Ok(receipt);
+            }
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match tokio::time::timeout(remaining, notified).await {
+                // The event of the transaction is received, but it may not change
+                // the status known to the client, so the receipt is checked again.
+                Ok(Ok(())) => continue,
+                // Either the timeout has expired or the event stream has stopped.
+                _ => return Ok(receipt),
+            }
+        }
+    }
+
     async fn tx_data(&self, tx_hash: TxHash) -> Result<Option<TxData>, Error> {
         let mut storage = self
             .tx_sender
@@ -122,6 +251,82 @@ impl ApiTransactionData {
         }
     }
 
+    async fn withdrawal_finality(
+        &self,
+        tx_hash: TxHash,
+    ) -> Result<Option<WithdrawalFinality>, Error> {
+        let tx = match self.tx_data(tx_hash).await? {
+            Some(data) => data.tx,
+            None => return Ok(None),
+        };
+        let eth_tx_hash = match &tx.op {
+            TransactionData::L2(L2Transaction::Withdraw(withdraw)) => withdraw.eth_tx_hash,
+            TransactionData::L2(L2Transaction::ForcedExit(forced_exit)) => forced_exit.eth_tx_hash,
+            TransactionData::L2(L2Transaction::WithdrawNFT(withdraw)) => withdraw.eth_tx_hash,
+            TransactionData::L1(L1Transaction::FullExit(_)) => None,
+            _ => return Err(Error::from(InvalidDataError::NotWithdrawal)),
+        };
+        if tx.status == TxInBlockStatus::Rejected {
+            return Err(Error::from(InvalidDataError::WithdrawalRejected));
+        }
+
+        let mut storage = self
+            .tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?;
+        let mut stages = Vec::with_capacity(PIPELINE_STAGES.len());
+        for action_type in PIPELINE_STAGES {
+            let last_block = storage
+                .chain()
+                .operations_schema()
+                .get_last_block_by_aggregated_action(*action_type, Some(true))
+                .await
+                .map_err(Error::storage)?;
+            let operations = storage
+                .chain()
+                .operations_schema()
+                .load_recent_confirmed_aggregated_actions(*action_type, CADENCE_WINDOW)
+                .await
+                .map_err(Error::storage)?;
+            stages.push((last_block, StageCadence::new(&operations)));
+        }
+        let (last_committed_block, last_proven_block, last_finalized_block) =
+            (stages[0].0, stages[1].0, stages[2].0);
+
+        let stage = match tx.block_number {
+            None => WithdrawalStage::Queued,
+            Some(block) if block <= last_finalized_block => WithdrawalStage::Finalized,
+            Some(block) if block <= last_proven_block => WithdrawalStage::Proven,
+            Some(block) if block <= last_committed_block => WithdrawalStage::Committed,
+            Some(_) => WithdrawalStage::IncludedInBlock,
+        };
+        // The block of the queued withdrawal isn't known, so its finalization isn't estimated.
+        let estimated_finalized_at = match tx.block_number {
+            Some(block) if stage != WithdrawalStage::Finalized => {
+                estimate_finalized_at(block, &stages, Utc::now())
+            }
+            _ => None,
+        };
+        Ok(Some(WithdrawalFinality {
+            tx_hash,
+            stage,
+            rollup_block: tx.block_number,
+            last_committed_block,
+            last_proven_block,
+            last_finalized_block,
+            blocks_to_prove: tx
+                .block_number
+                .map(|block| block.saturating_sub(*last_proven_block)),
+            blocks_to_finalize: tx
+                .block_number
+                .map(|block| block.saturating_sub(*last_finalized_block)),
+            estimated_finalized_at,
+            eth_tx_hash,
+        }))
+    }
+
     async fn get_batch(&self, batch_hash: TxHash) -> Result<Option<ApiTxBatch>, Error> {
         let mut storage = self
             .tx_sender
@@ -150,6 +355,24 @@ async fn tx_status(
     res
 }
 
+async fn wait_tx_status(
+    data: web::Data<ApiTransactionData>,
+    tx_hash: web::Path<TxHash>,
+    web::Query(query): web::Query<TxStatusWaitQuery>,
+) -> ApiResult<Option<Receipt>> {
+    let start = Instant::now();
+    let timeout = query
+        .timeout
+        .map_or(DEFAULT_WAIT_TIMEOUT, Duration::from_secs)
+        .min(MAX_WAIT_TIMEOUT);
+    let res = data
+        .wait_tx_status(*tx_hash, query.status, timeout)
+        .await
+        .into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "wait_tx_status");
+    res
+}
+
 async fn tx_data(
     data: web::Data<ApiTransactionData>,
     tx_hash: web::Path<TxHash>,
@@ -160,52 +383,89 @@ async fn tx_data(
     res
 }
 
+async fn withdrawal_finality(
+    data: web::Data<ApiTransactionData>,
+    tx_hash: web::Path<TxHash>,
+) -> ApiResult<Option<WithdrawalFinality>> {
+    let start = Instant::now();
+    let res = data.withdrawal_finality(*tx_hash).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "withdrawal_finality");
+    res
+}
+
 async fn submit_tx(
     data: web::Data<ApiTransactionData>,
+    req: HttpRequest,
     Json(body): Json<TxWithSignature>,
 ) -> ApiResult<TxHashSerializeWrapper> {
     let start = Instant::now();
+    let idempotency_key = match IdempotencyKeys::request_key(&req) {
+        Ok(key) => key,
+        Err(err) => return err.into(),
+    };
+    let request_hash = body.tx.hash();
+    let sender = body.tx.account_id().ok();
+    let submit = async {
+        let tx_hash = data
+            .tx_sender
+            .submit_tx(body.tx, body.signature, None)
+            .await;
+
+        if let Err(err) = &tx_hash {
+            let err_label = match err {
+                SubmitError::IncorrectTx(err) => err.clone(),
+                SubmitError::TxAdd(err) => err.to_string(),
+                _ => "other".to_string(),
+            };
+            let labels = vec![("stage", "api".to_string()), ("error", err_label)];
+            metrics::increment_counter!("rejected_txs", &labels);
+        }
+
+        tx_hash.map(TxHashSerializeWrapper).map_err(Error::from)
+    };
     let tx_hash = data
-        .tx_sender
-        .submit_tx(body.tx, body.signature, None)
+        .idempotency_keys
+        .submit(idempotency_key, sender, request_hash, submit)
         .await;
-
-    if let Err(err) = &tx_hash {
-        let err_label = match err {
-            SubmitError::IncorrectTx(err) => err.clone(),
-            SubmitError::TxAdd(err) => err.to_string(),
-            _ => "other".to_string(),
-        };
-        let labels = vec![("stage", "api".to_string()), ("error", err_label)];
-        metrics::increment_counter!("rejected_txs", &labels);
-    }
-
-    let tx_hash = tx_hash.map_err(Error::from);
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "submit_tx");
-    tx_hash.map(TxHashSerializeWrapper).into()
+    tx_hash.into()
 }
 
 async fn submit_batch(
     data: web::Data<ApiTransactionData>,
+    req: HttpRequest,
     Json(body): Json<IncomingTxBatch>,
 ) -> ApiResult<SubmitBatchResponse> {
     let start = Instant::now();
+    let idempotency_key = match IdempotencyKeys::request_key(&req) {
+        Ok(key) => key,
+        Err(err) => return err.into(),
+    };
+    let tx_hashes: Vec<TxHash> = body.txs.iter().map(|tx| tx.tx.hash()).collect();
+    let request_hash = TxHash::batch_hash(&tx_hashes);
+    let sender = body.txs.first().and_then(|tx| tx.tx.account_id().ok());
+    let submit = async {
+        let response = data
+            .tx_sender
+            .submit_txs_batch(body.txs, body.signature, None)
+            .await;
+
+        if let Err(err) = &response {
+            let err_label = match err {
+                SubmitError::IncorrectTx(err) => err.clone(),
+                SubmitError::TxAdd(err) => err.to_string(),
+                _ => "other".to_string(),
+            };
+            let labels = vec![("stage", "api".to_string()), ("error", err_label)];
+            metrics::increment_counter!("rejected_txs", &labels);
+        }
+
+        response.map_err(Error::from)
+    };
     let response = data
-        .tx_sender
-        .submit_txs_batch(body.txs, body.signature, None)
+        .idempotency_keys
+        .submit(idempotency_key, sender, request_hash, submit)
         .await;
-
-    if let Err(err) = &response {
-        let err_label = match err {
-            SubmitError::IncorrectTx(err) => err.clone(),
-            SubmitError::TxAdd(err) => err.to_string(),
-            _ => "other".to_string(),
-        };
-        let labels = vec![("stage", "api".to_string()), ("error", err_label)];
-        metrics::increment_counter!("rejected_txs", &labels);
-    }
-
-    let response = response.map_err(Error::from);
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "submit_batch");
     response.into()
 }
@@ -235,14 +495,20 @@ async fn get_batch(
     res
 }
 
-pub fn api_scope(tx_sender: TxSender) -> Scope {
-    let data = ApiTransactionData::new(tx_sender);
+pub fn api_scope(
+    tx_sender: TxSender,
+    event_stream: Addr<EventStreamManager>,
+    idempotency_keys: IdempotencyKeys,
+) -> Scope {
+    let data = ApiTransactionData::new(tx_sender, event_stream, idempotency_keys);
 
     web::scope("transactions")
         .app_data(web::Data::new(data))
         .route("", web::post().to(submit_tx))
         .route("{tx_hash}", web::get().to(tx_status))
+        .route("{tx_hash}/wait", web::get().to(wait_tx_status))
         .route("{tx_hash}/data", web::get().to(tx_data))
+        .route("{tx_hash}/withdrawal", web::get().to(withdrawal_finality))
         .route("/batches", web::post().to(submit_batch))
         .route("/batches/{batch_hash}", web::get().to(get_batch))
         .route("/toggle2FA", web::post().to(toggle_2fa))
@@ -252,13 +518,15 @@ pub fn api_scope(tx_sender: TxSender) -> Scope {
 mod tests {
     use super::*;
     use crate::api_server::rest::v02::{
+        error::ErrorCode,
         test_utils::{
             deserialize_response_result, dummy_fee_ticker, dummy_sign_verifier, TestServerConfig,
-            TestTransactions,
+            TestTransactions, COMMITTED_OP_SERIAL_ID, VERIFIED_OP_SERIAL_ID,
         },
         SharedData,
     };
     use crate::fee_ticker::validator::cache::TokenInMemoryCache;
+    use actix::Actor;
     use chrono::Utc;
     use futures::{channel::mpsc, StreamExt};
     use num::{rational::Ratio, BigUint};
@@ -270,6 +538,7 @@ mod tests {
         ApiVersion,
     };
     use zksync_mempool::MempoolTransactionRequest;
+    use zksync_storage::tokens::memory::MemoryTokensStorage;
     use zksync_types::{
         tokens::{Token, TokenMarketVolume},
         tx::{
@@ -279,6 +548,47 @@ mod tests {
         Address, BlockNumber, SignedZkSyncTx, TokenId, TokenKind, TokenLike,
     };
 
+    #[test]
+    fn finalization_estimate() {
+        let now = Utc::now();
+        let minutes = |minutes| chrono::Duration::minutes(minutes);
+        // The blocks are executed every 10 minutes.
+        let executions = StageCadence::new(&[
+            (BlockNumber(12), now - minutes(5)),
+            (BlockNumber(11), now - minutes(15)),
+            (BlockNumber(10), now - minutes(25)),
+        ])
+        .unwrap();
+        assert_eq!(executions.block_interval, minutes(10));
+        assert_eq!(executions.estimated_at(BlockNumber(14)), now + minutes(15));
+        // The cadence isn't known from the operation of a single block.
+        assert!(StageCadence::new(&[(BlockNumber(12), now)]).is_none());
+        assert!(StageCadence::new(&[]).is_none());
+
+        // The proofs are published every 30 minutes, so the execution waits for them.
+        let proofs = StageCadence::new(&[
+            (BlockNumber(13), now - minutes(10)),
+            (BlockNumber(11), now - minutes(70)),
+        ])
+        .unwrap();
+        let stages = [
+            (BlockNumber(20), None),
+            (proofs.last_block, Some(proofs)),
+            (executions.last_block, Some(executions)),
+        ];
+        assert_eq!(
+            estimate_finalized_at(BlockNumber(14), &stages, now),
+            Some(now + minutes(20))
+        );
+        // The stalled pipeline passes the block not earlier than now.
+        assert_eq!(
+            estimate_finalized_at(BlockNumber(13), &stages, now + minutes(60)),
+            Some(now + minutes(60))
+        );
+        // The block isn't committed yet, and the commits cadence isn't known.
+        assert_eq!(estimate_finalized_at(BlockNumber(21), &stages, now), None);
+    }
+
     fn submit_txs_loopback() -> (mpsc::Sender<MempoolTransactionRequest>, JoinHandle<()>) {
         let (mempool_tx_request_sender, mut mempool_tx_request_receiver) = mpsc::channel(100);
 
@@ -294,6 +604,7 @@ mod tests {
                     MempoolTransactionRequest::NewTxsBatch(_, _, resp) => {
                         resp.send(Ok(())).unwrap_or_default()
                     }
+                    MempoolTransactionRequest::EthBlockWatched(_) => {}
                 }
             }
         });
@@ -317,11 +628,7 @@ mod tests {
             api_version: ApiVersion::V02,
         };
 
-        let mut tokens = HashMap::new();
-        tokens.insert(
-            TokenLike::Id(TokenId(0)),
-            Token::new(TokenId(0), Default::default(), "ETH", 18, TokenKind::ERC20),
-        );
+        // ETH is stored by default.
         let mut market = HashMap::new();
         market.insert(
             TokenId(0),
@@ -330,9 +637,8 @@ mod tests {
                 last_updated: Utc::now(),
             },
         );
-        let cache = TokenInMemoryCache::new()
-            .with_tokens(tokens)
-            .with_market(market);
+        let cache =
+            TokenInMemoryCache::from(MemoryTokensStorage::new().with_market_volumes(market));
 
         let prices = vec![
             (TokenLike::Id(TokenId(0)), 10500_u64.into()),
@@ -346,14 +652,18 @@ mod tests {
 
         let (client, server) = cfg.start_server(
             move |cfg: &TestServerConfig| {
-                api_scope(TxSender::new(
-                    cfg.pool.clone(),
-                    dummy_sign_verifier(),
-                    dummy_fee_ticker(&prices, Some(cache.clone())),
-                    &cfg.config.api.common,
-                    &cfg.config.api.token_config,
-                    sender.clone(),
-                ))
+                api_scope(
+                    TxSender::new(
+                        cfg.pool.clone(),
+                        dummy_sign_verifier(),
+                        dummy_fee_ticker(&prices, Some(cache.clone())),
+                        &cfg.config.api.common,
+                        &cfg.config.api.token_config,
+                        sender.clone(),
+                    ),
+                    EventStreamManager::new(cfg.pool.clone()).start(),
+                    IdempotencyKeys::new(cfg.pool.clone(), Duration::from_secs(3600)),
+                )
             },
             Some(shared_data),
         );
@@ -365,6 +675,32 @@ mod tests {
         let tx_hash: TxHash = deserialize_response_result(response)?;
         assert_eq!(tx.hash(), tx_hash);
 
+        // The retried request returns the stored response,
+        // and the key can't be used for another transaction.
+        // The key is unique, since the stored responses outlive the test.
+        let idempotency_key = format!("tx-key-{}", Utc::now().timestamp_nanos());
+        for _ in 0..2 {
+            let response = client
+                .submit_tx_with_idempotency_key(
+                    tx.clone(),
+                    TxEthSignatureVariant::Single(None),
+                    &idempotency_key,
+                )
+                .await?;
+            let tx_hash: TxHash = deserialize_response_result(response)?;
+            assert_eq!(tx.hash(), tx_hash);
+        }
+        let other_tx = TestServerConfig::gen_zk_txs(200_u64).txs[0].0.clone();
+        let response = client
+            .submit_tx_with_idempotency_key(
+                other_tx,
+                TxEthSignatureVariant::Single(None),
+                &idempotency_key,
+            )
+            .await?;
+        let error = serde_json::from_value::<Error>(response.error.unwrap()).unwrap();
+        assert_eq!(error.code, ErrorCode::IdempotencyKeyReused);
+
         let TestTransactions { acc, txs } = TestServerConfig::gen_zk_txs(1_00);
         let eth = Token::new(TokenId(0), Default::default(), "ETH", 18, TokenKind::ERC20);
         let (good_batch, expected_tx_hashes): (Vec<_>, Vec<_>) = txs
@@ -450,11 +786,20 @@ mod tests {
         };
         let response = client.tx_status(tx_hash).await?;
         let tx_status: Receipt = deserialize_response_result(response)?;
+        let l1_details = match &tx_status {
+            Receipt::L2(receipt) => receipt.l1_details.clone(),
+            receipt => panic!("Unexpected receipt: {:?}", receipt),
+        };
+        assert!(l1_details.commit_tx_hash.is_some());
+        assert!(l1_details.committed_at.is_some());
+        assert!(l1_details.verify_tx_hash.is_some());
+        assert!(l1_details.finalized_at.is_some());
         let expected_tx_status = Receipt::L2(L2Receipt {
             tx_hash,
             rollup_block: Some(BlockNumber(1)),
             status: TxInBlockStatus::Finalized,
             fail_reason: None,
+            l1_details,
         });
         assert_eq!(tx_status, expected_tx_status);
 
@@ -462,6 +807,17 @@ mod tests {
         let tx_data: Option<TxData> = deserialize_response_result(response)?;
         assert_eq!(tx_data.unwrap().tx.tx_hash, tx_hash);
 
+        // The final status is returned without waiting.
+        let query = TxStatusWaitQuery {
+            status: Some(TxInBlockStatus::Finalized),
+            timeout: Some(10),
+        };
+        let started_at = Instant::now();
+        let response = client.wait_tx_status(tx_hash, &query).await?;
+        let tx_status: Receipt = deserialize_response_result(response)?;
+        assert_eq!(tx_status, expected_tx_status);
+        assert!(started_at.elapsed() < Duration::from_secs(10));
+
         let pending_tx_hash = {
             let mut storage = cfg.pool.access_storage().await?;
 
@@ -486,6 +842,7 @@ mod tests {
             rollup_block: None,
             status: TxInBlockStatus::Queued,
             fail_reason: None,
+            l1_details: Default::default(),
         });
         assert_eq!(tx_status, expected_tx_status);
 
@@ -493,10 +850,122 @@ mod tests {
         let tx_data: Option<TxData> = deserialize_response_result(response)?;
         assert_eq!(tx_data.unwrap().tx.tx_hash, pending_tx_hash);
 
+        // The status different from the known one is returned immediately,
+        // the same one is returned after the timeout.
+        let query = TxStatusWaitQuery::default();
+        let response = client.wait_tx_status(pending_tx_hash, &query).await?;
+        let tx_status: Receipt = deserialize_response_result(response)?;
+        assert_eq!(tx_status, expected_tx_status);
+        let query = TxStatusWaitQuery {
+            status: Some(TxInBlockStatus::Queued),
+            timeout: Some(1),
+        };
+        let started_at = Instant::now();
+        let response = client.wait_tx_status(pending_tx_hash, &query).await?;
+        let tx_status: Receipt = deserialize_response_result(response)?;
+        assert_eq!(tx_status, expected_tx_status);
+        assert!(started_at.elapsed() >= Duration::from_secs(1));
+
         let tx = TestServerConfig::gen_zk_txs(1_u64).txs[0].0.clone();
         let response = client.tx_data(tx.hash()).await?;
         let tx_data: Option<TxData> = deserialize_response_result(response)?;
         assert!(tx_data.is_none());
+        let query = TxStatusWaitQuery {
+            status: None,
+            timeout: Some(1),
+        };
+        let response = client.wait_tx_status(tx.hash(), &query).await?;
+        let tx_status: Option<Receipt> = deserialize_response_result(response)?;
+        assert!(tx_status.is_none());
+
+        let response = client.withdrawal_finality(tx.hash()).await?;
+        let finality: Option<WithdrawalFinality> = deserialize_response_result(response)?;
+        assert!(finality.is_none());
+
+        let (full_exit_hash, deposit_hash) = {
+            let mut storage = cfg.pool.access_storage().await?;
+            let mut hashes = Vec::new();
+            for serial_id in &[COMMITTED_OP_SERIAL_ID, VERIFIED_OP_SERIAL_ID] {
+                let op = storage
+                    .chain()
+                    .operations_schema()
+                    .get_executed_priority_operation(*serial_id as u32)
+                    .await?
+                    .unwrap();
+                hashes.push(TxHash::from_slice(&op.tx_hash).unwrap());
+            }
+            (hashes[0], hashes[1])
+        };
+        // The block of the full exit is committed, but not finalized yet.
+        let response = client.withdrawal_finality(full_exit_hash).await?;
+        let finality: Option<WithdrawalFinality> = deserialize_response_result(response)?;
+        let finality = finality.unwrap();
+        assert!(finality.stage >= WithdrawalStage::Committed);
+        assert!(finality.stage < WithdrawalStage::Finalized);
+        let block = finality.rollup_block.unwrap();
+        assert!(block > finality.last_finalized_block);
+        assert_eq!(
+            finality.blocks_to_finalize,
+            Some(*block - *finality.last_finalized_block)
+        );
+        assert!(finality.eth_tx_hash.is_none());
+
+        // The finality is tracked for the withdrawals only.
+        let response = client.withdrawal_finality(deposit_hash).await?;
+        let error = serde_json::from_value::<Error>(response.error.unwrap()).unwrap();
+        assert_eq!(error.code, ErrorCode::NotWithdrawal);
+
+        server.stop().await;
+        task.abort();
+        Ok(())
+    }
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn read_only_mode() -> anyhow::Result<()> {
+        let (sender, task) = submit_txs_loopback();
+
+        // The mode forced by the config doesn't depend on the database state.
+        let mut cfg = TestServerConfig::default();
+        cfg.config.api.common.read_only = true;
+
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+        let prices = vec![(TokenLike::Id(TokenId(0)), 10500_u64.into())];
+        let (client, server) = cfg.start_server(
+            move |cfg: &TestServerConfig| {
+                api_scope(
+                    TxSender::new(
+                        cfg.pool.clone(),
+                        dummy_sign_verifier(),
+                        dummy_fee_ticker(&prices, None),
+                        &cfg.config.api.common,
+                        &cfg.config.api.token_config,
+                        sender.clone(),
+                    ),
+                    EventStreamManager::new(cfg.pool.clone()).start(),
+                    IdempotencyKeys::new(cfg.pool.clone(), Duration::from_secs(3600)),
+                )
+            },
+            Some(shared_data),
+        );
+
+        let tx = TestServerConfig::gen_zk_txs(100_u64).txs[0].0.clone();
+        let response = client
+            .submit_tx(tx.clone(), TxEthSignatureVariant::Single(None))
+            .await?;
+        let error = serde_json::from_value::<Error>(response.error.unwrap()).unwrap();
+        assert_eq!(error.code, ErrorCode::ReadOnlyMode);
+
+        // The queries keep working.
+        let response = client.tx_status(tx.hash()).await?;
+        let tx_status: Option<Receipt> = deserialize_response_result(response)?;
+        assert!(tx_status.is_none());
 
         server.stop().await;
         task.abort();
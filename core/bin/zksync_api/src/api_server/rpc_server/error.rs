@@ -3,7 +3,10 @@ use jsonrpc_core::ErrorCode;
 use zksync_types::tx::error::TxAddError;
 // Workspace uses
 // Local uses
-use crate::api_server::tx_sender::SubmitError;
+use crate::api_server::{
+    rest::v02::error::{Error as ApiV02Error, ErrorCode as ApiV02ErrorCode},
+    tx_sender::SubmitError,
+};
 
 #[derive(Debug, Clone, Copy)]
 pub enum RpcErrorCodes {
@@ -22,6 +25,8 @@ pub enum RpcErrorCodes {
     OperationsLimitReached = 302,
     UnsupportedFastProcessing = 303,
     Toggle2FA = 304,
+    ReadOnlyMode = 305,
+    TokenNotFound = 306,
 }
 
 impl From<TxAddError> for RpcErrorCodes {
@@ -41,6 +46,13 @@ impl From<TxAddError> for RpcErrorCodes {
             TxAddError::BatchTooBig => Self::Other,
             TxAddError::BatchWithdrawalsOverload => Self::Other,
             TxAddError::EthSignaturesLimitExceeded => Self::Other,
+            TxAddError::TokenDenylisted(_) => Self::IncorrectTx,
+            TxAddError::BatchNonceGap => Self::NonceMismatch,
+            TxAddError::ReplacementFeeTooLow(_) => Self::FeeTooLow,
+            TxAddError::ReplacementFeeTokenMismatch => Self::InappropriateFeeToken,
+            TxAddError::ReplacedTxInBatch => Self::NonceMismatch,
+            TxAddError::ReplacedTxExecuted => Self::NonceMismatch,
+            TxAddError::TxExpired => Self::IncorrectTx,
         }
     }
 }
@@ -92,6 +104,11 @@ impl From<SubmitError> for jsonrpc_core::Error {
                 message: "Error communicating core server".to_string(),
                 data: Some(reason.into()),
             },
+            SubmitError::ReadOnlyMode(_) => Self {
+                code: RpcErrorCodes::ReadOnlyMode.into(),
+                message: inner.to_string(),
+                data: None,
+            },
             SubmitError::Internal(msg) => Self {
                 code: ErrorCode::InternalError,
                 message: msg.to_string(),
@@ -110,3 +127,51 @@ impl From<SubmitError> for jsonrpc_core::Error {
         }
     }
 }
+
+/// The errors of the API v0.2 data shared with the REST API keep their
+/// v0.2 representation in the `data` field.
+impl From<ApiV02Error> for jsonrpc_core::Error {
+    fn from(inner: ApiV02Error) -> Self {
+        let code = match inner.code {
+            ApiV02ErrorCode::TokenNotFound => RpcErrorCodes::TokenNotFound.into(),
+            ApiV02ErrorCode::UnreacheableError
+            | ApiV02ErrorCode::CoreApiError
+            | ApiV02ErrorCode::StorageError
+            | ApiV02ErrorCode::ExternalApiError
+            | ApiV02ErrorCode::InternalError
+            | ApiV02ErrorCode::CommunicationCoreServer
+            | ApiV02ErrorCode::Other => ErrorCode::InternalError,
+            _ => ErrorCode::InvalidParams,
+        };
+        Self {
+            code,
+            message: inner.message.clone(),
+            data: serde_json::to_value(inner).ok(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rest::v02::error::InvalidDataError;
+    use crate::fee_ticker::PriceError;
+
+    #[test]
+    fn api_v02_errors() {
+        let err = jsonrpc_core::Error::from(ApiV02Error::from(InvalidDataError::TooManyTokens));
+        assert_eq!(err.code, ErrorCode::InvalidParams);
+        assert_eq!(
+            err.data.unwrap()["code"],
+            ApiV02ErrorCode::TooManyTokens as u16
+        );
+
+        let err = jsonrpc_core::Error::from(ApiV02Error::from(PriceError::token_not_found(
+            "Token not found in storage",
+        )));
+        assert_eq!(err.code, ErrorCode::from(RpcErrorCodes::TokenNotFound));
+
+        let err = jsonrpc_core::Error::from(ApiV02Error::storage("connection refused"));
+        assert_eq!(err.code, ErrorCode::InternalError);
+    }
+}
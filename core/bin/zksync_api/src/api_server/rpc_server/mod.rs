@@ -8,7 +8,7 @@ use jsonrpc_http_server::ServerBuilder;
 use tokio::task::JoinHandle;
 
 // Workspace uses
-use zksync_config::configs::api::{CommonApiConfig, JsonRpcConfig, TokenConfig};
+use zksync_config::ZkSyncConfig;
 use zksync_storage::{
     chain::{
         block::records::StorageBlockDetails, operations::records::StoredExecutedPriorityOperation,
@@ -20,7 +20,12 @@ use zksync_types::{tx::TxHash, Address, BlockNumber};
 use zksync_utils::panic_notify::{spawn_panic_handler, ThreadPanicNotify};
 
 // Local uses
-use crate::{signature_checker::VerifySignatureRequest, utils::shared_lru_cache::AsyncLruCache};
+use crate::{
+    api_server::{config_reload::ConfigReloader, rest::v02::token::ApiTokenData},
+    fiat_rates::FiatRates,
+    signature_checker::VerifySignatureRequest,
+    utils::shared_lru_cache::AsyncLruCache,
+};
 
 pub mod error;
 mod ip_insert_middleware;
@@ -44,6 +49,8 @@ pub struct RpcApp {
     pub confirmations_for_eth_event: u64,
 
     tx_sender: TxSender,
+    /// The token queries are served by the same data as the REST API v0.2.
+    token_data: ApiTokenData,
 }
 
 impl RpcApp {
@@ -51,21 +58,29 @@ impl RpcApp {
         connection_pool: ConnectionPool,
         sign_verify_request_sender: mpsc::Sender<VerifySignatureRequest>,
         ticker: FeeTicker,
-        config: &CommonApiConfig,
-        token_config: &TokenConfig,
+        config: &ZkSyncConfig,
         confirmations_for_eth_event: u64,
         mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
+        config_reloader: &ConfigReloader,
     ) -> Self {
-        let api_requests_caches_size = config.caches_size;
+        let api_requests_caches_size = config.api.common.caches_size;
 
         let tx_sender = TxSender::new(
-            connection_pool,
+            connection_pool.clone(),
             sign_verify_request_sender,
-            ticker,
-            config,
-            token_config,
+            ticker.clone(),
+            &config.api.common,
+            &config.api.token_config,
             mempool_tx_sender,
         );
+        let token_data = ApiTokenData::new(
+            config,
+            connection_pool,
+            tx_sender.tokens.clone(),
+            ticker,
+            FiatRates::from_config(&config.api.token_config),
+            config_reloader,
+        );
 
         RpcApp {
             cache_of_executed_priority_operations: AsyncLruCache::new(api_requests_caches_size),
@@ -75,6 +90,7 @@ impl RpcApp {
             confirmations_for_eth_event,
 
             tx_sender,
+            token_data,
         }
     }
 
@@ -261,21 +277,20 @@ pub fn start_rpc_server(
     connection_pool: ConnectionPool,
     sign_verify_request_sender: mpsc::Sender<VerifySignatureRequest>,
     ticker: FeeTicker,
-    config: &JsonRpcConfig,
-    common_api_config: &CommonApiConfig,
-    token_config: &TokenConfig,
+    config: &ZkSyncConfig,
     mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
     confirmations_for_eth_event: u64,
+    config_reloader: &ConfigReloader,
 ) -> JoinHandle<()> {
-    let addr = config.http_bind_addr();
+    let addr = config.api.json_rpc.http_bind_addr();
     let rpc_app = RpcApp::new(
         connection_pool,
         sign_verify_request_sender,
         ticker,
-        common_api_config,
-        token_config,
+        config,
         confirmations_for_eth_event,
         mempool_tx_sender,
+        config_reloader,
     );
 
     let (handler, panic_sender) = spawn_panic_handler();
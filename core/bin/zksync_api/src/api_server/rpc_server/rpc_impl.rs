@@ -7,7 +7,7 @@ use jsonrpc_core::{Error, Result};
 use zksync_api_types::{
     v02::{
         fee::ApiTxFeeTypes,
-        token::ApiNFT,
+        token::{ApiNFT, ApiToken, FeeAcceptability, TokenLookup, TokenPriceLookup},
         transaction::{Toggle2FA, Toggle2FAResponse},
     },
     TxWithSignature,
@@ -451,4 +451,57 @@ impl RpcApp {
         metrics::histogram!("api", start.elapsed(), "type" => "rpc", "endpoint_name" => "get_nft_id_by_tx_hash");
         Ok(response)
     }
+
+    pub async fn _impl_get_token_info(self, token: TokenLike) -> Result<ApiToken> {
+        let start = Instant::now();
+        let result = self.token_data.api_token(token).await.map_err(Error::from);
+        metrics::histogram!("api", start.elapsed(), "type" => "rpc", "endpoint_name" => "get_token_info");
+        result
+    }
+
+    pub async fn _impl_get_tokens_by_ids(self, ids: Vec<TokenId>) -> Result<Vec<TokenLookup>> {
+        let start = Instant::now();
+        let result = self
+            .token_data
+            .tokens_by_ids(ids)
+            .await
+            .map_err(Error::from);
+        metrics::histogram!("api", start.elapsed(), "type" => "rpc", "endpoint_name" => "get_tokens_by_ids");
+        result
+    }
+
+    pub async fn _impl_get_token_prices(
+        self,
+        tokens: Vec<TokenLike>,
+    ) -> Result<Vec<TokenPriceLookup>> {
+        let start = Instant::now();
+        let result = self
+            .token_data
+            .token_price_lookups(tokens)
+            .await
+            .map_err(Error::from);
+        metrics::histogram!("api", start.elapsed(), "type" => "rpc", "endpoint_name" => "get_token_prices");
+        result
+    }
+
+    pub async fn _impl_get_fee_tokens(self) -> Result<Vec<ApiToken>> {
+        let start = Instant::now();
+        let result = self.token_data.fee_tokens().await.map_err(Error::from);
+        metrics::histogram!("api", start.elapsed(), "type" => "rpc", "endpoint_name" => "get_fee_tokens");
+        result
+    }
+
+    pub async fn _impl_fee_acceptable_batch(
+        self,
+        tokens: Vec<TokenLike>,
+    ) -> Result<Vec<FeeAcceptability>> {
+        let start = Instant::now();
+        let result = self
+            .token_data
+            .fee_acceptable_batch(tokens)
+            .await
+            .map_err(Error::from);
+        metrics::histogram!("api", start.elapsed(), "type" => "rpc", "endpoint_name" => "fee_acceptable_batch");
+        result
+    }
 }
@@ -8,7 +8,7 @@ use jsonrpc_derive::rpc;
 use zksync_api_types::{
     v02::{
         fee::ApiTxFeeTypes,
-        token::ApiNFT,
+        token::{ApiNFT, ApiToken, FeeAcceptability, TokenLookup, TokenPriceLookup},
         transaction::{Toggle2FA, Toggle2FAResponse},
     },
     TxWithSignature,
@@ -110,6 +110,25 @@ pub trait Rpc {
 
     #[rpc(name = "get_nft_id_by_tx_hash", returns = "Option<TokenId>")]
     fn get_nft_id_by_tx_hash(&self, tx_hash: TxHash) -> BoxFutureResult<Option<TokenId>>;
+
+    // The token methods below have the semantics of the REST API v0.2.
+    #[rpc(name = "get_token_info", returns = "ApiToken")]
+    fn get_token_info(&self, token_like: TokenLike) -> BoxFutureResult<ApiToken>;
+
+    #[rpc(name = "get_tokens_by_ids", returns = "Vec<TokenLookup>")]
+    fn get_tokens_by_ids(&self, ids: Vec<TokenId>) -> BoxFutureResult<Vec<TokenLookup>>;
+
+    #[rpc(name = "get_token_prices", returns = "Vec<TokenPriceLookup>")]
+    fn get_token_prices(&self, tokens: Vec<TokenLike>) -> BoxFutureResult<Vec<TokenPriceLookup>>;
+
+    #[rpc(name = "get_fee_tokens", returns = "Vec<ApiToken>")]
+    fn get_fee_tokens(&self) -> BoxFutureResult<Vec<ApiToken>>;
+
+    #[rpc(name = "fee_acceptable_batch", returns = "Vec<FeeAcceptability>")]
+    fn fee_acceptable_batch(
+        &self,
+        tokens: Vec<TokenLike>,
+    ) -> BoxFutureResult<Vec<FeeAcceptability>>;
 }
 
 impl Rpc for RpcApp {
@@ -210,4 +229,27 @@ impl Rpc for RpcApp {
     fn get_nft_id_by_tx_hash(&self, tx_hash: TxHash) -> BoxFutureResult<Option<TokenId>> {
         spawn!(self._impl_get_nft_id_by_tx_hash(tx_hash))
     }
+
+    fn get_token_info(&self, token_like: TokenLike) -> BoxFutureResult<ApiToken> {
+        spawn!(self._impl_get_token_info(token_like))
+    }
+
+    fn get_tokens_by_ids(&self, ids: Vec<TokenId>) -> BoxFutureResult<Vec<TokenLookup>> {
+        spawn!(self._impl_get_tokens_by_ids(ids))
+    }
+
+    fn get_token_prices(&self, tokens: Vec<TokenLike>) -> BoxFutureResult<Vec<TokenPriceLookup>> {
+        spawn!(self._impl_get_token_prices(tokens))
+    }
+
+    fn get_fee_tokens(&self) -> BoxFutureResult<Vec<ApiToken>> {
+        spawn!(self._impl_get_fee_tokens())
+    }
+
+    fn fee_acceptable_batch(
+        &self,
+        tokens: Vec<TokenLike>,
+    ) -> BoxFutureResult<Vec<FeeAcceptability>> {
+        spawn!(self._impl_fee_acceptable_batch(tokens))
+    }
 }
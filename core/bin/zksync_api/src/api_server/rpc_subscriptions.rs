@@ -11,7 +11,7 @@ use jsonrpc_pubsub::{typed::Subscriber, PubSubHandler, Session, SubscriptionId};
 use jsonrpc_ws_server::RequestContext;
 use tokio::task::JoinHandle;
 // Workspace uses
-use zksync_config::configs::api::{CommonApiConfig, JsonRpcConfig, TokenConfig};
+use zksync_config::ZkSyncConfig;
 use zksync_mempool::MempoolTransactionRequest;
 use zksync_storage::ConnectionPool;
 use zksync_types::{tx::TxHash, ActionType, Address};
@@ -19,6 +19,7 @@ use zksync_utils::panic_notify::{spawn_panic_handler, ThreadPanicNotify};
 // Local uses
 use crate::fee_ticker::FeeTicker;
 use crate::{
+    api_server::config_reload::ConfigReloader,
     api_server::event_notify::{start_sub_notifier, EventNotifierRequest, EventSubscribeRequest},
     api_server::rpc_server::types::{ETHOpInfoResp, ResponseAccountState, TransactionInfoResp},
     signature_checker::VerifySignatureRequest,
@@ -182,33 +183,32 @@ pub fn start_ws_server(
     db_pool: ConnectionPool,
     sign_verify_request_sender: mpsc::Sender<VerifySignatureRequest>,
     ticker: FeeTicker,
-    common_config: &CommonApiConfig,
-    token_config: &TokenConfig,
-    config: &JsonRpcConfig,
+    config: &ZkSyncConfig,
     miniblock_iteration_interval: Duration,
     mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
     confirmations_for_eth_event: u64,
+    config_reloader: &ConfigReloader,
 ) -> JoinHandle<()> {
-    let addr = config.ws_bind_addr();
+    let addr = config.api.json_rpc.ws_bind_addr();
 
     let (event_sub_sender, event_sub_receiver) = mpsc::channel(2048);
 
     start_sub_notifier(
         db_pool.clone(),
         event_sub_receiver,
-        common_config.caches_size,
+        config.api.common.caches_size,
         miniblock_iteration_interval,
-        token_config,
+        &config.api.token_config,
     );
 
     let req_rpc_app = super::rpc_server::RpcApp::new(
         db_pool,
         sign_verify_request_sender,
         ticker,
-        common_config,
-        token_config,
+        config,
         confirmations_for_eth_event,
         mempool_tx_sender,
+        config_reloader,
     );
 
     let (handler, panic_sender) = spawn_panic_handler();
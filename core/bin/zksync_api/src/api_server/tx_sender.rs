@@ -41,8 +41,13 @@ use zksync_utils::{
 
 // Local uses
 use crate::{
-    api_server::forced_exit_checker::{ForcedExitAccountAgeChecker, ForcedExitChecker},
-    fee_ticker::{ResponseBatchFee, ResponseFee, TokenPriceRequestType},
+    api_server::{
+        forced_exit_checker::{ForcedExitAccountAgeChecker, ForcedExitChecker},
+        read_only_mode::ReadOnlyMode,
+    },
+    fee_ticker::{
+        discounts::FEE_DISCOUNT_SUBSIDY_TYPE, ResponseBatchFee, ResponseFee, TokenPriceRequestType,
+    },
     signature_checker::{
         BatchRequest, OrderRequest, RequestData, Toggle2FARequest, TxRequest, VerifiedTx,
         VerifySignatureRequest,
@@ -80,6 +85,8 @@ pub struct TxSender {
     pub current_subsidy_type: String,
     pub max_subsidy_usd: Ratio<BigUint>,
     pub subsidized_ips: HashSet<String>,
+
+    pub read_only_mode: ReadOnlyMode,
 }
 
 #[derive(Debug, Error)]
@@ -105,6 +112,8 @@ pub enum SubmitError {
     MempoolCommunication(String),
     #[error("Price error {0}")]
     PriceError(#[from] PriceError),
+    #[error("API is in the read-only mode: {0}.")]
+    ReadOnlyMode(String),
     #[error("Internal error.")]
     Internal(#[from] anyhow::Error),
     #[error("{0}")]
@@ -153,6 +162,7 @@ impl TxSender {
         let max_number_of_transactions_per_batch =
             config.max_number_of_transactions_per_batch as usize;
         let max_number_of_authors_per_batch = config.max_number_of_authors_per_batch as usize;
+        let read_only_mode = ReadOnlyMode::new(connection_pool.clone(), config);
 
         Self {
             mempool_tx_sender,
@@ -172,6 +182,7 @@ impl TxSender {
             current_subsidy_type: config.subsidy_name.clone(),
             max_subsidy_usd: config.max_subsidy_usd(),
             subsidized_ips: config.subsidized_ips.clone().into_iter().collect(),
+            read_only_mode,
         }
     }
 
@@ -217,6 +228,7 @@ impl TxSender {
         &self,
         toggle_2fa: Toggle2FA,
     ) -> Result<Toggle2FAResponse, SubmitError> {
+        self.read_only_mode.check().await?;
         let account_id = toggle_2fa.account_id;
         let current_type = self
             .get_sender_type(toggle_2fa.account_id)
@@ -447,6 +459,7 @@ impl TxSender {
         normal_fee: BigUint,
         subsidized_fee: BigUint,
         token_id: TokenId,
+        subsidy_type: &str,
     ) -> Result<(), anyhow::Error> {
         let token_price_in_usd = self
             .ticker
@@ -467,7 +480,7 @@ impl TxSender {
             token_id,
             token_amount: biguint_to_big_decimal(subsidized_fee),
             full_cost_token: biguint_to_big_decimal(normal_fee),
-            subsidy_type: self.current_subsidy_type.clone(),
+            subsidy_type: subsidy_type.to_string(),
             tx_hash: hash,
         };
 
@@ -481,6 +494,34 @@ impl TxSender {
         Ok(())
     }
 
+    /// Records the discounted part of the fee. The transaction is already accepted at this point,
+    /// so the failure is only reported rather than returned to the user.
+    async fn store_fee_discount_data(
+        &self,
+        hash: TxHash,
+        undiscounted_fee: BigUint,
+        discounted_fee: BigUint,
+        token_id: TokenId,
+    ) {
+        if let Err(err) = self
+            .store_subsidy_data(
+                hash,
+                undiscounted_fee,
+                discounted_fee,
+                token_id,
+                FEE_DISCOUNT_SUBSIDY_TYPE,
+            )
+            .await
+        {
+            metrics::increment_counter!("tx_sender.store_fee_discount_data_fail");
+            vlog::error!(
+                "Failed to store the fee discount to database. Reason: {}",
+                err
+            );
+        }
+    }
+
+    #[tracing::instrument(name = "tx_sender.submit_tx", skip_all)]
     pub async fn submit_tx(
         &self,
         tx: ZkSyncTx,
@@ -495,6 +536,7 @@ impl TxSender {
         // The initial state of processing tx
         metrics::increment_counter!("process_tx_count", &labels);
 
+        self.read_only_mode.check().await?;
         if tx.is_close() {
             return Err(SubmitError::AccountCloseDisabled);
         }
@@ -523,6 +565,7 @@ impl TxSender {
         let sign_verify_channel = self.sign_verify_requests.clone();
 
         let mut fee_data_for_subsidy: Option<ResponseFee> = None;
+        let mut fee_discount = None;
 
         if let Some((tx_type, token, address, provided_fee)) = tx_fee_info {
             let should_enforce_fee = !matches!(tx_type, TxFeeTypes::ChangePubKey { .. })
@@ -538,6 +581,12 @@ impl TxSender {
                 .ticker
                 .get_fee_from_ticker_in_wei(tx_type, token.clone(), address)
                 .await?;
+            if let Some(undiscounted_fee) = required_fee_data.undiscounted_fee.clone() {
+                fee_discount = Some((
+                    undiscounted_fee,
+                    required_fee_data.normal_fee.total_fee.clone(),
+                ));
+            }
 
             let required_fee_data = if self
                 .should_subsidize_cpk(
@@ -615,6 +664,7 @@ impl TxSender {
                 fee_data_for_subsidy.normal_fee.total_fee,
                 fee_data_for_subsidy.subsidized_fee.total_fee,
                 token.id,
+                &self.current_subsidy_type,
             )
             .await
             .map_err(|e| {
@@ -625,11 +675,16 @@ impl TxSender {
                 ))
             })?;
         }
+        if let Some((undiscounted_fee, discounted_fee)) = fee_discount {
+            self.store_fee_discount_data(tx.hash(), undiscounted_fee, discounted_fee, token.id)
+                .await;
+        }
 
         // if everything is OK, return the transactions hashes.
         Ok(tx.hash())
     }
 
+    #[tracing::instrument(name = "tx_sender.submit_txs_batch", skip_all)]
     pub async fn submit_txs_batch(
         &self,
         txs: Vec<TxWithSignature>,
@@ -639,6 +694,7 @@ impl TxSender {
         // Bring the received signatures into a vector for simplified work.
         let eth_signatures = EthBatchSignatures::api_arg_to_vec(eth_signatures);
 
+        self.read_only_mode.check().await?;
         if txs.is_empty() {
             return Err(SubmitError::TxAdd(TxAddError::EmptyBatch));
         }
@@ -722,6 +778,7 @@ impl TxSender {
         }
 
         let mut fee_data_for_subsidy: Option<ResponseBatchFee> = None;
+        let mut fee_discount = None;
 
         // Only one token in batch
         if token_fees.len() == 1 {
@@ -730,6 +787,12 @@ impl TxSender {
                 .ticker
                 .get_batch_from_ticker_in_wei(batch_token.into(), transaction_types.clone())
                 .await?;
+            if let Some(undiscounted_fee) = batch_token_fee.undiscounted_fee.clone() {
+                fee_discount = Some((
+                    undiscounted_fee,
+                    batch_token_fee.normal_fee.total_fee.clone(),
+                ));
+            }
 
             let required_fee = if self
                 .should_subsidize_cpk(
@@ -765,6 +828,12 @@ impl TxSender {
                 .ticker
                 .get_batch_from_ticker_in_wei(eth_token.clone(), transaction_types)
                 .await?;
+            if let Some(undiscounted_fee) = required_eth_fee.undiscounted_fee.clone() {
+                fee_discount = Some((
+                    undiscounted_fee,
+                    required_eth_fee.normal_fee.total_fee.clone(),
+                ));
+            }
 
             let required_fee = if self
                 .should_subsidize_cpk(
@@ -890,18 +959,18 @@ impl TxSender {
 
         let batch_hash = TxHash::batch_hash(&tx_hashes);
 
+        let subsidy_token_id = if token_fees_ids.len() == 1 {
+            token_fees_ids[0]
+        } else {
+            // When there are more than token to pay the fee with,
+            // we get the price of the batch in ETH and then convert it to USD.
+            // Since the `subsidies` table contains the token_id field and the only fee which is fetched from the fee_ticker is
+            // in ETH, then we can consider ETH as the token_id of the subsidy. Even though formally this may not be the case.
+            TokenId(0)
+        };
+
         // fee_data_for_subsidy has Some value only if the batch of transactions is subsidised
         if let Some(fee_data) = fee_data_for_subsidy {
-            let subsidy_token_id = if token_fees_ids.len() == 1 {
-                token_fees_ids[0]
-            } else {
-                // When there are more than token to pay the fee with,
-                // we get the price of the batch in ETH and then convert it to USD.
-                // Since the `subsidies` table contains the token_id field and the only fee which is fetched from the fee_ticker is
-                // in ETH, then we can consider ETH as the token_id of the subsidy. Even though formally this may not be the case.
-                TokenId(0)
-            };
-
             // The following two bad scenarios are possible when applying subsidy for the tx:
             // - The subsidy is stored, but the tx is then rejected by the state keeper
             // - The tx is accepted by the state keeper, but the the `store_subsidy_data` returns an error for some reason
@@ -913,6 +982,7 @@ impl TxSender {
                 fee_data.normal_fee.total_fee,
                 fee_data.subsidized_fee.total_fee,
                 subsidy_token_id,
+                &self.current_subsidy_type,
             )
             .await
             .map_err(|e| {
@@ -924,6 +994,15 @@ impl TxSender {
                 ))
             })?;
         }
+        if let Some((undiscounted_fee, discounted_fee)) = fee_discount {
+            self.store_fee_discount_data(
+                batch_hash,
+                undiscounted_fee,
+                discounted_fee,
+                subsidy_token_id,
+            )
+            .await;
+        }
 
         Ok(SubmitBatchResponse {
             transaction_hashes: tx_hashes.into_iter().map(TxHashSerializeWrapper).collect(),
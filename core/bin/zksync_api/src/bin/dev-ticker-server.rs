@@ -2,6 +2,7 @@
 //!
 //! Implements coinmarketcap API for tokens deployed using `deploy-dev-erc20`
 //! Prices are randomly distributed around base values estimated from real world prices.
+//! Also serves the fixed exchange rates of the fiat currencies.
 
 use actix_cors::Cors;
 use actix_web::{middleware, web, App, HttpRequest, HttpResponse, HttpServer, Result};
@@ -167,6 +168,21 @@ async fn handle_coingecko_token_price_query(
     Ok(HttpResponse::Ok().json(resp))
 }
 
+async fn handle_fiat_rates_query(
+    _req: HttpRequest,
+    _data: web::Data<Vec<TokenData>>,
+) -> Result<HttpResponse> {
+    let resp = json!({
+        "base": "USD",
+        "rates": {
+            "EUR": 0.85,
+            "GBP": 0.75,
+            "JPY": 110,
+        }
+    });
+    Ok(HttpResponse::Ok().json(resp))
+}
+
 fn main_scope(sloppy_mode: bool) -> actix_web::Scope {
     let localhost_tokens = load_tokens(&"etc/tokens/localhost.json");
     let rinkeby_tokens = load_tokens(&"etc/tokens/rinkeby.json");
@@ -193,6 +209,10 @@ fn main_scope(sloppy_mode: bool) -> actix_web::Scope {
                 "/api/v3/coins/{coin_id}/market_chart",
                 web::get().to(make_sloppy!(handle_coingecko_token_price_query)),
             )
+            .route(
+                "/latest",
+                web::get().to(make_sloppy!(handle_fiat_rates_query)),
+            )
     } else {
         web::scope("")
             .app_data(web::Data::new(data))
@@ -208,6 +228,7 @@ fn main_scope(sloppy_mode: bool) -> actix_web::Scope {
                 "/api/v3/coins/{coin_id}/market_chart",
                 web::get().to(handle_coingecko_token_price_query),
             )
+            .route("/latest", web::get().to(handle_fiat_rates_query))
     }
 }
 
@@ -0,0 +1,153 @@
+//! Discounts of the transaction fees.
+//!
+//! The discounts are set by the admin API for the tokens, the transaction types and
+//! the transaction addresses, every criterion left unset matches any transaction.
+//! If several discounts match the transaction, the biggest one is applied.
+//! The discounted amounts are stored as the subsidies of the `FEE_DISCOUNT_SUBSIDY_TYPE` type.
+
+// External deps
+use num::{rational::Ratio, BigUint};
+use serde::{Deserialize, Serialize};
+// Workspace deps
+use zksync_storage::fee_discounts::records::StoredFeeDiscount;
+use zksync_types::{Address, OutputFeeType, TokenId};
+
+/// Type of the subsidies recording the discounted parts of the fees.
+pub const FEE_DISCOUNT_SUBSIDY_TYPE: &str = "FeeDiscount";
+
+/// Names of the transaction types the discounts can be set for.
+pub const FEE_TYPE_NAMES: &[&str] = &[
+    "Transfer",
+    "TransferToNew",
+    "Withdraw",
+    "FastWithdraw",
+    "WithdrawNFT",
+    "FastWithdrawNFT",
+    "Swap",
+    "MintNFT",
+    "ChangePubKey",
+];
+
+/// Returns the name of the transaction type, all the `ChangePubKey` variants share the same name.
+pub fn fee_type_name(fee_type: OutputFeeType) -> &'static str {
+    match fee_type {
+        OutputFeeType::Transfer => "Transfer",
+        OutputFeeType::TransferToNew => "TransferToNew",
+        OutputFeeType::Withdraw => "Withdraw",
+        OutputFeeType::FastWithdraw => "FastWithdraw",
+        OutputFeeType::WithdrawNFT => "WithdrawNFT",
+        OutputFeeType::FastWithdrawNFT => "FastWithdrawNFT",
+        OutputFeeType::Swap => "Swap",
+        OutputFeeType::MintNFT => "MintNFT",
+        OutputFeeType::ChangePubKey(_) => "ChangePubKey",
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeDiscount {
+    pub id: i64,
+    pub token_id: Option<TokenId>,
+    pub tx_type: Option<String>,
+    /// Recipient of the transaction, or the account itself for `ChangePubKey`.
+    pub address: Option<Address>,
+    pub discount_percent: u32,
+    pub description: String,
+}
+
+impl FeeDiscount {
+    fn matches(&self, token_id: TokenId, fee_type: OutputFeeType, address: Address) -> bool {
+        self.token_id.map_or(true, |id| id == token_id)
+            && self
+                .tx_type
+                .as_deref()
+                .map_or(true, |name| name == fee_type_name(fee_type))
+            && self
+                .address
+                .map_or(true, |discounted| discounted == address)
+    }
+}
+
+impl From<StoredFeeDiscount> for FeeDiscount {
+    fn from(discount: StoredFeeDiscount) -> Self {
+        Self {
+            id: discount.id,
+            token_id: discount.token_id.map(|id| TokenId(id as u32)),
+            tx_type: discount.tx_type,
+            address: discount
+                .address
+                .map(|address| Address::from_slice(&address)),
+            discount_percent: discount.discount_percent as u32,
+            description: discount.description,
+        }
+    }
+}
+
+/// Returns the biggest discount matching the transaction in percents.
+pub fn discount_percent(
+    discounts: &[FeeDiscount],
+    token_id: TokenId,
+    fee_type: OutputFeeType,
+    address: Address,
+) -> u32 {
+    discounts
+        .iter()
+        .filter(|discount| discount.matches(token_id, fee_type, address))
+        .map(|discount| discount.discount_percent.min(100))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Returns the part of the amount left after the discount.
+pub fn apply_discount(amount: Ratio<BigUint>, discount_percent: u32) -> Ratio<BigUint> {
+    amount * Ratio::new(BigUint::from(100 - discount_percent), BigUint::from(100u32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::{tokens::ChangePubKeyFeeTypeArg, tx::ChangePubKeyType};
+
+    #[test]
+    fn matching_discounts() {
+        let partner = Address::repeat_byte(0x11);
+        let discount =
+            |id, token_id, tx_type: Option<&str>, address, discount_percent| FeeDiscount {
+                id,
+                token_id,
+                tx_type: tx_type.map(str::to_string),
+                address,
+                discount_percent,
+                description: String::new(),
+            };
+        let discounts = vec![
+            discount(1, Some(TokenId(1)), None, None, 10),
+            discount(2, None, Some("ChangePubKey"), None, 50),
+            discount(3, None, Some("Transfer"), Some(partner), 100),
+        ];
+        let cpk = OutputFeeType::ChangePubKey(ChangePubKeyFeeTypeArg::ContractsV4Version(
+            ChangePubKeyType::ECDSA,
+        ));
+
+        let percent = |token_id, fee_type, address| {
+            discount_percent(&discounts, TokenId(token_id), fee_type, address)
+        };
+        assert_eq!(percent(0, OutputFeeType::Withdraw, partner), 0);
+        assert_eq!(percent(1, OutputFeeType::Withdraw, Address::zero()), 10);
+        assert_eq!(percent(0, cpk, Address::zero()), 50);
+        // The biggest of the matching discounts is applied.
+        assert_eq!(percent(1, cpk, Address::zero()), 50);
+        assert_eq!(percent(1, OutputFeeType::Transfer, partner), 100);
+        assert_eq!(percent(1, OutputFeeType::TransferToNew, partner), 10);
+
+        let amount = Ratio::from_integer(BigUint::from(200u32));
+        assert_eq!(
+            apply_discount(amount.clone(), 25),
+            Ratio::from_integer(BigUint::from(150u32))
+        );
+        assert_eq!(
+            apply_discount(amount, 100),
+            Ratio::from_integer(BigUint::from(0u32))
+        );
+    }
+}
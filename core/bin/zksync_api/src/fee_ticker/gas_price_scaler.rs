@@ -0,0 +1,139 @@
+//! Scaling of the gas price used in the fees.
+//!
+//! Due to the high volatility of gas prices, the fees include the risk of the gas price
+//! growing before the transaction is sent to L1. The gas price reported by the oracle is
+//! exponentially smoothed, and the multiplier follows the ratio of the reported price to the
+//! smoothed one: the fees grow faster than the gas price while it is rising, and fall back
+//! to the lower bound once it is stable.
+
+// Built-in deps
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+// External deps
+use num::{BigUint, ToPrimitive};
+use serde::{Deserialize, Serialize};
+// Local deps
+use crate::utils::clock::{Clock, SystemClock};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GasPriceScaleConfig {
+    /// Lower bound of the multiplier in percents, applied while the gas price is stable.
+    pub min_percent: u32,
+    /// Upper bound of the multiplier in percents.
+    pub max_percent: u32,
+    /// Time constant of the smoothing, the smoothed price follows the changes slower
+    /// the bigger it is.
+    pub smoothing: Duration,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct SmoothedGasPrice {
+    value: f64,
+    updated_at: Instant,
+}
+
+/// The scaler is shared by the clones of the fee ticker, so every reported gas price
+/// contributes to the same smoothed value.
+#[derive(Debug, Clone)]
+pub struct GasPriceScaler {
+    config: GasPriceScaleConfig,
+    smoothed: Arc<Mutex<Option<SmoothedGasPrice>>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl GasPriceScaler {
+    pub fn new(config: GasPriceScaleConfig) -> Self {
+        Self::with_clock(config, Arc::new(SystemClock))
+    }
+
+    pub fn with_clock(config: GasPriceScaleConfig, clock: Arc<dyn Clock>) -> Self {
+        assert!(
+            config.min_percent <= config.max_percent,
+            "Incorrect gas price scale bounds"
+        );
+        Self {
+            config,
+            smoothed: Arc::new(Mutex::new(None)),
+            clock,
+        }
+    }
+
+    /// Accounts the gas price reported by the oracle and returns the multiplier in percents.
+    fn update(&self, gas_price: f64) -> u32 {
+        let now = self.clock.now();
+        let mut smoothed = self.smoothed.lock().expect("poisoned smoothed gas price");
+        let value = match *smoothed {
+            Some(previous) => {
+                let elapsed = now.saturating_duration_since(previous.updated_at);
+                let weight = if self.config.smoothing.as_secs_f64() > 0.0 {
+                    1.0 - (-elapsed.as_secs_f64() / self.config.smoothing.as_secs_f64()).exp()
+                } else {
+                    1.0
+                };
+                previous.value + weight * (gas_price - previous.value)
+            }
+            None => gas_price,
+        };
+        *smoothed = Some(SmoothedGasPrice {
+            value,
+            updated_at: now,
+        });
+
+        let min_percent = f64::from(self.config.min_percent);
+        let percent = if value > 0.0 {
+            (min_percent * gas_price / value).round()
+        } else {
+            min_percent
+        };
+        percent
+            .max(min_percent)
+            .min(f64::from(self.config.max_percent)) as u32
+    }
+
+    /// Returns the gas price increased by the current multiplier.
+    pub fn scale(&self, gas_price: &BigUint) -> BigUint {
+        let percent = self.update(gas_price.to_f64().unwrap_or(f64::MAX));
+        metrics::gauge!("ticker.gas_price_scale_percent", f64::from(percent));
+        gas_price * BigUint::from(percent) / BigUint::from(100u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::clock::MockClock;
+
+    fn scaler(clock: &MockClock) -> GasPriceScaler {
+        let config = GasPriceScaleConfig {
+            min_percent: 120,
+            max_percent: 200,
+            smoothing: Duration::from_secs(60),
+        };
+        GasPriceScaler::with_clock(config, Arc::new(clock.clone()))
+    }
+
+    #[test]
+    fn gas_price_scale_follows_smoothed_price() {
+        let clock = MockClock::default();
+        let scaler = scaler(&clock);
+        let gwei = |amount: u32| BigUint::from(amount) * BigUint::from(10u32).pow(9);
+
+        // The first price is the smoothed one, so the lower bound is applied.
+        assert_eq!(scaler.scale(&gwei(100)), gwei(120));
+        clock.advance(Duration::from_secs(60));
+        assert_eq!(scaler.scale(&gwei(100)), gwei(120));
+
+        // The sudden growth of the price is scaled more until the smoothed price catches up.
+        clock.advance(Duration::from_secs(1));
+        let spike = scaler.scale(&gwei(150));
+        assert!(spike > gwei(150) * BigUint::from(170u32) / BigUint::from(100u32));
+        clock.advance(Duration::from_secs(600));
+        assert_eq!(scaler.scale(&gwei(150)), gwei(180));
+
+        // The multiplier doesn't exceed the upper bound, and doesn't fall below the lower one.
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(scaler.scale(&gwei(1000)), gwei(2000));
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(scaler.scale(&gwei(10)), gwei(12));
+    }
+}
@@ -8,6 +8,7 @@ use std::collections::{HashMap, HashSet};
 use std::convert::TryFrom;
 use std::fmt::Display;
 use std::iter::FromIterator;
+use std::sync::Arc;
 use std::time::Duration;
 
 // External deps
@@ -26,27 +27,38 @@ use tokio::time::Instant;
 
 use zksync_config::configs::ticker::TokenPriceSource;
 use zksync_storage::ConnectionPool;
-use zksync_token_db_cache::TokenDBCache;
+use zksync_token_db_cache::{TokenDBCache, TokenDenylist};
 use zksync_types::{
     gas_counter::GasCounter, tokens::ChangePubKeyFeeTypeArg, tx::ChangePubKeyType, Address,
     BatchFee, ChangePubKeyOp, Fee, MintNFTOp, OutputFeeType, SwapOp, Token, TokenId, TokenLike,
-    TransferOp, TransferToNewOp, TxFeeTypes, WithdrawNFTOp, WithdrawOp,
+    TokenPrice, TransferOp, TransferToNewOp, TxFeeTypes, WithdrawNFTOp, WithdrawOp,
 };
 use zksync_utils::{big_decimal_to_ratio, ratio_to_big_decimal};
 
 // Local deps
+use crate::api_server::config_reload::ConfigReloader;
 use crate::fee_ticker::constants::AMORTIZED_COST_PER_CHUNK;
+use crate::fee_ticker::discounts::{apply_discount, discount_percent, FeeDiscount};
+use crate::fee_ticker::gas_price_scaler::{GasPriceScaleConfig, GasPriceScaler};
 pub use crate::fee_ticker::ticker_info::{FeeTickerInfo, TickerInfo};
 use crate::fee_ticker::validator::FeeTokenValidator;
 use crate::fee_ticker::{
     ticker_api::{
-        coingecko::CoinGeckoAPI, coinmarkercap::CoinMarketCapAPI, FeeTickerAPI, TickerApi,
-        CONNECTION_TIMEOUT,
+        chainlink::ChainlinkPriceAPI,
+        coingecko::CoinGeckoAPI,
+        coinmarkercap::CoinMarketCapAPI,
+        price_validator::PriceValidator,
+        provider_manager::PriceProviderManager,
+        uniswap::{UniswapPriceAPI, UniswapVersion},
+        FeeTickerAPI, PerTokenPriceAPI, TickerApi, TokenPriceAPI, CONNECTION_TIMEOUT,
     },
     validator::{watcher::UniswapTokenWatcher, MarketUpdater},
 };
+use crate::utils::ttl_cache::TtlCache;
 
 mod constants;
+pub mod discounts;
+pub mod gas_price_scaler;
 mod ticker_api;
 pub(crate) mod ticker_info;
 pub mod validator;
@@ -143,9 +155,10 @@ pub struct TickerConfig {
     pub scale_fee_coefficient: Ratio<BigUint>,
     pub max_blocks_to_aggregate: u32,
     pub subsidy_cpk_price_usd: Ratio<BigUint>,
+    pub gas_price_scale: GasPriceScaleConfig,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TokenPriceRequestType {
     USDForOneWei,
     USDForOneToken,
@@ -156,6 +169,8 @@ pub struct ResponseFee {
     pub normal_fee: Fee,
     pub subsidized_fee: Fee,
     pub subsidy_size_usd: Ratio<BigUint>,
+    /// The normal fee before the discounts, `None` if no discount is applied.
+    pub undiscounted_fee: Option<BigUint>,
 }
 
 #[derive(Debug, Clone)]
@@ -163,9 +178,11 @@ pub struct ResponseBatchFee {
     pub normal_fee: BatchFee,
     pub subsidized_fee: BatchFee,
     pub subsidy_size_usd: Ratio<BigUint>,
+    /// The normal fee before the discounts, `None` if no discount is applied.
+    pub undiscounted_fee: Option<BigUint>,
 }
 
-#[derive(Debug, Error)]
+#[derive(Debug, Clone, Error)]
 pub enum PriceError {
     #[error("Token not found: {0}")]
     TokenNotFound(String),
@@ -189,11 +206,33 @@ impl PriceError {
     }
 }
 
+/// Counts the requests being handled by the ticker, the gauge is decremented
+/// once the request is finished, even if its future is dropped.
+struct InFlightRequest;
+
+impl InFlightRequest {
+    fn new() -> Self {
+        metrics::increment_gauge!("ticker.requests_in_flight", 1.0);
+        Self
+    }
+}
+
+impl Drop for InFlightRequest {
+    fn drop(&mut self) {
+        metrics::decrement_gauge!("ticker.requests_in_flight", 1.0);
+    }
+}
+
+/// The fee ticker is cloned by every API worker, the clones share the cache of the token prices,
+/// so the prices are loaded from the database once per `TOKEN_PRICE_CACHE_TTL` at most.
 #[derive(Clone)]
 pub struct FeeTicker {
     info: Box<dyn FeeTickerInfo>,
     config: TickerConfig,
     validator: FeeTokenValidator,
+    prices: TtlCache<TokenLike, TokenPrice>,
+    discounts: TtlCache<(), Vec<FeeDiscount>>,
+    gas_price_scaler: GasPriceScaler,
 }
 
 const CPK_CREATE2_FEE_TYPE: OutputFeeType = OutputFeeType::ChangePubKey(
@@ -202,17 +241,92 @@ const CPK_CREATE2_FEE_TYPE: OutputFeeType = OutputFeeType::ChangePubKey(
 // Make no more than (Number of tokens) queries per 5 minutes to database is a good result
 // for updating names for tokens.
 const TOKEN_INVALIDATE_CACHE: Duration = Duration::from_secs(5 * 60);
+// The prices are updated by the price fetchers once in a while, so there is no need
+// to load them from the database for every fee.
+const TOKEN_PRICE_CACHE_TTL: Duration = Duration::from_secs(5);
+// The discounts changed by the admin API take effect after this time at most.
+const FEE_DISCOUNTS_CACHE_TTL: Duration = Duration::from_secs(10);
+
+async fn price_source_api(
+    client: reqwest::Client,
+    source: TokenPriceSource,
+    url: String,
+) -> Arc<dyn TokenPriceAPI + Send + Sync> {
+    match source {
+        TokenPriceSource::CoinMarketCap => Arc::new(CoinMarketCapAPI::new(
+            client,
+            url.parse().expect("Correct CoinMarketCap url"),
+        )),
+        TokenPriceSource::CoinGecko => Arc::new(
+            CoinGeckoAPI::new(client, url.parse().expect("Correct CoinGecko url"))
+                .await
+                .expect("failed to init CoinGecko client"),
+        ),
+        TokenPriceSource::UniswapV2 => {
+            Arc::new(UniswapPriceAPI::new(client, url, UniswapVersion::V2))
+        }
+        TokenPriceSource::UniswapV3 => {
+            Arc::new(UniswapPriceAPI::new(client, url, UniswapVersion::V3))
+        }
+    }
+}
+
+/// Returns the price APIs of the tokens which are not priced by the `token_price_source`.
+fn token_price_apis(
+    client: reqwest::Client,
+    config: &zksync_config::TickerConfig,
+    web3_url: &str,
+) -> Vec<(Address, Arc<dyn TokenPriceAPI + Send + Sync>)> {
+    let mut token_apis: Vec<(Address, Arc<dyn TokenPriceAPI + Send + Sync>)> = Vec::new();
+
+    let uniswap_v2 = Arc::new(UniswapPriceAPI::new(
+        client.clone(),
+        config.uniswap_url.clone(),
+        UniswapVersion::V2,
+    ));
+    for token in &config.uniswap_v2_price_tokens {
+        token_apis.push((*token, uniswap_v2.clone()));
+    }
+    let uniswap_v3 = Arc::new(UniswapPriceAPI::new(
+        client,
+        config.uniswap_v3_url.clone(),
+        UniswapVersion::V3,
+    ));
+    for token in &config.uniswap_v3_price_tokens {
+        token_apis.push((*token, uniswap_v3.clone()));
+    }
+
+    let chainlink_feeds: HashMap<Address, Address> = config.chainlink_feeds().into_iter().collect();
+    if !chainlink_feeds.is_empty() {
+        let tokens: Vec<Address> = chainlink_feeds.keys().copied().collect();
+        let chainlink = Arc::new(
+            ChainlinkPriceAPI::new(web3_url, chainlink_feeds)
+                .expect("failed to init Chainlink client"),
+        );
+        for token in tokens {
+            token_apis.push((token, chainlink.clone()));
+        }
+    }
+    token_apis
+}
 
 #[must_use]
 pub fn run_updaters(
     db_pool: ConnectionPool,
     config: &zksync_config::TickerConfig,
+    web3_url: &str,
+    config_reloader: &ConfigReloader,
 ) -> Vec<JoinHandle<()>> {
     let cache = (db_pool.clone(), TokenDBCache::new(TOKEN_INVALIDATE_CACHE));
 
     let watcher = UniswapTokenWatcher::new(config.uniswap_url.clone());
 
-    let updater = MarketUpdater::new(cache, watcher);
+    let updater = MarketUpdater::new(
+        cache,
+        watcher,
+        BigDecimal::try_from(config.liquidity_volume).expect("Valid f64 for decimal"),
+    )
+    .with_config_updates(config_reloader.subscribe());
     let mut tasks = vec![tokio::spawn(
         updater.keep_updated(config.token_market_update_time),
     )];
@@ -221,26 +335,37 @@ pub fn run_updaters(
         .connect_timeout(CONNECTION_TIMEOUT)
         .build()
         .expect("Failed to build reqwest::Client");
-    let (price_source, base_url) = config.price_source();
-    let price_updater = match price_source {
-        TokenPriceSource::CoinMarketCap => {
-            let token_price_api =
-                CoinMarketCapAPI::new(client, base_url.parse().expect("Correct CoinMarketCap url"));
-
-            let ticker_api = TickerApi::new(db_pool, token_price_api);
-            tokio::spawn(ticker_api.keep_price_updated())
+    let price_sources = config.price_sources();
+    let max_price_age = config.max_price_age();
+    let price_history_retention = config.price_history_retention();
+    let price_validator = PriceValidator::new(
+        config.max_price_deviation_percent,
+        config.price_deviation_window(),
+    );
+    let token_apis = token_price_apis(client.clone(), config, web3_url);
+    let price_updater = tokio::spawn(async move {
+        let mut providers = Vec::with_capacity(price_sources.len());
+        for (source, url) in price_sources {
+            providers.push((source, price_source_api(client.clone(), source, url).await));
         }
+        let provider_manager = PriceProviderManager::new(providers, max_price_age);
+        let token_price_api = token_apis.into_iter().fold(
+            PerTokenPriceAPI::new(Arc::new(provider_manager.clone())),
+            |api, (token, token_api)| api.with_token_api(token, token_api),
+        );
+        let ticker_api = TickerApi::new(
+            db_pool.clone(),
+            token_price_api,
+            price_validator,
+            price_history_retention,
+        );
 
-        TokenPriceSource::CoinGecko => tokio::spawn(async move {
-            let token_price_api =
-                CoinGeckoAPI::new(client, base_url.parse().expect("Correct CoinGecko url"))
-                    .await
-                    .expect("failed to init CoinGecko client");
-            let ticker_api = TickerApi::new(db_pool, token_price_api);
-
-            ticker_api.keep_price_updated().await;
-        }),
-    };
+        futures::future::join(
+            ticker_api.keep_price_updated(),
+            provider_manager.keep_health_saved(db_pool),
+        )
+        .await;
+    });
     tasks.push(price_updater);
     tasks
 }
@@ -251,10 +376,14 @@ impl FeeTicker {
         config: TickerConfig,
         validator: FeeTokenValidator,
     ) -> Self {
+        let gas_price_scaler = GasPriceScaler::new(config.gas_price_scale);
         Self {
             info,
             config,
             validator,
+            prices: TtlCache::new(TOKEN_PRICE_CACHE_TTL),
+            discounts: TtlCache::new(FEE_DISCOUNTS_CACHE_TTL),
+            gas_price_scaler,
         }
     }
 
@@ -263,7 +392,13 @@ impl FeeTicker {
         config: zksync_config::TickerConfig,
         max_blocks_to_aggregate: u32,
         connection_pool: ConnectionPool,
+        config_reloader: &ConfigReloader,
     ) -> Self {
+        let token_denylist = TokenDenylist::new(
+            connection_pool.clone(),
+            config_reloader.current().denylisted_tokens,
+        );
+        config_reloader.register_denylist(&token_denylist);
         let cache = (connection_pool, TokenDBCache::new(TOKEN_INVALIDATE_CACHE));
         let ticker_config = TickerConfig {
             zkp_cost_chunk_usd: Ratio::from_integer(BigUint::from(10u32).pow(3u32)).inv(),
@@ -275,65 +410,151 @@ impl FeeTicker {
             ),
             max_blocks_to_aggregate,
             subsidy_cpk_price_usd: config.subsidy_cpk_price_usd(),
+            gas_price_scale: GasPriceScaleConfig {
+                min_percent: config.min_gas_price_scale_percent,
+                max_percent: config.max_gas_price_scale_percent,
+                smoothing: config.gas_price_smoothing(),
+            },
         };
         let validator = FeeTokenValidator::new(
             cache,
             chrono::Duration::seconds(config.available_liquidity_seconds as i64),
             BigDecimal::try_from(config.liquidity_volume).expect("Valid f64 for decimal"),
             HashSet::from_iter(config.unconditionally_valid_tokens),
-        );
+        )
+        .with_token_denylist(token_denylist)
+        .with_config_updates(config_reloader.subscribe());
         Self::new(info, ticker_config, validator)
     }
 }
 
 impl FeeTicker {
-    /// Increases the gas price by a constant coefficient.
-    /// Due to the high volatility of gas prices, we are include the risk
-    /// in the fee in order not to go into negative territory.
-    fn risk_gas_price_estimate(gas_price: BigUint) -> BigUint {
-        gas_price * BigUint::from(130u32) / BigUint::from(100u32)
+    async fn last_token_price(&self, token: TokenLike) -> Result<TokenPrice, PriceError> {
+        let cache_key = token.to_lowercase();
+        if let Some(price) = self.prices.get(&cache_key) {
+            return Ok(price);
+        }
+
+        let price = self.info.get_last_token_price(token).await?;
+        self.prices.insert(cache_key, price.clone());
+        Ok(price)
     }
 
-    pub async fn get_token_price(
+    /// Returns the active fee discounts. If they can't be loaded, the fee is quoted without
+    /// the discounts rather than not quoted at all.
+    async fn fee_discounts(&self) -> Vec<FeeDiscount> {
+        if let Some(discounts) = self.discounts.get(&()) {
+            return discounts;
+        }
+
+        match self.info.get_fee_discounts().await {
+            Ok(discounts) => {
+                self.discounts.insert((), discounts.clone());
+                discounts
+            }
+            Err(err) => {
+                vlog::error!("Can't load the fee discounts: {}", err);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Returns the number of the price units in one token.
+    async fn price_factor(
         &self,
         token: TokenLike,
-        request_type: TokenPriceRequestType,
-    ) -> Result<BigDecimal, PriceError> {
-        let start = Instant::now();
-        let factor = match request_type {
+        request_type: &TokenPriceRequestType,
+    ) -> Result<BigUint, PriceError> {
+        match request_type {
             TokenPriceRequestType::USDForOneWei => {
                 let token_decimals = self
                     .info
-                    .get_token(token.clone())
+                    .get_token(token)
                     .await
                     .map_err(PriceError::db_error)?
                     .decimals;
-                BigUint::from(10u32).pow(u32::from(token_decimals))
+                Ok(BigUint::from(10u32).pow(u32::from(token_decimals)))
             }
-            TokenPriceRequestType::USDForOneToken => BigUint::from(1u32),
-        };
+            TokenPriceRequestType::USDForOneToken => Ok(BigUint::from(1u32)),
+        }
+    }
+
+    #[tracing::instrument(name = "fee_ticker.get_token_price", skip_all)]
+    pub async fn get_token_price(
+        &self,
+        token: TokenLike,
+        request_type: TokenPriceRequestType,
+    ) -> Result<BigDecimal, PriceError> {
+        let _in_flight = InFlightRequest::new();
+        let start = Instant::now();
+        let factor = self.price_factor(token.clone(), &request_type).await?;
 
         let res = self
-            .info
-            .get_last_token_price(token)
+            .last_token_price(token)
             .await
             .map(|price| ratio_to_big_decimal(&(price.usd_price / factor), 100));
         metrics::histogram!("ticker.get_token_price", start.elapsed());
         res
     }
 
+    /// Returns the prices of the list of tokens, in the order of the request.
+    /// The prices missing in the cache are loaded at once rather than one by one.
+    #[tracing::instrument(name = "fee_ticker.get_token_prices", skip_all)]
+    pub async fn get_token_prices(
+        &self,
+        tokens: Vec<TokenLike>,
+        request_type: TokenPriceRequestType,
+    ) -> Vec<Result<BigDecimal, PriceError>> {
+        let _in_flight = InFlightRequest::new();
+        let start = Instant::now();
+        let cache_keys: Vec<TokenLike> = tokens.iter().map(TokenLike::to_lowercase).collect();
+        let mut prices: Vec<Option<Result<TokenPrice, PriceError>>> = cache_keys
+            .iter()
+            .map(|cache_key| self.prices.get(cache_key).map(Ok))
+            .collect();
+
+        let missing: Vec<usize> = (0..prices.len()).filter(|&i| prices[i].is_none()).collect();
+        if !missing.is_empty() {
+            let missing_tokens = missing.iter().map(|&i| tokens[i].clone()).collect();
+            let loaded = self.info.get_last_token_prices(missing_tokens).await;
+            for (i, price) in missing.into_iter().zip(loaded) {
+                if let Ok(price) = &price {
+                    self.prices.insert(cache_keys[i].clone(), price.clone());
+                }
+                prices[i] = Some(price);
+            }
+        }
+
+        let mut result = Vec::with_capacity(tokens.len());
+        for (token, price) in tokens.into_iter().zip(prices) {
+            let price = price.expect("all the missing prices are loaded");
+            let price = match price {
+                Ok(price) => self
+                    .price_factor(token, &request_type)
+                    .await
+                    .map(|factor| ratio_to_big_decimal(&(price.usd_price / factor), 100)),
+                Err(err) => Err(err),
+            };
+            result.push(price);
+        }
+        metrics::histogram!("ticker.get_token_prices", start.elapsed());
+        result
+    }
+
+    #[tracing::instrument(name = "fee_ticker.get_fee_from_ticker_in_wei", skip_all)]
     pub async fn get_fee_from_ticker_in_wei(
         &self,
         tx_type: TxFeeTypes,
         token: TokenLike,
         recipient: Address,
     ) -> Result<ResponseFee, anyhow::Error> {
+        let _in_flight = InFlightRequest::new();
         let start = Instant::now();
         let zkp_cost_chunk = self.config.zkp_cost_chunk_usd.clone();
         let token = self.info.get_token(token).await?;
 
         let gas_price_wei = self.info.get_gas_price_wei().await?;
-        let scale_gas_price = Self::risk_gas_price_estimate(gas_price_wei.clone());
+        let scale_gas_price = self.gas_price_scaler.scale(&gas_price_wei);
         let wei_price_usd = self.wei_price_usd().await?;
         let token_usd_risk = self.token_usd_risk(&token).await?;
 
@@ -354,13 +575,28 @@ impl FeeTicker {
             normal_gas_fee *= self.config.scale_fee_coefficient.clone();
         }
 
+        let discount = discount_percent(&self.fee_discounts().await, token.id, fee_type, recipient);
         let normal_fee = Fee::new(
             fee_type,
-            zkp_fee,
-            normal_gas_fee,
-            gas_tx_amount,
+            apply_discount(zkp_fee.clone(), discount),
+            apply_discount(normal_gas_fee.clone(), discount),
+            gas_tx_amount.clone(),
             gas_price_wei.clone(),
         );
+        let undiscounted_fee = if discount > 0 {
+            Some(
+                Fee::new(
+                    fee_type,
+                    zkp_fee,
+                    normal_gas_fee,
+                    gas_tx_amount,
+                    gas_price_wei,
+                )
+                .total_fee,
+            )
+        } else {
+            None
+        };
 
         if fee_type == CPK_CREATE2_FEE_TYPE {
             let token_price = self
@@ -394,6 +630,7 @@ impl FeeTicker {
                 normal_fee,
                 subsidized_fee,
                 subsidy_size_usd,
+                undiscounted_fee,
             });
         }
 
@@ -402,21 +639,24 @@ impl FeeTicker {
             normal_fee: normal_fee.clone(),
             subsidized_fee: normal_fee,
             subsidy_size_usd: Ratio::from(BigUint::from(0u32)),
+            undiscounted_fee,
         })
     }
 
+    #[tracing::instrument(name = "fee_ticker.get_batch_from_ticker_in_wei", skip_all)]
     pub async fn get_batch_from_ticker_in_wei(
         &self,
         token: TokenLike,
         txs: Vec<(TxFeeTypes, Address)>,
     ) -> anyhow::Result<ResponseBatchFee> {
+        let _in_flight = InFlightRequest::new();
         let start = Instant::now();
         let zkp_cost_chunk = self.config.zkp_cost_chunk_usd.clone();
 
         let token = self.info.get_token(token).await?;
 
         let gas_price_wei = self.info.get_gas_price_wei().await?;
-        let scale_gas_price = Self::risk_gas_price_estimate(gas_price_wei.clone());
+        let scale_gas_price = self.gas_price_scaler.scale(&gas_price_wei);
         let wei_price_usd = self.wei_price_usd().await?;
         let token_usd_risk = self.token_usd_risk(&token).await?;
 
@@ -424,6 +664,10 @@ impl FeeTicker {
         let mut total_op_chunks = Ratio::from(BigUint::zero());
         let mut total_subsidized_gas_tx_amount = Ratio::from(BigUint::zero());
         let mut total_subsidized_op_chunks = Ratio::from(BigUint::zero());
+        let mut total_undiscounted_gas_tx_amount = Ratio::from(BigUint::zero());
+        let mut total_undiscounted_op_chunks = Ratio::from(BigUint::zero());
+        let mut discounted = false;
+        let discounts = self.fee_discounts().await;
 
         /*
             The input of each operation in the batch gas price is the following:
@@ -476,6 +720,13 @@ impl FeeTicker {
                 gas_tx_amount.into()
             };
 
+            total_undiscounted_gas_tx_amount += &gas_tx_amount;
+            total_undiscounted_op_chunks += &op_chunks;
+            let discount = discount_percent(&discounts, token.id, output_fee_type, recipient);
+            discounted |= discount > 0;
+            let gas_tx_amount = apply_discount(gas_tx_amount, discount);
+            let op_chunks = apply_discount(op_chunks.into(), discount);
+
             total_normal_gas_tx_amount += &gas_tx_amount;
             total_op_chunks += &op_chunks;
 
@@ -496,6 +747,16 @@ impl FeeTicker {
             BatchFee::new(total_zkp_fee, total_gas_fee)
         };
 
+        let undiscounted_fee = if discounted {
+            let total_zkp_fee = (&zkp_cost_chunk * total_undiscounted_op_chunks) * &token_usd_risk;
+            let total_gas_fee =
+                (&wei_price_usd * total_undiscounted_gas_tx_amount * &scale_gas_price)
+                    * &token_usd_risk;
+            Some(BatchFee::new(total_zkp_fee, total_gas_fee).total_fee)
+        } else {
+            None
+        };
+
         let subsidized_fee = {
             let total_zkp_fee = (zkp_cost_chunk * total_subsidized_op_chunks) * &token_usd_risk;
             let total_gas_fee =
@@ -515,14 +776,14 @@ impl FeeTicker {
             normal_fee,
             subsidized_fee,
             subsidy_size_usd,
+            undiscounted_fee,
         })
     }
 
     pub async fn wei_price_usd(&self) -> anyhow::Result<Ratio<BigUint>> {
         let start = Instant::now();
         let res = self
-            .info
-            .get_last_token_price(TokenLike::Id(TokenId(0)))
+            .last_token_price(TokenLike::Id(TokenId(0)))
             .await?
             .usd_price
             / BigUint::from(10u32).pow(18u32);
@@ -540,8 +801,7 @@ impl FeeTicker {
             .unwrap_or_else(|| Ratio::from_integer(1u32.into()));
 
         let token_price_usd = self
-            .info
-            .get_last_token_price(TokenLike::Id(token.id))
+            .last_token_price(TokenLike::Id(token.id))
             .await?
             .usd_price
             / BigUint::from(10u32).pow(u32::from(token.decimals));
@@ -11,7 +11,8 @@ use zksync_utils::{
 };
 
 use crate::fee_ticker::{
-    ticker_api::TokenPriceAPI,
+    discounts::FeeDiscount,
+    ticker_api::{PerTokenPriceAPI, TokenPriceAPI},
     validator::{cache::TokenInMemoryCache, FeeTokenValidator},
 };
 
@@ -114,6 +115,11 @@ pub fn get_test_ticker_config() -> TickerConfig {
         scale_fee_coefficient: Ratio::new(BigUint::from(150u32), BigUint::from(100u32)),
         max_blocks_to_aggregate: 5,
         subsidy_cpk_price_usd: scaled_u64_to_ratio(SUBSIDY_CPK_PRICE_USD_SCALED),
+        gas_price_scale: GasPriceScaleConfig {
+            min_percent: 130,
+            max_percent: 130,
+            smoothing: Duration::from_secs(600),
+        },
     }
 }
 
@@ -130,6 +136,9 @@ impl FeeTickerAPI for MockApiProvider {
 struct MockTickerInfo {
     pub future_blocks: BlocksInFutureAggregatedOperations,
     pub remaining_chunks: Option<usize>,
+    pub discounts: Vec<FeeDiscount>,
+    /// Whether the discounts fail to load.
+    pub discounts_unavailable: bool,
 }
 
 impl Default for MockTickerInfo {
@@ -141,6 +150,8 @@ impl Default for MockTickerInfo {
                 blocks_to_execute: 0,
             },
             remaining_chunks: None,
+            discounts: Vec::new(),
+            discounts_unavailable: false,
         }
     }
 }
@@ -195,6 +206,13 @@ impl FeeTickerInfo for MockTickerInfo {
         unreachable!("incorrect token input")
     }
 
+    async fn get_fee_discounts(&self) -> anyhow::Result<Vec<FeeDiscount>> {
+        if self.discounts_unavailable {
+            anyhow::bail!("The discounts are unavailable");
+        }
+        Ok(self.discounts.clone())
+    }
+
     fn into_any(self: Box<Self>) -> Box<dyn Any> {
         self
     }
@@ -213,6 +231,18 @@ impl TokenPriceAPI for ErrorTickerApi {
     }
 }
 
+struct FixedPriceApi(u32);
+
+#[async_trait::async_trait]
+impl TokenPriceAPI for FixedPriceApi {
+    async fn get_price(&self, _token: &Token) -> Result<TokenPrice, PriceError> {
+        Ok(TokenPrice {
+            usd_price: Ratio::from_integer(self.0.into()),
+            last_updated: Utc::now(),
+        })
+    }
+}
+
 fn get_normal_and_subsidy_fee(
     ticker: &mut FeeTicker,
     tx_type: TxFeeTypes,
@@ -708,3 +738,188 @@ fn test_zero_price_token_fee() {
     ))
     .unwrap_err();
 }
+
+#[test]
+fn test_token_prices_are_shared_between_clones() {
+    let validator = FeeTokenValidator::new(
+        TokenInMemoryCache::new(),
+        chrono::Duration::seconds(100),
+        BigDecimal::from(100),
+        Default::default(),
+    );
+
+    let config = get_test_ticker_config();
+    let ticker = FeeTicker::new(Box::new(MockTickerInfo::default()), config, validator);
+    let clone = ticker.clone();
+
+    let token = TestToken::eth();
+    let price =
+        block_on(ticker.get_token_price(token.id.into(), TokenPriceRequestType::USDForOneToken))
+            .expect("failed to get token price");
+
+    // The price loaded by one ticker is served to the others from the cache.
+    let cached = clone
+        .prices
+        .get(&TokenLike::from(token.id))
+        .expect("price is not cached");
+    assert_eq!(cached.usd_price, token.price_usd);
+    assert_eq!(
+        block_on(clone.get_token_price(token.id.into(), TokenPriceRequestType::USDForOneToken))
+            .unwrap(),
+        price
+    );
+}
+
+#[test]
+fn test_batch_token_prices() {
+    let validator = FeeTokenValidator::new(
+        TokenInMemoryCache::new(),
+        chrono::Duration::seconds(100),
+        BigDecimal::from(100),
+        Default::default(),
+    );
+
+    let config = get_test_ticker_config();
+    let ticker = FeeTicker::new(Box::new(MockTickerInfo::default()), config, validator);
+
+    let tokens: Vec<TokenLike> = TestToken::all_tokens()
+        .into_iter()
+        .map(|token| token.id.into())
+        .collect();
+    // Some of the prices are already cached.
+    block_on(ticker.get_token_price(tokens[1].clone(), TokenPriceRequestType::USDForOneWei))
+        .unwrap();
+
+    for request_type in [
+        TokenPriceRequestType::USDForOneToken,
+        TokenPriceRequestType::USDForOneWei,
+    ] {
+        let prices = block_on(ticker.get_token_prices(tokens.clone(), request_type.clone()));
+        assert_eq!(prices.len(), tokens.len());
+        for (token, price) in tokens.iter().zip(prices) {
+            let expected =
+                block_on(ticker.get_token_price(token.clone(), request_type.clone())).unwrap();
+            assert_eq!(price.unwrap(), expected, "wrong price of {:?}", token);
+        }
+    }
+    assert_eq!(ticker.prices.len(), tokens.len());
+}
+
+#[test]
+fn test_per_token_price_api() {
+    let listed_token = Address::repeat_byte(1);
+    let unlisted_token = Address::repeat_byte(2);
+    let api = PerTokenPriceAPI::new(Arc::new(FixedPriceApi(1)))
+        .with_token_api(listed_token, Arc::new(FixedPriceApi(2)))
+        .with_token_api(unlisted_token, Arc::new(ErrorTickerApi));
+
+    let usd_price = |address: Address| {
+        let token = Token::new(TokenId(1), address, "TEST", 18, TokenKind::ERC20);
+        block_on(api.get_price(&token)).unwrap().usd_price
+    };
+    assert_eq!(usd_price(listed_token), Ratio::from_integer(2u32.into()));
+    // The default API is used for the tokens without their own API, and if their API fails.
+    assert_eq!(
+        usd_price(Address::default()),
+        Ratio::from_integer(1u32.into())
+    );
+    assert_eq!(usd_price(unlisted_token), Ratio::from_integer(1u32.into()));
+}
+
+#[test]
+fn test_fee_discounts() {
+    let validator = FeeTokenValidator::new(
+        TokenInMemoryCache::new(),
+        chrono::Duration::seconds(100),
+        BigDecimal::from(100),
+        Default::default(),
+    );
+    let token = TestToken::hex();
+    let partner = Address::repeat_byte(0x11);
+
+    let info = MockTickerInfo {
+        discounts: vec![FeeDiscount {
+            id: 1,
+            token_id: Some(token.id),
+            tx_type: Some("Withdraw".to_string()),
+            address: None,
+            discount_percent: 50,
+            description: String::new(),
+        }],
+        ..MockTickerInfo::default()
+    };
+    let ticker = FeeTicker::new(Box::new(info), get_test_ticker_config(), validator);
+    let fee = |tx_type| {
+        block_on(ticker.get_fee_from_ticker_in_wei(tx_type, token.id.into(), partner)).unwrap()
+    };
+
+    let withdraw_fee = fee(TxFeeTypes::Withdraw);
+    let undiscounted_fee = withdraw_fee
+        .undiscounted_fee
+        .clone()
+        .expect("discount is not applied");
+    // The fees are rounded to be packable, so the discounted fee is roughly the half.
+    let discounted_fee = Ratio::from(withdraw_fee.normal_fee.total_fee.clone());
+    let half_fee = Ratio::new(undiscounted_fee, BigUint::from(2u32));
+    let tolerance = &half_fee / BigUint::from(100u32);
+    assert!(discounted_fee > &half_fee - &tolerance);
+    assert!(discounted_fee < &half_fee + &tolerance);
+    assert_eq!(
+        withdraw_fee.subsidized_fee.total_fee,
+        withdraw_fee.normal_fee.total_fee
+    );
+
+    let transfer_fee = fee(TxFeeTypes::Transfer);
+    assert!(transfer_fee.undiscounted_fee.is_none());
+
+    // The batch fee is discounted for the matching transactions only.
+    let batch_fee = block_on(ticker.get_batch_from_ticker_in_wei(
+        token.id.into(),
+        vec![
+            (TxFeeTypes::Withdraw, partner),
+            (TxFeeTypes::Transfer, partner),
+        ],
+    ))
+    .unwrap();
+    assert!(batch_fee.undiscounted_fee.unwrap() > batch_fee.normal_fee.total_fee);
+    assert!(batch_fee.normal_fee.total_fee > withdraw_fee.normal_fee.total_fee);
+}
+
+#[test]
+fn test_fee_discounts_unavailable() {
+    let token = TestToken::hex();
+    let partner = Address::repeat_byte(0x11);
+    let ticker = |discounts_unavailable| {
+        let validator = FeeTokenValidator::new(
+            TokenInMemoryCache::new(),
+            chrono::Duration::seconds(100),
+            BigDecimal::from(100),
+            Default::default(),
+        );
+        let info = MockTickerInfo {
+            discounts_unavailable,
+            ..MockTickerInfo::default()
+        };
+        FeeTicker::new(Box::new(info), get_test_ticker_config(), validator)
+    };
+    let (ticker, working_ticker) = (ticker(true), ticker(false));
+
+    // The fee is quoted without the discount.
+    let fee =
+        block_on(ticker.get_fee_from_ticker_in_wei(TxFeeTypes::Withdraw, token.id.into(), partner))
+            .unwrap();
+    let expected_fee = block_on(working_ticker.get_fee_from_ticker_in_wei(
+        TxFeeTypes::Withdraw,
+        token.id.into(),
+        partner,
+    ))
+    .unwrap();
+    assert!(fee.undiscounted_fee.is_none());
+    assert_eq!(fee.normal_fee.total_fee, expected_fee.normal_fee.total_fee);
+
+    let batch_fee = block_on(
+        ticker.get_batch_from_ticker_in_wei(token.id.into(), vec![(TxFeeTypes::Withdraw, partner)]),
+    )
+    .unwrap();
+    assert!(batch_fee.undiscounted_fee.is_none());
+}
@@ -0,0 +1,157 @@
+// Built-in deps
+use std::collections::HashMap;
+use std::time::Instant;
+// External deps
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use ethabi::{ParamType, Token as AbiToken};
+use num::{rational::Ratio, BigUint};
+use web3::{
+    transports::Http,
+    types::{Bytes, CallRequest, U256},
+    Web3,
+};
+// Workspace deps
+use super::TokenPriceAPI;
+use crate::fee_ticker::PriceError;
+use zksync_types::{Address, Token, TokenPrice};
+
+/// Chainlink feeds are updated at least once a day, the older answer means that the feed is stuck.
+const MAX_ANSWER_AGE_SECS: i64 = 24 * 60 * 60;
+
+/// Prices of the tokens reported by the Chainlink USD price feeds.
+/// https://docs.chain.link/docs/ethereum-addresses/
+#[derive(Debug, Clone)]
+pub struct ChainlinkPriceAPI {
+    web3: Web3<Http>,
+    /// Addresses of the price feeds of the tokens.
+    feeds: HashMap<Address, Address>,
+}
+
+impl ChainlinkPriceAPI {
+    pub fn new(web3_url: &str, feeds: HashMap<Address, Address>) -> anyhow::Result<Self> {
+        let transport = Http::new(web3_url)?;
+        Ok(Self {
+            web3: Web3::new(transport),
+            feeds,
+        })
+    }
+
+    async fn call_feed(&self, feed: Address, function: &str) -> Result<Vec<u8>, PriceError> {
+        let request = CallRequest {
+            to: Some(feed),
+            data: Some(Bytes(ethabi::short_signature(function, &[]).to_vec())),
+            ..Default::default()
+        };
+        let response =
+            self.web3.eth().call(request, None).await.map_err(|err| {
+                PriceError::api_error(format!("Chainlink feed call failed: {}", err))
+            })?;
+        Ok(response.0)
+    }
+}
+
+#[async_trait]
+impl TokenPriceAPI for ChainlinkPriceAPI {
+    async fn get_price(&self, token: &Token) -> Result<TokenPrice, PriceError> {
+        let start = Instant::now();
+        let feed = *self.feeds.get(&token.address).ok_or_else(|| {
+            PriceError::token_not_found(format!(
+                "Token '{}, {:?}' has no Chainlink price feed",
+                token.symbol, token.address
+            ))
+        })?;
+
+        let decimals = self.call_feed(feed, "decimals").await?;
+        let round_data = self.call_feed(feed, "latestRoundData").await?;
+        let price = decode_price(&decimals, &round_data)?;
+        if Utc::now() - price.last_updated > chrono::Duration::seconds(MAX_ANSWER_AGE_SECS) {
+            return Err(PriceError::api_error(format!(
+                "Chainlink price feed of '{}' is stale, last updated at {}",
+                token.symbol, price.last_updated
+            )));
+        }
+
+        metrics::histogram!("ticker.chainlink.request", start.elapsed());
+        Ok(price)
+    }
+}
+
+fn u256_to_biguint(value: U256) -> BigUint {
+    let mut bytes = [0u8; 32];
+    value.to_big_endian(&mut bytes);
+    BigUint::from_bytes_be(&bytes)
+}
+
+/// Decodes the answers of the `decimals()` and `latestRoundData()` feed functions.
+fn decode_price(decimals: &[u8], round_data: &[u8]) -> Result<TokenPrice, PriceError> {
+    let decode_error = |err: ethabi::Error| {
+        PriceError::api_error(format!("Can't decode Chainlink feed answer: {}", err))
+    };
+    let decimals = match ethabi::decode(&[ParamType::Uint(8)], decimals)
+        .map_err(decode_error)?
+        .as_slice()
+    {
+        [AbiToken::Uint(decimals)] => decimals.as_u32(),
+        _ => unreachable!("decoded according to the types"),
+    };
+    let (answer, updated_at) = match ethabi::decode(
+        &[
+            ParamType::Uint(80),
+            ParamType::Int(256),
+            ParamType::Uint(256),
+            ParamType::Uint(256),
+            ParamType::Uint(80),
+        ],
+        round_data,
+    )
+    .map_err(decode_error)?
+    .as_slice()
+    {
+        [_, AbiToken::Int(answer), _, AbiToken::Uint(updated_at), _] => (*answer, *updated_at),
+        _ => unreachable!("decoded according to the types"),
+    };
+
+    // The answer is the signed integer in two's complement.
+    if answer.bit(255) {
+        return Err(PriceError::api_error(
+            "Chainlink feed answered with negative price",
+        ));
+    }
+    let usd_price = Ratio::new(u256_to_biguint(answer), BigUint::from(10u32).pow(decimals));
+    let naive_last_updated = NaiveDateTime::from_timestamp(updated_at.low_u64() as i64, 0);
+    Ok(TokenPrice {
+        usd_price,
+        last_updated: DateTime::<Utc>::from_utc(naive_last_updated, Utc),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn feed_answer_decoding() {
+        let decimals = ethabi::encode(&[AbiToken::Uint(8.into())]);
+        let round_data = |answer: U256| {
+            ethabi::encode(&[
+                AbiToken::Uint(1.into()),
+                AbiToken::Int(answer),
+                AbiToken::Uint(1_600_000_000.into()),
+                AbiToken::Uint(1_600_000_000.into()),
+                AbiToken::Uint(1.into()),
+            ])
+        };
+
+        // 1234.5 USD with 8 decimals.
+        let price = decode_price(&decimals, &round_data(123_450_000_000u64.into())).unwrap();
+        assert_eq!(
+            price.usd_price,
+            Ratio::new(BigUint::from(12345u32), BigUint::from(10u32))
+        );
+        assert_eq!(price.last_updated.timestamp(), 1_600_000_000);
+
+        // -1 in two's complement.
+        assert!(decode_price(&decimals, &round_data(U256::max_value())).is_err());
+    }
+}
@@ -3,15 +3,23 @@ use super::PriceError;
 use anyhow::format_err;
 use async_trait::async_trait;
 use chrono::Utc;
-use num::rational::Ratio;
+use num::{rational::Ratio, Zero};
 
+use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use zksync_storage::ConnectionPool;
-use zksync_types::{Token, TokenId, TokenPrice};
+use zksync_types::{Address, Token, TokenId, TokenPrice};
 
+use price_validator::PriceValidator;
+
+pub mod chainlink;
 pub mod coingecko;
 pub mod coinmarkercap;
+pub mod price_validator;
+pub mod provider_manager;
+pub mod uniswap;
 
 const UPDATE_PRICE_INTERVAL_SECS: u64 = 10 * 60;
 /// The limit of time we are willing to wait for response.
@@ -24,6 +32,53 @@ pub trait TokenPriceAPI {
     async fn get_price(&self, token: &Token) -> Result<TokenPrice, PriceError>;
 }
 
+/// Price API which takes the prices of some tokens from the other sources than the default one.
+pub struct PerTokenPriceAPI {
+    default_api: Arc<dyn TokenPriceAPI + Send + Sync>,
+    token_apis: HashMap<Address, Arc<dyn TokenPriceAPI + Send + Sync>>,
+}
+
+impl PerTokenPriceAPI {
+    pub fn new(default_api: Arc<dyn TokenPriceAPI + Send + Sync>) -> Self {
+        Self {
+            default_api,
+            token_apis: HashMap::new(),
+        }
+    }
+
+    /// Sets the API used for the token instead of the default one.
+    pub fn with_token_api(
+        mut self,
+        token: Address,
+        token_api: Arc<dyn TokenPriceAPI + Send + Sync>,
+    ) -> Self {
+        self.token_apis.insert(token, token_api);
+        self
+    }
+}
+
+#[async_trait]
+impl TokenPriceAPI for PerTokenPriceAPI {
+    async fn get_price(&self, token: &Token) -> Result<TokenPrice, PriceError> {
+        let token_api = match self.token_apis.get(&token.address) {
+            Some(token_api) => token_api,
+            None => return self.default_api.get_price(token).await,
+        };
+        match token_api.get_price(token).await {
+            Ok(price) => Ok(price),
+            // The price from the default API is better than no price at all.
+            Err(err) => {
+                vlog::warn!(
+                    "Can't get price for token {} from its price API, using the default one: {}",
+                    token.symbol,
+                    err
+                );
+                self.default_api.get_price(token).await
+            }
+        }
+    }
+}
+
 /// Api responsible for querying for TokenPrices
 #[async_trait]
 pub trait FeeTickerAPI {
@@ -35,13 +90,23 @@ pub(super) struct TickerApi<T: TokenPriceAPI> {
     db_pool: ConnectionPool,
 
     token_price_api: T,
+    price_validator: PriceValidator,
+    /// The price snapshots older than this are removed from the price history.
+    price_history_retention: Duration,
 }
 
 impl<T: TokenPriceAPI> TickerApi<T> {
-    pub fn new(db_pool: ConnectionPool, token_price_api: T) -> Self {
+    pub fn new(
+        db_pool: ConnectionPool,
+        token_price_api: T,
+        price_validator: PriceValidator,
+        price_history_retention: Duration,
+    ) -> Self {
         Self {
             db_pool,
             token_price_api,
+            price_validator,
+            price_history_retention,
         }
     }
 
@@ -58,6 +123,20 @@ impl<T: TokenPriceAPI> TickerApi<T> {
             .map_err(|err| PriceError::DBError(err.to_string()))?;
         Ok(tokens.into_values().collect())
     }
+
+    async fn get_stored_price(&self, token_id: TokenId) -> Result<Option<TokenPrice>, PriceError> {
+        let mut storage = self
+            .db_pool
+            .access_storage()
+            .await
+            .map_err(PriceError::db_error)?;
+        storage
+            .tokens_schema()
+            .get_historical_ticker_price(token_id)
+            .await
+            .map_err(PriceError::db_error)
+    }
+
     async fn update_stored_value(
         &self,
         token_id: TokenId,
@@ -69,14 +148,51 @@ impl<T: TokenPriceAPI> TickerApi<T> {
             .await
             .map_err(|e| format_err!("Can't access storage: {}", e))?;
 
-        storage
+        let mut transaction = storage
+            .start_transaction()
+            .await
+            .map_err(|e| format_err!("Can't start transaction: {}", e))?;
+        // Tokens that are not listed by the API have no price to chart.
+        if !price.usd_price.is_zero() {
+            transaction
+                .tokens_schema()
+                .store_ticker_price_snapshot(token_id, price.clone())
+                .await
+                .map_err(|e| format_err!("Can't store ticker price snapshot: {}", e))?;
+        }
+        transaction
             .tokens_schema()
             .update_historical_ticker_price(token_id, price)
             .await
             .map_err(|e| format_err!("Can't update historical ticker price from storage: {}", e))?;
+        transaction
+            .commit()
+            .await
+            .map_err(|e| format_err!("Can't commit transaction: {}", e))?;
+
+        Ok(())
+    }
 
+    /// Removes the price snapshots which are older than the retention period.
+    async fn remove_old_price_history(&self) -> anyhow::Result<()> {
+        let retention = chrono::Duration::from_std(self.price_history_retention)?;
+        let mut storage = self
+            .db_pool
+            .access_storage()
+            .await
+            .map_err(|e| format_err!("Can't access storage: {}", e))?;
+        let removed = storage
+            .tokens_schema()
+            .remove_ticker_price_history(Utc::now() - retention)
+            .await
+            .map_err(|e| format_err!("Can't remove the price history: {}", e))?;
+        if removed > 0 {
+            vlog::debug!("Removed {} outdated price snapshots", removed);
+        }
         Ok(())
     }
+
+    #[tracing::instrument(name = "fee_ticker.update_price", skip_all, fields(token = %token.symbol))]
     async fn update_price(&self, token: &Token) -> Result<(), PriceError> {
         let start = Instant::now();
         let api_price = match self.token_price_api.get_price(token).await {
@@ -90,6 +206,19 @@ impl<T: TokenPriceAPI> TickerApi<T> {
             Err(e) => return Err(e),
         };
 
+        let stored_price = self.get_stored_price(token.id).await?;
+        if let Err(reason) = self
+            .price_validator
+            .check(stored_price.as_ref(), &api_price)
+        {
+            // The last accepted price is kept until the new one passes the check.
+            metrics::increment_counter!("ticker.price_rejected", "token" => token.symbol.clone());
+            return Err(PriceError::api_error(format!(
+                "Rejected the price update of {}: {}",
+                token.symbol, reason
+            )));
+        }
+
         self.update_stored_value(token.id, api_price.clone())
             .await
             .map_err(|err| PriceError::DBError(err.to_string()))?;
@@ -115,6 +244,9 @@ impl<T: TokenPriceAPI + Send + Sync> FeeTickerAPI for TickerApi<T> {
             } else {
                 vlog::warn!("Can't get info from the database; waiting for the next iteration");
             };
+            if let Err(e) = self.remove_old_price_history().await {
+                vlog::error!("Can't remove the outdated price history. Error: {}", e);
+            }
             tokio::time::sleep(Duration::from_secs(UPDATE_PRICE_INTERVAL_SECS)).await;
         }
     }
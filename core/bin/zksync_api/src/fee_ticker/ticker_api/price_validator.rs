@@ -0,0 +1,110 @@
+//! Sanity check of the token prices received from the price APIs.
+//!
+//! A sudden jump of the price is more likely to be caused by the broken API or
+//! the manipulated market than by the real price change, so such an update is rejected
+//! until the last accepted price gets older than the configured window.
+
+// Built-in deps
+use std::time::Duration;
+// External deps
+use chrono::Utc;
+use num::{rational::Ratio, BigUint, Zero};
+// Workspace deps
+use zksync_types::TokenPrice;
+
+#[derive(Debug, Clone)]
+pub struct PriceValidator {
+    /// Maximum allowed deviation from the last accepted price in percents, `0` disables the check.
+    max_deviation_percent: u32,
+    /// Updates are compared only with the prices accepted during this window.
+    window: chrono::Duration,
+}
+
+impl PriceValidator {
+    pub fn new(max_deviation_percent: u32, window: Duration) -> Self {
+        Self {
+            max_deviation_percent,
+            window: chrono::Duration::from_std(window).expect("Incorrect price deviation window"),
+        }
+    }
+
+    /// Checks the new price against the last accepted one.
+    /// Returns the description of the rejection reason if the price should not be used.
+    pub fn check(
+        &self,
+        last_accepted: Option<&TokenPrice>,
+        price: &TokenPrice,
+    ) -> Result<(), String> {
+        let last_accepted = match last_accepted {
+            Some(last_accepted) => last_accepted,
+            None => return Ok(()),
+        };
+        if self.max_deviation_percent == 0
+            || last_accepted.usd_price.is_zero()
+            || Utc::now() - last_accepted.last_updated > self.window
+        {
+            return Ok(());
+        }
+
+        let deviation = if price.usd_price > last_accepted.usd_price {
+            &price.usd_price - &last_accepted.usd_price
+        } else {
+            &last_accepted.usd_price - &price.usd_price
+        };
+        let max_deviation = &last_accepted.usd_price
+            * Ratio::new(
+                BigUint::from(self.max_deviation_percent),
+                BigUint::from(100u32),
+            );
+        if deviation > max_deviation {
+            return Err(format!(
+                "price {} deviates more than {}% from the price {} accepted at {}",
+                price.usd_price,
+                self.max_deviation_percent,
+                last_accepted.usd_price,
+                last_accepted.last_updated
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn price(usd_price: u32, age: chrono::Duration) -> TokenPrice {
+        TokenPrice {
+            usd_price: Ratio::from_integer(BigUint::from(usd_price)),
+            last_updated: Utc::now() - age,
+        }
+    }
+
+    #[test]
+    fn price_deviation() {
+        let validator = PriceValidator::new(20, Duration::from_secs(3600));
+        let last_accepted = price(100, chrono::Duration::minutes(10));
+
+        assert!(validator
+            .check(None, &price(1000, chrono::Duration::zero()))
+            .is_ok());
+        for usd_price in &[80, 100, 120] {
+            let new_price = price(*usd_price, chrono::Duration::zero());
+            assert!(validator.check(Some(&last_accepted), &new_price).is_ok());
+        }
+        for usd_price in &[0, 79, 121] {
+            let new_price = price(*usd_price, chrono::Duration::zero());
+            assert!(validator.check(Some(&last_accepted), &new_price).is_err());
+        }
+
+        // The price accepted before the window, or no accepted price at all, doesn't restrict the update.
+        let old_price = price(100, chrono::Duration::hours(2));
+        let new_price = price(1000, chrono::Duration::zero());
+        assert!(validator.check(Some(&old_price), &new_price).is_ok());
+        let unlisted = price(0, chrono::Duration::minutes(10));
+        assert!(validator.check(Some(&unlisted), &new_price).is_ok());
+
+        let disabled = PriceValidator::new(0, Duration::from_secs(3600));
+        assert!(disabled.check(Some(&last_accepted), &new_price).is_ok());
+    }
+}
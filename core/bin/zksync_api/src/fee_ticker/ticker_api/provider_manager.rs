@@ -0,0 +1,306 @@
+//! Chain of the price providers used one after another until one of them
+//! returns the fresh price of the token.
+//!
+//! The health of every provider is tracked and periodically saved to the database,
+//! so that it can be reported by the API servers.
+
+// Built-in deps
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+// External deps
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+// Workspace deps
+use zksync_config::configs::ticker::TokenPriceSource;
+use zksync_storage::{tokens::records::StoredPriceProviderHealth, ConnectionPool};
+use zksync_types::{Token, TokenPrice};
+// Local deps
+use super::TokenPriceAPI;
+use crate::{
+    fee_ticker::PriceError,
+    utils::clock::{Clock, SystemClock},
+};
+
+/// How often the health of the providers is saved to the database.
+const SAVE_HEALTH_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Default)]
+struct ProviderHealth {
+    last_success_at: Option<DateTime<Utc>>,
+    last_failure_at: Option<DateTime<Utc>>,
+    last_error: Option<String>,
+    consecutive_failures: u32,
+}
+
+#[derive(Clone)]
+pub struct PriceProviderManager {
+    /// Providers in the order of preference.
+    providers: Vec<(TokenPriceSource, Arc<dyn TokenPriceAPI + Send + Sync>)>,
+    max_price_age: chrono::Duration,
+    health: Arc<Mutex<Vec<ProviderHealth>>>,
+    /// The prices are checked for staleness against the time of this clock.
+    clock: Arc<dyn Clock>,
+}
+
+impl PriceProviderManager {
+    pub fn new(
+        providers: Vec<(TokenPriceSource, Arc<dyn TokenPriceAPI + Send + Sync>)>,
+        max_price_age: Duration,
+    ) -> Self {
+        Self::with_clock(providers, max_price_age, Arc::new(SystemClock))
+    }
+
+    pub fn with_clock(
+        providers: Vec<(TokenPriceSource, Arc<dyn TokenPriceAPI + Send + Sync>)>,
+        max_price_age: Duration,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        let health = vec![ProviderHealth::default(); providers.len()];
+        Self {
+            providers,
+            max_price_age: chrono::Duration::from_std(max_price_age)
+                .expect("Incorrect max price age"),
+            health: Arc::new(Mutex::new(health)),
+            clock,
+        }
+    }
+
+    fn record_success(&self, provider: usize) {
+        let mut health = self.health.lock().expect("poisoned price provider health");
+        let health = &mut health[provider];
+        health.last_success_at = Some(self.clock.utc_now());
+        health.consecutive_failures = 0;
+    }
+
+    fn record_failure(&self, provider: usize, error: &PriceError) {
+        let source = self.providers[provider].0;
+        vlog::warn!("Price provider {:?} failed: {}", source, error);
+        metrics::increment_counter!("ticker.price_provider.failures", "provider" => format!("{:?}", source));
+
+        let mut health = self.health.lock().expect("poisoned price provider health");
+        let health = &mut health[provider];
+        health.last_failure_at = Some(self.clock.utc_now());
+        health.last_error = Some(error.to_string());
+        health.consecutive_failures += 1;
+    }
+
+    fn stored_health(&self) -> Vec<StoredPriceProviderHealth> {
+        let updated_at = self.clock.utc_now();
+        let health = self.health.lock().expect("poisoned price provider health");
+        self.providers
+            .iter()
+            .zip(health.iter())
+            .enumerate()
+            .map(
+                |(priority, ((source, _), health))| StoredPriceProviderHealth {
+                    provider: format!("{:?}", source),
+                    priority: priority as i32,
+                    last_success_at: health.last_success_at,
+                    last_failure_at: health.last_failure_at,
+                    last_error: health.last_error.clone(),
+                    consecutive_failures: health.consecutive_failures as i32,
+                    updated_at,
+                },
+            )
+            .collect()
+    }
+
+    async fn save_health(&self, db_pool: &ConnectionPool) -> anyhow::Result<()> {
+        let health = self.stored_health();
+        let mut storage = db_pool.access_storage().await?;
+        storage
+            .tokens_schema()
+            .store_price_providers_health(&health)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn keep_health_saved(self, db_pool: ConnectionPool) {
+        let mut timer = tokio::time::interval(SAVE_HEALTH_INTERVAL);
+        loop {
+            timer.tick().await;
+            if let Err(err) = self.save_health(&db_pool).await {
+                vlog::warn!("Can't save the health of the price providers: {}", err);
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl TokenPriceAPI for PriceProviderManager {
+    async fn get_price(&self, token: &Token) -> Result<TokenPrice, PriceError> {
+        let mut last_error = None;
+        let mut not_found = None;
+        for (provider, (source, api)) in self.providers.iter().enumerate() {
+            match api.get_price(token).await {
+                Ok(price) if self.clock.utc_now() - price.last_updated <= self.max_price_age => {
+                    self.record_success(provider);
+                    return Ok(price);
+                }
+                Ok(price) => {
+                    let error = PriceError::api_error(format!(
+                        "{:?} price of {} is stale, last updated at {}",
+                        source, token.symbol, price.last_updated
+                    ));
+                    self.record_failure(provider, &error);
+                    last_error = Some(error);
+                }
+                // The provider doesn't list the token, it's not a failure of the provider.
+                Err(error @ PriceError::TokenNotFound(_)) => {
+                    not_found.get_or_insert(error);
+                }
+                Err(error) => {
+                    self.record_failure(provider, &error);
+                    last_error = Some(error);
+                }
+            }
+        }
+
+        // The token is reported as not found only if none of the providers has failed,
+        // otherwise the price stored earlier would be reset.
+        Err(last_error
+            .or(not_found)
+            .unwrap_or_else(|| PriceError::api_error("No price providers")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::clock::MockClock;
+    use num::{rational::Ratio, BigUint};
+    use zksync_types::{TokenId, TokenKind};
+
+    const MAX_PRICE_AGE: Duration = Duration::from_secs(3600);
+
+    enum MockPriceApi {
+        /// The price updated the given time ago.
+        Price(u32, chrono::Duration),
+        /// The price updated at the given time.
+        PriceAt(u32, DateTime<Utc>),
+        Failure,
+        NotListed,
+    }
+
+    #[async_trait]
+    impl TokenPriceAPI for MockPriceApi {
+        async fn get_price(&self, _token: &Token) -> Result<TokenPrice, PriceError> {
+            match self {
+                Self::Price(usd_price, age) => Ok(TokenPrice {
+                    usd_price: Ratio::from_integer(BigUint::from(*usd_price)),
+                    last_updated: Utc::now() - *age,
+                }),
+                Self::PriceAt(usd_price, last_updated) => Ok(TokenPrice {
+                    usd_price: Ratio::from_integer(BigUint::from(*usd_price)),
+                    last_updated: *last_updated,
+                }),
+                Self::Failure => Err(PriceError::api_error("API request failed")),
+                Self::NotListed => Err(PriceError::token_not_found("Token is not listed")),
+            }
+        }
+    }
+
+    fn providers(
+        apis: Vec<MockPriceApi>,
+    ) -> Vec<(TokenPriceSource, Arc<dyn TokenPriceAPI + Send + Sync>)> {
+        let sources = [
+            TokenPriceSource::CoinGecko,
+            TokenPriceSource::CoinMarketCap,
+            TokenPriceSource::UniswapV3,
+        ];
+        sources
+            .iter()
+            .copied()
+            .zip(apis)
+            .map(|(source, api)| {
+                (
+                    source,
+                    Arc::new(api) as Arc<dyn TokenPriceAPI + Send + Sync>,
+                )
+            })
+            .collect()
+    }
+
+    fn provider_manager(apis: Vec<MockPriceApi>) -> PriceProviderManager {
+        PriceProviderManager::new(providers(apis), MAX_PRICE_AGE)
+    }
+
+    fn price(manager: &PriceProviderManager) -> Result<TokenPrice, PriceError> {
+        let token = Token::new(TokenId(1), Default::default(), "TEST", 18, TokenKind::ERC20);
+        futures::executor::block_on(manager.get_price(&token))
+    }
+
+    #[test]
+    fn providers_fallback() {
+        let manager = provider_manager(vec![
+            MockPriceApi::Failure,
+            MockPriceApi::Price(2, chrono::Duration::hours(2)),
+            MockPriceApi::Price(3, chrono::Duration::minutes(1)),
+        ]);
+
+        // The failed provider and the one with the stale price are skipped.
+        let usd_price = price(&manager).unwrap().usd_price;
+        assert_eq!(usd_price, Ratio::from_integer(BigUint::from(3u32)));
+
+        let health = manager.stored_health();
+        let providers: Vec<_> = health.iter().map(|h| h.provider.as_str()).collect();
+        assert_eq!(providers, vec!["CoinGecko", "CoinMarketCap", "UniswapV3"]);
+        assert_eq!(health[0].consecutive_failures, 1);
+        assert_eq!(
+            health[0].last_error.as_deref(),
+            Some("Api error: API request failed")
+        );
+        assert_eq!(health[1].consecutive_failures, 1);
+        assert!(health[1].last_error.as_ref().unwrap().contains("stale"));
+        assert_eq!(health[2].consecutive_failures, 0);
+        assert!(health[2].last_success_at.is_some());
+
+        price(&manager).unwrap();
+        assert_eq!(manager.stored_health()[0].consecutive_failures, 2);
+    }
+
+    #[test]
+    fn token_not_found() {
+        let manager = provider_manager(vec![MockPriceApi::NotListed, MockPriceApi::NotListed]);
+        assert!(matches!(price(&manager), Err(PriceError::TokenNotFound(_))));
+        // Not listing the token is not a failure of the provider.
+        assert!(manager
+            .stored_health()
+            .iter()
+            .all(|health| health.consecutive_failures == 0 && health.last_error.is_none()));
+
+        // If one of the providers has failed, the token is not reported as missing.
+        let manager = provider_manager(vec![MockPriceApi::NotListed, MockPriceApi::Failure]);
+        assert!(matches!(price(&manager), Err(PriceError::ApiError(_))));
+    }
+
+    /// Checks the staleness boundary without relying on the wall-clock time.
+    #[test]
+    fn stale_price_with_mock_clock() {
+        let clock = MockClock::default();
+        let updated_at = clock.utc_now();
+        let manager = PriceProviderManager::with_clock(
+            providers(vec![MockPriceApi::PriceAt(1, updated_at)]),
+            MAX_PRICE_AGE,
+            Arc::new(clock.clone()),
+        );
+
+        // The fresh price is accepted until it's older than the max price age.
+        assert_eq!(price(&manager).unwrap().last_updated, updated_at);
+        clock.advance(MAX_PRICE_AGE);
+        assert_eq!(price(&manager).unwrap().last_updated, updated_at);
+        let health = &manager.stored_health()[0];
+        assert_eq!(health.last_success_at, Some(clock.utc_now()));
+        assert_eq!(health.consecutive_failures, 0);
+
+        // The stale price is rejected.
+        clock.advance(Duration::from_secs(1));
+        match price(&manager) {
+            Err(PriceError::ApiError(err)) => assert!(err.contains("stale")),
+            other => panic!("stale price is not rejected: {:?}", other),
+        }
+        let health = &manager.stored_health()[0];
+        assert_eq!(health.last_failure_at, Some(clock.utc_now()));
+        assert_eq!(health.consecutive_failures, 1);
+    }
+}
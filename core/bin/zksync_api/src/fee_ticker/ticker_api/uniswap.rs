@@ -0,0 +1,154 @@
+// Built-in deps
+use std::time::Instant;
+// External deps
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use num::{rational::Ratio, BigUint};
+use serde::{Deserialize, Serialize};
+// Workspace deps
+use super::{TokenPriceAPI, REQUEST_TIMEOUT};
+use crate::fee_ticker::PriceError;
+use zksync_types::{Token, TokenPrice};
+use zksync_utils::UnsignedRatioSerializeAsDecimal;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UniswapVersion {
+    /// https://thegraph.com/explorer/subgraph/uniswap/uniswap-v2
+    V2,
+    /// https://thegraph.com/explorer/subgraph/uniswap/uniswap-v3
+    V3,
+}
+
+impl UniswapVersion {
+    /// Length of the periods of the token data in the subgraph (seconds).
+    fn period_secs(self) -> i64 {
+        match self {
+            Self::V2 => 24 * 60 * 60,
+            Self::V3 => 60 * 60,
+        }
+    }
+}
+
+/// Prices of the tokens traded on Uniswap, taken as the time-weighted average of the
+/// USD price over the recent periods of the subgraph.
+#[derive(Debug, Clone)]
+pub struct UniswapPriceAPI {
+    client: reqwest::Client,
+    url: String,
+    version: UniswapVersion,
+}
+
+impl UniswapPriceAPI {
+    pub fn new(client: reqwest::Client, url: String, version: UniswapVersion) -> Self {
+        Self {
+            client,
+            url,
+            version,
+        }
+    }
+
+    fn price_query(&self, token: &Token) -> String {
+        // V2 subgraph has only the daily token data, so the average is taken over the last day,
+        // V3 one has the hourly data and the average is taken over the last 6 hours.
+        let (entity, timestamp, periods) = match self.version {
+            UniswapVersion::V2 => ("tokenDayDatas", "date", 1),
+            UniswapVersion::V3 => ("tokenHourDatas", "periodStartUnix", 6),
+        };
+        format!(
+            "{{periods: {}(first: {}, orderBy: {}, orderDirection: desc, where: {{token: \"{:#x}\"}}){{timestamp: {}, priceUSD}}}}",
+            entity, periods, timestamp, token.address, timestamp
+        )
+    }
+}
+
+#[async_trait]
+impl TokenPriceAPI for UniswapPriceAPI {
+    async fn get_price(&self, token: &Token) -> Result<TokenPrice, PriceError> {
+        let start = Instant::now();
+        // Uniswap has graphql API, using full graphql client for one query is overkill for current task
+        let response = self
+            .client
+            .post(&self.url)
+            .json(&serde_json::json!({
+                "query": self.price_query(token),
+            }))
+            .timeout(REQUEST_TIMEOUT)
+            .send()
+            .await
+            .map_err(|err| PriceError::api_error(format!("Uniswap API request failed: {}", err)))?
+            .json::<UniswapPriceResponse>()
+            .await
+            .map_err(PriceError::api_error)?;
+
+        let periods = response.data.periods;
+        // The data of the period is updated by every trade in it.
+        let last_updated = periods
+            .first()
+            .map(|period| period.timestamp + self.version.period_secs())
+            .ok_or_else(|| {
+                PriceError::token_not_found(format!(
+                    "Token '{}, {:?}' is not traded on Uniswap",
+                    token.symbol, token.address
+                ))
+            })?;
+        let usd_price = twap(&periods);
+
+        let naive_last_updated = NaiveDateTime::from_timestamp(last_updated, 0);
+        let last_updated = DateTime::<Utc>::from_utc(naive_last_updated, Utc).min(Utc::now());
+        metrics::histogram!("ticker.uniswap.request", start.elapsed());
+        Ok(TokenPrice {
+            usd_price,
+            last_updated,
+        })
+    }
+}
+
+/// Averages the prices of the periods, all of them have the same length.
+fn twap(periods: &[UniswapPricePeriod]) -> Ratio<BigUint> {
+    periods
+        .iter()
+        .map(|period| period.price_usd.clone())
+        .sum::<Ratio<BigUint>>()
+        / BigUint::from(periods.len())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UniswapPricePeriod {
+    /// Start of the period (seconds).
+    pub timestamp: i64,
+    #[serde(rename = "priceUSD", with = "UnsignedRatioSerializeAsDecimal")]
+    pub price_usd: Ratio<BigUint>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UniswapPricePeriods {
+    pub periods: Vec<UniswapPricePeriod>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UniswapPriceResponse {
+    pub data: UniswapPricePeriods,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn price_is_averaged_over_periods() {
+        let response: UniswapPriceResponse = serde_json::from_value(serde_json::json!({
+            "data": {
+                "periods": [
+                    { "timestamp": 1_600_003_600, "priceUSD": "2.5" },
+                    { "timestamp": 1_600_000_000, "priceUSD": "1.5" },
+                ]
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            twap(&response.data.periods),
+            Ratio::from_integer(BigUint::from(2u32))
+        );
+    }
+}
@@ -5,6 +5,7 @@
 #[cfg(test)]
 use std::any::Any;
 
+use std::collections::HashMap;
 use std::time::Instant;
 // External deps
 use anyhow::format_err;
@@ -13,12 +14,13 @@ use chrono::Utc;
 use num::rational::Ratio;
 use num::BigUint;
 // Workspace deps
+use zksync_eth_client::{EthereumGateway, GasPriceOracle};
 use zksync_storage::ConnectionPool;
 use zksync_token_db_cache::TokenDBCache;
 use zksync_types::aggregated_operations::AggregatedActionType;
 use zksync_types::{Address, Token, TokenId, TokenLike, TokenPrice};
 // Local deps
-use crate::fee_ticker::PriceError;
+use crate::fee_ticker::{discounts::FeeDiscount, PriceError};
 
 pub trait FeeTickerClone {
     fn clone_box(&self) -> Box<dyn FeeTickerInfo>;
@@ -55,11 +57,28 @@ pub trait FeeTickerInfo: FeeTickerClone + Send + Sync + 'static {
     /// Get last price for token from ticker info
     async fn get_last_token_price(&self, token: TokenLike) -> Result<TokenPrice, PriceError>;
 
+    /// Get last prices for the list of tokens, in the order of the request.
+    async fn get_last_token_prices(
+        &self,
+        tokens: Vec<TokenLike>,
+    ) -> Vec<Result<TokenPrice, PriceError>> {
+        let mut prices = Vec::with_capacity(tokens.len());
+        for token in tokens {
+            prices.push(self.get_last_token_price(token).await);
+        }
+        prices
+    }
+
     /// Get current gas price in ETH
     async fn get_gas_price_wei(&self) -> Result<BigUint, anyhow::Error>;
 
     async fn get_token(&self, token: TokenLike) -> Result<Token, anyhow::Error>;
 
+    /// Get the discounts of the fees
+    async fn get_fee_discounts(&self) -> anyhow::Result<Vec<FeeDiscount>> {
+        Ok(Vec::new())
+    }
+
     /// Make boxed value to any. Helpful for downcasting in tests
     #[cfg(test)]
     fn into_any(self: Box<Self>) -> Box<dyn Any>;
@@ -69,6 +88,8 @@ pub trait FeeTickerInfo: FeeTickerClone + Send + Sync + 'static {
 pub struct TickerInfo {
     db: ConnectionPool,
     token_db_cache: TokenDBCache,
+    /// Oracle reporting the gas price until the Ethereum sender gathers the gas price statistics.
+    gas_price_oracle: Option<(GasPriceOracle, EthereumGateway)>,
 }
 
 impl TickerInfo {
@@ -76,8 +97,18 @@ impl TickerInfo {
         Self {
             db,
             token_db_cache: Default::default(),
+            gas_price_oracle: None,
         }
     }
+
+    pub fn with_gas_price_oracle(
+        mut self,
+        oracle: GasPriceOracle,
+        ethereum: EthereumGateway,
+    ) -> Self {
+        self.gas_price_oracle = Some((oracle, ethereum));
+        self
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -166,39 +197,12 @@ impl FeeTickerInfo for TickerInfo {
     async fn get_last_token_price(&self, token: TokenLike) -> Result<TokenPrice, PriceError> {
         let start = Instant::now();
 
-        let token = {
-            // Try to find the token in the cache first.
-            if let Some(token) = self
-                .token_db_cache
-                .try_get_token_from_cache(token.clone())
-                .await
-            {
-                token
-            } else {
-                // Establish db connection and repeat the query, so the token is loaded
-                // from the db.
-                let mut storage = self
-                    .db
-                    .access_storage()
-                    .await
-                    .map_err(PriceError::db_error)?;
-                self.token_db_cache
-                    .get_token(&mut storage, token.clone())
-                    .await
-                    .map_err(PriceError::db_error)?
-                    .ok_or_else(|| {
-                        PriceError::token_not_found(format!("Token not found: {:?}", token))
-                    })?
-            }
-        };
+        let token = self.price_token(token).await?;
 
         // TODO: remove hardcode for Matter Labs Trial Token (ZKS-63).
         if token.symbol == "MLTT" {
             metrics::histogram!("ticker_info.get_last_token_price", start.elapsed(), "type" => "MLTT");
-            return Ok(TokenPrice {
-                usd_price: Ratio::from_integer(1u32.into()),
-                last_updated: Utc::now(),
-            });
+            return Ok(mltt_price());
         }
 
         let historical_price = self
@@ -214,6 +218,52 @@ impl FeeTickerInfo for TickerInfo {
         Err(PriceError::db_error("No price stored in database"))
     }
 
+    /// Get last prices from ticker, loading all the stored prices with one query.
+    async fn get_last_token_prices(
+        &self,
+        tokens: Vec<TokenLike>,
+    ) -> Vec<Result<TokenPrice, PriceError>> {
+        let start = Instant::now();
+
+        let mut price_tokens = Vec::with_capacity(tokens.len());
+        for token in tokens {
+            price_tokens.push(self.price_token(token).await);
+        }
+        let token_ids: Vec<TokenId> = price_tokens
+            .iter()
+            .filter_map(|token| token.as_ref().ok())
+            .map(|token| token.id)
+            .collect();
+        let stored_prices = match self.load_ticker_prices(&token_ids).await {
+            Ok(stored_prices) => stored_prices,
+            Err(err) => {
+                vlog::warn!("Failed to load historical ticker prices: {}", err);
+                return price_tokens
+                    .into_iter()
+                    .map(|_| Err(PriceError::db_error(&err)))
+                    .collect();
+            }
+        };
+
+        let prices = price_tokens
+            .into_iter()
+            .map(|token| {
+                let token = token?;
+                // TODO: remove hardcode for Matter Labs Trial Token (ZKS-63).
+                if token.symbol == "MLTT" {
+                    return Ok(mltt_price());
+                }
+                stored_prices
+                    .get(&token.id)
+                    .cloned()
+                    .ok_or_else(|| PriceError::db_error("No price stored in database"))
+            })
+            .collect();
+
+        metrics::histogram!("ticker_info.get_last_token_prices", start.elapsed());
+        prices
+    }
+
     /// Get current gas price in ETH
     async fn get_gas_price_wei(&self) -> Result<BigUint, anyhow::Error> {
         let start = Instant::now();
@@ -223,16 +273,19 @@ impl FeeTickerInfo for TickerInfo {
             .access_storage()
             .await
             .map_err(|e| format_err!("Can't access storage: {}", e))?;
-        let average_gas_price = storage
-            .ethereum_schema()
-            .load_average_gas_price()
-            .await?
-            .unwrap_or_default()
-            .as_u64();
-        let average_gas_price = BigUint::from(average_gas_price);
+        let average_gas_price = storage.ethereum_schema().load_average_gas_price().await?;
+        drop(storage);
+        let gas_price = match (average_gas_price, &self.gas_price_oracle) {
+            (Some(price), _) => price,
+            // The statistics of the Ethereum sender are empty after the start, so the current
+            // gas price is used until they are gathered.
+            (None, Some((oracle, ethereum))) => oracle.get_gas_price(ethereum).await?,
+            (None, None) => Default::default(),
+        };
+        let gas_price = BigUint::from(gas_price.as_u64());
 
         metrics::histogram!("ticker_info.get_gas_price_wei", start.elapsed());
-        Ok(average_gas_price)
+        Ok(gas_price)
     }
 
     async fn get_token(&self, token: TokenLike) -> Result<Token, anyhow::Error> {
@@ -258,13 +311,81 @@ impl FeeTickerInfo for TickerInfo {
         result
     }
 
+    async fn get_fee_discounts(&self) -> anyhow::Result<Vec<FeeDiscount>> {
+        let start = Instant::now();
+        let mut storage = self.db.access_storage().await?;
+        let discounts = storage
+            .fee_discounts_schema()
+            .load_fee_discounts()
+            .await?
+            .into_iter()
+            .map(FeeDiscount::from)
+            .collect();
+
+        metrics::histogram!("ticker_info.get_fee_discounts", start.elapsed());
+        Ok(discounts)
+    }
+
     #[cfg(test)]
     fn into_any(self: Box<Self>) -> Box<dyn Any> {
         self
     }
 }
 
+fn mltt_price() -> TokenPrice {
+    TokenPrice {
+        usd_price: Ratio::from_integer(1u32.into()),
+        last_updated: Utc::now(),
+    }
+}
+
 impl TickerInfo {
+    /// Finds the token the price is requested for.
+    async fn price_token(&self, token: TokenLike) -> Result<Token, PriceError> {
+        // Try to find the token in the cache first.
+        if let Some(token) = self
+            .token_db_cache
+            .try_get_token_from_cache(token.clone())
+            .await
+        {
+            return Ok(token);
+        }
+
+        // Establish db connection and repeat the query, so the token is loaded
+        // from the db.
+        let mut storage = self
+            .db
+            .access_storage()
+            .await
+            .map_err(PriceError::db_error)?;
+        self.token_db_cache
+            .get_token(&mut storage, token.clone())
+            .await
+            .map_err(PriceError::db_error)?
+            .ok_or_else(|| PriceError::token_not_found(format!("Token not found: {:?}", token)))
+    }
+
+    async fn load_ticker_prices(
+        &self,
+        token_ids: &[TokenId],
+    ) -> Result<HashMap<TokenId, TokenPrice>, anyhow::Error> {
+        let start = Instant::now();
+        let mut storage = self
+            .db
+            .access_storage()
+            .await
+            .map_err(|e| format_err!("Can't access storage: {}", e))?;
+
+        let result = storage
+            .tokens_schema()
+            .load_historical_ticker_prices(token_ids)
+            .await
+            .map_err(|e| format_err!("Can't load historical ticker prices from storage: {}", e));
+
+        metrics::histogram!("ticker.load_historical_ticker_prices", start.elapsed());
+        result
+    }
+
     async fn get_ticker_price(
         &self,
         token_id: TokenId,
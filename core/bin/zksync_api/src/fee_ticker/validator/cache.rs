@@ -1,9 +1,11 @@
 #[cfg(test)]
-use std::{collections::HashMap, sync::Arc};
+use std::sync::Arc;
 #[cfg(test)]
 use tokio::sync::Mutex;
 use zksync_token_db_cache::TokenDBCache;
 
+#[cfg(test)]
+use zksync_storage::tokens::{memory::MemoryTokensStorage, TokensStorage};
 use zksync_storage::ConnectionPool;
 use zksync_types::{tokens::TokenMarketVolume, Token, TokenId, TokenLike};
 
@@ -20,11 +22,11 @@ pub(crate) struct TokenInDBCache {
     pool: ConnectionPool,
 }
 
+/// Cache on top of the in-memory token storage, so the fee ticker is tested without the database.
 #[derive(Debug, Clone, Default)]
 #[cfg(test)]
 pub struct TokenInMemoryCache {
-    tokens: Arc<Mutex<HashMap<TokenLike, Token>>>,
-    market: Arc<Mutex<HashMap<TokenId, TokenMarketVolume>>>,
+    storage: Arc<Mutex<MemoryTokensStorage>>,
 }
 
 impl TokenInDBCache {
@@ -38,18 +40,13 @@ impl TokenInMemoryCache {
     pub fn new() -> Self {
         Self::default()
     }
+}
 
-    pub fn with_tokens(self, tokens: HashMap<TokenLike, Token>) -> Self {
-        Self {
-            tokens: Arc::new(Mutex::new(tokens)),
-            ..self
-        }
-    }
-
-    pub fn with_market(self, market: HashMap<TokenId, TokenMarketVolume>) -> Self {
+#[cfg(test)]
+impl From<MemoryTokensStorage> for TokenInMemoryCache {
+    fn from(storage: MemoryTokensStorage) -> Self {
         Self {
-            market: Arc::new(Mutex::new(market)),
-            ..self
+            storage: Arc::new(Mutex::new(storage)),
         }
     }
 }
@@ -88,7 +85,7 @@ impl TokenCacheWrapper {
                     .await
             }
             #[cfg(test)]
-            Self::Memory(cache) => Ok(cache.tokens.lock().await.get(&token_like).cloned()),
+            Self::Memory(cache) => Ok(cache.storage.lock().await.get_token(token_like).await?),
         }
     }
 
@@ -105,7 +102,35 @@ impl TokenCacheWrapper {
                 .await
             }
             #[cfg(test)]
-            Self::Memory(cache) => Ok(cache.market.lock().await.get(&token_id).cloned()),
+            Self::Memory(cache) => Ok(cache
+                .storage
+                .lock()
+                .await
+                .get_token_market_volume(token_id)
+                .await?),
+        }
+    }
+
+    /// Returns the eligibility of the token for paying fees set by the admin, if any.
+    pub async fn get_fee_eligibility_override(
+        &self,
+        token_id: TokenId,
+    ) -> anyhow::Result<Option<bool>> {
+        match self {
+            Self::DB(cache) => Ok(cache
+                .pool
+                .access_storage()
+                .await?
+                .tokens_schema()
+                .get_fee_eligibility_override(token_id)
+                .await?),
+            #[cfg(test)]
+            Self::Memory(cache) => Ok(cache
+                .storage
+                .lock()
+                .await
+                .get_fee_eligibility_override(token_id)
+                .await?),
         }
     }
 
@@ -121,13 +146,17 @@ impl TokenCacheWrapper {
                     token_id,
                     market_volume,
                 )
-                .await
-            }
-            #[cfg(test)]
-            Self::Memory(cache) => {
-                cache.market.lock().await.insert(token_id, market_volume);
+                .await?;
+                cache.inner.invalidate(token_id).await;
                 Ok(())
             }
+            #[cfg(test)]
+            Self::Memory(cache) => Ok(cache
+                .storage
+                .lock()
+                .await
+                .update_token_market_volume(token_id, market_volume)
+                .await?),
         }
     }
     pub async fn get_all_tokens(&self) -> anyhow::Result<Vec<Token>> {
@@ -137,11 +166,12 @@ impl TokenCacheWrapper {
             }
             #[cfg(test)]
             Self::Memory(cache) => Ok(cache
-                .tokens
+                .storage
                 .lock()
                 .await
-                .iter()
-                .map(|(_k, v)| v.clone())
+                .load_tokens()
+                .await?
+                .into_values()
                 .collect()),
         }
     }
@@ -7,13 +7,17 @@ pub mod watcher;
 // Built-in uses
 use std::{
     collections::HashSet,
+    convert::TryFrom,
     time::{Duration, Instant},
 };
 
 use bigdecimal::BigDecimal;
 use chrono::Utc;
+use tokio::sync::watch;
 
 // Workspace uses
+use zksync_config::ReloadableConfig;
+use zksync_token_db_cache::TokenDenylist;
 use zksync_types::{
     tokens::{Token, TokenLike, TokenMarketVolume},
     Address,
@@ -26,45 +30,121 @@ use zksync_utils::{big_decimal_to_ratio, ratio_to_big_decimal};
 
 const CRITICAL_NUMBER_OF_ERRORS: u32 = 500;
 
+/// Returns the liquidity volume of the reloaded config if the updates are received,
+/// or the one set on creation otherwise. The one set on creation is also used if the
+/// reloaded value isn't a valid decimal.
+fn current_liquidity_volume(
+    liquidity_volume: &BigDecimal,
+    config_updates: &Option<watch::Receiver<ReloadableConfig>>,
+) -> BigDecimal {
+    let reloaded = match config_updates {
+        Some(updates) => updates.borrow().liquidity_volume,
+        None => return liquidity_volume.clone(),
+    };
+    BigDecimal::try_from(reloaded).unwrap_or_else(|_| {
+        vlog::warn!(
+            "The reloaded liquidity volume {} is invalid, {} is used instead",
+            reloaded,
+            liquidity_volume
+        );
+        liquidity_volume.clone()
+    })
+}
+
 /// We don't want to send requests to the Internet for every request from users.
-/// Market updater periodically updates the values of the token market in the cache  
+/// Market updater periodically updates the values of the token market in the cache,
+/// and reports the tokens becoming enabled or disabled for paying fees.
 #[derive(Clone, Debug)]
 pub(crate) struct MarketUpdater<W> {
     tokens_cache: TokenCacheWrapper,
     watcher: W,
+    /// Tokens with at least this market volume are enabled for paying fees.
+    liquidity_volume: BigDecimal,
+    config_updates: Option<watch::Receiver<ReloadableConfig>>,
 }
 
 impl<W: TokenWatcher> MarketUpdater<W> {
-    pub(crate) fn new(cache: impl Into<TokenCacheWrapper>, watcher: W) -> Self {
+    pub(crate) fn new(
+        cache: impl Into<TokenCacheWrapper>,
+        watcher: W,
+        liquidity_volume: BigDecimal,
+    ) -> Self {
         Self {
             tokens_cache: cache.into(),
             watcher,
+            liquidity_volume,
+            config_updates: None,
+        }
+    }
+
+    /// The liquidity volume is taken from the reloaded config since then.
+    pub(crate) fn with_config_updates(
+        self,
+        config_updates: watch::Receiver<ReloadableConfig>,
+    ) -> Self {
+        Self {
+            config_updates: Some(config_updates),
+            ..self
         }
     }
 
-    async fn update_token(&mut self, token: &Token) -> anyhow::Result<TokenMarketVolume> {
+    fn enabled_for_fees(&self, market: &TokenMarketVolume) -> bool {
+        ratio_to_big_decimal(&market.market_volume, 2)
+            >= current_liquidity_volume(&self.liquidity_volume, &self.config_updates)
+    }
+
+    /// Updates the market volume of the token. Returns the new eligibility of the token
+    /// for paying fees if it has changed.
+    async fn update_token(&mut self, token: &Token) -> anyhow::Result<Option<bool>> {
+        let previous = self.tokens_cache.get_token_market_volume(token.id).await?;
         let amount = self.watcher.get_token_market_volume(token).await?;
         let market = TokenMarketVolume {
             market_volume: big_decimal_to_ratio(&amount).unwrap(),
             last_updated: Utc::now(),
         };
 
-        if let Err(e) = self
-            .tokens_cache
+        self.tokens_cache
             .update_token_market_volume(token.id, market.clone())
-            .await
-        {
-            vlog::error!("Error in updating token market volume {}", e);
+            .await?;
+
+        let was_enabled = previous.map_or(false, |previous| self.enabled_for_fees(&previous));
+        let enabled = self.enabled_for_fees(&market);
+        if was_enabled == enabled {
+            return Ok(None);
         }
-        Ok(market)
+        vlog::info!(
+            "Token {} is {} for paying fees, its market volume is {}",
+            token.symbol,
+            if enabled { "enabled" } else { "disabled" },
+            amount
+        );
+        metrics::increment_counter!(
+            "ticker.validator.fee_token_transitions",
+            "token" => token.symbol.clone(),
+            "enabled" => enabled.to_string()
+        );
+        Ok(Some(enabled))
     }
 
+    /// Updates the market volumes of the tokens. The token failed to be updated
+    /// doesn't prevent the rest of them from being updated.
     pub async fn update_all_tokens(&mut self, tokens: Vec<Token>) -> anyhow::Result<()> {
         let start = Instant::now();
+        let mut failed = 0;
         for token in tokens {
-            self.update_token(&token).await?;
+            if let Err(err) = self.update_token(&token).await {
+                vlog::warn!(
+                    "Can't update the market volume of {}: {}",
+                    token.symbol,
+                    err
+                );
+                failed += 1;
+            }
         }
         metrics::histogram!("ticker.validator.update_all_tokens", start.elapsed());
+        if failed > 0 {
+            anyhow::bail!("Market volume of {} tokens is not updated", failed);
+        }
         Ok(())
     }
 
@@ -100,6 +180,9 @@ pub struct FeeTokenValidator {
     tokens_cache: TokenCacheWrapper,
     available_time: chrono::Duration,
     liquidity_volume: BigDecimal,
+    config_updates: Option<watch::Receiver<ReloadableConfig>>,
+    /// Denylisted tokens aren't suitable for paying fees regardless of anything else.
+    token_denylist: Option<TokenDenylist>,
 }
 
 impl FeeTokenValidator {
@@ -114,6 +197,26 @@ impl FeeTokenValidator {
             tokens_cache: cache.into(),
             available_time,
             liquidity_volume,
+            config_updates: None,
+            token_denylist: None,
+        }
+    }
+
+    /// The liquidity volume is taken from the reloaded config since then.
+    pub(crate) fn with_config_updates(
+        self,
+        config_updates: watch::Receiver<ReloadableConfig>,
+    ) -> Self {
+        Self {
+            config_updates: Some(config_updates),
+            ..self
+        }
+    }
+
+    pub(crate) fn with_token_denylist(self, token_denylist: TokenDenylist) -> Self {
+        Self {
+            token_denylist: Some(token_denylist),
+            ..self
         }
     }
 
@@ -121,6 +224,11 @@ impl FeeTokenValidator {
     pub(crate) async fn token_allowed(&self, token: TokenLike) -> anyhow::Result<bool> {
         let token = self.resolve_token(token).await?;
         if let Some(token) = token {
+            if let Some(token_denylist) = &self.token_denylist {
+                if token_denylist.is_denylisted(token.id).await? {
+                    return Ok(false);
+                }
+            }
             if self.unconditionally_valid.contains(&token.address) {
                 return Ok(true);
             }
@@ -137,6 +245,14 @@ impl FeeTokenValidator {
 
     async fn check_token(&self, token: Token) -> anyhow::Result<bool> {
         let start = Instant::now();
+        // The eligibility set by the admin takes precedence over the market volume.
+        if let Some(allowed) = self
+            .tokens_cache
+            .get_fee_eligibility_override(token.id)
+            .await?
+        {
+            return Ok(allowed);
+        }
         let volume = match self.get_token_market_volume(&token).await? {
             Some(volume) => volume,
             None => return Ok(false),
@@ -145,7 +261,8 @@ impl FeeTokenValidator {
         if Utc::now() - volume.last_updated > self.available_time {
             vlog::warn!("Token market amount for {} is not relevant", &token.symbol)
         }
-        let allowed = ratio_to_big_decimal(&volume.market_volume, 2) >= self.liquidity_volume;
+        let allowed = ratio_to_big_decimal(&volume.market_volume, 2)
+            >= current_liquidity_volume(&self.liquidity_volume, &self.config_updates);
         metrics::histogram!("ticker.validator.check_token", start.elapsed());
         Ok(allowed)
     }
@@ -168,6 +285,7 @@ mod tests {
     use std::str::FromStr;
     use std::sync::Arc;
     use tokio::sync::Mutex;
+    use zksync_storage::tokens::memory::MemoryTokensStorage;
     use zksync_types::{TokenId, TokenKind};
 
     #[derive(Clone)]
@@ -198,7 +316,7 @@ mod tests {
         let phnx_token = Token::new(TokenId(2), phnx_token_address, "PHNX", 18, TokenKind::ERC20);
 
         let eth_address = Address::from_str("0000000000000000000000000000000000000000").unwrap();
-        let eth_token = Token::new(TokenId(2), eth_address, "ETH", 18, TokenKind::ERC20);
+        let eth_token = Token::new(TokenId(0), eth_address, "ETH", 18, TokenKind::ERC20);
         let all_tokens = vec![dai_token.clone(), phnx_token.clone()];
 
         let mut market = HashMap::new();
@@ -217,19 +335,18 @@ mod tests {
             },
         );
 
-        let mut tokens = HashMap::new();
-        tokens.insert(TokenLike::Address(dai_token_address), dai_token.clone());
-        tokens.insert(TokenLike::Address(phnx_token_address), phnx_token.clone());
-        tokens.insert(TokenLike::Address(eth_address), eth_token);
+        let tokens = vec![dai_token.clone(), phnx_token.clone(), eth_token];
         let mut amounts = HashMap::new();
         amounts.insert(dai_token_address, BigDecimal::from(200));
         amounts.insert(phnx_token_address, BigDecimal::from(10));
         let mut unconditionally_valid = HashSet::new();
         unconditionally_valid.insert(eth_address);
 
-        let cache = TokenInMemoryCache::new()
-            .with_tokens(tokens)
-            .with_market(market);
+        let cache = TokenInMemoryCache::from(
+            MemoryTokensStorage::new()
+                .with_tokens(tokens.clone())
+                .with_market_volumes(market),
+        );
 
         let watcher = InMemoryTokenWatcher {
             amounts: Arc::new(Mutex::new(amounts)),
@@ -242,7 +359,7 @@ mod tests {
             unconditionally_valid,
         );
 
-        let mut updater = MarketUpdater::new(cache, watcher);
+        let mut updater = MarketUpdater::new(cache, watcher.clone(), BigDecimal::from(100));
         updater.update_all_tokens(all_tokens).await.unwrap();
 
         let new_dai_token_market = validator
@@ -283,5 +400,72 @@ mod tests {
         assert!(dai_allowed);
         assert!(!phnx_allowed);
         assert!(eth_allowed);
+
+        // The liquidity volume of the reloaded config is used once it's received.
+        let reloaded_config = |liquidity_volume| ReloadableConfig {
+            liquidity_volume,
+            requests_per_second_per_ip: 0,
+            denylisted_tokens: vec![],
+        };
+        let (config_sender, config_updates) = watch::channel(reloaded_config(5.0));
+        let reloaded_validator = validator.clone().with_config_updates(config_updates);
+        assert!(reloaded_validator
+            .token_allowed(TokenLike::Address(phnx_token_address))
+            .await
+            .unwrap());
+        config_sender.send(reloaded_config(500.0)).unwrap();
+        assert!(!reloaded_validator
+            .token_allowed(TokenLike::Address(dai_token_address))
+            .await
+            .unwrap());
+        // The invalid value doesn't fail the check, the one set on creation is used instead.
+        config_sender.send(reloaded_config(f64::NAN)).unwrap();
+        assert!(reloaded_validator
+            .token_allowed(TokenLike::Address(dai_token_address))
+            .await
+            .unwrap());
+        assert!(!reloaded_validator
+            .token_allowed(TokenLike::Address(phnx_token_address))
+            .await
+            .unwrap());
+
+        // Only the updates crossing the liquidity volume change the eligibility of the token.
+        assert_eq!(updater.update_token(&dai_token).await.unwrap(), None);
+        watcher
+            .amounts
+            .lock()
+            .await
+            .insert(dai_token_address, BigDecimal::from(50));
+        assert_eq!(updater.update_token(&dai_token).await.unwrap(), Some(false));
+        watcher
+            .amounts
+            .lock()
+            .await
+            .insert(phnx_token_address, BigDecimal::from(100));
+        assert_eq!(updater.update_token(&phnx_token).await.unwrap(), Some(true));
+
+        // The eligibility set by the admin takes precedence over the market volume.
+        let mut fee_eligibility = HashMap::new();
+        fee_eligibility.insert(dai_token.id, true);
+        fee_eligibility.insert(phnx_token.id, false);
+        let cache = TokenInMemoryCache::from(
+            MemoryTokensStorage::new()
+                .with_tokens(tokens)
+                .with_fee_eligibility_overrides(fee_eligibility),
+        );
+        let validator = FeeTokenValidator::new(
+            cache,
+            chrono::Duration::seconds(100),
+            BigDecimal::from(100),
+            HashSet::new(),
+        );
+        assert!(validator
+            .token_allowed(TokenLike::Address(dai_token_address))
+            .await
+            .unwrap());
+        assert!(!validator
+            .token_allowed(TokenLike::Address(phnx_token_address))
+            .await
+            .unwrap());
     }
 }
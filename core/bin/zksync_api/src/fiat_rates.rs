@@ -0,0 +1,234 @@
+//! Exchange rates of the fiat currencies against USD.
+//!
+//! The token prices are known only in USD, so the rates are used to show them
+//! in the other fiat currencies.
+
+// Built-in uses
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+// External uses
+use async_trait::async_trait;
+use bigdecimal::BigDecimal;
+use reqwest::Url;
+use serde::Deserialize;
+// Workspace uses
+use zksync_config::configs::api::TokenConfig;
+// Local uses
+use crate::{
+    fee_ticker::PriceError,
+    utils::{
+        clock::{Clock, SystemClock},
+        ttl_cache::TtlCache,
+    },
+};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_millis(700);
+
+/// Fiat currencies in which the token prices can be shown besides USD.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FiatCurrency {
+    Eur,
+    Gbp,
+    Jpy,
+}
+
+impl FiatCurrency {
+    pub const ALL: [FiatCurrency; 3] = [FiatCurrency::Eur, FiatCurrency::Gbp, FiatCurrency::Jpy];
+
+    /// ISO 4217 code of the currency.
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Eur => "EUR",
+            Self::Gbp => "GBP",
+            Self::Jpy => "JPY",
+        }
+    }
+}
+
+impl FromStr for FiatCurrency {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|currency| currency.code().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("Unknown fiat currency: {}", s))
+    }
+}
+
+impl fmt::Display for FiatCurrency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.code().to_lowercase())
+    }
+}
+
+/// Source of the fiat exchange rates.
+#[async_trait]
+pub trait FiatRatesAPI: Send + Sync + 'static {
+    /// Returns the amount of each currency one can buy for 1 USD.
+    async fn get_usd_rates(&self) -> Result<HashMap<FiatCurrency, BigDecimal>, PriceError>;
+}
+
+/// Client of the `exchangerate.host` compatible API.
+#[derive(Debug, Clone)]
+pub struct ExchangeRateHostAPI {
+    base_url: Url,
+    client: reqwest::Client,
+}
+
+impl ExchangeRateHostAPI {
+    pub fn new(client: reqwest::Client, base_url: Url) -> Self {
+        Self { base_url, client }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ExchangeRatesResponse {
+    rates: HashMap<String, BigDecimal>,
+}
+
+#[async_trait]
+impl FiatRatesAPI for ExchangeRateHostAPI {
+    async fn get_usd_rates(&self) -> Result<HashMap<FiatCurrency, BigDecimal>, PriceError> {
+        let start = Instant::now();
+        let latest_url = self
+            .base_url
+            .join("latest")
+            .expect("failed to join URL path");
+        let symbols = FiatCurrency::ALL
+            .iter()
+            .map(|currency| currency.code())
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let response = self
+            .client
+            .get(latest_url)
+            .timeout(REQUEST_TIMEOUT)
+            .query(&[("base", "USD"), ("symbols", symbols.as_str())])
+            .send()
+            .await
+            .map_err(|err| PriceError::api_error(format!("Fiat rates request failed: {}", err)))?
+            .json::<ExchangeRatesResponse>()
+            .await
+            .map_err(PriceError::api_error)?;
+
+        let rates = response
+            .rates
+            .into_iter()
+            .filter_map(|(code, rate)| Some((FiatCurrency::from_str(&code).ok()?, rate)))
+            .collect();
+        metrics::histogram!("fiat_rates.request", start.elapsed());
+        Ok(rates)
+    }
+}
+
+/// Fiat exchange rates cached for the configured time-to-live.
+#[derive(Clone)]
+pub struct FiatRates {
+    api: Arc<dyn FiatRatesAPI>,
+    rates: TtlCache<FiatCurrency, BigDecimal>,
+}
+
+impl FiatRates {
+    pub fn new(api: Arc<dyn FiatRatesAPI>, ttl: Duration) -> Self {
+        Self::with_clock(api, ttl, Arc::new(SystemClock))
+    }
+
+    /// Creates the rates with the given clock used to expire the cached rates.
+    pub fn with_clock(api: Arc<dyn FiatRatesAPI>, ttl: Duration, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            api,
+            rates: TtlCache::with_clock(ttl, clock),
+        }
+    }
+
+    /// Creates the rates requested from the API configured for the tokens.
+    pub fn from_config(config: &TokenConfig) -> Self {
+        let base_url = Url::parse(&config.fiat_rates_url).expect("Incorrect fiat rates url");
+        let api = ExchangeRateHostAPI::new(reqwest::Client::new(), base_url);
+        Self::new(Arc::new(api), config.fiat_rates_ttl())
+    }
+
+    /// Returns the amount of the currency one can buy for 1 USD.
+    pub async fn usd_rate(&self, currency: FiatCurrency) -> Result<BigDecimal, PriceError> {
+        if let Some(rate) = self.rates.get(&currency) {
+            return Ok(rate);
+        }
+
+        // All the rates are returned at once, so there is no need to request them separately.
+        let rates = self.api.get_usd_rates().await?;
+        for (currency, rate) in &rates {
+            self.rates.insert(*currency, rate.clone());
+        }
+        rates.get(&currency).cloned().ok_or_else(|| {
+            PriceError::api_error(format!(
+                "Exchange rate for {} is not available",
+                currency.code()
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::clock::MockClock;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Returns the same rates and counts the requests.
+    #[derive(Debug, Default)]
+    struct CountingFiatRatesAPI {
+        requests: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl FiatRatesAPI for CountingFiatRatesAPI {
+        async fn get_usd_rates(&self) -> Result<HashMap<FiatCurrency, BigDecimal>, PriceError> {
+            self.requests.fetch_add(1, Ordering::SeqCst);
+            let mut rates = HashMap::new();
+            rates.insert(FiatCurrency::Eur, BigDecimal::from(2));
+            rates.insert(FiatCurrency::Jpy, BigDecimal::from(100));
+            Ok(rates)
+        }
+    }
+
+    #[test]
+    fn parse_fiat_currency() {
+        assert_eq!(FiatCurrency::from_str("eur"), Ok(FiatCurrency::Eur));
+        assert_eq!(FiatCurrency::from_str("GBP"), Ok(FiatCurrency::Gbp));
+        assert!(FiatCurrency::from_str("usd").is_err());
+        assert_eq!(FiatCurrency::Jpy.to_string(), "jpy");
+    }
+
+    #[tokio::test]
+    async fn rates_are_cached() {
+        let api = Arc::new(CountingFiatRatesAPI::default());
+        let clock = MockClock::default();
+        let ttl = Duration::from_secs(60);
+        let rates = FiatRates::with_clock(api.clone(), ttl, Arc::new(clock.clone()));
+
+        assert_eq!(
+            rates.usd_rate(FiatCurrency::Eur).await.unwrap(),
+            BigDecimal::from(2)
+        );
+        // The rates of the other currencies are cached with the first request.
+        assert_eq!(
+            rates.usd_rate(FiatCurrency::Jpy).await.unwrap(),
+            BigDecimal::from(100)
+        );
+        assert_eq!(api.requests.load(Ordering::SeqCst), 1);
+
+        // The missing rate is requested every time.
+        assert!(rates.usd_rate(FiatCurrency::Gbp).await.is_err());
+        assert_eq!(api.requests.load(Ordering::SeqCst), 2);
+
+        clock.advance(ttl);
+        rates.usd_rate(FiatCurrency::Eur).await.unwrap();
+        assert_eq!(api.requests.load(Ordering::SeqCst), 3);
+    }
+}
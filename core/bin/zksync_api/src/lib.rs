@@ -3,6 +3,7 @@
 pub mod api_server;
 pub mod eth_checker;
 pub mod fee_ticker;
+pub mod fiat_rates;
 pub mod signature_checker;
 pub mod tx_error;
 pub mod utils;
@@ -1,11 +1,15 @@
 //! `signature_checker` module provides a detached thread routine
 //! dedicated for checking the signatures of incoming transactions.
-//! Main routine of this module operates a multithreaded event loop,
-//! which is used to spawn concurrent tasks to efficiently check the
-//! transactions signatures.
+//! Main routine of this module takes the bursts of the incoming requests
+//! and verifies them in batches by a dedicated thread pool, so the
+//! CPU-heavy ECDSA and zkSync signature checks don't block the async
+//! runtime and don't serialize on a single task. The requests which require
+//! the queries to Ethereum (EIP-1271 signatures and on-chain `ChangePubKey`
+//! authorizations) are queried concurrently first and verified by the pool after.
 
 // Built-in uses
 use std::collections::HashSet;
+use std::sync::Arc;
 use std::time::Instant;
 
 // External uses
@@ -13,9 +17,11 @@ use futures::{
     channel::{mpsc, oneshot},
     StreamExt,
 };
+use rayon::{prelude::*, ThreadPool, ThreadPoolBuilder};
 use tokio::task::JoinHandle;
 
 // Workspace uses
+use zksync_config::configs::api::CommonApiConfig;
 use zksync_eth_client::EthereumGateway;
 use zksync_types::{
     tx::{error::TxAddError, EthBatchSignData, EthSignData, TxEthSignature},
@@ -52,6 +58,21 @@ impl VerifiedTx {
         eth_checker: &EthereumChecker,
     ) -> Result<Self, TxAddError> {
         verify_eth_signature(&request_data, eth_checker).await?;
+        Self::verify_correctness(&request_data)
+    }
+
+    /// Verifies the request which doesn't require the queries to Ethereum in the current thread.
+    fn verify_offchain(
+        request_data: RequestData,
+        eth_checker: &EthereumChecker,
+    ) -> Result<Self, TxAddError> {
+        // None of the Ethereum signature checks of such a request awaits anything,
+        // so the future is completed right away.
+        futures::executor::block_on(Self::verify(request_data, eth_checker))
+    }
+
+    /// Checks the correctness of the (batch of) transaction(s) including its `ZKSync` signature.
+    fn verify_correctness(request_data: &RequestData) -> Result<Self, TxAddError> {
         let mut tx_variant = request_data.get_tx_variant();
         verify_tx_correctness(&mut tx_variant)?;
 
@@ -373,6 +394,52 @@ impl RequestData {
             RequestData::Toggle2FA(_) => TxVariant::Toggle2FA,
         }
     }
+
+    /// Returns `true` if the Ethereum signatures of the request are checked by the queries
+    /// to Ethereum, i.e. the request contains EIP-1271 signatures or on-chain `ChangePubKey`.
+    fn requires_ethereum_queries(&self) -> bool {
+        let is_eip1271 =
+            |signature: &TxEthSignature| matches!(signature, TxEthSignature::EIP1271Signature(_));
+        let tx_requires_queries = |tx: &SignedZkSyncTx| {
+            let is_onchain_change_pk =
+                matches!(&tx.tx, ZkSyncTx::ChangePubKey(change_pk) if change_pk.is_onchain());
+            is_onchain_change_pk
+                || tx
+                    .eth_sign_data
+                    .as_ref()
+                    .map_or(false, |sign_data| is_eip1271(&sign_data.signature))
+        };
+
+        match self {
+            RequestData::Tx(request) => tx_requires_queries(&request.tx),
+            RequestData::Batch(request) => {
+                request.txs.iter().any(tx_requires_queries)
+                    || request.batch_sign_data.as_ref().map_or(false, |sign_data| {
+                        sign_data.signatures.iter().any(is_eip1271)
+                    })
+            }
+            RequestData::Order(request) => is_eip1271(&request.sign_data.signature),
+            RequestData::Toggle2FA(request) => is_eip1271(&request.sign_data.signature),
+        }
+    }
+}
+
+/// Parameters of the signature checker thread pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignatureCheckerConfig {
+    /// Number of the verifying threads, 0 for the number of CPUs.
+    pub threads: usize,
+    /// Maximum number of the requests taken from the channel to be verified at once.
+    pub batch_size: usize,
+}
+
+impl SignatureCheckerConfig {
+    pub fn from_config(config: &CommonApiConfig) -> Self {
+        Self {
+            threads: config.signature_checker_threads,
+            batch_size: config.signature_checker_batch_size,
+        }
+    }
 }
 
 /// Main routine of the concurrent signature checker.
@@ -380,23 +447,152 @@ impl RequestData {
 pub fn start_sign_checker(
     client: EthereumGateway,
     input: mpsc::Receiver<VerifySignatureRequest>,
+    config: SignatureCheckerConfig,
 ) -> JoinHandle<()> {
     let eth_checker = EthereumChecker::new(client);
+    let pool = ThreadPoolBuilder::new()
+        .num_threads(config.threads)
+        .thread_name(|index| format!("signature-checker-{}", index))
+        .build()
+        .expect("Failed to create the signature checker thread pool");
 
     /// Basically it receives the requests through the channel and verifies signatures,
     /// notifying the request sender about the check result.
     async fn checker_routine(
         mut input: mpsc::Receiver<VerifySignatureRequest>,
         eth_checker: EthereumChecker,
+        pool: Arc<ThreadPool>,
+        batch_size: usize,
     ) {
-        while let Some(VerifySignatureRequest { data, response }) = input.next().await {
-            let eth_checker = eth_checker.clone();
-            tokio::spawn(async move {
-                let resp = VerifiedTx::verify(data, &eth_checker).await;
+        while let Some(request) = input.next().await {
+            // Take the requests received meanwhile to verify the whole burst at once.
+            let mut requests = vec![request];
+            while requests.len() < batch_size {
+                match input.try_next() {
+                    Ok(Some(request)) => requests.push(request),
+                    _ => break,
+                }
+            }
+            metrics::histogram!("signature_checker.batch_size", requests.len() as f64);
+
+            let (onchain_requests, offchain_requests): (Vec<_>, Vec<_>) = requests
+                .into_iter()
+                .partition(|request| request.data.requires_ethereum_queries());
+
+            for VerifySignatureRequest { data, response } in onchain_requests {
+                let eth_checker = eth_checker.clone();
+                let pool = pool.clone();
+                tokio::spawn(async move {
+                    if let Err(err) = verify_eth_signature(&data, &eth_checker).await {
+                        response.send(Err(err)).unwrap_or_default();
+                        return;
+                    }
+                    pool.spawn(move || {
+                        let resp = VerifiedTx::verify_correctness(&data);
+                        response.send(resp).unwrap_or_default();
+                    });
+                });
+            }
+
+            if !offchain_requests.is_empty() {
+                let eth_checker = eth_checker.clone();
+                pool.spawn(move || {
+                    offchain_requests.into_par_iter().for_each(
+                        |VerifySignatureRequest { data, response }| {
+                            let resp = VerifiedTx::verify_offchain(data, &eth_checker);
+                            response.send(resp).unwrap_or_default();
+                        },
+                    );
+                });
+            }
+        }
+    }
+    tokio::spawn(checker_routine(
+        input,
+        eth_checker,
+        Arc::new(pool),
+        config.batch_size,
+    ))
+}
 
-                response.send(resp).unwrap_or_default();
-            });
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use zksync_eth_client::clients::mock::MockEthereum;
+    use zksync_test_account::ZkSyncAccount;
+    use zksync_types::{tx::PackedEthSignature, AccountId, TokenId, TokenKind, H256};
+
+    fn transfer_request(account: &ZkSyncAccount, correct_eth_signature: bool) -> RequestData {
+        let token = Token::new(TokenId(0), Address::zero(), "ETH", 18, TokenKind::ERC20);
+        let (transfer, eth_signature) = account.sign_transfer(
+            token.id,
+            &token.symbol,
+            100u32.into(),
+            10u32.into(),
+            &Address::repeat_byte(1),
+            None,
+            true,
+            Default::default(),
+        );
+        let message = transfer
+            .get_ethereum_sign_message(&token.symbol, token.decimals)
+            .into_bytes();
+        let signature = if correct_eth_signature {
+            eth_signature.unwrap()
+        } else {
+            PackedEthSignature::sign(&H256::repeat_byte(2), &message).unwrap()
+        };
+
+        RequestData::Tx(TxRequest {
+            tx: SignedZkSyncTx {
+                tx: transfer.into(),
+                eth_sign_data: Some(EthSignData {
+                    signature: TxEthSignature::EthereumSignature(signature),
+                    message,
+                }),
+                created_at: Utc::now(),
+            },
+            sender: account.address,
+            token,
+        })
+    }
+
+    /// Checks that the burst of the requests is verified by the thread pool.
+    #[tokio::test]
+    async fn verify_requests_burst() {
+        let account = ZkSyncAccount::rand();
+        account.set_account_id(Some(AccountId(1)));
+
+        let (mut sender, receiver) = mpsc::channel(16);
+        let config = SignatureCheckerConfig {
+            threads: 2,
+            batch_size: 4,
+        };
+        let handle = start_sign_checker(
+            EthereumGateway::Mock(MockEthereum::default()),
+            receiver,
+            config,
+        );
+
+        let mut responses = Vec::new();
+        for i in 0..10 {
+            let (response, receiver) = oneshot::channel();
+            let data = transfer_request(&account, i % 3 != 0);
+            sender
+                .try_send(VerifySignatureRequest { data, response })
+                .unwrap();
+            responses.push(receiver);
+        }
+
+        for (i, response) in responses.into_iter().enumerate() {
+            let resp = response.await.unwrap();
+            if i % 3 != 0 {
+                assert!(resp.is_ok());
+            } else {
+                assert!(matches!(resp, Err(TxAddError::IncorrectEthSignature)));
+            }
         }
+        handle.abort();
     }
-    tokio::spawn(checker_routine(input, eth_checker))
 }
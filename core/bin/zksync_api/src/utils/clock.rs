@@ -0,0 +1,85 @@
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+
+/// Source of the current time for the time-dependent caches and the staleness checks.
+///
+/// Abstracting the time allows tests to control it instead of sleeping.
+pub trait Clock: Debug + Send + Sync + 'static {
+    fn now(&self) -> Instant;
+
+    /// Returns the current wall-clock time, which the timestamps of the prices are compared with.
+    fn utc_now(&self) -> DateTime<Utc>;
+
+    /// Returns the amount of time elapsed since the `earlier` moment.
+    fn elapsed(&self, earlier: Instant) -> Duration {
+        self.now().saturating_duration_since(earlier)
+    }
+}
+
+/// Clock that uses the system monotonic time.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn utc_now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Clock whose time moves only when it's explicitly advanced.
+/// Clones share the same time.
+#[derive(Debug, Clone)]
+pub struct MockClock(Arc<Mutex<(Instant, DateTime<Utc>)>>);
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self(Arc::new(Mutex::new((Instant::now(), Utc::now()))))
+    }
+}
+
+impl MockClock {
+    pub fn advance(&self, duration: Duration) {
+        let mut time = self.0.lock().unwrap();
+        time.0 += duration;
+        time.1 = time.1 + chrono::Duration::from_std(duration).expect("too long duration");
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        self.0.lock().unwrap().0
+    }
+
+    fn utc_now(&self) -> DateTime<Utc> {
+        self.0.lock().unwrap().1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mock_clock_advances_only_explicitly() {
+        let clock = MockClock::default();
+        let start = clock.now();
+        let utc_start = clock.utc_now();
+        assert_eq!(clock.elapsed(start), Duration::from_secs(0));
+
+        clock.clone().advance(Duration::from_secs(5));
+        assert_eq!(clock.elapsed(start), Duration::from_secs(5));
+        assert_eq!(clock.utc_now() - utc_start, chrono::Duration::seconds(5));
+        // The moment in the future is not elapsed at all.
+        assert_eq!(
+            clock.elapsed(start + Duration::from_secs(10)),
+            Duration::from_secs(0)
+        );
+    }
+}
@@ -1,2 +1,5 @@
 pub mod block_details_cache;
+pub mod clock;
+pub mod price_history;
 pub mod shared_lru_cache;
+pub mod ttl_cache;
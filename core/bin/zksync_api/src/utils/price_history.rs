@@ -0,0 +1,85 @@
+use bigdecimal::{BigDecimal, Zero};
+use dashmap::DashMap;
+use std::collections::VecDeque;
+use std::hash::Hash;
+use std::sync::Arc;
+
+/// `PriceHistory` is a thread-safe buffer keeping a bounded number of the most
+/// recent price points for every key. Once the buffer for a key is full, the oldest
+/// point is discarded.
+#[derive(Clone, Debug)]
+pub struct PriceHistory<K: Eq + Hash> {
+    points: Arc<DashMap<K, VecDeque<BigDecimal>>>,
+    capacity: usize,
+}
+
+impl<K: Eq + Hash> PriceHistory<K> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            points: Arc::new(DashMap::new()),
+            capacity,
+        }
+    }
+
+    pub fn push(&self, key: K, price: BigDecimal) {
+        let mut points = self.points.entry(key).or_default();
+        if points.len() >= self.capacity {
+            points.pop_front();
+        }
+        points.push_back(price);
+    }
+
+    /// Returns the number of the price points stored for the key.
+    pub fn len(&self, key: &K) -> usize {
+        self.points.get(key).map_or(0, |points| points.len())
+    }
+
+    /// Returns the simple moving average over the `window` most recent price points.
+    /// Returns `None` if the window is empty or there are fewer points than requested.
+    pub fn simple_moving_average(&self, key: &K, window: usize) -> Option<BigDecimal> {
+        let points = self.points.get(key)?;
+        if window == 0 || window > points.len() {
+            return None;
+        }
+
+        let sum = points
+            .iter()
+            .rev()
+            .take(window)
+            .fold(BigDecimal::zero(), |sum, price| sum + price);
+        Some(sum / BigDecimal::from(window as u64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spot_and_smoothed_prices() {
+        let history = PriceHistory::new(3);
+        for price in &[100u32, 10, 20, 30] {
+            history.push(1, BigDecimal::from(*price));
+        }
+        // The oldest point has been discarded.
+        assert_eq!(history.len(&1), 3);
+
+        // Window of a single point is the spot price.
+        assert_eq!(
+            history.simple_moving_average(&1, 1),
+            Some(BigDecimal::from(30))
+        );
+        assert_eq!(
+            history.simple_moving_average(&1, 2),
+            Some(BigDecimal::from(25))
+        );
+        assert_eq!(
+            history.simple_moving_average(&1, 3),
+            Some(BigDecimal::from(20))
+        );
+
+        assert_eq!(history.simple_moving_average(&1, 0), None);
+        assert_eq!(history.simple_moving_average(&1, 4), None);
+        assert_eq!(history.simple_moving_average(&2, 1), None);
+    }
+}
@@ -0,0 +1,183 @@
+use dashmap::DashMap;
+use std::hash::Hash;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::clock::{Clock, SystemClock};
+
+/// `TtlCache` is a thread-safe cache whose entries expire after the configured time-to-live.
+///
+/// Unlike the `SharedLruCache`, it is backed by a sharded concurrent map, so the readers of
+/// different keys don't serialize on a single lock. Expired entries are evicted lazily on
+/// access, and can also be purged in bulk via `evict_expired`.
+#[derive(Clone, Debug)]
+pub struct TtlCache<K: Eq + Hash, V: Clone> {
+    entries: Arc<DashMap<K, (V, Instant)>>,
+    ttl: Duration,
+    clock: Arc<dyn Clock>,
+}
+
+impl<K: Eq + Hash, V: Clone> TtlCache<K, V> {
+    pub fn new(ttl: Duration) -> Self {
+        Self::with_clock(ttl, Arc::new(SystemClock))
+    }
+
+    pub fn with_clock(ttl: Duration, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            entries: Arc::new(DashMap::new()),
+            ttl,
+            clock,
+        }
+    }
+
+    pub fn insert(&self, key: K, value: V) {
+        self.entries.insert(key, (value, self.clock.now()));
+    }
+
+    /// Returns the cached value if it has not expired yet.
+    pub fn get(&self, key: &K) -> Option<V> {
+        let expired = match self.entries.get(key) {
+            Some(entry) => {
+                let (value, inserted_at) = entry.value();
+                if self.clock.elapsed(*inserted_at) < self.ttl {
+                    return Some(value.clone());
+                }
+                true
+            }
+            None => false,
+        };
+
+        if expired {
+            // The read guard is released at this point, so another thread could have already
+            // refreshed the entry. We remove it only if it's still expired.
+            let ttl = self.ttl;
+            let clock = &self.clock;
+            self.entries.remove_if(key, |_, (_, inserted_at)| {
+                clock.elapsed(*inserted_at) >= ttl
+            });
+        }
+        None
+    }
+
+    /// Removes all the expired entries from the cache.
+    pub fn evict_expired(&self) {
+        let ttl = self.ttl;
+        let clock = &self.clock;
+        self.entries
+            .retain(|_, (_, inserted_at)| clock.elapsed(*inserted_at) < ttl);
+    }
+
+    /// Returns the number of entries in the cache, including the expired ones
+    /// that haven't been evicted yet.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::clock::MockClock;
+    use std::thread;
+
+    const TTL: Duration = Duration::from_millis(200);
+    const THREADS: usize = 8;
+    const KEYS: u32 = 100;
+
+    fn read_all_concurrently(cache: &TtlCache<u32, u32>) -> Vec<Option<u32>> {
+        let handles: Vec<_> = (0..THREADS)
+            .map(|_| {
+                let cache = cache.clone();
+                thread::spawn(move || (0..KEYS).map(|key| cache.get(&key)).collect::<Vec<_>>())
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect()
+    }
+
+    /// Checks that entries are served until they expire and are evicted afterwards,
+    /// while being accessed from several threads at once.
+    #[test]
+    fn entries_expire_under_concurrent_access() {
+        let cache = TtlCache::new(TTL);
+        for key in 0..KEYS {
+            cache.insert(key, key * 2);
+        }
+
+        let values = read_all_concurrently(&cache);
+        assert!(values.iter().all(Option::is_some));
+
+        thread::sleep(TTL * 2);
+
+        let values = read_all_concurrently(&cache);
+        assert!(values.iter().all(Option::is_none));
+        assert!(cache.is_empty());
+    }
+
+    /// Checks that the lazy eviction doesn't remove an entry that was refreshed
+    /// by a concurrent writer.
+    #[test]
+    fn refreshed_entry_survives_concurrent_eviction() {
+        let cache = TtlCache::new(TTL);
+        cache.insert(1, 1);
+        thread::sleep(TTL + TTL / 4);
+
+        let readers: Vec<_> = (0..THREADS)
+            .map(|_| {
+                let cache = cache.clone();
+                thread::spawn(move || {
+                    for _ in 0..1000 {
+                        cache.get(&1);
+                    }
+                })
+            })
+            .collect();
+        cache.insert(1, 2);
+        for reader in readers {
+            reader.join().unwrap();
+        }
+
+        assert_eq!(cache.get(&1), Some(2));
+    }
+
+    #[test]
+    fn evict_expired() {
+        let cache = TtlCache::new(TTL);
+        cache.insert(1, 1);
+        thread::sleep(TTL + TTL / 4);
+        cache.insert(2, 2);
+
+        cache.evict_expired();
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get(&2), Some(2));
+    }
+
+    /// Checks the expiry boundary without relying on the wall-clock time.
+    #[test]
+    fn entries_expire_with_mock_clock() {
+        let clock = MockClock::default();
+        let cache = TtlCache::with_clock(TTL, Arc::new(clock.clone()));
+        cache.insert(1, 1);
+
+        clock.advance(TTL - Duration::from_millis(1));
+        assert_eq!(cache.get(&1), Some(1));
+
+        // The entry inserted later expires later.
+        cache.insert(2, 2);
+        clock.advance(Duration::from_millis(1));
+        assert_eq!(cache.get(&1), None);
+        assert_eq!(cache.get(&2), Some(2));
+        assert_eq!(cache.len(), 1);
+
+        clock.advance(TTL);
+        cache.evict_expired();
+        assert!(cache.is_empty());
+    }
+}
@@ -0,0 +1,71 @@
+//! Client of the admin API.
+
+// Built-in uses
+use std::time::Duration;
+
+// External uses
+use anyhow::{bail, Context};
+use reqwest::{Method, RequestBuilder};
+use serde_json::Value;
+
+// Workspace uses
+use zksync_utils::auth::encode_auth_token;
+
+// Local uses
+
+/// The auth tokens expire after this time, a new token is generated for every request.
+const AUTH_TOKEN_LIFETIME: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone)]
+pub struct AdminClient {
+    client: reqwest::Client,
+    url: String,
+    secret_auth: String,
+}
+
+impl AdminClient {
+    pub fn new(url: String, secret_auth: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url: url.trim_end_matches('/').to_string(),
+            secret_auth,
+        }
+    }
+
+    /// Encodes the JWT signed by the admin secret.
+    fn auth_token(&self) -> anyhow::Result<String> {
+        let token = encode_auth_token(&self.secret_auth, AUTH_TOKEN_LIFETIME)?;
+        Ok(token)
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        self.client
+            .request(method, format!("{}/api/admin/{}", self.url, path))
+    }
+
+    /// Sends the request, returns the JSON body of the response or `null` if it's empty.
+    async fn send(&self, request: RequestBuilder) -> anyhow::Result<Value> {
+        let response = request
+            .bearer_auth(self.auth_token()?)
+            .send()
+            .await
+            .context("the admin API is unavailable")?;
+        let status = response.status();
+        let body = response.text().await?;
+        if !status.is_success() {
+            bail!("the admin API has responded with {}: {}", status, body);
+        }
+        if body.is_empty() {
+            return Ok(Value::Null);
+        }
+        Ok(serde_json::from_str(&body)?)
+    }
+
+    pub async fn get(&self, path: &str) -> anyhow::Result<Value> {
+        self.send(self.request(Method::GET, path)).await
+    }
+
+    pub async fn post(&self, path: &str) -> anyhow::Result<Value> {
+        self.send(self.request(Method::POST, path)).await
+    }
+}
@@ -0,0 +1,145 @@
+//! Command line tool for the common actions of the zkSync operators, talking to the admin API
+//! and to the REST API v0.2 of the server.
+
+// Built-in uses
+
+// External uses
+use serde::Serialize;
+use structopt::StructOpt;
+
+// Workspace uses
+use zksync_api_client::rest::v02::typed::TypedClient;
+use zksync_types::ethereum::EthOpId;
+
+// Local uses
+use crate::admin::AdminClient;
+
+mod admin;
+
+#[derive(Debug, StructOpt)]
+enum TokensCommand {
+    /// Lists the tokens with their fee eligibility
+    List {
+        /// Lists only the tokens accepted for paying fees.
+        #[structopt(long)]
+        fee_only: bool,
+    },
+    /// Accepts the token for paying fees regardless of its market volume
+    Enable { token_id: u32 },
+    /// Rejects the token for paying fees regardless of its market volume
+    Disable { token_id: u32 },
+    /// Makes the fee eligibility of the token depend on its market volume again
+    Reset { token_id: u32 },
+}
+
+#[derive(Debug, StructOpt)]
+enum EthOperationsCommand {
+    /// Lists the unconfirmed Ethereum operations along with their transactions
+    List,
+    /// Requests the replacement of the stuck transaction of the operation with the higher gas price
+    Bump { id: EthOpId },
+}
+
+#[derive(Debug, StructOpt)]
+enum ConfigCommand {
+    /// Shows the reloadable parameters of the config of the API server
+    Show,
+    /// Reloads the reloadable parameters of the config from the environment of the API server
+    Reload,
+}
+
+#[derive(Debug, StructOpt)]
+enum ProverCommand {
+    /// Shows the connected provers and the depth of the prover job queue
+    Status,
+}
+
+#[derive(Debug, StructOpt)]
+enum Command {
+    /// Manages the tokens accepted for paying fees
+    Tokens(TokensCommand),
+    /// Shows the state of the mempool queue
+    Mempool,
+    /// Manages the operations of the Ethereum sender
+    EthOperations(EthOperationsCommand),
+    /// Manages the config of the API server
+    Config(ConfigCommand),
+    /// Inspects the provers
+    Prover(ProverCommand),
+}
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "zksync-cli", author = "Matter Labs")]
+#[structopt(about = "Tool for the common actions of the zkSync operators")]
+struct Opt {
+    /// URL of the admin API.
+    #[structopt(long, env = "API_ADMIN_URL")]
+    admin_url: String,
+    /// Secret of the auth tokens of the admin API.
+    #[structopt(long, env = "API_ADMIN_SECRET_AUTH", hide_env_values = true)]
+    admin_secret: String,
+    /// URL of the REST API.
+    #[structopt(long, env = "API_REST_URL")]
+    api_url: String,
+    #[structopt(subcommand)]
+    command: Command,
+}
+
+/// Prints the output of the command as JSON, so it can be processed by the other tools.
+fn print_json<T: Serialize>(output: &T) -> anyhow::Result<()> {
+    println!("{}", serde_json::to_string_pretty(output)?);
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let opt = Opt::from_args();
+    let admin = AdminClient::new(opt.admin_url, opt.admin_secret);
+    let api = TypedClient::new(opt.api_url.trim_end_matches('/').to_string());
+
+    match opt.command {
+        Command::Tokens(TokensCommand::List { fee_only }) => {
+            let tokens = api
+                .all_tokens()
+                .await?
+                .into_iter()
+                .filter(|token| !fee_only || token.enabled_for_fees)
+                .collect::<Vec<_>>();
+            print_json(&tokens)?;
+        }
+        Command::Tokens(TokensCommand::Enable { token_id }) => {
+            admin
+                .post(&format!("fee_tokens/{}/enable", token_id))
+                .await?;
+            println!("Token {} is accepted for paying fees", token_id);
+        }
+        Command::Tokens(TokensCommand::Disable { token_id }) => {
+            admin
+                .post(&format!("fee_tokens/{}/disable", token_id))
+                .await?;
+            println!("Token {} is rejected for paying fees", token_id);
+        }
+        Command::Tokens(TokensCommand::Reset { token_id }) => {
+            admin
+                .post(&format!("fee_tokens/{}/reset", token_id))
+                .await?;
+            println!("Fee eligibility of the token {} is reset", token_id);
+        }
+        Command::Mempool => print_json(&api.mempool_stats().await?)?,
+        Command::EthOperations(EthOperationsCommand::List) => {
+            print_json(&admin.get("eth_operations").await?)?
+        }
+        Command::EthOperations(EthOperationsCommand::Bump { id }) => {
+            admin.post(&format!("eth_operations/{}/bump", id)).await?;
+            println!(
+                "Replacement of the transaction of the operation {} is requested",
+                id
+            );
+        }
+        Command::Config(ConfigCommand::Show) => print_json(&admin.get("config").await?)?,
+        Command::Config(ConfigCommand::Reload) => print_json(&admin.post("config/reload").await?)?,
+        Command::Prover(ProverCommand::Status) => print_json(&admin.get("prover_status").await?)?,
+    }
+
+    Ok(())
+}
@@ -19,7 +19,7 @@ mod aggregated_committer;
 
 // In this component, the most interesting part of the database is decimals,
 // Usually we don't change them, so we can invalidate the cache once an hour.
-const TOKEN_INVALIDATE_CACHE: Duration = Duration::from_secs(60 * 60);
+pub const TOKEN_INVALIDATE_CACHE: Duration = Duration::from_secs(60 * 60);
 
 #[derive(Debug)]
 pub enum CommitRequest {
@@ -54,9 +54,12 @@ pub struct ExecutedOpsNotify {
 
 const PROOF_POLL_INTERVAL: Duration = Duration::from_secs(1);
 
-async fn handle_new_commit_task(mut rx_for_ops: Receiver<CommitRequest>, pool: ConnectionPool) {
+async fn handle_new_commit_task(
+    mut rx_for_ops: Receiver<CommitRequest>,
+    pool: ConnectionPool,
+    mut token_db_cache: TokenDBCache,
+) {
     vlog::info!("Run committer");
-    let mut token_db_cache = TokenDBCache::new(TOKEN_INVALIDATE_CACHE);
     token_db_cache
         .fill_token_cache(&mut pool.access_storage().await.unwrap())
         .await;
@@ -303,7 +306,12 @@ pub fn run_committer(
     rx_for_ops: Receiver<CommitRequest>,
     pool: ConnectionPool,
     config: ChainConfig,
+    token_db_cache: TokenDBCache,
 ) -> JoinHandle<()> {
-    tokio::spawn(handle_new_commit_task(rx_for_ops, pool.clone()));
+    tokio::spawn(handle_new_commit_task(
+        rx_for_ops,
+        pool.clone(),
+        token_db_cache,
+    ));
     tokio::spawn(poll_for_new_proofs_task(pool, config))
 }
@@ -67,6 +67,12 @@ pub enum EthWatchRequest {
         last_eth_block: Option<u64>,
         resp: oneshot::Sender<Vec<RegisterNFTFactoryEvent>>,
     },
+    /// Re-reads the NewToken events from the last `window` blocks having
+    /// the sufficient amount of confirmations, used to reconcile the tokens.
+    ReloadNewTokens {
+        window: u64,
+        resp: oneshot::Sender<anyhow::Result<Vec<NewTokenEvent>>>,
+    },
 }
 
 #[derive(Debug, Error)]
@@ -287,6 +293,11 @@ impl<W: EthClient> EthWatch<W> {
 
         // TODO maybe retry? It can be the only problem is database
         receiver.await.expect("Mempool actor was dropped")?;
+        self.mempool_tx_sender
+            .send(MempoolTransactionRequest::EthBlockWatched(
+                current_ethereum_block,
+            ))
+            .await?;
         // The backup block number is not used.
         let state = ETHState::new(
             current_ethereum_block,
@@ -315,6 +326,20 @@ impl<W: EthClient> EthWatch<W> {
 
         events
     }
+    async fn reload_new_tokens(&self, window: u64) -> anyhow::Result<Vec<NewTokenEvent>> {
+        let block_to = self
+            .eth_state
+            .last_ethereum_block()
+            .saturating_sub(self.number_of_confirmations_for_event);
+        let block_from = block_to.saturating_sub(window);
+        self.client
+            .get_new_tokens_events(
+                BlockNumber::Number(block_from.into()),
+                BlockNumber::Number(block_to.into()),
+            )
+            .await
+    }
+
     fn get_new_tokens(&self, last_block_number: Option<u64>) -> Vec<NewTokenEvent> {
         let mut new_tokens = self.eth_state.new_tokens().to_vec();
 
@@ -444,6 +469,9 @@ impl<W: EthClient> EthWatch<W> {
                     resp.send(self.get_register_factory_event(last_eth_block))
                         .ok();
                 }
+                EthWatchRequest::ReloadNewTokens { window, resp } => {
+                    resp.send(self.reload_new_tokens(window).await).ok();
+                }
             }
         }
     }
@@ -152,6 +152,7 @@ async fn fake_mempool(
                 channel.send(Ok(())).unwrap_or_default()
             }
             MempoolTransactionRequest::NewTxsBatch(_, _, _) => unreachable!(),
+            MempoolTransactionRequest::EthBlockWatched(_) => {}
         }
     }
 }
@@ -1,9 +1,9 @@
 use crate::register_factory_handler::run_register_factory_handler;
 use crate::state_keeper::ZkSyncStateInitParams;
 use crate::{
-    committer::run_committer,
+    committer::{run_committer, TOKEN_INVALIDATE_CACHE},
     eth_watch::start_eth_watch,
-    state_keeper::{start_root_hash_calculator, start_state_keeper, ZkSyncStateKeeper},
+    state_keeper::{start_root_hash_calculator, start_state_keeper, SealPolicy, ZkSyncStateKeeper},
     token_handler::run_token_handler,
 };
 use futures::{channel::mpsc, future};
@@ -12,15 +12,18 @@ use zksync_config::{ChainConfig, ZkSyncConfig};
 use zksync_eth_client::EthereumGateway;
 use zksync_mempool::{run_mempool_block_handler, run_mempool_tx_handler};
 use zksync_storage::ConnectionPool;
+use zksync_token_db_cache::{TokenDBCache, TokenDenylist};
 use zksync_types::{tokens::get_genesis_token_list, Token, TokenId, TokenKind};
 
 const DEFAULT_CHANNEL_CAPACITY: usize = 32_768;
 
 pub mod committer;
 pub mod eth_watch;
+pub mod network_stats_aggregator;
 pub mod register_factory_handler;
 pub mod rejected_tx_cleaner;
 pub mod state_keeper;
+pub mod state_pruner;
 pub mod token_handler;
 pub mod tx_event_emitter;
 
@@ -108,6 +111,10 @@ pub async fn run_core(
         connection_pool.clone(),
         mempool_tx_request_receiver,
         config.chain.state_keeper.block_chunk_sizes.clone(),
+        TokenDenylist::new(
+            connection_pool.clone(),
+            config.token_handler.denylisted_tokens.clone(),
+        ),
     );
 
     // Run health check api for core
@@ -148,6 +155,7 @@ pub async fn run_core(
         config.chain.state_keeper.block_chunk_sizes.clone(),
         config.chain.state_keeper.miniblock_iterations as usize,
         config.chain.state_keeper.fast_block_miniblock_iterations as usize,
+        SealPolicy::from_config(&config.chain.state_keeper),
         processed_tx_events_sender,
     );
 
@@ -160,11 +168,16 @@ pub async fn run_core(
     );
     let root_hash_calculator_task = start_root_hash_calculator(root_hash_calculator);
 
+    // The cache of the committer is shared with the token handler, so that the tokens
+    // updated by the latter are invalidated.
+    let token_db_cache = TokenDBCache::new(TOKEN_INVALIDATE_CACHE);
+
     // Start committer.
     let committer_task = run_committer(
         proposed_blocks_receiver,
         connection_pool.clone(),
         config.chain.clone(),
+        token_db_cache.clone(),
     );
 
     // Start mempool.
@@ -180,6 +193,7 @@ pub async fn run_core(
         eth_gateway.clone(),
         &config.token_handler,
         eth_watch_req_sender.clone(),
+        token_db_cache,
     );
 
     // Start token handler.
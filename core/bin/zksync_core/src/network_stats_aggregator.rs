@@ -0,0 +1,50 @@
+//! The aggregator is responsible for the network statistics served by the API:
+//! the TVL of the tokens and the transactions, the volume, the fees and the active
+//! accounts over the last day.
+//!
+//! The statistics are expensive to calculate on every request, so the aggregator
+//! recalculates them periodically and stores them into the database, from where
+//! the API loads them as they are. The aggregator is run by the `network-stats` component.
+
+// Built-in uses
+use std::time::{Duration, Instant};
+
+// External uses
+use chrono::Utc;
+use tokio::{task::JoinHandle, time};
+
+// Workspace deps
+use zksync_storage::ConnectionPool;
+
+/// How often the statistics are recalculated.
+const AGGREGATION_INTERVAL: Duration = Duration::from_secs(60);
+
+#[must_use]
+pub fn run_network_stats_aggregator(db_pool: ConnectionPool) -> JoinHandle<()> {
+    let mut timer = time::interval(AGGREGATION_INTERVAL);
+
+    tokio::spawn(async move {
+        loop {
+            timer.tick().await;
+            if let Err(err) = update_network_stats(&db_pool).await {
+                vlog::error!("Can't update the network stats: {}", err);
+            }
+        }
+    })
+}
+
+async fn update_network_stats(db_pool: &ConnectionPool) -> anyhow::Result<()> {
+    let start = Instant::now();
+    let since = Utc::now() - chrono::Duration::hours(24);
+    let mut storage = db_pool.access_storage().await?;
+    storage
+        .network_stats_schema()
+        .update_network_stats(since)
+        .await?;
+
+    metrics::histogram!(
+        "network_stats_aggregator.update_network_stats",
+        start.elapsed()
+    );
+    Ok(())
+}
@@ -37,12 +37,14 @@ use crate::{
 pub use self::{
     init_params::ZkSyncStateInitParams,
     root_hash_calculator::{start_root_hash_calculator, BlockRootHashJobQueue},
+    seal_policy::SealPolicy,
     types::StateKeeperTestkitRequest,
 };
 
 mod init_params;
 mod pending_block;
 mod root_hash_calculator;
+mod seal_policy;
 mod state_restore;
 mod types;
 mod utils;
@@ -56,6 +58,8 @@ pub struct ZkSyncStateKeeper {
     state: ZkSyncState,
     pending_block: PendingBlock,
     config: StateKeeperConfig,
+    /// Additional triggers of the pending block sealing.
+    seal_policy: SealPolicy,
 
     tx_for_commitments: mpsc::Sender<CommitRequest>,
     tx_for_mempool: mpsc::Sender<MempoolBlocksRequest>,
@@ -85,6 +89,7 @@ impl ZkSyncStateKeeper {
         available_block_chunk_sizes: Vec<usize>,
         max_miniblock_iterations: usize,
         fast_miniblock_iterations: usize,
+        seal_policy: SealPolicy,
         processed_tx_events_sender: mpsc::Sender<ProcessedOperations>,
     ) -> (Self, RootHashCalculator) {
         // We need two copies of state:
@@ -151,6 +156,7 @@ impl ZkSyncStateKeeper {
             state: sk_state,
             pending_block,
             config,
+            seal_policy,
 
             tx_for_commitments,
             tx_for_mempool,
@@ -463,7 +469,18 @@ impl ZkSyncStateKeeper {
 
         // Check whether we should seal this block and start processing the next one, or we just need
         // to persist the pending block.
-        if self.pending_block.should_seal(max_miniblock_iterations) {
+        let seal_trigger = self
+            .seal_policy
+            .seal_trigger(&self.pending_block, system_time_timestamp());
+        if let Some(trigger) = seal_trigger {
+            vlog::debug!(
+                "Sealing the block {} by the {} trigger",
+                *self.pending_block.number,
+                trigger.as_str()
+            );
+            metrics::increment_counter!("state_keeper.seal_trigger", "trigger" => trigger.as_str());
+        }
+        if self.pending_block.should_seal(max_miniblock_iterations) || seal_trigger.is_some() {
             self.seal_pending_block().await;
         } else {
             // State keeper may process empty blocks (or blocks containing rejected transactions only), and it's an
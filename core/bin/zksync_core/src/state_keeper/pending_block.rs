@@ -9,6 +9,7 @@ use zksync_types::{
 
 use crate::committer::AppliedUpdatesRequest;
 // Local uses
+use super::utils::system_time_timestamp;
 
 #[derive(Debug, Clone)]
 pub(super) struct PendingBlock {
@@ -30,6 +31,10 @@ pub(super) struct PendingBlock {
     /// Number of stored account updates in the db (from `account_updates` field)
     pub(super) stored_account_updates: usize,
     pub(super) timestamp: u64,
+    /// Unix timestamp (seconds) of the first successful operation execution in the block.
+    pub(super) first_tx_timestamp: Option<u64>,
+    /// Unix timestamp (seconds) of the first priority operation execution in the block.
+    pub(super) first_priority_op_timestamp: Option<u64>,
 
     // Two fields below are for optimization: we don't want to overwrite all the block contents over and over.
     // With these fields we'll be able save the diff between two pending block states only.
@@ -61,6 +66,8 @@ impl PendingBlock {
             collected_fees: Vec::new(),
             stored_account_updates: 0,
             timestamp,
+            first_tx_timestamp: None,
+            first_priority_op_timestamp: None,
 
             success_txs_pending_len: 0,
             failed_txs_pending_len: 0,
@@ -107,8 +114,11 @@ impl PendingBlock {
         }
         self.pending_op_block_index += 1;
 
+        let now = system_time_timestamp();
+        self.first_tx_timestamp.get_or_insert(now);
         if exec_result.is_priority() {
             self.unprocessed_priority_op_current += 1;
+            self.first_priority_op_timestamp.get_or_insert(now);
         }
 
         self.success_operations.push(exec_result);
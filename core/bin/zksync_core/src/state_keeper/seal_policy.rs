@@ -0,0 +1,163 @@
+//! Additional triggers of the pending block sealing.
+//!
+//! By default, the block is sealed once it has no chunks left or enough miniblock iterations have passed.
+//! The policy allows to seal the block earlier: once it is old enough, once its operations are expensive enough
+//! to commit, or once the priority operations included into it have waited for too long. The triggers only apply
+//! to the blocks with at least one successfully executed operation.
+
+// Built-in deps
+use std::time::Duration;
+// Workspace uses
+use zksync_config::configs::chain::StateKeeper as StateKeeperConfig;
+use zksync_types::U256;
+// Local uses
+use super::pending_block::PendingBlock;
+
+/// Reason of the pending block sealing chosen by the `SealPolicy`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum SealTrigger {
+    /// Too much time has passed since the first operation of the block.
+    BlockAge,
+    /// The commit gas cost of the block operations has reached the budget.
+    GasBudget,
+    /// The first priority operation of the block has waited for too long.
+    PriorityOpDeadline,
+}
+
+impl SealTrigger {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::BlockAge => "block_age",
+            Self::GasBudget => "gas_budget",
+            Self::PriorityOpDeadline => "priority_op_deadline",
+        }
+    }
+}
+
+/// Sealing triggers of the pending block beyond the chunks and iterations limits.
+/// Every trigger is disabled if not set.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SealPolicy {
+    /// Maximum time since the first operation of the block.
+    pub max_block_age: Option<Duration>,
+    /// Maximum commit gas cost of the block operations.
+    pub gas_budget: Option<U256>,
+    /// Maximum time since the first priority operation of the block.
+    pub priority_op_deadline: Option<Duration>,
+}
+
+impl SealPolicy {
+    pub fn from_config(config: &StateKeeperConfig) -> Self {
+        Self {
+            max_block_age: config.seal_block_max_age(),
+            gas_budget: Some(config.seal_block_gas_budget)
+                .filter(|&gas| gas > 0)
+                .map(U256::from),
+            priority_op_deadline: config.seal_priority_op_deadline(),
+        }
+    }
+
+    /// Returns the trigger requiring the block to be sealed at the moment, if any.
+    /// `now` is the current unix timestamp in seconds.
+    pub(super) fn seal_trigger(&self, block: &PendingBlock, now: u64) -> Option<SealTrigger> {
+        let first_tx_timestamp = block.first_tx_timestamp?;
+
+        let expired = |since: u64, timeout: Option<Duration>| {
+            timeout.map_or(false, |timeout| {
+                now.saturating_sub(since) >= timeout.as_secs()
+            })
+        };
+
+        if expired(first_tx_timestamp, self.max_block_age) {
+            Some(SealTrigger::BlockAge)
+        } else if self.gas_budget.map_or(false, |budget| {
+            block.gas_counter.commit_gas_limit() >= budget
+        }) {
+            Some(SealTrigger::GasBudget)
+        } else if block
+            .first_priority_op_timestamp
+            .map_or(false, |since| expired(since, self.priority_op_deadline))
+        {
+            Some(SealTrigger::PriorityOpDeadline)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{gas_counter::GasCounter, BlockNumber};
+
+    use super::*;
+
+    const NOW: u64 = 1_000;
+
+    fn pending_block(first_tx_timestamp: Option<u64>) -> PendingBlock {
+        let mut block = PendingBlock::new(BlockNumber(1), 0, 100, 0);
+        block.first_tx_timestamp = first_tx_timestamp;
+        block
+    }
+
+    #[test]
+    fn no_triggers() {
+        let block = pending_block(Some(0));
+        assert_eq!(SealPolicy::default().seal_trigger(&block, NOW), None);
+    }
+
+    #[test]
+    fn block_age_trigger() {
+        let policy = SealPolicy {
+            max_block_age: Some(Duration::from_secs(60)),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            policy.seal_trigger(&pending_block(Some(NOW - 59)), NOW),
+            None
+        );
+        assert_eq!(
+            policy.seal_trigger(&pending_block(Some(NOW - 60)), NOW),
+            Some(SealTrigger::BlockAge)
+        );
+        // The block without successful operations is not sealed.
+        assert_eq!(policy.seal_trigger(&pending_block(None), NOW), None);
+    }
+
+    #[test]
+    fn gas_budget_trigger() {
+        let base_gas = GasCounter::new().commit_gas_limit();
+        let mut policy = SealPolicy {
+            gas_budget: Some(base_gas + 1),
+            ..Default::default()
+        };
+
+        assert_eq!(policy.seal_trigger(&pending_block(Some(NOW)), NOW), None);
+        policy.gas_budget = Some(base_gas);
+        assert_eq!(
+            policy.seal_trigger(&pending_block(Some(NOW)), NOW),
+            Some(SealTrigger::GasBudget)
+        );
+        assert_eq!(policy.seal_trigger(&pending_block(None), NOW), None);
+    }
+
+    #[test]
+    fn priority_op_deadline_trigger() {
+        let policy = SealPolicy {
+            priority_op_deadline: Some(Duration::from_secs(10)),
+            ..Default::default()
+        };
+
+        // The block without priority operations is not sealed.
+        let mut block = pending_block(Some(NOW - 100));
+        assert_eq!(policy.seal_trigger(&block, NOW), None);
+
+        block.first_priority_op_timestamp = Some(NOW - 9);
+        assert_eq!(policy.seal_trigger(&block, NOW), None);
+        block.first_priority_op_timestamp = Some(NOW - 10);
+        assert_eq!(
+            policy.seal_trigger(&block, NOW),
+            Some(SealTrigger::PriorityOpDeadline)
+        );
+    }
+}
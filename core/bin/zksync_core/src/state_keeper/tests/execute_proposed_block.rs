@@ -1,11 +1,12 @@
 use chrono::Utc;
 use zksync_mempool::ProposedBlock;
 use zksync_types::{
-    mempool::SignedTxVariant, AccountId, BlockNumber, Nonce, SignedZkSyncTx, TokenId, Transfer,
-    ZkSyncTx,
+    gas_counter::GasCounter, mempool::SignedTxVariant, AccountId, BlockNumber, Nonce,
+    SignedZkSyncTx, TokenId, Transfer, ZkSyncTx,
 };
 
 use super::utils::*;
+use crate::state_keeper::SealPolicy;
 
 /// Checks if executing a proposed_block with just enough chunks is done correctly
 /// and checks if number of chunks left is correct after each operation.
@@ -236,6 +237,34 @@ async fn fast_withdrawal() {
     tester.assert_sealed().await;
 }
 
+/// Checks that the block is sealed once the seal policy trigger fires, even though
+/// there are chunks and iterations left.
+#[tokio::test]
+async fn seal_policy_trigger() {
+    let mut tester = StateKeeperTester::new(20, 100, 100);
+    tester.state_keeper.seal_policy = SealPolicy {
+        gas_budget: Some(GasCounter::new().commit_gas_limit()),
+        ..Default::default()
+    };
+
+    // The empty block is not sealed.
+    tester
+        .state_keeper
+        .execute_proposed_block(ProposedBlock::new())
+        .await;
+    assert_eq!(tester.state_keeper.pending_block.number, BlockNumber(1));
+
+    apply_single_transfer(&mut tester).await;
+    tester
+        .assert_sealed_with(|block| assert_eq!(block.block_transactions.len(), 1))
+        .await;
+    assert!(tester
+        .state_keeper
+        .pending_block
+        .first_tx_timestamp
+        .is_none());
+}
+
 /// Checks the following things:
 /// 1. if proposed block is empty, no pending block is yielded from the state keeper.
 /// 2. if there were no successful operations in the block, pending block iteration is not incremented after empty or rejected-only updates.
@@ -43,6 +43,7 @@ impl StateKeeperTester {
             vec![available_chunk_size],
             max_iterations,
             fast_iterations,
+            SealPolicy::default(),
             events_sender,
         );
 
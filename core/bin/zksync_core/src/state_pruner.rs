@@ -0,0 +1,129 @@
+//! The pruner is responsible for removing the history of the old blocks from the database:
+//! the superseded state diffs, the events and the executed operations left in the mempool.
+//!
+//! Only the blocks older than the retention horizon are pruned, which is the configured amount
+//! of blocks before the last verified block or the last block with the account tree cache,
+//! whichever is earlier. The state diffs after the horizon are kept, so the state keeper and
+//! the witness generator can still restore the state, and the verified state and proofs
+//! aren't touched at all.
+//!
+//! The blocks whose events aren't consumed by the event consumers (e.g. the event listener) yet
+//! are kept as well, so the lagging consumer doesn't lose the events.
+//!
+//! The blocks are pruned in batches, every batch is pruned within one database transaction
+//! along with the checkpoint, so the pruner continues from the last pruned batch after restarts.
+//! The pruner is opt-in and run only by the `state-pruner` component.
+
+// Built-in uses
+use std::time::Instant;
+
+// External uses
+use tokio::{task::JoinHandle, time};
+
+// Workspace deps
+use zksync_config::DBConfig;
+use zksync_storage::ConnectionPool;
+use zksync_types::BlockNumber;
+
+#[must_use]
+pub fn run_state_pruner(config: &DBConfig, db_pool: ConnectionPool) -> JoinHandle<()> {
+    let retention_blocks = config.pruning_retention_blocks;
+    let blocks_per_iteration = config.pruning_blocks_per_iteration.max(1);
+    let mut timer = time::interval(config.pruning_interval());
+
+    tokio::spawn(async move {
+        loop {
+            timer.tick().await;
+            if let Err(err) = prune_history(&db_pool, retention_blocks, blocks_per_iteration).await
+            {
+                vlog::error!("Can't prune the historical state: {}", err);
+            }
+        }
+    })
+}
+
+/// Returns the last block whose history can be pruned.
+fn pruning_horizon(
+    last_verified_block: BlockNumber,
+    last_cached_block: Option<BlockNumber>,
+    retention_blocks: u32,
+) -> BlockNumber {
+    let last_needed_block = last_cached_block.map_or(last_verified_block, |cached_block| {
+        cached_block.min(last_verified_block)
+    });
+    BlockNumber(last_needed_block.saturating_sub(retention_blocks))
+}
+
+async fn prune_history(
+    db_pool: &ConnectionPool,
+    retention_blocks: u32,
+    blocks_per_iteration: u32,
+) -> anyhow::Result<()> {
+    let mut storage = db_pool.access_storage().await?;
+    let last_verified_block = storage
+        .chain()
+        .block_schema()
+        .get_last_verified_confirmed_block()
+        .await?;
+    let last_cached_block = storage
+        .chain()
+        .tree_cache_schema_bincode()
+        .get_last_block_with_account_tree_cache()
+        .await?;
+    let horizon = pruning_horizon(last_verified_block, last_cached_block, retention_blocks);
+
+    let mut last_pruned_block = storage.pruning_schema().load_last_pruned_block().await?;
+    while last_pruned_block < horizon {
+        let start = Instant::now();
+        let last_block =
+            BlockNumber(last_pruned_block.saturating_add(blocks_per_iteration)).min(horizon);
+        let pruned = storage.pruning_schema().prune_blocks(last_block).await?;
+
+        metrics::histogram!("state_pruner.prune_blocks", start.elapsed());
+        metrics::counter!("state_pruner.pruned_rows", pruned.total());
+        metrics::gauge!(
+            "state_pruner.last_pruned_block",
+            *pruned.last_pruned_block as f64
+        );
+        if pruned.last_pruned_block > last_pruned_block {
+            vlog::info!(
+                "Pruned the history of the blocks {}..={}: {:?}",
+                *last_pruned_block + 1,
+                *pruned.last_pruned_block,
+                pruned
+            );
+        }
+        if pruned.last_pruned_block < last_block {
+            vlog::info!(
+                "The events after the block {} aren't consumed yet, the later blocks are kept",
+                *pruned.last_pruned_block
+            );
+            break;
+        }
+        last_pruned_block = last_block;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retention_horizon() {
+        assert_eq!(
+            pruning_horizon(BlockNumber(1000), None, 100),
+            BlockNumber(900)
+        );
+        // The diffs after the account tree cache are needed to restore the tree.
+        assert_eq!(
+            pruning_horizon(BlockNumber(1000), Some(BlockNumber(500)), 100),
+            BlockNumber(400)
+        );
+        assert_eq!(
+            pruning_horizon(BlockNumber(1000), Some(BlockNumber(1010)), 100),
+            BlockNumber(900)
+        );
+        assert_eq!(pruning_horizon(BlockNumber(50), None, 100), BlockNumber(0));
+    }
+}
@@ -3,10 +3,18 @@
 //!
 //! To set the name and the decimals parameter for the token, a match is searched for with the
 //! token list (which is taken from the environment). If the token address is not found in the
-//! trusted token list, then the default values are used (name = "ERC20-{id}", decimals = 18).
+//! trusted token list, then the symbol and the decimals are read from the token contract,
+//! and the default values are used if the contract doesn't provide them
+//! (name = "ERC20-{id}", decimals = 18). The name of the token read from the contract
+//! is stored as its display name.
+//!
+//! The events are processed once, so the token handler also reconciles the stored tokens
+//! with the events re-read over the sliding window of the recent confirmed blocks: the tokens
+//! missed or stored from the reorged events are repaired and removed from the cache.
 
 // Built-in deps
 use std::collections::HashMap;
+use std::time::Instant;
 // External uses
 use futures::{
     channel::{mpsc, oneshot},
@@ -14,27 +22,91 @@ use futures::{
 };
 use tokio::task::JoinHandle;
 // Workspace uses
+use zksync_api_types::v02::token::TokenMetadata;
 use zksync_config::TokenHandlerConfig;
 use zksync_notifier::Notifier;
 use zksync_storage::{tokens::StoreTokenError, ConnectionPool, StorageProcessor};
+use zksync_token_db_cache::TokenDBCache;
 use zksync_types::{
     tokens::{NewTokenEvent, Token, TokenInfo},
-    Address, TokenId, TokenKind, U256,
+    Address, TokenId, TokenKind, TokenLike, H256, U256,
 };
 // Local uses
 use crate::eth_watch::EthWatchRequest;
 use web3::contract::Options;
-use zksync_contracts::erc20_contract;
+use zksync_contracts::{erc20_bytes32_metadata_contract, erc20_contract, erc20_metadata_contract};
 use zksync_eth_client::EthereumGateway;
 
+/// Symbols longer than this are not accepted from the token contracts.
+const MAX_SYMBOL_LENGTH: usize = 16;
+/// Names longer than this are not accepted from the token contracts.
+const MAX_NAME_LENGTH: usize = 64;
+
+/// Parameters of the token read from its contract.
+#[derive(Debug, Clone, Default)]
+struct OnChainTokenMetadata {
+    symbol: Option<String>,
+    name: Option<String>,
+    decimals: Option<u8>,
+}
+
+/// Decodes the string returned as `bytes32`, which is padded with zero bytes.
+fn bytes32_to_string(bytes: H256) -> Option<String> {
+    let bytes = bytes.as_bytes();
+    let len = bytes
+        .iter()
+        .position(|byte| *byte == 0)
+        .unwrap_or(bytes.len());
+    String::from_utf8(bytes[..len].to_vec()).ok()
+}
+
+/// Returns the trimmed string if it's not empty, not too long and consists of
+/// the printable ASCII characters, spaces are allowed only if `allow_spaces` is set.
+fn sanitize_token_string(value: &str, max_length: usize, allow_spaces: bool) -> Option<String> {
+    let value = value.trim();
+    let is_valid = !value.is_empty()
+        && value.len() <= max_length
+        && value
+            .chars()
+            .all(|c| c.is_ascii_graphic() || (allow_spaces && c == ' '));
+    if is_valid {
+        Some(value.to_string())
+    } else {
+        None
+    }
+}
+
+/// Difference between the stored token and its event on L1.
+#[derive(Debug, Clone, PartialEq)]
+enum TokenDiscrepancy {
+    /// The event of the token was missed.
+    Missing,
+    /// The token was stored from the reorged event with another address.
+    AddressMismatch { stored: Address, actual: Address },
+}
+
+fn token_discrepancy(stored: Option<&Token>, event: &NewTokenEvent) -> Option<TokenDiscrepancy> {
+    match stored {
+        None => Some(TokenDiscrepancy::Missing),
+        Some(token) if token.address != event.address => Some(TokenDiscrepancy::AddressMismatch {
+            stored: token.address,
+            actual: event.address,
+        }),
+        Some(_) => None,
+    }
+}
+
 struct TokenHandler {
     connection_pool: ConnectionPool,
     poll_interval: std::time::Duration,
+    reconciliation_interval: std::time::Duration,
+    reconciliation_window: u64,
     eth_watcher_req: mpsc::Sender<EthWatchRequest>,
     eth_client: EthereumGateway,
     token_list: HashMap<Address, TokenInfo>,
     last_eth_block: Option<u64>,
     notifier: Option<Notifier>,
+    token_db_cache: TokenDBCache,
 }
 
 impl TokenHandler {
@@ -43,8 +115,10 @@ impl TokenHandler {
         eth_watcher_req: mpsc::Sender<EthWatchRequest>,
         eth_client: EthereumGateway,
         config: TokenHandlerConfig,
+        token_db_cache: TokenDBCache,
     ) -> Self {
         let poll_interval = config.poll_interval();
+        let reconciliation_interval = config.reconciliation_interval();
         let token_list = config
             .token_list()
             .into_iter()
@@ -59,9 +133,12 @@ impl TokenHandler {
             eth_client,
             token_list,
             poll_interval,
+            reconciliation_interval,
+            reconciliation_window: config.reconciliation_window,
             notifier,
             last_eth_block: None, // TODO: Maybe load last viewed Ethereum block number for TokenHandler from DB (ZKS-518).
             eth_watcher_req,
+            token_db_cache,
         }
     }
 
@@ -79,6 +156,20 @@ impl TokenHandler {
         receiver.await.expect("Err response from eth watch")
     }
 
+    async fn reload_new_token_events(&self) -> anyhow::Result<Vec<NewTokenEvent>> {
+        let (sender, receiver) = oneshot::channel();
+        self.eth_watcher_req
+            .clone()
+            .send(EthWatchRequest::ReloadNewTokens {
+                window: self.reconciliation_window,
+                resp: sender,
+            })
+            .await
+            .expect("ETH watch req receiver dropped");
+
+        receiver.await.expect("Err response from eth watch")
+    }
+
     async fn is_contract_erc20(&self, address: Address) -> bool {
         self.eth_client
             .call_contract_function::<U256, _, _, _>(
@@ -94,6 +185,114 @@ impl TokenHandler {
             .is_ok()
     }
 
+    /// Calls the metadata function of the token contract returning a string,
+    /// the non-standard tokens returning `bytes32` are supported as well.
+    async fn query_token_string(&self, func: &str, address: Address) -> Option<String> {
+        let value = self
+            .eth_client
+            .call_contract_function::<String, _, _, _>(
+                func,
+                (),
+                None,
+                Options::default(),
+                None,
+                address,
+                erc20_metadata_contract(),
+            )
+            .await;
+        match value {
+            Ok(value) => Some(value),
+            Err(_) => self
+                .eth_client
+                .call_contract_function::<H256, _, _, _>(
+                    func,
+                    (),
+                    None,
+                    Options::default(),
+                    None,
+                    address,
+                    erc20_bytes32_metadata_contract(),
+                )
+                .await
+                .ok()
+                .and_then(bytes32_to_string),
+        }
+    }
+
+    /// Reads the symbol, the name and the decimals of the token from its contract,
+    /// the values that can't be read or don't look sane are skipped.
+    async fn query_token_metadata(&self, address: Address) -> OnChainTokenMetadata {
+        let symbol = self
+            .query_token_string("symbol", address)
+            .await
+            .and_then(|symbol| sanitize_token_string(&symbol, MAX_SYMBOL_LENGTH, false));
+        let name = self
+            .query_token_string("name", address)
+            .await
+            .and_then(|name| sanitize_token_string(&name, MAX_NAME_LENGTH, true));
+        let decimals = self
+            .eth_client
+            .call_contract_function::<U256, _, _, _>(
+                "decimals",
+                (),
+                None,
+                Options::default(),
+                None,
+                address,
+                erc20_metadata_contract(),
+            )
+            .await
+            .ok()
+            .filter(|decimals| *decimals <= U256::from(u8::MAX))
+            .map(|decimals| decimals.as_u32() as u8);
+
+        if symbol.is_none() || decimals.is_none() {
+            vlog::warn!(
+                "Token {:?} doesn't provide the metadata, the default values are used instead",
+                address
+            );
+        }
+        OnChainTokenMetadata {
+            symbol,
+            name,
+            decimals,
+        }
+    }
+
+    /// Builds the token of the event with the parameters from the token list or its contract.
+    async fn build_token(&self, token_event: &NewTokenEvent) -> (Token, OnChainTokenMetadata) {
+        let default_decimals = 18;
+
+        let is_erc20 = self.is_contract_erc20(token_event.address).await;
+        let (token_kind, metadata) = if is_erc20 {
+            let metadata = self.query_token_metadata(token_event.address).await;
+            (TokenKind::ERC20, metadata)
+        } else {
+            (TokenKind::None, OnChainTokenMetadata::default())
+        };
+
+        // Find a token in the list of trusted tokens, then read its parameters from
+        // the contract, otherwise use default values (name = "ERC20-{id}", decimals = 18).
+        let (symbol, decimals) = match self.token_list.get(&token_event.address) {
+            Some(token_info) => (token_info.symbol.clone(), token_info.decimals),
+            None => (
+                metadata
+                    .symbol
+                    .clone()
+                    .unwrap_or_else(|| default_token_symbol(token_event.id)),
+                metadata.decimals.unwrap_or(default_decimals),
+            ),
+        };
+        let token = Token::new(
+            token_event.id,
+            token_event.address,
+            &symbol,
+            decimals,
+            token_kind,
+        );
+        (token, metadata)
+    }
+
     async fn save_new_tokens(
         &self,
         storage: &mut StorageProcessor<'_>,
@@ -110,71 +309,22 @@ impl TokenHandler {
                 continue;
             }
 
-            // Find a token in the list of trusted tokens
-            // or use default values (name = "ERC20-{id}", decimals = 18).
-            let default_symbol = format!("ERC20-{}", token_event.id);
-            let default_decimals = 18;
-
-            let is_erc20 = self.is_contract_erc20(token_event.address).await;
-            let token_kind = if is_erc20 {
-                TokenKind::ERC20
-            } else {
-                TokenKind::None
-            };
+            let default_symbol = default_token_symbol(token_event.id);
+            let (token, metadata) = self.build_token(&token_event).await;
+            let symbol = token.symbol.clone();
+            let token_kind = token.kind;
 
-            let token_from_list = {
-                let token_info = self.token_list.get(&token_event.address).cloned();
-
-                if let Some(token_info) = token_info {
-                    Some(Token::new(
-                        token_event.id,
-                        token_info.address,
-                        &token_info.symbol,
-                        token_info.decimals,
-                        token_kind,
-                    ))
-                } else {
-                    None
-                }
-            };
-
-            let token = match token_from_list {
-                Some(token_from_list) => {
-                    let try_insert_token = token_schema.store_token(token_from_list.clone()).await;
-
-                    match try_insert_token {
-                        Ok(..) => token_from_list,
-                        Err(StoreTokenError::TokenAlreadyExistsError(..)) => {
-                            // If a token with such parameters already exists in the database
-                            // then try insert token with other symbol.
-                            let token = Token::new(
-                                token_from_list.id,
-                                token_from_list.address,
-                                &default_symbol,
-                                token_from_list.decimals,
-                                token_kind,
-                            );
-                            let try_insert_token = token_schema.store_token(token.clone()).await;
-                            match try_insert_token {
-                                Ok(..) => (),
-                                Err(StoreTokenError::Other(anyhow_err)) => return Err(anyhow_err),
-                                Err(StoreTokenError::TokenAlreadyExistsError(err)) => {
-                                    vlog::warn!("failed to store token in database: {}", err)
-                                }
-                            }
-
-                            token
-                        }
-                        Err(StoreTokenError::Other(anyhow_err)) => return Err(anyhow_err),
-                    }
-                }
-                None => {
-                    // Token with default parameters.
+            let mut is_stored = true;
+            let token = match token_schema.store_token(token.clone()).await {
+                Ok(..) => token,
+                Err(StoreTokenError::TokenAlreadyExistsError(..)) if symbol != default_symbol => {
+                    // If a token with such parameters already exists in the database
+                    // then try insert token with other symbol.
                     let token = Token::new(
-                        token_event.id,
-                        token_event.address,
+                        token.id,
+                        token.address,
                         &default_symbol,
-                        default_decimals,
+                        token.decimals,
                         token_kind,
                     );
                     let try_insert_token = token_schema.store_token(token.clone()).await;
@@ -182,14 +332,33 @@ impl TokenHandler {
                         Ok(..) => (),
                         Err(StoreTokenError::Other(anyhow_err)) => return Err(anyhow_err),
                         Err(StoreTokenError::TokenAlreadyExistsError(err)) => {
-                            vlog::warn!("failed to store token in database: {}", err)
+                            vlog::warn!("failed to store token in database: {}", err);
+                            is_stored = false;
                         }
                     }
 
                     token
                 }
+                Err(StoreTokenError::TokenAlreadyExistsError(err)) => {
+                    vlog::warn!("failed to store token in database: {}", err);
+                    is_stored = false;
+                    token
+                }
+                Err(StoreTokenError::Other(anyhow_err)) => return Err(anyhow_err),
             };
 
+            if is_stored {
+                if let Some(name) = metadata.name {
+                    let metadata = TokenMetadata {
+                        display_name: Some(name),
+                        ..Default::default()
+                    };
+                    token_schema
+                        .store_token_metadata(token.id, &metadata)
+                        .await?;
+                }
+            }
+
             new_tokens.push(token);
         }
 
@@ -197,11 +366,109 @@ impl TokenHandler {
         Ok(new_tokens)
     }
 
+    /// Compares the stored tokens with the events re-read from L1 and repairs the tokens
+    /// that were missed or stored from the reorged events. Returns the repaired tokens along
+    /// with the tokens they replaced.
+    async fn reconcile_tokens(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        events: Vec<NewTokenEvent>,
+    ) -> anyhow::Result<Vec<(Token, Option<Token>)>> {
+        let mut transaction = storage.start_transaction().await?;
+        let mut repaired_tokens = Vec::new();
+
+        for token_event in events {
+            let stored = transaction
+                .tokens_schema()
+                .get_token(TokenLike::Id(token_event.id))
+                .await?;
+            let discrepancy = match token_discrepancy(stored.as_ref(), &token_event) {
+                Some(discrepancy) => discrepancy,
+                None => continue,
+            };
+
+            // The address can't be moved from the token stored with another id.
+            let address_owner = transaction
+                .tokens_schema()
+                .get_token(TokenLike::Address(token_event.address))
+                .await?;
+            if let Some(owner) = address_owner.filter(|owner| owner.id != token_event.id) {
+                vlog::error!(
+                    "Token {} can't be repaired ({:?}), its address {:?} is used by the token {}",
+                    token_event.id,
+                    discrepancy,
+                    token_event.address,
+                    owner.id
+                );
+                continue;
+            }
+
+            let (mut token, metadata) = self.build_token(&token_event).await;
+            let symbol_owner = transaction
+                .tokens_schema()
+                .get_token(TokenLike::Symbol(token.symbol.clone()))
+                .await?;
+            if symbol_owner.map_or(false, |owner| owner.id != token.id) {
+                token.symbol = default_token_symbol(token.id);
+            }
+
+            vlog::warn!("Repairing the token {} ({:?})", token.id, discrepancy);
+            transaction
+                .tokens_schema()
+                .store_or_update_token(token.clone())
+                .await?;
+            let metadata = TokenMetadata {
+                display_name: metadata.name,
+                ..Default::default()
+            };
+            transaction
+                .tokens_schema()
+                .store_token_metadata(token.id, &metadata)
+                .await?;
+
+            repaired_tokens.push((token, stored));
+        }
+
+        transaction.commit().await?;
+        Ok(repaired_tokens)
+    }
+
+    async fn reconcile(&self) -> anyhow::Result<()> {
+        let events = self.reload_new_token_events().await?;
+        let mut storage = self.connection_pool.access_storage().await?;
+        let repaired_tokens = self.reconcile_tokens(&mut storage, events).await?;
+
+        for (token, replaced) in repaired_tokens {
+            for token in std::iter::once(&token).chain(replaced.as_ref()) {
+                self.token_db_cache.invalidate(token.id).await;
+                self.token_db_cache.invalidate(token.address).await;
+                self.token_db_cache.invalidate(token.symbol.as_str()).await;
+            }
+            if let Some(notifier) = &self.notifier {
+                notifier
+                    .send_new_token_notify(token)
+                    .await
+                    .unwrap_or_else(|e| {
+                        vlog::error!("Failed to send a token repair notification: {}", e);
+                    });
+            }
+        }
+        Ok(())
+    }
+
     async fn run(&mut self) {
         let mut timer = tokio::time::interval(self.poll_interval);
+        let mut last_reconciliation = Instant::now();
         loop {
             timer.tick().await;
 
+            if last_reconciliation.elapsed() >= self.reconciliation_interval {
+                last_reconciliation = Instant::now();
+                if let Err(err) = self.reconcile().await {
+                    vlog::error!("Failed to reconcile the tokens: {}", err);
+                }
+            }
+
             let new_tokens_events = self.load_new_token_events().await;
 
             // Ether is a standard token, so we can assume that at least the last token ID is zero.
@@ -222,6 +489,13 @@ impl TokenHandler {
                 .await
                 .expect("failed to add tokens to the database");
 
+            // The aliases of the new tokens could be cached before they were added.
+            for token in &new_tokens {
+                self.token_db_cache.invalidate(token.id).await;
+                self.token_db_cache.invalidate(token.address).await;
+                self.token_db_cache.invalidate(token.symbol.as_str()).await;
+            }
+
             // Send a notification that the token has been successfully added to the database.
             if let Some(notifier) = &self.notifier {
                 for token in new_tokens {
@@ -237,18 +511,99 @@ impl TokenHandler {
     }
 }
 
+fn default_token_symbol(token_id: TokenId) -> String {
+    format!("ERC20-{}", token_id)
+}
+
 #[must_use]
 pub fn run_token_handler(
     db_pool: ConnectionPool,
     eth_client: EthereumGateway,
     config: &TokenHandlerConfig,
     eth_watcher_req: mpsc::Sender<EthWatchRequest>,
+    token_db_cache: TokenDBCache,
 ) -> JoinHandle<()> {
     let config = config.clone();
     tokio::spawn(async move {
-        let mut token_handler =
-            TokenHandler::new(db_pool, eth_watcher_req, eth_client, config.clone());
+        let mut token_handler = TokenHandler::new(
+            db_pool,
+            eth_watcher_req,
+            eth_client,
+            config.clone(),
+            token_db_cache,
+        );
 
         token_handler.run().await
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_strings_parsing() {
+        let mut bytes = [0u8; 32];
+        bytes[..3].copy_from_slice(b"MKR");
+        assert_eq!(bytes32_to_string(H256(bytes)).as_deref(), Some("MKR"));
+        bytes[0] = 0xff;
+        assert_eq!(bytes32_to_string(H256(bytes)), None);
+
+        assert_eq!(
+            sanitize_token_string(" USDC ", MAX_SYMBOL_LENGTH, false).as_deref(),
+            Some("USDC")
+        );
+        assert_eq!(
+            sanitize_token_string("USD Coin", MAX_NAME_LENGTH, true).as_deref(),
+            Some("USD Coin")
+        );
+        assert_eq!(
+            sanitize_token_string("USD Coin", MAX_SYMBOL_LENGTH, false),
+            None
+        );
+        assert_eq!(sanitize_token_string("", MAX_SYMBOL_LENGTH, false), None);
+        assert_eq!(
+            sanitize_token_string("TOKEN\n", MAX_SYMBOL_LENGTH, false).as_deref(),
+            Some("TOKEN")
+        );
+        assert_eq!(
+            sanitize_token_string("TO\u{0}KEN", MAX_SYMBOL_LENGTH, false),
+            None
+        );
+        assert_eq!(
+            sanitize_token_string(&"A".repeat(17), MAX_SYMBOL_LENGTH, false),
+            None
+        );
+    }
+
+    #[test]
+    fn token_discrepancies() {
+        let address = Address::repeat_byte(0x01);
+        let event = NewTokenEvent {
+            eth_block_number: 10,
+            address,
+            id: TokenId(5),
+        };
+        let token = Token::new(TokenId(5), address, "TKN", 18, TokenKind::ERC20);
+        assert_eq!(token_discrepancy(Some(&token), &event), None);
+        assert_eq!(
+            token_discrepancy(None, &event),
+            Some(TokenDiscrepancy::Missing)
+        );
+
+        let reorged = Token::new(
+            TokenId(5),
+            Address::repeat_byte(0x02),
+            "TKN",
+            18,
+            TokenKind::ERC20,
+        );
+        assert_eq!(
+            token_discrepancy(Some(&reorged), &event),
+            Some(TokenDiscrepancy::AddressMismatch {
+                stored: reorged.address,
+                actual: address,
+            })
+        );
+    }
+}
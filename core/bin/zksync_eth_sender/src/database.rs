@@ -1,12 +1,14 @@
 // Built-in deps
 use std::collections::VecDeque;
+use std::convert::TryFrom;
 use std::str::FromStr;
 // External uses
+use anyhow::format_err;
 use num::BigUint;
-use zksync_basic_types::{H256, U256};
+use zksync_basic_types::{Address, H256, U256};
 // Workspace uses
 use zksync_storage::{ConnectionPool, StorageProcessor};
-use zksync_types::ethereum::{ETHOperation, EthOpId, InsertedOperationResponse};
+use zksync_types::ethereum::{ETHOperation, ETHTxAttempt, EthOpId, InsertedOperationResponse};
 // Local uses
 use super::transactions::ETHStats;
 use zksync_types::aggregated_operations::{AggregatedActionType, AggregatedOperation};
@@ -48,6 +50,8 @@ pub(super) trait DatabaseInterface {
     ) -> anyhow::Result<()>;
 
     /// Saves a new unconfirmed operation to the database.
+    /// The priority fee is set only for the EIP-1559 transactions.
+    #[allow(clippy::too_many_arguments)]
     async fn save_new_eth_tx(
         &self,
         connection: &mut StorageProcessor<'_>,
@@ -55,6 +59,7 @@ pub(super) trait DatabaseInterface {
         op: Option<(i64, AggregatedOperation)>,
         deadline_block: i64,
         used_gas_price: U256,
+        used_priority_fee: Option<U256>,
         raw_tx: Vec<u8>,
     ) -> anyhow::Result<InsertedOperationResponse>;
 
@@ -73,14 +78,19 @@ pub(super) trait DatabaseInterface {
         eth_op_id: EthOpId,
         new_deadline_block: i64,
         new_gas_value: U256,
+        new_priority_fee: Option<U256>,
     ) -> anyhow::Result<()>;
 
-    /// Marks an operation as completed in the database.
+    /// Marks an operation as completed in the database, `eth_block` is the Ethereum block
+    /// which includes the transaction. The gas used by the transaction is stored as the L1 cost
+    /// of the operation blocks, if known.
     async fn confirm_operation(
         &self,
         connection: &mut StorageProcessor<'_>,
         hash: &H256,
         op: &ETHOperation,
+        eth_block: u64,
+        gas_used: Option<U256>,
     ) -> anyhow::Result<()>;
 
     /// Loads the stored Ethereum operations stats.
@@ -105,6 +115,65 @@ pub(super) trait DatabaseInterface {
         connection: &mut StorageProcessor<'_>,
         op: &ETHOperation,
     ) -> anyhow::Result<bool>;
+
+    /// Registers the operator accounts which aren't stored yet. The main account takes over
+    /// the nonce of the Ethereum operations sent before, the reserve ones start from the provided nonces.
+    async fn register_operator_accounts(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        main_account: Address,
+        reserve_accounts: Vec<(Address, U256)>,
+    ) -> anyhow::Result<()>;
+
+    /// Loads the address of the active operator account, `None` if the accounts aren't registered.
+    async fn load_active_operator(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<Option<Address>>;
+
+    /// Stores the last observed balance of the operator account.
+    async fn update_operator_balance(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        address: Address,
+        balance: U256,
+    ) -> anyhow::Result<()>;
+
+    /// Makes the operator account active starting from the provided nonce.
+    async fn activate_operator_account(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        address: Address,
+        nonce: U256,
+    ) -> anyhow::Result<()>;
+
+    /// Moves the Ethereum operation to the active operator account.
+    /// Returns the address of the account and the new nonce of the operation.
+    async fn reassign_eth_tx_operator(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        eth_op_id: EthOpId,
+    ) -> anyhow::Result<(Address, U256)>;
+
+    /// Stores the sent transaction in the attempts history of the Ethereum operation.
+    async fn add_tx_attempt(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        attempt: &ETHTxAttempt,
+    ) -> anyhow::Result<()>;
+
+    /// Loads the Ethereum blocks at which the first transactions of the operations were sent.
+    async fn load_first_attempt_blocks(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        eth_op_ids: &[EthOpId],
+    ) -> anyhow::Result<Vec<(EthOpId, u64)>>;
+
+    /// Removes and returns the requests of the manual replacement of the stuck transactions.
+    async fn take_tx_bump_requests(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<Vec<EthOpId>>;
 }
 
 /// The actual database wrapper.
@@ -184,6 +253,7 @@ impl DatabaseInterface for Database {
         op: Option<(i64, AggregatedOperation)>,
         deadline_block: i64,
         used_gas_price: U256,
+        used_priority_fee: Option<U256>,
         raw_tx: Vec<u8>,
     ) -> anyhow::Result<InsertedOperationResponse> {
         let result = connection
@@ -193,6 +263,7 @@ impl DatabaseInterface for Database {
                 op,
                 deadline_block,
                 BigUint::from_str(&used_gas_price.to_string()).unwrap(),
+                used_priority_fee.map(|fee| BigUint::from_str(&fee.to_string()).unwrap()),
                 raw_tx,
             )
             .await?;
@@ -218,6 +289,7 @@ impl DatabaseInterface for Database {
         eth_op_id: EthOpId,
         new_deadline_block: i64,
         new_gas_value: U256,
+        new_priority_fee: Option<U256>,
     ) -> anyhow::Result<()> {
         Ok(connection
             .ethereum_schema()
@@ -225,6 +297,7 @@ impl DatabaseInterface for Database {
                 eth_op_id,
                 new_deadline_block,
                 BigUint::from_str(&new_gas_value.to_string()).unwrap(),
+                new_priority_fee.map(|fee| BigUint::from_str(&fee.to_string()).unwrap()),
             )
             .await?)
     }
@@ -256,6 +329,8 @@ impl DatabaseInterface for Database {
         connection: &mut StorageProcessor<'_>,
         hash: &H256,
         op: &ETHOperation,
+        eth_block: u64,
+        gas_used: Option<U256>,
     ) -> anyhow::Result<()> {
         let mut transaction = connection.start_transaction().await?;
 
@@ -312,7 +387,24 @@ impl DatabaseInterface for Database {
             _ => {}
         }
 
-        transaction.ethereum_schema().confirm_eth_tx(hash).await?;
+        if let (Some((_, aggregated_op)), Some(gas_used)) = (&op.op, gas_used) {
+            let (first_block, last_block) = aggregated_op.get_block_range();
+            transaction
+                .ethereum_schema()
+                .store_block_verification_costs(
+                    first_block,
+                    last_block,
+                    op.op_type,
+                    hash,
+                    gas_used.as_u64(),
+                )
+                .await?;
+        }
+
+        transaction
+            .ethereum_schema()
+            .confirm_eth_tx_in_block(hash, Some(eth_block))
+            .await?;
         transaction.commit().await?;
 
         Ok(())
@@ -343,6 +435,111 @@ impl DatabaseInterface for Database {
             .await?;
         Ok(())
     }
+
+    async fn register_operator_accounts(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        main_account: Address,
+        reserve_accounts: Vec<(Address, U256)>,
+    ) -> anyhow::Result<()> {
+        let reserve_accounts = reserve_accounts
+            .into_iter()
+            .map(|(address, nonce)| {
+                i64::try_from(nonce)
+                    .map(|nonce| (address, nonce))
+                    .map_err(|_| format_err!("Nonce of the account {:#x} is too big", address))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        connection
+            .ethereum_schema()
+            .register_operator_accounts(main_account, &reserve_accounts)
+            .await?;
+        Ok(())
+    }
+
+    async fn load_active_operator(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<Option<Address>> {
+        let accounts = connection
+            .ethereum_schema()
+            .load_operator_accounts()
+            .await?;
+        let active = accounts
+            .into_iter()
+            .find(|account| account.is_active)
+            .map(|account| Address::from_slice(&account.address));
+        Ok(active)
+    }
+
+    async fn update_operator_balance(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        address: Address,
+        balance: U256,
+    ) -> anyhow::Result<()> {
+        connection
+            .ethereum_schema()
+            .update_operator_balance(address, BigUint::from_str(&balance.to_string()).unwrap())
+            .await?;
+        Ok(())
+    }
+
+    async fn activate_operator_account(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        address: Address,
+        nonce: U256,
+    ) -> anyhow::Result<()> {
+        let nonce = i64::try_from(nonce)
+            .map_err(|_| format_err!("Nonce of the account {:#x} is too big", address))?;
+        connection
+            .ethereum_schema()
+            .activate_operator_account(address, nonce)
+            .await?;
+        Ok(())
+    }
+
+    async fn reassign_eth_tx_operator(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        eth_op_id: EthOpId,
+    ) -> anyhow::Result<(Address, U256)> {
+        let result = connection
+            .ethereum_schema()
+            .reassign_eth_tx_operator(eth_op_id)
+            .await?;
+        Ok(result)
+    }
+
+    async fn add_tx_attempt(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        attempt: &ETHTxAttempt,
+    ) -> anyhow::Result<()> {
+        connection.ethereum_schema().add_tx_attempt(attempt).await?;
+        Ok(())
+    }
+
+    async fn load_first_attempt_blocks(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        eth_op_ids: &[EthOpId],
+    ) -> anyhow::Result<Vec<(EthOpId, u64)>> {
+        let blocks = connection
+            .ethereum_schema()
+            .load_first_attempt_blocks(eth_op_ids)
+            .await?;
+        Ok(blocks)
+    }
+
+    async fn take_tx_bump_requests(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<Vec<EthOpId>> {
+        let eth_op_ids = connection.ethereum_schema().take_tx_bump_requests().await?;
+        Ok(eth_op_ids)
+    }
 }
 
 impl Database {
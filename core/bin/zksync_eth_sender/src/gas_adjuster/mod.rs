@@ -1,8 +1,9 @@
 // Built-in deps
 use std::{collections::VecDeque, marker::PhantomData, time::Instant};
 // External deps
+use anyhow::format_err;
 use zksync_basic_types::U256;
-use zksync_eth_client::EthereumGateway;
+use zksync_eth_client::{EthereumGateway, GasPriceOracle};
 // Local deps
 use crate::database::DatabaseInterface;
 
@@ -11,9 +12,21 @@ mod parameters;
 #[cfg(test)]
 mod tests;
 
+/// Fees of the EIP-1559 transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct Eip1559Fees {
+    /// Maximum fee per gas, the base fee of the block including the transaction can't exceed it.
+    pub max_fee_per_gas: U256,
+    /// Priority fee per gas (the miner tip).
+    pub max_priority_fee_per_gas: U256,
+}
+
 /// Gas adjuster is an entity capable of scaling the gas price for
 /// all the Ethereum transactions.
 ///
+/// The network gas price is reported by the gas price oracle, which
+/// aggregates the configured sources of the price.
+///
 /// Gas price is adjusted with an upper limit, which is configured
 /// dynamically based on the average gas price observed within past
 /// sent transactions, and with a lower limit (for managing "stuck"
@@ -24,6 +37,8 @@ mod tests;
 pub(super) struct GasAdjuster<DB: DatabaseInterface> {
     /// Collected statistics about recently used gas prices.
     statistics: GasStatistics,
+    /// Source of the network gas price.
+    oracle: GasPriceOracle,
     /// Timestamp of the last maximum gas price update.
     last_price_renewal: Instant,
     /// Timestamp of the last sample added to the `statistics`.
@@ -32,7 +47,7 @@ pub(super) struct GasAdjuster<DB: DatabaseInterface> {
 }
 
 impl<DB: DatabaseInterface> GasAdjuster<DB> {
-    pub async fn new(db: &DB) -> Self {
+    pub async fn new(db: &DB, oracle: GasPriceOracle) -> Self {
         let mut connection = db
             .acquire_connection()
             .await
@@ -43,6 +58,7 @@ impl<DB: DatabaseInterface> GasAdjuster<DB> {
             .expect("Can't load the gas price limit");
         Self {
             statistics: GasStatistics::new(gas_price_limit),
+            oracle,
             last_price_renewal: Instant::now(),
             last_sample_added: Instant::now(),
 
@@ -54,15 +70,16 @@ impl<DB: DatabaseInterface> GasAdjuster<DB> {
         &self,
         ethereum: &EthereumGateway,
         old_tx_gas_price: Option<U256>,
+        fee_increase_percent: u64,
     ) -> anyhow::Result<U256> {
         if let Some(price) = self.statistics.get_average_price() {
             return Ok(price);
         }
 
-        let network_price = ethereum.get_gas_price().await?;
+        let network_price = self.oracle.get_gas_price(ethereum).await?;
         let scaled_price = if let Some(old_price) = old_tx_gas_price {
             // Stuck transaction, scale it up.
-            self.scale_up(old_price, network_price)
+            self.scale_up(old_price, network_price, fee_increase_percent)
         } else {
             // New transaction, use the network price as the base.
             network_price
@@ -71,13 +88,17 @@ impl<DB: DatabaseInterface> GasAdjuster<DB> {
     }
 
     /// Calculates a new gas amount for the replacement of the stuck tx.
-    /// Replacement price is usually suggested to be at least 10% higher, we make it 15% higher.
+    /// Replacement price is usually suggested to be at least 10% higher, the increase is
+    /// set by the resubmission policy and is used only for the replacements.
     pub async fn get_gas_price(
         &mut self,
         ethereum: &EthereumGateway,
         old_tx_gas_price: Option<U256>,
+        fee_increase_percent: u64,
     ) -> anyhow::Result<U256> {
-        let scaled_price = self.get_suggested_price(ethereum, old_tx_gas_price).await?;
+        let scaled_price = self
+            .get_suggested_price(ethereum, old_tx_gas_price, fee_increase_percent)
+            .await?;
         // Now, cut the price if it's too big.
         let price = self.limit_max(scaled_price);
 
@@ -93,13 +114,67 @@ impl<DB: DatabaseInterface> GasAdjuster<DB> {
         Ok(price)
     }
 
+    /// Calculates the fees for the EIP-1559 transaction.
+    ///
+    /// The max fee of the new transaction covers the doubled base fee of the latest block, so the
+    /// transaction remains includable for several full blocks in a row. For the replacement of the
+    /// stuck tx both fees are increased by the provided percentage at least, since the nodes require
+    /// both of them to be at least 10% higher. The max fee is cut by the same limit as the legacy gas price.
+    pub async fn get_eip1559_fees(
+        &mut self,
+        ethereum: &EthereumGateway,
+        priority_fee_per_gas: U256,
+        old_tx_fees: Option<Eip1559Fees>,
+        fee_increase_percent: u64,
+    ) -> anyhow::Result<Eip1559Fees> {
+        let base_fee_per_gas = ethereum.get_base_fee_per_gas().await?.ok_or_else(|| {
+            format_err!("The latest block has no base fee, EIP-1559 isn't supported by the chain")
+        })?;
+
+        let (max_priority_fee, max_fee) = match old_tx_fees {
+            Some(old_fees) => {
+                // Stuck transaction, scale both fees up.
+                let priority_fee = self.scale_up(
+                    old_fees.max_priority_fee_per_gas,
+                    priority_fee_per_gas,
+                    fee_increase_percent,
+                );
+                let max_fee = self.scale_up(
+                    old_fees.max_fee_per_gas,
+                    base_fee_per_gas * 2 + priority_fee,
+                    fee_increase_percent,
+                );
+                (priority_fee, max_fee)
+            }
+            None => (
+                priority_fee_per_gas,
+                base_fee_per_gas * 2 + priority_fee_per_gas,
+            ),
+        };
+        let max_fee = self.limit_max(max_fee);
+        if max_fee == self.get_current_max_price() {
+            vlog::warn!(
+                "Maximum possible max fee per gas will be used: <{}>",
+                max_fee
+            );
+        }
+
+        // The used fees aren't reported to the statistics, since the max fee is an upper bound
+        // of the price, not the price which is actually paid.
+        Ok(Eip1559Fees {
+            max_fee_per_gas: max_fee,
+            // The priority fee can't exceed the max fee.
+            max_priority_fee_per_gas: std::cmp::min(max_priority_fee, max_fee),
+        })
+    }
+
     /// Performs an actualization routine for `GasAdjuster`:
     /// This method is intended to be invoked periodically, and it updates the
     /// current max gas price limit according to the configurable update interval.
     pub async fn keep_updated(&mut self, ethereum: &EthereumGateway, db: &DB) {
         if self.last_sample_added.elapsed() >= parameters::sample_adding_interval() {
             // Report the current price to be gathered by the statistics module.
-            match ethereum.get_gas_price().await {
+            match self.oracle.get_gas_price(ethereum).await {
                 Ok(network_price) => {
                     self.statistics.add_sample(network_price);
 
@@ -150,8 +225,14 @@ impl<DB: DatabaseInterface> GasAdjuster<DB> {
         }
     }
 
-    fn scale_up(&self, price_to_scale: U256, current_network_price: U256) -> U256 {
-        let replacement_price = (price_to_scale * U256::from(115)) / U256::from(100);
+    fn scale_up(
+        &self,
+        price_to_scale: U256,
+        current_network_price: U256,
+        fee_increase_percent: u64,
+    ) -> U256 {
+        let replacement_price =
+            (price_to_scale * U256::from(100 + fee_increase_percent)) / U256::from(100);
         std::cmp::max(current_network_price, replacement_price)
     }
 
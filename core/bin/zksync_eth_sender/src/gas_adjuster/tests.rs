@@ -3,12 +3,15 @@
 use zksync_basic_types::U256;
 // Local uses
 use crate::{
-    gas_adjuster::{parameters::limit_scale_factor, GasStatistics},
+    gas_adjuster::{parameters::limit_scale_factor, Eip1559Fees, GasStatistics},
     tests::mock::{default_eth_sender, MockDatabase},
     DatabaseInterface, GasAdjuster,
 };
 
-use zksync_eth_client::ethereum_gateway::EthereumGateway;
+use zksync_eth_client::{ethereum_gateway::EthereumGateway, GasPriceOracle};
+
+/// Gas price increase used for the replacements of the stuck transactions.
+const FEE_INCREASE_PERCENT: u64 = 15;
 
 /// Creates `Ethereum` and `Database` instances for the `GasAdjuster` tests.
 async fn eth_and_db_clients() -> (EthereumGateway, MockDatabase) {
@@ -30,7 +33,8 @@ fn scale_gas_limit(value: u64) -> u64 {
 async fn initial_price() {
     let (mut ethereum, db) = eth_and_db_clients().await;
     let mut connection = db.acquire_connection().await.unwrap();
-    let mut gas_adjuster: GasAdjuster<MockDatabase> = GasAdjuster::new(&db).await;
+    let mut gas_adjuster: GasAdjuster<MockDatabase> =
+        GasAdjuster::new(&db, GasPriceOracle::default()).await;
 
     // Vector of ethereum client prices.
     let test_vector = vec![
@@ -50,7 +54,10 @@ async fn initial_price() {
             .await
             .unwrap();
 
-        let scaled_gas = gas_adjuster.get_gas_price(&ethereum, None).await.unwrap();
+        let scaled_gas = gas_adjuster
+            .get_gas_price(&ethereum, None, FEE_INCREASE_PERCENT)
+            .await
+            .unwrap();
         assert_eq!(scaled_gas, eth_client_price.into());
     }
 }
@@ -62,7 +69,8 @@ async fn initial_price() {
 async fn lower_gas_limit() {
     let (mut ethereum, db) = eth_and_db_clients().await;
 
-    let mut gas_adjuster: GasAdjuster<MockDatabase> = GasAdjuster::new(&db).await;
+    let mut gas_adjuster: GasAdjuster<MockDatabase> =
+        GasAdjuster::new(&db, GasPriceOracle::default()).await;
 
     // Test vector of pairs (ethereum client price, price of the last tx, expected price).
     let test_vector = vec![
@@ -84,13 +92,72 @@ async fn lower_gas_limit() {
 
         // Check that gas price of 1000 is increased to 1150.
         let scaled_gas = gas_adjuster
-            .get_gas_price(&ethereum, Some(previous_price.into()))
+            .get_gas_price(&ethereum, Some(previous_price.into()), FEE_INCREASE_PERCENT)
             .await
             .unwrap();
         assert_eq!(scaled_gas, expected_price.into());
     }
 }
 
+/// Checks the EIP-1559 fees: the max fee of the new transaction covers the doubled base fee
+/// with the priority fee, and both fees of the stuck transaction are increased by 15% at least.
+#[tokio::test]
+async fn eip1559_fees() {
+    const PRICE_LIMIT: i64 = 1000;
+    const PRIORITY_FEE: u64 = 10;
+
+    let (mut ethereum, db) = eth_and_db_clients().await;
+    db.update_gas_price_limit(PRICE_LIMIT).await.unwrap();
+    let mut gas_adjuster: GasAdjuster<MockDatabase> =
+        GasAdjuster::new(&db, GasPriceOracle::default()).await;
+
+    let fees = |max_fee: u64, priority_fee: u64| Eip1559Fees {
+        max_fee_per_gas: max_fee.into(),
+        max_priority_fee_per_gas: priority_fee.into(),
+    };
+    // Test vector of (base fee, fees of the stuck tx, expected fees).
+    let test_vector = vec![
+        (50, None, fees(110, 10)),                      // New tx
+        (50, Some(fees(100, 10)), fees(115, 11)),       // Both fees are increased by 15%
+        (100, Some(fees(100, 10)), fees(211, 11)),      // The base fee has grown since
+        (50, Some(fees(100, 100)), fees(215, 115)),     // Legacy tx is replaced
+        (500, None, fees(1000, 10)),                    // Max fee is cut by the limit
+        (500, Some(fees(1000, 990)), fees(1000, 1000)), // Priority fee can't exceed max fee
+    ];
+
+    for (base_fee, old_fees, expected_fees) in test_vector {
+        ethereum
+            .get_mut_mock()
+            .unwrap()
+            .set_base_fee_per_gas(Some(base_fee.into()))
+            .await
+            .unwrap();
+
+        let fees = gas_adjuster
+            .get_eip1559_fees(
+                &ethereum,
+                PRIORITY_FEE.into(),
+                old_fees,
+                FEE_INCREASE_PERCENT,
+            )
+            .await
+            .unwrap();
+        assert_eq!(fees, expected_fees);
+    }
+
+    // The fees can't be calculated for the chains without EIP-1559.
+    ethereum
+        .get_mut_mock()
+        .unwrap()
+        .set_base_fee_per_gas(None)
+        .await
+        .unwrap();
+    assert!(gas_adjuster
+        .get_eip1559_fees(&ethereum, PRIORITY_FEE.into(), None, FEE_INCREASE_PERCENT)
+        .await
+        .is_err());
+}
+
 // Checks that after re-creation the price limit is restored from the database.
 #[tokio::test]
 async fn gas_price_limit_restore() {
@@ -99,7 +166,8 @@ async fn gas_price_limit_restore() {
 
     let (_, db) = eth_and_db_clients().await;
     db.update_gas_price_limit(PRICE_LIMIT).await.unwrap();
-    let gas_adjuster: GasAdjuster<MockDatabase> = GasAdjuster::new(&db).await;
+    let gas_adjuster: GasAdjuster<MockDatabase> =
+        GasAdjuster::new(&db, GasPriceOracle::default()).await;
 
     assert_eq!(gas_adjuster.get_current_max_price(), PRICE_LIMIT.into());
 }
@@ -115,7 +183,8 @@ async fn initial_upper_gas_limit() {
     let (mut ethereum, db) = eth_and_db_clients().await;
 
     db.update_gas_price_limit(PRICE_LIMIT).await.unwrap();
-    let mut gas_adjuster: GasAdjuster<MockDatabase> = GasAdjuster::new(&db).await;
+    let mut gas_adjuster: GasAdjuster<MockDatabase> =
+        GasAdjuster::new(&db, GasPriceOracle::default()).await;
 
     // Set the gas price in Ethereum, which is greater than the current limit.
     ethereum
@@ -126,7 +195,10 @@ async fn initial_upper_gas_limit() {
         .unwrap();
 
     // Check that gas price of `PRICE_LIMIT` + 1 is clamped to `PRICE_LIMIT`.
-    let scaled_gas = gas_adjuster.get_gas_price(&ethereum, None).await.unwrap();
+    let scaled_gas = gas_adjuster
+        .get_gas_price(&ethereum, None, FEE_INCREASE_PERCENT)
+        .await
+        .unwrap();
     assert_eq!(scaled_gas, PRICE_LIMIT.into());
 
     // Check that gas price is clamped even if both the ethereum client price
@@ -140,7 +212,7 @@ async fn initial_upper_gas_limit() {
     let previous_price = U256::from(PRICE_LIMIT) * 2;
 
     let scaled_gas = gas_adjuster
-        .get_gas_price(&ethereum, Some(previous_price))
+        .get_gas_price(&ethereum, Some(previous_price), FEE_INCREASE_PERCENT)
         .await
         .unwrap();
     assert_eq!(scaled_gas, PRICE_LIMIT.into());
@@ -155,7 +227,8 @@ async fn average_gas_price_stored_correctly() {
     let (mut ethereum, db) = eth_and_db_clients().await;
 
     db.update_gas_price_limit(PRICE_LIMIT).await.unwrap();
-    let mut gas_adjuster: GasAdjuster<MockDatabase> = GasAdjuster::new(&db).await;
+    let mut gas_adjuster: GasAdjuster<MockDatabase> =
+        GasAdjuster::new(&db, GasPriceOracle::default()).await;
 
     let initial_db_price = db.average_gas_price().await;
     assert_eq!(initial_db_price, 0u64.into()); // Check just in case.
@@ -204,7 +277,8 @@ async fn gas_price_limit_scaling() {
 
     db.update_gas_price_limit(PRICE_LIMIT as i64).await.unwrap();
 
-    let mut gas_adjuster: GasAdjuster<MockDatabase> = GasAdjuster::new(&db).await;
+    let mut gas_adjuster: GasAdjuster<MockDatabase> =
+        GasAdjuster::new(&db, GasPriceOracle::default()).await;
 
     // Set the client price way beyond the limit.
     ethereum
@@ -220,7 +294,7 @@ async fn gas_price_limit_scaling() {
     // The reason for the dividing is that we update samples it twice per iteration
     for _ in 0..N_SAMPLES / 2 {
         let suggested_price = gas_adjuster
-            .get_gas_price(&ethereum, Some(expected_price.into()))
+            .get_gas_price(&ethereum, Some(expected_price.into()), FEE_INCREASE_PERCENT)
             .await
             .unwrap();
 
@@ -287,7 +361,8 @@ async fn gas_price_limit_average_basis() {
     let (mut ethereum, db) = eth_and_db_clients().await;
     let mut connection = db.acquire_connection().await.unwrap();
     db.update_gas_price_limit(PRICE_LIMIT as i64).await.unwrap();
-    let mut gas_adjuster: GasAdjuster<MockDatabase> = GasAdjuster::new(&db).await;
+    let mut gas_adjuster: GasAdjuster<MockDatabase> =
+        GasAdjuster::new(&db, GasPriceOracle::default()).await;
 
     // Set the client price way beyond the limit.
     ethereum
@@ -306,7 +381,7 @@ async fn gas_price_limit_average_basis() {
         // Request the gas price N times to gather statistics in GasAdjuster.
         for _ in 0..N_SAMPLES {
             let suggested_price = gas_adjuster
-                .get_gas_price(&ethereum, Some(expected_price.into()))
+                .get_gas_price(&ethereum, Some(expected_price.into()), FEE_INCREASE_PERCENT)
                 .await
                 .unwrap();
 
@@ -352,7 +427,8 @@ async fn gas_price_limit_preservation() {
     let (mut ethereum, db) = eth_and_db_clients().await;
     let mut connection = db.acquire_connection().await.unwrap();
     db.update_gas_price_limit(price_limit as i64).await.unwrap();
-    let mut gas_adjuster: GasAdjuster<MockDatabase> = GasAdjuster::new(&db).await;
+    let mut gas_adjuster: GasAdjuster<MockDatabase> =
+        GasAdjuster::new(&db, GasPriceOracle::default()).await;
 
     // Set the client price way beyond the limit.
     ethereum
@@ -368,7 +444,10 @@ async fn gas_price_limit_preservation() {
             // Every time we get the new price (without old price provided), so no scaling
             // involved, every time an Ethereum client price is provided (since it's lower
             // than the limit).
-            let suggested_price = gas_adjuster.get_gas_price(&ethereum, None).await.unwrap();
+            let suggested_price = gas_adjuster
+                .get_gas_price(&ethereum, None, FEE_INCREASE_PERCENT)
+                .await
+                .unwrap();
             assert_eq!(suggested_price, SUGGESTED_PRICE.into());
         }
 
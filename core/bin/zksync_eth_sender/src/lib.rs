@@ -11,17 +11,19 @@ use anyhow::format_err;
 use tokio::{task::JoinHandle, time};
 use web3::{
     contract::Options,
-    types::{TransactionReceipt, H256, U256},
+    types::{Address, TransactionReceipt, H256, U256},
 };
 // Workspace uses
 use zksync_config::ETHSenderConfig;
-use zksync_eth_client::{EthereumGateway, SignedCallResult};
-use zksync_storage::ConnectionPool;
-use zksync_types::ethereum::ETHOperation;
+use zksync_eth_client::{EthereumGateway, GasPriceOracle, SignedCallResult};
+use zksync_storage::{ConnectionPool, StorageProcessor};
+use zksync_types::ethereum::{ETHOperation, ETHTxAttempt, ETHTxAttemptReason};
 // Local uses
 use self::{
     database::{Database, DatabaseInterface},
-    gas_adjuster::GasAdjuster,
+    gas_adjuster::{Eip1559Fees, GasAdjuster},
+    operators::{OperatorAccount, Operators},
+    resubmission::{ResubmissionDecision, ResubmissionPolicy},
     transactions::*,
     tx_queue::{TxData, TxQueue, TxQueueBuilder},
 };
@@ -32,6 +34,8 @@ use zksync_types::{
 
 mod database;
 mod gas_adjuster;
+mod operators;
+mod resubmission;
 mod transactions;
 mod tx_queue;
 
@@ -85,6 +89,15 @@ enum TxCheckMode {
 /// keep the list of all sent transaction hashes for one particular operations, since we can't be
 /// sure which one will be committed; thus we have to track all of them.
 ///
+/// If EIP-1559 is enabled in the config, the EIP-1559 transactions are sent instead of the legacy
+/// ones, and both the max fee and the priority fee are increased for the stuck transactions.
+///
+/// The increase of the fees follows the configured escalation curve. The stuck transaction isn't
+/// replaced anymore once the operation has the maximum amount of transactions sent, or once the
+/// resubmission deadline since its first transaction has passed. The replacement can still be
+/// requested manually via the admin API. Every sent transaction is stored in the attempts history
+/// of the operation.
+///
 /// Note: make sure to save signed tx to db before sending it to ETH, this way we can be sure
 /// that state is always recoverable.
 ///
@@ -102,9 +115,20 @@ enum TxCheckMode {
 /// 2. Withdraw operations (only if both commit/verify for the same block operations were sent).
 /// 3. Commit operations.
 ///
+/// # Operator accounts
+///
+/// The transactions are signed by the main operator account, unless the reserve operator accounts
+/// are configured. In this case the account sending the transactions is rotated once its balance
+/// gets lower than the configured minimum, or its transaction is replaced too many times without
+/// being mined. The ongoing operations of the previous account are moved to the new one: they get
+/// the nonces of the new account and are resent by it, so the order of operations is preserved.
+/// The previous transactions of these operations are still tracked, and the operation is committed
+/// by whichever transaction gets mined.
+///
 /// # Failure policy
 ///
-/// By default, `ETHSender` expects no transactions to fail, and thus upon a failure it will
+/// By default, `ETHSender` expects no transactions to fail, and thus upon a failure of all the
+/// transactions of the operation it will
 /// report the incident to the log and then panic to prevent continue working in a probably
 /// erroneous conditions. Failure handling policy is determined by a corresponding callback,
 /// which can be changed if needed.
@@ -113,18 +137,30 @@ struct ETHSender<DB: DatabaseInterface> {
     ongoing_ops: VecDeque<ETHOperation>,
     /// Connection to the database.
     db: DB,
-    /// Ethereum intermediator.
+    /// Ethereum intermediator, signs the transactions of the main operator account.
     ethereum: EthereumGateway,
+    /// Operator accounts signing the transactions.
+    operators: Operators,
     /// Queue for ordered transaction processing.
     tx_queue: TxQueue,
     /// Utility for managing the gas price for transactions.
     gas_adjuster: GasAdjuster<DB>,
+    /// Policy of the replacements of the stuck transactions.
+    resubmission: ResubmissionPolicy,
     /// Settings for the `ETHSender`.
     options: ETHSenderConfig,
 }
 
 impl<DB: DatabaseInterface> ETHSender<DB> {
-    pub async fn new(options: ETHSenderConfig, db: DB, ethereum: EthereumGateway) -> Self {
+    pub async fn new(
+        options: ETHSenderConfig,
+        db: DB,
+        ethereum: EthereumGateway,
+        reserve_operators: Vec<OperatorAccount>,
+    ) -> Self {
+        let mut operators =
+            Operators::new(options.sender.operator_commit_eth_addr, reserve_operators);
+
         let mut connection = db
             .acquire_connection()
             .await
@@ -164,7 +200,20 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             .with_execute_operations_count(stats.last_executed_block)
             .build();
 
-        let gas_adjuster = GasAdjuster::new(&db).await;
+        let gas_price_oracle = GasPriceOracle::from_config(&options.gas_price_oracle);
+        let gas_adjuster = GasAdjuster::new(&db, gas_price_oracle).await;
+
+        let mut resubmission = ResubmissionPolicy::new(options.resubmission.clone());
+        let eth_op_ids: Vec<_> = ongoing_ops.iter().map(|op| op.id).collect();
+        let first_attempt_blocks = db
+            .load_first_attempt_blocks(&mut transaction, &eth_op_ids)
+            .await
+            .expect("Can't load the attempts history of the ongoing operations");
+        resubmission.restore(first_attempt_blocks);
+
+        Self::restore_active_operator(&db, &mut transaction, &ethereum, &mut operators)
+            .await
+            .expect("Can't restore the active operator account");
 
         transaction
             .commit()
@@ -176,12 +225,48 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             ongoing_ops,
             db,
             ethereum,
+            operators,
             tx_queue,
             gas_adjuster,
+            resubmission,
             options,
         }
     }
 
+    /// Registers the configured operator accounts and loads the active one. If the active account
+    /// isn't configured anymore, the main account becomes active.
+    async fn restore_active_operator(
+        db: &DB,
+        connection: &mut StorageProcessor<'_>,
+        ethereum: &EthereumGateway,
+        operators: &mut Operators,
+    ) -> anyhow::Result<()> {
+        if operators.is_rotation_enabled() {
+            let mut reserve_accounts = Vec::new();
+            for account in operators.reserve_accounts() {
+                let nonce = account.ethereum.pending_nonce().await?;
+                reserve_accounts.push((account.address, nonce));
+            }
+            db.register_operator_accounts(connection, operators.main_address(), reserve_accounts)
+                .await?;
+        }
+
+        match db.load_active_operator(connection).await? {
+            Some(active) if operators.contains(active) => operators.set_active(active)?,
+            Some(active) => {
+                vlog::warn!(
+                    "Active operator account {:#x} isn't configured, the main account is used instead",
+                    active
+                );
+                let nonce = ethereum.pending_nonce().await?;
+                db.activate_operator_account(connection, operators.main_address(), nonce)
+                    .await?;
+            }
+            None => {}
+        }
+        Ok(())
+    }
+
     /// Main routine of `ETHSender`.
     pub async fn run(mut self) {
         // `eth_sender` must perform some of the activities only once per block change.
@@ -258,6 +343,16 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             }
         };
 
+        // The new operations must not be sent until the ongoing ones are moved to the active
+        // operator account, otherwise they could be mined out of order.
+        if let Err(e) = self.keep_operators_updated(current_block).await {
+            Self::process_error(e).await;
+            return last_used_block;
+        }
+        if let Err(e) = self.process_tx_bump_requests(current_block).await {
+            Self::process_error(e).await;
+        }
+
         while let Some(tx) = self.tx_queue.pop_front() {
             if let Err(e) = self.initialize_operation(tx.clone(), current_block).await {
                 Self::process_error(e).await;
@@ -338,10 +433,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
     /// Stores the new operation in the database and sends the corresponding transaction.
     async fn initialize_operation(&mut self, tx: TxData, current_block: u64) -> anyhow::Result<()> {
         let deadline_block = self.get_deadline_block(current_block);
-        let gas_price = self
-            .gas_adjuster
-            .get_gas_price(&self.ethereum, None)
-            .await?;
+        let (gas_price, priority_fee) = self.get_tx_fees(None, 0).await?;
 
         let mut connection = self.db.acquire_connection().await?;
         let mut transaction = connection.start_transaction().await?;
@@ -358,6 +450,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                     Some(tx.operation.clone()),
                     deadline_block as i64,
                     gas_price,
+                    priority_fee,
                     tx.raw.clone(),
                 )
                 .await?;
@@ -367,8 +460,10 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                 op_type: tx.op_type,
                 op: Some(tx.operation),
                 nonce: assigned_data.nonce,
+                operator: assigned_data.operator,
                 last_deadline_block: deadline_block,
                 last_used_gas_price: gas_price,
+                last_used_priority_fee: priority_fee,
                 used_tx_hashes: vec![], // No hash yet, will be added below.
                 encoded_tx_data: tx.raw,
                 confirmed: false,
@@ -376,13 +471,21 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             };
 
             // Sign the transaction.
-            let signed_tx = Self::sign_new_tx(&self.ethereum, &new_op).await?;
+            let ethereum = self.operators.gateway(&self.ethereum, new_op.operator)?;
+            let signed_tx = Self::sign_new_tx(ethereum, &new_op).await?;
 
             // With signed tx, update the hash in the operation entry and in the db.
             new_op.used_tx_hashes.push(signed_tx.hash);
             self.db
                 .add_hash_entry(&mut transaction, new_op.id, &signed_tx.hash)
                 .await?;
+            let attempt = Self::tx_attempt(
+                &new_op,
+                signed_tx.hash,
+                current_block,
+                ETHTxAttemptReason::Initial,
+            );
+            self.db.add_tx_attempt(&mut transaction, &attempt).await?;
 
             (new_op, signed_tx)
         };
@@ -390,6 +493,8 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
         // We should store the operation as `ongoing` **before** sending it as well,
         // so if sending will fail, we won't forget about it.
         self.ongoing_ops.push_back(new_op.clone());
+        self.resubmission
+            .report_first_attempt(new_op.id, current_block);
 
         // After storing all the tx data in the database, we can finally send the tx.
         vlog::info!(
@@ -457,6 +562,9 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
         // Check statuses of existing transactions.
         // Go through every transaction in a loop. We will exit this method early
         // if there will be discovered a pending or successfully committed transaction.
+        // The failure is handled only if none of the transactions can be committed, since
+        // the transactions of the operation may be sent by different operator accounts.
+        let mut failed_tx = None;
         for (idx, tx_hash) in op.used_tx_hashes.iter().enumerate() {
             let mode = if idx == op.used_tx_hashes.len() - 1 {
                 TxCheckMode::Latest
@@ -472,7 +580,10 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                     // Transaction is pending, nothing to do yet.
                     return Ok(OperationCommitment::Pending);
                 }
-                TxCheckOutcome::Committed => {
+                TxCheckOutcome::Committed {
+                    eth_block,
+                    gas_used,
+                } => {
                     let mut connection = self.db.acquire_connection().await?;
                     let mut transaction = connection.start_transaction().await?;
 
@@ -506,9 +617,11 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                         op.id, op.op_type, tx_hash, self.zksync_operation_description(op),
                     );
                     self.db
-                        .confirm_operation(&mut transaction, tx_hash, op)
+                        .confirm_operation(&mut transaction, tx_hash, op, eth_block, gas_used)
                         .await?;
                     transaction.commit().await?;
+                    self.operators.report_commitment(op.id);
+                    self.resubmission.report_commitment(op.id);
                     return Ok(OperationCommitment::Committed);
                 }
                 TxCheckOutcome::Stuck => {
@@ -516,21 +629,69 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                     // the last entry of the list, a new tx will be sent.
                 }
                 TxCheckOutcome::Failed(receipt) => {
-                    vlog::warn!(
-                        "ETH transaction failed: tx: {:#x}, op_type: {:?}, op: {:?}; tx_receipt: {:#?} ",
-                        tx_hash,
-                        op.op_type,
-                        op.op,
-                        receipt,
-                    );
-                    // Process the failure according to the chosen policy.
-                    self.failure_handler(&receipt).await;
+                    failed_tx = Some((*tx_hash, receipt));
                 }
             }
         }
 
+        if let Some((tx_hash, receipt)) = failed_tx {
+            vlog::warn!(
+                "ETH transaction failed: tx: {:#x}, op_type: {:?}, op: {:?}; tx_receipt: {:#?} ",
+                tx_hash,
+                op.op_type,
+                op.op,
+                receipt,
+            );
+            // Process the failure according to the chosen policy.
+            self.failure_handler(&receipt).await;
+        }
+
         // Reaching this point will mean that the latest transaction got stuck.
-        // We should create another tx based on it, and send it.
+        // We should create another tx based on it, and send it, unless the policy forbids it.
+        if let ResubmissionDecision::Hold(reason) = self.resubmission.decide(op, current_block) {
+            if self.resubmission.report_hold(op.id) {
+                vlog::warn!(
+                    "Stuck tx of ETH Operation <id: {}> is not replaced anymore, since {}. The replacement can be requested via the admin API",
+                    op.id,
+                    reason
+                );
+                metrics::increment_counter!("eth_sender.resubmission_hold");
+            }
+            return Ok(OperationCommitment::Pending);
+        }
+        self.send_supplement_tx(op, current_block, ETHTxAttemptReason::Stuck)
+            .await?;
+
+        metrics::histogram!("eth_sender.perform_commitment_step", start.elapsed());
+        Ok(OperationCommitment::Pending)
+    }
+
+    /// Sends a new transaction for the ongoing operation, either the replacement of the stuck one,
+    /// or the one signed by the active operator account if the operation was sent by another one.
+    async fn send_supplement_tx(
+        &mut self,
+        op: &mut ETHOperation,
+        current_block: u64,
+        reason: ETHTxAttemptReason,
+    ) -> anyhow::Result<()> {
+        if self.operators.is_active(op.operator) {
+            self.operators.report_replacement(op.id);
+        } else {
+            let mut connection = self.db.acquire_connection().await?;
+            let (operator, nonce) = self
+                .db
+                .reassign_eth_tx_operator(&mut connection, op.id)
+                .await?;
+            vlog::info!(
+                "ETH Operation <id: {}> is moved to the operator account {:#x}, new nonce: {}",
+                op.id,
+                operator,
+                nonce
+            );
+            op.operator = Some(operator);
+            op.nonce = nonce;
+        }
+
         let deadline_block = self.get_deadline_block(current_block);
         // Raw tx contents are the same for every transaction, so we just
         // create a new one from the old one with updated parameters.
@@ -545,22 +706,153 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                 op.id,
                 deadline_block as i64,
                 new_tx.gas_price,
+                op.last_used_priority_fee,
             )
             .await?;
         self.db
             .add_hash_entry(&mut transaction, op.id, &new_tx.hash)
             .await?;
+        let attempt = Self::tx_attempt(op, new_tx.hash, current_block, reason);
+        self.db.add_tx_attempt(&mut transaction, &attempt).await?;
 
         vlog::info!(
-            "Stuck tx processing: sending tx for op, eth_op_id: {}; ETH tx: {}",
+            "Stuck tx processing: sending tx for op, eth_op_id: {}; reason: {}; ETH tx: {}",
             op.id,
+            reason.as_str(),
             self.eth_tx_description(&new_tx),
         );
         self.ethereum.send_raw_tx(new_tx.raw_tx).await?;
         transaction.commit().await?;
 
-        metrics::histogram!("eth_sender.perform_commitment_step", start.elapsed());
-        Ok(OperationCommitment::Pending)
+        Ok(())
+    }
+
+    /// Stores the balance of the active operator account and rotates the account if it runs low
+    /// on funds or its transactions are stuck, then moves the ongoing operations to the active account.
+    async fn keep_operators_updated(&mut self, current_block: u64) -> anyhow::Result<()> {
+        if !self.operators.is_rotation_enabled() {
+            return Ok(());
+        }
+
+        let active = self.operators.active_address();
+        let balance = self
+            .operators
+            .gateway(&self.ethereum, Some(active))?
+            .sender_eth_balance()
+            .await?;
+        let mut connection = self.db.acquire_connection().await?;
+        self.db
+            .update_operator_balance(&mut connection, active, balance)
+            .await?;
+        drop(connection);
+
+        let min_balance = U256::from(self.options.sender.min_operator_balance);
+        let max_replacements = self.options.sender.max_replacements_per_operator;
+        if balance < min_balance {
+            vlog::warn!(
+                "Balance of the operator account {:#x} is {} wei, which is lower than the minimum {} wei",
+                active,
+                balance,
+                min_balance
+            );
+            self.rotate_operator(min_balance).await?;
+        } else if self.operators.max_replacements() >= max_replacements {
+            vlog::warn!(
+                "Transaction of the operator account {:#x} is replaced {} times without being mined",
+                active,
+                self.operators.max_replacements()
+            );
+            self.rotate_operator(min_balance).await?;
+        }
+
+        // The operations are moved in order, so they are still mined in order.
+        let mut ongoing_ops = std::mem::take(&mut self.ongoing_ops);
+        let mut result = Ok(());
+        for op in ongoing_ops.iter_mut() {
+            if !self.operators.is_active(op.operator) {
+                result = self
+                    .send_supplement_tx(op, current_block, ETHTxAttemptReason::Rotation)
+                    .await;
+                if result.is_err() {
+                    break;
+                }
+            }
+        }
+        self.ongoing_ops = ongoing_ops;
+        result
+    }
+
+    /// Replaces the transactions of the operations requested via the admin API, regardless of
+    /// the resubmission policy. The requests of the operations which aren't ongoing are skipped.
+    async fn process_tx_bump_requests(&mut self, current_block: u64) -> anyhow::Result<()> {
+        let mut connection = self.db.acquire_connection().await?;
+        let eth_op_ids = self.db.take_tx_bump_requests(&mut connection).await?;
+        drop(connection);
+
+        let mut ongoing_ops = std::mem::take(&mut self.ongoing_ops);
+        for eth_op_id in eth_op_ids {
+            let op = match ongoing_ops.iter_mut().find(|op| op.id == eth_op_id) {
+                Some(op) => op,
+                None => {
+                    vlog::warn!(
+                        "Replacement of the tx of ETH Operation <id: {}> is requested, but the operation is not ongoing",
+                        eth_op_id
+                    );
+                    continue;
+                }
+            };
+            vlog::info!(
+                "Replacement of the tx of ETH Operation <id: {}> is requested manually",
+                eth_op_id
+            );
+            // The failed request isn't retried, since the replacement can be requested again.
+            if let Err(e) = self
+                .send_supplement_tx(op, current_block, ETHTxAttemptReason::ManualBump)
+                .await
+            {
+                Self::process_error(e).await;
+            }
+        }
+        self.ongoing_ops = ongoing_ops;
+        Ok(())
+    }
+
+    /// Makes the next operator account with enough funds active. The active account is kept
+    /// if there are no such accounts.
+    async fn rotate_operator(&mut self, min_balance: U256) -> anyhow::Result<()> {
+        let mut connection = self.db.acquire_connection().await?;
+        let mut next_account = None;
+        for (address, ethereum) in self.operators.next_accounts(&self.ethereum) {
+            let balance = ethereum.sender_eth_balance().await?;
+            self.db
+                .update_operator_balance(&mut connection, address, balance)
+                .await?;
+            if balance >= min_balance {
+                next_account = Some((address, ethereum.pending_nonce().await?));
+                break;
+            }
+        }
+
+        let (address, nonce) = match next_account {
+            Some(account) => account,
+            None => {
+                vlog::error!("There are no operator accounts with enough funds to rotate to");
+                return Ok(());
+            }
+        };
+        self.db
+            .activate_operator_account(&mut connection, address, nonce)
+            .await?;
+        drop(connection);
+
+        self.operators.set_active(address)?;
+        metrics::increment_counter!("eth_sender.operator_rotation");
+        vlog::info!(
+            "Operator account {:#x} is active now, nonce: {}",
+            address,
+            nonce
+        );
+        Ok(())
     }
 
     /// Handles a transaction execution failure by reporting the issue to the log
@@ -578,6 +870,25 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
         panic!("Cannot operate after unexpected TX failure");
     }
 
+    /// Creates the attempts history entry of the transaction just signed for the operation.
+    fn tx_attempt(
+        op: &ETHOperation,
+        tx_hash: H256,
+        current_block: u64,
+        reason: ETHTxAttemptReason,
+    ) -> ETHTxAttempt {
+        ETHTxAttempt {
+            eth_op_id: op.id,
+            tx_hash,
+            nonce: op.nonce,
+            gas_price: op.last_used_gas_price,
+            priority_fee: op.last_used_priority_fee,
+            operator: op.operator,
+            sent_at_block: current_block,
+            reason,
+        }
+    }
+
     /// Helper method encapsulating the logic of determining the next deadline block.
     fn get_deadline_block(&self, current_block: u64) -> u64 {
         current_block + self.options.sender.expected_wait_time_block
@@ -602,7 +913,10 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             Some(status) if status.success => {
                 // Check if transaction has enough confirmations.
                 if status.confirmations >= self.options.sender.wait_confirmations {
-                    TxCheckOutcome::Committed
+                    TxCheckOutcome::Committed {
+                        eth_block: current_block.saturating_sub(status.confirmations),
+                        gas_used: status.gas_used,
+                    }
                 } else {
                     TxCheckOutcome::Pending
                 }
@@ -656,13 +970,22 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                 gas_limit
             );
 
-            Options {
-                nonce: Some(op.nonce),
-                gas_price: Some(op.last_used_gas_price),
-                gas: Some(gas_limit),
-                max_fee_per_gas: None,
-                max_priority_fee_per_gas: None,
-                ..Default::default()
+            match op.last_used_priority_fee {
+                Some(priority_fee) => Options {
+                    nonce: Some(op.nonce),
+                    gas: Some(gas_limit),
+                    max_fee_per_gas: Some(op.last_used_gas_price),
+                    max_priority_fee_per_gas: Some(priority_fee),
+                    ..Default::default()
+                },
+                None => Options {
+                    nonce: Some(op.nonce),
+                    gas_price: Some(op.last_used_gas_price),
+                    gas: Some(gas_limit),
+                    max_fee_per_gas: None,
+                    max_priority_fee_per_gas: None,
+                    ..Default::default()
+                },
             }
         };
 
@@ -703,12 +1026,18 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
         stuck_tx: &mut ETHOperation,
     ) -> anyhow::Result<SignedCallResult> {
         let tx_options = self.tx_options_from_stuck_tx(stuck_tx).await?;
+        let priority_fee = tx_options.max_priority_fee_per_gas;
 
         let raw_tx = stuck_tx.encoded_tx_data.clone();
-        let signed_tx = self.ethereum.sign_prepared_tx(raw_tx, tx_options).await?;
+        let signed_tx = self
+            .operators
+            .gateway(&self.ethereum, stuck_tx.operator)?
+            .sign_prepared_tx(raw_tx, tx_options)
+            .await?;
 
         stuck_tx.last_deadline_block = deadline_block;
         stuck_tx.last_used_gas_price = signed_tx.gas_price;
+        stuck_tx.last_used_priority_fee = priority_fee;
         stuck_tx.used_tx_hashes.push(signed_tx.hash);
 
         Ok(signed_tx)
@@ -721,10 +1050,15 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
         stuck_tx: &ETHOperation,
     ) -> anyhow::Result<Options> {
         let old_tx_gas_price = stuck_tx.last_used_gas_price;
+        // The gas price of the legacy transaction is both of its fees for the replacement.
+        let old_tx_fees = Eip1559Fees {
+            max_fee_per_gas: old_tx_gas_price,
+            max_priority_fee_per_gas: stuck_tx.last_used_priority_fee.unwrap_or(old_tx_gas_price),
+        };
 
-        let new_gas_price = self
-            .gas_adjuster
-            .get_gas_price(&self.ethereum, Some(old_tx_gas_price))
+        let fee_increase_percent = self.resubmission.fee_increase_percent(stuck_tx);
+        let (new_gas_price, new_priority_fee) = self
+            .get_tx_fees(Some(old_tx_fees), fee_increase_percent)
             .await?;
         let nonce = stuck_tx.nonce;
         let gas_limit = Self::gas_limit_for_op(stuck_tx);
@@ -736,21 +1070,61 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
         );
 
         vlog::info!(
-            "Replacing tx: hash: {:#x}, old_gas: {}, new_gas: {}, used nonce: {}, gas limit: {}",
+            "Replacing tx: hash: {:#x}, old_gas: {}, new_gas: {}, new_priority_fee: {:?}, used nonce: {}, gas limit: {}",
             stuck_tx.used_tx_hashes.last().unwrap(),
             old_tx_gas_price,
             new_gas_price,
+            new_priority_fee,
             nonce,
             gas_limit,
         );
 
         Ok(Options::with(move |opt| {
-            opt.gas_price = Some(new_gas_price);
+            match new_priority_fee {
+                Some(priority_fee) => {
+                    opt.max_fee_per_gas = Some(new_gas_price);
+                    opt.max_priority_fee_per_gas = Some(priority_fee);
+                }
+                None => opt.gas_price = Some(new_gas_price),
+            }
             opt.nonce = Some(nonce);
             opt.gas = Some(gas_limit);
         }))
     }
 
+    /// Returns the gas price for the new transaction, or for the replacement of the stuck one if
+    /// the fees of the stuck transaction are provided, the fees of the latter are increased by
+    /// the provided percentage at least. If EIP-1559 is enabled, the gas price is the max fee
+    /// per gas and the priority fee is returned as well.
+    async fn get_tx_fees(
+        &mut self,
+        old_tx_fees: Option<Eip1559Fees>,
+        fee_increase_percent: u64,
+    ) -> anyhow::Result<(U256, Option<U256>)> {
+        if self.options.sender.is_eip1559_enabled {
+            let fees = self
+                .gas_adjuster
+                .get_eip1559_fees(
+                    &self.ethereum,
+                    self.options.gas_price_limit.priority_fee_per_gas.into(),
+                    old_tx_fees,
+                    fee_increase_percent,
+                )
+                .await?;
+            Ok((fees.max_fee_per_gas, Some(fees.max_priority_fee_per_gas)))
+        } else {
+            let gas_price = self
+                .gas_adjuster
+                .get_gas_price(
+                    &self.ethereum,
+                    old_tx_fees.map(|fees| fees.max_fee_per_gas),
+                    fee_increase_percent,
+                )
+                .await?;
+            Ok((gas_price, None))
+        }
+    }
+
     /// Encodes the operation data to the Ethereum tx payload (not signs it!).
     fn operation_to_raw_tx(&self, op: &AggregatedOperation) -> Vec<u8> {
         match op {
@@ -800,12 +1174,17 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
 pub fn run_eth_sender(
     pool: ConnectionPool,
     eth_gateway: EthereumGateway,
+    reserve_operators: Vec<(Address, EthereumGateway)>,
     options: ETHSenderConfig,
 ) -> JoinHandle<()> {
     let db = Database::new(pool);
+    let reserve_operators = reserve_operators
+        .into_iter()
+        .map(|(address, ethereum)| OperatorAccount { address, ethereum })
+        .collect();
 
     tokio::spawn(async move {
-        let eth_sender = ETHSender::new(options, db, eth_gateway).await;
+        let eth_sender = ETHSender::new(options, db, eth_gateway, reserve_operators).await;
 
         eth_sender.run().await
     })
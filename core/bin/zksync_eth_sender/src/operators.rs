@@ -0,0 +1,202 @@
+//! Operator accounts which sign the Ethereum transactions of `ETHSender`.
+//!
+//! Besides the main operator account, several reserve ones can be configured. Only one account
+//! is active at a time: the new transactions are sent by it, and the ongoing operations sent by
+//! the other accounts are moved to it. The active account is rotated once its balance gets lower
+//! than the configured minimum or its transactions don't get mined after several replacements
+//! (e.g. because of the stuck nonce). The nonces and the balances of the accounts are tracked
+//! in the database.
+
+// Built-in deps
+use std::collections::HashMap;
+// External uses
+use anyhow::format_err;
+// Workspace uses
+use zksync_basic_types::Address;
+use zksync_eth_client::EthereumGateway;
+use zksync_types::ethereum::EthOpId;
+
+/// Reserve operator account along with the gateway signing its transactions.
+#[derive(Debug, Clone)]
+pub(super) struct OperatorAccount {
+    pub address: Address,
+    pub ethereum: EthereumGateway,
+}
+
+#[derive(Debug)]
+pub(super) struct Operators {
+    /// Address of the main operator account, its transactions are signed by the main gateway
+    /// of `ETHSender`.
+    main: Address,
+    /// Reserve operator accounts in the order of rotation.
+    reserve: Vec<OperatorAccount>,
+    /// Index of the active reserve account, `None` if the main account is active.
+    active: Option<usize>,
+    /// Amount of the replacements of the ongoing operations sent by the active account.
+    replacements: HashMap<EthOpId, u64>,
+}
+
+impl Operators {
+    pub fn new(main: Address, reserve: Vec<OperatorAccount>) -> Self {
+        Self {
+            main,
+            reserve,
+            active: None,
+            replacements: HashMap::new(),
+        }
+    }
+
+    /// Returns `true` if there are reserve accounts to rotate the active account to.
+    pub fn is_rotation_enabled(&self) -> bool {
+        !self.reserve.is_empty()
+    }
+
+    pub fn main_address(&self) -> Address {
+        self.main
+    }
+
+    pub fn reserve_accounts(&self) -> &[OperatorAccount] {
+        &self.reserve
+    }
+
+    pub fn active_address(&self) -> Address {
+        match self.active {
+            Some(idx) => self.reserve[idx].address,
+            None => self.main,
+        }
+    }
+
+    /// Returns `true` if the operator account is configured.
+    pub fn contains(&self, address: Address) -> bool {
+        address == self.main
+            || self
+                .reserve
+                .iter()
+                .any(|account| account.address == address)
+    }
+
+    /// Returns `true` if the transactions of the operation are sent by the active account.
+    /// The operations without the operator account are sent by the main one.
+    pub fn is_active(&self, operator: Option<Address>) -> bool {
+        operator.unwrap_or(self.main) == self.active_address()
+    }
+
+    /// Makes the operator account active, the replacements are counted from scratch.
+    pub fn set_active(&mut self, address: Address) -> anyhow::Result<()> {
+        self.active = if address == self.main {
+            None
+        } else {
+            let idx = self
+                .reserve
+                .iter()
+                .position(|account| account.address == address)
+                .ok_or_else(|| format_err!("Unknown operator account {:#x}", address))?;
+            Some(idx)
+        };
+        self.replacements.clear();
+        Ok(())
+    }
+
+    /// Returns the gateway signing the transactions of the operator account.
+    pub fn gateway<'a>(
+        &'a self,
+        main: &'a EthereumGateway,
+        operator: Option<Address>,
+    ) -> anyhow::Result<&'a EthereumGateway> {
+        let address = operator.unwrap_or(self.main);
+        if address == self.main {
+            return Ok(main);
+        }
+        self.reserve
+            .iter()
+            .find(|account| account.address == address)
+            .map(|account| &account.ethereum)
+            .ok_or_else(|| format_err!("Unknown operator account {:#x}", address))
+    }
+
+    /// Returns the accounts to rotate the active account to, in the order of rotation.
+    pub fn next_accounts<'a>(
+        &'a self,
+        main: &'a EthereumGateway,
+    ) -> Vec<(Address, &'a EthereumGateway)> {
+        let mut accounts: Vec<_> = Some((self.main, main))
+            .into_iter()
+            .chain(
+                self.reserve
+                    .iter()
+                    .map(|account| (account.address, &account.ethereum)),
+            )
+            .collect();
+        let active = self.active.map_or(0, |idx| idx + 1);
+        accounts.rotate_left(active + 1);
+        accounts.pop();
+        accounts
+    }
+
+    /// Reports the replacement of the stuck transaction sent by the active account.
+    pub fn report_replacement(&mut self, eth_op_id: EthOpId) {
+        *self.replacements.entry(eth_op_id).or_default() += 1;
+    }
+
+    /// Stops counting the replacements of the completed operation.
+    pub fn report_commitment(&mut self, eth_op_id: EthOpId) {
+        self.replacements.remove(&eth_op_id);
+    }
+
+    /// Returns the maximum amount of the replacements of the ongoing operation sent by the active account.
+    pub fn max_replacements(&self) -> u64 {
+        self.replacements
+            .values()
+            .copied()
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_eth_client::clients::mock::MockEthereum;
+
+    #[test]
+    fn rotation_order() {
+        let main = EthereumGateway::Mock(MockEthereum::default());
+        let reserve: Vec<_> = (2..4)
+            .map(|byte| OperatorAccount {
+                address: Address::repeat_byte(byte),
+                ethereum: EthereumGateway::Mock(MockEthereum::default()),
+            })
+            .collect();
+        let mut operators = Operators::new(Address::repeat_byte(1), reserve);
+        let next_addresses = |operators: &Operators| -> Vec<Address> {
+            operators
+                .next_accounts(&main)
+                .into_iter()
+                .map(|(address, _)| address)
+                .collect()
+        };
+
+        assert!(operators.is_active(None));
+        assert_eq!(
+            next_addresses(&operators),
+            vec![Address::repeat_byte(2), Address::repeat_byte(3)]
+        );
+
+        operators.report_replacement(1);
+        operators.report_replacement(1);
+        assert_eq!(operators.max_replacements(), 2);
+
+        operators.set_active(Address::repeat_byte(3)).unwrap();
+        assert_eq!(operators.max_replacements(), 0);
+        assert!(!operators.is_active(None));
+        assert!(operators.is_active(Some(Address::repeat_byte(3))));
+        assert_eq!(
+            next_addresses(&operators),
+            vec![Address::repeat_byte(1), Address::repeat_byte(2)]
+        );
+        assert!(operators.set_active(Address::repeat_byte(4)).is_err());
+        assert!(operators
+            .gateway(&main, Some(Address::repeat_byte(4)))
+            .is_err());
+    }
+}
@@ -0,0 +1,179 @@
+//! Resubmission policy of the stuck Ethereum transactions of `ETHSender`.
+//!
+//! Every replacement of the stuck transaction increases the gas price by the next step of the
+//! configured escalation curve. The automatic replacements stop once the operation has too many
+//! transactions sent, or once the deadline since its first transaction has passed: at this point
+//! the gas price is most likely not the reason for the transaction to be stuck, so the operation
+//! waits for the operator, who can request the replacement manually via the admin API.
+
+// Built-in deps
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+// Workspace uses
+use zksync_config::configs::eth_sender::Resubmission;
+use zksync_types::ethereum::{ETHOperation, EthOpId};
+
+/// Reason to stop the automatic replacements of the stuck transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum HoldReason {
+    /// The maximum amount of the transactions is sent for the operation.
+    AttemptsExhausted,
+    /// The deadline since the first transaction of the operation has passed.
+    DeadlinePassed,
+}
+
+impl fmt::Display for HoldReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AttemptsExhausted => write!(f, "the maximum amount of attempts is reached"),
+            Self::DeadlinePassed => write!(f, "the resubmission deadline has passed"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ResubmissionDecision {
+    /// The stuck transaction should be replaced.
+    Resubmit,
+    /// The stuck transaction should be kept until the replacement is requested manually.
+    Hold(HoldReason),
+}
+
+#[derive(Debug)]
+pub(super) struct ResubmissionPolicy {
+    config: Resubmission,
+    /// Ethereum blocks at which the first transactions of the ongoing operations were sent.
+    first_attempt_blocks: HashMap<EthOpId, u64>,
+    /// Ongoing operations whose transactions aren't replaced automatically anymore.
+    held: HashSet<EthOpId>,
+}
+
+impl ResubmissionPolicy {
+    pub fn new(config: Resubmission) -> Self {
+        Self {
+            config,
+            first_attempt_blocks: HashMap::new(),
+            held: HashSet::new(),
+        }
+    }
+
+    /// Restores the blocks of the first transactions of the ongoing operations loaded from the database.
+    pub fn restore(&mut self, first_attempt_blocks: Vec<(EthOpId, u64)>) {
+        self.first_attempt_blocks.extend(first_attempt_blocks);
+    }
+
+    /// Reports the first transaction of the operation.
+    pub fn report_first_attempt(&mut self, eth_op_id: EthOpId, block: u64) {
+        self.first_attempt_blocks.entry(eth_op_id).or_insert(block);
+    }
+
+    /// Returns the gas price increase in percents for the next replacement of the operation transaction.
+    pub fn fee_increase_percent(&self, op: &ETHOperation) -> u64 {
+        let replacements = op.used_tx_hashes.len().saturating_sub(1);
+        self.config.fee_increase_percent(replacements)
+    }
+
+    /// Decides whether the stuck transaction of the operation should be replaced automatically.
+    /// The deadline of the operations sent before the first attempts were tracked is counted
+    /// from the first check.
+    pub fn decide(&mut self, op: &ETHOperation, current_block: u64) -> ResubmissionDecision {
+        let first_attempt_block = *self
+            .first_attempt_blocks
+            .entry(op.id)
+            .or_insert(current_block);
+
+        if op.used_tx_hashes.len() as u64 >= self.config.max_attempts {
+            ResubmissionDecision::Hold(HoldReason::AttemptsExhausted)
+        } else if self.config.deadline_blocks > 0
+            && current_block >= first_attempt_block + self.config.deadline_blocks
+        {
+            ResubmissionDecision::Hold(HoldReason::DeadlinePassed)
+        } else {
+            ResubmissionDecision::Resubmit
+        }
+    }
+
+    /// Reports that the replacements of the operation transaction are stopped.
+    /// Returns `false` if they were stopped already, so the incident is reported once.
+    pub fn report_hold(&mut self, eth_op_id: EthOpId) -> bool {
+        self.held.insert(eth_op_id)
+    }
+
+    /// Stops tracking the completed operation.
+    pub fn report_commitment(&mut self, eth_op_id: EthOpId) {
+        self.first_attempt_blocks.remove(&eth_op_id);
+        self.held.remove(&eth_op_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::{aggregated_operations::AggregatedActionType, H256};
+
+    fn operation(id: EthOpId, txs_count: u64) -> ETHOperation {
+        ETHOperation {
+            id,
+            op_type: AggregatedActionType::CommitBlocks,
+            op: None,
+            nonce: 0.into(),
+            operator: None,
+            last_deadline_block: 0,
+            last_used_gas_price: 0.into(),
+            last_used_priority_fee: None,
+            used_tx_hashes: (0..txs_count).map(H256::from_low_u64_be).collect(),
+            encoded_tx_data: Vec::new(),
+            confirmed: false,
+            final_hash: None,
+        }
+    }
+
+    #[test]
+    fn resubmission_decisions() {
+        let mut policy = ResubmissionPolicy::new(Resubmission {
+            max_attempts: 3,
+            fee_increase_percents: vec![15, 30],
+            deadline_blocks: 100,
+        });
+        policy.restore(vec![(1, 10)]);
+
+        // The escalation curve is followed, and its last step is repeated.
+        assert_eq!(policy.fee_increase_percent(&operation(1, 1)), 15);
+        assert_eq!(policy.fee_increase_percent(&operation(1, 2)), 30);
+        assert_eq!(policy.fee_increase_percent(&operation(1, 5)), 30);
+
+        assert_eq!(
+            policy.decide(&operation(1, 2), 50),
+            ResubmissionDecision::Resubmit
+        );
+        assert_eq!(
+            policy.decide(&operation(1, 3), 50),
+            ResubmissionDecision::Hold(HoldReason::AttemptsExhausted)
+        );
+        assert_eq!(
+            policy.decide(&operation(1, 2), 110),
+            ResubmissionDecision::Hold(HoldReason::DeadlinePassed)
+        );
+        assert!(policy.report_hold(1));
+        assert!(!policy.report_hold(1));
+
+        // The deadline of the operation without tracked attempts is counted from the first check.
+        assert_eq!(
+            policy.decide(&operation(2, 1), 110),
+            ResubmissionDecision::Resubmit
+        );
+        assert_eq!(
+            policy.decide(&operation(2, 1), 210),
+            ResubmissionDecision::Hold(HoldReason::DeadlinePassed)
+        );
+
+        // The completed operation is forgotten.
+        policy.report_commitment(1);
+        policy.report_first_attempt(1, 200);
+        assert_eq!(
+            policy.decide(&operation(1, 1), 210),
+            ResubmissionDecision::Resubmit
+        );
+        assert!(policy.report_hold(1));
+    }
+}
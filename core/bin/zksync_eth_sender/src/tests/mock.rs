@@ -6,16 +6,19 @@ use std::convert::TryFrom;
 // External uses
 use tokio::sync::RwLock;
 use web3::contract::Options;
-use zksync_basic_types::{BlockNumber, H256, U256};
+use zksync_basic_types::{Address, BlockNumber, H256, U256};
 // Workspace uses
-use zksync_config::configs::eth_sender::{ETHSenderConfig, GasLimit, Sender};
+use zksync_config::configs::eth_sender::{
+    ETHSenderConfig, GasLimit, GasOracle, Resubmission, Sender,
+};
 use zksync_eth_client::EthereumGateway;
 use zksync_storage::{ethereum::records::ETHParams, StorageProcessor};
 use zksync_types::aggregated_operations::{AggregatedActionType, AggregatedOperation};
-use zksync_types::ethereum::{ETHOperation, EthOpId, InsertedOperationResponse};
+use zksync_types::ethereum::{ETHOperation, ETHTxAttempt, EthOpId, InsertedOperationResponse};
 // Local uses
 use super::ETHSender;
 use crate::database::DatabaseInterface;
+use crate::operators::OperatorAccount;
 use crate::transactions::ETHStats;
 use zksync_eth_client::clients::mock::MockEthereum;
 
@@ -26,6 +29,18 @@ pub(crate) struct MockDatabase {
     aggregated_operations: RwLock<Vec<(i64, AggregatedOperation)>>,
     unprocessed_operations: RwLock<Vec<(i64, AggregatedOperation)>>,
     eth_parameters: RwLock<ETHParams>,
+    operator_accounts: RwLock<Vec<MockOperatorAccount>>,
+    tx_attempts: RwLock<Vec<ETHTxAttempt>>,
+    tx_bump_requests: RwLock<Vec<EthOpId>>,
+}
+
+/// Operator account stored in the mock database.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct MockOperatorAccount {
+    pub address: Address,
+    pub nonce: U256,
+    pub balance: Option<U256>,
+    pub is_active: bool,
 }
 
 impl MockDatabase {
@@ -41,9 +56,33 @@ impl MockDatabase {
             aggregated_operations: RwLock::new(aggregated_operations),
             unprocessed_operations: RwLock::new(unprocessed_operations),
             eth_parameters: RwLock::new(eth_parameters),
+            operator_accounts: RwLock::new(Vec::new()),
+            tx_attempts: RwLock::new(Vec::new()),
+            tx_bump_requests: RwLock::new(Vec::new()),
         }
     }
 
+    /// Returns the stored attempts history of the Ethereum operation.
+    pub async fn tx_attempts(&self, eth_op_id: EthOpId) -> Vec<ETHTxAttempt> {
+        self.tx_attempts
+            .read()
+            .await
+            .iter()
+            .filter(|attempt| attempt.eth_op_id == eth_op_id)
+            .cloned()
+            .collect()
+    }
+
+    /// Simulates the admin API, requests the replacement of the operation transaction.
+    pub async fn request_tx_bump(&self, eth_op_id: EthOpId) {
+        self.tx_bump_requests.write().await.push(eth_op_id);
+    }
+
+    /// Returns the stored operator accounts.
+    pub async fn operator_accounts(&self) -> Vec<MockOperatorAccount> {
+        self.operator_accounts.read().await.clone()
+    }
+
     pub async fn update_gas_price_limit(&self, value: i64) -> anyhow::Result<()> {
         let mut eth_parameters = self.eth_parameters.write().await;
         eth_parameters.gas_price_limit = value;
@@ -204,20 +243,33 @@ impl DatabaseInterface for MockDatabase {
         op: Option<(i64, AggregatedOperation)>,
         deadline_block: i64,
         used_gas_price: U256,
+        used_priority_fee: Option<U256>,
         encoded_tx_data: Vec<u8>,
     ) -> anyhow::Result<InsertedOperationResponse> {
         let mut eth_operations = self.eth_operations.write().await;
         let id = eth_operations.len() as i64;
-        let nonce = eth_operations.len();
+        let mut operator_accounts = self.operator_accounts.write().await;
+        let (operator, nonce) = match operator_accounts
+            .iter_mut()
+            .find(|account| account.is_active)
+        {
+            Some(account) => {
+                account.nonce += U256::one();
+                (Some(account.address), account.nonce - 1)
+            }
+            None => (None, eth_operations.len().into()),
+        };
 
         // Store with the assigned ID.
         let eth_operation = ETHOperation {
             id,
             op_type,
             op,
-            nonce: nonce.into(),
+            nonce,
+            operator,
             last_deadline_block: deadline_block as u64,
             last_used_gas_price: used_gas_price,
+            last_used_priority_fee: used_priority_fee,
             used_tx_hashes: vec![],
             encoded_tx_data,
             confirmed: false,
@@ -228,7 +280,8 @@ impl DatabaseInterface for MockDatabase {
 
         let response = InsertedOperationResponse {
             id,
-            nonce: nonce.into(),
+            nonce,
+            operator,
         };
 
         Ok(response)
@@ -261,6 +314,7 @@ impl DatabaseInterface for MockDatabase {
         eth_op_id: EthOpId,
         new_deadline_block: i64,
         new_gas_value: U256,
+        new_priority_fee: Option<U256>,
     ) -> anyhow::Result<()> {
         let mut eth_operations = self.eth_operations.write().await;
         let eth_op = eth_operations
@@ -270,6 +324,7 @@ impl DatabaseInterface for MockDatabase {
         if let Some(eth_op) = eth_op {
             eth_op.last_deadline_block = new_deadline_block as u64;
             eth_op.last_used_gas_price = new_gas_value;
+            eth_op.last_used_priority_fee = new_priority_fee;
         } else {
             panic!("Attempt to update tx that is not unconfirmed");
         }
@@ -282,6 +337,8 @@ impl DatabaseInterface for MockDatabase {
         _connection: &mut StorageProcessor<'_>,
         hash: &H256,
         _op: &ETHOperation,
+        _eth_block: u64,
+        _gas_used: Option<U256>,
     ) -> anyhow::Result<()> {
         let mut eth_operations = self.eth_operations.write().await;
         let mut op_idx: Option<i64> = None;
@@ -355,6 +412,152 @@ impl DatabaseInterface for MockDatabase {
 
         Ok(confirmed)
     }
+
+    async fn register_operator_accounts(
+        &self,
+        _connection: &mut StorageProcessor<'_>,
+        main_account: Address,
+        reserve_accounts: Vec<(Address, U256)>,
+    ) -> anyhow::Result<()> {
+        let mut operator_accounts = self.operator_accounts.write().await;
+        let has_active = operator_accounts.iter().any(|account| account.is_active);
+        let main_nonce = self.eth_parameters.read().await.nonce.into();
+        let accounts = Some((main_account, main_nonce, !has_active))
+            .into_iter()
+            .chain(
+                reserve_accounts
+                    .into_iter()
+                    .map(|(address, nonce)| (address, nonce, false)),
+            );
+        for (address, nonce, is_active) in accounts {
+            if !operator_accounts
+                .iter()
+                .any(|account| account.address == address)
+            {
+                operator_accounts.push(MockOperatorAccount {
+                    address,
+                    nonce,
+                    balance: None,
+                    is_active,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn load_active_operator(
+        &self,
+        _connection: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<Option<Address>> {
+        let operator_accounts = self.operator_accounts.read().await;
+        let active = operator_accounts
+            .iter()
+            .find(|account| account.is_active)
+            .map(|account| account.address);
+
+        Ok(active)
+    }
+
+    async fn update_operator_balance(
+        &self,
+        _connection: &mut StorageProcessor<'_>,
+        address: Address,
+        balance: U256,
+    ) -> anyhow::Result<()> {
+        let mut operator_accounts = self.operator_accounts.write().await;
+        for account in operator_accounts.iter_mut() {
+            if account.address == address {
+                account.balance = Some(balance);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn activate_operator_account(
+        &self,
+        _connection: &mut StorageProcessor<'_>,
+        address: Address,
+        nonce: U256,
+    ) -> anyhow::Result<()> {
+        let mut operator_accounts = self.operator_accounts.write().await;
+        assert!(
+            operator_accounts
+                .iter()
+                .any(|account| account.address == address),
+            "Attempt to activate the operator account that is not registered"
+        );
+        for account in operator_accounts.iter_mut() {
+            account.is_active = account.address == address;
+            if account.is_active {
+                account.nonce = nonce;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn reassign_eth_tx_operator(
+        &self,
+        _connection: &mut StorageProcessor<'_>,
+        eth_op_id: EthOpId,
+    ) -> anyhow::Result<(Address, U256)> {
+        let mut operator_accounts = self.operator_accounts.write().await;
+        let account = operator_accounts
+            .iter_mut()
+            .find(|account| account.is_active)
+            .expect("There is no active operator account");
+        let (operator, nonce) = (account.address, account.nonce);
+        account.nonce += U256::one();
+
+        let mut eth_operations = self.eth_operations.write().await;
+        let eth_op = eth_operations
+            .iter_mut()
+            .find(|eth_op| eth_op.id == eth_op_id && !eth_op.confirmed)
+            .expect("Attempt to update tx that is not unconfirmed");
+        eth_op.operator = Some(operator);
+        eth_op.nonce = nonce;
+
+        Ok((operator, nonce))
+    }
+
+    async fn add_tx_attempt(
+        &self,
+        _connection: &mut StorageProcessor<'_>,
+        attempt: &ETHTxAttempt,
+    ) -> anyhow::Result<()> {
+        self.tx_attempts.write().await.push(attempt.clone());
+        Ok(())
+    }
+
+    async fn load_first_attempt_blocks(
+        &self,
+        _connection: &mut StorageProcessor<'_>,
+        eth_op_ids: &[EthOpId],
+    ) -> anyhow::Result<Vec<(EthOpId, u64)>> {
+        let tx_attempts = self.tx_attempts.read().await;
+        let blocks = eth_op_ids
+            .iter()
+            .filter_map(|eth_op_id| {
+                tx_attempts
+                    .iter()
+                    .filter(|attempt| attempt.eth_op_id == *eth_op_id)
+                    .map(|attempt| attempt.sent_at_block)
+                    .min()
+                    .map(|block| (*eth_op_id, block))
+            })
+            .collect();
+        Ok(blocks)
+    }
+
+    async fn take_tx_bump_requests(
+        &self,
+        _connection: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<Vec<EthOpId>> {
+        let eth_op_ids = std::mem::take(&mut *self.tx_bump_requests.write().await);
+        Ok(eth_op_ids)
+    }
 }
 
 /// Creates a default `ETHParams` for use by mock `ETHSender` .
@@ -379,6 +582,31 @@ pub(crate) async fn default_eth_sender() -> ETHSender<MockDatabase> {
         Vec::new(),
         Vec::new(),
         default_eth_parameters(),
+        Vec::new(),
+    )
+    .await
+}
+
+/// Creates an `ETHSender` with mock Ethereum connection/database and no operations in DB
+/// which sends the transactions by the reserve operator accounts as well.
+pub(crate) async fn eth_sender_with_reserve_operators(
+    reserve_operators: Vec<(Address, MockEthereum)>,
+) -> ETHSender<MockDatabase> {
+    let reserve_operators = reserve_operators
+        .into_iter()
+        .map(|(address, ethereum)| OperatorAccount {
+            address,
+            ethereum: EthereumGateway::Mock(ethereum),
+        })
+        .collect();
+
+    build_eth_sender(
+        1,
+        Vec::new(),
+        Vec::new(),
+        Vec::new(),
+        default_eth_parameters(),
+        reserve_operators,
     )
     .await
 }
@@ -393,6 +621,7 @@ pub(crate) async fn concurrent_eth_sender(max_txs_in_flight: u64) -> ETHSender<M
         Vec::new(),
         Vec::new(),
         default_eth_parameters(),
+        Vec::new(),
     )
     .await
 }
@@ -413,6 +642,7 @@ pub(crate) async fn restored_eth_sender(
         aggregated_operations,
         unprocessed_operations,
         eth_parameters,
+        Vec::new(),
     )
     .await
 }
@@ -424,6 +654,7 @@ async fn build_eth_sender(
     aggregated_operations: Vec<(i64, AggregatedOperation)>,
     unprocessed_operations: Vec<(i64, AggregatedOperation)>,
     eth_parameters: ETHParams,
+    reserve_operators: Vec<OperatorAccount>,
 ) -> ETHSender<MockDatabase> {
     let ethereum = EthereumGateway::Mock(MockEthereum::default());
     let db = MockDatabase::with_restorable_state(
@@ -440,18 +671,38 @@ async fn build_eth_sender(
             wait_confirmations: super::WAIT_CONFIRMATIONS,
             tx_poll_period: 0,
             is_enabled: true,
+            is_eip1559_enabled: false,
             operator_commit_eth_addr: Default::default(),
             operator_private_key: Default::default(),
+            remote_signer_url: None,
+            reserve_operator_addresses: Vec::new(),
+            reserve_operator_private_keys: Vec::new(),
+            min_operator_balance: 1000,
+            max_replacements_per_operator: 2,
         },
         gas_price_limit: GasLimit {
             default: 1000,
             sample_interval: 15,
             update_interval: 15,
             scale_factor: 1.0f64,
+            priority_fee_per_gas: 10,
+        },
+        gas_price_oracle: GasOracle {
+            sources: vec!["node".into()],
+            fee_history_blocks: 10,
+            external_api_url: None,
+            external_api_json_pointer: String::new(),
+            min_gas_price: 0,
+            max_gas_price: u64::MAX,
+        },
+        resubmission: Resubmission {
+            max_attempts: 10,
+            fee_increase_percents: vec![15],
+            deadline_blocks: 0,
         },
     };
 
-    ETHSender::new(options, db, ethereum).await
+    ETHSender::new(options, db, ethereum, reserve_operators).await
 }
 
 /// Behaves the same as `ETHSender::sign_new_tx`, but does not affect nonce.
@@ -483,8 +734,10 @@ pub(crate) async fn create_signed_tx(
         op_type,
         op: Some(aggregated_operation.clone()),
         nonce: signed_tx.nonce,
+        operator: None,
         last_deadline_block: deadline_block,
         last_used_gas_price: signed_tx.gas_price,
+        last_used_priority_fee: None,
         used_tx_hashes: vec![signed_tx.hash],
         encoded_tx_data: raw_tx,
         confirmed: false,
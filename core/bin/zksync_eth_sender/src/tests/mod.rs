@@ -1,11 +1,16 @@
 // Local uses
 use self::mock::{
     concurrent_eth_sender, create_signed_tx, default_eth_parameters, default_eth_sender,
-    restored_eth_sender,
+    eth_sender_with_reserve_operators, restored_eth_sender, MockDatabase,
 };
-use super::{transactions::TxCheckOutcome, ETHSender, TxCheckMode};
-use web3::types::U64;
+use super::{
+    resubmission::ResubmissionPolicy, transactions::TxCheckOutcome, ETHSender, TxCheckMode,
+};
+use web3::types::{Address, U256, U64};
+use zksync_config::configs::eth_sender::Resubmission;
+use zksync_eth_client::clients::mock::MockEthereum;
 use zksync_eth_client::ethereum_gateway::ExecutedTxStatus;
+use zksync_types::ethereum::ETHTxAttemptReason;
 
 const EXPECTED_WAIT_TIME_BLOCKS: u64 = 30;
 const WAIT_CONFIRMATIONS: u64 = 3;
@@ -78,6 +83,7 @@ async fn transaction_state() {
         confirmations: WAIT_CONFIRMATIONS,
         success: true,
         receipt: None,
+        gas_used: None,
     };
     eth_sender
         .ethereum
@@ -91,6 +97,7 @@ async fn transaction_state() {
         confirmations: WAIT_CONFIRMATIONS - 1,
         success: true,
         receipt: None,
+        gas_used: None,
     };
     eth_sender
         .ethereum
@@ -104,6 +111,7 @@ async fn transaction_state() {
         confirmations: WAIT_CONFIRMATIONS,
         success: false,
         receipt: Some(Default::default()),
+        gas_used: None,
     };
     eth_sender
         .ethereum
@@ -117,6 +125,7 @@ async fn transaction_state() {
         confirmations: WAIT_CONFIRMATIONS - 1,
         success: false,
         receipt: Some(Default::default()),
+        gas_used: None,
     };
     eth_sender
         .ethereum
@@ -139,7 +148,10 @@ async fn transaction_state() {
             )
             .await
             .unwrap(),
-        TxCheckOutcome::Committed
+        TxCheckOutcome::Committed {
+            eth_block: current_block,
+            gas_used: None,
+        }
     );
 
     // Pending operation (no enough confirmations).
@@ -410,6 +422,137 @@ async fn stuck_transaction() {
     eth_sender.db.assert_confirmed(&stuck_tx).await;
 }
 
+/// Checks that with EIP-1559 enabled the transactions are sent with the max fee and the
+/// priority fee, and both of them are increased for the replacement of the stuck transaction.
+#[tokio::test]
+async fn stuck_eip1559_transaction() {
+    let mut eth_sender = default_eth_sender().await;
+    eth_sender.options.sender.is_eip1559_enabled = true;
+
+    let aggregated_operation = test_data::commit_blocks_operation(0);
+    eth_sender
+        .db
+        .send_aggregated_operation(aggregated_operation)
+        .await
+        .unwrap();
+    eth_sender.load_new_operations().await.unwrap();
+    eth_sender.proceed_next_operations(0).await;
+
+    // The max fee is the doubled base fee (50) plus the priority fee (10).
+    let sent_op = eth_sender.ongoing_ops[0].clone();
+    assert_eq!(sent_op.last_used_gas_price, 110.into());
+    assert_eq!(sent_op.last_used_priority_fee, Some(10.into()));
+    eth_sender.db.assert_stored(&sent_op).await;
+
+    let block_number = U64::from(
+        eth_sender
+            .ethereum
+            .get_mock()
+            .unwrap()
+            .block_number()
+            .await
+            .unwrap()
+            .as_u64()
+            + EXPECTED_WAIT_TIME_BLOCKS,
+    );
+    eth_sender
+        .ethereum
+        .get_mut_mock()
+        .unwrap()
+        .set_block_number(block_number)
+        .await
+        .unwrap();
+    eth_sender.proceed_next_operations(0).await;
+
+    // Both fees of the replacement are increased by 15%.
+    let replaced_op = eth_sender.ongoing_ops[0].clone();
+    assert_eq!(replaced_op.used_tx_hashes.len(), 2);
+    assert_eq!(replaced_op.last_used_gas_price, 126.into());
+    assert_eq!(replaced_op.last_used_priority_fee, Some(11.into()));
+    eth_sender.db.assert_stored(&replaced_op).await;
+    eth_sender
+        .ethereum
+        .get_mock()
+        .unwrap()
+        .assert_sent(replaced_op.used_tx_hashes[1].as_bytes())
+        .await;
+}
+
+/// Checks that the stuck transaction isn't replaced once the maximum amount of attempts is reached,
+/// unless the replacement is requested manually, and that every attempt is stored.
+#[tokio::test]
+async fn resubmission_policy() {
+    let mut eth_sender = default_eth_sender().await;
+    eth_sender.resubmission = ResubmissionPolicy::new(Resubmission {
+        max_attempts: 2,
+        fee_increase_percents: vec![15, 30],
+        deadline_blocks: 0,
+    });
+
+    let aggregated_operation = test_data::commit_blocks_operation(0);
+    eth_sender
+        .db
+        .send_aggregated_operation(aggregated_operation)
+        .await
+        .unwrap();
+    eth_sender.load_new_operations().await.unwrap();
+    eth_sender.proceed_next_operations(0).await;
+    let eth_op_id = eth_sender.ongoing_ops[0].id;
+
+    // Skips the blocks to make the latest transaction stuck.
+    async fn skip_blocks(eth_sender: &mut ETHSender<MockDatabase>) {
+        let mock = eth_sender.ethereum.get_mut_mock().unwrap();
+        let block_number =
+            U64::from(mock.block_number().await.unwrap().as_u64() + EXPECTED_WAIT_TIME_BLOCKS);
+        mock.set_block_number(block_number).await.unwrap();
+        eth_sender.proceed_next_operations(0).await;
+    }
+
+    // The first replacement follows the first step of the escalation curve.
+    let initial_price = eth_sender.ongoing_ops[0].last_used_gas_price;
+    skip_blocks(&mut eth_sender).await;
+    let replaced_op = eth_sender.ongoing_ops[0].clone();
+    assert_eq!(replaced_op.used_tx_hashes.len(), 2);
+    assert_eq!(
+        replaced_op.last_used_gas_price,
+        initial_price * U256::from(115) / U256::from(100)
+    );
+
+    // The maximum amount of attempts is reached, so the transaction is kept.
+    skip_blocks(&mut eth_sender).await;
+    assert_eq!(eth_sender.ongoing_ops[0].used_tx_hashes.len(), 2);
+
+    // The manual replacement follows the next step of the escalation curve.
+    eth_sender.db.request_tx_bump(eth_op_id).await;
+    eth_sender.proceed_next_operations(0).await;
+    let bumped_op = eth_sender.ongoing_ops[0].clone();
+    assert_eq!(bumped_op.used_tx_hashes.len(), 3);
+    assert_eq!(
+        bumped_op.last_used_gas_price,
+        replaced_op.last_used_gas_price * U256::from(130) / U256::from(100)
+    );
+    eth_sender.db.assert_stored(&bumped_op).await;
+    eth_sender
+        .ethereum
+        .get_mock()
+        .unwrap()
+        .assert_sent(bumped_op.used_tx_hashes[2].as_bytes())
+        .await;
+
+    let attempts = eth_sender.db.tx_attempts(eth_op_id).await;
+    let reasons: Vec<_> = attempts.iter().map(|attempt| attempt.reason).collect();
+    assert_eq!(
+        reasons,
+        vec![
+            ETHTxAttemptReason::Initial,
+            ETHTxAttemptReason::Stuck,
+            ETHTxAttemptReason::ManualBump
+        ]
+    );
+    let hashes: Vec<_> = attempts.iter().map(|attempt| attempt.tx_hash).collect();
+    assert_eq!(hashes, bumped_op.used_tx_hashes);
+}
+
 /// This test verifies that with multiple operations received all-together,
 /// their order is respected and no processing of the next operation is started until
 /// the previous one is committed.
@@ -591,6 +734,80 @@ async fn transaction_failure() {
     eth_sender.proceed_next_operations(0).await;
 }
 
+/// Checks that the operator account is rotated once it runs low on funds, and the ongoing
+/// operation is resent by the new account and committed by whichever transaction is mined.
+#[tokio::test]
+async fn operator_rotation() {
+    let reserve_operator = Address::repeat_byte(2);
+    let mut eth_sender =
+        eth_sender_with_reserve_operators(vec![(reserve_operator, MockEthereum::default())]).await;
+    let main_operator = eth_sender.options.sender.operator_commit_eth_addr;
+
+    // The main account is active until the rotation.
+    let accounts = eth_sender.db.operator_accounts().await;
+    assert_eq!(accounts.len(), 2);
+    assert_eq!(accounts[0].address, main_operator);
+    assert!(accounts[0].is_active);
+    assert!(!accounts[1].is_active);
+
+    let aggregated_operation = test_data::commit_blocks_operation(0);
+    eth_sender
+        .db
+        .send_aggregated_operation(aggregated_operation)
+        .await
+        .unwrap();
+    eth_sender.load_new_operations().await.unwrap();
+    eth_sender.proceed_next_operations(0).await;
+
+    let sent_op = eth_sender.ongoing_ops[0].clone();
+    assert_eq!(sent_op.operator, Some(main_operator));
+    assert_eq!(sent_op.nonce, 0.into());
+
+    // The balance of the main account gets lower than the minimum, so the operation is
+    // moved to the reserve account.
+    eth_sender
+        .ethereum
+        .get_mut_mock()
+        .unwrap()
+        .set_balance(10.into())
+        .await
+        .unwrap();
+    eth_sender.proceed_next_operations(0).await;
+
+    let moved_op = eth_sender.ongoing_ops[0].clone();
+    assert_eq!(moved_op.operator, Some(reserve_operator));
+    assert_eq!(moved_op.nonce, 0.into());
+    assert_eq!(moved_op.used_tx_hashes.len(), 2);
+    eth_sender.db.assert_stored(&moved_op).await;
+    eth_sender
+        .ethereum
+        .get_mock()
+        .unwrap()
+        .assert_sent(moved_op.used_tx_hashes[1].as_bytes())
+        .await;
+
+    let accounts = eth_sender.db.operator_accounts().await;
+    assert_eq!(accounts[0].balance, Some(10.into()));
+    assert!(!accounts[0].is_active);
+    assert!(accounts[1].is_active);
+    assert_eq!(accounts[1].nonce, 1.into());
+
+    // The transaction of the main account fails after the one of the reserve account is mined,
+    // which doesn't prevent the operation from being committed.
+    let mock = eth_sender.ethereum.get_mut_mock().unwrap();
+    mock.add_failed_execution(&moved_op.used_tx_hashes[0], WAIT_CONFIRMATIONS)
+        .await;
+    mock.add_successfull_execution(moved_op.used_tx_hashes[1], WAIT_CONFIRMATIONS)
+        .await;
+    eth_sender.proceed_next_operations(0).await;
+
+    assert!(eth_sender.ongoing_ops.is_empty());
+    let mut committed_op = moved_op.clone();
+    committed_op.confirmed = true;
+    committed_op.final_hash = Some(moved_op.used_tx_hashes[1]);
+    eth_sender.db.assert_confirmed(&committed_op).await;
+}
+
 /// Check that after recovering state with several non-processed operations
 /// they will be processed normally.
 #[tokio::test]
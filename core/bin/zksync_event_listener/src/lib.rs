@@ -1,6 +1,7 @@
 //! The `zksync_event_listener` is a stand-alone server-application responsible for
-//! fetching new events that happen in the zkSync network from the database
-//! and streaming them to the connected WebSocket clients.
+//! fetching new events that happen in the zkSync network from the database,
+//! streaming them to the connected WebSocket clients, delivering them to the webhooks
+//! and publishing them to Kafka or NATS.
 
 // Built-in uses
 // Workspace uses
@@ -13,12 +14,16 @@ use actix_web_actors::ws;
 use listener::EventListener;
 use messages::RegisterServerHandle;
 use monitor::ServerMonitor;
+use streaming::EventPublisher;
 use subscriber::Subscriber;
+use webhooks::WebhookDispatcher;
 
 pub mod listener;
 pub mod messages;
 pub mod monitor;
+pub mod streaming;
 pub mod subscriber;
+pub mod webhooks;
 
 #[derive(Debug)]
 struct AppState {
@@ -35,10 +40,20 @@ async fn ws_index(
 
 pub async fn run_event_server(config: ZkSyncConfig) {
     let monitor = ServerMonitor::new().start();
-    EventListener::new(monitor.clone(), &config)
+    let webhook_dispatcher = WebhookDispatcher::new(&config).unwrap().start();
+    let event_publisher = EventPublisher::new(&config)
         .await
         .unwrap()
-        .start();
+        .map(Actor::start);
+    EventListener::new(
+        monitor.clone(),
+        webhook_dispatcher,
+        event_publisher,
+        &config,
+    )
+    .await
+    .unwrap()
+    .start();
 
     let state = web::Data::new(AppState {
         server_monitor: monitor.clone(),
@@ -10,6 +10,8 @@ use zksync_types::event::{EventId, ZkSyncEvent};
 // Local uses
 use crate::messages::{NewEvents, NewStorageEvent, Shutdown};
 use crate::monitor::ServerMonitor;
+use crate::streaming::EventPublisher;
+use crate::webhooks::WebhookDispatcher;
 
 /// The main actor which is responsible for fetching new events from
 /// the database and sending them to the [`ServerMonitor`].
@@ -18,6 +20,10 @@ pub struct EventListener {
     db_pool: ConnectionPool,
     /// Address of the [`ServerMonitor`] actor for communication.
     server_monitor: Addr<ServerMonitor>,
+    /// Address of the [`WebhookDispatcher`] actor notified about the new events as well.
+    webhook_dispatcher: Addr<WebhookDispatcher>,
+    /// Address of the [`EventPublisher`] actor, if the event stream is enabled.
+    event_publisher: Option<Addr<EventPublisher>>,
     /// A storage listener that gets notified about new database events.
     /// This field gets consumed at the start of the actor.
     listener: Option<StorageListener>,
@@ -27,6 +33,9 @@ pub struct EventListener {
 
 type NotifyResult = anyhow::Result<NewStorageEvent>;
 
+/// Name the progress of the listener is stored by, so the pruner keeps the events it hasn't fetched yet.
+const EVENT_CONSUMER_NAME: &str = "event_listener";
+
 impl StreamHandler<NotifyResult> for EventListener {
     fn handle(&mut self, new_event: NotifyResult, ctx: &mut Self::Context) {
         // If we encounter an error during event processing, the actor
@@ -52,15 +61,21 @@ impl StreamHandler<NotifyResult> for EventListener {
         let last_processed_event_id = self.last_processed_event_id;
         async move {
             // Try to fetch and deserialize new events.
-            Ok(pool
-                .access_storage()
-                .await?
+            let mut storage = pool.access_storage().await?;
+            let events: Vec<_> = storage
                 .event_schema()
                 .fetch_new_events(last_processed_event_id)
                 .await?
                 .into_iter()
                 .map(ZkSyncEvent::try_from)
-                .collect::<Result<_, _>>()?)
+                .collect::<Result<_, _>>()?;
+            if let Some(event) = events.last() {
+                storage
+                    .event_schema()
+                    .store_consumer_progress(EVENT_CONSUMER_NAME, event.id)
+                    .await?;
+            }
+            Ok(events)
         }
         .into_actor(self)
         .then(|result: anyhow::Result<Vec<ZkSyncEvent>>, act, _| {
@@ -73,6 +88,10 @@ impl StreamHandler<NotifyResult> for EventListener {
                     }
                     // We don't process new notifications until we send the message.
                     let msg = NewEvents(Arc::new(events));
+                    act.webhook_dispatcher.do_send(msg.clone());
+                    if let Some(event_publisher) = &act.event_publisher {
+                        event_publisher.do_send(msg.clone());
+                    }
                     Either::Left(act.server_monitor.send(msg))
                 }
                 Err(err) => {
@@ -130,19 +149,25 @@ impl EventListener {
 
     pub async fn new(
         server_monitor: Addr<ServerMonitor>,
+        webhook_dispatcher: Addr<WebhookDispatcher>,
+        event_publisher: Option<Addr<EventPublisher>>,
         config: &ZkSyncConfig,
     ) -> anyhow::Result<EventListener> {
         let mut listener = StorageListener::connect().await?;
         let db_pool = ConnectionPool::new(Some(Self::DB_POOL_SIZE));
         // Load the offset, we don't want to broadcast events that already
         // happened.
-        let last_processed_event_id = db_pool
-            .access_storage()
-            .await?
+        let mut storage = db_pool.access_storage().await?;
+        let last_processed_event_id = storage
             .event_schema()
             .get_last_event_id()
             .await?
             .unwrap_or(EventId(0));
+        storage
+            .event_schema()
+            .store_consumer_progress(EVENT_CONSUMER_NAME, last_processed_event_id)
+            .await?;
+        drop(storage);
 
         // Configure the listener.
         let channel_name = &config.event_listener.channel_name;
@@ -151,6 +176,8 @@ impl EventListener {
         Ok(EventListener {
             db_pool,
             server_monitor,
+            webhook_dispatcher,
+            event_publisher,
             listener: Some(listener),
             last_processed_event_id,
         })
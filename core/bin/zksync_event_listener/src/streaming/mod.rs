@@ -0,0 +1,100 @@
+//! Publishing of the chain events to Kafka or NATS, so the indexers don't have to poll the API.
+//!
+//! The block committed, block finalized and transaction executed events are published
+//! in the JSON format of the [`StreamEvent`], every type of the events to its own
+//! `<prefix>.<event_type>` topic, in the order they happened. The publishing of the failed
+//! message is retried a few times before it's skipped, the consumers may skip the events
+//! published more than once by their ids. The events which happened while the event listener
+//! was down aren't published.
+
+// Built-in uses
+use std::{sync::Arc, time::Duration};
+// External uses
+use actix::prelude::*;
+// Workspace uses
+use zksync_config::ZkSyncConfig;
+use zksync_types::event::{stream::StreamEvent, ZkSyncEvent};
+// Local uses
+use self::producer::StreamProducer;
+use crate::messages::NewEvents;
+
+pub mod producer;
+
+/// The actor publishing the new events to the event stream.
+pub struct EventPublisher {
+    producer: Arc<StreamProducer>,
+    topic_prefix: String,
+}
+
+impl EventPublisher {
+    /// Attempts to publish a single message before it's skipped.
+    const PUBLISH_ATTEMPTS: u32 = 5;
+    const RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+    /// Connects to the configured broker, returns `None` if the event stream is disabled.
+    pub async fn new(config: &ZkSyncConfig) -> anyhow::Result<Option<Self>> {
+        let config = &config.event_listener;
+        Ok(StreamProducer::connect(config).await?.map(|producer| Self {
+            producer: Arc::new(producer),
+            topic_prefix: config.stream_topic_prefix.clone(),
+        }))
+    }
+
+    async fn publish_events(
+        producer: Arc<StreamProducer>,
+        topic_prefix: String,
+        events: Arc<Vec<ZkSyncEvent>>,
+    ) {
+        for event in events.iter().filter_map(StreamEvent::from_event) {
+            let topic = format!("{}.{}", topic_prefix, event.event_type.as_str());
+            let key = event.block_number.to_string();
+            let payload = serde_json::to_vec(&event).expect("stream event is serializable");
+
+            for attempt in 1..=Self::PUBLISH_ATTEMPTS {
+                match producer.publish(&topic, &key, &payload).await {
+                    Ok(()) => break,
+                    Err(err) if attempt < Self::PUBLISH_ATTEMPTS => {
+                        vlog::warn!(
+                            "Couldn't publish the event {} to {}, retrying: {}",
+                            event.event_id,
+                            topic,
+                            err
+                        );
+                        actix::clock::sleep(Self::RETRY_INTERVAL).await;
+                    }
+                    Err(err) => {
+                        vlog::error!(
+                            "Couldn't publish the event {} to {}, skipping it: {}",
+                            event.event_id,
+                            topic,
+                            err
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Actor for EventPublisher {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        ctx.set_mailbox_capacity(1 << 32);
+    }
+
+    fn stopped(&mut self, _ctx: &mut Self::Context) {
+        vlog::warn!("EventPublisher actor has stopped");
+    }
+}
+
+impl Handler<NewEvents> for EventPublisher {
+    type Result = ();
+
+    fn handle(&mut self, msg: NewEvents, ctx: &mut Self::Context) {
+        // The next events aren't handled until these ones are published to keep their order.
+        Self::publish_events(self.producer.clone(), self.topic_prefix.clone(), msg.0)
+            .into_actor(self)
+            .wait(ctx);
+    }
+}
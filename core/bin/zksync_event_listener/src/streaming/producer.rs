@@ -0,0 +1,72 @@
+// Built-in uses
+#[cfg(feature = "kafka")]
+use std::time::Duration;
+// External uses
+use anyhow::bail;
+#[cfg(feature = "kafka")]
+use rdkafka::{
+    config::ClientConfig,
+    producer::{FutureProducer, FutureRecord},
+    util::Timeout,
+};
+// Workspace uses
+use zksync_config::configs::event_listener::{EventListenerConfig, EventStreamBackend};
+// Local uses
+
+/// Timeout of the delivery of the message to the Kafka brokers.
+#[cfg(feature = "kafka")]
+const KAFKA_DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Producer of the messages of the configured broker. The backends are only available
+/// if the event listener is built with the `kafka` or the `nats` feature.
+pub enum StreamProducer {
+    #[cfg(feature = "kafka")]
+    Kafka(FutureProducer),
+    #[cfg(feature = "nats")]
+    Nats(nats::asynk::Connection),
+}
+
+impl StreamProducer {
+    /// Connects to the configured broker, returns `None` if the event stream is disabled.
+    pub async fn connect(config: &EventListenerConfig) -> anyhow::Result<Option<Self>> {
+        let producer = match config.stream_backend {
+            EventStreamBackend::Disabled => return Ok(None),
+            #[cfg(feature = "kafka")]
+            EventStreamBackend::Kafka => {
+                let producer = ClientConfig::new()
+                    .set("bootstrap.servers", &config.stream_url)
+                    // Keeps the order of the messages and avoids their duplicates on the retries.
+                    .set("enable.idempotence", "true")
+                    .create()?;
+                Self::Kafka(producer)
+            }
+            #[cfg(feature = "nats")]
+            EventStreamBackend::Nats => Self::Nats(nats::asynk::connect(&config.stream_url).await?),
+            #[allow(unreachable_patterns)]
+            backend => bail!(
+                "the event listener is built without the support of the {:?} event stream",
+                backend
+            ),
+        };
+        Ok(Some(producer))
+    }
+
+    /// Publishes the message to the topic. The `key` is only used by Kafka
+    /// to choose the partition of the message.
+    #[cfg_attr(not(feature = "kafka"), allow(unused_variables))]
+    pub async fn publish(&self, topic: &str, key: &str, payload: &[u8]) -> anyhow::Result<()> {
+        match *self {
+            #[cfg(feature = "kafka")]
+            Self::Kafka(ref producer) => {
+                let record = FutureRecord::to(topic).key(key).payload(payload);
+                producer
+                    .send(record, Timeout::After(KAFKA_DELIVERY_TIMEOUT))
+                    .await
+                    .map_err(|(err, _)| err)?;
+                Ok(())
+            }
+            #[cfg(feature = "nats")]
+            Self::Nats(ref connection) => Ok(connection.publish(topic, payload).await?),
+        }
+    }
+}
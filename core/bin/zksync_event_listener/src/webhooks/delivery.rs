@@ -0,0 +1,98 @@
+// Built-in uses
+use std::time::Duration;
+// External uses
+use parity_crypto::hmac;
+use reqwest::{header::CONTENT_TYPE, Client};
+// Workspace uses
+use zksync_storage::webhooks::records::StoredDueDelivery;
+// Local uses
+
+/// Header with the HMAC-SHA256 signature of the payload by the webhook secret.
+pub const SIGNATURE_HEADER: &str = "X-Zksync-Signature";
+/// Header with the id of the delivery, the same for all of its attempts.
+pub const DELIVERY_HEADER: &str = "X-Zksync-Delivery";
+/// Header with the id of the delivered event.
+pub const EVENT_HEADER: &str = "X-Zksync-Event";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeliveryOutcome {
+    /// The webhook has responded with the successful status.
+    Delivered(u16),
+    Failed {
+        response_code: Option<u16>,
+        error: String,
+    },
+}
+
+/// Signs the payload by the webhook secret, the signature is hex-encoded.
+pub fn sign_payload(secret: &str, payload: &[u8]) -> String {
+    let key = hmac::SigKey::sha256(secret.as_bytes());
+    hex::encode(&*hmac::sign(&key, payload))
+}
+
+/// Returns the delay before the next attempt of the delivery failed `attempts` times,
+/// or `None` if the delivery has no attempts left. The delay is doubled for every next retry.
+pub fn retry_delay(attempts: u32, max_attempts: u32, retry_interval: Duration) -> Option<Duration> {
+    if attempts >= max_attempts {
+        return None;
+    }
+    let exponent = attempts.saturating_sub(1).min(16);
+    Some(retry_interval * 2u32.pow(exponent))
+}
+
+/// Sends the payload of the delivery to its webhook.
+pub async fn deliver(client: &Client, delivery: &StoredDueDelivery) -> DeliveryOutcome {
+    let body = serde_json::to_vec(&delivery.payload).expect("JSON payload is serializable");
+    let signature = sign_payload(&delivery.secret, &body);
+
+    let response = client
+        .post(delivery.url.as_str())
+        .header(CONTENT_TYPE, "application/json")
+        .header(SIGNATURE_HEADER, format!("sha256={}", signature))
+        .header(DELIVERY_HEADER, delivery.id.to_string())
+        .header(EVENT_HEADER, delivery.event_id.to_string())
+        .body(body)
+        .send()
+        .await;
+
+    match response {
+        Ok(response) if response.status().is_success() => {
+            DeliveryOutcome::Delivered(response.status().as_u16())
+        }
+        Ok(response) => DeliveryOutcome::Failed {
+            response_code: Some(response.status().as_u16()),
+            error: format!("unexpected response status {}", response.status()),
+        },
+        Err(err) => DeliveryOutcome::Failed {
+            response_code: None,
+            error: err.to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn payload_signature() {
+        // Test vector of RFC 4231.
+        assert_eq!(
+            sign_payload("Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        assert_ne!(
+            sign_payload("other", b"what do ya want for nothing?"),
+            sign_payload("Jefe", b"what do ya want for nothing?")
+        );
+    }
+
+    #[test]
+    fn retry_delays() {
+        let interval = Duration::from_secs(10);
+        assert_eq!(retry_delay(1, 4, interval), Some(Duration::from_secs(10)));
+        assert_eq!(retry_delay(2, 4, interval), Some(Duration::from_secs(20)));
+        assert_eq!(retry_delay(3, 4, interval), Some(Duration::from_secs(40)));
+        assert_eq!(retry_delay(4, 4, interval), None);
+    }
+}
@@ -0,0 +1,181 @@
+//! Delivery of the zkSync events to the webhooks registered via the admin API.
+//!
+//! Every new event matching the filter of the webhook is scheduled for the delivery in the database,
+//! and the due deliveries are sent to the webhooks in the JSON format of the WebSocket messages,
+//! signed by the secret of the webhook. The failed deliveries are retried with the exponential backoff
+//! until the attempts are exhausted, and the outcome of every attempt is kept in the delivery log.
+//! The events which happened while the event listener was down aren't delivered.
+
+// Built-in uses
+use std::time::Duration;
+// External uses
+use actix::prelude::*;
+use futures_util::future::join_all;
+use reqwest::Client;
+// Workspace uses
+use zksync_config::{configs::event_listener::EventListenerConfig, ZkSyncConfig};
+use zksync_storage::ConnectionPool;
+use zksync_types::event::{webhook::WebhookFilter, ZkSyncEvent};
+// Local uses
+use self::delivery::{deliver, retry_delay, DeliveryOutcome};
+use crate::messages::NewEvents;
+
+pub mod delivery;
+
+/// The actor scheduling the new events for the delivery to the webhooks
+/// and delivering them.
+pub struct WebhookDispatcher {
+    db_pool: ConnectionPool,
+    client: Client,
+    config: EventListenerConfig,
+    /// Whether the deliveries leased by the previous round are still being sent.
+    delivering: bool,
+}
+
+impl WebhookDispatcher {
+    const DB_POOL_SIZE: u32 = 2;
+    /// Maximum amount of the deliveries sent in a single round.
+    const DELIVERIES_BATCH_SIZE: u32 = 100;
+    /// Time the leased deliveries are kept beyond the request timeout before they're leased again.
+    const LEASE_MARGIN: Duration = Duration::from_secs(30);
+
+    pub fn new(config: &ZkSyncConfig) -> anyhow::Result<Self> {
+        let config = config.event_listener.clone();
+        let client = Client::builder()
+            .timeout(config.webhook_timeout())
+            .build()?;
+
+        Ok(Self {
+            db_pool: ConnectionPool::new(Some(Self::DB_POOL_SIZE)),
+            client,
+            config,
+            delivering: false,
+        })
+    }
+
+    async fn schedule_deliveries(
+        db_pool: ConnectionPool,
+        events: &[ZkSyncEvent],
+    ) -> anyhow::Result<()> {
+        let mut storage = db_pool.access_storage().await?;
+        let webhooks = storage.webhooks_schema().load_webhooks().await?;
+
+        let mut deliveries = Vec::new();
+        for webhook in webhooks {
+            let filter: WebhookFilter = match serde_json::from_value(webhook.filter) {
+                Ok(filter) => filter,
+                Err(err) => {
+                    vlog::warn!("Invalid filter of the webhook {}: {}", webhook.id, err);
+                    continue;
+                }
+            };
+            for event in events.iter().filter(|event| filter.matches(event)) {
+                deliveries.push((webhook.id, event.id, serde_json::to_value(event)?));
+            }
+        }
+
+        storage
+            .webhooks_schema()
+            .schedule_deliveries(&deliveries)
+            .await?;
+        Ok(())
+    }
+
+    async fn send_due_deliveries(
+        db_pool: ConnectionPool,
+        client: Client,
+        config: EventListenerConfig,
+    ) -> anyhow::Result<()> {
+        let mut storage = db_pool.access_storage().await?;
+        let deliveries = storage
+            .webhooks_schema()
+            .lease_due_deliveries(
+                Self::DELIVERIES_BATCH_SIZE,
+                config.webhook_timeout() + Self::LEASE_MARGIN,
+            )
+            .await?;
+        let outcomes = join_all(deliveries.iter().map(|delivery| deliver(&client, delivery))).await;
+
+        for (delivery, outcome) in deliveries.iter().zip(outcomes) {
+            match outcome {
+                DeliveryOutcome::Delivered(response_code) => {
+                    storage
+                        .webhooks_schema()
+                        .record_delivery_success(delivery.id, response_code)
+                        .await?;
+                }
+                DeliveryOutcome::Failed {
+                    response_code,
+                    error,
+                } => {
+                    let retry_in = retry_delay(
+                        delivery.attempts as u32 + 1,
+                        config.webhook_max_attempts,
+                        config.webhook_retry_interval(),
+                    );
+                    if retry_in.is_none() {
+                        vlog::warn!(
+                            "Couldn't deliver the event {} to the webhook {}, reason: {}",
+                            delivery.event_id,
+                            delivery.webhook_id,
+                            error
+                        );
+                    }
+                    storage
+                        .webhooks_schema()
+                        .record_delivery_failure(delivery.id, response_code, &error, retry_in)
+                        .await?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Actor for WebhookDispatcher {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        ctx.set_mailbox_capacity(1 << 32);
+        ctx.run_interval(self.config.webhook_delivery_interval(), |act, ctx| {
+            // Don't lease the next deliveries until the previous ones are sent.
+            if act.delivering {
+                return;
+            }
+            act.delivering = true;
+            Self::send_due_deliveries(act.db_pool.clone(), act.client.clone(), act.config.clone())
+                .into_actor(act)
+                .map(|result, act, _| {
+                    act.delivering = false;
+                    if let Err(err) = result {
+                        vlog::error!("Couldn't send the webhook deliveries, reason: {}", err);
+                    }
+                })
+                .spawn(ctx);
+        });
+    }
+
+    fn stopped(&mut self, _ctx: &mut Self::Context) {
+        vlog::warn!("WebhookDispatcher actor has stopped");
+    }
+}
+
+impl Handler<NewEvents> for WebhookDispatcher {
+    type Result = ();
+
+    fn handle(&mut self, msg: NewEvents, ctx: &mut Self::Context) {
+        if msg.0.is_empty() {
+            return;
+        }
+        // The next events aren't handled until these ones are scheduled.
+        let db_pool = self.db_pool.clone();
+        async move { Self::schedule_deliveries(db_pool, msg.0.as_ref()).await }
+            .into_actor(self)
+            .map(|result, _, _| {
+                if let Err(err) = result {
+                    vlog::error!("Couldn't schedule the webhook deliveries, reason: {}", err);
+                }
+            })
+            .wait(ctx);
+    }
+}
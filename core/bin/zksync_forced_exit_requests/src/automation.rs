@@ -0,0 +1,303 @@
+//! Automation of the ForcedExit requests accepted by the `api/v0.2/forced_exit` endpoints.
+//!
+//! The pending requests are submitted in batches by the forced exit sender account: the batch
+//! is sent once the configured amount of the requests is collected, or once the oldest request
+//! has waited for too long. Right before the submission every request is checked once again and
+//! the ones which aren't possible anymore are marked as failed.
+
+use std::{ops::AddAssign, time::Duration};
+
+use chrono::{DateTime, Utc};
+use futures::{
+    channel::{mpsc, oneshot},
+    SinkExt,
+};
+use tokio::{task::JoinHandle, time};
+
+use zksync_api::api_server::forced_exit_checker::{ForcedExitAccountAgeChecker, ForcedExitChecker};
+use zksync_config::ForcedExitRequestsConfig;
+use zksync_mempool::MempoolTransactionRequest;
+use zksync_storage::{
+    forced_exit_requests::records::StoredForcedExitAutomationRequest, ConnectionPool,
+};
+use zksync_types::{tx::TxHash, AccountId, Address, PubKeyHash, TokenId};
+use zksync_utils::biguint_to_big_decimal;
+
+use crate::{
+    core_interaction_wrapper::{CoreInteractionWrapper, MempoolCoreInteractionWrapper},
+    forced_exit_sender::MempoolForcedExitSender,
+    prepare_forced_exit_sender::{
+        check_forced_exit_sender_prepared, prepare_forced_exit_sender_account,
+    },
+    utils::read_signing_key,
+};
+
+/// Returns whether the pending requests should be submitted now: either the batch is full,
+/// or the oldest request has waited for at least `max_wait`.
+pub fn should_submit(
+    requests: &[StoredForcedExitAutomationRequest],
+    batch_size: u32,
+    max_wait: Duration,
+    now: DateTime<Utc>,
+) -> bool {
+    let oldest = match requests.first() {
+        Some(request) => request,
+        None => return false,
+    };
+    if requests.len() >= batch_size as usize {
+        return true;
+    }
+    let max_wait =
+        chrono::Duration::from_std(max_wait).unwrap_or_else(|_| chrono::Duration::max_value());
+    now - oldest.created_at >= max_wait
+}
+
+struct ForcedExitAutomation {
+    connection_pool: ConnectionPool,
+    mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
+    forced_exit_checker: ForcedExitChecker,
+    forced_exit_sender: MempoolForcedExitSender<MempoolCoreInteractionWrapper>,
+    core_interaction_wrapper: MempoolCoreInteractionWrapper,
+    config: ForcedExitRequestsConfig,
+    sender_account_id: AccountId,
+}
+
+impl ForcedExitAutomation {
+    /// Returns the reason why the ForcedExit of the request isn't possible anymore, if any.
+    async fn check_request(
+        &self,
+        request: &StoredForcedExitAutomationRequest,
+    ) -> anyhow::Result<Option<&'static str>> {
+        let target = Address::from_slice(&request.target);
+        let mut storage = self.connection_pool.access_storage().await?;
+        let account = storage
+            .chain()
+            .account_schema()
+            .account_state_by_address(target)
+            .await?
+            .committed
+            .map(|(_, account)| account);
+        let account = match account {
+            Some(account) => account,
+            None => return Ok(Some("Target account does not exist")),
+        };
+        if account.pub_key_hash != PubKeyHash::default() {
+            return Ok(Some("Target account has the signing key set"));
+        }
+        let eligible = self
+            .forced_exit_checker
+            .check_forced_exit(&mut storage, target)
+            .await?;
+        if !eligible {
+            return Ok(Some("Target account is too young for the ForcedExit"));
+        }
+        let balance = account.get_balance(TokenId(request.token_id as u32));
+        if biguint_to_big_decimal(balance) <= request.estimated_fee {
+            return Ok(Some(
+                "Balance of the target account doesn't cover the ForcedExit fee",
+            ));
+        }
+        Ok(None)
+    }
+
+    async fn process_requests(&mut self) -> anyhow::Result<()> {
+        let requests = self
+            .connection_pool
+            .access_storage()
+            .await?
+            .forced_exit_requests_schema()
+            .load_pending_automation_requests(self.config.automation_batch_size)
+            .await?;
+        if !should_submit(
+            &requests,
+            self.config.automation_batch_size,
+            self.config.automation_max_wait(),
+            Utc::now(),
+        ) {
+            return Ok(());
+        }
+
+        let mut nonce = self
+            .core_interaction_wrapper
+            .get_nonce(self.sender_account_id)
+            .await?
+            .expect("Forced Exit sender account does not have nonce");
+        let mut txs = Vec::new();
+        let mut submitted = Vec::new();
+        for request in requests {
+            if let Some(reason) = self.check_request(&request).await? {
+                vlog::info!("ForcedExit request {} has failed: {}", request.id, reason);
+                self.connection_pool
+                    .access_storage()
+                    .await?
+                    .forced_exit_requests_schema()
+                    .set_automation_request_failed(request.id, reason)
+                    .await?;
+                continue;
+            }
+            let tx = self.forced_exit_sender.build_forced_exit(
+                nonce,
+                Address::from_slice(&request.target),
+                TokenId(request.token_id as u32),
+            );
+            submitted.push((request.id, tx.hash()));
+            txs.push(tx);
+            nonce.add_assign(1);
+        }
+        if txs.is_empty() {
+            return Ok(());
+        }
+
+        // The requests stay pending if the batch is rejected, so they're retried on the next round.
+        let (sender, receiver) = oneshot::channel();
+        self.mempool_tx_sender
+            .send(MempoolTransactionRequest::NewTxsBatch(txs, vec![], sender))
+            .await?;
+        receiver.await??;
+
+        let mut storage = self.connection_pool.access_storage().await?;
+        for (id, tx_hash) in &submitted {
+            storage
+                .forced_exit_requests_schema()
+                .set_automation_request_submitted(*id, *tx_hash)
+                .await?;
+        }
+        drop(storage);
+        vlog::info!("Submitted {} ForcedExit requests", submitted.len());
+
+        // The transactions are sent in a batch, so it's enough to wait for the first one.
+        let first_hash: TxHash = submitted[0].1;
+        if let Err(err) = self
+            .forced_exit_sender
+            .wait_until_comitted(first_hash)
+            .await
+        {
+            vlog::error!("ForcedExit batch {:?} has failed: {}", first_hash, err);
+            let mut storage = self.connection_pool.access_storage().await?;
+            for (id, _) in &submitted {
+                storage
+                    .forced_exit_requests_schema()
+                    .set_automation_request_failed(*id, "ForcedExit transaction has failed")
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn run(mut self) {
+        let mut timer = time::interval(self.config.automation_interval());
+        loop {
+            timer.tick().await;
+            if let Err(err) = self.process_requests().await {
+                vlog::error!("Couldn't process the ForcedExit requests: {}", err);
+            }
+        }
+    }
+}
+
+/// Waits until the forced exit sender account is prepared by the contract watcher.
+async fn wait_for_sender_prepared(
+    connection_pool: &ConnectionPool,
+    config: &ForcedExitRequestsConfig,
+) -> anyhow::Result<AccountId> {
+    let sender_sk = hex::decode(&config.sender_private_key[2..])?;
+    let sender_sk = read_signing_key(&sender_sk)?;
+    let mut timer = time::interval(config.automation_interval());
+    loop {
+        timer.tick().await;
+        let mut storage = connection_pool.access_storage().await?;
+        if let Some(id) = check_forced_exit_sender_prepared(
+            &mut storage,
+            &sender_sk,
+            config.sender_account_address,
+        )
+        .await?
+        {
+            return Ok(id);
+        }
+    }
+}
+
+pub fn run_forced_exit_automation(
+    connection_pool: ConnectionPool,
+    mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
+    config: ForcedExitRequestsConfig,
+    forced_exit_minimum_account_age_secs: u64,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        // The contract watcher prepares the sender account itself if it's enabled,
+        // so the account isn't prepared twice.
+        let sender_account_id = if config.enabled {
+            wait_for_sender_prepared(&connection_pool, &config).await
+        } else {
+            prepare_forced_exit_sender_account(
+                connection_pool.clone(),
+                &config,
+                mempool_tx_sender.clone(),
+            )
+            .await
+        }
+        .expect("Failed to prepare the forced exit sender account");
+
+        let core_interaction_wrapper = MempoolCoreInteractionWrapper::new(
+            forced_exit_minimum_account_age_secs,
+            connection_pool.clone(),
+            mempool_tx_sender.clone(),
+        );
+        let forced_exit_sender = MempoolForcedExitSender::new(
+            core_interaction_wrapper.clone(),
+            config.clone(),
+            sender_account_id,
+        );
+        let automation = ForcedExitAutomation {
+            connection_pool,
+            mempool_tx_sender,
+            forced_exit_checker: ForcedExitChecker::new(forced_exit_minimum_account_age_secs),
+            forced_exit_sender,
+            core_interaction_wrapper,
+            config,
+            sender_account_id,
+        };
+        automation.run().await;
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::BigUint;
+
+    fn request(created_at: DateTime<Utc>) -> StoredForcedExitAutomationRequest {
+        StoredForcedExitAutomationRequest {
+            id: 1,
+            target: Address::repeat_byte(0x01).as_bytes().to_vec(),
+            token_id: 0,
+            estimated_fee: biguint_to_big_decimal(BigUint::from(1000u32)),
+            status: "pending".to_owned(),
+            tx_hash: None,
+            error: None,
+            created_at,
+            processed_at: None,
+        }
+    }
+
+    #[test]
+    fn requests_are_batched() {
+        let now = Utc::now();
+        let max_wait = Duration::from_secs(600);
+
+        assert!(!should_submit(&[], 2, max_wait, now));
+        // The batch isn't full and the requests are fresh.
+        assert!(!should_submit(&[request(now)], 2, max_wait, now));
+        // The batch is full.
+        assert!(should_submit(
+            &[request(now), request(now)],
+            2,
+            max_wait,
+            now
+        ));
+        // The oldest request has waited for too long.
+        let old = now - chrono::Duration::seconds(600);
+        assert!(should_submit(&[request(old)], 2, max_wait, now));
+    }
+}
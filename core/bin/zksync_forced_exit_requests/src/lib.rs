@@ -7,6 +7,7 @@ use forced_exit_sender::ForcedExitSender;
 use zksync_config::configs::api::CommonApiConfig;
 use zksync_mempool::MempoolTransactionRequest;
 
+pub mod automation;
 mod core_interaction_wrapper;
 pub mod eth_watch;
 pub mod forced_exit_sender;
@@ -25,6 +26,14 @@ pub fn run_forced_exit_requests_actors(
     contracts: ContractsConfig,
     web3_url: String,
 ) -> JoinHandle<()> {
+    if config.automation_enabled {
+        automation::run_forced_exit_automation(
+            pool.clone(),
+            sender.clone(),
+            config.clone(),
+            common.forced_exit_minimum_account_age_secs,
+        );
+    }
     eth_watch::run_forced_exit_contract_watcher(
         sender,
         pool,
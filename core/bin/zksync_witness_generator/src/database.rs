@@ -5,8 +5,13 @@
 
 // Built-in
 use std::clone::Clone;
+use std::time::Duration;
 // Workspace uses
 use zksync_crypto::proof::{AggregatedProof, SingleProof};
+use zksync_storage::ethereum::records::StorageBlockVerificationCost;
+use zksync_storage::prover::records::{
+    StorageConnectedProver, StorageJobQueueDepth, StorageProofGenerationTime, StorageProvingTime,
+};
 use zksync_storage::{ConnectionPool, StorageProcessor};
 use zksync_types::{
     aggregated_operations::{AggregatedActionType, AggregatedOperation},
@@ -106,11 +111,14 @@ impl DatabaseInterface for Database {
         Ok(proof)
     }
 
-    async fn mark_stale_jobs_as_idle(
+    async fn release_expired_prover_job_leases(
         &self,
         connection: &mut StorageProcessor<'_>,
     ) -> anyhow::Result<()> {
-        connection.prover_schema().mark_stale_jobs_as_idle().await?;
+        connection
+            .prover_schema()
+            .release_expired_prover_job_leases()
+            .await?;
 
         Ok(())
     }
@@ -151,32 +159,123 @@ impl DatabaseInterface for Database {
         Ok(tree_cache)
     }
 
-    async fn load_idle_prover_job_from_job_queue(
+    async fn lease_prover_job(
         &self,
         connection: &mut StorageProcessor<'_>,
+        prover_name: &str,
+        lease_duration: Duration,
     ) -> anyhow::Result<Option<ProverJob>> {
-        let proof = connection
+        let job = connection
             .prover_schema()
-            .get_idle_prover_job_from_job_queue()
+            .lease_prover_job(prover_name, lease_duration)
             .await?;
 
-        Ok(proof)
+        Ok(job)
     }
 
-    async fn record_prover_is_working(
+    async fn renew_prover_job_lease(
         &self,
         connection: &mut StorageProcessor<'_>,
         job_id: i32,
         prover_name: &str,
+        lease_duration: Duration,
+    ) -> anyhow::Result<bool> {
+        let renewed = connection
+            .prover_schema()
+            .renew_prover_job_lease(job_id, prover_name, lease_duration)
+            .await?;
+
+        Ok(renewed)
+    }
+
+    async fn set_block_job_priority(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        block_number: BlockNumber,
+        job_priority: i32,
+    ) -> anyhow::Result<()> {
+        connection
+            .prover_schema()
+            .set_block_job_priority(block_number, job_priority)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn record_prover_heartbeat(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        prover_name: &str,
     ) -> anyhow::Result<()> {
         connection
             .prover_schema()
-            .record_prover_is_working(job_id, prover_name)
+            .record_prover_heartbeat(prover_name)
             .await?;
 
         Ok(())
     }
 
+    async fn load_connected_provers(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        timeout: Duration,
+    ) -> anyhow::Result<Vec<StorageConnectedProver>> {
+        let provers = connection
+            .prover_schema()
+            .load_connected_provers(timeout)
+            .await?;
+
+        Ok(provers)
+    }
+
+    async fn load_proving_times(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        period: Duration,
+    ) -> anyhow::Result<Vec<StorageProvingTime>> {
+        let proving_times = connection
+            .prover_schema()
+            .load_proving_times(period)
+            .await?;
+
+        Ok(proving_times)
+    }
+
+    async fn load_proof_generation_times(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        period: Duration,
+    ) -> anyhow::Result<Vec<StorageProofGenerationTime>> {
+        let times = connection
+            .prover_schema()
+            .load_proof_generation_times(period)
+            .await?;
+
+        Ok(times)
+    }
+
+    async fn load_block_verification_costs(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        period: Duration,
+    ) -> anyhow::Result<Vec<StorageBlockVerificationCost>> {
+        let costs = connection
+            .ethereum_schema()
+            .load_block_verification_costs(period)
+            .await?;
+
+        Ok(costs)
+    }
+
+    async fn load_job_queue_depth(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<Vec<StorageJobQueueDepth>> {
+        let queue_depth = connection.prover_schema().load_job_queue_depth().await?;
+
+        Ok(queue_depth)
+    }
+
     async fn store_proof(
         &self,
         connection: &mut StorageProcessor<'_>,
@@ -293,4 +392,31 @@ impl DatabaseInterface for Database {
 
         Ok(count)
     }
+
+    async fn load_witness_generator_progress(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        shard_index: u32,
+    ) -> anyhow::Result<Option<BlockNumber>> {
+        let last_block = connection
+            .prover_schema()
+            .load_witness_generator_progress(shard_index)
+            .await?;
+
+        Ok(last_block)
+    }
+
+    async fn store_witness_generator_progress(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        shard_index: u32,
+        last_block: BlockNumber,
+    ) -> anyhow::Result<()> {
+        connection
+            .prover_schema()
+            .store_witness_generator_progress(shard_index, last_block)
+            .await?;
+
+        Ok(())
+    }
 }
@@ -6,8 +6,13 @@
 // Built-in
 use std::clone::Clone;
 use std::marker::{Send, Sync};
+use std::time::Duration;
 // Workspace uses
 use zksync_crypto::proof::{AggregatedProof, SingleProof};
+use zksync_storage::ethereum::records::StorageBlockVerificationCost;
+use zksync_storage::prover::records::{
+    StorageConnectedProver, StorageJobQueueDepth, StorageProofGenerationTime, StorageProvingTime,
+};
 use zksync_storage::StorageProcessor;
 use zksync_types::aggregated_operations::{AggregatedActionType, AggregatedOperation};
 use zksync_types::{
@@ -59,7 +64,8 @@ pub trait DatabaseInterface: Send + Sync + Clone + 'static {
         block_number: BlockNumber,
     ) -> anyhow::Result<Option<SingleProof>>;
 
-    async fn mark_stale_jobs_as_idle(
+    /// Returns the jobs with the expired leases back to the queue.
+    async fn release_expired_prover_job_leases(
         &self,
         connection: &mut StorageProcessor<'_>,
     ) -> anyhow::Result<()>;
@@ -80,18 +86,71 @@ pub trait DatabaseInterface: Send + Sync + Clone + 'static {
         connection: &mut StorageProcessor<'_>,
     ) -> anyhow::Result<Option<(BlockNumber, Vec<u8>)>>;
 
-    async fn load_idle_prover_job_from_job_queue(
+    /// Leases the idle job with the highest priority to the prover.
+    async fn lease_prover_job(
         &self,
         connection: &mut StorageProcessor<'_>,
+        prover_name: &str,
+        lease_duration: Duration,
     ) -> anyhow::Result<Option<ProverJob>>;
 
-    async fn record_prover_is_working(
+    /// Renews the lease of the job, returns `false` if the job isn't leased to the prover anymore.
+    async fn renew_prover_job_lease(
         &self,
         connection: &mut StorageProcessor<'_>,
         job_id: i32,
         prover_name: &str,
+        lease_duration: Duration,
+    ) -> anyhow::Result<bool>;
+
+    async fn set_block_job_priority(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        block_number: BlockNumber,
+        job_priority: i32,
+    ) -> anyhow::Result<()>;
+
+    async fn record_prover_heartbeat(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        prover_name: &str,
     ) -> anyhow::Result<()>;
 
+    /// Returns the provers that have reached the server within the `timeout` along with their current jobs.
+    async fn load_connected_provers(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        timeout: Duration,
+    ) -> anyhow::Result<Vec<StorageConnectedProver>>;
+
+    /// Returns the times of the single block proofs created within the `period` grouped by the block size.
+    async fn load_proving_times(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        period: Duration,
+    ) -> anyhow::Result<Vec<StorageProvingTime>>;
+
+    /// Returns the wall times of the proofs created within the `period` grouped by the job type,
+    /// the block size and the amount of blocks in the proof.
+    async fn load_proof_generation_times(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        period: Duration,
+    ) -> anyhow::Result<Vec<StorageProofGenerationTime>>;
+
+    /// Returns the L1 gas used by the blocks confirmed within the `period` grouped by the operation type,
+    /// the block size and the amount of blocks in the operation.
+    async fn load_block_verification_costs(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        period: Duration,
+    ) -> anyhow::Result<Vec<StorageBlockVerificationCost>>;
+
+    async fn load_job_queue_depth(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<Vec<StorageJobQueueDepth>>;
+
     async fn store_proof(
         &self,
         connection: &mut StorageProcessor<'_>,
@@ -146,4 +205,18 @@ pub trait DatabaseInterface: Send + Sync + Clone + 'static {
         &self,
         connection: &mut StorageProcessor<'_>,
     ) -> anyhow::Result<u32>;
+
+    /// Returns the last block up to which all the witnesses of the witness generator shard are generated.
+    async fn load_witness_generator_progress(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        shard_index: u32,
+    ) -> anyhow::Result<Option<BlockNumber>>;
+
+    async fn store_witness_generator_progress(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        shard_index: u32,
+        last_block: BlockNumber,
+    ) -> anyhow::Result<()>;
 }
@@ -0,0 +1,203 @@
+//! The exit proof generator processes the exit proof requests stored by the API
+//! and stores the generated proofs, so the users can withdraw their funds via the exodus
+//! mode of the contract without running the offline proof generation.
+//!
+//! The proofs are generated for the last verified state one by one, since the generation takes
+//! minutes and requires the universal setup keys. The requests for the earlier blocks are
+//! moved to the last verified block first, since only its state can be restored from the database.
+//! The generator is opt-in and run only by the `exit-proof-generator` component.
+
+// Built-in uses
+use std::time::{Duration, Instant};
+
+// External uses
+use anyhow::format_err;
+use tokio::{task::JoinHandle, time};
+
+// Workspace deps
+use zksync_crypto::params::MIN_NFT_TOKEN_ID;
+use zksync_prover_utils::exit_proof::{create_exit_proof_fungible, create_exit_proof_nft};
+use zksync_storage::{ConnectionPool, StorageProcessor};
+use zksync_types::{
+    exit_proof::{ExitProofData, ExitProofRequest, StoredBlockInfo},
+    AccountId, TokenLike,
+};
+
+/// Interval of checking for the new requests when there are no pending ones.
+const POLLING_INTERVAL: Duration = Duration::from_secs(10);
+
+#[must_use]
+pub fn run_exit_proof_generator(db_pool: ConnectionPool) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut timer = time::interval(POLLING_INTERVAL);
+        loop {
+            timer.tick().await;
+            loop {
+                match process_pending_request(&db_pool).await {
+                    Ok(true) => {}
+                    Ok(false) => break,
+                    Err(err) => {
+                        vlog::error!("Can't process the exit proof request: {}", err);
+                        break;
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// Generates the exit proof for the oldest pending request.
+/// Returns `false` if there are no pending requests.
+async fn process_pending_request(db_pool: &ConnectionPool) -> anyhow::Result<bool> {
+    let mut storage = db_pool.access_storage().await?;
+    let request = match storage.exit_proofs_schema().load_pending_request().await? {
+        Some(request) => request,
+        None => return Ok(false),
+    };
+
+    let last_verified_block = storage
+        .chain()
+        .block_schema()
+        .get_last_verified_confirmed_block()
+        .await?;
+    if request.block_number < last_verified_block {
+        storage
+            .exit_proofs_schema()
+            .store_request(request.account_id, request.token_id, last_verified_block)
+            .await?;
+        return Ok(true);
+    }
+
+    let start = Instant::now();
+    match generate_exit_proof(&mut storage, &request).await {
+        Ok(proof_data) => {
+            storage
+                .exit_proofs_schema()
+                .store_proof(&proof_data)
+                .await?;
+            metrics::histogram!("exit_proof_generator.generate_exit_proof", start.elapsed());
+            vlog::info!(
+                "Generated the exit proof for the account {} and the token {} at the block {}",
+                *request.account_id,
+                *request.token_id,
+                *request.block_number
+            );
+        }
+        Err(err) => {
+            metrics::increment_counter!("exit_proof_generator.failed_requests");
+            vlog::warn!(
+                "Can't generate the exit proof for the account {} and the token {}: {}",
+                *request.account_id,
+                *request.token_id,
+                err
+            );
+            storage
+                .exit_proofs_schema()
+                .store_error(
+                    request.account_id,
+                    request.token_id,
+                    request.block_number,
+                    &err.to_string(),
+                )
+                .await?;
+        }
+    }
+    Ok(true)
+}
+
+/// Generates the exit proof for the verified state of the block of the request,
+/// which is expected to be the last verified block.
+async fn generate_exit_proof(
+    storage: &mut StorageProcessor<'_>,
+    request: &ExitProofRequest,
+) -> anyhow::Result<ExitProofData> {
+    let (verified_block, accounts) = storage.chain().state_schema().load_verified_state().await?;
+    if verified_block != request.block_number {
+        return Err(format_err!(
+            "The last verified block {} differs from the requested block {}",
+            *verified_block,
+            *request.block_number
+        ));
+    }
+    let block = storage
+        .chain()
+        .block_schema()
+        .get_block(request.block_number)
+        .await?
+        .ok_or_else(|| format_err!("Block {} is not stored", *request.block_number))?;
+    let owner = accounts
+        .get(&request.account_id)
+        .map(|account| account.address)
+        .ok_or_else(|| format_err!("Account {} is not verified", *request.account_id))?;
+    let token = storage
+        .tokens_schema()
+        .get_token(TokenLike::Id(request.token_id))
+        .await?
+        .ok_or_else(|| format_err!("Token {} is not found", *request.token_id))?;
+
+    let (account_id, token_id) = (request.account_id, request.token_id);
+    let (nft_creator_id, nft_creator_address, nft_serial_id, nft_content_hash, proof, amount) =
+        if *token_id < MIN_NFT_TOKEN_ID {
+            let (proof, amount) = tokio::task::spawn_blocking(move || {
+                create_exit_proof_fungible(accounts, account_id, owner, token_id)
+            })
+            .await??;
+            // The placeholder creator of the fungible token is the account with id 0.
+            let creator_address = storage
+                .chain()
+                .account_schema()
+                .account_address_by_id(AccountId(0))
+                .await?
+                .ok_or_else(|| format_err!("Account with id 0 does not exist"))?;
+            (
+                AccountId(0),
+                creator_address,
+                0,
+                Default::default(),
+                proof,
+                amount,
+            )
+        } else {
+            let nft = storage
+                .tokens_schema()
+                .get_nft(token_id)
+                .await?
+                .ok_or_else(|| format_err!("NFT {} is not found", *token_id))?;
+            let (creator_id, serial_id, content_hash) =
+                (nft.creator_id, nft.serial_id, nft.content_hash);
+            let (proof, amount) = tokio::task::spawn_blocking(move || {
+                create_exit_proof_nft(
+                    accounts,
+                    account_id,
+                    owner,
+                    token_id,
+                    creator_id,
+                    serial_id,
+                    content_hash,
+                )
+            })
+            .await??;
+            (
+                nft.creator_id,
+                nft.creator_address,
+                nft.serial_id,
+                nft.content_hash,
+                proof,
+                amount,
+            )
+        };
+
+    Ok(ExitProofData {
+        stored_block_info: StoredBlockInfo::from_block(&block),
+        owner,
+        account_id,
+        token_id,
+        amount: amount.into(),
+        nft_creator_id,
+        nft_creator_address,
+        nft_serial_id,
+        nft_content_hash,
+        proof,
+        token_address: token.address,
+    })
+}
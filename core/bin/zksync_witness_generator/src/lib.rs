@@ -11,8 +11,7 @@ use actix_web_httpauth::extractors::{
 };
 use actix_web_httpauth::middleware::HttpAuthentication;
 
-use jsonwebtoken::errors::Error as JwtError;
-use jsonwebtoken::{decode, DecodingKey, Validation};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 // Workspace deps
@@ -34,33 +33,39 @@ use zksync_types::prover::{
     ProverJobType, AGGREGATED_PROOF_JOB_PRIORITY, SINGLE_PROOF_JOB_PRIORITY,
 };
 use zksync_types::BlockNumber;
+use zksync_utils::auth::AuthTokenValidator;
 use zksync_utils::panic_notify::{spawn_panic_handler, ThreadPanicNotify};
 
 #[cfg(test)]
 mod tests;
 
+/// Period of the proving times reported by the fleet status.
+const PROVING_TIMES_PERIOD: Duration = Duration::from_secs(24 * 60 * 60);
+/// Period of the proving and verification costs reported by the costs stats.
+const COSTS_STATS_PERIOD: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
 pub mod database;
 mod database_interface;
+pub mod exit_proof_generator;
 mod scaler;
 mod witness_generator;
 
-#[derive(Debug, Serialize, Deserialize)]
-struct PayloadAuthToken {
-    /// Subject (whom auth token refers to).
-    sub: String,
-    /// Expiration time (as UTC timestamp).
-    exp: usize,
-}
-
 #[derive(Debug, Clone)]
 struct AppState<DB: DatabaseInterface> {
     secret_auth: String,
     database: DB,
     scaler_oracle: Arc<RwLock<ScalerOracle<DB>>>,
+    /// Duration of the job lease, the job is given to another prover if the lease isn't renewed within it.
+    lease_duration: Duration,
 }
 
 impl<DB: DatabaseInterface> AppState<DB> {
-    pub fn new(secret_auth: String, database: DB, idle_provers: u32) -> Self {
+    pub fn new(
+        secret_auth: String,
+        database: DB,
+        idle_provers: u32,
+        lease_duration: Duration,
+    ) -> Self {
         let scaler_oracle = Arc::new(RwLock::new(ScalerOracle::new(
             database.clone(),
             idle_provers,
@@ -70,6 +75,7 @@ impl<DB: DatabaseInterface> AppState<DB> {
             secret_auth,
             database,
             scaler_oracle,
+            lease_duration,
         }
     }
 
@@ -79,39 +85,37 @@ impl<DB: DatabaseInterface> AppState<DB> {
             actix_web::error::ErrorInternalServerError(e)
         })
     }
-}
 
-/// The structure that stores the secret key for checking JsonWebToken matching.
-struct AuthTokenValidator<'a> {
-    decoding_key: DecodingKey<'a>,
-}
-
-impl<'a> AuthTokenValidator<'a> {
-    fn new(secret: &'a str) -> Self {
-        Self {
-            decoding_key: DecodingKey::from_secret(secret.as_ref()),
+    /// Records the heartbeat of the prover for the fleet status. The failure doesn't affect the request,
+    /// since the heartbeats are only used for the monitoring.
+    async fn record_heartbeat(
+        &self,
+        storage: &mut zksync_storage::StorageProcessor<'_>,
+        prover_name: &str,
+    ) {
+        if let Err(e) = self
+            .database
+            .record_prover_heartbeat(storage, prover_name)
+            .await
+        {
+            vlog::warn!("Failed to record the heartbeat of the prover: {}", e);
         }
     }
+}
 
-    /// Checks whether the secret key and the authorization token match.
-    fn validate_auth_token(&self, token: &str) -> Result<(), JwtError> {
-        decode::<PayloadAuthToken>(token, &self.decoding_key, &Validation::default())?;
-
-        Ok(())
-    }
-
-    async fn validator(
-        &self,
-        req: ServiceRequest,
-        credentials: BearerAuth,
-    ) -> actix_web::Result<ServiceRequest> {
-        let config = req.app_data::<Config>().cloned().unwrap_or_default();
+/// Checks the JsonWebToken of the prover request against the secret.
+async fn validate_auth(
+    secret_auth: &str,
+    req: ServiceRequest,
+    credentials: BearerAuth,
+) -> actix_web::Result<ServiceRequest> {
+    let config = req.app_data::<Config>().cloned().unwrap_or_default();
 
-        self.validate_auth_token(credentials.token())
-            .map_err(|_| AuthenticationError::from(config))?;
+    AuthTokenValidator::new(secret_auth)
+        .validate_auth_token(credentials.token())
+        .map_err(|_| AuthenticationError::from(config))?;
 
-        Ok(req)
-    }
+    Ok(req)
 }
 
 async fn status() -> actix_web::Result<String> {
@@ -127,9 +131,10 @@ async fn get_job<DB: DatabaseInterface>(
         return Err(actix_web::error::ErrorBadRequest("empty name"));
     }
     let mut storage = data.access_storage().await?;
+    data.record_heartbeat(&mut storage, &r.prover_name).await;
     let ret = data
         .database
-        .load_idle_prover_job_from_job_queue(&mut storage)
+        .lease_prover_job(&mut storage, &r.prover_name, data.lease_duration)
         .await
         .map_err(|e| {
             vlog::warn!("could not get next unverified commit operation: {}", e);
@@ -167,15 +172,28 @@ async fn working_on<DB: DatabaseInterface>(
         .access_storage()
         .await
         .map_err(actix_web::error::ErrorInternalServerError)?;
-    data.database
-        .record_prover_is_working(&mut storage, r.job_id, &r.prover_name)
+    data.record_heartbeat(&mut storage, &r.prover_name).await;
+    let is_renewed = data
+        .database
+        .renew_prover_job_lease(&mut storage, r.job_id, &r.prover_name, data.lease_duration)
         .await
         .map_err(|e| {
             vlog::warn!("failed to record prover work in progress request: {}", e);
             actix_web::error::ErrorInternalServerError("storage layer error")
         })?;
 
-    Ok(HttpResponse::Ok().finish())
+    if is_renewed {
+        Ok(HttpResponse::Ok().finish())
+    } else {
+        // The lease has expired and the job could be given to another prover.
+        // The proof is still accepted if the prover manages to publish it first.
+        vlog::info!(
+            "Prover '{}' has lost the lease of the job {}",
+            r.prover_name,
+            r.job_id
+        );
+        Ok(HttpResponse::Conflict().body("job lease is lost"))
+    }
 }
 
 async fn publish<DB: DatabaseInterface>(
@@ -284,6 +302,229 @@ async fn required_replicas<DB: DatabaseInterface>(
     Ok(HttpResponse::Ok().json(response))
 }
 
+/// Input of the `/api/internal/prover/priority` endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockJobPriorityInput {
+    block_number: BlockNumber,
+    /// Priority of the prover jobs of the block, the jobs with the lower values are proven first.
+    job_priority: i32,
+}
+
+async fn set_block_job_priority<DB: DatabaseInterface>(
+    data: web::Data<AppState<DB>>,
+    input: web::Json<BlockJobPriorityInput>,
+) -> actix_web::Result<HttpResponse> {
+    let mut storage = data.access_storage().await?;
+
+    vlog::info!(
+        "Setting the priority {} for the prover jobs of the block {}",
+        input.job_priority,
+        input.block_number
+    );
+
+    data.database
+        .set_block_job_priority(&mut storage, input.block_number, input.job_priority)
+        .await
+        .map_err(|e| {
+            vlog::warn!("failed to set the block job priority: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Prover connected to the server along with its current job.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectedProver {
+    prover_name: String,
+    last_seen_at: DateTime<Utc>,
+    job: Option<ProverFleetJob>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProverFleetJob {
+    job_id: i32,
+    job_type: String,
+    first_block: BlockNumber,
+    last_block: BlockNumber,
+    leased_at: Option<DateTime<Utc>>,
+}
+
+/// Proving times of the single block proofs of the block size.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockSizeProvingTime {
+    block_size: usize,
+    proofs_count: u64,
+    average_secs: f64,
+    max_secs: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobQueueDepth {
+    job_type: String,
+    idle_jobs: u64,
+    in_progress_jobs: u64,
+}
+
+/// Output of the `/api/internal/prover/fleet` endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProverFleetStatus {
+    provers: Vec<ConnectedProver>,
+    /// Proving times of the blocks proven within the last `PROVING_TIMES_PERIOD`.
+    proving_times: Vec<BlockSizeProvingTime>,
+    queue: Vec<JobQueueDepth>,
+}
+
+async fn fleet_status<DB: DatabaseInterface>(
+    data: web::Data<AppState<DB>>,
+) -> actix_web::Result<HttpResponse> {
+    let mut storage = data.access_storage().await?;
+    let storage_error = |e: anyhow::Error| {
+        vlog::warn!("failed to load the prover fleet status: {}", e);
+        actix_web::error::ErrorInternalServerError("storage layer error")
+    };
+
+    // The prover is considered connected as long as its job lease could be renewed.
+    let provers = data
+        .database
+        .load_connected_provers(&mut storage, data.lease_duration)
+        .await
+        .map_err(storage_error)?
+        .into_iter()
+        .map(|prover| {
+            let job = match (
+                prover.job_id,
+                prover.job_type,
+                prover.first_block,
+                prover.last_block,
+            ) {
+                (Some(job_id), Some(job_type), Some(first_block), Some(last_block)) => {
+                    Some(ProverFleetJob {
+                        job_id,
+                        job_type,
+                        first_block: BlockNumber(first_block as u32),
+                        last_block: BlockNumber(last_block as u32),
+                        leased_at: prover.leased_at,
+                    })
+                }
+                _ => None,
+            };
+            ConnectedProver {
+                prover_name: prover.prover_name,
+                last_seen_at: prover.last_seen_at,
+                job,
+            }
+        })
+        .collect();
+    let proving_times = data
+        .database
+        .load_proving_times(&mut storage, PROVING_TIMES_PERIOD)
+        .await
+        .map_err(storage_error)?
+        .into_iter()
+        .map(|time| BlockSizeProvingTime {
+            block_size: time.block_size as usize,
+            proofs_count: time.proofs_count as u64,
+            average_secs: time.average_secs,
+            max_secs: time.max_secs,
+        })
+        .collect();
+    let queue = data
+        .database
+        .load_job_queue_depth(&mut storage)
+        .await
+        .map_err(storage_error)?
+        .into_iter()
+        .map(|depth| JobQueueDepth {
+            job_type: depth.job_type,
+            idle_jobs: depth.idle_jobs as u64,
+            in_progress_jobs: depth.in_progress_jobs as u64,
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(ProverFleetStatus {
+        provers,
+        proving_times,
+        queue,
+    }))
+}
+
+/// Wall times of the proofs of the same type, block size and amount of blocks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProofGenerationTime {
+    job_type: String,
+    block_size: usize,
+    blocks_count: u64,
+    proofs_count: u64,
+    average_ms: f64,
+    max_ms: u64,
+}
+
+/// L1 gas used per block by the operations of the same type, block size and amount of blocks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockVerificationCost {
+    action_type: String,
+    block_size: usize,
+    /// Amount of blocks processed by a single operation.
+    blocks_per_operation: u64,
+    blocks_count: u64,
+    average_gas_per_block: f64,
+    max_gas_per_block: u64,
+}
+
+/// Output of the `/api/internal/prover/costs` endpoint, covering the last `COSTS_STATS_PERIOD`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CostsStats {
+    proof_generation_times: Vec<ProofGenerationTime>,
+    verification_costs: Vec<BlockVerificationCost>,
+}
+
+async fn costs_stats<DB: DatabaseInterface>(
+    data: web::Data<AppState<DB>>,
+) -> actix_web::Result<HttpResponse> {
+    let mut storage = data.access_storage().await?;
+    let storage_error = |e: anyhow::Error| {
+        vlog::warn!("failed to load the costs stats: {}", e);
+        actix_web::error::ErrorInternalServerError("storage layer error")
+    };
+
+    let proof_generation_times = data
+        .database
+        .load_proof_generation_times(&mut storage, COSTS_STATS_PERIOD)
+        .await
+        .map_err(storage_error)?
+        .into_iter()
+        .map(|time| ProofGenerationTime {
+            job_type: time.job_type,
+            block_size: time.block_size as usize,
+            blocks_count: time.blocks_count as u64,
+            proofs_count: time.proofs_count as u64,
+            average_ms: time.average_ms,
+            max_ms: time.max_ms as u64,
+        })
+        .collect();
+    let verification_costs = data
+        .database
+        .load_block_verification_costs(&mut storage, COSTS_STATS_PERIOD)
+        .await
+        .map_err(storage_error)?
+        .into_iter()
+        .map(|cost| BlockVerificationCost {
+            action_type: cost.action_type,
+            block_size: cost.block_size as usize,
+            blocks_per_operation: cost.operation_blocks_count as u64,
+            blocks_count: cost.blocks_count as u64,
+            average_gas_per_block: cost.average_gas_per_block,
+            max_gas_per_block: cost.max_gas_per_block as u64,
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(CostsStats {
+        proof_generation_times,
+        verification_costs,
+    }))
+}
+
 async fn update_prover_job_queue_loop<DB: DatabaseInterface>(
     database: DB,
     prepare_data_interval: Duration,
@@ -381,7 +622,9 @@ async fn update_prover_job_queue<DB: DatabaseInterface>(database: DB) -> anyhow:
                 .await?;
         }
     }
-    database.mark_stale_jobs_as_idle(&mut connection).await?;
+    database
+        .release_expired_prover_job_leases(&mut connection)
+        .await?;
 
     Ok(())
 }
@@ -407,42 +650,35 @@ pub fn run_prover_server<DB: DatabaseInterface>(
                     witness_generator_opts.prepare_data_interval(),
                 ));
 
-                let last_verified_block = {
-                    let mut storage = database
-                        .acquire_connection()
-                        .await
-                        .expect("Failed to access storage");
-
-                    *database
-                        .load_last_verified_block(&mut storage)
-                        .await
-                        .expect("Failed to get last verified block number")
-                        as usize
-                };
-
-                // Start pool maintainer threads.
-                for offset in 0..witness_generator_opts.witness_generators {
-                    let start_block = (last_verified_block + offset + 1) as u32;
-                    let block_step = witness_generator_opts.witness_generators as u32;
-                    vlog::info!(
-                        "Starting witness generator ({},{})",
-                        start_block,
-                        block_step
-                    );
-                    let pool_maintainer = witness_generator::WitnessGenerator::new(
-                        database.clone(),
-                        witness_generator_opts.prepare_data_interval(),
-                        BlockNumber(start_block),
-                        BlockNumber(block_step),
-                    );
-                    pool_maintainer.start(panic_sender.clone());
-                }
+                // Start the witness generator.
+                let shard = witness_generator::WitnessGeneratorShard::new(
+                    witness_generator_opts.shard_index,
+                    witness_generator_opts.shards_count,
+                );
+                vlog::info!(
+                    "Starting witness generator shard({},{}) with {} workers",
+                    shard.index,
+                    shard.count,
+                    witness_generator_opts.witness_generators
+                );
+                let pool_maintainer = witness_generator::WitnessGenerator::new(
+                    database.clone(),
+                    witness_generator_opts.prepare_data_interval(),
+                    witness_generator_opts.witness_generators,
+                    shard,
+                );
+                pool_maintainer.start(panic_sender.clone());
                 // Start HTTP server.
                 let secret_auth = prover_api_opts.secret_auth.clone();
                 let idle_provers = core_opts.idle_provers;
+                let lease_duration = core_opts.gone_timeout();
                 HttpServer::new(move || {
-                    let app_state =
-                        AppState::new(secret_auth.clone(), database.clone(), idle_provers);
+                    let app_state = AppState::new(
+                        secret_auth.clone(),
+                        database.clone(),
+                        idle_provers,
+                        lease_duration,
+                    );
 
                     let auth = HttpAuthentication::bearer(move |req, credentials| async {
                         let secret_auth = req
@@ -450,9 +686,7 @@ pub fn run_prover_server<DB: DatabaseInterface>(
                             .expect("failed get AppState upon receipt of the authentication token")
                             .secret_auth
                             .clone();
-                        AuthTokenValidator::new(&secret_auth)
-                            .validator(req, credentials)
-                            .await
+                        validate_auth(&secret_auth, req, credentials).await
                     });
 
                     // By calling `register_data` instead of `data` we're avoiding double
@@ -469,6 +703,18 @@ pub fn run_prover_server<DB: DatabaseInterface>(
                             "/api/internal/prover/replicas",
                             web::post().to(required_replicas::<DB>),
                         )
+                        .route(
+                            "/api/internal/prover/fleet",
+                            web::get().to(fleet_status::<DB>),
+                        )
+                        .route(
+                            "/api/internal/prover/costs",
+                            web::get().to(costs_stats::<DB>),
+                        )
+                        .route(
+                            "/api/internal/prover/priority",
+                            web::post().to(set_block_job_priority::<DB>),
+                        )
                 })
                 .bind(&prover_api_opts.bind_addr())
                 .expect("failed to bind")
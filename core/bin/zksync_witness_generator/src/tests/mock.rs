@@ -1,17 +1,22 @@
 // Built-in
 use std::clone::Clone;
+use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 // External uses
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use tokio::sync::RwLock;
 use tokio::time::sleep;
 // Workspace uses
 use zksync_crypto::params::account_tree_depth;
 use zksync_crypto::proof::{AggregatedProof, SingleProof};
 use zksync_storage::chain::tree_cache::records::AccountTreeCache;
-use zksync_storage::prover::records::{StorageBlockWitness, StorageProverJobQueue, StoredProof};
+use zksync_storage::ethereum::records::StorageBlockVerificationCost;
+use zksync_storage::prover::records::{
+    StorageBlockWitness, StorageConnectedProver, StorageJobQueueDepth, StorageProofGenerationTime,
+    StorageProverJobQueue, StorageProvingTime, StoredProof,
+};
 use zksync_storage::StorageProcessor;
 use zksync_types::{
     aggregated_operations::{AggregatedActionType, AggregatedOperation},
@@ -31,6 +36,9 @@ pub struct MockDatabase {
     blocks: Arc<RwLock<Vec<Block>>>,
     account_tree_cache: Arc<RwLock<AccountTreeCache>>,
     accounts_state: Arc<RwLock<(u32, AccountMap)>>,
+    witness_generator_progress: Arc<RwLock<HashMap<u32, BlockNumber>>>,
+    block_priorities: Arc<RwLock<HashMap<BlockNumber, i32>>>,
+    prover_heartbeats: Arc<RwLock<HashMap<String, DateTime<Utc>>>>,
 }
 
 impl MockDatabase {
@@ -49,6 +57,9 @@ impl MockDatabase {
                 tree_cache_binary,
             })),
             accounts_state: Arc::new(RwLock::new((0, accounts))),
+            witness_generator_progress: Arc::new(RwLock::new(HashMap::new())),
+            block_priorities: Arc::new(RwLock::new(HashMap::new())),
+            prover_heartbeats: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
@@ -75,6 +86,17 @@ impl MockDatabase {
     pub async fn add_block(&self, block: Block) {
         self.blocks.write().await.push(block);
     }
+
+    /// Returns the highest priority set for the blocks of the job, if any.
+    async fn job_priority(&self, first_block: i64, last_block: i64) -> Option<i32> {
+        self.block_priorities
+            .read()
+            .await
+            .iter()
+            .filter(|(block, _)| (first_block..=last_block).contains(&i64::from(***block)))
+            .map(|(_, priority)| *priority)
+            .min()
+    }
 }
 
 #[async_trait::async_trait]
@@ -94,6 +116,10 @@ impl DatabaseInterface for MockDatabase {
         job_priority: i32,
         job_type: ProverJobType,
     ) -> anyhow::Result<()> {
+        let job_priority = self
+            .job_priority(i64::from(*first_block), i64::from(*last_block))
+            .await
+            .unwrap_or(job_priority);
         let mut prover_job_queue = self.prover_job_queue.write().await;
         let id = prover_job_queue.0;
         (*prover_job_queue).0 += 1;
@@ -109,6 +135,10 @@ impl DatabaseInterface for MockDatabase {
             id,
             job_priority,
             job_data,
+            leased_by: None,
+            lease_expires_at: None,
+            lease_count: 0,
+            leased_at: None,
         };
 
         prover_job_queue.1.push(new_job);
@@ -171,15 +201,23 @@ impl DatabaseInterface for MockDatabase {
         Ok(single_proof)
     }
 
-    async fn mark_stale_jobs_as_idle(&self, _: &mut StorageProcessor<'_>) -> anyhow::Result<()> {
+    async fn release_expired_prover_job_leases(
+        &self,
+        _: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<()> {
         let now = Utc::now();
         let prover_job_queue = &mut self.prover_job_queue.write().await.1;
 
         for job in prover_job_queue.iter_mut() {
-            if now - job.updated_at > chrono::Duration::seconds(10) {
+            let is_expired = job
+                .lease_expires_at
+                .map_or(true, |expires_at| expires_at <= now);
+            if job.job_status == ProverJobStatus::InProgress.to_number() && is_expired {
                 job.job_status = ProverJobStatus::Idle.to_number();
                 job.updated_at = now;
-                job.updated_by = "server_clean_idle".to_string();
+                job.updated_by = "server_release_lease".to_string();
+                job.leased_by = None;
+                job.lease_expires_at = None;
             }
         }
 
@@ -221,9 +259,11 @@ impl DatabaseInterface for MockDatabase {
         Ok(Some(result))
     }
 
-    async fn load_idle_prover_job_from_job_queue(
+    async fn lease_prover_job(
         &self,
         _: &mut StorageProcessor<'_>,
+        prover_name: &str,
+        lease_duration: Duration,
     ) -> anyhow::Result<Option<ProverJob>> {
         let prover_job_queue = &mut self.prover_job_queue.write().await.1;
         let idle_prover_job = prover_job_queue
@@ -235,6 +275,10 @@ impl DatabaseInterface for MockDatabase {
             job.job_status = ProverJobStatus::InProgress.to_number();
             job.updated_at = Utc::now();
             job.updated_by = "server_give_job".to_string();
+            job.leased_by = Some(prover_name.to_string());
+            job.leased_at = Some(Utc::now());
+            job.lease_expires_at = Some(Utc::now() + chrono::Duration::from_std(lease_duration)?);
+            job.lease_count += 1;
 
             Some(ProverJob::new(
                 job.id,
@@ -249,23 +293,190 @@ impl DatabaseInterface for MockDatabase {
         Ok(prover_job)
     }
 
-    async fn record_prover_is_working(
+    async fn renew_prover_job_lease(
         &self,
         _: &mut StorageProcessor<'_>,
         job_id: i32,
         prover_name: &str,
-    ) -> anyhow::Result<()> {
+        lease_duration: Duration,
+    ) -> anyhow::Result<bool> {
         let prover_job_queue = &mut self.prover_job_queue.write().await.1;
-        let prover_job = prover_job_queue.iter_mut().find(|job| job.id == job_id);
+        let prover_job = prover_job_queue.iter_mut().find(|job| {
+            job.id == job_id
+                && job.leased_by.as_deref() == Some(prover_name)
+                && job.job_status == ProverJobStatus::InProgress.to_number()
+        });
 
         if let Some(job) = prover_job {
             job.updated_at = Utc::now();
             job.updated_by = prover_name.to_string();
+            job.lease_expires_at = Some(Utc::now() + chrono::Duration::from_std(lease_duration)?);
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    async fn set_block_job_priority(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        block_number: BlockNumber,
+        job_priority: i32,
+    ) -> anyhow::Result<()> {
+        self.block_priorities
+            .write()
+            .await
+            .insert(block_number, job_priority);
+
+        let mut prover_job_queue = self.prover_job_queue.write().await;
+        for job in prover_job_queue.1.iter_mut() {
+            let covers_block =
+                (job.first_block..=job.last_block).contains(&i64::from(*block_number));
+            if covers_block && job.job_status != ProverJobStatus::Done.to_number() {
+                job.job_priority = self
+                    .job_priority(job.first_block, job.last_block)
+                    .await
+                    .unwrap_or(job_priority);
+            }
         }
 
         Ok(())
     }
 
+    async fn record_prover_heartbeat(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        prover_name: &str,
+    ) -> anyhow::Result<()> {
+        self.prover_heartbeats
+            .write()
+            .await
+            .insert(prover_name.to_string(), Utc::now());
+        Ok(())
+    }
+
+    async fn load_connected_provers(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        timeout: Duration,
+    ) -> anyhow::Result<Vec<StorageConnectedProver>> {
+        let since = Utc::now() - chrono::Duration::from_std(timeout)?;
+        let prover_job_queue = &self.prover_job_queue.read().await.1;
+        let mut provers: Vec<_> = self
+            .prover_heartbeats
+            .read()
+            .await
+            .iter()
+            .filter(|(_, last_seen_at)| **last_seen_at >= since)
+            .map(|(prover_name, last_seen_at)| {
+                let job = prover_job_queue.iter().find(|job| {
+                    job.leased_by.as_ref() == Some(prover_name)
+                        && job.job_status == ProverJobStatus::InProgress.to_number()
+                });
+                StorageConnectedProver {
+                    prover_name: prover_name.clone(),
+                    last_seen_at: *last_seen_at,
+                    job_id: job.map(|job| job.id),
+                    job_type: job.map(|job| job.job_type.clone()),
+                    first_block: job.map(|job| job.first_block),
+                    last_block: job.map(|job| job.last_block),
+                    leased_at: job.and_then(|job| job.leased_at),
+                }
+            })
+            .collect();
+        provers.sort_by(|a, b| a.prover_name.cmp(&b.prover_name));
+
+        Ok(provers)
+    }
+
+    async fn load_proving_times(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        period: Duration,
+    ) -> anyhow::Result<Vec<StorageProvingTime>> {
+        let since = Utc::now() - chrono::Duration::from_std(period)?;
+        let blocks = self.blocks.read().await;
+        let mut proving_times: HashMap<i64, Vec<f64>> = HashMap::new();
+        for job in self.prover_job_queue.read().await.1.iter() {
+            let is_proven = job.job_type == ProverJobType::SingleProof.to_string()
+                && job.job_status == ProverJobStatus::Done.to_number()
+                && job.updated_at >= since;
+            let block = blocks
+                .iter()
+                .find(|block| i64::from(*block.block_number) == job.first_block);
+            if let (true, Some(leased_at), Some(block)) = (is_proven, job.leased_at, block) {
+                let secs = (job.updated_at - leased_at).num_milliseconds() as f64 / 1000.0;
+                proving_times
+                    .entry(block.block_chunks_size as i64)
+                    .or_default()
+                    .push(secs);
+            }
+        }
+
+        let mut proving_times: Vec<_> = proving_times
+            .into_iter()
+            .map(|(block_size, times)| StorageProvingTime {
+                block_size,
+                proofs_count: times.len() as i64,
+                average_secs: times.iter().sum::<f64>() / times.len() as f64,
+                max_secs: times.iter().cloned().fold(0.0, f64::max),
+            })
+            .collect();
+        proving_times.sort_by_key(|time| time.block_size);
+
+        Ok(proving_times)
+    }
+
+    async fn load_proof_generation_times(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        _: Duration,
+    ) -> anyhow::Result<Vec<StorageProofGenerationTime>> {
+        Ok(Vec::new())
+    }
+
+    async fn load_block_verification_costs(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        _: Duration,
+    ) -> anyhow::Result<Vec<StorageBlockVerificationCost>> {
+        Ok(Vec::new())
+    }
+
+    async fn load_job_queue_depth(
+        &self,
+        _: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<Vec<StorageJobQueueDepth>> {
+        let mut queue_depth: Vec<StorageJobQueueDepth> = Vec::new();
+        for job in self.prover_job_queue.read().await.1.iter() {
+            if job.job_status == ProverJobStatus::Done.to_number() {
+                continue;
+            }
+            let position = match queue_depth
+                .iter()
+                .position(|depth| depth.job_type == job.job_type)
+            {
+                Some(position) => position,
+                None => {
+                    queue_depth.push(StorageJobQueueDepth {
+                        job_type: job.job_type.clone(),
+                        idle_jobs: 0,
+                        in_progress_jobs: 0,
+                    });
+                    queue_depth.len() - 1
+                }
+            };
+            if job.job_status == ProverJobStatus::Idle.to_number() {
+                queue_depth[position].idle_jobs += 1;
+            } else {
+                queue_depth[position].in_progress_jobs += 1;
+            }
+        }
+        queue_depth.sort_by(|a, b| a.job_type.cmp(&b.job_type));
+
+        Ok(queue_depth)
+    }
+
     async fn store_proof(
         &self,
         _: &mut StorageProcessor<'_>,
@@ -310,13 +521,16 @@ impl DatabaseInterface for MockDatabase {
         let prover_job_queue = &mut self.prover_job_queue.write().await.1;
 
         for job in prover_job_queue.iter_mut() {
-            if job.updated_by == prover_name
+            if job.leased_by.as_deref() == Some(prover_name)
                 && job.job_status == ProverJobStatus::InProgress.to_number()
             {
                 job.job_status = ProverJobStatus::Idle.to_number();
                 job.updated_at = Utc::now();
+                job.leased_by = None;
+                job.lease_expires_at = None;
             }
         }
+        self.prover_heartbeats.write().await.remove(prover_name);
 
         Ok(())
     }
@@ -394,4 +608,26 @@ impl DatabaseInterface for MockDatabase {
 
         Ok(())
     }
+
+    async fn load_witness_generator_progress(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        shard_index: u32,
+    ) -> anyhow::Result<Option<BlockNumber>> {
+        let progress = self.witness_generator_progress.read().await;
+        Ok(progress.get(&shard_index).copied())
+    }
+
+    async fn store_witness_generator_progress(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        shard_index: u32,
+        last_block: BlockNumber,
+    ) -> anyhow::Result<()> {
+        self.witness_generator_progress
+            .write()
+            .await
+            .insert(shard_index, last_block);
+        Ok(())
+    }
 }
@@ -47,6 +47,8 @@ impl Default for MockProverOptions {
             witness_generator: WitnessGenerator {
                 prepare_data_interval: 500,
                 witness_generators: 1,
+                shard_index: 0,
+                shards_count: 1,
             },
         };
 
@@ -1,8 +1,24 @@
+//! Witness generator preparing the prover data of the committed blocks.
+//!
+//! The block is the unit of the work: the witnesses of its operations depend on the account tree
+//! state left by the previous ones, so they are built sequentially, while the tree hashing is
+//! already parallelized by the parallel sparse Merkle tree. Every block only needs the tree state
+//! after the previous block, which is restored from the cache and the state diff, so the witnesses
+//! of the different blocks are generated concurrently by a bounded amount of the workers.
+//!
+//! The blocks can also be split between several witness generators (e.g. on the different machines)
+//! by sharding: the generator only handles the blocks with `block_number % shards_count == shard_index`.
+//! Every shard persists the last block up to which all of its witnesses are generated, so it resumes
+//! from this block after the restart instead of the last verified one.
+
 use std::time::Instant;
 // Built-in
+use std::collections::{BTreeSet, VecDeque};
 use std::{thread, time};
 // External
 use futures::channel::mpsc;
+use futures::stream::{FuturesUnordered, StreamExt};
+use tokio::task::JoinHandle;
 use tokio::time::sleep;
 use zksync_crypto::merkle_tree::parallel_smt::SparseMerkleTreeSerializableCacheBN256;
 // Workspace deps
@@ -15,19 +31,87 @@ use zksync_types::block::Block;
 use zksync_types::BlockNumber;
 use zksync_utils::panic_notify::ThreadPanicNotify;
 
+/// Subset of the blocks handled by the witness generator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WitnessGeneratorShard {
+    /// Index of the shard, the remainder of the division of its block numbers by `count`.
+    pub index: u32,
+    /// Total amount of the shards.
+    pub count: u32,
+}
+
+impl WitnessGeneratorShard {
+    pub fn new(index: u32, count: u32) -> Self {
+        assert!(count > 0, "Witness generator shards count must be positive");
+        assert!(
+            index < count,
+            "Witness generator shard index must be less than the shards count"
+        );
+        Self { index, count }
+    }
+
+    /// Returns the first block of the shard after the given block.
+    fn next_block(&self, block: BlockNumber) -> BlockNumber {
+        let next = *block + 1;
+        let offset = (self.index + self.count - next % self.count) % self.count;
+        BlockNumber(next + offset)
+    }
+}
+
+/// Tracks the blocks of the shard whose witnesses are generated to find the last block
+/// up to which all the witnesses of the shard are generated.
+#[derive(Debug)]
+struct ShardProgress {
+    shard: WitnessGeneratorShard,
+    last_block: BlockNumber,
+    /// Blocks after the `last_block` whose witnesses are generated.
+    completed: BTreeSet<BlockNumber>,
+}
+
+impl ShardProgress {
+    fn new(shard: WitnessGeneratorShard, last_block: BlockNumber) -> Self {
+        Self {
+            shard,
+            last_block,
+            completed: BTreeSet::new(),
+        }
+    }
+
+    /// Reports the block whose witness is generated.
+    /// Returns `true` if the last block of the progress has advanced.
+    fn complete(&mut self, block: BlockNumber) -> bool {
+        if block <= self.last_block {
+            return false;
+        }
+        self.completed.insert(block);
+
+        let mut advanced = false;
+        while self
+            .completed
+            .remove(&self.shard.next_block(self.last_block))
+        {
+            self.last_block = self.shard.next_block(self.last_block);
+            advanced = true;
+        }
+        advanced
+    }
+}
+
 /// The essential part of this structure is `maintain` function
 /// which runs forever and adds data to the database.
 ///
-/// This will generate and store in db witnesses for blocks with indexes
-/// start_block, start_block + block_step, start_block + 2*block_step, ...
+/// This will generate and store in db witnesses for the blocks of the shard,
+/// processing up to `workers` blocks at the same time.
+#[derive(Clone)]
 pub struct WitnessGenerator<DB: DatabaseInterface> {
     /// Connection to the database.
     database: DB,
     /// Routine refresh interval.
     rounds_interval: time::Duration,
+    /// Maximum amount of the blocks processed concurrently.
+    workers: usize,
 
-    start_block: BlockNumber,
-    block_step: BlockNumber,
+    shard: WitnessGeneratorShard,
 }
 
 #[derive(Debug)]
@@ -42,14 +126,15 @@ impl<DB: DatabaseInterface> WitnessGenerator<DB> {
     pub fn new(
         database: DB,
         rounds_interval: time::Duration,
-        start_block: BlockNumber,
-        block_step: BlockNumber,
+        workers: usize,
+        shard: WitnessGeneratorShard,
     ) -> Self {
+        assert!(workers > 0, "Witness generator needs at least one worker");
         Self {
             database,
             rounds_interval,
-            start_block,
-            block_step,
+            workers,
+            shard,
         }
     }
 
@@ -224,28 +309,73 @@ impl<DB: DatabaseInterface> WitnessGenerator<DB> {
         let mut circuit_account_tree = self.load_account_tree(block.block_number - 1).await?;
         metrics::histogram!("witness_generator", start.elapsed(), "stage" => "load_tree_full");
 
-        let start = Instant::now();
-        let witness: ProverData = build_block_witness(&mut circuit_account_tree, &block)?.into();
-        metrics::histogram!("witness_generator", start.elapsed(), "stage" => "build_witness");
+        // Building the witness is CPU-bound, so it shouldn't block the runtime running the other workers.
+        let block_number = block.block_number;
+        let witness = tokio::task::spawn_blocking(move || -> anyhow::Result<serde_json::Value> {
+            let start = Instant::now();
+            let witness: ProverData =
+                build_block_witness(&mut circuit_account_tree, &block)?.into();
+            metrics::histogram!("witness_generator", start.elapsed(), "stage" => "build_witness");
+
+            Ok(serde_json::to_value(witness).expect("Witness serialize to json"))
+        })
+        .await
+        .unwrap_or_else(|err| std::panic::resume_unwind(err.into_panic()))?;
 
         let start = Instant::now();
         self.database
-            .store_witness(
-                &mut storage,
-                block.block_number,
-                serde_json::to_value(witness).expect("Witness serialize to json"),
-            )
+            .store_witness(&mut storage, block_number, witness)
             .await?;
         metrics::histogram!("witness_generator", start.elapsed(), "stage" => "store_witness");
 
         metrics::histogram!("witness_generator", fn_start.elapsed(), "stage" => "prepare_witness_and_save_it");
+        Ok(())
+    }
+
+    /// Returns the last block up to which all the witnesses of the shard are generated. The witnesses
+    /// of the verified blocks aren't needed anymore, so the progress starts from the last verified block.
+    async fn load_progress(&self) -> anyhow::Result<BlockNumber> {
+        let mut storage = self.database.acquire_connection().await?;
+        let last_verified_block = self.database.load_last_verified_block(&mut storage).await?;
+        let last_block = self
+            .database
+            .load_witness_generator_progress(&mut storage, self.shard.index)
+            .await?;
+
+        Ok(last_block.map_or(last_verified_block, |block| block.max(last_verified_block)))
+    }
+
+    /// Reports the block whose witness is generated and persists the progress of the shard if it has advanced.
+    async fn report_witness(&self, progress: &mut ShardProgress, block_number: BlockNumber) {
+        if !progress.complete(block_number) {
+            return;
+        }
+
+        let result = async {
+            let mut storage = self.database.acquire_connection().await?;
+            self.database
+                .store_witness_generator_progress(
+                    &mut storage,
+                    self.shard.index,
+                    progress.last_block,
+                )
+                .await
+        }
+        .await;
+        if let Err(err) = result {
+            // Not critical: the progress is stored again once the next block is processed.
+            vlog::warn!(
+                "Failed to store the progress of the witness generator shard {}: {}",
+                self.shard.index,
+                err
+            );
+        }
 
         metrics::gauge!(
             "last_processed_block",
-            block.block_number.0 as f64,
+            progress.last_block.0 as f64,
             "stage" => "witness_generator"
         );
-        Ok(())
     }
 
     /// Returns next block for generating witness
@@ -262,13 +392,14 @@ impl<DB: DatabaseInterface> WitnessGenerator<DB> {
         }
     }
 
-    /// Updates witness data in database in an infinite loop,
-    /// awaiting `rounds_interval` time between updates.
+    /// Updates witness data in database in an infinite loop. The new blocks are scheduled once
+    /// any of the workers is free, or every `rounds_interval` if none of the blocks is ready.
     async fn maintain(self) {
         vlog::info!(
-            "preparing prover data routine started with start_block({}), block_step({})",
-            *self.start_block,
-            *self.block_step
+            "preparing prover data routine started for the shard({}) of shards_count({}) with {} workers",
+            self.shard.index,
+            self.shard.count,
+            self.workers
         );
 
         // Initialize counters for cache hits/misses.
@@ -276,29 +407,85 @@ impl<DB: DatabaseInterface> WitnessGenerator<DB> {
         metrics::register_counter!("witness_generator.cache_access", "type" => "off_by_1");
         metrics::register_counter!("witness_generator.cache_access", "type" => "miss");
 
-        let mut current_block = self.start_block;
-        loop {
-            sleep(self.rounds_interval).await;
-            let should_work = match self.should_work_on_block(current_block).await {
-                Ok(should_work) => should_work,
+        let last_block = loop {
+            match self.load_progress().await {
+                Ok(last_block) => break last_block,
                 Err(err) => {
-                    vlog::warn!("witness for block {} check failed: {}", current_block, err);
-                    continue;
+                    vlog::warn!("Failed to load the witness generator progress: {}", err);
+                    sleep(self.rounds_interval).await;
+                }
+            }
+        };
+        let mut progress = ShardProgress::new(self.shard, last_block);
+        let block_step = BlockNumber(self.shard.count);
+        let mut current_block = self.shard.next_block(last_block);
+
+        // Blocks whose witness generation has failed, they are retried before the new blocks.
+        let mut failed_blocks = VecDeque::new();
+        let mut jobs: FuturesUnordered<JoinHandle<(BlockNumber, anyhow::Result<()>)>> =
+            FuturesUnordered::new();
+        loop {
+            while jobs.len() < self.workers {
+                let (block_number, is_retry) = match failed_blocks.pop_front() {
+                    Some(block_number) => (block_number, true),
+                    None => (current_block, false),
+                };
+                let block_info = match self.should_work_on_block(block_number).await {
+                    Ok(block_info) => block_info,
+                    Err(err) => {
+                        vlog::warn!("witness for block {} check failed: {}", block_number, err);
+                        if is_retry {
+                            failed_blocks.push_front(block_number);
+                        }
+                        break;
+                    }
+                };
+                if !is_retry {
+                    current_block =
+                        Self::next_witness_block(current_block, block_step, &block_info);
                 }
-            };
 
-            let next_block = Self::next_witness_block(current_block, self.block_step, &should_work);
-            if let BlockInfo::NoWitness(block) = should_work {
-                let block_number = block.block_number;
-                if let Err(err) = self.prepare_witness_and_save_it(block).await {
-                    vlog::warn!("Witness generator ({},{}) failed to prepare witness for block: {}, err: {}",
-                        self.start_block, self.block_step, block_number, err);
-                    continue; // Retry the same block on the next iteration.
+                match block_info {
+                    BlockInfo::NotReadyBlock => break,
+                    BlockInfo::WithWitness => {
+                        self.report_witness(&mut progress, block_number).await
+                    }
+                    BlockInfo::NoWitness(block) => {
+                        let generator = self.clone();
+                        jobs.push(tokio::spawn(async move {
+                            let result = generator.prepare_witness_and_save_it(block).await;
+                            (block_number, result)
+                        }));
+                    }
                 }
             }
+            metrics::gauge!("witness_generator.jobs_in_progress", jobs.len() as f64);
 
-            // Update current block.
-            current_block = next_block;
+            let finished_job = if jobs.is_empty() {
+                sleep(self.rounds_interval).await;
+                None
+            } else {
+                tokio::select! {
+                    job = jobs.next() => job,
+                    _ = sleep(self.rounds_interval) => None,
+                }
+            };
+            match finished_job {
+                Some(Ok((block_number, Ok(())))) => {
+                    self.report_witness(&mut progress, block_number).await
+                }
+                Some(Ok((block_number, Err(err)))) => {
+                    vlog::warn!(
+                        "Witness generator shard({}) failed to prepare witness for block: {}, err: {}",
+                        self.shard.index,
+                        block_number,
+                        err
+                    );
+                    failed_blocks.push_back(block_number);
+                }
+                Some(Err(err)) => std::panic::resume_unwind(err.into_panic()),
+                None => {}
+            }
         }
     }
 }
@@ -310,6 +497,37 @@ mod tests {
     use zksync_crypto::Fr;
     use zksync_types::{AccountId, H256, U256};
 
+    #[test]
+    fn shard_next_block() {
+        let shard = WitnessGeneratorShard::new(1, 3);
+        assert_eq!(shard.next_block(BlockNumber(0)), BlockNumber(1));
+        assert_eq!(shard.next_block(BlockNumber(1)), BlockNumber(4));
+        assert_eq!(shard.next_block(BlockNumber(2)), BlockNumber(4));
+        assert_eq!(shard.next_block(BlockNumber(3)), BlockNumber(4));
+
+        let single_shard = WitnessGeneratorShard::new(0, 1);
+        assert_eq!(single_shard.next_block(BlockNumber(5)), BlockNumber(6));
+    }
+
+    #[test]
+    fn shard_progress() {
+        let mut progress = ShardProgress::new(WitnessGeneratorShard::new(0, 2), BlockNumber(3));
+
+        // The witnesses generated out of order don't advance the progress until the gap is filled.
+        assert!(!progress.complete(BlockNumber(6)));
+        assert!(!progress.complete(BlockNumber(10)));
+        assert_eq!(progress.last_block, BlockNumber(3));
+        assert!(progress.complete(BlockNumber(4)));
+        assert_eq!(progress.last_block, BlockNumber(6));
+        assert!(progress.complete(BlockNumber(8)));
+        assert_eq!(progress.last_block, BlockNumber(10));
+
+        // The blocks before the progress are ignored.
+        assert!(!progress.complete(BlockNumber(2)));
+        assert_eq!(progress.last_block, BlockNumber(10));
+        assert!(progress.completed.is_empty());
+    }
+
     #[test]
     fn test_next_witness_block() {
         assert_eq!(
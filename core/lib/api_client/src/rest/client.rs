@@ -1,12 +1,15 @@
 //! Built-in API client.
 
+// Built-in uses
+use std::time::Duration;
+
 // External uses
 use reqwest::StatusCode;
 use serde::{de::DeserializeOwned, ser::Serialize};
 use thiserror::Error;
 
 // Local uses
-use super::error::ErrorBody;
+use super::{error::ErrorBody, v02::ApiError};
 
 pub type Result<T> = std::result::Result<T, ClientError>;
 
@@ -24,6 +27,20 @@ pub enum ClientError {
     Other(reqwest::Error),
     #[error("Method {0} not found")]
     NotFound(String),
+    #[error("API error {}: {}", .0.code, .0.message)]
+    Api(ApiError),
+    #[error("Cannot deserialize the result: {0}")]
+    Deserialize(serde_json::Error),
+}
+
+impl ClientError {
+    /// Returns the code of the API error, if the request has failed with it.
+    pub fn api_error_code(&self) -> Option<u16> {
+        match self {
+            Self::Api(error) => Some(error.code),
+            _ => None,
+        }
+    }
 }
 
 impl From<reqwest::Error> for ClientError {
@@ -32,22 +49,68 @@ impl From<reqwest::Error> for ClientError {
     }
 }
 
+/// Policy of retrying the requests failed for the transient reasons: the connection errors,
+/// and also the timeouts and the `429`, `502` and `504` responses of the idempotent requests.
+/// The delay before every next retry is doubled.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// The failed requests are not retried.
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the delay before the retry with the given index, starting from zero.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        self.initial_backoff
+            .checked_mul(2u32.pow(retry.min(16)))
+            .map_or(self.max_backoff, |backoff| backoff.min(self.max_backoff))
+    }
+}
+
 /// Client reference implementation for interacting with zkSync REST API v1.
 #[derive(Debug, Clone)]
 pub struct Client {
     inner: reqwest::Client,
     url: String,
+    retry_policy: RetryPolicy,
 }
 
 impl Client {
     /// Creates a new REST API client with the specified Url.
+    /// The failed requests are not retried unless the retry policy is set.
     pub fn new(url: String) -> Self {
         Self {
             inner: reqwest::Client::new(),
             url,
+            retry_policy: RetryPolicy::none(),
         }
     }
 
+    /// Sets the policy of retrying the requests failed for the transient reasons.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
     fn endpoint(&self, scope: &str, method: &str) -> String {
         [&self.url, scope, method].concat()
     }
@@ -61,6 +124,8 @@ impl Client {
         ClientRequestBuilder {
             inner: self.inner.get(&url),
             url,
+            retry_policy: self.retry_policy,
+            idempotent: true,
         }
     }
 
@@ -73,6 +138,8 @@ impl Client {
         ClientRequestBuilder {
             inner: self.inner.post(&url),
             url,
+            retry_policy: self.retry_policy,
+            idempotent: false,
         }
     }
 }
@@ -82,6 +149,9 @@ impl Client {
 pub struct ClientRequestBuilder {
     inner: reqwest::RequestBuilder,
     url: String,
+    retry_policy: RetryPolicy,
+    /// Whether the request may be retried after it has reached the server.
+    idempotent: bool,
 }
 
 impl ClientRequestBuilder {
@@ -93,7 +163,19 @@ impl ClientRequestBuilder {
     pub fn query<Q: Serialize + ?Sized>(self, query: &Q) -> Self {
         Self {
             inner: self.inner.query(query),
-            url: self.url,
+            ..self
+        }
+    }
+
+    /// Add a header to the request.
+    ///
+    /// See [reqwest] documentation for details
+    ///
+    /// [reqwest]: https://docs.rs/reqwest/latest/reqwest/struct.RequestBuilder.html#method.header
+    pub fn header(self, name: &str, value: &str) -> Self {
+        Self {
+            inner: self.inner.header(name, value),
+            ..self
         }
     }
 
@@ -105,7 +187,41 @@ impl ClientRequestBuilder {
     pub fn body<B: Serialize + ?Sized>(self, body: &B) -> Self {
         Self {
             inner: self.inner.json(body),
-            url: self.url,
+            ..self
+        }
+    }
+
+    /// Returns `true` if the attempt of the request has failed for the transient reason.
+    fn is_transient_failure(&self, response: &reqwest::Result<reqwest::Response>) -> bool {
+        match response {
+            Ok(response) => {
+                self.idempotent
+                    && matches!(
+                        response.status(),
+                        StatusCode::TOO_MANY_REQUESTS
+                            | StatusCode::BAD_GATEWAY
+                            | StatusCode::GATEWAY_TIMEOUT
+                    )
+            }
+            Err(err) => err.is_connect() || (self.idempotent && err.is_timeout()),
+        }
+    }
+
+    /// Sends the request retrying it according to the retry policy of the client.
+    async fn send_with_retries(self) -> self::Result<(reqwest::Response, String)> {
+        let mut retry = 0;
+        loop {
+            let request = match self.inner.try_clone() {
+                Some(request) if retry < self.retry_policy.max_retries => request,
+                // The last attempt, or the request can't be retried.
+                _ => return Ok((self.inner.send().await?, self.url)),
+            };
+            let response = request.send().await;
+            if !self.is_transient_failure(&response) {
+                return Ok((response?, self.url));
+            }
+            tokio::time::sleep(self.retry_policy.backoff(retry)).await;
+            retry += 1;
         }
     }
 
@@ -113,14 +229,38 @@ impl ClientRequestBuilder {
     ///
     /// This method takes account of the responses structure and the error handling specific.
     pub async fn send<T: DeserializeOwned>(self) -> self::Result<T> {
-        let response = self.inner.send().await?;
+        let (response, url) = self.send_with_retries().await?;
 
         let status = response.status();
-        if status.is_success() {
+        // The submissions rejected in the read-only mode are answered with
+        // `503 Service Unavailable`, but the body is the usual API response.
+        if status.is_success() || status == StatusCode::SERVICE_UNAVAILABLE {
             Ok(response.json().await.map_err(ClientError::Parse)?)
         } else {
             if status == StatusCode::NOT_FOUND {
-                return Err(ClientError::NotFound(self.url));
+                return Err(ClientError::NotFound(url));
+            }
+
+            Err(ClientError::BadRequest {
+                http_code: status,
+                body: response.json().await.map_err(ClientError::Parse)?,
+            })
+        }
+    }
+
+    /// Constructs the Request and sends it to the target URL, returning the whole body
+    /// of the successful response as the text, e.g. the exported CSV.
+    ///
+    /// The errors are handled the same way as in the `send` method.
+    pub async fn send_text(self) -> self::Result<String> {
+        let (response, url) = self.send_with_retries().await?;
+
+        let status = response.status();
+        if status.is_success() {
+            Ok(response.text().await?)
+        } else {
+            if status == StatusCode::NOT_FOUND {
+                return Err(ClientError::NotFound(url));
             }
 
             Err(ClientError::BadRequest {
@@ -130,3 +270,23 @@ impl ClientRequestBuilder {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_backoff() {
+        let policy = RetryPolicy {
+            max_retries: 10,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(1),
+        };
+        assert_eq!(policy.backoff(0), Duration::from_millis(100));
+        assert_eq!(policy.backoff(1), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(800));
+        assert_eq!(policy.backoff(4), Duration::from_secs(1));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(1));
+        assert_eq!(RetryPolicy::none().max_retries, 0);
+    }
+}
@@ -1,10 +1,11 @@
 use crate::rest::client::{Client, Result};
 
 use zksync_api_types::v02::{
-    pagination::{ApiEither, PaginationQuery},
+    account::{IncomingAccountProofQuery, IncomingExitProofQuery},
+    pagination::{AccountTxsFilters, ApiEither, PaginationQuery, PaginationQueryOrCursor},
     Response,
 };
-use zksync_types::{tx::TxHash, SerialId};
+use zksync_types::{tx::TxHash, BlockNumber, SerialId};
 
 impl Client {
     pub async fn account_info(
@@ -29,6 +30,64 @@ impl Client {
         .await
     }
 
+    pub async fn account_state_at_block(
+        &self,
+        account_id_or_address: &str,
+        block_number: BlockNumber,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!(
+                "accounts/{}/state_at/{}",
+                account_id_or_address, *block_number
+            ),
+        )
+        .send()
+        .await
+    }
+
+    pub async fn account_proof(
+        &self,
+        account_id_or_address: &str,
+        query: &IncomingAccountProofQuery,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/proof", account_id_or_address),
+        )
+        .query(query)
+        .send()
+        .await
+    }
+
+    pub async fn request_exit_proof(
+        &self,
+        account_id_or_address: &str,
+        query: &IncomingExitProofQuery,
+    ) -> Result<Response> {
+        self.post_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/exit_proof", account_id_or_address),
+        )
+        .query(query)
+        .send()
+        .await
+    }
+
+    pub async fn exit_proof(
+        &self,
+        account_id_or_address: &str,
+        query: &IncomingExitProofQuery,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/exit_proof", account_id_or_address),
+        )
+        .query(query)
+        .send()
+        .await
+    }
+
     pub async fn account_txs(
         &self,
         pagination_query: &PaginationQuery<ApiEither<TxHash>>,
@@ -43,6 +102,75 @@ impl Client {
         .await
     }
 
+    pub async fn account_txs_filtered(
+        &self,
+        pagination_query: &PaginationQuery<ApiEither<TxHash>>,
+        account_id_or_address: &str,
+        filters: &AccountTxsFilters,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/transactions", account_id_or_address),
+        )
+        .query(&pagination_query)
+        .query(filters)
+        .send()
+        .await
+    }
+
+    /// Exports the whole account history matching the filters, `accept` is either
+    /// `text/csv` or `application/x-ndjson`.
+    pub async fn account_txs_export(
+        &self,
+        account_id_or_address: &str,
+        accept: &str,
+        filters: &AccountTxsFilters,
+    ) -> Result<String> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/transactions", account_id_or_address),
+        )
+        .header("Accept", accept)
+        .query(filters)
+        .send_text()
+        .await
+    }
+
+    pub async fn account_txs_by_cursor(
+        &self,
+        cursor: &str,
+        account_id_or_address: &str,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/transactions", account_id_or_address),
+        )
+        .query(&PaginationQueryOrCursor::from_cursor(cursor.to_string()))
+        .send()
+        .await
+    }
+
+    pub async fn account_queued_txs(&self, account_id_or_address: &str) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/pending", account_id_or_address),
+        )
+        .send()
+        .await
+    }
+
+    pub async fn account_unconfirmed_deposits(
+        &self,
+        account_id_or_address: &str,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/deposits/unconfirmed", account_id_or_address),
+        )
+        .send()
+        .await
+    }
+
     pub async fn account_pending_txs(
         &self,
         pagination_query: &PaginationQuery<ApiEither<SerialId>>,
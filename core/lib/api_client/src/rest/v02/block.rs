@@ -1,7 +1,7 @@
 use crate::rest::client::{Client, Result};
 
 use zksync_api_types::v02::{
-    pagination::{ApiEither, PaginationQuery},
+    pagination::{ApiEither, PaginationQuery, PaginationQueryOrCursor},
     Response,
 };
 use zksync_types::{tx::TxHash, BlockNumber};
@@ -45,4 +45,25 @@ impl Client {
             .send()
             .await
     }
+
+    pub async fn block_pagination_by_cursor(&self, cursor: &str) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, "blocks")
+            .query(&PaginationQueryOrCursor::from_cursor(cursor.to_string()))
+            .send()
+            .await
+    }
+
+    pub async fn block_transactions_by_cursor(
+        &self,
+        cursor: &str,
+        block_position: &str,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("blocks/{}/transactions", block_position),
+        )
+        .query(&PaginationQueryOrCursor::from_cursor(cursor.to_string()))
+        .send()
+        .await
+    }
 }
@@ -0,0 +1,33 @@
+// Local uses
+use crate::rest::client::{Client, Result};
+use zksync_api_types::v02::{
+    create2::{Create2AddressRequest, Create2ValidationRequest},
+    Response,
+};
+use zksync_types::tx::ChangePubKey;
+
+impl Client {
+    /// Derives the address of the CREATE2 smart wallet.
+    pub async fn create2_address(&self, request: &Create2AddressRequest) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "create2/address")
+            .body(request)
+            .send()
+            .await
+    }
+
+    /// Validates the CREATE2 auth data of the `ChangePubKey` of the account.
+    pub async fn validate_create2(&self, request: &Create2ValidationRequest) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "create2/validate")
+            .body(request)
+            .send()
+            .await
+    }
+
+    /// Submits the `ChangePubKey` with the CREATE2 auth data.
+    pub async fn submit_create2_change_pubkey(&self, tx: &ChangePubKey) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "create2/change_pubkey")
+            .body(tx)
+            .send()
+            .await
+    }
+}
@@ -0,0 +1,10 @@
+use crate::rest::client::{Client, Result};
+use zksync_api_types::v02::Response;
+
+impl Client {
+    pub async fn error_codes(&self) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, "errors")
+            .send()
+            .await
+    }
+}
@@ -1,7 +1,7 @@
 // Local uses
 use crate::rest::client::{Client, Result};
 use zksync_api_types::v02::{
-    fee::{ApiTxFeeTypes, BatchFeeRequest, TxFeeRequest, TxInBatchFeeRequest},
+    fee::{ApiTxFeeTypes, BatchFeeRequest, FeeHistoryQuery, TxFeeRequest, TxInBatchFeeRequest},
     Response,
 };
 use zksync_types::{Address, TokenLike};
@@ -36,4 +36,11 @@ impl Client {
             .send()
             .await
     }
+
+    pub async fn fee_history(&self, query: &FeeHistoryQuery) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, "fee/history")
+            .query(query)
+            .send()
+            .await
+    }
 }
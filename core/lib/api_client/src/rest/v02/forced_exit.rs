@@ -0,0 +1,35 @@
+// Local uses
+use crate::rest::client::{Client, Result};
+use zksync_api_types::v02::{forced_exit::ForcedExitRequestBody, Response};
+use zksync_types::{Address, TokenLike};
+
+impl Client {
+    /// Estimates the fee of the ForcedExit of the token of the target account.
+    pub async fn estimate_forced_exit(
+        &self,
+        target: Address,
+        token: TokenLike,
+    ) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "forced_exit/estimate")
+            .body(&ForcedExitRequestBody { target, token })
+            .send()
+            .await
+    }
+
+    /// Requests the operator to submit the ForcedExit of the token of the target account.
+    pub async fn request_forced_exit(&self, target: Address, token: TokenLike) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "forced_exit/requests")
+            .body(&ForcedExitRequestBody { target, token })
+            .send()
+            .await
+    }
+
+    pub async fn forced_exit_request(&self, id: i64) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("forced_exit/requests/{}", id),
+        )
+        .send()
+        .await
+    }
+}
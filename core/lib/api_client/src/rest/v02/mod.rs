@@ -1,9 +1,51 @@
+// External uses
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+// Workspace uses
+use zksync_api_types::v02::Response;
+
+// Local uses
+use crate::rest::client::{ClientError, Result};
+
 pub mod account;
 pub mod block;
 pub mod config;
+pub mod create2;
+pub mod errors;
 pub mod fee;
+pub mod forced_exit;
+pub mod mempool;
+pub mod nft;
+pub mod order;
+pub mod search;
 pub mod status;
 pub mod token;
 pub mod transaction;
+pub mod typed;
 
 const API_V02_SCOPE: &str = "/api/v0.2/";
+
+/// Error returned in the `error` field of the response.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiError {
+    pub error_type: String,
+    /// Code of the error, see the `/errors` endpoint for the catalog of the codes.
+    pub code: u16,
+    pub message: String,
+    #[serde(default)]
+    pub details: Option<Value>,
+}
+
+/// Deserializes the result of the response, or returns the error of the response.
+/// The response without either of them has the `null` result.
+pub fn deserialize_result<T: DeserializeOwned>(response: Response) -> Result<T> {
+    match (response.result, response.error) {
+        (Some(result), _) => serde_json::from_value(result).map_err(ClientError::Deserialize),
+        (None, Some(error)) => Err(ClientError::Api(
+            serde_json::from_value(error).map_err(ClientError::Deserialize)?,
+        )),
+        (None, None) => serde_json::from_value(Value::Null).map_err(ClientError::Deserialize),
+    }
+}
@@ -0,0 +1,40 @@
+use crate::rest::client::{Client, Result};
+use zksync_api_types::v02::{token::NFTContentHashQuery, Response};
+use zksync_types::{tx::TxHash, TokenId, H256};
+
+impl Client {
+    pub async fn nft_info(&self, id: TokenId) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, &format!("nft/{}", id))
+            .send()
+            .await
+    }
+
+    pub async fn nft_owner(&self, id: TokenId) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, &format!("nft/{}/owner", id))
+            .send()
+            .await
+    }
+
+    pub async fn verify_nft_content_hash(
+        &self,
+        id: TokenId,
+        content_hash: H256,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("nft/{}/verify_content_hash", id),
+        )
+        .query(&NFTContentHashQuery { content_hash })
+        .send()
+        .await
+    }
+
+    pub async fn nft_id_by_mint_tx_hash(&self, tx_hash: TxHash) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("nft/id_by_tx_hash/{}", tx_hash.to_string()),
+        )
+        .send()
+        .await
+    }
+}
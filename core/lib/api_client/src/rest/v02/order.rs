@@ -0,0 +1,31 @@
+// Local uses
+use crate::rest::client::{Client, Result};
+use zksync_api_types::v02::{order::OrderQuoteRequest, Response};
+use zksync_types::Order;
+
+impl Client {
+    /// Validates the signed order against the committed state of its account.
+    pub async fn validate_order(&self, order: &Order) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "orders/validate")
+            .body(order)
+            .send()
+            .await
+    }
+
+    /// Quotes the swap of the amount with the ratio of the order, including the fee.
+    pub async fn quote_order(&self, request: &OrderQuoteRequest) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "orders/quote")
+            .body(request)
+            .send()
+            .await
+    }
+
+    pub async fn order_nonce_state(&self, account_id_or_address: &str) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("orders/nonce/{}", account_id_or_address),
+        )
+        .send()
+        .await
+    }
+}
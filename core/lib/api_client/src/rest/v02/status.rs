@@ -7,4 +7,16 @@ impl Client {
             .send()
             .await
     }
+
+    pub async fn price_providers_health(&self) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, "networkStatus/priceProviders")
+            .send()
+            .await
+    }
+
+    pub async fn network_stats(&self) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, "networkStats")
+            .send()
+            .await
+    }
 }
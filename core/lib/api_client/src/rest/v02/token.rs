@@ -1,9 +1,15 @@
 use crate::rest::client::{Client, Result};
+use num::BigUint;
 use zksync_api_types::v02::{
-    pagination::{ApiEither, PaginationQuery},
+    pagination::{ApiEither, PaginationQuery, PaginationQueryOrCursor},
+    token::{
+        FeeAcceptableBatchRequest, PriceHistoryQuery, TokenFilter, TokenIncludeQuery,
+        TokenPriceQuery, TokenRateQuery, TokensByIdsQuery,
+    },
     Response,
 };
 use zksync_types::{tx::TxHash, TokenId, TokenLike};
+use zksync_utils::BigUintSerdeWrapper;
 
 impl Client {
     pub async fn token_pagination(
@@ -16,12 +22,44 @@ impl Client {
             .await
     }
 
+    pub async fn token_pagination_by_cursor(&self, cursor: &str) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, "tokens")
+            .query(&PaginationQueryOrCursor::from_cursor(cursor.to_string()))
+            .send()
+            .await
+    }
+
+    pub async fn token_pagination_filtered(
+        &self,
+        pagination_query: &PaginationQuery<ApiEither<TokenId>>,
+        filter: &TokenFilter,
+        includes: &TokenIncludeQuery,
+    ) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, "tokens")
+            .query(&pagination_query)
+            .query(filter)
+            .query(includes)
+            .send()
+            .await
+    }
+
     pub async fn token_by_id(&self, token: &TokenLike) -> Result<Response> {
         self.get_with_scope(super::API_V02_SCOPE, &format!("tokens/{}", token))
             .send()
             .await
     }
 
+    pub async fn token_by_id_with_includes(
+        &self,
+        token: &TokenLike,
+        includes: &TokenIncludeQuery,
+    ) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, &format!("tokens/{}", token))
+            .query(includes)
+            .send()
+            .await
+    }
+
     pub async fn token_price(&self, token: &TokenLike, token_id_or_usd: &str) -> Result<Response> {
         self.get_with_scope(
             super::API_V02_SCOPE,
@@ -31,6 +69,97 @@ impl Client {
         .await
     }
 
+    pub async fn token_price_with_smoothing(
+        &self,
+        token: &TokenLike,
+        token_id_or_usd: &str,
+        window: usize,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("tokens/{}/priceIn/{}", token, token_id_or_usd),
+        )
+        .query(&TokenPriceQuery {
+            smoothing: Some(window),
+        })
+        .send()
+        .await
+    }
+
+    pub async fn token_rate(
+        &self,
+        base_token: &TokenLike,
+        quote_token: &TokenLike,
+        amount: Option<BigUint>,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("tokens/{}/rate/{}", base_token, quote_token),
+        )
+        .query(&TokenRateQuery {
+            amount: amount.map(BigUintSerdeWrapper),
+            formatted: false,
+        })
+        .send()
+        .await
+    }
+
+    pub async fn tokens_by_ids(&self, ids: &[TokenId]) -> Result<Response> {
+        let ids = ids
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+        self.get_with_scope(super::API_V02_SCOPE, "tokens")
+            .query(&TokensByIdsQuery { ids })
+            .send()
+            .await
+    }
+
+    pub async fn fee_acceptable_batch(&self, tokens: Vec<TokenLike>) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "tokens/fee_acceptable_batch")
+            .body(&FeeAcceptableBatchRequest { tokens })
+            .send()
+            .await
+    }
+
+    pub async fn token_price_history(
+        &self,
+        token: &TokenLike,
+        query: &PriceHistoryQuery,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("tokens/{}/price_history", token),
+        )
+        .query(query)
+        .send()
+        .await
+    }
+
+    pub async fn token_available_currencies(&self, token: &TokenLike) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("tokens/{}/available_currencies", token),
+        )
+        .send()
+        .await
+    }
+
+    pub async fn token_txs(
+        &self,
+        pagination_query: &PaginationQuery<ApiEither<TxHash>>,
+        token: &TokenLike,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("tokens/{}/transactions", token),
+        )
+        .query(pagination_query)
+        .send()
+        .await
+    }
+
     pub async fn nft_by_id(&self, id: TokenId) -> Result<Response> {
         self.get_with_scope(super::API_V02_SCOPE, &format!("tokens/nft/{}", id))
             .send()
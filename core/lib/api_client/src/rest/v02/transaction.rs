@@ -1,6 +1,9 @@
 use crate::rest::client::{Client, Result};
 use zksync_api_types::{
-    v02::{transaction::IncomingTxBatch, Response},
+    v02::{
+        transaction::{IncomingTxBatch, TxStatusWaitQuery},
+        Response,
+    },
     TxWithSignature,
 };
 use zksync_types::tx::{EthBatchSignatures, TxEthSignatureVariant, TxHash, ZkSyncTx};
@@ -17,6 +20,21 @@ impl Client {
             .await
     }
 
+    /// Submits the transaction, the retried request with the same idempotency key
+    /// returns the response of the first one instead of submitting the transaction again.
+    pub async fn submit_tx_with_idempotency_key(
+        &self,
+        tx: ZkSyncTx,
+        signature: TxEthSignatureVariant,
+        idempotency_key: &str,
+    ) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "transactions")
+            .header("Idempotency-Key", idempotency_key)
+            .body(&TxWithSignature { tx, signature })
+            .send()
+            .await
+    }
+
     pub async fn submit_batch(
         &self,
         txs: Vec<TxWithSignature>,
@@ -28,6 +46,21 @@ impl Client {
             .await
     }
 
+    /// Submits the batch, the retried request with the same idempotency key
+    /// returns the response of the first one instead of submitting the batch again.
+    pub async fn submit_batch_with_idempotency_key(
+        &self,
+        txs: Vec<TxWithSignature>,
+        signature: Option<EthBatchSignatures>,
+        idempotency_key: &str,
+    ) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "transactions/batches")
+            .header("Idempotency-Key", idempotency_key)
+            .body(&IncomingTxBatch { txs, signature })
+            .send()
+            .await
+    }
+
     pub async fn tx_status(&self, tx_hash: TxHash) -> Result<Response> {
         self.get_with_scope(
             super::API_V02_SCOPE,
@@ -37,6 +70,21 @@ impl Client {
         .await
     }
 
+    /// Waits for the status of the transaction to become different from the one in the query.
+    pub async fn wait_tx_status(
+        &self,
+        tx_hash: TxHash,
+        query: &TxStatusWaitQuery,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("transactions/{}/wait", tx_hash.to_string()),
+        )
+        .query(query)
+        .send()
+        .await
+    }
+
     pub async fn tx_data(&self, tx_hash: TxHash) -> Result<Response> {
         self.get_with_scope(
             super::API_V02_SCOPE,
@@ -46,6 +94,17 @@ impl Client {
         .await
     }
 
+    /// Loads the stage of the withdrawal in the prove and execute pipeline
+    /// and the estimated time of its finalization.
+    pub async fn withdrawal_finality(&self, tx_hash: TxHash) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("transactions/{}/withdrawal", tx_hash.to_string()),
+        )
+        .send()
+        .await
+    }
+
     pub async fn get_batch(&self, batch_hash: TxHash) -> Result<Response> {
         self.get_with_scope(
             super::API_V02_SCOPE,
@@ -54,4 +113,12 @@ impl Client {
         .send()
         .await
     }
+
+    /// Loads the lifecycle of the priority operation, `id` is either its serial id
+    /// or the hash of its L1 transaction.
+    pub async fn priority_op(&self, id: &str) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, &format!("priority_ops/{}", id))
+            .send()
+            .await
+    }
 }
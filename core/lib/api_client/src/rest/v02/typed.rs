@@ -0,0 +1,327 @@
+//! Typed client of the REST API v0.2.
+//!
+//! Unlike the methods of the [`Client`] returning the raw responses, its methods return
+//! the results deserialized into the types shared with the server, and the error
+//! of the response as the [`ClientError::Api`](crate::rest::client::ClientError::Api). The `all_*` methods follow the cursors
+//! of the paginated endpoints to load every page.
+
+// Built-in uses
+use std::future::Future;
+
+// External uses
+use num::BigUint;
+use serde::{de::DeserializeOwned, Serialize};
+
+// Workspace uses
+use zksync_api_types::{
+    v02::{
+        account::{Account, AccountCommittedAndPendingState, UnconfirmedDeposits},
+        block::BlockInfo,
+        mempool::MempoolStats,
+        pagination::{ApiEither, Paginated, PaginationDirection, PaginationQuery, MAX_LIMIT},
+        token::{
+            ApiToken, PriceHistoryQuery, TokenLookup, TokenPrice, TokenPriceHistory, TokenRate,
+        },
+        transaction::{Receipt, SubmitBatchResponse, Transaction, TxData, WithdrawalFinality},
+    },
+    TxWithSignature,
+};
+use zksync_types::{
+    tx::{EthBatchSignatures, TxEthSignatureVariant, TxHash, ZkSyncTx},
+    BlockNumber, TokenId, TokenLike,
+};
+
+// Local uses
+use super::deserialize_result;
+use crate::rest::client::{Client, Result, RetryPolicy};
+
+/// Loads the pages following the first one by their cursors until the last page,
+/// returns the items of all the pages.
+pub async fn collect_pages<T, F, Fut, N>(first: Paginated<T, F>, mut next_page: N) -> Result<Vec<T>>
+where
+    T: Serialize,
+    F: Serialize,
+    N: FnMut(String) -> Fut,
+    Fut: Future<Output = Result<Paginated<T, F>>>,
+{
+    let mut items = first.list;
+    let mut cursor = first.pagination.next_cursor;
+    while let Some(next_cursor) = cursor {
+        let page = next_page(next_cursor).await?;
+        items.extend(page.list);
+        cursor = page.pagination.next_cursor;
+    }
+    Ok(items)
+}
+
+/// Client of the REST API v0.2 returning the typed results.
+#[derive(Debug, Clone)]
+pub struct TypedClient {
+    client: Client,
+}
+
+impl From<Client> for TypedClient {
+    fn from(client: Client) -> Self {
+        Self { client }
+    }
+}
+
+impl TypedClient {
+    /// Creates a new client with the specified Url.
+    pub fn new(url: String) -> Self {
+        Self::from(Client::new(url))
+    }
+
+    /// Sets the policy of retrying the requests failed for the transient reasons.
+    pub fn with_retry_policy(self, retry_policy: RetryPolicy) -> Self {
+        Self::from(self.client.with_retry_policy(retry_policy))
+    }
+
+    /// Returns the client of the raw responses, e.g. for the endpoints without the typed methods.
+    pub fn untyped(&self) -> &Client {
+        &self.client
+    }
+
+    async fn typed<T, Fut>(response: Fut) -> Result<T>
+    where
+        T: DeserializeOwned,
+        Fut: Future<Output = Result<zksync_api_types::v02::Response>>,
+    {
+        deserialize_result(response.await?)
+    }
+
+    pub async fn token_pagination(
+        &self,
+        pagination_query: &PaginationQuery<ApiEither<TokenId>>,
+    ) -> Result<Paginated<ApiToken, TokenId>> {
+        Self::typed(self.client.token_pagination(pagination_query)).await
+    }
+
+    pub async fn token_pagination_by_cursor(
+        &self,
+        cursor: &str,
+    ) -> Result<Paginated<ApiToken, TokenId>> {
+        Self::typed(self.client.token_pagination_by_cursor(cursor)).await
+    }
+
+    /// Loads all the tokens in the ascending order of their ids.
+    pub async fn all_tokens(&self) -> Result<Vec<ApiToken>> {
+        let first = self
+            .token_pagination(&PaginationQuery {
+                from: ApiEither::from(TokenId(0)),
+                limit: MAX_LIMIT,
+                direction: PaginationDirection::Newer,
+            })
+            .await?;
+        collect_pages(first, |cursor| async move {
+            self.token_pagination_by_cursor(&cursor).await
+        })
+        .await
+    }
+
+    pub async fn token_by_id(&self, token: &TokenLike) -> Result<ApiToken> {
+        Self::typed(self.client.token_by_id(token)).await
+    }
+
+    pub async fn tokens_by_ids(&self, ids: &[TokenId]) -> Result<Vec<TokenLookup>> {
+        Self::typed(self.client.tokens_by_ids(ids)).await
+    }
+
+    /// Loads the price of the token in the token with the given id, or in the fiat currency.
+    pub async fn token_price(&self, token: &TokenLike, currency: &str) -> Result<TokenPrice> {
+        Self::typed(self.client.token_price(token, currency)).await
+    }
+
+    pub async fn token_price_history(
+        &self,
+        token: &TokenLike,
+        query: &PriceHistoryQuery,
+    ) -> Result<TokenPriceHistory> {
+        Self::typed(self.client.token_price_history(token, query)).await
+    }
+
+    /// Loads the rate of the token pair, converting the amount of the base token if it's given.
+    pub async fn token_rate(
+        &self,
+        base_token: &TokenLike,
+        quote_token: &TokenLike,
+        amount: Option<BigUint>,
+    ) -> Result<TokenRate> {
+        Self::typed(self.client.token_rate(base_token, quote_token, amount)).await
+    }
+
+    /// Loads the account in the given state, either `committed` or `finalized`.
+    pub async fn account_info(
+        &self,
+        account_id_or_address: &str,
+        state_type: &str,
+    ) -> Result<Option<Account>> {
+        Self::typed(self.client.account_info(account_id_or_address, state_type)).await
+    }
+
+    pub async fn account_full_info(
+        &self,
+        account_id_or_address: &str,
+    ) -> Result<Option<AccountCommittedAndPendingState>> {
+        Self::typed(self.client.account_full_info(account_id_or_address)).await
+    }
+
+    pub async fn account_unconfirmed_deposits(
+        &self,
+        account_id_or_address: &str,
+    ) -> Result<UnconfirmedDeposits> {
+        Self::typed(
+            self.client
+                .account_unconfirmed_deposits(account_id_or_address),
+        )
+        .await
+    }
+
+    pub async fn account_txs(
+        &self,
+        pagination_query: &PaginationQuery<ApiEither<TxHash>>,
+        account_id_or_address: &str,
+    ) -> Result<Paginated<Transaction, TxHash>> {
+        Self::typed(
+            self.client
+                .account_txs(pagination_query, account_id_or_address),
+        )
+        .await
+    }
+
+    pub async fn account_txs_by_cursor(
+        &self,
+        cursor: &str,
+        account_id_or_address: &str,
+    ) -> Result<Paginated<Transaction, TxHash>> {
+        Self::typed(
+            self.client
+                .account_txs_by_cursor(cursor, account_id_or_address),
+        )
+        .await
+    }
+
+    /// Loads the whole history of the account, the latest transactions go first.
+    pub async fn all_account_txs(&self, account_id_or_address: &str) -> Result<Vec<Transaction>> {
+        let first = self
+            .account_txs(
+                &PaginationQuery {
+                    from: ApiEither::latest(),
+                    limit: MAX_LIMIT,
+                    direction: PaginationDirection::Older,
+                },
+                account_id_or_address,
+            )
+            .await?;
+        collect_pages(first, |cursor| async move {
+            self.account_txs_by_cursor(&cursor, account_id_or_address)
+                .await
+        })
+        .await
+    }
+
+    /// Loads the block, `block_position` is either its number, `lastCommitted` or `lastFinalized`.
+    pub async fn block_by_position(&self, block_position: &str) -> Result<Option<BlockInfo>> {
+        Self::typed(self.client.block_by_position(block_position)).await
+    }
+
+    pub async fn block_pagination(
+        &self,
+        pagination_query: &PaginationQuery<ApiEither<BlockNumber>>,
+    ) -> Result<Paginated<BlockInfo, BlockNumber>> {
+        Self::typed(self.client.block_pagination(pagination_query)).await
+    }
+
+    pub async fn block_pagination_by_cursor(
+        &self,
+        cursor: &str,
+    ) -> Result<Paginated<BlockInfo, BlockNumber>> {
+        Self::typed(self.client.block_pagination_by_cursor(cursor)).await
+    }
+
+    pub async fn transaction_in_block(
+        &self,
+        block_number: u32,
+        tx_index: u32,
+    ) -> Result<Option<TxData>> {
+        Self::typed(self.client.transaction_in_block(block_number, tx_index)).await
+    }
+
+    pub async fn block_transactions(
+        &self,
+        pagination_query: &PaginationQuery<ApiEither<TxHash>>,
+        block_position: &str,
+    ) -> Result<Paginated<Transaction, TxHash>> {
+        Self::typed(
+            self.client
+                .block_transactions(pagination_query, block_position),
+        )
+        .await
+    }
+
+    pub async fn block_transactions_by_cursor(
+        &self,
+        cursor: &str,
+        block_position: &str,
+    ) -> Result<Paginated<Transaction, TxHash>> {
+        Self::typed(
+            self.client
+                .block_transactions_by_cursor(cursor, block_position),
+        )
+        .await
+    }
+
+    /// Loads all the transactions of the block, the latest transactions go first.
+    pub async fn all_block_transactions(&self, block_position: &str) -> Result<Vec<Transaction>> {
+        let first = self
+            .block_transactions(
+                &PaginationQuery {
+                    from: ApiEither::latest(),
+                    limit: MAX_LIMIT,
+                    direction: PaginationDirection::Older,
+                },
+                block_position,
+            )
+            .await?;
+        collect_pages(first, |cursor| async move {
+            self.block_transactions_by_cursor(&cursor, block_position)
+                .await
+        })
+        .await
+    }
+
+    pub async fn mempool_stats(&self) -> Result<MempoolStats> {
+        Self::typed(self.client.mempool_stats()).await
+    }
+
+    /// Submits the transaction, returns its hash.
+    pub async fn submit_tx(
+        &self,
+        tx: ZkSyncTx,
+        signature: TxEthSignatureVariant,
+    ) -> Result<TxHash> {
+        Self::typed(self.client.submit_tx(tx, signature)).await
+    }
+
+    pub async fn submit_batch(
+        &self,
+        txs: Vec<TxWithSignature>,
+        signature: Option<EthBatchSignatures>,
+    ) -> Result<SubmitBatchResponse> {
+        Self::typed(self.client.submit_batch(txs, signature)).await
+    }
+
+    /// Loads the receipt of the transaction, or `None` if there is no such transaction.
+    pub async fn tx_status(&self, tx_hash: TxHash) -> Result<Option<Receipt>> {
+        Self::typed(self.client.tx_status(tx_hash)).await
+    }
+
+    pub async fn tx_data(&self, tx_hash: TxHash) -> Result<Option<TxData>> {
+        Self::typed(self.client.tx_data(tx_hash)).await
+    }
+
+    /// Loads the finality of the withdrawal, or `None` if there is no such transaction.
+    pub async fn withdrawal_finality(&self, tx_hash: TxHash) -> Result<Option<WithdrawalFinality>> {
+        Self::typed(self.client.withdrawal_finality(tx_hash)).await
+    }
+}
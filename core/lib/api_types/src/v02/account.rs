@@ -1,15 +1,22 @@
 use std::collections::{BTreeMap, HashMap};
 
+use chrono::{DateTime, Utc};
 use num::{BigUint, ToPrimitive};
 use serde::{Deserialize, Serialize};
 
+use zksync_crypto::{
+    serialization::{FrSerde, VecFrSerde},
+    Fr,
+};
 use zksync_types::{
-    AccountId, Address, BlockNumber, Nonce, PriorityOp, PubKeyHash, TokenId, ZkSyncPriorityOp,
+    tx::TxHash, AccountId, Address, BlockNumber, Nonce, PriorityOp, PubKeyHash, SerialId, TokenId,
+    ZkSyncPriorityOp, H256,
 };
 use zksync_utils::{BigUintSerdeAsRadix10Str, BigUintSerdeWrapper};
 
-use super::pagination::PaginationDirection;
+use super::pagination::{AccountTxDirection, PaginationDirection};
 use super::token::NFT;
+use super::transaction::TxInBlockStatus;
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
 #[serde(rename_all = "camelCase")]
@@ -28,11 +35,68 @@ pub struct Account {
     pub pub_key_hash: PubKeyHash,
     pub last_update_in_block: BlockNumber,
     pub balances: BTreeMap<String, BigUintSerdeWrapper>,
+    /// Balances scaled by the decimals of the tokens, set if the formatted amounts are requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub formatted_balances: Option<BTreeMap<String, String>>,
     pub nfts: BTreeMap<TokenId, NFT>,
     pub minted_nfts: BTreeMap<TokenId, NFT>,
     pub account_type: Option<EthAccountType>,
 }
 
+/// Committed state of the account along with the transactions waiting for it in the mempool.
+///
+/// The projected balances are estimated by applying the pending transactions to the
+/// committed balances, the transactions can still fail or be rejected by the server.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountCommittedAndPendingState {
+    pub committed: Account,
+    pub finalized: Option<Account>,
+    /// Hashes of the pending transactions in the order they will be executed.
+    pub pending_txs: Vec<TxHash>,
+    /// Nonce to be used for the next transaction of the account.
+    pub effective_nonce: Nonce,
+    pub projected_balances: BTreeMap<String, BigUintSerdeWrapper>,
+    /// Projected balances scaled by the decimals of the tokens, set if the formatted amounts
+    /// are requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub formatted_projected_balances: Option<BTreeMap<String, String>>,
+}
+
+/// Merkle proof of the account inclusion into the account tree of the block.
+///
+/// The leaf of the account is the hash of its nonce, public key hash, address and the
+/// hash of the balance tree root with the zero padding. The paths contain the sibling
+/// hashes starting from the leaf level, the directions are given by the bits of the
+/// account or the token id.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountProof {
+    pub account_id: AccountId,
+    pub block_number: BlockNumber,
+    /// Root hash of the account tree, equal to the state root of the block.
+    #[serde(with = "FrSerde")]
+    pub root_hash: Fr,
+    pub address: Address,
+    pub nonce: Nonce,
+    pub pub_key_hash: PubKeyHash,
+    #[serde(with = "FrSerde")]
+    pub balance_tree_root: Fr,
+    #[serde(with = "VecFrSerde")]
+    pub account_path: Vec<Fr>,
+    pub balance: Option<BalanceProof>,
+}
+
+/// Merkle proof of the balance inclusion into the balance tree of the account.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceProof {
+    pub token_id: TokenId,
+    pub balance: BigUintSerdeWrapper,
+    #[serde(with = "VecFrSerde")]
+    pub path: Vec<Fr>,
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 #[serde(rename_all = "camelCase")]
 pub enum AccountAddressOrId {
@@ -92,6 +156,35 @@ impl OngoingDeposit {
     }
 }
 
+/// Deposits to the address seen on Ethereum which don't have enough confirmations to be
+/// processed yet. The deposits can still be dropped if their Ethereum blocks are reverted.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UnconfirmedDeposits {
+    pub address: Address,
+    /// Last Ethereum block processed by the eth watcher, `None` if it's not known yet.
+    pub last_watched_eth_block: Option<u64>,
+    /// Number of the confirmations the deposit needs to be processed.
+    pub required_confirmations: u64,
+    pub deposits: Vec<UnconfirmedDeposit>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UnconfirmedDeposit {
+    pub serial_id: SerialId,
+    pub eth_hash: H256,
+    pub eth_block: u64,
+    pub from: Address,
+    pub token_id: TokenId,
+    pub token_symbol: String,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub amount: BigUint,
+    /// Number of the Ethereum blocks mined on top of the deposit block,
+    /// zero if the last watched block isn't known.
+    pub confirmations: u64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct DepositingFunds {
@@ -109,9 +202,47 @@ pub struct DepositingAccountBalances {
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct IncomingAccountTxsQuery {
-    pub from: String,
-    pub limit: u32,
-    pub direction: PaginationDirection,
+    pub from: Option<String>,
+    pub limit: Option<u32>,
+    pub direction: Option<PaginationDirection>,
+    pub cursor: Option<String>,
     pub token: Option<String>,
     pub second_account: Option<String>,
+    pub tx_type: Option<String>,
+    pub tx_direction: Option<AccountTxDirection>,
+    pub status: Option<TxInBlockStatus>,
+    pub created_after: Option<DateTime<Utc>>,
+    pub created_before: Option<DateTime<Utc>>,
+}
+
+/// Transaction of the exported account history. The amounts are in the smallest units
+/// of the tokens, the tokens are identified by their symbols.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountTxExport {
+    pub tx_hash: TxHash,
+    pub block_number: BlockNumber,
+    pub created_at: DateTime<Utc>,
+    pub tx_type: String,
+    pub from: Address,
+    pub to: Option<Address>,
+    pub token: Option<String>,
+    pub amount: Option<String>,
+    pub fee_token: Option<String>,
+    pub fee: Option<String>,
+    pub success: bool,
+    pub fail_reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IncomingAccountProofQuery {
+    pub token: Option<String>,
+    pub block: Option<BlockNumber>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IncomingExitProofQuery {
+    pub token: String,
 }
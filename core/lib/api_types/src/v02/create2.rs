@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+use zksync_types::{tx::ChangePubKeyCREATE2Data, AccountId, Address, PubKeyHash};
+
+/// The CREATE2 data of the smart wallet along with the public key hash it's deployed for.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Create2AddressRequest {
+    #[serde(flatten)]
+    pub create2_data: ChangePubKeyCREATE2Data,
+    pub pub_key_hash: PubKeyHash,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Create2Address {
+    pub address: Address,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Create2ValidationRequest {
+    /// Address of the smart wallet setting the public key hash.
+    pub account: Address,
+    pub pub_key_hash: PubKeyHash,
+    pub auth_data: ChangePubKeyCREATE2Data,
+}
+
+/// Result of the validation of the CREATE2 auth data of the `ChangePubKey` transaction.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Create2Validation {
+    /// Address derived from the CREATE2 data and the public key hash.
+    pub expected_address: Address,
+    /// Id of the account, present if the account exists in the network.
+    pub account_id: Option<AccountId>,
+    /// Whether the `ChangePubKey` with this auth data is accepted.
+    pub valid: bool,
+    /// Reason why the `ChangePubKey` isn't accepted, present if it's invalid.
+    pub reason: Option<String>,
+}
@@ -1,7 +1,10 @@
+use chrono::{DateTime, Utc};
 use num::BigUint;
 use serde::{Deserialize, Serialize};
-use zksync_types::{tokens::ChangePubKeyFeeTypeArg, Address, BatchFee, Fee, TokenLike, TxFeeTypes};
-use zksync_utils::BigUintSerdeAsRadix10Str;
+use zksync_types::{
+    tokens::ChangePubKeyFeeTypeArg, Address, BatchFee, Fee, TokenId, TokenLike, TxFeeTypes,
+};
+use zksync_utils::{format_units, BigUintSerdeAsRadix10Str};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -12,6 +15,29 @@ pub struct ApiFee {
     pub zkp_fee: BigUint,
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     pub total_fee: BigUint,
+    /// The fee scaled by the decimals of the token, set if the formatted amounts are requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub formatted: Option<ApiFormattedFee>,
+}
+
+impl ApiFee {
+    /// Sets the fee scaled by the decimals of the fee token.
+    pub fn with_formatted(mut self, decimals: u8) -> Self {
+        self.formatted = Some(ApiFormattedFee {
+            gas_fee: format_units(&self.gas_fee, decimals),
+            zkp_fee: format_units(&self.zkp_fee, decimals),
+            total_fee: format_units(&self.total_fee, decimals),
+        });
+        self
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiFormattedFee {
+    pub gas_fee: String,
+    pub zkp_fee: String,
+    pub total_fee: String,
 }
 
 impl From<Fee> for ApiFee {
@@ -20,6 +46,7 @@ impl From<Fee> for ApiFee {
             gas_fee: fee.gas_fee,
             zkp_fee: fee.zkp_fee,
             total_fee: fee.total_fee,
+            formatted: None,
         }
     }
 }
@@ -30,6 +57,7 @@ impl From<BatchFee> for ApiFee {
             gas_fee: fee.gas_fee,
             zkp_fee: fee.zkp_fee,
             total_fee: fee.total_fee,
+            formatted: None,
         }
     }
 }
@@ -71,6 +99,23 @@ impl From<ApiTxFeeTypes> for TxFeeTypes {
     }
 }
 
+impl ApiTxFeeTypes {
+    /// Returns the name of the transaction type, all the `ChangePubKey` variants share the same name.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ApiTxFeeTypes::Withdraw => "Withdraw",
+            ApiTxFeeTypes::FastWithdraw => "FastWithdraw",
+            ApiTxFeeTypes::Transfer => "Transfer",
+            ApiTxFeeTypes::ChangePubKey(_) => "ChangePubKey",
+            ApiTxFeeTypes::ForcedExit => "ForcedExit",
+            ApiTxFeeTypes::MintNFT => "MintNFT",
+            ApiTxFeeTypes::WithdrawNFT => "WithdrawNFT",
+            ApiTxFeeTypes::FastWithdrawNFT => "FastWithdrawNFT",
+            ApiTxFeeTypes::Swap => "Swap",
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct TxFeeRequest {
@@ -92,3 +137,38 @@ pub struct BatchFeeRequest {
     pub transactions: Vec<TxInBatchFeeRequest>,
     pub token_like: TokenLike,
 }
+
+/// Transaction type of the fee quotes made for the whole batch.
+pub const BATCH_FEE_QUOTE_TYPE: &str = "Batch";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeHistoryQuery {
+    /// Name of the transaction type, or `Batch` for the batch fee quotes.
+    pub tx_type: Option<String>,
+    pub token: String,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiFeeQuote {
+    pub tx_type: String,
+    pub address: Address,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub gas_fee: BigUint,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub zkp_fee: BigUint,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub total_fee: BigUint,
+    pub quoted_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeQuoteHistory {
+    pub token_id: TokenId,
+    pub token_symbol: String,
+    pub quotes: Vec<ApiFeeQuote>,
+}
@@ -0,0 +1,55 @@
+use chrono::{DateTime, Utc};
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+use zksync_types::{tx::TxHash, Address, TokenId, TokenLike};
+use zksync_utils::BigUintSerdeAsRadix10Str;
+
+use super::fee::ApiFee;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ForcedExitRequestBody {
+    pub target: Address,
+    pub token: TokenLike,
+}
+
+/// Estimation of the ForcedExit of the token of the target account.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ForcedExitEstimation {
+    pub target: Address,
+    pub token_id: TokenId,
+    pub token_symbol: String,
+    /// Committed balance of the token withdrawn by the ForcedExit.
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub balance: BigUint,
+    /// Fee of the ForcedExit in the token.
+    pub fee: ApiFee,
+    /// Whether the ForcedExit is submitted by the operator, i.e. the balance covers the fee.
+    pub viable: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ForcedExitAutomationStatus {
+    Pending,
+    Submitted,
+    Failed,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiForcedExitRequest {
+    pub id: i64,
+    pub target: Address,
+    pub token_id: TokenId,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub estimated_fee: BigUint,
+    pub status: ForcedExitAutomationStatus,
+    /// Hash of the ForcedExit transaction, present once the request is submitted.
+    pub tx_hash: Option<TxHash>,
+    /// Reason why the ForcedExit isn't possible anymore, present if the request has failed.
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub processed_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,41 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use zksync_types::{tx::TxHash, AccountId, Nonce};
+
+/// Current state of the mempool queue.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MempoolStats {
+    /// Amount of the queued transactions, including the ones of the batches.
+    pub txs_count: u64,
+    pub batches_count: u64,
+    /// Amount of the transactions returned to the queue by the blocks revert,
+    /// they are executed before the other ones.
+    pub reverted_txs_count: u64,
+    /// Amount of the confirmed priority operations awaiting for the execution.
+    pub priority_ops_count: u64,
+    pub oldest_tx_created_at: Option<DateTime<Utc>>,
+}
+
+/// Transaction of the account awaiting in the mempool queue.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct QueuedTx {
+    pub tx_hash: TxHash,
+    pub nonce: Nonce,
+    pub batch_id: Option<u64>,
+    pub created_at: DateTime<Utc>,
+    /// Amount of the transactions ahead in the queue. It's an estimation, since the
+    /// transactions ahead may be rejected and the priority operations are executed first.
+    pub queue_position: u64,
+}
+
+/// Transactions sent by the account which are awaiting in the mempool queue.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountQueuedTxs {
+    pub account_id: AccountId,
+    /// Total amount of the queued transactions.
+    pub queue_size: u64,
+    pub txs: Vec<QueuedTx>,
+}
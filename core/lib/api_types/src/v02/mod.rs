@@ -6,12 +6,26 @@ use zksync_types::network::Network;
 
 pub mod account;
 pub mod block;
+pub mod create2;
 pub mod fee;
+pub mod forced_exit;
+pub mod mempool;
+pub mod order;
 pub mod pagination;
+pub mod search;
 pub mod status;
 pub mod token;
 pub mod transaction;
 
+/// Query of the endpoints returning the token amounts: with `formatted=true` the amounts are
+/// also returned scaled by the decimals of their tokens, e.g. `"1.5"` along with the raw
+/// `"1500000000000000000"` of ETH, in the fields prefixed with `formatted`.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FormattedAmountsQuery {
+    #[serde(default)]
+    pub formatted: bool,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub enum ZksyncVersion {
@@ -0,0 +1,67 @@
+use num::{rational::Ratio, BigUint};
+use serde::{Deserialize, Serialize};
+use zksync_types::{AccountId, Address, Nonce, PubKeyHash, TokenId, TokenLike};
+use zksync_utils::{
+    BigUintPairSerdeAsRadix10Str, BigUintSerdeAsRadix10Str, UnsignedRatioSerializeAsDecimal,
+};
+
+use super::fee::ApiFee;
+
+/// Result of the validation of the signed order against the committed state of its account.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderValidation {
+    /// Whether the order may be used in the `Swap` now.
+    pub valid: bool,
+    /// Reason why the order can't be used, present if it's invalid.
+    pub reason: Option<String>,
+    /// Public key hash of the key the order is signed with.
+    pub signer: Option<PubKeyHash>,
+    /// Committed nonce of the account, present if the account exists.
+    pub account_nonce: Option<Nonce>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderQuoteRequest {
+    /// Submitter of the `Swap` paying the fee.
+    pub submitter: Address,
+    pub token_sell: TokenLike,
+    pub token_buy: TokenLike,
+    /// Amount of the sold token.
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub amount: BigUint,
+    /// Ratio of the order, i.e. the amount of the sold token to the amount of the bought token.
+    #[serde(with = "BigUintPairSerdeAsRadix10Str")]
+    pub ratio: (BigUint, BigUint),
+    pub fee_token: TokenLike,
+}
+
+/// Quote of the `Swap` of the amount with the ratio of the order.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderQuote {
+    pub token_sell: TokenId,
+    pub token_buy: TokenId,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub amount_sell: BigUint,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub amount_buy: BigUint,
+    pub fee_token: TokenId,
+    pub fee: ApiFee,
+    /// Bought token units received for one sold token unit, the fee is taken into account
+    /// if it's paid in either of the swapped tokens.
+    #[serde(with = "UnsignedRatioSerializeAsDecimal")]
+    pub effective_rate: Ratio<BigUint>,
+}
+
+/// Nonce state of the account signing the orders.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderNonceState {
+    pub account_id: AccountId,
+    /// Nonce the next order of the account should be signed with. The orders with the
+    /// lesser nonces are either filled or cancelled.
+    pub committed_nonce: Nonce,
+    pub finalized_nonce: Option<Nonce>,
+}
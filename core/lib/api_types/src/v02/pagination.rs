@@ -1,9 +1,13 @@
+use chrono::{DateTime, Utc};
 use either::Either;
+use num::{rational::Ratio, BigUint};
 use serde::{Deserialize, Serialize, Serializer};
 use std::str::FromStr;
 use thiserror::Error;
 use zksync_types::{tx::TxHash, AccountId, Address, BlockNumber, SerialId, TokenId};
 
+use super::{token::TokenFilter, transaction::TxInBlockStatus};
+
 pub const MAX_LIMIT: u32 = 100;
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
@@ -42,9 +46,7 @@ impl<T: FromStr + Serialize> FromStr for ApiEither<T> {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
-            "latest" => Ok(ApiEither {
-                inner: Either::Right(Latest),
-            }),
+            "latest" => Ok(ApiEither::latest()),
             _ => {
                 if let Ok(value) = T::from_str(s) {
                     Ok(ApiEither::from(value))
@@ -56,6 +58,15 @@ impl<T: FromStr + Serialize> FromStr for ApiEither<T> {
     }
 }
 
+impl<T: Serialize> ApiEither<T> {
+    /// The `latest` option of the pagination query.
+    pub fn latest() -> Self {
+        ApiEither {
+            inner: Either::Right(Latest),
+        }
+    }
+}
+
 impl<T: Serialize> From<T> for ApiEither<T> {
     fn from(value: T) -> ApiEither<T> {
         ApiEither {
@@ -83,6 +94,37 @@ pub struct PaginationQuery<Id> {
     pub direction: PaginationDirection,
 }
 
+/// Pagination query given either explicitly or by the opaque cursor returned
+/// with the previous page. The explicit parameters are ignored if the cursor is given.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PaginationQueryOrCursor {
+    pub from: Option<String>,
+    pub limit: Option<u32>,
+    pub direction: Option<PaginationDirection>,
+    pub cursor: Option<String>,
+}
+
+impl From<PaginationQuery<String>> for PaginationQueryOrCursor {
+    fn from(query: PaginationQuery<String>) -> Self {
+        Self {
+            from: Some(query.from),
+            limit: Some(query.limit),
+            direction: Some(query.direction),
+            cursor: None,
+        }
+    }
+}
+
+impl PaginationQueryOrCursor {
+    pub fn from_cursor(cursor: String) -> Self {
+        Self {
+            cursor: Some(cursor),
+            ..Default::default()
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct PaginationDetails<F: Serialize> {
@@ -90,6 +132,9 @@ pub struct PaginationDetails<F: Serialize> {
     pub limit: u32,
     pub direction: PaginationDirection,
     pub count: u32,
+    /// Opaque cursor of the next page, absent for the last page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -114,6 +159,7 @@ impl<T: Sized + Serialize, F: Serialize> Paginated<T, F> {
                 limit,
                 direction,
                 count,
+                next_cursor: None,
             },
         }
     }
@@ -132,10 +178,64 @@ pub struct PendingOpsRequest {
     pub serial_id: ApiEither<SerialId>,
 }
 
+#[derive(Debug, Serialize)]
+pub struct TokensRequest {
+    pub token_id: ApiEither<TokenId>,
+    pub filter: TokenFilter,
+    /// Market volume required for the token to be enabled for fees.
+    #[serde(skip)]
+    pub min_market_volume: Ratio<BigUint>,
+    /// Tokens excluded from the result.
+    #[serde(skip)]
+    pub denylisted_tokens: Vec<TokenId>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct AccountTxsRequest {
     pub address: Address,
     pub tx_hash: ApiEither<TxHash>,
     pub token: Option<TokenId>,
     pub second_address: Option<Address>,
+    pub filters: AccountTxsFilters,
+}
+
+/// Direction of the transaction relative to the account.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum AccountTxDirection {
+    /// The account is the recipient of the transaction.
+    Incoming,
+    /// The account is the sender of the transaction.
+    Outgoing,
+}
+
+/// Filters of the account transactions, only the transactions matching all of them are returned.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountTxsFilters {
+    /// Type of the transaction or the priority operation, e.g. `Transfer` or `Deposit`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx_direction: Option<AccountTxDirection>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<TxInBlockStatus>,
+    /// The transactions created at or after the time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_after: Option<DateTime<Utc>>,
+    /// The transactions created before the time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_before: Option<DateTime<Utc>>,
+}
+
+impl AccountTxsFilters {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct TokenTxsRequest {
+    pub token: TokenId,
+    pub tx_hash: ApiEither<TxHash>,
 }
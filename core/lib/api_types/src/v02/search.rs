@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+use zksync_types::{tx::TxHash, AccountId, Address, BlockNumber, Token};
+use zksync_utils::ZeroPrefixHexSerde;
+
+/// The entity found by the explorer search.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SearchResult {
+    #[serde(rename_all = "camelCase")]
+    Transaction {
+        #[serde(serialize_with = "ZeroPrefixHexSerde::serialize")]
+        tx_hash: TxHash,
+    },
+    #[serde(rename_all = "camelCase")]
+    Account {
+        address: Address,
+        account_id: AccountId,
+    },
+    #[serde(rename_all = "camelCase")]
+    Block { block_number: BlockNumber },
+    #[serde(rename_all = "camelCase")]
+    Token { token: Token },
+}
@@ -1,6 +1,9 @@
 use crate::CoreStatus;
+use chrono::{DateTime, Utc};
+use num::BigUint;
 use serde::{Deserialize, Serialize};
-use zksync_types::BlockNumber;
+use zksync_types::{BlockNumber, TokenId};
+use zksync_utils::{BigUintSerdeAsRadix10Str, BigUintSerdeWrapper};
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -11,3 +14,47 @@ pub struct NetworkStatus {
     pub mempool_size: u32,
     pub core_status: Option<CoreStatus>,
 }
+
+/// Health of the price provider of the fee ticker, the providers are used one after
+/// another until one of them returns the fresh price.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceProviderHealth {
+    pub provider: String,
+    /// Whether the last request to the provider has succeeded.
+    pub healthy: bool,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub last_failure_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub consecutive_failures: u32,
+    /// When the health was reported by the fee ticker.
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Statistics of the network over the last day, which are aggregated periodically
+/// by the server, so they are as fresh as `updated_at`.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkStats {
+    /// Amount of the successful transactions and the priority operations.
+    pub tx_count_24h: u64,
+    /// Amount of the distinct accounts which initiated the transactions.
+    pub active_accounts_24h: u64,
+    pub tokens: Vec<TokenStats>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenStats {
+    pub token_id: TokenId,
+    pub symbol: String,
+    /// Total balance of the accounts in the token.
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub tvl: BigUint,
+    /// Sum of the amounts of the transfers, the withdrawals and the deposits.
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub volume_24h: BigUint,
+    /// Average non-zero fee paid in the token, `None` if no fee was paid.
+    pub average_fee_24h: Option<BigUintSerdeWrapper>,
+}
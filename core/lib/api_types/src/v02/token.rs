@@ -1,6 +1,10 @@
 use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use zksync_types::{AccountId, Address, Token, TokenId, H256};
+use zksync_types::{AccountId, Address, Token, TokenId, TokenLike, H256};
+use zksync_utils::BigUintSerdeWrapper;
+
+use super::pagination::PaginationDirection;
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
@@ -10,6 +14,28 @@ pub struct ApiToken {
     pub symbol: String,
     pub decimals: u8,
     pub enabled_for_fees: bool,
+    /// Present only if requested with the `include=price` query.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usd_price: Option<BigDecimal>,
+    /// Present only if requested with the `include=volume` query.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub market_volume: Option<BigDecimal>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logo_uri: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_url: Option<String>,
+    /// Full name of the token, e.g. "USD Coin" for "USDC".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+}
+
+/// Optional metadata of the token, set by the admin.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenMetadata {
+    pub logo_uri: Option<String>,
+    pub project_url: Option<String>,
+    pub display_name: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -20,6 +46,213 @@ pub struct TokenPrice {
     pub price_in: String,
     pub decimals: u8,
     pub price: BigDecimal,
+    /// Number of the price points the price was averaged over, if it was smoothed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub smoothing_window: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenPriceQuery {
+    /// Number of the most recent price points to average the price over.
+    pub smoothing: Option<usize>,
+}
+
+/// Rate of the token pair, i.e. the price of one base token in the quote tokens.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenRate {
+    pub base_token_id: TokenId,
+    pub base_token_symbol: String,
+    pub quote_token_id: TokenId,
+    pub quote_token_symbol: String,
+    pub rate: BigDecimal,
+    /// Amount of the base token from the query, in its smallest units.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount: Option<BigUintSerdeWrapper>,
+    /// The amount converted to the smallest units of the quote token, rounded down.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub converted_amount: Option<BigUintSerdeWrapper>,
+    /// The amounts scaled by the decimals of the tokens, set if the formatted amounts are
+    /// requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub formatted_amount: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub formatted_converted_amount: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenRateQuery {
+    /// Amount of the base token to convert, in its smallest units.
+    pub amount: Option<BigUintSerdeWrapper>,
+    #[serde(default)]
+    pub formatted: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceHistoryQuery {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    /// Length of the interval the prices are averaged over, in seconds.
+    pub resolution: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenPricePoint {
+    /// Start of the interval the price is averaged over.
+    pub time: DateTime<Utc>,
+    pub price: BigDecimal,
+}
+
+/// USD prices of the token over time.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenPriceHistory {
+    pub token_id: TokenId,
+    pub token_symbol: String,
+    pub resolution: u32,
+    pub points: Vec<TokenPricePoint>,
+}
+
+/// Request sent by the client of the price subscription WebSocket.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum PriceSubscriptionRequest {
+    Subscribe { tokens: Vec<TokenId> },
+    Unsubscribe { tokens: Vec<TokenId> },
+}
+
+/// USD price of the token pushed to the price subscribers.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenPriceUpdate {
+    pub token_id: TokenId,
+    pub price: BigDecimal,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Currencies in which the price of the token can currently be shown.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AvailableCurrencies {
+    pub token_id: TokenId,
+    pub token_symbol: String,
+    pub currencies: Vec<String>,
+}
+
+/// Order in which the tokens are paginated.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TokenSortOrder {
+    Id,
+    MarketVolume,
+}
+
+impl Default for TokenSortOrder {
+    fn default() -> Self {
+        Self::Id
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokensQuery {
+    pub from: Option<String>,
+    pub limit: Option<u32>,
+    pub direction: Option<PaginationDirection>,
+    pub cursor: Option<String>,
+    pub enabled_for_fees: Option<bool>,
+    pub symbol_prefix: Option<String>,
+    pub sort: Option<TokenSortOrder>,
+    pub include: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct TokenIncludeQuery {
+    /// Comma-separated list of the optional token fields, `price` and `volume`.
+    pub include: Option<String>,
+}
+
+/// Restrictions on the paginated tokens.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenFilter {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled_for_fees: Option<bool>,
+    /// Case-insensitive prefix of the token symbol.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol_prefix: Option<String>,
+    #[serde(default)]
+    pub sort: TokenSortOrder,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct TokensByIdsQuery {
+    /// Comma-separated list of the token ids.
+    pub ids: String,
+}
+
+/// Result of the lookup of a single token in the batch request.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenLookup {
+    pub id: TokenId,
+    pub token: Option<ApiToken>,
+    pub error: Option<String>,
+}
+
+/// Result of the lookup of a single token USD price in the batch request.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenPriceLookup {
+    pub token: TokenLike,
+    pub price: Option<BigDecimal>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeAcceptableBatchRequest {
+    pub tokens: Vec<TokenLike>,
+}
+
+/// Reason why the token can't be used to pay fees.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum FeeUnacceptableReason {
+    NotFound,
+    BelowThreshold,
+    NoVolumeData,
+    NoLivePrice,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeAcceptability {
+    pub token: TokenLike,
+    pub acceptable: bool,
+    pub reason: Option<FeeUnacceptableReason>,
+}
+
+impl FeeAcceptability {
+    pub fn acceptable(token: TokenLike) -> Self {
+        Self {
+            token,
+            acceptable: true,
+            reason: None,
+        }
+    }
+
+    pub fn unacceptable(token: TokenLike, reason: FeeUnacceptableReason) -> Self {
+        Self {
+            token,
+            acceptable: false,
+            reason: Some(reason),
+        }
+    }
 }
 
 impl ApiToken {
@@ -30,6 +263,11 @@ impl ApiToken {
             symbol: token.symbol,
             decimals: token.decimals,
             enabled_for_fees: eligibility,
+            usd_price: None,
+            market_volume: None,
+            logo_uri: None,
+            project_url: None,
+            display_name: None,
         }
     }
 }
@@ -73,3 +311,18 @@ pub struct ApiNFT {
     pub current_factory: Address,
     pub withdrawn_factory: Option<Address>,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct NFTContentHashQuery {
+    pub content_hash: H256,
+}
+
+/// Result of the comparison of the given content hash with the one the NFT was minted with.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct NFTContentHashVerification {
+    pub id: TokenId,
+    pub content_hash: H256,
+    pub matches: bool,
+}
@@ -23,6 +23,8 @@ pub struct IncomingTxBatch {
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
 #[serde(rename_all = "camelCase")]
 pub enum TxInBlockStatus {
+    /// The transaction is in the mempool, but its `valid_from` timestamp hasn't come yet.
+    Scheduled,
     Queued,
     Committed,
     Finalized,
@@ -45,6 +47,20 @@ pub struct TxData {
     pub eth_signature: Option<String>,
 }
 
+/// Ethereum transactions committing and executing the block of the operation, the fields
+/// of each transaction are empty until it's confirmed. The Ethereum block and the time
+/// of the confirmation aren't known for the transactions confirmed before they were recorded.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct L1Details {
+    pub commit_tx_hash: Option<H256>,
+    pub commit_eth_block: Option<EthBlockId>,
+    pub committed_at: Option<DateTime<Utc>>,
+    pub verify_tx_hash: Option<H256>,
+    pub verify_eth_block: Option<EthBlockId>,
+    pub finalized_at: Option<DateTime<Utc>>,
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct L1Receipt {
@@ -52,6 +68,8 @@ pub struct L1Receipt {
     pub eth_block: EthBlockId,
     pub rollup_block: Option<BlockNumber>,
     pub id: SerialId,
+    #[serde(flatten)]
+    pub l1_details: L1Details,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -62,6 +80,8 @@ pub struct L2Receipt {
     pub rollup_block: Option<BlockNumber>,
     pub status: TxInBlockStatus,
     pub fail_reason: Option<String>,
+    #[serde(flatten)]
+    pub l1_details: L1Details,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -71,6 +91,114 @@ pub enum Receipt {
     L2(L2Receipt),
 }
 
+impl Receipt {
+    pub fn status(&self) -> TxInBlockStatus {
+        match self {
+            Receipt::L1(receipt) => receipt.status,
+            Receipt::L2(receipt) => receipt.status,
+        }
+    }
+}
+
+/// Query of the long-polling request waiting for the status of the transaction to change.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TxStatusWaitQuery {
+    /// The last status of the transaction known to the client, the response is sent
+    /// once the status is different. If not set, the response is sent once the transaction is found.
+    pub status: Option<TxInBlockStatus>,
+    /// Maximum time to wait for the change in seconds, the current receipt is returned after it.
+    pub timeout: Option<u64>,
+}
+
+/// Priority operation is identified either by its serial id or by the hash of the L1 transaction.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum PriorityOpId {
+    SerialId(SerialId),
+    EthHash(H256),
+}
+
+/// Stages of the priority operation in the order they are passed.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum PriorityOpStage {
+    /// The operation is emitted on L1, it's executed once it has enough confirmations.
+    SeenOnL1,
+    /// The operation is executed in the block, which isn't committed on L1 yet.
+    IncludedInBlock,
+    /// The block with the operation is committed on L1.
+    Committed,
+    /// The block with the operation is executed on L1.
+    Finalized,
+}
+
+/// Lifecycle of the priority operation from its emission on L1 to the finalization of its block.
+/// The fields of the stages which aren't passed yet are empty.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PriorityOpLifecycle {
+    pub serial_id: SerialId,
+    pub tx_hash: TxHash,
+    pub eth_hash: H256,
+    pub op_type: String,
+    pub stage: PriorityOpStage,
+    pub eth_block: EthBlockId,
+    pub eth_block_index: Option<u64>,
+    /// Whether the L1 block of the operation has enough confirmations for the operation
+    /// to be executed.
+    pub confirmed_on_l1: bool,
+    pub rollup_block: Option<BlockNumber>,
+    pub block_index: Option<u32>,
+    pub executed_at: Option<DateTime<Utc>>,
+    pub commit_tx_hash: Option<H256>,
+    pub committed_at: Option<DateTime<Utc>>,
+    pub verify_tx_hash: Option<H256>,
+    pub finalized_at: Option<DateTime<Utc>>,
+}
+
+/// Stages of the withdrawal in the order they are passed, the withdrawn funds are available
+/// on L1 once the block with the withdrawal is finalized.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum WithdrawalStage {
+    /// The withdrawal is in the mempool.
+    Queued,
+    /// The withdrawal is executed in the block, which isn't committed on L1 yet.
+    IncludedInBlock,
+    /// The block with the withdrawal is committed on L1 and waits for the proof.
+    Committed,
+    /// The proof of the block is published on L1, the block waits for the execution.
+    Proven,
+    /// The block with the withdrawal is executed on L1.
+    Finalized,
+}
+
+/// Position of the withdrawal in the prove and execute pipeline and the estimated time
+/// when the withdrawn funds become available on L1.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawalFinality {
+    #[serde(serialize_with = "ZeroPrefixHexSerde::serialize")]
+    pub tx_hash: TxHash,
+    pub stage: WithdrawalStage,
+    pub rollup_block: Option<BlockNumber>,
+    pub last_committed_block: BlockNumber,
+    pub last_proven_block: BlockNumber,
+    pub last_finalized_block: BlockNumber,
+    /// Number of the blocks up to the block of the withdrawal which proofs aren't published yet,
+    /// empty until the withdrawal is included in the block.
+    pub blocks_to_prove: Option<u32>,
+    /// Number of the blocks up to the block of the withdrawal which aren't executed yet,
+    /// empty until the withdrawal is included in the block.
+    pub blocks_to_finalize: Option<u32>,
+    /// Estimated time of the finalization, based on the recent cadence of the commits, the proofs
+    /// and the executions. Empty for the finalized withdrawals and if there is no recent cadence.
+    pub estimated_finalized_at: Option<DateTime<Utc>>,
+    /// Hash of the L1 transaction in which the funds were withdrawn.
+    pub eth_tx_hash: Option<H256>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Transaction {
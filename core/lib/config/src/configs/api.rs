@@ -6,7 +6,7 @@ use std::net::SocketAddr;
 use std::time::Duration;
 use zksync_utils::scaled_u64_to_ratio;
 // Workspace uses
-use zksync_types::AccountId;
+use zksync_types::{AccountId, H256};
 // Local uses
 use crate::envy_load;
 
@@ -19,6 +19,8 @@ pub struct ApiConfig {
     pub admin: AdminApiConfig,
     /// Configuration options for the REST API server.
     pub rest: RestApiConfig,
+    /// Configuration options for the gRPC API server.
+    pub grpc: GrpcApiConfig,
     /// Configuration options for the JSON RPC servers.
     pub json_rpc: JsonRpcConfig,
     /// Configuration options for the web3 JSON RPC server.
@@ -38,6 +40,7 @@ impl ApiConfig {
             common: envy_load!("common", "API_COMMON_"),
             admin: envy_load!("admin", "API_ADMIN_"),
             rest: envy_load!("rest", "API_REST_"),
+            grpc: envy_load!("grpc", "API_GRPC_"),
             json_rpc: envy_load!("json_rpc", "API_JSON_RPC_"),
             web3: envy_load!("web3", "API_WEB3_"),
             private: envy_load!("private", "API_PRIVATE_"),
@@ -56,6 +59,14 @@ impl CommonApiConfig {
     pub fn from_env() -> Self {
         envy_load!("common", "API_COMMON_")
     }
+
+    pub fn idempotency_key_window(&self) -> Duration {
+        Duration::from_secs(self.idempotency_key_window_sec)
+    }
+
+    pub fn read_only_retry_after(&self) -> Duration {
+        Duration::from_secs(self.read_only_retry_after_sec)
+    }
 }
 
 impl AdminApiConfig {
@@ -70,6 +81,12 @@ impl RestApiConfig {
     }
 }
 
+impl GrpcApiConfig {
+    pub fn from_env() -> Self {
+        envy_load!("grpc", "API_GRPC_")
+    }
+}
+
 impl JsonRpcConfig {
     pub fn from_env() -> Self {
         envy_load!("json_rpc", "API_JSON_RPC_")
@@ -122,12 +139,34 @@ pub struct CommonApiConfig {
 
     /// The name of current subsidy. It is needed to conveniently fetch historical data regarding subsidies for different partners
     pub subsidy_name: String,
+
+    /// How long the responses of the transaction submissions are returned again
+    /// for the requests with the same `Idempotency-Key` header.
+    pub idempotency_key_window_sec: u64,
+
+    /// Whether the transaction submissions are rejected regardless of the read-only mode set by the admin API.
+    pub read_only: bool,
+    /// How long the clients are asked to wait before retrying the submissions in the read-only mode.
+    pub read_only_retry_after_sec: u64,
+
+    /// Number of threads verifying the signatures of the incoming transactions, 0 for the number of CPUs.
+    pub signature_checker_threads: usize,
+    /// Maximum number of the verification requests verified by the signature checker threads at once.
+    pub signature_checker_batch_size: usize,
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct TokenConfig {
     /// The interval of updating tokens from database
     pub invalidate_token_cache_period_sec: u64,
+    /// Time-to-live of the token prices cached by the API server
+    pub price_cache_ttl_sec: u64,
+    /// URL of the API providing the exchange rates of the fiat currencies.
+    pub fiat_rates_url: String,
+    /// Time-to-live of the fiat exchange rates cached by the API server
+    pub fiat_rates_ttl_sec: u64,
+    /// How long the clients may cache the responses of the token endpoints.
+    pub response_max_age_sec: u64,
 }
 
 impl TokenConfig {
@@ -138,6 +177,18 @@ impl TokenConfig {
     pub fn invalidate_token_cache_period(&self) -> Duration {
         Duration::from_secs(self.invalidate_token_cache_period_sec)
     }
+
+    pub fn price_cache_ttl(&self) -> Duration {
+        Duration::from_secs(self.price_cache_ttl_sec)
+    }
+
+    pub fn fiat_rates_ttl(&self) -> Duration {
+        Duration::from_secs(self.fiat_rates_ttl_sec)
+    }
+
+    pub fn response_max_age(&self) -> Duration {
+        Duration::from_secs(self.response_max_age_sec)
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -148,6 +199,9 @@ pub struct AdminApiConfig {
     pub url: String,
     /// Secret used to generate access token (JWT).
     pub secret_auth: String,
+    /// Origins allowed to send the credentialed cross-origin requests to the admin API,
+    /// the wildcard isn't allowed for the credentialed requests.
+    pub cors_allowed_origins: Vec<String>,
 }
 
 impl AdminApiConfig {
@@ -192,12 +246,55 @@ pub struct RestApiConfig {
     pub port: u16,
     /// URL to access API server.
     pub url: String,
+    /// Secret used to sign the opaque pagination cursors.
+    pub pagination_cursor_secret: String,
+    /// Maximum number of requests per second from a single IP address, `0` disables the limit.
+    /// The clients with an API key are limited by the quota of the key instead.
+    pub requests_per_second_per_ip: u32,
+    /// Whether the `/metrics` endpoint with the metrics in the Prometheus format is exposed.
+    pub metrics_endpoint: bool,
+    /// How long the in-flight requests are waited for once the server is being stopped.
+    pub shutdown_timeout_sec: u64,
+    /// Whether the responses are compressed with gzip or brotli if the client accepts it.
+    pub compression_enabled: bool,
+    /// Responses smaller than this number of bytes are sent uncompressed.
+    pub compression_min_size: usize,
+    /// Content types of the responses which are compressed, e.g. `application/json`.
+    pub compression_content_types: Vec<String>,
+    /// Origins allowed to send the cross-origin requests, `*` allows any origin.
+    pub cors_allowed_origins: Vec<String>,
+    /// Methods allowed in the cross-origin requests.
+    pub cors_allowed_methods: Vec<String>,
+    /// How long the results of the preflight requests may be cached by the browsers.
+    pub cors_max_age_sec: usize,
+    /// Private key the responses of the account balances and the exit proofs are signed by, so the clients
+    /// querying the API through the untrusted proxies can verify them. The responses aren't signed if not set.
+    /// Must not be one of the operator keys.
+    pub response_signing_key: Option<H256>,
 }
 
 impl RestApiConfig {
     pub fn bind_addr(&self) -> SocketAddr {
         SocketAddr::new("0.0.0.0".parse().unwrap(), self.port)
     }
+
+    pub fn shutdown_timeout(&self) -> Duration {
+        Duration::from_secs(self.shutdown_timeout_sec)
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct GrpcApiConfig {
+    /// Port to which the gRPC API server is listening.
+    pub port: u16,
+    /// URL to access gRPC API server.
+    pub url: String,
+}
+
+impl GrpcApiConfig {
+    pub fn bind_addr(&self) -> SocketAddr {
+        SocketAddr::new("0.0.0.0".parse().unwrap(), self.port)
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -248,7 +345,7 @@ pub struct PrometheusConfig {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::configs::test_utils::set_env;
+    use crate::configs::test_utils::{hash, set_env};
     use std::net::IpAddr;
 
     fn expected_config() -> ApiConfig {
@@ -263,15 +360,47 @@ mod tests {
                 subsidized_ips: vec!["127.0.0.1".to_owned()],
                 max_subsidy_usd_scaled: 20000,
                 subsidy_name: String::from("PartnerName"),
+                idempotency_key_window_sec: 86400,
+                read_only: false,
+                read_only_retry_after_sec: 60,
+                signature_checker_threads: 0,
+                signature_checker_batch_size: 64,
             },
             admin: AdminApiConfig {
                 port: 8080,
                 url: "http://127.0.0.1:8080".into(),
                 secret_auth: "sample".into(),
+                cors_allowed_origins: vec!["http://127.0.0.1:3000".to_owned()],
             },
             rest: RestApiConfig {
                 port: 3001,
                 url: "http://127.0.0.1:3001".into(),
+                pagination_cursor_secret: "sample".into(),
+                requests_per_second_per_ip: 50,
+                metrics_endpoint: true,
+                shutdown_timeout_sec: 30,
+                compression_enabled: true,
+                compression_min_size: 1024,
+                compression_content_types: vec![
+                    "application/json".to_owned(),
+                    "text/csv".to_owned(),
+                    "application/x-ndjson".to_owned(),
+                ],
+                cors_allowed_origins: vec!["*".to_owned()],
+                cors_allowed_methods: vec![
+                    "GET".to_owned(),
+                    "POST".to_owned(),
+                    "PUT".to_owned(),
+                    "DELETE".to_owned(),
+                ],
+                cors_max_age_sec: 3600,
+                response_signing_key: Some(hash(
+                    "e4b9a8ed3d6c2d2ae2e9c0a1e5b7d9e2a7df3a5c6c0e2d8f1aa3b9c5d2e8f4a1",
+                )),
+            },
+            grpc: GrpcApiConfig {
+                port: 3060,
+                url: "http://127.0.0.1:3060".into(),
             },
             json_rpc: JsonRpcConfig {
                 http_port: 3030,
@@ -297,6 +426,10 @@ mod tests {
             prometheus: PrometheusConfig { port: 3312 },
             token_config: TokenConfig {
                 invalidate_token_cache_period_sec: 10,
+                price_cache_ttl_sec: 5,
+                fiat_rates_url: "http://127.0.0.1:9876".into(),
+                fiat_rates_ttl_sec: 600,
+                response_max_age_sec: 5,
             },
         }
     }
@@ -311,14 +444,37 @@ API_COMMON_ENFORCE_PUBKEY_CHANGE_FEE=true
 API_COMMON_SUBSIDIZED_IPS="127.0.0.1"
 API_COMMON_MAX_SUBSIDY_USD_SCALED=20000
 API_COMMON_SUBSIDY_NAME=PartnerName
+API_COMMON_IDEMPOTENCY_KEY_WINDOW_SEC=86400
+API_COMMON_READ_ONLY=false
+API_COMMON_READ_ONLY_RETRY_AFTER_SEC=60
+API_COMMON_SIGNATURE_CHECKER_THREADS=0
+API_COMMON_SIGNATURE_CHECKER_BATCH_SIZE=64
 API_COMMON_MAX_NUMBER_OF_TRANSACTIONS_PER_BATCH=200
 API_COMMON_MAX_NUMBER_OF_AUTHORS_PER_BATCH=10
 API_TOKEN_INVALIDATE_TOKEN_CACHE_PERIOD_SEC="10"
+API_TOKEN_PRICE_CACHE_TTL_SEC="5"
+API_TOKEN_FIAT_RATES_URL="http://127.0.0.1:9876"
+API_TOKEN_FIAT_RATES_TTL_SEC="600"
+API_TOKEN_RESPONSE_MAX_AGE_SEC="5"
 API_ADMIN_PORT="8080"
 API_ADMIN_URL="http://127.0.0.1:8080"
 API_ADMIN_SECRET_AUTH="sample"
+API_ADMIN_CORS_ALLOWED_ORIGINS="http://127.0.0.1:3000"
 API_REST_PORT="3001"
 API_REST_URL="http://127.0.0.1:3001"
+API_REST_PAGINATION_CURSOR_SECRET="sample"
+API_REST_REQUESTS_PER_SECOND_PER_IP="50"
+API_REST_METRICS_ENDPOINT="true"
+API_REST_SHUTDOWN_TIMEOUT_SEC="30"
+API_REST_COMPRESSION_ENABLED="true"
+API_REST_COMPRESSION_MIN_SIZE="1024"
+API_REST_COMPRESSION_CONTENT_TYPES="application/json,text/csv,application/x-ndjson"
+API_REST_CORS_ALLOWED_ORIGINS="*"
+API_REST_CORS_ALLOWED_METHODS="GET,POST,PUT,DELETE"
+API_REST_CORS_MAX_AGE_SEC="3600"
+API_REST_RESPONSE_SIGNING_KEY="0xe4b9a8ed3d6c2d2ae2e9c0a1e5b7d9e2a7df3a5c6c0e2d8f1aa3b9c5d2e8f4a1"
+API_GRPC_PORT="3060"
+API_GRPC_URL="http://127.0.0.1:3060"
 API_JSON_RPC_HTTP_PORT="3030"
 API_JSON_RPC_HTTP_URL="http://127.0.0.1:3030"
 API_JSON_RPC_WS_PORT="3031"
@@ -358,6 +514,10 @@ API_PROMETHEUS_PORT="3312"
             config.rest.bind_addr(),
             SocketAddr::new(bind_broadcast_addr, config.rest.port)
         );
+        assert_eq!(
+            config.grpc.bind_addr(),
+            SocketAddr::new(bind_broadcast_addr, config.grpc.port)
+        );
         assert_eq!(
             config.private.bind_addr(),
             SocketAddr::new(bind_broadcast_addr, config.private.port)
@@ -94,6 +94,14 @@ pub struct StateKeeper {
     pub block_prove_deadline: u64,
     pub block_execute_deadline: u64,
     pub max_aggregated_tx_gas: usize,
+    /// Time (seconds) since the first transaction of the block after which the block is sealed.
+    /// `0` disables the trigger.
+    pub seal_block_max_age: u64,
+    /// Commit gas cost of the block operations after which the block is sealed. `0` disables the trigger.
+    pub seal_block_gas_budget: u64,
+    /// Time (seconds) since the first priority operation of the block after which the block is sealed.
+    /// `0` disables the trigger.
+    pub seal_priority_op_deadline: u64,
 }
 
 impl StateKeeper {
@@ -113,6 +121,18 @@ impl StateKeeper {
     pub fn block_execute_deadline(&self) -> Duration {
         Duration::from_secs(self.block_execute_deadline)
     }
+
+    pub fn seal_block_max_age(&self) -> Option<Duration> {
+        Some(self.seal_block_max_age)
+            .filter(|&secs| secs > 0)
+            .map(Duration::from_secs)
+    }
+
+    pub fn seal_priority_op_deadline(&self) -> Option<Duration> {
+        Some(self.seal_priority_op_deadline)
+            .filter(|&secs| secs > 0)
+            .map(Duration::from_secs)
+    }
 }
 
 #[cfg(test)]
@@ -147,6 +167,9 @@ mod tests {
                 block_prove_deadline: 3_000,
                 block_execute_deadline: 4_000,
                 max_aggregated_tx_gas: 4_000_000,
+                seal_block_max_age: 60,
+                seal_block_gas_budget: 2_000_000,
+                seal_priority_op_deadline: 0,
             },
         }
     }
@@ -175,6 +198,9 @@ CHAIN_STATE_KEEPER_BLOCK_COMMIT_DEADLINE="300"
 CHAIN_STATE_KEEPER_BLOCK_PROVE_DEADLINE="3000"
 CHAIN_STATE_KEEPER_BLOCK_EXECUTE_DEADLINE="4000"
 CHAIN_STATE_KEEPER_MAX_AGGREGATED_TX_GAS="4000000"
+CHAIN_STATE_KEEPER_SEAL_BLOCK_MAX_AGE="60"
+CHAIN_STATE_KEEPER_SEAL_BLOCK_GAS_BUDGET="2000000"
+CHAIN_STATE_KEEPER_SEAL_PRIORITY_OP_DEADLINE="0"
         "#;
         set_env(config);
 
@@ -191,5 +217,10 @@ CHAIN_STATE_KEEPER_MAX_AGGREGATED_TX_GAS="4000000"
             config.state_keeper.miniblock_iteration_interval(),
             Duration::from_millis(config.state_keeper.miniblock_iteration_interval)
         );
+        assert_eq!(
+            config.state_keeper.seal_block_max_age(),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(config.state_keeper.seal_priority_op_deadline(), None);
     }
 }
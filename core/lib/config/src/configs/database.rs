@@ -18,6 +18,14 @@ pub struct DBConfig {
     pub rejected_transactions_max_age: u64,
     /// Sleep time (in hours) of the actor responsible for deleting failed transactions from the database.
     pub rejected_transactions_cleaner_interval: u64,
+    /// The queries executed longer than this amount of milliseconds are logged with their SQL.
+    pub slow_query_threshold_ms: u64,
+    /// The history of the blocks older than this amount of blocks before the last verified block is pruned.
+    pub pruning_retention_blocks: u32,
+    /// Amount of blocks pruned at once, the progress is stored after pruning each of the batches.
+    pub pruning_blocks_per_iteration: u32,
+    /// Sleep time (in seconds) of the pruner once the history is pruned up to the retention horizon.
+    pub pruning_interval: u64,
 }
 
 impl DBConfig {
@@ -34,6 +42,14 @@ impl DBConfig {
     pub fn rejected_transactions_cleaner_interval(&self) -> time::Duration {
         time::Duration::from_secs(self.rejected_transactions_cleaner_interval)
     }
+
+    pub fn slow_query_threshold(&self) -> time::Duration {
+        time::Duration::from_millis(self.slow_query_threshold_ms)
+    }
+
+    pub fn pruning_interval(&self) -> time::Duration {
+        time::Duration::from_secs(self.pruning_interval)
+    }
 }
 
 #[cfg(test)]
@@ -47,6 +63,10 @@ mod tests {
             url: "postgres://postgres@localhost/plasma".into(),
             rejected_transactions_max_age: 336,
             rejected_transactions_cleaner_interval: 24,
+            slow_query_threshold_ms: 500,
+            pruning_retention_blocks: 10000,
+            pruning_blocks_per_iteration: 100,
+            pruning_interval: 600,
         }
     }
 
@@ -57,6 +77,10 @@ DATABASE_POOL_SIZE="10"
 DATABASE_URL="postgres://postgres@localhost/plasma"
 DATABASE_REJECTED_TRANSACTIONS_MAX_AGE="336"
 DATABASE_REJECTED_TRANSACTIONS_CLEANER_INTERVAL="24"
+DATABASE_SLOW_QUERY_THRESHOLD_MS="500"
+DATABASE_PRUNING_RETENTION_BLOCKS="10000"
+DATABASE_PRUNING_BLOCKS_PER_ITERATION="100"
+DATABASE_PRUNING_INTERVAL="600"
         "#;
         set_env(config);
 
@@ -14,6 +14,10 @@ pub struct ETHSenderConfig {
     pub sender: Sender,
     /// Options related to the `gas_adjuster` submodule.
     pub gas_price_limit: GasLimit,
+    /// Options related to the gas price oracle.
+    pub gas_price_oracle: GasOracle,
+    /// Options related to the resubmission of the stuck transactions.
+    pub resubmission: Resubmission,
 }
 
 impl ETHSenderConfig {
@@ -24,16 +28,38 @@ impl ETHSenderConfig {
                 "eth_sender.gas_price_limit",
                 "ETH_SENDER_GAS_PRICE_LIMIT_"
             ),
+            gas_price_oracle: envy_load!(
+                "eth_sender.gas_price_oracle",
+                "ETH_SENDER_GAS_PRICE_ORACLE_"
+            ),
+            resubmission: envy_load!("eth_sender.resubmission", "ETH_SENDER_RESUBMISSION_"),
         }
     }
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct Sender {
-    /// Private key of the operator account.
+    /// Private key of the operator account, not used if the remote signer is configured.
+    #[serde(default)]
     pub operator_private_key: H256,
     /// Address of the operator account.
     pub operator_commit_eth_addr: Address,
+    /// URL of the remote signer (e.g. Web3Signer) keeping the keys of the operator accounts.
+    /// If set, the transactions are signed by it instead of the private keys from the config.
+    #[serde(default)]
+    pub remote_signer_url: Option<String>,
+    /// Addresses of the reserve operator accounts whose keys are kept by the remote signer.
+    #[serde(default)]
+    pub reserve_operator_addresses: Vec<Address>,
+    /// Private keys of the reserve operator accounts, the Ethereum transactions are sent by them
+    /// once the active operator account runs low on funds or its transactions get stuck.
+    /// All the operator accounts must be the validators of the zkSync contract.
+    #[serde(default)]
+    pub reserve_operator_private_keys: Vec<H256>,
+    /// Minimal balance of the operator account in wei, the operator account is rotated if its balance is lower.
+    pub min_operator_balance: u64,
+    /// Amount of the replacements of the stuck transaction after which the operator account is rotated.
+    pub max_replacements_per_operator: u64,
     /// mount of confirmations required to consider L1 transaction committed.
     pub wait_confirmations: u64,
     /// Amount of blocks we will wait before considering L1 transaction stuck.
@@ -44,6 +70,8 @@ pub struct Sender {
     pub max_txs_in_flight: u64,
     /// Whether sender should interact with L1 or not.
     pub is_enabled: bool,
+    /// Whether sender should send the EIP-1559 transactions, must be disabled for the chains without EIP-1559.
+    pub is_eip1559_enabled: bool,
 }
 
 impl Sender {
@@ -63,6 +91,8 @@ pub struct GasLimit {
     pub sample_interval: u64,
     /// Scale factor for gas price limit (used by GasAdjuster).
     pub scale_factor: f64,
+    /// Priority fee per gas (the miner tip) for the new EIP-1559 transactions in wei.
+    pub priority_fee_per_gas: u64,
 }
 
 impl GasLimit {
@@ -77,6 +107,48 @@ impl GasLimit {
     }
 }
 
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct GasOracle {
+    /// Sources of the gas price: `node` (the `eth_gasPrice` of the node), `fee_history`
+    /// (the base fees of the latest blocks) and `external_api`. The median of the prices is used.
+    pub sources: Vec<String>,
+    /// Amount of the latest blocks taken into account by the `fee_history` source.
+    pub fee_history_blocks: u64,
+    /// URL of the external gas price API, required by the `external_api` source.
+    #[serde(default)]
+    pub external_api_url: Option<String>,
+    /// JSON pointer to the gas price in gwei in the response of the external API.
+    pub external_api_json_pointer: String,
+    /// Lower bound of the gas price reported by the oracle in wei.
+    pub min_gas_price: u64,
+    /// Upper bound of the gas price reported by the oracle in wei.
+    pub max_gas_price: u64,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Resubmission {
+    /// The maximum amount of the transactions sent for one operation. Once it's reached,
+    /// the stuck transaction isn't replaced until the replacement is requested manually.
+    pub max_attempts: u64,
+    /// Increase of the gas price in percents for every replacement of the stuck transaction,
+    /// the last value is used for the further replacements.
+    pub fee_increase_percents: Vec<u64>,
+    /// Amount of blocks since the first transaction of the operation after which the stuck
+    /// transaction isn't replaced automatically anymore, 0 means no deadline.
+    pub deadline_blocks: u64,
+}
+
+impl Resubmission {
+    /// Returns the gas price increase in percents for the replacement with the given number, starting from 0.
+    pub fn fee_increase_percent(&self, replacement: usize) -> u64 {
+        self.fee_increase_percents
+            .get(replacement)
+            .or_else(|| self.fee_increase_percents.last())
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -90,16 +162,39 @@ mod tests {
                 tx_poll_period: 3,
                 max_txs_in_flight: 3,
                 is_enabled: true,
+                is_eip1559_enabled: true,
                 operator_private_key: hash(
                     "27593fea79697e947890ecbecce7901b0008345e5d7259710d0dd5e500d040be",
                 ),
                 operator_commit_eth_addr: addr("de03a0B5963f75f1C8485B355fF6D30f3093BDE7"),
+                remote_signer_url: Some("http://127.0.0.1:9000".into()),
+                reserve_operator_addresses: vec![addr("36615cf349d7f6344891b1e7ca7c72883f5dc049")],
+                reserve_operator_private_keys: vec![
+                    hash("d4e2d9d3b4ee56b4bd0ed2c14b3b31a6b3b2e3a6f2afe0fc8ef6e4c8b1b0c4a1"),
+                    hash("4c3b9b9ae0b1b3a6c5e9b5e5a1bb7a1f1f2f5e7d2a0c5bbf1e8d6e4c1b9a3a27"),
+                ],
+                min_operator_balance: 100000000000000000,
+                max_replacements_per_operator: 5,
             },
             gas_price_limit: GasLimit {
                 default: 400000000000,
                 update_interval: 150,
                 sample_interval: 15,
                 scale_factor: 1.0f64,
+                priority_fee_per_gas: 1500000000,
+            },
+            gas_price_oracle: GasOracle {
+                sources: vec!["node".into(), "fee_history".into(), "external_api".into()],
+                fee_history_blocks: 10,
+                external_api_url: Some("https://api.etherscan.io/api?module=gastracker".into()),
+                external_api_json_pointer: "/result/ProposeGasPrice".into(),
+                min_gas_price: 1000000000,
+                max_gas_price: 1000000000000,
+            },
+            resubmission: Resubmission {
+                max_attempts: 10,
+                fee_increase_percents: vec![15, 20, 30],
+                deadline_blocks: 300,
             },
         }
     }
@@ -112,12 +207,28 @@ ETH_SENDER_SENDER_EXPECTED_WAIT_TIME_BLOCK="30"
 ETH_SENDER_SENDER_TX_POLL_PERIOD="3"
 ETH_SENDER_SENDER_MAX_TXS_IN_FLIGHT="3"
 ETH_SENDER_SENDER_IS_ENABLED="true"
+ETH_SENDER_SENDER_IS_EIP1559_ENABLED="true"
 ETH_SENDER_SENDER_OPERATOR_PRIVATE_KEY="0x27593fea79697e947890ecbecce7901b0008345e5d7259710d0dd5e500d040be"
 ETH_SENDER_SENDER_OPERATOR_COMMIT_ETH_ADDR="0xde03a0B5963f75f1C8485B355fF6D30f3093BDE7"
+ETH_SENDER_SENDER_REMOTE_SIGNER_URL="http://127.0.0.1:9000"
+ETH_SENDER_SENDER_RESERVE_OPERATOR_ADDRESSES="0x36615cf349d7f6344891b1e7ca7c72883f5dc049"
+ETH_SENDER_SENDER_RESERVE_OPERATOR_PRIVATE_KEYS="0xd4e2d9d3b4ee56b4bd0ed2c14b3b31a6b3b2e3a6f2afe0fc8ef6e4c8b1b0c4a1,0x4c3b9b9ae0b1b3a6c5e9b5e5a1bb7a1f1f2f5e7d2a0c5bbf1e8d6e4c1b9a3a27"
+ETH_SENDER_SENDER_MIN_OPERATOR_BALANCE="100000000000000000"
+ETH_SENDER_SENDER_MAX_REPLACEMENTS_PER_OPERATOR="5"
 ETH_SENDER_GAS_PRICE_LIMIT_DEFAULT="400000000000"
 ETH_SENDER_GAS_PRICE_LIMIT_UPDATE_INTERVAL="150"
 ETH_SENDER_GAS_PRICE_LIMIT_SAMPLE_INTERVAL="15"
 ETH_SENDER_GAS_PRICE_LIMIT_SCALE_FACTOR="1"
+ETH_SENDER_GAS_PRICE_LIMIT_PRIORITY_FEE_PER_GAS="1500000000"
+ETH_SENDER_GAS_PRICE_ORACLE_SOURCES="node,fee_history,external_api"
+ETH_SENDER_GAS_PRICE_ORACLE_FEE_HISTORY_BLOCKS="10"
+ETH_SENDER_GAS_PRICE_ORACLE_EXTERNAL_API_URL="https://api.etherscan.io/api?module=gastracker"
+ETH_SENDER_GAS_PRICE_ORACLE_EXTERNAL_API_JSON_POINTER="/result/ProposeGasPrice"
+ETH_SENDER_GAS_PRICE_ORACLE_MIN_GAS_PRICE="1000000000"
+ETH_SENDER_GAS_PRICE_ORACLE_MAX_GAS_PRICE="1000000000000"
+ETH_SENDER_RESUBMISSION_MAX_ATTEMPTS="10"
+ETH_SENDER_RESUBMISSION_FEE_INCREASE_PERCENTS="15,20,30"
+ETH_SENDER_RESUBMISSION_DEADLINE_BLOCKS="300"
         "#;
         set_env(config);
 
@@ -143,5 +254,9 @@ ETH_SENDER_GAS_PRICE_LIMIT_SCALE_FACTOR="1"
             config.gas_price_limit.sample_interval(),
             Duration::from_secs(config.gas_price_limit.sample_interval)
         );
+
+        assert_eq!(config.resubmission.fee_increase_percent(0), 15);
+        assert_eq!(config.resubmission.fee_increase_percent(2), 30);
+        assert_eq!(config.resubmission.fee_increase_percent(5), 30);
     }
 }
@@ -1,5 +1,6 @@
 // Built-in uses
 use std::net::SocketAddr;
+use std::time::Duration;
 
 // External uses
 use serde::Deserialize;
@@ -7,6 +8,15 @@ use serde::Deserialize;
 // Local uses
 use crate::envy_load;
 
+/// Broker the chain events are published to.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+pub enum EventStreamBackend {
+    /// The chain events aren't published.
+    Disabled,
+    Kafka,
+    Nats,
+}
+
 /// Configuration for the Event listener crate.
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct EventListenerConfig {
@@ -17,6 +27,21 @@ pub struct EventListenerConfig {
     /// PostgreSQL channel name to listen on. Must be equal to the one
     /// hardcoded into database migrations.
     pub channel_name: String,
+    /// Maximum amount of attempts to deliver the event to the webhook.
+    pub webhook_max_attempts: u32,
+    /// Delay before the first retry of the failed delivery, doubled for every next retry.
+    pub webhook_retry_interval_sec: u64,
+    /// Timeout of the request delivering the event to the webhook.
+    pub webhook_timeout_ms: u64,
+    /// Interval of polling the database for the deliveries due.
+    pub webhook_delivery_interval_ms: u64,
+    /// Broker the block and transaction events are published to.
+    pub stream_backend: EventStreamBackend,
+    /// Comma-separated addresses of the Kafka brokers or the URL of the NATS server.
+    pub stream_url: String,
+    /// Prefix of the Kafka topics or the NATS subjects, the events of every type
+    /// are published to the `<prefix>.<event_type>` one.
+    pub stream_topic_prefix: String,
 }
 
 impl EventListenerConfig {
@@ -27,6 +52,18 @@ impl EventListenerConfig {
     pub fn ws_bind_addr(&self) -> SocketAddr {
         SocketAddr::new("0.0.0.0".parse().unwrap(), self.ws_port)
     }
+
+    pub fn webhook_retry_interval(&self) -> Duration {
+        Duration::from_secs(self.webhook_retry_interval_sec)
+    }
+
+    pub fn webhook_timeout(&self) -> Duration {
+        Duration::from_millis(self.webhook_timeout_ms)
+    }
+
+    pub fn webhook_delivery_interval(&self) -> Duration {
+        Duration::from_millis(self.webhook_delivery_interval_ms)
+    }
 }
 
 #[cfg(test)]
@@ -40,6 +77,13 @@ mod tests {
             ws_port: 65535,
             ws_url: "ws://localhost:12345".into(),
             channel_name: "zksync_event_channel".into(),
+            webhook_max_attempts: 8,
+            webhook_retry_interval_sec: 10,
+            webhook_timeout_ms: 5000,
+            webhook_delivery_interval_ms: 1000,
+            stream_backend: EventStreamBackend::Kafka,
+            stream_url: "localhost:9092".into(),
+            stream_topic_prefix: "zksync".into(),
         }
     }
 
@@ -49,6 +93,13 @@ mod tests {
 EVENT_LISTENER_WS_URL="ws://localhost:12345"
 EVENT_LISTENER_WS_PORT="65535"
 EVENT_LISTENER_CHANNEL_NAME="zksync_event_channel"
+EVENT_LISTENER_WEBHOOK_MAX_ATTEMPTS="8"
+EVENT_LISTENER_WEBHOOK_RETRY_INTERVAL_SEC="10"
+EVENT_LISTENER_WEBHOOK_TIMEOUT_MS="5000"
+EVENT_LISTENER_WEBHOOK_DELIVERY_INTERVAL_MS="1000"
+EVENT_LISTENER_STREAM_BACKEND="Kafka"
+EVENT_LISTENER_STREAM_URL="localhost:9092"
+EVENT_LISTENER_STREAM_TOPIC_PREFIX="zksync"
         "#;
         set_env(config);
 
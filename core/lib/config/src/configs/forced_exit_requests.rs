@@ -28,6 +28,10 @@ struct ForcedExitRequestsInternalConfig {
     pub expiration_period: u64,
     pub blocks_check_amount: u64,
     pub eth_node_poll_interval: u64,
+    pub automation_enabled: bool,
+    pub automation_batch_size: u32,
+    pub automation_max_wait: u64,
+    pub automation_interval: u64,
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -45,6 +49,10 @@ pub struct ForcedExitRequestsConfig {
     pub expiration_period: u64,
     pub blocks_check_amount: u64,
     pub eth_node_poll_interval: u64,
+    pub automation_enabled: bool,
+    pub automation_batch_size: u32,
+    pub automation_max_wait: u64,
+    pub automation_interval: u64,
 }
 
 // Checks that in no way the price will overlap with the requests id space
@@ -87,10 +95,24 @@ impl ForcedExitRequestsConfig {
             expiration_period: config.expiration_period,
             blocks_check_amount: config.blocks_check_amount,
             eth_node_poll_interval: config.eth_node_poll_interval,
+            automation_enabled: config.automation_enabled,
+            automation_batch_size: config.automation_batch_size,
+            automation_max_wait: config.automation_max_wait,
+            automation_interval: config.automation_interval,
         }
     }
 
     pub fn poll_interval(&self) -> Duration {
         Duration::from_millis(self.eth_node_poll_interval)
     }
+
+    /// How often the pending requests are checked by the automation.
+    pub fn automation_interval(&self) -> Duration {
+        Duration::from_secs(self.automation_interval)
+    }
+
+    /// How long the oldest pending request may wait for the batch to be filled.
+    pub fn automation_max_wait(&self) -> Duration {
+        Duration::from_secs(self.automation_max_wait)
+    }
 }
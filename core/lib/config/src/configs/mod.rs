@@ -4,8 +4,8 @@ pub use self::{
     dev_liquidity_token_watcher::DevLiquidityTokenWatcherConfig, eth_client::ETHClientConfig,
     eth_sender::ETHSenderConfig, eth_watch::ETHWatchConfig, event_listener::EventListenerConfig,
     forced_exit_requests::ForcedExitRequestsConfig, gateway_watcher::GatewayWatcherConfig,
-    misc::MiscConfig, prover::ProverConfig, ticker::TickerConfig,
-    token_handler::TokenHandlerConfig,
+    misc::MiscConfig, network_profile::NetworkProfile, prover::ProverConfig,
+    reloadable::ReloadableConfig, ticker::TickerConfig, token_handler::TokenHandlerConfig,
 };
 
 pub mod api;
@@ -20,7 +20,9 @@ pub mod event_listener;
 pub mod forced_exit_requests;
 pub mod gateway_watcher;
 pub mod misc;
+pub mod network_profile;
 pub mod prover;
+pub mod reloadable;
 pub mod ticker;
 pub mod token_handler;
 
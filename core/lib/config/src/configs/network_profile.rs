@@ -0,0 +1,164 @@
+//! Named network profiles: the L1 endpoints, the contract addresses and the fee ticker settings
+//! of each network, so the network can be switched without editing every config.
+//!
+//! The profile is selected by the `ZKSYNC_NETWORK_PROFILE` variable or by the `network_profile`
+//! argument of the server and the prover. The variables of the selected profile, named
+//! `NETWORK_PROFILE_<PROFILE>_<VARIABLE>`, override the corresponding `<VARIABLE>` before the
+//! configs are loaded.
+
+// Built-in uses
+use std::{env, fmt, str::FromStr};
+
+/// Variable selecting the network profile.
+pub const NETWORK_PROFILE_VARIABLE: &str = "ZKSYNC_NETWORK_PROFILE";
+
+/// Prefixes of the configs which can be set by the network profile.
+const PROFILE_CONFIGS: &[&str] = &["CHAIN_ETH_", "ETH_CLIENT_", "CONTRACTS_", "FEE_TICKER_"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetworkProfile {
+    Mainnet,
+    Testnet,
+    Local,
+}
+
+impl NetworkProfile {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Mainnet => "mainnet",
+            Self::Testnet => "testnet",
+            Self::Local => "local",
+        }
+    }
+
+    /// Returns the profile selected by the `ZKSYNC_NETWORK_PROFILE` variable, if any.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the selected profile is unknown.
+    pub fn from_env() -> Option<Self> {
+        let profile = env::var(NETWORK_PROFILE_VARIABLE)
+            .ok()
+            .filter(|profile| !profile.is_empty())?;
+        Some(profile.parse().unwrap_or_else(|err| panic!("{}", err)))
+    }
+
+    /// Returns the config variables set by the profile, given the variables of the environment.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the profile sets a variable of the config which doesn't depend on the network.
+    pub fn overrides(
+        &self,
+        variables: impl IntoIterator<Item = (String, String)>,
+    ) -> Vec<(String, String)> {
+        let prefix = format!("NETWORK_PROFILE_{}_", self.as_str().to_uppercase());
+        variables
+            .into_iter()
+            .filter_map(|(name, value)| {
+                let name = name.strip_prefix(&prefix)?.to_string();
+                assert!(
+                    PROFILE_CONFIGS
+                        .iter()
+                        .any(|config| name.starts_with(config)),
+                    "Variable {} can't be set by the network profile <{}>",
+                    name,
+                    self
+                );
+                Some((name, value))
+            })
+            .collect()
+    }
+
+    /// Sets the config variables of the profile, the configs loaded afterwards use them.
+    pub fn apply(&self) {
+        for (name, value) in self.overrides(env::vars()) {
+            env::set_var(name, value);
+        }
+    }
+}
+
+impl FromStr for NetworkProfile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "mainnet" => Ok(Self::Mainnet),
+            "testnet" => Ok(Self::Testnet),
+            "local" => Ok(Self::Local),
+            other => Err(format!("{} is not a valid network profile", other)),
+        }
+    }
+}
+
+impl fmt::Display for NetworkProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Applies the network profile selected by the argument or, if it isn't provided, by the
+/// `ZKSYNC_NETWORK_PROFILE` variable. Returns the applied profile.
+pub fn apply_network_profile(profile: Option<NetworkProfile>) -> Option<NetworkProfile> {
+    let profile = profile.or_else(NetworkProfile::from_env)?;
+    // The configs loaded by the components later on must use the same profile.
+    env::set_var(NETWORK_PROFILE_VARIABLE, profile.as_str());
+    profile.apply();
+    Some(profile)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variables(variables: &[(&str, &str)]) -> Vec<(String, String)> {
+        variables
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn profile_overrides() {
+        let environment = variables(&[
+            ("ETH_CLIENT_CHAIN_ID", "9"),
+            ("NETWORK_PROFILE_MAINNET_ETH_CLIENT_CHAIN_ID", "1"),
+            ("NETWORK_PROFILE_MAINNET_CHAIN_ETH_NETWORK", "mainnet"),
+            ("NETWORK_PROFILE_TESTNET_ETH_CLIENT_CHAIN_ID", "4"),
+        ]);
+
+        assert_eq!(
+            NetworkProfile::Mainnet.overrides(environment.clone()),
+            variables(&[
+                ("ETH_CLIENT_CHAIN_ID", "1"),
+                ("CHAIN_ETH_NETWORK", "mainnet"),
+            ])
+        );
+        assert_eq!(
+            NetworkProfile::Testnet.overrides(environment.clone()),
+            variables(&[("ETH_CLIENT_CHAIN_ID", "4")])
+        );
+        assert!(NetworkProfile::Local.overrides(environment).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "can't be set by the network profile")]
+    fn profile_overrides_network_configs_only() {
+        NetworkProfile::Local.overrides(variables(&[(
+            "NETWORK_PROFILE_LOCAL_DATABASE_URL",
+            "postgres://localhost/zksync",
+        )]));
+    }
+
+    #[test]
+    fn profile_names() {
+        for profile in &[
+            NetworkProfile::Mainnet,
+            NetworkProfile::Testnet,
+            NetworkProfile::Local,
+        ] {
+            assert_eq!(profile.to_string().parse::<NetworkProfile>(), Ok(*profile));
+        }
+        assert!("rinkeby".parse::<NetworkProfile>().is_err());
+    }
+}
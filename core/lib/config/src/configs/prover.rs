@@ -56,7 +56,8 @@ impl Prover {
 /// Core settings related to the prover applications interacting with it.
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct Core {
-    /// Timeout to consider prover gone in ms.
+    /// Timeout to consider prover gone in ms. The jobs are leased to the provers for this duration,
+    /// and the job is given to another prover if its lease isn't renewed by the heartbeat.
     pub gone_timeout: u64,
     /// Amount of provers in the cluser if there is no pending jobs.
     pub idle_provers: u32,
@@ -73,8 +74,12 @@ impl Core {
 pub struct WitnessGenerator {
     /// Interval to check whether a new witness generation job should be started in ms.
     pub prepare_data_interval: u64,
-    /// Amount of witness generator threads.
+    /// Maximum amount of blocks whose witnesses are generated concurrently.
     pub witness_generators: usize,
+    /// Index of the shard of blocks processed by this witness generator.
+    pub shard_index: u32,
+    /// Amount of witness generator shards, the blocks are distributed between them by their numbers.
+    pub shards_count: u32,
 }
 
 impl WitnessGenerator {
@@ -104,6 +109,8 @@ mod tests {
             witness_generator: WitnessGenerator {
                 prepare_data_interval: 500,
                 witness_generators: 2,
+                shard_index: 0,
+                shards_count: 1,
             },
         }
     }
@@ -119,6 +126,8 @@ PROVER_CORE_GONE_TIMEOUT="60000"
 PROVER_CORE_IDLE_PROVERS="1"
 PROVER_WITNESS_GENERATOR_PREPARE_DATA_INTERVAL="500"
 PROVER_WITNESS_GENERATOR_WITNESS_GENERATORS="2"
+PROVER_WITNESS_GENERATOR_SHARD_INDEX="0"
+PROVER_WITNESS_GENERATOR_SHARDS_COUNT="1"
         "#;
         set_env(config);
 
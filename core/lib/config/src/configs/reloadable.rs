@@ -0,0 +1,166 @@
+// Built-in uses
+use std::{collections::HashMap, env, fs, path::Path};
+// External uses
+use serde::{Deserialize, Serialize};
+// Workspace uses
+use zksync_types::Address;
+// Local uses
+use crate::ZkSyncConfig;
+
+/// Variable with the path of the env file the config was loaded from, it's set by the `zk` tool.
+pub const ENV_FILE_VARIABLE: &str = "ENV_FILE";
+
+/// Parameters of the other configs which can be changed without restarting the server.
+///
+/// The parameters are re-read from the env file on reload, the variables missing in it
+/// are taken from the environment of the process.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ReloadableConfig {
+    /// `FEE_TICKER_LIQUIDITY_VOLUME`, the tokens with a lower market volume can't be used for paying fees.
+    pub liquidity_volume: f64,
+    /// `API_REST_REQUESTS_PER_SECOND_PER_IP`, the limit of the requests of the clients without API keys.
+    pub requests_per_second_per_ip: u32,
+    /// `TOKEN_HANDLER_DENYLISTED_TOKENS`, the tokens denylisted by the config.
+    pub denylisted_tokens: Vec<Address>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TickerSection {
+    liquidity_volume: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct RestApiSection {
+    requests_per_second_per_ip: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenHandlerSection {
+    denylisted_tokens: Vec<Address>,
+}
+
+impl ReloadableConfig {
+    pub fn from_config(config: &ZkSyncConfig) -> Self {
+        Self {
+            liquidity_volume: config.ticker.liquidity_volume,
+            requests_per_second_per_ip: config.api.rest.requests_per_second_per_ip,
+            denylisted_tokens: config.token_handler.denylisted_tokens.clone(),
+        }
+    }
+
+    /// Loads the config from the env file set in the `ENV_FILE` variable.
+    pub fn reload() -> anyhow::Result<Self> {
+        let env_file = env::var(ENV_FILE_VARIABLE)
+            .map_err(|_| anyhow::anyhow!("{} variable is not set", ENV_FILE_VARIABLE))?;
+        Self::from_env_file(env_file)
+    }
+
+    /// Loads the config from the env file, the variables missing in it are taken from the environment.
+    pub fn from_env_file(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)
+            .map_err(|err| anyhow::anyhow!("Can't read {}: {}", path.display(), err))?;
+        let mut vars: HashMap<String, String> = env::vars().collect();
+        vars.extend(parse_env_file(&contents));
+        Self::from_vars(vars)
+    }
+
+    fn from_vars(vars: HashMap<String, String>) -> anyhow::Result<Self> {
+        let ticker: TickerSection = envy::prefixed("FEE_TICKER_").from_iter(vars.clone())?;
+        let rest_api: RestApiSection = envy::prefixed("API_REST_").from_iter(vars.clone())?;
+        let token_handler: TokenHandlerSection =
+            envy::prefixed("TOKEN_HANDLER_").from_iter(vars)?;
+        let config = Self {
+            liquidity_volume: ticker.liquidity_volume,
+            requests_per_second_per_ip: rest_api.requests_per_second_per_ip,
+            denylisted_tokens: token_handler.denylisted_tokens,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Checks the parameters which are accepted by the deserialization but can't be applied.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if !self.liquidity_volume.is_finite() || self.liquidity_volume < 0.0 {
+            anyhow::bail!(
+                "The liquidity volume must be a finite non-negative number, got {}",
+                self.liquidity_volume
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Parses the `VARIABLE_NAME=variable_value` lines of the env file, skipping the comments.
+fn parse_env_file(contents: &str) -> impl Iterator<Item = (String, String)> + '_ {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let (name, value) = line.split_once('=')?;
+            Some((
+                name.trim().to_string(),
+                value.trim().trim_matches('"').to_string(),
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::test_utils::addr;
+
+    #[test]
+    fn from_vars() {
+        let env_file = r#"
+# The reloadable parameters.
+FEE_TICKER_LIQUIDITY_VOLUME=250.5
+API_REST_REQUESTS_PER_SECOND_PER_IP=20
+TOKEN_HANDLER_DENYLISTED_TOKENS="0x38A2fDc11f526Ddd5a607C1F251C065f40fBF2f7"
+TOKEN_HANDLER_POLL_INTERVAL=1
+        "#;
+        let config = ReloadableConfig::from_vars(parse_env_file(env_file).collect()).unwrap();
+        assert_eq!(
+            config,
+            ReloadableConfig {
+                liquidity_volume: 250.5,
+                requests_per_second_per_ip: 20,
+                denylisted_tokens: vec![addr("38A2fDc11f526Ddd5a607C1F251C065f40fBF2f7")],
+            }
+        );
+
+        // The invalid values are rejected rather than panicking.
+        let env_file = r#"
+FEE_TICKER_LIQUIDITY_VOLUME=-1
+API_REST_REQUESTS_PER_SECOND_PER_IP=20
+TOKEN_HANDLER_DENYLISTED_TOKENS=
+        "#;
+        assert!(ReloadableConfig::from_vars(parse_env_file(env_file).collect()).is_err());
+        assert!(ReloadableConfig::from_vars(HashMap::new()).is_err());
+        for liquidity_volume in ["NaN", "inf", "-inf"] {
+            let vars = parse_env_file(env_file).chain(std::iter::once((
+                "FEE_TICKER_LIQUIDITY_VOLUME".to_string(),
+                liquidity_volume.to_string(),
+            )));
+            assert!(ReloadableConfig::from_vars(vars.collect()).is_err());
+        }
+    }
+
+    #[test]
+    fn validate() {
+        let config = ReloadableConfig {
+            liquidity_volume: 0.0,
+            requests_per_second_per_ip: 20,
+            denylisted_tokens: vec![],
+        };
+        assert!(config.validate().is_ok());
+        for liquidity_volume in [-1.0, f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let config = ReloadableConfig {
+                liquidity_volume,
+                ..config.clone()
+            };
+            assert!(config.validate().is_err());
+        }
+    }
+}
@@ -1,5 +1,7 @@
-use num::{rational::Ratio, BigUint};
+// Built-in uses
+use std::time::Duration;
 // External uses
+use num::{rational::Ratio, BigUint};
 use serde::Deserialize;
 // Workspace uses
 use zksync_types::Address;
@@ -7,10 +9,15 @@ use zksync_utils::scaled_u64_to_ratio;
 // Local uses
 use crate::envy_load;
 
+/// Snapshots of the token prices older than this number of days are removed by default.
+pub const DEFAULT_PRICE_HISTORY_RETENTION_DAYS: u64 = 90;
+
 #[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
 pub enum TokenPriceSource {
     CoinGecko,
     CoinMarketCap,
+    UniswapV2,
+    UniswapV3,
 }
 
 /// Configuration for the fee ticker.
@@ -18,12 +25,31 @@ pub enum TokenPriceSource {
 pub struct TickerConfig {
     /// Indicator of the API to be used for getting token prices.
     pub token_price_source: TokenPriceSource,
+    /// APIs to get the token prices from if the `token_price_source` fails, in the order of preference.
+    #[serde(default)]
+    pub fallback_price_sources: Vec<TokenPriceSource>,
+    /// Prices updated earlier than this number of seconds ago are considered stale,
+    /// and are taken from the next price source.
+    pub max_price_age_sec: u64,
+    /// Maximum deviation of the price update from the last accepted price in percents,
+    /// the bigger deviations are rejected. Set to `0` to accept any update.
+    pub max_price_deviation_percent: u32,
+    /// Updates are compared with the last accepted price only if it was updated
+    /// less than this number of seconds ago.
+    pub price_deviation_window_sec: u64,
     /// URL of CoinMarketCap API. Can be set to the mock server for local development.
     pub coinmarketcap_base_url: String,
     /// URL of CoinGecko API. Can be set to the mock server for local development.
     pub coingecko_base_url: String,
     /// Coefficient for scaling all fees in percent.
     pub scale_fee_percent: u32,
+    /// Lower bound of the gas price multiplier in percent, applied while the gas price is stable.
+    pub min_gas_price_scale_percent: u32,
+    /// Upper bound of the gas price multiplier in percent, applied while the gas price is rising fast.
+    pub max_gas_price_scale_percent: u32,
+    /// Time constant of the gas price smoothing in seconds, the multiplier reacts to
+    /// the gas price changes slower the bigger it is.
+    pub gas_price_smoothing_sec: u64,
     /// Coefficient for the fee price for fast withdrawal requests.
     pub fast_processing_coeff: f64,
     /// Url to uniswap api
@@ -40,6 +66,21 @@ pub struct TickerConfig {
     pub number_of_ticker_actors: u8,
     /// Subsidized price for ChangePubKey in cents scaled by SUBSIDY_USD_AMOUNTS_SCALE
     pub subsidy_cpk_price_usd_scaled: u64,
+    /// Url to the Uniswap V3 api, used for the time-weighted average prices.
+    pub uniswap_v3_url: String,
+    /// Tokens priced by the Uniswap V2 pools instead of the `token_price_source`.
+    #[serde(default)]
+    pub uniswap_v2_price_tokens: Vec<Address>,
+    /// Tokens priced by the Uniswap V3 pools instead of the `token_price_source`.
+    #[serde(default)]
+    pub uniswap_v3_price_tokens: Vec<Address>,
+    /// Chainlink USD price feeds of the tokens priced by them instead of the `token_price_source`,
+    /// as the `<token address>:<feed address>` pairs.
+    #[serde(default)]
+    pub chainlink_price_feeds: Vec<String>,
+    /// Snapshots of the token prices are kept in the price history for this number of days.
+    #[serde(default = "TickerConfig::default_price_history_retention_days")]
+    pub price_history_retention_days: u64,
 }
 
 impl TickerConfig {
@@ -53,11 +94,72 @@ impl TickerConfig {
 
     /// Returns the token price source type and the corresponding API URL.
     pub fn price_source(&self) -> (TokenPriceSource, String) {
-        let url = match self.token_price_source {
+        (
+            self.token_price_source,
+            self.source_url(self.token_price_source),
+        )
+    }
+
+    /// Returns the token price sources in the order of preference with the corresponding API URLs.
+    pub fn price_sources(&self) -> Vec<(TokenPriceSource, String)> {
+        let mut sources = vec![self.token_price_source];
+        for source in &self.fallback_price_sources {
+            if !sources.contains(source) {
+                sources.push(*source);
+            }
+        }
+        sources
+            .into_iter()
+            .map(|source| (source, self.source_url(source)))
+            .collect()
+    }
+
+    pub fn max_price_age(&self) -> Duration {
+        Duration::from_secs(self.max_price_age_sec)
+    }
+
+    pub fn price_deviation_window(&self) -> Duration {
+        Duration::from_secs(self.price_deviation_window_sec)
+    }
+
+    pub fn gas_price_smoothing(&self) -> Duration {
+        Duration::from_secs(self.gas_price_smoothing_sec)
+    }
+
+    pub fn price_history_retention(&self) -> Duration {
+        Duration::from_secs(self.price_history_retention_days * 24 * 60 * 60)
+    }
+
+    fn default_price_history_retention_days() -> u64 {
+        DEFAULT_PRICE_HISTORY_RETENTION_DAYS
+    }
+
+    fn source_url(&self, source: TokenPriceSource) -> String {
+        match source {
             TokenPriceSource::CoinGecko => self.coingecko_base_url.clone(),
             TokenPriceSource::CoinMarketCap => self.coinmarketcap_base_url.clone(),
-        };
-        (self.token_price_source, url)
+            TokenPriceSource::UniswapV2 => self.uniswap_url.clone(),
+            TokenPriceSource::UniswapV3 => self.uniswap_v3_url.clone(),
+        }
+    }
+
+    /// Returns the addresses of the Chainlink price feeds for the tokens.
+    pub fn chainlink_feeds(&self) -> Vec<(Address, Address)> {
+        self.chainlink_price_feeds
+            .iter()
+            .map(|pair| {
+                let (token, feed) = pair
+                    .split_once(':')
+                    .unwrap_or_else(|| panic!("Incorrect Chainlink price feed: {}", pair));
+                let parse = |address: &str| {
+                    address
+                        .trim_start_matches("0x")
+                        .parse::<Address>()
+                        .unwrap_or_else(|_| panic!("Incorrect Chainlink price feed: {}", pair))
+                };
+                (parse(token), parse(feed))
+            })
+            .collect()
     }
 }
 
@@ -69,9 +171,16 @@ mod tests {
     fn expected_config() -> TickerConfig {
         TickerConfig {
             token_price_source: TokenPriceSource::CoinGecko,
+            fallback_price_sources: vec![TokenPriceSource::CoinMarketCap],
+            max_price_age_sec: 7200,
+            max_price_deviation_percent: 20,
+            price_deviation_window_sec: 3600,
             coinmarketcap_base_url: "http://127.0.0.1:9876".into(),
             coingecko_base_url: "http://127.0.0.1:9876".into(),
             scale_fee_percent: 100,
+            min_gas_price_scale_percent: 130,
+            max_gas_price_scale_percent: 200,
+            gas_price_smoothing_sec: 600,
             fast_processing_coeff: 10.0f64,
             uniswap_url: "http://127.0.0.1:9975/graphql".to_string(),
             liquidity_volume: 100.0,
@@ -80,6 +189,14 @@ mod tests {
             token_market_update_time: 120,
             number_of_ticker_actors: 4,
             subsidy_cpk_price_usd_scaled: 100,
+            uniswap_v3_url: "http://127.0.0.1:9975/graphql".to_string(),
+            uniswap_v2_price_tokens: vec![addr("38a2fdc11f526ddd5a607c1f251c065f40fbf2f7")],
+            uniswap_v3_price_tokens: Vec::new(),
+            chainlink_price_feeds: vec![
+                "0x0000000000000000000000000000000000000000:0x5f4ec3df9cbd43714fe2740f5e3616155c5b8419"
+                    .to_string(),
+            ],
+            price_history_retention_days: DEFAULT_PRICE_HISTORY_RETENTION_DAYS,
         }
     }
 
@@ -87,6 +204,10 @@ mod tests {
     fn from_env() {
         let config = r#"
 FEE_TICKER_TOKEN_PRICE_SOURCE="CoinGecko"
+FEE_TICKER_FALLBACK_PRICE_SOURCES="CoinMarketCap"
+FEE_TICKER_MAX_PRICE_AGE_SEC=7200
+FEE_TICKER_MAX_PRICE_DEVIATION_PERCENT=20
+FEE_TICKER_PRICE_DEVIATION_WINDOW_SEC=3600
 FEE_TICKER_COINMARKETCAP_BASE_URL="http://127.0.0.1:9876"
 FEE_TICKER_COINGECKO_BASE_URL="http://127.0.0.1:9876"
 FEE_TICKER_FAST_PROCESSING_COEFF="10"
@@ -98,7 +219,13 @@ FEE_TICKER_LIQUIDITY_VOLUME=100
 FEE_TICKER_NUMBER_OF_TICKER_ACTORS="4"
 FEE_TICKER_SUBSIDIZED_TOKENS_LIMITS=156
 FEE_TICKER_SCALE_FEE_PERCENT=100
+FEE_TICKER_MIN_GAS_PRICE_SCALE_PERCENT=130
+FEE_TICKER_MAX_GAS_PRICE_SCALE_PERCENT=200
+FEE_TICKER_GAS_PRICE_SMOOTHING_SEC=600
 FEE_TICKER_SUBSIDY_CPK_PRICE_USD_SCALED=100
+FEE_TICKER_UNISWAP_V3_URL=http://127.0.0.1:9975/graphql
+FEE_TICKER_UNISWAP_V2_PRICE_TOKENS="0x38A2fDc11f526Ddd5a607C1F251C065f40fBF2f7"
+FEE_TICKER_CHAINLINK_PRICE_FEEDS="0x0000000000000000000000000000000000000000:0x5f4ec3df9cbd43714fe2740f5e3616155c5b8419"
         "#;
         set_env(config);
 
@@ -128,5 +255,33 @@ FEE_TICKER_SUBSIDY_CPK_PRICE_USD_SCALED=100
             config.price_source(),
             (TokenPriceSource::CoinMarketCap, COINMARKETCAP_URL.into())
         );
+
+        config.fallback_price_sources = vec![
+            TokenPriceSource::CoinGecko,
+            TokenPriceSource::CoinMarketCap,
+            TokenPriceSource::UniswapV3,
+        ];
+        assert_eq!(
+            config.price_sources(),
+            vec![
+                (TokenPriceSource::CoinMarketCap, COINMARKETCAP_URL.into()),
+                (TokenPriceSource::CoinGecko, COINGECKO_URL.into()),
+                (TokenPriceSource::UniswapV3, config.uniswap_v3_url.clone()),
+            ]
+        );
+        assert_eq!(config.max_price_age(), Duration::from_secs(7200));
+        assert_eq!(config.price_deviation_window(), Duration::from_secs(3600));
+        assert_eq!(
+            config.price_history_retention(),
+            Duration::from_secs(90 * 24 * 3600)
+        );
+
+        assert_eq!(
+            config.chainlink_feeds(),
+            vec![(
+                addr("0000000000000000000000000000000000000000"),
+                addr("5f4ec3df9cbd43714fe2740f5e3616155c5b8419")
+            )]
+        );
     }
 }
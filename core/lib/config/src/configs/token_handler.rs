@@ -4,7 +4,7 @@ use std::time::Duration;
 // External uses
 use serde::Deserialize;
 // Workspace uses
-use zksync_types::TokenInfo;
+use zksync_types::{Address, TokenInfo};
 // Local uses
 use crate::envy_load;
 
@@ -17,6 +17,13 @@ pub struct TokenHandlerConfig {
     pub poll_interval: u64,
     /// Link to MatterMost channel for token list notification.
     pub webhook_url: String,
+    /// Addresses of the tokens that can't be used in the transactions and are hidden from the API,
+    /// in addition to the ones denylisted by the admin API.
+    pub denylisted_tokens: Vec<Address>,
+    /// The number of seconds between the reconciliations of the stored tokens with the events on L1.
+    pub reconciliation_interval: u64,
+    /// The number of the recent confirmed blocks in which the token events are re-read.
+    pub reconciliation_window: u64,
 }
 
 impl TokenHandlerConfig {
@@ -33,6 +40,11 @@ impl TokenHandlerConfig {
         Duration::from_secs(self.poll_interval)
     }
 
+    /// Converts self.reconciliation_interval into Duration.
+    pub fn reconciliation_interval(&self) -> Duration {
+        Duration::from_secs(self.reconciliation_interval)
+    }
+
     pub fn token_list(&self) -> Vec<TokenInfo> {
         let token_list_name = self.token_list_file();
         let path = format!("./etc/token-lists/{}.json", token_list_name);
@@ -45,13 +57,16 @@ impl TokenHandlerConfig {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::configs::test_utils::set_env;
+    use crate::configs::test_utils::{addr, set_env};
 
     fn expected_config() -> TokenHandlerConfig {
         TokenHandlerConfig {
             token_list_name: "localhost".to_string(),
             poll_interval: 1,
             webhook_url: "http://127.0.0.1".to_string(),
+            denylisted_tokens: vec![addr("38A2fDc11f526Ddd5a607C1F251C065f40fBF2f7")],
+            reconciliation_interval: 600,
+            reconciliation_window: 10000,
         }
     }
 
@@ -61,6 +76,9 @@ mod tests {
 TOKEN_HANDLER_POLL_INTERVAL=1
 TOKEN_HANDLER_WEBHOOK_URL="http://127.0.0.1"
 TOKEN_HANDLER_TOKEN_LIST_NAME="localhost"
+TOKEN_HANDLER_DENYLISTED_TOKENS="0x38A2fDc11f526Ddd5a607C1F251C065f40fBF2f7"
+TOKEN_HANDLER_RECONCILIATION_INTERVAL=600
+TOKEN_HANDLER_RECONCILIATION_WINDOW=10000
         "#;
         set_env(config);
 
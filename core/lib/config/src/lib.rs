@@ -1,15 +1,19 @@
 pub use crate::configs::{
     ApiConfig, ChainConfig, ContractsConfig, DBConfig, DevLiquidityTokenWatcherConfig,
     ETHClientConfig, ETHSenderConfig, ETHWatchConfig, EventListenerConfig,
-    ForcedExitRequestsConfig, GatewayWatcherConfig, MiscConfig, ProverConfig, TickerConfig,
-    TokenHandlerConfig,
+    ForcedExitRequestsConfig, GatewayWatcherConfig, MiscConfig, NetworkProfile, ProverConfig,
+    ReloadableConfig, TickerConfig, TokenHandlerConfig,
 };
 
+use crate::configs::network_profile::apply_network_profile;
+
 pub mod configs;
 pub mod test_config;
 
 #[derive(Debug, Clone)]
 pub struct ZkSyncConfig {
+    /// Network profile the configs are loaded with, if any.
+    pub network_profile: Option<NetworkProfile>,
     pub api: ApiConfig,
     pub chain: ChainConfig,
     pub contracts: ContractsConfig,
@@ -26,8 +30,12 @@ pub struct ZkSyncConfig {
 }
 
 impl ZkSyncConfig {
+    /// Loads the configs, the network profile selected by `ZKSYNC_NETWORK_PROFILE`
+    /// is applied beforehand.
     pub fn from_env() -> Self {
+        let network_profile = apply_network_profile(None);
         Self {
+            network_profile,
             api: ApiConfig::from_env(),
             chain: ChainConfig::from_env(),
             contracts: ContractsConfig::from_env(),
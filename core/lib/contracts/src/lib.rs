@@ -20,6 +20,18 @@ const UPGRADE_GATEKEEPER_CONTRACT_FILE: &str =
 const FORCED_EXIT_CONTRACT_FILE: &str =
     "contracts/artifacts/cache/solpp-generated-contracts/ForcedExit.sol/ForcedExit.json";
 
+/// ABI of the optional metadata functions of the ERC20 tokens, which are not the part of `IERC20`.
+const ERC20_METADATA_ABI: &str = r#"[
+    {"type": "function", "name": "name", "inputs": [], "outputs": [{"name": "", "type": "string"}], "stateMutability": "view"},
+    {"type": "function", "name": "symbol", "inputs": [], "outputs": [{"name": "", "type": "string"}], "stateMutability": "view"},
+    {"type": "function", "name": "decimals", "inputs": [], "outputs": [{"name": "", "type": "uint8"}], "stateMutability": "view"}
+]"#;
+/// ABI of the same functions of the non-standard tokens returning `bytes32` instead of `string` (e.g. MKR).
+const ERC20_BYTES32_METADATA_ABI: &str = r#"[
+    {"type": "function", "name": "name", "inputs": [], "outputs": [{"name": "", "type": "bytes32"}], "stateMutability": "view"},
+    {"type": "function", "name": "symbol", "inputs": [], "outputs": [{"name": "", "type": "bytes32"}], "stateMutability": "view"}
+]"#;
+
 fn read_file_to_json_value(path: &str) -> io::Result<serde_json::Value> {
     let zksync_home = std::env::var("ZKSYNC_HOME").unwrap_or_else(|_| ".".into());
     let path = std::path::Path::new(&zksync_home).join(path);
@@ -91,6 +103,15 @@ pub fn erc20_contract() -> Contract {
     Contract::load(abi_string.as_bytes()).expect("erc20 contract abi")
 }
 
+pub fn erc20_metadata_contract() -> Contract {
+    Contract::load(ERC20_METADATA_ABI.as_bytes()).expect("erc20 metadata contract abi")
+}
+
+pub fn erc20_bytes32_metadata_contract() -> Contract {
+    Contract::load(ERC20_BYTES32_METADATA_ABI.as_bytes())
+        .expect("erc20 bytes32 metadata contract abi")
+}
+
 pub fn eip1271_contract() -> Contract {
     let abi_string = read_file_to_json_value(IEIP1271_CONTRACT_FILE)
         .expect("couldn't read IEIP1271_CONTRACT_FILE")
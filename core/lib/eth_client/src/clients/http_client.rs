@@ -22,12 +22,14 @@ use web3::{
 // Workspace uses
 use zksync_eth_signer::{raw_ethereum_tx::RawTransaction, EthereumSigner};
 
-use crate::ethereum_gateway::{ExecutedTxStatus, FailureInfo, SignedCallResult};
+use crate::ethereum_gateway::{ExecutedTxStatus, FailureInfo, FeeHistory, SignedCallResult};
 /// Gas limit value to be used in transaction if for some reason
 /// gas limit was not set for it.
 ///
 /// This is an emergency value, which will not be used normally.
 const FALLBACK_GAS_LIMIT: u64 = 3_000_000;
+/// Type of the EIP-1559 transactions.
+const EIP1559_TX_TYPE: u64 = 2;
 
 struct ETHDirectClientInner<S: EthereumSigner> {
     eth_signer: S,
@@ -153,6 +155,54 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
         Ok(network_gas_price)
     }
 
+    /// Returns the base fee per gas of the latest block, `None` if the chain doesn't support EIP-1559.
+    pub async fn get_base_fee_per_gas(&self) -> Result<Option<U256>, anyhow::Error> {
+        #[cfg(feature = "with-metrics")]
+        let start = Instant::now();
+        let base_fee_per_gas = self
+            .inner
+            .web3
+            .eth()
+            .block(BlockId::Number(BlockNumber::Latest))
+            .await?
+            .and_then(|block| block.base_fee_per_gas);
+        #[cfg(feature = "with-metrics")]
+        metrics::histogram!("eth_client.direct.get_base_fee_per_gas", start.elapsed());
+        Ok(base_fee_per_gas)
+    }
+
+    /// Returns the fees of the latest blocks, the priority fees are taken at the percentile of the block transactions.
+    pub async fn get_fee_history(
+        &self,
+        block_count: u64,
+        reward_percentile: f64,
+    ) -> Result<FeeHistory, anyhow::Error> {
+        #[cfg(feature = "with-metrics")]
+        let start = Instant::now();
+        let history = self
+            .inner
+            .web3
+            .eth()
+            .fee_history(
+                block_count.into(),
+                BlockNumber::Latest,
+                Some(vec![reward_percentile]),
+            )
+            .await?;
+        let priority_fees_per_gas = history
+            .reward
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|rewards| rewards.first().copied())
+            .collect();
+        #[cfg(feature = "with-metrics")]
+        metrics::histogram!("eth_client.direct.get_fee_history", start.elapsed());
+        Ok(FeeHistory {
+            base_fees_per_gas: history.base_fee_per_gas,
+            priority_fees_per_gas,
+        })
+    }
+
     pub async fn sign_prepared_tx(
         &self,
         data: Vec<u8>,
@@ -171,8 +221,17 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
         #[cfg(feature = "with-metrics")]
         let start = Instant::now();
 
+        // The EIP-1559 transaction is signed if the max fee per gas is set, the legacy one otherwise.
+        let (transaction_type, max_priority_fee_per_gas) = match options.max_fee_per_gas {
+            Some(_) => (
+                Some(U64::from(EIP1559_TX_TYPE)),
+                Some(options.max_priority_fee_per_gas.unwrap_or_default()),
+            ),
+            None => (None, None),
+        };
+
         // fetch current gas_price
-        let gas_price = match options.gas_price {
+        let gas_price = match options.max_fee_per_gas.or(options.gas_price) {
             Some(gas_price) => gas_price,
             None => self.get_gas_price().await?,
         };
@@ -200,16 +259,16 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
         // form and sign tx
         let tx = RawTransaction {
             chain_id: self.inner.chain_id,
-            transaction_type: None,
+            transaction_type,
             access_list: None,
-            max_fee_per_gas: Default::default(),
+            max_fee_per_gas: options.max_fee_per_gas,
             nonce,
             to: Some(contract_addr),
             value: options.value.unwrap_or_default(),
             gas_price,
             gas,
             data,
-            max_priority_fee_per_gas: Default::default(),
+            max_priority_fee_per_gas,
         };
 
         let signed_tx = self.inner.eth_signer.sign_transaction(tx).await?;
@@ -442,6 +501,7 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
                 };
                 let confirmations = current_block.saturating_sub(tx_block_number.as_u64());
                 let success = status.as_u64() == 1;
+                let gas_used = receipt.as_ref().and_then(|receipt| receipt.gas_used);
 
                 // Set the receipt only for failures.
                 let receipt = if success {
@@ -454,6 +514,7 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
                     confirmations,
                     success,
                     receipt,
+                    gas_used,
                 }))
             }
             _ => Ok(None),
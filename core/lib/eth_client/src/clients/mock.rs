@@ -12,7 +12,7 @@ use web3::types::{BlockId, Filter, Log, Transaction, U64};
 use zksync_types::{TransactionReceipt, H160, H256, U256};
 
 use crate::{
-    ethereum_gateway::{ExecutedTxStatus, FailureInfo},
+    ethereum_gateway::{ExecutedTxStatus, FailureInfo, FeeHistory},
     SignedCallResult,
 };
 
@@ -20,6 +20,8 @@ use crate::{
 struct MockEthereumInner {
     block_number: u64,
     gas_price: U256,
+    base_fee_per_gas: Option<U256>,
+    balance: U256,
     tx_statuses: Arc<RwLock<HashMap<H256, ExecutedTxStatus>>>,
     sent_txs: Arc<RwLock<HashSet<Vec<u8>>>>,
 }
@@ -35,6 +37,8 @@ impl Default for MockEthereumInner {
         Self {
             block_number: 1,
             gas_price: 100.into(),
+            base_fee_per_gas: Some(50.into()),
+            balance: U256::exp10(18),
             tx_statuses: Default::default(),
             sent_txs: Default::default(),
         }
@@ -83,6 +87,7 @@ impl MockEthereum {
             confirmations,
             success: true,
             receipt: None,
+            gas_used: None,
         };
         self.inner.tx_statuses.write().await.insert(tx_hash, status);
     }
@@ -95,6 +100,7 @@ impl MockEthereum {
             confirmations,
             success: false,
             receipt: Some(Default::default()),
+            gas_used: None,
         };
         self.inner.tx_statuses.write().await.insert(*hash, status);
     }
@@ -124,6 +130,37 @@ impl MockEthereum {
         Ok(self.inner.gas_price)
     }
 
+    pub async fn get_base_fee_per_gas(&self) -> anyhow::Result<Option<U256>> {
+        Ok(self.inner.base_fee_per_gas)
+    }
+
+    /// Returns the history of the constant base fee, the blocks contain no priority fees.
+    pub async fn get_fee_history(
+        &self,
+        block_count: u64,
+        _reward_percentile: f64,
+    ) -> anyhow::Result<FeeHistory> {
+        let block_count = block_count as usize;
+        let base_fees_per_gas = match self.inner.base_fee_per_gas {
+            Some(base_fee) => vec![base_fee; block_count + 1],
+            None => Vec::new(),
+        };
+        Ok(FeeHistory {
+            base_fees_per_gas,
+            priority_fees_per_gas: vec![U256::zero(); block_count],
+        })
+    }
+
+    pub async fn set_base_fee_per_gas(&mut self, val: Option<U256>) -> anyhow::Result<()> {
+        Arc::get_mut(&mut self.inner).unwrap().base_fee_per_gas = val;
+        Ok(())
+    }
+
+    pub async fn set_balance(&mut self, val: U256) -> anyhow::Result<()> {
+        Arc::get_mut(&mut self.inner).unwrap().balance = val;
+        Ok(())
+    }
+
     pub async fn send_raw_tx(&self, tx: Vec<u8>) -> Result<H256, anyhow::Error> {
         // Cut hash of transaction
         let mut hash: [u8; 32] = Default::default();
@@ -137,13 +174,19 @@ impl MockEthereum {
         raw_tx: Vec<u8>,
         options: Options,
     ) -> anyhow::Result<SignedCallResult> {
-        let gas_price = options.gas_price.unwrap_or(self.inner.gas_price);
+        let gas_price = options
+            .max_fee_per_gas
+            .or(options.gas_price)
+            .unwrap_or(self.inner.gas_price);
         let nonce = options.nonce.expect("Nonce must be set for every tx");
 
         // Nonce and gas_price are appended to distinguish the same transactions
         // with different gas by their hash in tests.
         let mut data_for_hash = raw_tx.clone();
         data_for_hash.append(&mut ethabi::encode(gas_price.into_tokens().as_ref()));
+        if let Some(priority_fee) = options.max_priority_fee_per_gas {
+            data_for_hash.append(&mut ethabi::encode(priority_fee.into_tokens().as_ref()));
+        }
         data_for_hash.append(&mut ethabi::encode(nonce.into_tokens().as_ref()));
         let hash = Self::fake_sha256(data_for_hash.as_ref()); // Okay for test purposes.
                                                               // Concatenate raw_tx plus hash for test purposes
@@ -165,7 +208,8 @@ impl MockEthereum {
     }
 
     pub async fn pending_nonce(&self) -> Result<U256, Error> {
-        unreachable!()
+        // The mock doesn't track the nonces, so the accounts are supposed to be fresh.
+        Ok(U256::zero())
     }
 
     pub async fn current_nonce(&self) -> Result<U256, Error> {
@@ -173,7 +217,7 @@ impl MockEthereum {
     }
 
     pub async fn sender_eth_balance(&self) -> Result<U256, Error> {
-        unreachable!()
+        Ok(self.inner.balance)
     }
 
     pub async fn sign_prepared_tx_for_addr(
@@ -7,15 +7,15 @@ use web3::{
     transports::Http,
     types::{Address, BlockId, Filter, Log, Transaction, U64},
 };
-use zksync_eth_signer::PrivateKeySigner;
+use zksync_eth_signer::OperatorSigner;
 use zksync_types::{TransactionReceipt, H160, H256, U256};
 
-use crate::ethereum_gateway::{ExecutedTxStatus, FailureInfo, SignedCallResult};
+use crate::ethereum_gateway::{ExecutedTxStatus, FailureInfo, FeeHistory, SignedCallResult};
 use crate::ETHDirectClient;
 
 #[derive(Debug, Default)]
 struct MultiplexerEthereumClientInner {
-    clients: Vec<(String, ETHDirectClient<PrivateKeySigner>)>,
+    clients: Vec<(String, ETHDirectClient<OperatorSigner>)>,
     preferred: AtomicUsize,
 }
 
@@ -44,7 +44,7 @@ impl MultiplexerEthereumClient {
     pub fn add_client(
         &mut self,
         name: String,
-        client: ETHDirectClient<PrivateKeySigner>,
+        client: ETHDirectClient<OperatorSigner>,
     ) -> &mut Self {
         Arc::get_mut(&mut self.inner)
             .unwrap()
@@ -61,7 +61,7 @@ impl MultiplexerEthereumClient {
         }
     }
 
-    pub fn clients(&self) -> impl Iterator<Item = (&str, &ETHDirectClient<PrivateKeySigner>)> {
+    pub fn clients(&self) -> impl Iterator<Item = (&str, &ETHDirectClient<OperatorSigner>)> {
         let preferred = self.inner.preferred.load(Ordering::Relaxed);
         self.inner
             .clients
@@ -106,6 +106,18 @@ impl MultiplexerEthereumClient {
         multiple_call!(self, get_gas_price());
     }
 
+    pub async fn get_base_fee_per_gas(&self) -> Result<Option<U256>, anyhow::Error> {
+        multiple_call!(self, get_base_fee_per_gas());
+    }
+
+    pub async fn get_fee_history(
+        &self,
+        block_count: u64,
+        reward_percentile: f64,
+    ) -> Result<FeeHistory, anyhow::Error> {
+        multiple_call!(self, get_fee_history(block_count, reward_percentile));
+    }
+
     pub async fn sender_eth_balance(&self) -> Result<U256, anyhow::Error> {
         multiple_call!(self, sender_eth_balance());
     }
@@ -6,7 +6,7 @@ use web3::types::{Address, BlockId, Filter, Log, Transaction, U64};
 use std::fmt::Debug;
 use zksync_config::{ETHClientConfig, ETHSenderConfig};
 use zksync_contracts::zksync_contract;
-use zksync_eth_signer::PrivateKeySigner;
+use zksync_eth_signer::{OperatorSigner, PrivateKeySigner, RemoteSigner};
 use zksync_types::{TransactionReceipt, H160, H256, U256};
 
 use crate::clients::mock::MockEthereum;
@@ -21,6 +21,15 @@ pub struct SignedCallResult {
     pub hash: H256,
 }
 
+/// Fees of the latest blocks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeeHistory {
+    /// Base fees per gas of the blocks, including the next block after the latest one.
+    pub base_fees_per_gas: Vec<U256>,
+    /// Priority fees per gas paid in the blocks at the requested percentile.
+    pub priority_fees_per_gas: Vec<U256>,
+}
+
 /// State of the executed Ethereum transaction.
 #[derive(Debug, Clone)]
 pub struct ExecutedTxStatus {
@@ -31,6 +40,8 @@ pub struct ExecutedTxStatus {
     /// Receipt for a transaction. Will be set to `Some` only if the transaction
     /// failed during execution.
     pub receipt: Option<TransactionReceipt>,
+    /// Gas used by the transaction, if reported by the node.
+    pub gas_used: Option<U256>,
 }
 /// Information about transaction failure.
 #[derive(Debug, Clone)]
@@ -43,7 +54,7 @@ pub struct FailureInfo {
 
 #[derive(Debug, Clone)]
 pub enum EthereumGateway {
-    Direct(ETHDirectClient<PrivateKeySigner>),
+    Direct(ETHDirectClient<OperatorSigner>),
     Multiplexed(MultiplexerEthereumClient),
     Mock(MockEthereum),
 }
@@ -53,6 +64,27 @@ impl EthereumGateway {
         eth_client_config: &ETHClientConfig,
         eth_sender_config: &ETHSenderConfig,
         main_contract: Address,
+    ) -> Self {
+        let sender = &eth_sender_config.sender;
+        let signer = match &sender.remote_signer_url {
+            Some(url) => RemoteSigner::new(url.clone(), sender.operator_commit_eth_addr).into(),
+            None => PrivateKeySigner::new(sender.operator_private_key).into(),
+        };
+        Self::from_config_with_operator(
+            eth_client_config,
+            signer,
+            sender.operator_commit_eth_addr,
+            main_contract,
+        )
+    }
+
+    /// Creates the gateway which signs the transactions by the provided operator account
+    /// instead of the main one, e.g. by the reserve operator account of the `eth_sender`.
+    pub fn from_config_with_operator(
+        eth_client_config: &ETHClientConfig,
+        signer: OperatorSigner,
+        operator_address: Address,
+        main_contract: Address,
     ) -> Self {
         if eth_client_config.web3_url.len() == 1 {
             let transport = web3::transports::Http::new(&eth_client_config.web3_url()).unwrap();
@@ -60,8 +92,8 @@ impl EthereumGateway {
             EthereumGateway::Direct(ETHDirectClient::new(
                 transport,
                 zksync_contract(),
-                eth_sender_config.sender.operator_commit_eth_addr,
-                PrivateKeySigner::new(eth_sender_config.sender.operator_private_key),
+                operator_address,
+                signer,
                 main_contract,
                 eth_client_config.chain_id,
                 eth_client_config.gas_price_factor,
@@ -77,8 +109,8 @@ impl EthereumGateway {
                     ETHDirectClient::new(
                         transport,
                         contract.clone(),
-                        eth_sender_config.sender.operator_commit_eth_addr,
-                        PrivateKeySigner::new(eth_sender_config.sender.operator_private_key),
+                        operator_address,
+                        signer.clone(),
                         main_contract,
                         eth_client_config.chain_id,
                         eth_client_config.gas_price_factor,
@@ -126,6 +158,20 @@ impl EthereumGateway {
     pub async fn get_gas_price(&self) -> Result<U256, anyhow::Error> {
         delegate_call!(self.get_gas_price())
     }
+
+    /// Returns the base fee per gas of the latest block, `None` if the chain doesn't support EIP-1559.
+    pub async fn get_base_fee_per_gas(&self) -> Result<Option<U256>, anyhow::Error> {
+        delegate_call!(self.get_base_fee_per_gas())
+    }
+
+    /// Returns the fees of the latest blocks, the priority fees are taken at the percentile of the block transactions.
+    pub async fn get_fee_history(
+        &self,
+        block_count: u64,
+        reward_percentile: f64,
+    ) -> Result<FeeHistory, anyhow::Error> {
+        delegate_call!(self.get_fee_history(block_count, reward_percentile))
+    }
     /// Returns the account balance.
     pub async fn sender_eth_balance(&self) -> Result<U256, anyhow::Error> {
         delegate_call!(self.sender_eth_balance())
@@ -0,0 +1,339 @@
+//! Gas price oracle aggregating several sources of the gas price.
+//!
+//! Every source is queried on each request and the failed ones are skipped. The median of
+//! the reported prices is cut by the sanity bounds, so a single misbehaving source (e.g. a node
+//! reporting an outlier or an unavailable external API) doesn't affect the price as long as
+//! the majority of the sources is healthy.
+
+// Built-in deps
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(feature = "with-metrics")]
+use std::time::Instant;
+
+// External uses
+use anyhow::{ensure, format_err};
+use serde_json::Value;
+
+// Workspace uses
+use zksync_config::configs::eth_sender::GasOracle;
+use zksync_types::U256;
+
+use crate::EthereumGateway;
+
+/// Percentile of the priority fees of the block transactions taken by the `fee_history` source.
+const PRIORITY_FEE_PERCENTILE: f64 = 50.0;
+/// Timeout of the requests to the external gas price API.
+const EXTERNAL_API_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[async_trait::async_trait]
+pub trait GasPriceSource: fmt::Debug + Send + Sync + 'static {
+    /// Name of the source used in the logs.
+    fn name(&self) -> &'static str;
+
+    async fn gas_price(&self, ethereum: &EthereumGateway) -> anyhow::Result<U256>;
+}
+
+/// Gas price suggested by the Ethereum node (`eth_gasPrice`).
+#[derive(Debug, Clone, Copy)]
+pub struct NodeGasPrice;
+
+#[async_trait::async_trait]
+impl GasPriceSource for NodeGasPrice {
+    fn name(&self) -> &'static str {
+        "node"
+    }
+
+    async fn gas_price(&self, ethereum: &EthereumGateway) -> anyhow::Result<U256> {
+        ethereum.get_gas_price().await
+    }
+}
+
+/// Gas price based on the EIP-1559 fee history: the highest base fee of the latest blocks
+/// (including the next one) along with the median priority fee paid in them.
+#[derive(Debug, Clone, Copy)]
+pub struct FeeHistoryGasPrice {
+    block_count: u64,
+}
+
+impl FeeHistoryGasPrice {
+    pub fn new(block_count: u64) -> Self {
+        Self { block_count }
+    }
+}
+
+#[async_trait::async_trait]
+impl GasPriceSource for FeeHistoryGasPrice {
+    fn name(&self) -> &'static str {
+        "fee_history"
+    }
+
+    async fn gas_price(&self, ethereum: &EthereumGateway) -> anyhow::Result<U256> {
+        let history = ethereum
+            .get_fee_history(self.block_count, PRIORITY_FEE_PERCENTILE)
+            .await?;
+        let base_fee = history
+            .base_fees_per_gas
+            .iter()
+            .max()
+            .copied()
+            .ok_or_else(|| {
+                format_err!(
+                    "No base fees in the fee history, EIP-1559 isn't supported by the chain"
+                )
+            })?;
+        let priority_fee = median(history.priority_fees_per_gas).unwrap_or_default();
+        Ok(base_fee + priority_fee)
+    }
+}
+
+/// Gas price reported by the external API, e.g. the Etherscan gas tracker.
+/// The price in gwei is taken from the JSON response by the JSON pointer.
+#[derive(Debug, Clone)]
+pub struct ExternalApiGasPrice {
+    client: reqwest::Client,
+    url: String,
+    json_pointer: String,
+}
+
+impl ExternalApiGasPrice {
+    pub fn new(url: impl Into<String>, json_pointer: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url: url.into(),
+            json_pointer: json_pointer.into(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl GasPriceSource for ExternalApiGasPrice {
+    fn name(&self) -> &'static str {
+        "external_api"
+    }
+
+    async fn gas_price(&self, _ethereum: &EthereumGateway) -> anyhow::Result<U256> {
+        let response: Value = self
+            .client
+            .get(&self.url)
+            .timeout(EXTERNAL_API_TIMEOUT)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        parse_gwei(&response, &self.json_pointer)
+    }
+}
+
+/// Extracts the gas price in gwei from the JSON value and converts it to wei.
+fn parse_gwei(response: &Value, json_pointer: &str) -> anyhow::Result<U256> {
+    let value = response
+        .pointer(json_pointer)
+        .ok_or_else(|| format_err!("No gas price at {} in the response", json_pointer))?;
+    let gwei = match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(string) => string.parse::<f64>().ok(),
+        _ => None,
+    }
+    .ok_or_else(|| format_err!("Gas price {} is not a number", value))?;
+    ensure!(
+        gwei.is_finite() && gwei >= 0.0,
+        "Incorrect gas price {}",
+        gwei
+    );
+    Ok(U256::from((gwei * 1e9).round() as u64))
+}
+
+/// Returns the median of the values, the mean of the two middle ones for the even amount of them.
+fn median(mut values: Vec<U256>) -> Option<U256> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort();
+    let middle = values.len() / 2;
+    if values.len() % 2 == 0 {
+        Some((values[middle - 1] + values[middle]) / 2)
+    } else {
+        Some(values[middle])
+    }
+}
+
+/// Oracle aggregating the gas prices of several sources.
+#[derive(Debug, Clone)]
+pub struct GasPriceOracle {
+    sources: Vec<Arc<dyn GasPriceSource>>,
+    min_gas_price: U256,
+    max_gas_price: U256,
+}
+
+impl Default for GasPriceOracle {
+    /// Creates the oracle reporting the node gas price as is.
+    fn default() -> Self {
+        Self::new(
+            vec![Arc::new(NodeGasPrice)],
+            U256::zero(),
+            U256::max_value(),
+        )
+    }
+}
+
+impl GasPriceOracle {
+    pub fn new(
+        sources: Vec<Arc<dyn GasPriceSource>>,
+        min_gas_price: U256,
+        max_gas_price: U256,
+    ) -> Self {
+        assert!(!sources.is_empty(), "No gas price sources");
+        assert!(min_gas_price <= max_gas_price, "Incorrect gas price bounds");
+        Self {
+            sources,
+            min_gas_price,
+            max_gas_price,
+        }
+    }
+
+    /// Creates the oracle with the configured sources.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the source is unknown or the external API URL isn't set for the `external_api` source.
+    pub fn from_config(config: &GasOracle) -> Self {
+        let sources = config
+            .sources
+            .iter()
+            .map(|source| -> Arc<dyn GasPriceSource> {
+                match source.as_str() {
+                    "node" => Arc::new(NodeGasPrice),
+                    "fee_history" => Arc::new(FeeHistoryGasPrice::new(config.fee_history_blocks)),
+                    "external_api" => {
+                        let url = config
+                            .external_api_url
+                            .clone()
+                            .expect("The external gas price API URL is not set");
+                        Arc::new(ExternalApiGasPrice::new(
+                            url,
+                            config.external_api_json_pointer.clone(),
+                        ))
+                    }
+                    source => panic!("Unknown gas price source {}", source),
+                }
+            })
+            .collect();
+        Self::new(
+            sources,
+            config.min_gas_price.into(),
+            config.max_gas_price.into(),
+        )
+    }
+
+    /// Returns the median of the gas prices reported by the available sources cut by the bounds.
+    pub async fn get_gas_price(&self, ethereum: &EthereumGateway) -> anyhow::Result<U256> {
+        #[cfg(feature = "with-metrics")]
+        let start = Instant::now();
+        let mut prices = Vec::with_capacity(self.sources.len());
+        for source in &self.sources {
+            match source.gas_price(ethereum).await {
+                Ok(price) => prices.push(price),
+                Err(err) => vlog::warn!(
+                    "Cannot get the gas price from the {} source: {}",
+                    source.name(),
+                    err
+                ),
+            }
+        }
+        let price = median(prices)
+            .ok_or_else(|| format_err!("None of the gas price sources is available"))?;
+        #[cfg(feature = "with-metrics")]
+        metrics::histogram!("eth_client.gas_price_oracle.get_gas_price", start.elapsed());
+        Ok(price.clamp(self.min_gas_price, self.max_gas_price))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clients::mock::MockEthereum;
+    use serde_json::json;
+
+    #[derive(Debug)]
+    struct FixedGasPrice(Option<u64>);
+
+    #[async_trait::async_trait]
+    impl GasPriceSource for FixedGasPrice {
+        fn name(&self) -> &'static str {
+            "fixed"
+        }
+
+        async fn gas_price(&self, _ethereum: &EthereumGateway) -> anyhow::Result<U256> {
+            self.0
+                .map(U256::from)
+                .ok_or_else(|| format_err!("Unavailable"))
+        }
+    }
+
+    fn oracle(prices: &[Option<u64>]) -> GasPriceOracle {
+        let sources = prices
+            .iter()
+            .map(|price| -> Arc<dyn GasPriceSource> { Arc::new(FixedGasPrice(*price)) })
+            .collect();
+        GasPriceOracle::new(sources, 10.into(), 1000.into())
+    }
+
+    #[tokio::test]
+    async fn median_selection() {
+        let ethereum = EthereumGateway::Mock(MockEthereum::default());
+
+        // The outlier doesn't affect the price.
+        let price = oracle(&[Some(100), Some(110), Some(900)]);
+        assert_eq!(price.get_gas_price(&ethereum).await.unwrap(), 110.into());
+        // The failed sources are skipped.
+        let price = oracle(&[Some(100), None, Some(120)]);
+        assert_eq!(price.get_gas_price(&ethereum).await.unwrap(), 110.into());
+        // The price is cut by the bounds.
+        let price = oracle(&[Some(5)]);
+        assert_eq!(price.get_gas_price(&ethereum).await.unwrap(), 10.into());
+        let price = oracle(&[Some(2000), Some(3000)]);
+        assert_eq!(price.get_gas_price(&ethereum).await.unwrap(), 1000.into());
+        let price = oracle(&[None, None]);
+        assert!(price.get_gas_price(&ethereum).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn fee_history_source() {
+        // The mock base fee is 50 wei and the blocks contain no priority fees.
+        let ethereum = EthereumGateway::Mock(MockEthereum::default());
+        let price = FeeHistoryGasPrice::new(10)
+            .gas_price(&ethereum)
+            .await
+            .unwrap();
+        assert_eq!(price, 50.into());
+
+        let mut ethereum = MockEthereum::default();
+        ethereum.set_base_fee_per_gas(None).await.unwrap();
+        let ethereum = EthereumGateway::Mock(ethereum);
+        assert!(FeeHistoryGasPrice::new(10)
+            .gas_price(&ethereum)
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn external_api_response() {
+        let pointer = "/result/ProposeGasPrice";
+        let response = json!({ "result": { "ProposeGasPrice": "31.5" } });
+        assert_eq!(
+            parse_gwei(&response, pointer).unwrap(),
+            31_500_000_000u64.into()
+        );
+        let response = json!({ "result": { "ProposeGasPrice": 20 } });
+        assert_eq!(
+            parse_gwei(&response, pointer).unwrap(),
+            20_000_000_000u64.into()
+        );
+        assert!(parse_gwei(&json!({ "result": "Rate limit" }), pointer).is_err());
+        assert!(parse_gwei(&json!({ "result": { "ProposeGasPrice": "-1" } }), pointer).is_err());
+    }
+}
@@ -1,5 +1,7 @@
 pub mod clients;
 pub mod ethereum_gateway;
+pub mod gas_price_oracle;
 pub use clients::http_client::ETHDirectClient;
 pub use clients::multiplexer::MultiplexerEthereumClient;
 pub use ethereum_gateway::{EthereumGateway, SignedCallResult};
+pub use gas_price_oracle::GasPriceOracle;
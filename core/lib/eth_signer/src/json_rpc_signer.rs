@@ -287,9 +287,10 @@ impl JsonRpcSigner {
     }
 }
 
-mod messages {
+pub(crate) mod messages {
     use crate::RawTransaction;
     use hex::encode;
+    use web3::types::U64;
     use zksync_types::Address;
 
     #[derive(Debug, Serialize, Deserialize)]
@@ -365,6 +366,31 @@ mod messages {
             params.push(tx);
             Self::create("eth_signTransaction", params)
         }
+
+        /// Signs a transaction of any type with the provided chain ID, thus the EIP-1559 transaction
+        /// is signed if the max fee per gas is set. Used by the signers keeping the keys themselves.
+        pub fn sign_typed_transaction(from: Address, tx_data: RawTransaction) -> Self {
+            let mut tx = serde_json::json!({
+                "from": from,
+                "to": tx_data.to,
+                "gas": tx_data.gas,
+                "value": tx_data.value,
+                "data": format!("0x{}", encode(tx_data.data)),
+                "nonce": tx_data.nonce,
+                "chainId": U64::from(tx_data.chain_id),
+            });
+            match tx_data.max_fee_per_gas {
+                Some(max_fee_per_gas) => {
+                    tx["type"] =
+                        serde_json::json!(tx_data.transaction_type.unwrap_or_else(|| 2.into()));
+                    tx["maxFeePerGas"] = serde_json::json!(max_fee_per_gas);
+                    tx["maxPriorityFeePerGas"] =
+                        serde_json::json!(tx_data.max_priority_fee_per_gas.unwrap_or_default());
+                }
+                None => tx["gasPrice"] = serde_json::json!(tx_data.gas_price),
+            }
+            Self::create("eth_signTransaction", vec![tx])
+        }
     }
 }
 
@@ -9,11 +9,13 @@ use zksync_types::Address;
 pub use json_rpc_signer::JsonRpcSigner;
 pub use pk_signer::PrivateKeySigner;
 pub use raw_ethereum_tx::RawTransaction;
+pub use remote_signer::RemoteSigner;
 
 pub mod error;
 pub mod json_rpc_signer;
 pub mod pk_signer;
 pub mod raw_ethereum_tx;
+pub mod remote_signer;
 
 #[async_trait]
 pub trait EthereumSigner: Send + Sync + Clone {
@@ -21,3 +23,47 @@ pub trait EthereumSigner: Send + Sync + Clone {
     async fn sign_transaction(&self, raw_tx: RawTransaction) -> Result<Vec<u8>, SignerError>;
     async fn get_address(&self) -> Result<Address, SignerError>;
 }
+
+/// Signer of the operator transactions: either the private key from the config,
+/// or the remote signer keeping the key.
+#[derive(Debug, Clone)]
+pub enum OperatorSigner {
+    PrivateKey(PrivateKeySigner),
+    Remote(RemoteSigner),
+}
+
+impl From<PrivateKeySigner> for OperatorSigner {
+    fn from(signer: PrivateKeySigner) -> Self {
+        Self::PrivateKey(signer)
+    }
+}
+
+impl From<RemoteSigner> for OperatorSigner {
+    fn from(signer: RemoteSigner) -> Self {
+        Self::Remote(signer)
+    }
+}
+
+#[async_trait]
+impl EthereumSigner for OperatorSigner {
+    async fn sign_message(&self, message: &[u8]) -> Result<TxEthSignature, SignerError> {
+        match self {
+            Self::PrivateKey(signer) => signer.sign_message(message).await,
+            Self::Remote(signer) => signer.sign_message(message).await,
+        }
+    }
+
+    async fn sign_transaction(&self, raw_tx: RawTransaction) -> Result<Vec<u8>, SignerError> {
+        match self {
+            Self::PrivateKey(signer) => signer.sign_transaction(raw_tx).await,
+            Self::Remote(signer) => signer.sign_transaction(raw_tx).await,
+        }
+    }
+
+    async fn get_address(&self) -> Result<Address, SignerError> {
+        match self {
+            Self::PrivateKey(signer) => signer.get_address().await,
+            Self::Remote(signer) => signer.get_address().await,
+        }
+    }
+}
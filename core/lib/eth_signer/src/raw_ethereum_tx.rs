@@ -8,12 +8,14 @@
 //! We can refactor this code and adapt it for our needs better, but I prefer to reuse as much code as we can.
 //! In the case where it will be possible to use only the web3 library without copy-paste, the changes will be small and simple
 //! Link to @Deniallugo's PR to web3: https://github.com/tomusdrw/rust-web3/pull/630
-use rlp::RlpStream;
+use rlp::{DecoderError, Rlp, RlpStream};
 use web3::{
     signing::{self, Signature},
     types::{AccessList, Address, SignedTransaction, U256, U64},
 };
 
+use crate::error::SignerError;
+
 const LEGACY_TX_ID: u64 = 0;
 const ACCESSLISTS_TX_ID: u64 = 1;
 const EIP1559_TX_ID: u64 = 2;
@@ -217,3 +219,164 @@ impl Transaction {
         }
     }
 }
+
+/// Fields of the signed transaction which are checked before it's sent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedTransaction {
+    /// Sender recovered from the signature.
+    pub from: Address,
+    pub nonce: U256,
+    pub to: Option<Address>,
+    pub data: Vec<u8>,
+}
+
+/// Decodes the RLP-encoded signed transaction of any of the supported types
+/// and recovers its sender.
+pub fn decode_signed_transaction(raw_tx: &[u8]) -> Result<DecodedTransaction, SignerError> {
+    let decode_err = |err: DecoderError| SignerError::DecodeRawTxFailed(err.to_string());
+
+    let first_byte = *raw_tx
+        .first()
+        .ok_or_else(|| SignerError::DecodeRawTxFailed("Empty transaction".to_string()))?;
+    // The typed transactions start with their type, while the legacy ones are the RLP lists.
+    let (tx_type, payload) = if first_byte >= 0xc0 {
+        (LEGACY_TX_ID, raw_tx)
+    } else {
+        (first_byte as u64, &raw_tx[1..])
+    };
+    // Indices of the nonce and the `to` fields, and the number of the signed fields.
+    let (nonce_index, to_index, signed_fields) = match tx_type {
+        LEGACY_TX_ID => (0, 3, 6),
+        ACCESSLISTS_TX_ID => (1, 4, 8),
+        EIP1559_TX_ID => (1, 5, 9),
+        _ => {
+            return Err(SignerError::DecodeRawTxFailed(format!(
+                "Unsupported transaction type {}",
+                tx_type
+            )))
+        }
+    };
+    let rlp = Rlp::new(payload);
+    if rlp.item_count().map_err(decode_err)? != signed_fields + 3 {
+        return Err(SignerError::DecodeRawTxFailed(
+            "Unexpected number of the transaction fields".to_string(),
+        ));
+    }
+
+    let v: u64 = rlp.val_at(signed_fields).map_err(decode_err)?;
+    let r: U256 = rlp.val_at(signed_fields + 1).map_err(decode_err)?;
+    let s: U256 = rlp.val_at(signed_fields + 2).map_err(decode_err)?;
+
+    let mut stream = RlpStream::new();
+    let (message, recovery_id) = if tx_type == LEGACY_TX_ID {
+        // The EIP-155 signature also commits to the chain id.
+        let chain_id = v.checked_sub(35).map(|v| v / 2);
+        stream.begin_list(if chain_id.is_some() { 9 } else { 6 });
+        for index in 0..signed_fields {
+            stream.append_raw(rlp.at(index).map_err(decode_err)?.as_raw(), 1);
+        }
+        let recovery_id = match chain_id {
+            Some(chain_id) => {
+                stream.append(&chain_id);
+                stream.append(&0u8);
+                stream.append(&0u8);
+                (v - 35) % 2
+            }
+            None => v.checked_sub(27).filter(|id| *id < 2).ok_or_else(|| {
+                SignerError::DecodeRawTxFailed(format!("Invalid signature v = {}", v))
+            })?,
+        };
+        (stream.out().to_vec(), recovery_id)
+    } else {
+        stream.begin_list(signed_fields);
+        for index in 0..signed_fields {
+            stream.append_raw(rlp.at(index).map_err(decode_err)?.as_raw(), 1);
+        }
+        ([&[tx_type as u8], stream.as_raw()].concat(), v)
+    };
+
+    let mut signature = [0u8; 64];
+    r.to_big_endian(&mut signature[..32]);
+    s.to_big_endian(&mut signature[32..]);
+    let from = signing::recover(
+        &signing::keccak256(&message),
+        &signature,
+        recovery_id as i32,
+    )
+    .map_err(|err| SignerError::RecoverAddress(format!("{:?}", err)))?;
+
+    let to = rlp.at(to_index).map_err(decode_err)?;
+    let to = if to.is_empty() {
+        None
+    } else {
+        Some(to.as_val().map_err(decode_err)?)
+    };
+    Ok(DecodedTransaction {
+        from,
+        nonce: rlp.val_at(nonce_index).map_err(decode_err)?,
+        to,
+        data: rlp.val_at(to_index + 2).map_err(decode_err)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use web3::types::{AccessListItem, H256};
+
+    use super::*;
+    use crate::{EthereumSigner, PrivateKeySigner};
+
+    #[tokio::test]
+    async fn decode_signed_transactions() {
+        let key = H256::repeat_byte(0x11);
+        let signer = PrivateKeySigner::new(key);
+        let address = signer.get_address().await.unwrap();
+
+        let legacy_tx = RawTransaction {
+            nonce: 5.into(),
+            to: Some(Address::repeat_byte(0x22)),
+            gas: 100_000.into(),
+            gas_price: 200.into(),
+            value: 1.into(),
+            data: vec![1, 2, 3],
+            chain_id: 9,
+            ..Default::default()
+        };
+        let access_list_tx = RawTransaction {
+            transaction_type: Some(ACCESSLISTS_TX_ID.into()),
+            access_list: Some(vec![AccessListItem {
+                address: Address::repeat_byte(0x33),
+                storage_keys: vec![H256::repeat_byte(0x44)],
+            }]),
+            ..legacy_tx.clone()
+        };
+        let eip1559_tx = RawTransaction {
+            transaction_type: Some(EIP1559_TX_ID.into()),
+            max_fee_per_gas: Some(300.into()),
+            max_priority_fee_per_gas: Some(10.into()),
+            ..legacy_tx.clone()
+        };
+        // The contract deployment doesn't have the recipient.
+        let deployment_tx = RawTransaction {
+            to: None,
+            ..eip1559_tx.clone()
+        };
+
+        for tx in vec![legacy_tx, access_list_tx, eip1559_tx, deployment_tx] {
+            let signed_tx = signer.sign_transaction(tx.clone()).await.unwrap();
+            let decoded = decode_signed_transaction(&signed_tx).unwrap();
+            assert_eq!(
+                decoded,
+                DecodedTransaction {
+                    from: address,
+                    nonce: tx.nonce,
+                    to: tx.to,
+                    data: tx.data,
+                }
+            );
+        }
+
+        assert!(decode_signed_transaction(&[]).is_err());
+        assert!(decode_signed_transaction(&[EIP1559_TX_ID as u8, 0xc0]).is_err());
+    }
+}
@@ -0,0 +1,275 @@
+//! Signer delegating the signing to the remote service which keeps the private keys,
+//! e.g. Web3Signer or the KMS/HSM backed signer exposing the Ethereum JSON RPC signing methods.
+//!
+//! Unlike `JsonRpcSigner`, this signer doesn't unlock the accounts and doesn't
+//! detect the signer type: the remote signer is expected to keep the keys of the
+//! configured account and to follow the `eth_sign` semantics. The signed transactions
+//! are decoded and checked to be the requested ones of the configured account.
+
+use std::time::Duration;
+
+use crate::error::{RpcSignerError, SignerError};
+use crate::json_rpc_signer::{is_signature_from_address, messages::JsonRpcRequest};
+use crate::raw_ethereum_tx::decode_signed_transaction;
+use crate::{EthereumSigner, RawTransaction};
+
+use jsonrpc_core::types::response::Output;
+use serde_json::Value;
+use zksync_types::tx::{PackedEthSignature, TxEthSignature};
+use zksync_types::Address;
+
+/// Timeout of establishing the connection to the remote signer.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+/// Timeout of the whole signing request, so the sender isn't stalled by the hanging remote signer.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone)]
+pub struct RemoteSigner {
+    url: String,
+    client: reqwest::Client,
+    address: Address,
+}
+
+#[async_trait::async_trait]
+impl EthereumSigner for RemoteSigner {
+    /// Signs the message by the remote signer, which adds the `\x19Ethereum Signed Message:\n` prefix to it.
+    async fn sign_message(&self, message: &[u8]) -> Result<TxEthSignature, SignerError> {
+        let request = JsonRpcRequest::sign_message(self.address, message);
+        let result = self
+            .post(&request)
+            .await
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+        let signature: PackedEthSignature = serde_json::from_value(result)
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+
+        if is_signature_from_address(&signature, message, self.address)? {
+            Ok(TxEthSignature::EthereumSignature(signature))
+        } else {
+            Err(SignerError::SigningFailed(
+                "Invalid signature from RemoteSigner".to_string(),
+            ))
+        }
+    }
+
+    /// Signs and returns the RLP-encoded transaction.
+    async fn sign_transaction(&self, raw_tx: RawTransaction) -> Result<Vec<u8>, SignerError> {
+        let request = JsonRpcRequest::sign_typed_transaction(self.address, raw_tx.clone());
+        let result = self
+            .post(&request)
+            .await
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+
+        // Web3Signer returns the signed transaction itself, while the nodes wrap it into an object.
+        let signed_tx = match &result {
+            Value::String(signed_tx) => Some(signed_tx.as_str()),
+            Value::Object(object) => object.get("raw").and_then(Value::as_str),
+            _ => None,
+        };
+        let signed_tx = signed_tx.ok_or_else(|| {
+            SignerError::SigningFailed(format!("Unexpected response: {}", result))
+        })?;
+
+        let signed_tx = hex::decode(signed_tx.trim_start_matches("0x"))
+            .map_err(|err| SignerError::DecodeRawTxFailed(err.to_string()))?;
+
+        // The transaction is sent as is, so it should be the requested one of our account.
+        let decoded = decode_signed_transaction(&signed_tx)?;
+        if decoded.from != self.address
+            || decoded.nonce != raw_tx.nonce
+            || decoded.to != raw_tx.to
+            || decoded.data != raw_tx.data
+        {
+            return Err(SignerError::SigningFailed(format!(
+                "Signed transaction doesn't match the requested one: {:?}",
+                decoded
+            )));
+        }
+        Ok(signed_tx)
+    }
+
+    async fn get_address(&self) -> Result<Address, SignerError> {
+        Ok(self.address)
+    }
+}
+
+impl RemoteSigner {
+    /// Creates the signer of the account whose key is kept by the remote signer available by the URL.
+    pub fn new(url: impl Into<String>, address: Address) -> Self {
+        let client = reqwest::Client::builder()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .expect("Failed to build the remote signer client");
+        Self {
+            url: url.into(),
+            client,
+            address,
+        }
+    }
+
+    /// Performs a POST query to the remote signer and returns the result of the successful call.
+    async fn post(
+        &self,
+        message: impl serde::Serialize,
+    ) -> Result<serde_json::Value, RpcSignerError> {
+        let res = self
+            .client
+            .post(&self.url)
+            .json(&message)
+            .send()
+            .await
+            .map_err(|err| RpcSignerError::NetworkError(err.to_string()))?;
+        if res.status() != reqwest::StatusCode::OK {
+            let error = format!(
+                "Post query responded with a non-OK response: {}",
+                res.status()
+            );
+            return Err(RpcSignerError::NetworkError(error));
+        }
+        let reply: Output = res
+            .json()
+            .await
+            .map_err(|err| RpcSignerError::MalformedResponse(err.to_string()))?;
+
+        match reply {
+            Output::Success(success) => Ok(success.result),
+            Output::Failure(failure) => Err(RpcSignerError::RpcError(failure)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{post, web, App, HttpResponse, HttpServer, Responder};
+    use futures::future::{AbortHandle, Abortable};
+    use jsonrpc_core::{Id, Output, Success, Version};
+    use serde_json::json;
+    use web3::types::{U256, U64};
+
+    use zksync_types::{tx::PackedEthSignature, Address, H256};
+
+    use super::RemoteSigner;
+    use crate::error::SignerError;
+    use crate::json_rpc_signer::messages::JsonRpcRequest;
+    use crate::{EthereumSigner, PrivateKeySigner, RawTransaction};
+
+    const CHAIN_ID: u64 = 9;
+
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct TransactionRequest {
+        to: Option<Address>,
+        gas: U256,
+        gas_price: Option<U256>,
+        max_fee_per_gas: Option<U256>,
+        max_priority_fee_per_gas: Option<U256>,
+        value: U256,
+        data: String,
+        nonce: U256,
+        chain_id: U64,
+        #[serde(rename = "type")]
+        transaction_type: Option<U64>,
+    }
+
+    /// Emulates Web3Signer keeping the single private key.
+    #[post("/")]
+    async fn index(req: web::Json<JsonRpcRequest>, key: web::Data<H256>) -> impl Responder {
+        let result = match req.method.as_str() {
+            "eth_sign" => {
+                let data: String = serde_json::from_value(req.params[1].clone()).unwrap();
+                let data = hex::decode(&data[2..]).unwrap();
+                json!(PackedEthSignature::sign(&key, &data).unwrap())
+            }
+            "eth_signTransaction" => {
+                let tx: TransactionRequest = serde_json::from_value(req.params[0].clone()).unwrap();
+                let raw_tx = RawTransaction {
+                    nonce: tx.nonce,
+                    to: tx.to,
+                    gas: tx.gas,
+                    gas_price: tx.gas_price.unwrap_or_default(),
+                    value: tx.value,
+                    data: hex::decode(&tx.data[2..]).unwrap(),
+                    chain_id: tx.chain_id.as_u64(),
+                    transaction_type: tx.transaction_type,
+                    access_list: None,
+                    max_fee_per_gas: tx.max_fee_per_gas,
+                    max_priority_fee_per_gas: tx.max_priority_fee_per_gas,
+                };
+                let signed_tx = PrivateKeySigner::new(*key.get_ref())
+                    .sign_transaction(raw_tx)
+                    .await
+                    .unwrap();
+                json!(format!("0x{}", hex::encode(signed_tx)))
+            }
+            method => panic!("Unexpected method {}", method),
+        };
+        HttpResponse::Ok().json(Output::Success(Success {
+            jsonrpc: Some(Version::V2),
+            result,
+            id: Id::Num(1),
+        }))
+    }
+
+    fn run_server(key: H256) -> (String, AbortHandle) {
+        for port in 10000..10999 {
+            let url = format!("127.0.0.1:{}", port);
+            if let Ok(server) =
+                HttpServer::new(move || App::new().app_data(web::Data::new(key)).service(index))
+                    .bind(url.clone())
+            {
+                let (abort_handle, abort_registration) = AbortHandle::new_pair();
+                tokio::spawn(Abortable::new(server.run(), abort_registration));
+                return (format!("http://{}/", url), abort_handle);
+            }
+        }
+        panic!("Could not bind to port from 10000 to 10999");
+    }
+
+    /// Checks that the transactions signed by the remote signer are the same as the ones signed locally.
+    #[actix_rt::test]
+    async fn remote_signing() {
+        let key = H256::repeat_byte(0x11);
+        let address = PackedEthSignature::address_from_private_key(&key).unwrap();
+        let (url, abort_handle) = run_server(key);
+        let remote_signer = RemoteSigner::new(url, address);
+        let local_signer = PrivateKeySigner::new(key);
+
+        let message = b"some_text_message";
+        assert_eq!(
+            remote_signer.sign_message(message).await.unwrap(),
+            local_signer.sign_message(message).await.unwrap()
+        );
+
+        let legacy_tx = RawTransaction {
+            nonce: 1.into(),
+            to: Some(Address::repeat_byte(0x22)),
+            gas: 100_000.into(),
+            gas_price: 200.into(),
+            value: 0.into(),
+            data: vec![1, 2, 3],
+            chain_id: CHAIN_ID,
+            ..Default::default()
+        };
+        let eip1559_tx = RawTransaction {
+            transaction_type: Some(2.into()),
+            max_fee_per_gas: Some(300.into()),
+            max_priority_fee_per_gas: Some(10.into()),
+            ..legacy_tx.clone()
+        };
+        for tx in vec![legacy_tx.clone(), eip1559_tx] {
+            assert_eq!(
+                remote_signer.sign_transaction(tx.clone()).await.unwrap(),
+                local_signer.sign_transaction(tx).await.unwrap()
+            );
+        }
+
+        // The transaction signed by the key of another account is rejected.
+        let other_signer = RemoteSigner::new(remote_signer.url.clone(), Address::repeat_byte(0x33));
+        assert!(matches!(
+            other_signer.sign_transaction(legacy_tx).await,
+            Err(SignerError::SigningFailed(_))
+        ));
+
+        abort_handle.abort();
+    }
+}
@@ -17,14 +17,17 @@ use tokio::task::JoinHandle;
 
 // Workspace uses
 use zksync_storage::ConnectionPool;
+use zksync_token_db_cache::TokenDenylist;
 
 // Local uses
 use crate::block_handler::MempoolBlocksHandler;
 pub use crate::block_handler::{GetBlockRequest, MempoolBlocksRequest, ProposedBlock};
 use crate::mempool_transactions_queue::MempoolTransactionsQueue;
 use crate::state::MempoolState;
-pub use crate::transactions_handler::MempoolTransactionRequest;
 use crate::transactions_handler::MempoolTransactionsHandler;
+pub use crate::transactions_handler::{
+    MempoolTransactionRequest, REPLACEMENT_FEE_INCREASE_PERCENT,
+};
 
 mod block_handler;
 mod mempool_transactions_queue;
@@ -32,12 +35,14 @@ mod state;
 mod transactions_handler;
 
 // Due channel based nature, for better performance,
-// you need to run independent mempool_tx_handler for each actor, e.g. for each API actor
+// you need to run independent mempool_tx_handler for each actor, e.g. for each API actor.
+// The denylist may be shared by the handlers, so that its config tokens are replaced at once.
 #[must_use]
 pub fn run_mempool_tx_handler(
     db_pool: ConnectionPool,
     tx_requests: mpsc::Receiver<MempoolTransactionRequest>,
     block_chunk_sizes: Vec<usize>,
+    token_denylist: TokenDenylist,
 ) -> JoinHandle<()> {
     let mempool_state = MempoolState::new(db_pool.clone());
     let max_block_size_chunks = *block_chunk_sizes
@@ -49,6 +54,7 @@ pub fn run_mempool_tx_handler(
         mempool_state,
         requests: tx_requests,
         max_block_size_chunks,
+        token_denylist,
     };
     tokio::spawn(handler.run())
 }
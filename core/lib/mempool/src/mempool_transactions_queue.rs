@@ -6,22 +6,22 @@ use zksync_types::tx::error::TxAddError;
 use zksync_types::PriorityOp;
 
 #[derive(Debug, Clone)]
-struct MempoolPendingTransaction {
+struct MempoolScheduledTransaction {
     valid_from: u64,
     tx: SignedTxVariant,
 }
 
-impl Eq for MempoolPendingTransaction {}
+impl Eq for MempoolScheduledTransaction {}
 
-impl PartialEq for MempoolPendingTransaction {
+impl PartialEq for MempoolScheduledTransaction {
     fn eq(&self, other: &Self) -> bool {
         self.tx.hashes() == other.tx.hashes()
     }
 }
 
-impl Ord for MempoolPendingTransaction {
+impl Ord for MempoolScheduledTransaction {
     fn cmp(&self, other: &Self) -> Ordering {
-        // We will compare pending transactions by their `valid_from` value to use the earliest one
+        // We will compare scheduled transactions by their `valid_from` value to use the earliest one
         other
             .valid_from
             .cmp(&self.valid_from)
@@ -29,7 +29,7 @@ impl Ord for MempoolPendingTransaction {
     }
 }
 
-impl PartialOrd for MempoolPendingTransaction {
+impl PartialOrd for MempoolScheduledTransaction {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
@@ -39,8 +39,8 @@ impl PartialOrd for MempoolPendingTransaction {
 pub(crate) struct MempoolTransactionsQueue {
     /// Transactions ready for execution.
     ready_l2_transactions: VecDeque<SignedTxVariant>,
-    /// Transactions that are not ready yet because of the `valid_from` field.
-    pending_l2_transactions: BinaryHeap<MempoolPendingTransaction>,
+    /// Transactions scheduled for the execution, i.e. not ready yet because of the `valid_from` field.
+    scheduled_l2_transactions: BinaryHeap<MempoolScheduledTransaction>,
 
     l1_transactions: VecDeque<PriorityOp>,
 }
@@ -52,7 +52,7 @@ impl MempoolTransactionsQueue {
     ) -> Self {
         let mut res = Self {
             ready_l2_transactions: Default::default(),
-            pending_l2_transactions: Default::default(),
+            scheduled_l2_transactions: Default::default(),
             l1_transactions,
         };
         // Due to complexity of json structure in database for transactions it's easier and safer
@@ -80,8 +80,8 @@ impl MempoolTransactionsQueue {
     }
 
     fn add_l2_transaction(&mut self, tx: SignedTxVariant) {
-        self.pending_l2_transactions
-            .push(MempoolPendingTransaction {
+        self.scheduled_l2_transactions
+            .push(MempoolScheduledTransaction {
                 valid_from: tx
                     .get_transactions()
                     .into_iter()
@@ -93,14 +93,14 @@ impl MempoolTransactionsQueue {
     }
 
     fn prepare_new_ready_l2_transactions(&mut self, block_timestamp: u64) {
-        // Move some pending transactions to the ready_txs queue
-        let mut ready_pending_l2_operations = {
-            let mut ready_pending_l2_operations = Vec::new();
-
-            while let Some(pending_tx) = self.pending_l2_transactions.peek() {
-                if pending_tx.valid_from <= block_timestamp {
-                    ready_pending_l2_operations.push(pending_tx.tx.clone());
-                    self.pending_l2_transactions.pop();
+        // Move the scheduled transactions which became valid to the ready_txs queue
+        let mut ready_scheduled_l2_operations = {
+            let mut ready_scheduled_l2_operations = Vec::new();
+
+            while let Some(scheduled_tx) = self.scheduled_l2_transactions.peek() {
+                if scheduled_tx.valid_from <= block_timestamp {
+                    ready_scheduled_l2_operations.push(scheduled_tx.tx.clone());
+                    self.scheduled_l2_transactions.pop();
                 } else {
                     break;
                 }
@@ -108,7 +108,7 @@ impl MempoolTransactionsQueue {
 
             // Now transactions should be sorted by the nonce (transaction natural order)
             // According to our convention in batch `fee transaction` would be the last one, so we would use nonce from it as a key for sort
-            ready_pending_l2_operations.sort_by_key(|tx| match tx {
+            ready_scheduled_l2_operations.sort_by_key(|tx| match tx {
                 SignedTxVariant::Tx(tx) => tx.tx.nonce(),
                 SignedTxVariant::Batch(batch) => batch
                     .txs
@@ -118,11 +118,11 @@ impl MempoolTransactionsQueue {
                     .nonce(),
             });
 
-            VecDeque::<SignedTxVariant>::from(ready_pending_l2_operations)
+            VecDeque::<SignedTxVariant>::from(ready_scheduled_l2_operations)
         };
 
         self.ready_l2_transactions
-            .append(&mut ready_pending_l2_operations);
+            .append(&mut ready_scheduled_l2_operations);
     }
 
     /// Collect txs depending on desired chunks and execution time
@@ -182,6 +182,10 @@ impl MempoolTransactionsQueue {
         mempool_state: &MempoolState,
     ) -> Result<(usize, Vec<SignedTxVariant>), TxAddError> {
         self.prepare_new_ready_l2_transactions(block_timestamp);
+        metrics::gauge!(
+            "mempool.scheduled_txs",
+            self.scheduled_l2_transactions.len() as f64
+        );
 
         let mut txs_for_commit = Vec::new();
 
@@ -254,7 +258,7 @@ mod tests {
     fn test_priority_queue() {
         let mut transactions_queue = MempoolTransactionsQueue {
             ready_l2_transactions: VecDeque::new(),
-            pending_l2_transactions: BinaryHeap::new(),
+            scheduled_l2_transactions: BinaryHeap::new(),
             l1_transactions: Default::default(),
         };
 
@@ -358,7 +362,7 @@ mod tests {
     fn test_mempool_transactions_queue() {
         let mut transactions_queue = MempoolTransactionsQueue {
             ready_l2_transactions: VecDeque::new(),
-            pending_l2_transactions: BinaryHeap::new(),
+            scheduled_l2_transactions: BinaryHeap::new(),
             l1_transactions: Default::default(),
         };
 
@@ -371,7 +375,7 @@ mod tests {
             transactions_queue.add_l2_transaction(withdraw0.clone());
             assert_eq!(
                 transactions_queue
-                    .pending_l2_transactions
+                    .scheduled_l2_transactions
                     .peek()
                     .unwrap()
                     .valid_from,
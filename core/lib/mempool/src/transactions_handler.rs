@@ -1,19 +1,33 @@
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
 use futures::channel::{mpsc, oneshot};
 use futures::StreamExt;
 
-use zksync_storage::ConnectionPool;
+use zksync_storage::{ConnectionPool, QueryResult};
+use zksync_token_db_cache::TokenDenylist;
 use zksync_types::{
     mempool::SignedTxsBatch,
     tx::{error::TxAddError, TxEthSignature},
-    PriorityOp, SignedZkSyncTx,
+    AccountId, Nonce, PriorityOp, SignedZkSyncTx, ZkSyncTx,
 };
 
 use crate::state::MempoolState;
 
+/// Minimum fee increase (in percents) of the transaction replacing the pending one.
+pub const REPLACEMENT_FEE_INCREASE_PERCENT: u64 = 10;
+
 #[derive(Debug)]
 pub enum MempoolTransactionRequest {
     /// Add new transaction to mempool, transaction should be previously checked
     /// for correctness (including its Ethereum and ZKSync signatures).
+    /// If there is a pending transaction of the same account with the same nonce, it is
+    /// replaced by the new one, provided that the new transaction pays the fee in the same
+    /// token and the fee is at least `REPLACEMENT_FEE_INCREASE_PERCENT` higher. The replaced
+    /// transaction is removed from the mempool. Transactions of the batches and the ones already
+    /// executed, e.g. in the pending block, can't be replaced.
+    /// Expired transactions are rejected, while the ones which aren't valid yet are scheduled
+    /// for the execution once their validity period starts.
     /// oneshot is used to receive tx add result.
     NewTx(Box<SignedZkSyncTx>, oneshot::Sender<Result<(), TxAddError>>),
 
@@ -23,6 +37,9 @@ pub enum MempoolTransactionRequest {
         bool,
         oneshot::Sender<Result<(), TxAddError>>,
     ),
+    /// Store the last Ethereum block processed by the eth watcher,
+    /// so the API can count the confirmations of the unconfirmed priority ops.
+    EthBlockWatched(u64),
     /// Add a new batch of transactions to the mempool. All transactions in batch must
     /// be either executed successfully, or otherwise fail all together.
     /// Invariants for each individual transaction in the batch are the same as in
@@ -39,10 +56,93 @@ pub(crate) struct MempoolTransactionsHandler {
     pub mempool_state: MempoolState,
     pub requests: mpsc::Receiver<MempoolTransactionRequest>,
     pub max_block_size_chunks: usize,
+    pub token_denylist: TokenDenylist,
+}
+
+/// Checks that the transaction pays enough to replace the pending one with the same nonce.
+fn check_replacement_fee(pending_tx: &ZkSyncTx, tx: &ZkSyncTx) -> Result<(), TxAddError> {
+    if pending_tx.token_id() != tx.token_id() {
+        return Err(TxAddError::ReplacementFeeTokenMismatch);
+    }
+
+    let fee = |tx: &ZkSyncTx| {
+        tx.get_fee_info()
+            .map(|(_, _, _, fee)| fee)
+            .unwrap_or_default()
+    };
+    let pending_fee = fee(pending_tx);
+    let new_fee = fee(tx);
+    let min_fee = &pending_fee * (100 + REPLACEMENT_FEE_INCREASE_PERCENT) / 100u64;
+    if new_fee <= pending_fee || new_fee < min_fee {
+        return Err(TxAddError::ReplacementFeeTooLow(
+            REPLACEMENT_FEE_INCREASE_PERCENT,
+        ));
+    }
+    Ok(())
+}
+
+/// Rejects the transaction which can't be executed anymore at the given timestamp.
+fn check_time_range(tx: &ZkSyncTx, timestamp: u64) -> Result<(), TxAddError> {
+    if tx.time_range().is_expired(timestamp) {
+        Err(TxAddError::TxExpired)
+    } else {
+        Ok(())
+    }
+}
+
+/// Returns the nonce of the first transaction of each account in the batch.
+///
+/// The batch is executed atomically, so it's rejected as a whole if any of its transactions
+/// can't be executed after the previous ones of the same account.
+fn check_batch_nonces<'a>(
+    txs: impl IntoIterator<Item = &'a ZkSyncTx>,
+) -> Result<HashMap<AccountId, Nonce>, TxAddError> {
+    let mut first_nonces = HashMap::new();
+    let mut next_nonces = HashMap::new();
+    for tx in txs {
+        // Close operation does not exist so we will never met this error
+        let account_id = tx.account_id().map_err(|_| TxAddError::Other)?;
+        match next_nonces.get(&account_id) {
+            Some(next_nonce) if tx.nonce() != *next_nonce => {
+                return Err(TxAddError::BatchNonceGap);
+            }
+            Some(_) => {}
+            None => {
+                first_nonces.insert(account_id, tx.nonce());
+            }
+        }
+        next_nonces.insert(account_id, tx.nonce() + 1);
+    }
+    Ok(first_nonces)
+}
+
+fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("failed to get system time")
+        .as_secs()
 }
 
 impl MempoolTransactionsHandler {
+    /// Rejects the transaction using any of the denylisted tokens.
+    async fn check_denylisted_tokens(&self, tx: &SignedZkSyncTx) -> Result<(), TxAddError> {
+        let denylisted = self
+            .token_denylist
+            .find_denylisted(tx.tx.tokens())
+            .await
+            .map_err(|err| {
+                vlog::error!("Failed to load the token denylist: {}", err);
+                TxAddError::DbError
+            })?;
+        match denylisted {
+            Some(token_id) => Err(TxAddError::TokenDenylisted(token_id)),
+            None => Ok(()),
+        }
+    }
+
     async fn add_tx(&mut self, tx: SignedZkSyncTx) -> Result<(), TxAddError> {
+        self.check_denylisted_tokens(&tx).await?;
+        check_time_range(&tx.tx, current_timestamp())?;
         // Correctness should be checked by `signature_checker`, thus
         // `tx.check_correctness()` is not invoked here.
         let mut storage = self.db_pool.access_storage().await.map_err(|err| {
@@ -50,11 +150,12 @@ impl MempoolTransactionsHandler {
             TxAddError::DbError
         })?;
 
+        // Close operation does not exist so we will never met this error
+        let account_id = tx.account_id().map_err(|_| TxAddError::Other)?;
         let nonce = storage
             .chain()
             .account_schema()
-            // Close operation does not exist so we will never met this error
-            .estimate_nonce(tx.account_id().map_err(|_| TxAddError::Other)?)
+            .estimate_nonce(account_id)
             .await
             .map_err(|_| TxAddError::DbError)?
             .unwrap_or_default();
@@ -63,15 +164,56 @@ impl MempoolTransactionsHandler {
             return Err(TxAddError::NonceMismatch);
         }
 
-        storage
+        let pending_tx = storage
             .chain()
             .mempool_schema()
-            .insert_tx(&tx)
+            .get_pending_tx_by_nonce(account_id, tx.nonce())
             .await
-            .map_err(|err| {
-                vlog::error!("Mempool storage access error: {}", err);
-                TxAddError::DbError
-            })?;
+            .map_err(|_| TxAddError::DbError)?;
+        let replaced_tx_hash = match pending_tx {
+            Some((pending_tx, batch_id)) if pending_tx.hash() != tx.hash() => {
+                if batch_id.is_some() {
+                    return Err(TxAddError::ReplacedTxInBatch);
+                }
+                check_replacement_fee(&pending_tx.tx, &tx.tx)?;
+                Some(pending_tx.hash())
+            }
+            _ => None,
+        };
+
+        let result = match replaced_tx_hash {
+            Some(replaced_tx_hash) => {
+                storage
+                    .chain()
+                    .mempool_schema()
+                    .replace_tx(replaced_tx_hash, &tx)
+                    .await
+            }
+            None => storage
+                .chain()
+                .mempool_schema()
+                .insert_tx(&tx)
+                .await
+                .map(|_| true),
+        };
+        let added = result.map_err(|err| {
+            vlog::error!("Mempool storage access error: {}", err);
+            TxAddError::DbError
+        })?;
+        // The pending transaction could be picked by the state keeper in the meantime.
+        if !added {
+            return Err(TxAddError::ReplacedTxExecuted);
+        }
+
+        if let Some(replaced_tx_hash) = replaced_tx_hash {
+            vlog::info!(
+                "Transaction {} replaced the pending transaction {} of the account {}",
+                tx.hash().to_string(),
+                replaced_tx_hash.to_string(),
+                account_id
+            );
+            metrics::increment_counter!("mempool.replaced_txs");
+        }
 
         let labels = vec![
             ("stage", "mempool".to_string()),
@@ -149,19 +291,24 @@ impl MempoolTransactionsHandler {
             TxAddError::DbError
         })?;
 
+        let timestamp = current_timestamp();
         for tx in txs.iter() {
+            self.check_denylisted_tokens(tx).await?;
+            check_time_range(&tx.tx, timestamp)?;
             // Correctness should be checked by `signature_checker`, thus
             // `tx.check_correctness()` is not invoked here.
+        }
+        let first_nonces = check_batch_nonces(txs.iter().map(|tx| &tx.tx))?;
+        for (account_id, first_nonce) in first_nonces {
             let nonce = storage
                 .chain()
                 .account_schema()
-                // Close operation does not exist so we will never met this error
-                .estimate_nonce(tx.account_id().map_err(|_| TxAddError::Other)?)
+                .estimate_nonce(account_id)
                 .await
                 .map_err(|_| TxAddError::DbError)?
                 .unwrap_or_default();
 
-            if tx.nonce() < nonce {
+            if first_nonce < nonce {
                 return Err(TxAddError::NonceMismatch);
             }
         }
@@ -193,6 +340,16 @@ impl MempoolTransactionsHandler {
         Ok(())
     }
 
+    async fn store_last_watched_eth_block(&mut self, eth_block: u64) -> QueryResult<()> {
+        let mut storage = self.db_pool.access_storage().await?;
+        storage
+            .chain()
+            .mempool_schema()
+            .update_last_watched_eth_block(eth_block)
+            .await?;
+        Ok(())
+    }
+
     pub async fn run(mut self) {
         vlog::info!("Transaction mempool handler is running");
         while let Some(request) = self.requests.next().await {
@@ -209,7 +366,118 @@ impl MempoolTransactionsHandler {
                     let tx_add_result = self.add_priority_ops(ops, confirmed).await;
                     resp.send(tx_add_result).unwrap_or_default();
                 }
+                MempoolTransactionRequest::EthBlockWatched(eth_block) => {
+                    if let Err(err) = self.store_last_watched_eth_block(eth_block).await {
+                        vlog::warn!("Failed to store the last watched Ethereum block: {}", err);
+                    }
+                }
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::{
+        tx::{TimeRange, Transfer},
+        AccountId, Address, Nonce, TokenId,
+    };
+
+    fn account_transfer(
+        account_id: AccountId,
+        nonce: Nonce,
+        fee_token: TokenId,
+        fee: u32,
+        time_range: TimeRange,
+    ) -> ZkSyncTx {
+        ZkSyncTx::Transfer(Box::new(Transfer::new(
+            account_id,
+            Address::repeat_byte(1),
+            Address::repeat_byte(2),
+            fee_token,
+            100u32.into(),
+            fee.into(),
+            nonce,
+            time_range,
+            None,
+        )))
+    }
+
+    fn transfer_with_time_range(fee_token: TokenId, fee: u32, time_range: TimeRange) -> ZkSyncTx {
+        account_transfer(AccountId(1), Nonce(0), fee_token, fee, time_range)
+    }
+
+    fn transfer(fee_token: TokenId, fee: u32) -> ZkSyncTx {
+        transfer_with_time_range(fee_token, fee, Default::default())
+    }
+
+    #[test]
+    fn replacement_fee() {
+        let pending_tx = transfer(TokenId(0), 100);
+
+        assert!(check_replacement_fee(&pending_tx, &transfer(TokenId(0), 110)).is_ok());
+        assert!(matches!(
+            check_replacement_fee(&pending_tx, &transfer(TokenId(0), 109)),
+            Err(TxAddError::ReplacementFeeTooLow(
+                REPLACEMENT_FEE_INCREASE_PERCENT
+            ))
+        ));
+        assert!(matches!(
+            check_replacement_fee(&pending_tx, &transfer(TokenId(1), 200)),
+            Err(TxAddError::ReplacementFeeTokenMismatch)
+        ));
+
+        // The fee of the replacement should be strictly higher even for the free transactions.
+        let pending_tx = transfer(TokenId(0), 0);
+        assert!(check_replacement_fee(&pending_tx, &transfer(TokenId(0), 0)).is_err());
+        assert!(check_replacement_fee(&pending_tx, &transfer(TokenId(0), 1)).is_ok());
+    }
+
+    #[test]
+    fn time_range() {
+        let tx = transfer_with_time_range(TokenId(0), 0, TimeRange::new(100, 200));
+
+        // Transactions which aren't valid yet are accepted.
+        assert!(check_time_range(&tx, 50).is_ok());
+        assert!(check_time_range(&tx, 200).is_ok());
+        assert!(matches!(
+            check_time_range(&tx, 201),
+            Err(TxAddError::TxExpired)
+        ));
+    }
+
+    #[test]
+    fn batch_nonces() {
+        let tx = |account_id, nonce| {
+            account_transfer(
+                AccountId(account_id),
+                Nonce(nonce),
+                TokenId(0),
+                0,
+                Default::default(),
+            )
+        };
+
+        // The consecutive nonces of the same account are accepted, the first nonces of
+        // the accounts are checked against the stored ones.
+        let batch = vec![tx(1, 5), tx(2, 0), tx(1, 6), tx(1, 7)];
+        let first_nonces = check_batch_nonces(&batch).unwrap();
+        assert_eq!(first_nonces.len(), 2);
+        assert_eq!(first_nonces[&AccountId(1)], Nonce(5));
+        assert_eq!(first_nonces[&AccountId(2)], Nonce(0));
+
+        // The batch with the nonce gap can never be executed as a whole.
+        let batch = vec![tx(1, 5), tx(2, 0), tx(1, 7)];
+        assert!(matches!(
+            check_batch_nonces(&batch),
+            Err(TxAddError::BatchNonceGap)
+        ));
+        // As well as the batch with the repeated nonce.
+        let batch = vec![tx(1, 5), tx(1, 5)];
+        assert!(matches!(
+            check_batch_nonces(&batch),
+            Err(TxAddError::BatchNonceGap)
+        ));
+    }
+}
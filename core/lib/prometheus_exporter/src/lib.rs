@@ -1,8 +1,9 @@
 //! This module handles metric export to the Prometheus server
 
-use metrics_exporter_prometheus::PrometheusBuilder;
+use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
 use num::rational::Ratio;
 use num::{BigUint, ToPrimitive};
+use once_cell::sync::OnceCell;
 use std::collections::HashMap;
 use std::ops::Add;
 use std::time::{Duration, Instant};
@@ -16,6 +17,9 @@ use zksync_types::{ExecutedOperations, TokenId};
 
 const QUERY_INTERVAL: Duration = Duration::from_secs(30);
 
+/// Handle of the recorder installed by `run_prometheus_exporter`.
+static PROMETHEUS_HANDLE: OnceCell<PrometheusHandle> = OnceCell::new();
+
 pub fn run_operation_counter(connection_pool: ConnectionPool) -> JoinHandle<()> {
     tokio::spawn(async move {
         loop {
@@ -117,6 +121,8 @@ pub fn run_prometheus_exporter(port: u16) -> JoinHandle<()> {
         .listen_address(addr)
         .build_with_exporter()
         .expect("failed to install Prometheus recorder");
+    // The same metrics may be rendered by the application itself, e.g. by the API server.
+    let _ = PROMETHEUS_HANDLE.set(recorder.handle());
     metrics::set_boxed_recorder(Box::new(recorder)).expect("failed to set metrics recorder");
 
     tokio::spawn(async move {
@@ -129,6 +135,12 @@ pub fn run_prometheus_exporter(port: u16) -> JoinHandle<()> {
     })
 }
 
+/// Returns all the metrics of the process in the Prometheus text format,
+/// or `None` if the exporter is not run.
+pub fn render_metrics() -> Option<String> {
+    PROMETHEUS_HANDLE.get().map(PrometheusHandle::render)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{get_volumes, token_amount_to_usd, BigUint, ToPrimitive, TokenId};
@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS ticker_price_history (
+    token_id INTEGER NOT NULL REFERENCES tokens(id) ON UPDATE CASCADE,
+    usd_price NUMERIC NOT NULL,
+    created_at TIMESTAMP with time zone NOT NULL,
+    PRIMARY KEY (token_id, created_at)
+);
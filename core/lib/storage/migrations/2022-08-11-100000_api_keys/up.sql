@@ -0,0 +1,7 @@
+-- API keys granting the clients higher request quotas than the per-IP limit.
+CREATE TABLE IF NOT EXISTS api_keys (
+    api_key TEXT PRIMARY KEY,
+    requests_per_second INTEGER NOT NULL,
+    description TEXT NOT NULL DEFAULT '',
+    created_at TIMESTAMP with time zone NOT NULL DEFAULT now()
+);
@@ -0,0 +1,9 @@
+ALTER TABLE api_keys ADD COLUMN IF NOT EXISTS revoked_at TIMESTAMP with time zone;
+
+-- Number of the requests made with the API key per day.
+CREATE TABLE IF NOT EXISTS api_key_usage (
+    api_key TEXT NOT NULL REFERENCES api_keys(api_key) ON DELETE CASCADE,
+    day DATE NOT NULL,
+    requests BIGINT NOT NULL,
+    PRIMARY KEY (api_key, day)
+);
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS price_provider_health;
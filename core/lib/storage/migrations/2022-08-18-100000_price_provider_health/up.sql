@@ -0,0 +1,10 @@
+-- Health of the price providers of the fee ticker, in the order of their priority.
+CREATE TABLE IF NOT EXISTS price_provider_health (
+    provider TEXT NOT NULL PRIMARY KEY,
+    priority INTEGER NOT NULL,
+    last_success_at TIMESTAMP with time zone,
+    last_failure_at TIMESTAMP with time zone,
+    last_error TEXT,
+    consecutive_failures INTEGER NOT NULL,
+    updated_at TIMESTAMP with time zone NOT NULL
+);
@@ -0,0 +1,10 @@
+-- Discounts of the transaction fees. Every criterion left as NULL matches any transaction.
+CREATE TABLE IF NOT EXISTS fee_discounts (
+    id BIGSERIAL PRIMARY KEY,
+    token_id INTEGER,
+    tx_type TEXT,
+    address bytea,
+    discount_percent INTEGER NOT NULL,
+    description TEXT NOT NULL DEFAULT '',
+    created_at TIMESTAMP with time zone NOT NULL DEFAULT now()
+);
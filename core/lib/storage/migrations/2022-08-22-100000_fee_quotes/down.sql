@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS fee_quotes_token_created_at_idx;
+DROP TABLE IF EXISTS fee_quotes;
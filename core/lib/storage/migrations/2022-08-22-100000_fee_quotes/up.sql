@@ -0,0 +1,13 @@
+-- Fee quotes returned by the API, kept to reconcile the quoted fees with the charged ones.
+CREATE TABLE IF NOT EXISTS fee_quotes (
+    id BIGSERIAL PRIMARY KEY,
+    tx_type TEXT NOT NULL,
+    token_id INTEGER NOT NULL,
+    address bytea NOT NULL,
+    gas_fee NUMERIC NOT NULL,
+    zkp_fee NUMERIC NOT NULL,
+    total_fee NUMERIC NOT NULL,
+    created_at TIMESTAMP with time zone NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS fee_quotes_token_created_at_idx ON fee_quotes (token_id, created_at);
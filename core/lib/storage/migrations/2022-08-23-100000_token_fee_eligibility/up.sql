@@ -0,0 +1,3 @@
+-- Eligibility of the token for paying fees set by the admin API,
+-- NULL means that it's decided by the market volume of the token.
+ALTER TABLE tokens ADD COLUMN IF NOT EXISTS fee_eligibility_override BOOLEAN;
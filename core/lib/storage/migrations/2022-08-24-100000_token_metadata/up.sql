@@ -0,0 +1,8 @@
+-- Optional metadata of the tokens set by the admin API.
+CREATE TABLE IF NOT EXISTS token_metadata (
+    token_id INTEGER PRIMARY KEY REFERENCES tokens (id) ON DELETE CASCADE,
+    logo_uri TEXT,
+    project_url TEXT,
+    display_name TEXT,
+    updated_at TIMESTAMP with time zone NOT NULL DEFAULT now()
+);
@@ -0,0 +1,7 @@
+-- Tokens denylisted by the admin API, they can't be used in the transactions
+-- and are hidden from the API.
+CREATE TABLE IF NOT EXISTS token_denylist (
+    token_id INTEGER PRIMARY KEY REFERENCES tokens (id) ON DELETE CASCADE,
+    reason TEXT NOT NULL,
+    created_at TIMESTAMP with time zone NOT NULL DEFAULT now()
+);
@@ -0,0 +1,14 @@
+-- Responses of the transaction submissions, returned again to the clients
+-- retrying the request with the same idempotency key.
+-- The keys are scoped by the account submitting the request, the key is reserved
+-- with the empty response before the request is submitted.
+CREATE TABLE IF NOT EXISTS idempotency_keys (
+    account_id BIGINT NOT NULL,
+    key TEXT NOT NULL,
+    request_hash bytea NOT NULL,
+    response jsonb,
+    created_at TIMESTAMP with time zone NOT NULL DEFAULT now(),
+    PRIMARY KEY (account_id, key)
+);
+
+CREATE INDEX IF NOT EXISTS idempotency_keys_created_at_idx ON idempotency_keys (created_at);
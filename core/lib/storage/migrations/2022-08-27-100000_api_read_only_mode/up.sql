@@ -0,0 +1,9 @@
+-- Read-only mode of the API servers set by the admin API,
+-- the transaction submissions are rejected while it's enabled.
+-- The table has at most one row.
+CREATE TABLE IF NOT EXISTS api_read_only_mode (
+    id BOOLEAN PRIMARY KEY DEFAULT TRUE CHECK (id),
+    enabled BOOLEAN NOT NULL,
+    reason TEXT,
+    updated_at TIMESTAMP with time zone NOT NULL DEFAULT now()
+);
@@ -0,0 +1,9 @@
+-- Progress of the historical state pruner: the history of the blocks up to
+-- `last_pruned_block` is pruned. The table has at most one row.
+CREATE TABLE IF NOT EXISTS pruning_checkpoint (
+    id BOOLEAN PRIMARY KEY DEFAULT TRUE CHECK (id),
+    last_pruned_block BIGINT NOT NULL,
+    updated_at TIMESTAMP with time zone NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS events_block_number_idx ON events (block_number);
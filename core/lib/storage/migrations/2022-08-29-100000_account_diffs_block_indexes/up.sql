@@ -0,0 +1,6 @@
+-- The historical state of an account is replayed from its diffs up to the block,
+-- so the diffs are looked up by the account and the block range.
+CREATE INDEX IF NOT EXISTS account_balance_updates_account_id_block_number_idx
+    ON account_balance_updates (account_id, block_number);
+CREATE INDEX IF NOT EXISTS account_pubkey_updates_account_id_block_number_idx
+    ON account_pubkey_updates (account_id, block_number);
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS exit_proofs;
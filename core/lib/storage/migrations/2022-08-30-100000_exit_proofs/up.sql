@@ -0,0 +1,15 @@
+-- Requests for the exit proofs of the account balances, the proofs are generated
+-- for the verified state of `block_number` and stored once they are ready, or the
+-- error is stored if the proof can't be generated.
+CREATE TABLE IF NOT EXISTS exit_proofs (
+    account_id BIGINT NOT NULL,
+    token_id INTEGER NOT NULL,
+    block_number BIGINT NOT NULL,
+    proof jsonb,
+    error TEXT,
+    created_at TIMESTAMP with time zone NOT NULL DEFAULT now(),
+    generated_at TIMESTAMP with time zone,
+    PRIMARY KEY (account_id, token_id)
+);
+
+CREATE INDEX IF NOT EXISTS exit_proofs_pending_idx ON exit_proofs (created_at) WHERE proof IS NULL AND error IS NULL;
@@ -0,0 +1,3 @@
+-- The transactions of a token are looked up by the token and ordered by their sequence numbers.
+CREATE INDEX IF NOT EXISTS ix_tx_filters_token_sequence_number
+    ON tx_filters (token, sequence_number) INCLUDE (is_priority);
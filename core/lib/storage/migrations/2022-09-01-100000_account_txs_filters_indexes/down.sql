@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS ix_executed_transactions_sequence_number_filters;
+DROP INDEX IF EXISTS ix_executed_priority_operations_sequence_number_filters;
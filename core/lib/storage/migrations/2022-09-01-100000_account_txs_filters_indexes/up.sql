@@ -0,0 +1,8 @@
+-- The filtered account transactions are joined by their sequence numbers, the filtered
+-- columns are included, so the filters are checked without fetching the transactions.
+CREATE INDEX IF NOT EXISTS ix_executed_transactions_sequence_number_filters
+    ON executed_transactions (sequence_number)
+    INCLUDE (block_number, created_at, success, from_account, to_account);
+CREATE INDEX IF NOT EXISTS ix_executed_priority_operations_sequence_number_filters
+    ON executed_priority_operations (sequence_number)
+    INCLUDE (block_number, created_at, from_account, to_account);
@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS ix_executed_priority_operations_created_at;
+DROP INDEX IF EXISTS ix_executed_transactions_created_at;
+DROP TABLE IF EXISTS network_token_stats;
+DROP TABLE IF EXISTS network_stats;
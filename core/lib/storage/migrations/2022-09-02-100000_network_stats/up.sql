@@ -0,0 +1,20 @@
+-- Network statistics, which are aggregated periodically by the network stats aggregator
+-- over the last day of the transactions. The table has at most one row.
+CREATE TABLE IF NOT EXISTS network_stats (
+    id BOOLEAN PRIMARY KEY DEFAULT TRUE CHECK (id),
+    tx_count_24h BIGINT NOT NULL,
+    active_accounts_24h BIGINT NOT NULL,
+    updated_at TIMESTAMP with time zone NOT NULL DEFAULT now()
+);
+
+-- Statistics of the tokens, replaced along with the network statistics.
+-- `average_fee_24h` is NULL if no fee was paid in the token over the last day.
+CREATE TABLE IF NOT EXISTS network_token_stats (
+    token_id INTEGER PRIMARY KEY,
+    tvl NUMERIC NOT NULL,
+    volume_24h NUMERIC NOT NULL,
+    average_fee_24h NUMERIC
+);
+
+CREATE INDEX IF NOT EXISTS ix_executed_transactions_created_at ON executed_transactions (created_at);
+CREATE INDEX IF NOT EXISTS ix_executed_priority_operations_created_at ON executed_priority_operations (created_at);
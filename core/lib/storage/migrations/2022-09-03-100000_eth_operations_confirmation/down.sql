@@ -0,0 +1,2 @@
+ALTER TABLE eth_operations DROP COLUMN confirmed_eth_block;
+ALTER TABLE eth_operations DROP COLUMN confirmed_at;
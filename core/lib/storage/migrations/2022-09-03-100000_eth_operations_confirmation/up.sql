@@ -0,0 +1,4 @@
+-- The time and the Ethereum block of the confirmation, they are empty for the operations
+-- confirmed before they were recorded.
+ALTER TABLE eth_operations ADD COLUMN confirmed_at TIMESTAMP WITH TIME ZONE DEFAULT NULL;
+ALTER TABLE eth_operations ADD COLUMN confirmed_eth_block BIGINT DEFAULT NULL;
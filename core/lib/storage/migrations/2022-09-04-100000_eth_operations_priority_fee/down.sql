@@ -0,0 +1 @@
+ALTER TABLE eth_operations DROP COLUMN last_used_priority_fee;
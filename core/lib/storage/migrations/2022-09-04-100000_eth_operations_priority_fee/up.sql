@@ -0,0 +1,2 @@
+-- The priority fee of the last sent EIP-1559 transaction, it's empty for the legacy transactions.
+ALTER TABLE eth_operations ADD COLUMN last_used_priority_fee NUMERIC DEFAULT NULL;
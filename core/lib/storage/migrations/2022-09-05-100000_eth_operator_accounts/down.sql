@@ -0,0 +1,2 @@
+ALTER TABLE eth_operations DROP COLUMN operator_address;
+DROP TABLE eth_operator_accounts;
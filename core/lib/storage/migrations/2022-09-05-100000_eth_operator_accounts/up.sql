@@ -0,0 +1,12 @@
+-- Operator accounts sending the Ethereum transactions, the nonce of every account is assigned
+-- separately and the operations are sent by the active one.
+CREATE TABLE eth_operator_accounts (
+    address BYTEA PRIMARY KEY,
+    nonce BIGINT NOT NULL,
+    balance NUMERIC DEFAULT NULL,
+    is_active BOOLEAN NOT NULL DEFAULT false
+);
+
+-- The account which sends the transactions of the operation, it's empty for the operations
+-- sent by the main operator account before the accounts were tracked.
+ALTER TABLE eth_operations ADD COLUMN operator_address BYTEA DEFAULT NULL;
@@ -0,0 +1,2 @@
+DROP TABLE eth_tx_bump_requests;
+DROP TABLE eth_tx_attempts;
@@ -0,0 +1,21 @@
+-- History of the Ethereum transactions sent for the operations, one row per sent transaction.
+CREATE TABLE eth_tx_attempts (
+    id BIGSERIAL PRIMARY KEY,
+    eth_op_id BIGINT NOT NULL REFERENCES eth_operations(id),
+    tx_hash BYTEA NOT NULL,
+    nonce BIGINT NOT NULL,
+    gas_price NUMERIC NOT NULL,
+    priority_fee NUMERIC DEFAULT NULL,
+    operator_address BYTEA DEFAULT NULL,
+    sent_at_block BIGINT NOT NULL,
+    reason TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+CREATE INDEX eth_tx_attempts_eth_op_id_index ON eth_tx_attempts (eth_op_id);
+
+-- Operations whose stuck transactions are requested to be replaced by the admin,
+-- the requests are taken by the Ethereum sender.
+CREATE TABLE eth_tx_bump_requests (
+    eth_op_id BIGINT PRIMARY KEY REFERENCES eth_operations(id),
+    requested_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
@@ -0,0 +1 @@
+DROP TABLE witness_generator_progress;
@@ -0,0 +1,6 @@
+-- Last block of every witness generator shard up to which all the witnesses of the shard are generated.
+CREATE TABLE witness_generator_progress (
+    shard_index INT PRIMARY KEY,
+    last_block BIGINT NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
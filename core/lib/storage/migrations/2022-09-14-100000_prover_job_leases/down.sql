@@ -0,0 +1,7 @@
+DROP TABLE IF EXISTS prover_block_priorities;
+
+DROP INDEX IF EXISTS ix_prover_job_queue_job_status_lease_expires_at;
+ALTER TABLE prover_job_queue
+    DROP COLUMN leased_by,
+    DROP COLUMN lease_expires_at,
+    DROP COLUMN lease_count;
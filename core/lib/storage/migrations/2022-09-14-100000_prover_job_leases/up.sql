@@ -0,0 +1,14 @@
+-- The in-progress jobs are leased to the provers and returned to the queue once the lease expires.
+ALTER TABLE prover_job_queue
+    ADD COLUMN leased_by TEXT,
+    ADD COLUMN lease_expires_at TIMESTAMP WITH TIME ZONE,
+    ADD COLUMN lease_count INT NOT NULL DEFAULT 0;
+CREATE INDEX IF NOT EXISTS ix_prover_job_queue_job_status_lease_expires_at ON prover_job_queue ( job_status, lease_expires_at );
+
+-- Priorities of the prover jobs set for the blocks, the job takes the highest one of its blocks.
+CREATE TABLE prover_block_priorities
+(
+    block_number BIGINT PRIMARY KEY,
+    job_priority INT NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
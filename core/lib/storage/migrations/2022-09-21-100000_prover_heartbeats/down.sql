@@ -0,0 +1,3 @@
+ALTER TABLE prover_job_queue DROP COLUMN leased_at;
+
+DROP TABLE IF EXISTS prover_heartbeats;
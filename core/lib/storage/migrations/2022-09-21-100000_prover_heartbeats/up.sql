@@ -0,0 +1,9 @@
+-- Last time every prover has reached the prover server, either polling for a job or sending a heartbeat.
+CREATE TABLE prover_heartbeats
+(
+    prover_name TEXT PRIMARY KEY,
+    last_seen_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+
+-- Time the job was leased to the prover for the last time, used to measure the proving times.
+ALTER TABLE prover_job_queue ADD COLUMN leased_at TIMESTAMP WITH TIME ZONE;
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS proof_generation_times;
+DROP TABLE IF EXISTS block_verification_costs;
@@ -0,0 +1,26 @@
+-- Gas used by the confirmed Ethereum transactions, amortized over the blocks of their operations.
+CREATE TABLE block_verification_costs
+(
+    block_number BIGINT NOT NULL,
+    action_type TEXT NOT NULL,
+    eth_tx_hash BYTEA NOT NULL,
+    -- Gas used by the whole transaction and the amount of blocks it has processed.
+    operation_gas_used BIGINT NOT NULL,
+    operation_blocks_count INTEGER NOT NULL,
+    gas_used BIGINT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    PRIMARY KEY (block_number, action_type)
+);
+
+-- Wall time of the prover jobs, from the last lease of the job to the stored proof.
+CREATE TABLE proof_generation_times
+(
+    job_id INTEGER PRIMARY KEY,
+    job_type TEXT NOT NULL,
+    first_block BIGINT NOT NULL,
+    last_block BIGINT NOT NULL,
+    prover_name TEXT NOT NULL,
+    proving_time_ms BIGINT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+CREATE INDEX proof_generation_times_created_at_idx ON proof_generation_times (created_at);
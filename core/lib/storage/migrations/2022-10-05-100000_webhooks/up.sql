@@ -0,0 +1,31 @@
+-- Callback URLs notified about the events matching their filters.
+CREATE TABLE webhooks
+(
+    id SERIAL PRIMARY KEY,
+    url TEXT NOT NULL,
+    -- Secret signing the delivered payloads, so the receivers can authenticate them.
+    secret TEXT NOT NULL,
+    -- Serialized `WebhookFilter` of the events delivered to the webhook.
+    filter JSONB NOT NULL,
+    description TEXT NOT NULL DEFAULT '',
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    removed_at TIMESTAMP WITH TIME ZONE
+);
+
+-- Delivery log of the events to the webhooks. The status is either `pending`, `delivered` or `failed`.
+CREATE TABLE webhook_deliveries
+(
+    id BIGSERIAL PRIMARY KEY,
+    webhook_id INTEGER NOT NULL REFERENCES webhooks (id) ON DELETE CASCADE,
+    event_id BIGINT NOT NULL,
+    payload JSONB NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_response_code INTEGER,
+    last_error TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    last_attempt_at TIMESTAMP WITH TIME ZONE,
+    next_attempt_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    UNIQUE (webhook_id, event_id)
+);
+CREATE INDEX webhook_deliveries_pending_idx ON webhook_deliveries (next_attempt_at) WHERE status = 'pending';
@@ -0,0 +1,18 @@
+-- ForcedExit requests for a single token of the target account, submitted in batches by the operator.
+-- The status is either `pending`, `submitted` or `failed`.
+CREATE TABLE forced_exit_automation_requests
+(
+    id BIGSERIAL PRIMARY KEY,
+    target BYTEA NOT NULL,
+    token_id INTEGER NOT NULL,
+    -- Fee of the ForcedExit in the token estimated when the request was made.
+    estimated_fee NUMERIC NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending',
+    tx_hash BYTEA,
+    error TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    processed_at TIMESTAMP WITH TIME ZONE
+);
+-- There is at most one pending request for the token of the account.
+CREATE UNIQUE INDEX forced_exit_automation_requests_pending_idx
+    ON forced_exit_automation_requests (target, token_id) WHERE status = 'pending';
@@ -0,0 +1,8 @@
+-- The last Ethereum block processed by the eth watcher, used by the API to count
+-- the confirmations of the priority operations waiting in the mempool.
+-- The table has at most one row.
+CREATE TABLE IF NOT EXISTS eth_watch_state (
+    id BOOLEAN PRIMARY KEY DEFAULT TRUE CHECK (id),
+    last_eth_block BIGINT NOT NULL,
+    updated_at TIMESTAMP with time zone NOT NULL DEFAULT now()
+);
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS mempool_txs_account_id_nonce_index;
+ALTER TABLE mempool_txs DROP COLUMN IF EXISTS account_id, DROP COLUMN IF EXISTS nonce;
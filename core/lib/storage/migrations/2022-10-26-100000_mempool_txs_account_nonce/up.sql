@@ -0,0 +1,14 @@
+-- The account and the nonce of the transaction, extracted from its JSON, so the pending
+-- transaction with the given nonce is found by the index instead of the scan of the mempool.
+ALTER TABLE mempool_txs
+    ADD COLUMN account_id BIGINT GENERATED ALWAYS AS (
+        COALESCE(
+            tx->>'accountId',
+            tx->>'initiatorAccountId',
+            tx->>'creatorId',
+            tx->>'submitterId'
+        )::BIGINT
+    ) STORED,
+    ADD COLUMN nonce BIGINT GENERATED ALWAYS AS ((tx->>'nonce')::BIGINT) STORED;
+
+CREATE INDEX IF NOT EXISTS mempool_txs_account_id_nonce_index ON mempool_txs (account_id, nonce);
@@ -0,0 +1,7 @@
+-- The last event consumed by each of the event consumers, e.g. the event listener.
+-- The pruner keeps the events which are not consumed by all of them yet.
+CREATE TABLE IF NOT EXISTS event_consumers (
+    name TEXT PRIMARY KEY,
+    last_event_id BIGINT NOT NULL,
+    updated_at TIMESTAMP with time zone NOT NULL DEFAULT now()
+);
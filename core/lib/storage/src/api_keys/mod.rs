@@ -0,0 +1,140 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+// Local imports
+use self::records::{StoredApiKey, StoredApiKeyUsage};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// API keys schema handles the `api_keys` and `api_key_usage` tables, storing the keys
+/// which grant the REST API clients higher request quotas and the usage of these keys.
+#[derive(Debug)]
+pub struct ApiKeysSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> ApiKeysSchema<'a, 'c> {
+    /// Stores the API key with the given request quota. If the key already exists,
+    /// its quota is replaced and the key becomes active again.
+    pub async fn store_api_key(
+        &mut self,
+        api_key: &str,
+        requests_per_second: u32,
+        description: &str,
+    ) -> QueryResult<StoredApiKey> {
+        let start = Instant::now();
+        let stored_key = sqlx::query_as!(
+            StoredApiKey,
+            r#"
+            INSERT INTO api_keys ( api_key, requests_per_second, description )
+            VALUES ( $1, $2, $3 )
+            ON CONFLICT (api_key)
+            DO UPDATE SET requests_per_second = $2, description = $3, revoked_at = NULL
+            RETURNING api_key, requests_per_second, description, created_at, revoked_at
+            "#,
+            api_key,
+            requests_per_second as i32,
+            description
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.api_keys.store_api_key", start.elapsed());
+        Ok(stored_key)
+    }
+
+    /// Revokes the API key, returns `false` if there is no such active key.
+    pub async fn revoke_api_key(&mut self, api_key: &str) -> QueryResult<bool> {
+        let start = Instant::now();
+        let revoked = sqlx::query!(
+            r#"
+            UPDATE api_keys SET revoked_at = now()
+            WHERE api_key = $1 AND revoked_at IS NULL
+            "#,
+            api_key
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected()
+            > 0;
+
+        metrics::histogram!("sql.api_keys.revoke_api_key", start.elapsed());
+        Ok(revoked)
+    }
+
+    /// Returns the number of requests per second allowed for the API key,
+    /// `None` if there is no such key or it is revoked.
+    pub async fn get_api_key_quota(&mut self, api_key: &str) -> QueryResult<Option<u32>> {
+        let start = Instant::now();
+        let quota = sqlx::query!(
+            "SELECT requests_per_second FROM api_keys WHERE api_key = $1 AND revoked_at IS NULL",
+            api_key
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|record| record.requests_per_second as u32);
+
+        metrics::histogram!("sql.api_keys.get_api_key_quota", start.elapsed());
+        Ok(quota)
+    }
+
+    /// Loads all the API keys including the revoked ones, the oldest keys go first.
+    pub async fn load_api_keys(&mut self) -> QueryResult<Vec<StoredApiKey>> {
+        let start = Instant::now();
+        let keys = sqlx::query_as!(
+            StoredApiKey,
+            r#"
+            SELECT api_key, requests_per_second, description, created_at, revoked_at
+            FROM api_keys
+            ORDER BY created_at, api_key
+            "#
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.api_keys.load_api_keys", start.elapsed());
+        Ok(keys)
+    }
+
+    /// Adds the requests made with the API key to its usage for the current day.
+    pub async fn record_api_key_usage(&mut self, api_key: &str, requests: u64) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            INSERT INTO api_key_usage ( api_key, day, requests )
+            VALUES ( $1, CURRENT_DATE, $2 )
+            ON CONFLICT (api_key, day)
+            DO UPDATE SET requests = api_key_usage.requests + $2
+            "#,
+            api_key,
+            requests as i64
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.api_keys.record_api_key_usage", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads the daily usage of the API key, the latest days go first.
+    pub async fn load_api_key_usage(
+        &mut self,
+        api_key: &str,
+    ) -> QueryResult<Vec<StoredApiKeyUsage>> {
+        let start = Instant::now();
+        let usage = sqlx::query_as!(
+            StoredApiKeyUsage,
+            r#"
+            SELECT day, requests FROM api_key_usage
+            WHERE api_key = $1
+            ORDER BY day DESC
+            "#,
+            api_key
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.api_keys.load_api_key_usage", start.elapsed());
+        Ok(usage)
+    }
+}
@@ -0,0 +1,17 @@
+// External imports
+use chrono::{DateTime, NaiveDate, Utc};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredApiKey {
+    pub api_key: String,
+    pub requests_per_second: i32,
+    pub description: String,
+    pub created_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredApiKeyUsage {
+    pub day: NaiveDate,
+    pub requests: i64,
+}
@@ -6,7 +6,8 @@ use sqlx::{types::BigDecimal, Acquire};
 // Workspace imports
 use zksync_crypto::params::{MIN_NFT_TOKEN_ID, NFT_STORAGE_ACCOUNT_ID, NFT_TOKEN_ID};
 use zksync_types::{
-    Account, AccountId, AccountUpdates, Address, BlockNumber, Nonce, PubKeyHash, TokenId,
+    Account, AccountId, AccountUpdate, AccountUpdates, Address, BlockNumber, Nonce, PubKeyHash,
+    TokenId,
 };
 // Local imports
 use self::records::*;
@@ -327,6 +328,102 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
         Ok(((last_block, account), account_state))
     }
 
+    /// Loads the state of the account after the committed block by replaying all the diffs
+    /// of the account up to this block, along with the last block that updated the account.
+    /// Returns `None` if the account didn't exist at the block.
+    ///
+    /// The diffs of the pruned blocks are partially deleted, so the state is correct only
+    /// for the blocks starting from the last pruned one, the caller is expected to check it.
+    pub async fn account_state_at_block(
+        &mut self,
+        account_id: AccountId,
+        block_number: BlockNumber,
+    ) -> QueryResult<Option<(BlockNumber, Account)>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        let block_number = i64::from(*block_number);
+
+        let account_balance_diff = sqlx::query_as!(
+            StorageAccountUpdate,
+            "
+                SELECT * FROM account_balance_updates
+                WHERE account_id = $1 AND block_number <= $2
+            ",
+            i64::from(*account_id),
+            block_number
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+
+        let account_creation_diff = sqlx::query_as!(
+            StorageAccountCreation,
+            "
+                SELECT * FROM account_creates
+                WHERE account_id = $1 AND block_number <= $2
+            ",
+            i64::from(*account_id),
+            block_number
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+
+        let account_pubkey_diff = sqlx::query_as!(
+            StorageAccountPubkeyUpdate,
+            "
+                SELECT * FROM account_pubkey_updates
+                WHERE account_id = $1 AND block_number <= $2
+            ",
+            i64::from(*account_id),
+            block_number
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+
+        let mint_nft_updates = sqlx::query_as!(
+            StorageMintNFTUpdate,
+            "
+                SELECT * FROM mint_nft_updates
+                WHERE creator_account_id = $1 AND block_number <= $2
+            ",
+            *account_id as i32,
+            block_number
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+        transaction.commit().await?;
+
+        let mut account_diff = Vec::new();
+        account_diff.extend(
+            account_balance_diff
+                .into_iter()
+                .map(StorageAccountDiff::from),
+        );
+        account_diff.extend(
+            account_creation_diff
+                .into_iter()
+                .map(StorageAccountDiff::from),
+        );
+        account_diff.extend(
+            account_pubkey_diff
+                .into_iter()
+                .map(StorageAccountDiff::from),
+        );
+        account_diff.extend(mint_nft_updates.into_iter().map(StorageAccountDiff::from));
+        account_diff.sort_by(StorageAccountDiff::cmp_order);
+
+        let last_update_block = account_diff
+            .last()
+            .map(|diff| BlockNumber(diff.block_number() as u32));
+        // The account is replayed from scratch, starting with its creation.
+        let account_state = account_diff
+            .into_iter()
+            .map(|diff| <(AccountId, AccountUpdate)>::from(diff).1)
+            .fold(None, Account::apply_update);
+
+        metrics::histogram!("sql.chain.account.account_state_at_block", start.elapsed());
+        Ok(last_update_block.zip(account_state))
+    }
+
     /// Loads the last verified state for the account (i.e. the one obtained in the last block
     /// which was both committed and verified).
     pub async fn last_verified_state_for_account(
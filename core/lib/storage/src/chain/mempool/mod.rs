@@ -3,16 +3,17 @@ use std::{collections::VecDeque, convert::TryFrom, str::FromStr, time::Instant};
 // External imports
 use itertools::Itertools;
 // Workspace imports
+use zksync_api_types::v02::mempool::{MempoolStats, QueuedTx};
 use zksync_api_types::v02::pagination::PaginationDirection;
 use zksync_api_types::v02::transaction::{
-    ApiTxBatch, BatchStatus, TxHashSerializeWrapper, TxInBlockStatus,
+    ApiTxBatch, BatchStatus, PriorityOpId, TxHashSerializeWrapper, TxInBlockStatus,
 };
 use zksync_types::{
     block::IncompleteBlock,
     mempool::SignedTxVariant,
     tx::{TxEthSignature, TxHash},
-    AccountId, Address, BlockNumber, ExecutedOperations, ExecutedPriorityOp, ExecutedTx,
-    PriorityOp, SerialId, SignedZkSyncTx, ZkSyncPriorityOp, H256,
+    AccountId, Address, BlockNumber, ExecutedOperations, ExecutedPriorityOp, ExecutedTx, Nonce,
+    PriorityOp, SerialId, SignedZkSyncTx, ZkSyncPriorityOp, ZkSyncTx, H256,
 };
 // Local imports
 use self::records::{MempoolPriorityOp, MempoolTx, QueuedBatchTx, RevertedBlock};
@@ -47,7 +48,8 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         let excluded_txs: Vec<String> = executed_txs.iter().map(|tx| tx.to_string()).collect();
         let txs: Vec<MempoolTx> = sqlx::query_as!(
             MempoolTx,
-            "SELECT * FROM mempool_txs WHERE reverted = false AND tx_hash NOT IN (
+            "SELECT id, tx_hash, tx, created_at, eth_sign_data, batch_id, next_priority_op_serial_id, reverted
+            FROM mempool_txs WHERE reverted = false AND tx_hash NOT IN (
                 SELECT u.hashes FROM UNNEST ($1::text[]) as u(hashes)
             )
 
@@ -179,7 +181,8 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
 
             sqlx::query_as!(
                 MempoolTx,
-                "SELECT * FROM mempool_txs
+                "SELECT id, tx_hash, tx, created_at, eth_sign_data, batch_id, next_priority_op_serial_id, reverted
+                FROM mempool_txs
                 ORDER BY batch_id DESC
                 LIMIT 1",
             )
@@ -342,7 +345,8 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
 
         let mempool_tx = sqlx::query_as!(
             MempoolTx,
-            "SELECT * from mempool_txs
+            "SELECT id, tx_hash, tx, created_at, eth_sign_data, batch_id, next_priority_op_serial_id, reverted
+            FROM mempool_txs
             WHERE tx_hash = $1",
             &tx_hash
         )
@@ -353,6 +357,193 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         Ok(mempool_tx)
     }
 
+    /// Returns the transactions in the mempool that are either sent by the account
+    /// or transfer funds to its address, in the order they were received.
+    pub async fn get_account_pending_txs(
+        &mut self,
+        account_id: AccountId,
+        address: Address,
+    ) -> QueryResult<Vec<SignedZkSyncTx>> {
+        let start = Instant::now();
+
+        // The transactions are stored as JSON, so the account fields are matched
+        // in their serialized form.
+        let account_id = account_id.to_string();
+        let address = format!("{:?}", address);
+        let txs = sqlx::query_as!(
+            MempoolTx,
+            r#"SELECT id, tx_hash, tx, created_at, eth_sign_data, batch_id, next_priority_op_serial_id, reverted
+            FROM mempool_txs
+            WHERE tx->>'accountId' = $1
+                OR tx->>'initiatorAccountId' = $1
+                OR tx->>'creatorId' = $1
+                OR tx->>'submitterId' = $1
+                OR tx->'orders'->0->>'accountId' = $1
+                OR tx->'orders'->1->>'accountId' = $1
+                OR tx->>'to' = $2
+                OR tx->>'target' = $2
+                OR tx->'orders'->0->>'recipient' = $2
+                OR tx->'orders'->1->>'recipient' = $2
+            ORDER BY id"#,
+            account_id,
+            address
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "get_account_pending_txs");
+        txs.into_iter()
+            .map(SignedZkSyncTx::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(anyhow::Error::from)
+    }
+
+    /// Returns the pending transaction sent by the account with the given nonce along with
+    /// the ID of its batch, if the transaction is a part of some batch.
+    pub async fn get_pending_tx_by_nonce(
+        &mut self,
+        account_id: AccountId,
+        nonce: Nonce,
+    ) -> QueryResult<Option<(SignedZkSyncTx, Option<i64>)>> {
+        let start = Instant::now();
+
+        let mempool_tx = sqlx::query_as!(
+            MempoolTx,
+            "SELECT id, tx_hash, tx, created_at, eth_sign_data, batch_id, next_priority_op_serial_id, reverted
+            FROM mempool_txs
+            WHERE reverted = false AND account_id = $1 AND nonce = $2
+            ORDER BY id
+            LIMIT 1",
+            i64::from(*account_id),
+            i64::from(*nonce)
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "get_pending_tx_by_nonce");
+        mempool_tx
+            .map(|mempool_tx| {
+                let batch_id = Some(mempool_tx.batch_id).filter(|&batch_id| batch_id != 0);
+                SignedZkSyncTx::try_from(mempool_tx).map(|tx| (tx, batch_id))
+            })
+            .transpose()
+            .map_err(anyhow::Error::from)
+    }
+
+    /// Returns the transactions sent by the account which are awaiting in the mempool,
+    /// along with the amount of the transactions ahead of them in the queue.
+    /// The transactions returned to the mempool by the blocks revert are executed first.
+    pub async fn get_account_queued_txs(
+        &mut self,
+        account_id: AccountId,
+    ) -> QueryResult<Vec<QueuedTx>> {
+        let start = Instant::now();
+
+        let account_id = account_id.to_string();
+        let records = sqlx::query!(
+            r#"SELECT tx, created_at, batch_id, queue_position AS "queue_position!" FROM (
+                SELECT *, ROW_NUMBER() OVER (ORDER BY reverted DESC, id) - 1 AS queue_position
+                FROM mempool_txs
+            ) AS queue
+            WHERE tx->>'accountId' = $1
+                OR tx->>'initiatorAccountId' = $1
+                OR tx->>'creatorId' = $1
+                OR tx->>'submitterId' = $1
+            ORDER BY queue_position"#,
+            account_id
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        let queued_txs = records
+            .into_iter()
+            .map(|record| {
+                let tx: ZkSyncTx = serde_json::from_value(record.tx)?;
+                Ok(QueuedTx {
+                    tx_hash: tx.hash(),
+                    nonce: tx.nonce(),
+                    batch_id: Some(record.batch_id as u64).filter(|&batch_id| batch_id != 0),
+                    created_at: record.created_at,
+                    queue_position: record.queue_position as u64,
+                })
+            })
+            .collect::<Result<Vec<_>, serde_json::Error>>()?;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "get_account_queued_txs");
+        Ok(queued_txs)
+    }
+
+    /// Returns the current state of the mempool queue.
+    pub async fn get_mempool_stats(&mut self) -> QueryResult<MempoolStats> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let txs_stats = sqlx::query!(
+            r#"SELECT
+                COUNT(*) AS "txs_count!",
+                COUNT(DISTINCT batch_id) FILTER (WHERE batch_id <> 0) AS "batches_count!",
+                COUNT(*) FILTER (WHERE reverted) AS "reverted_txs_count!",
+                MIN(created_at) AS oldest_tx_created_at
+            FROM mempool_txs"#
+        )
+        .fetch_one(transaction.conn())
+        .await?;
+        let priority_ops_count = sqlx::query!(
+            r#"SELECT COUNT(*) AS "count!" FROM mempool_priority_operations
+            WHERE confirmed = true AND reverted = false"#
+        )
+        .fetch_one(transaction.conn())
+        .await?
+        .count;
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "get_mempool_stats");
+        Ok(MempoolStats {
+            txs_count: txs_stats.txs_count as u64,
+            batches_count: txs_stats.batches_count as u64,
+            reverted_txs_count: txs_stats.reverted_txs_count as u64,
+            priority_ops_count: priority_ops_count as u64,
+            oldest_tx_created_at: txs_stats.oldest_tx_created_at,
+        })
+    }
+
+    /// Replaces the pending transaction with the new one, which is placed
+    /// at the end of the mempool queue.
+    /// Returns `false` without changing the mempool if the pending transaction is already
+    /// executed, including the executed transactions of the pending block.
+    pub async fn replace_tx(
+        &mut self,
+        replaced_tx_hash: TxHash,
+        tx_data: &SignedZkSyncTx,
+    ) -> QueryResult<bool> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let removed = sqlx::query!(
+            "DELETE FROM mempool_txs
+            WHERE tx_hash = $1 AND NOT EXISTS (
+                SELECT 1 FROM executed_transactions WHERE tx_hash = $2
+            )",
+            hex::encode(replaced_tx_hash.as_ref()),
+            replaced_tx_hash.as_ref()
+        )
+        .execute(transaction.conn())
+        .await?
+        .rows_affected()
+            > 0;
+        if removed {
+            transaction
+                .chain()
+                .mempool_schema()
+                .insert_tx(tx_data)
+                .await?;
+        }
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.chain.mempool.replace_tx", start.elapsed());
+        Ok(removed)
+    }
+
     /// Removes transactions that are already committed.
     /// Though it's unlikely that mempool schema will ever contain a committed
     /// transaction, it's better to ensure that we won't process the same transaction
@@ -501,6 +692,18 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         Ok(serial_id.map(|v| v as u64))
     }
 
+    /// Returns the number of the pending priority operations of the address.
+    pub async fn get_pending_deposits_count(&mut self, address: Address) -> QueryResult<u32> {
+        let count = sqlx::query!(
+            r#"SELECT COUNT(*) as "count!" FROM mempool_priority_operations WHERE l2_address = $1"#,
+            address.as_bytes().to_vec()
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .count;
+        Ok(count as u32)
+    }
+
     pub async fn get_pending_deposits_for(
         &mut self,
         address: Address,
@@ -548,6 +751,48 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         .map(|op| op.into());
         Ok(op)
     }
+
+    /// Loads the priority operation waiting for the execution along with the flag whether
+    /// it has enough confirmations on L1 to be executed.
+    pub async fn get_pending_priority_op(
+        &mut self,
+        id: PriorityOpId,
+    ) -> QueryResult<Option<(PriorityOp, bool)>> {
+        let start = Instant::now();
+        let (serial_id, eth_hash) = match id {
+            PriorityOpId::SerialId(serial_id) => (serial_id as i64, Vec::new()),
+            PriorityOpId::EthHash(eth_hash) => (-1, eth_hash.as_bytes().to_vec()),
+        };
+        let op = sqlx::query!(
+            r#"
+                SELECT serial_id,data,deadline_block,eth_hash,
+                       tx_hash,eth_block,eth_block_index,created_at,confirmed
+                FROM mempool_priority_operations
+                WHERE (serial_id = $1 OR eth_hash = $2) AND reverted = false
+            "#,
+            serial_id,
+            eth_hash
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|op| {
+            let priority_op = MempoolPriorityOp {
+                serial_id: op.serial_id,
+                tx_hash: op.tx_hash,
+                eth_hash: op.eth_hash,
+                data: op.data,
+                created_at: op.created_at,
+                eth_block: op.eth_block,
+                eth_block_index: op.eth_block_index,
+                deadline_block: op.deadline_block,
+            };
+            (priority_op.into(), op.confirmed)
+        });
+
+        metrics::histogram!("sql.chain.mempool.get_pending_priority_op", start.elapsed());
+        Ok(op)
+    }
+
     pub async fn get_pending_deposits(&mut self, address: Address) -> QueryResult<Vec<PriorityOp>> {
         let ops = sqlx::query_as!(
             MempoolPriorityOp,
@@ -564,6 +809,62 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         Ok(ops.into_iter().map(|op| op.into()).collect())
     }
 
+    /// Loads the deposits to the address seen by the eth watcher which don't have
+    /// enough confirmations to be processed yet.
+    pub async fn get_unconfirmed_deposits(
+        &mut self,
+        address: Address,
+    ) -> QueryResult<Vec<PriorityOp>> {
+        let start = Instant::now();
+        let ops = sqlx::query_as!(
+            MempoolPriorityOp,
+            r#"
+            SELECT serial_id,data,deadline_block,eth_hash,
+                   tx_hash,eth_block,eth_block_index,created_at
+            FROM mempool_priority_operations
+            WHERE type = 'Deposit' AND l2_address = $1 AND NOT confirmed AND reverted = false
+            ORDER BY serial_id"#,
+            address.as_bytes().to_vec()
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "schema" => "mempool", "method" => "get_unconfirmed_deposits");
+        Ok(ops.into_iter().map(|op| op.into()).collect())
+    }
+
+    /// Stores the last Ethereum block processed by the eth watcher.
+    pub async fn update_last_watched_eth_block(&mut self, eth_block: u64) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            INSERT INTO eth_watch_state ( last_eth_block )
+            VALUES ( $1 )
+            ON CONFLICT (id) DO UPDATE
+            SET last_eth_block = EXCLUDED.last_eth_block, updated_at = now()
+            "#,
+            eth_block as i64
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "schema" => "mempool", "method" => "update_last_watched_eth_block");
+        Ok(())
+    }
+
+    /// Loads the last Ethereum block processed by the eth watcher,
+    /// returns `None` if the watcher hasn't processed any block yet.
+    pub async fn last_watched_eth_block(&mut self) -> QueryResult<Option<u64>> {
+        let start = Instant::now();
+        let eth_block = sqlx::query!("SELECT last_eth_block FROM eth_watch_state")
+            .fetch_optional(self.0.conn())
+            .await?
+            .map(|row| row.last_eth_block as u64);
+
+        metrics::histogram!("sql.chain", start.elapsed(), "schema" => "mempool", "method" => "last_watched_eth_block");
+        Ok(eth_block)
+    }
+
     pub async fn remove_priority_ops_from_mempool(&mut self, ids: &[u64]) -> QueryResult<()> {
         let ids: Vec<_> = ids.iter().map(|v| *v as i64).collect();
         sqlx::query!(
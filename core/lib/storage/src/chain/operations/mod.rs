@@ -1,7 +1,7 @@
 // Built-in deps
 use std::time::Instant;
 // External imports
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
 // Workspace imports
 use zksync_types::{
     aggregated_operations::{AggregatedActionType, AggregatedOperation},
@@ -51,6 +51,35 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         Ok(BlockNumber(max_block as u32))
     }
 
+    /// Returns the last blocks of the latest `limit` confirmed aggregated operations of the given type
+    /// with the creation time of each operation, the latest operation first.
+    pub async fn load_recent_confirmed_aggregated_actions(
+        &mut self,
+        aggregated_action_type: AggregatedActionType,
+        limit: u32,
+    ) -> QueryResult<Vec<(BlockNumber, DateTime<Utc>)>> {
+        let start = Instant::now();
+        let actions = sqlx::query!(
+            r#"SELECT to_block, created_at FROM aggregate_operations
+            WHERE action_type = $1 AND confirmed IS DISTINCT FROM false
+            ORDER BY to_block DESC
+            LIMIT $2"#,
+            aggregated_action_type.to_string(),
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|action| (BlockNumber(action.to_block as u32), action.created_at))
+        .collect();
+
+        metrics::histogram!(
+            "sql.chain.operations.load_recent_confirmed_aggregated_actions",
+            start.elapsed()
+        );
+        Ok(actions)
+    }
+
     pub async fn get_stored_aggregated_operation(
         &mut self,
         block_number: BlockNumber,
@@ -716,6 +745,18 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         )
         .execute(transaction.conn())
         .await?;
+        sqlx::query!(
+            "DELETE FROM eth_tx_attempts WHERE eth_op_id = ANY($1)",
+            &eth_op_ids
+        )
+        .execute(transaction.conn())
+        .await?;
+        sqlx::query!(
+            "DELETE FROM eth_tx_bump_requests WHERE eth_op_id = ANY($1)",
+            &eth_op_ids
+        )
+        .execute(transaction.conn())
+        .await?;
         sqlx::query!(
             "DELETE FROM eth_aggregated_ops_binding WHERE op_id = ANY($1)",
             &op_ids
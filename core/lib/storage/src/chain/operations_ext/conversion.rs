@@ -1,9 +1,10 @@
 // Built-in deps
 // External imports
+use chrono::Utc;
 // Workspace imports
 use zksync_api_types::v02::transaction::{
-    ForcedExitData, L1Receipt, L1Transaction, L2Receipt, L2Transaction, Receipt, Transaction,
-    TransactionData, TxData, TxInBlockStatus, WithdrawData, WithdrawNFTData,
+    ForcedExitData, L1Details, L1Receipt, L1Transaction, L2Receipt, L2Transaction, Receipt,
+    Transaction, TransactionData, TxData, TxInBlockStatus, WithdrawData, WithdrawNFTData,
 };
 use zksync_types::{
     tx::{EthSignData, TxHash},
@@ -12,10 +13,21 @@ use zksync_types::{
 // Local imports
 use super::records::{StorageTxData, StorageTxReceipt};
 
+/// Returns the status of the transaction which is not executed yet: it's scheduled
+/// until its validity period starts.
+fn mempool_tx_status(tx: &ZkSyncTx) -> TxInBlockStatus {
+    if tx.time_range().is_scheduled(Utc::now().timestamp() as u64) {
+        TxInBlockStatus::Scheduled
+    } else {
+        TxInBlockStatus::Queued
+    }
+}
+
 impl StorageTxReceipt {
     pub(super) fn receipt_from_storage_receipt(
         receipt: StorageTxReceipt,
         is_block_finalized: Option<bool>,
+        l1_details: L1Details,
     ) -> Receipt {
         if receipt.block_number.is_some() {
             let status = if receipt.success.unwrap() {
@@ -35,6 +47,7 @@ impl StorageTxReceipt {
                         .block_number
                         .map(|number| BlockNumber(number as u32)),
                     id: receipt.priority_op_serialid.unwrap() as u64,
+                    l1_details,
                 })
             } else {
                 Receipt::L2(L2Receipt {
@@ -44,14 +57,17 @@ impl StorageTxReceipt {
                         .block_number
                         .map(|number| BlockNumber(number as u32)),
                     fail_reason: receipt.fail_reason,
+                    l1_details,
                 })
             }
         } else {
+            let tx: ZkSyncTx = serde_json::from_value(receipt.op.unwrap()).unwrap();
             Receipt::L2(L2Receipt {
-                status: TxInBlockStatus::Queued,
+                status: mempool_tx_status(&tx),
                 tx_hash: TxHash::from_slice(&receipt.tx_hash).unwrap(),
                 rollup_block: None,
                 fail_reason: None,
+                l1_details,
             })
         }
     }
@@ -127,16 +143,15 @@ impl StorageTxData {
                 batch_id,
             }
         } else {
-            let tx_data = Self::tx_data_from_zksync_tx(
-                serde_json::from_value(data.op).unwrap(),
-                complete_withdrawals_tx_hash,
-            );
+            let tx: ZkSyncTx = serde_json::from_value(data.op).unwrap();
+            let status = mempool_tx_status(&tx);
+            let tx_data = Self::tx_data_from_zksync_tx(tx, complete_withdrawals_tx_hash);
             Transaction {
                 tx_hash,
                 block_index: data.block_index.map(|i| i as u32),
                 block_number: None,
                 op: tx_data,
-                status: TxInBlockStatus::Queued,
+                status,
                 fail_reason: None,
                 created_at: Some(data.created_at),
                 batch_id,
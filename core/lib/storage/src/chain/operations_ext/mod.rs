@@ -1,24 +1,28 @@
 // Built-in deps
-use std::time::Instant;
+use std::{convert::TryFrom, str::FromStr, time::Instant};
 
 // External imports
 use chrono::{DateTime, Utc};
+use sqlx::{postgres::PgArguments, query::QueryAs, Postgres};
 
 // Workspace imports
 use zksync_api_types::{
     v02::{
-        pagination::{AccountTxsRequest, PaginationDirection, PaginationQuery},
+        pagination::{
+            AccountTxDirection, AccountTxsFilters, AccountTxsRequest, PaginationDirection,
+            PaginationQuery, TokenTxsRequest,
+        },
         transaction::{
-            ApiTxBatch, BatchStatus, Receipt, Transaction, TxData, TxHashSerializeWrapper,
-            TxInBlockStatus,
+            ApiTxBatch, BatchStatus, L1Details, PriorityOpId, PriorityOpLifecycle, PriorityOpStage,
+            Receipt, Transaction, TxData, TxHashSerializeWrapper, TxInBlockStatus,
         },
     },
     Either,
 };
 use zksync_crypto::params;
 use zksync_types::{
-    aggregated_operations::AggregatedActionType, tx::TxHash, Address, BlockNumber, TokenId,
-    ZkSyncOp, ZkSyncTx, H256,
+    aggregated_operations::AggregatedActionType, tx::TxHash, Address, BlockNumber, EthBlockId,
+    PriorityOp, TokenId, ZkSyncOp, ZkSyncTx, H256,
 };
 
 // Local imports
@@ -26,7 +30,7 @@ use self::records::{
     AccountCreatedAt, InBlockBatchTx, PriorityOpReceiptResponse, StorageTxData, StorageTxReceipt,
     TransactionsHistoryItem, TxByHashResponse, TxReceiptResponse, Web3TxData, Web3TxReceipt,
 };
-use crate::chain::operations_ext::records::SequenceNumberRecord;
+use crate::chain::operations_ext::records::{AccountTxExportRecord, SequenceNumberRecord};
 use crate::{
     chain::{
         block::records::TransactionItem,
@@ -48,6 +52,104 @@ pub enum SearchDirection {
     Newer,
 }
 
+/// Joins the executed transaction or priority operation of every `tx_filters` row, so that
+/// the account transactions can be filtered by their columns.
+const FILTERS_JOINS: &str = "
+    LEFT JOIN executed_transactions t
+        ON NOT f.is_priority AND t.sequence_number = f.sequence_number
+    LEFT JOIN executed_priority_operations p
+        ON f.is_priority AND p.sequence_number = f.sequence_number";
+
+/// Returns the SQL conditions of the account transactions filters, each one prefixed with `AND`.
+///
+/// The conditions refer to the executed transactions aliased as `t` and, if `with_priority_ops`
+/// is set, to the executed priority operations aliased as `p`, only one of which is joined for
+/// every row. The account address is the parameter `$1`, the values of the filters are the four
+/// parameters starting from `first_param`, which are bound by `bind_filters`.
+fn filters_conditions(
+    filters: &AccountTxsFilters,
+    with_priority_ops: bool,
+    first_param: usize,
+) -> String {
+    let column = |name: &str| {
+        if with_priority_ops {
+            format!("COALESCE(t.{0}, p.{0})", name)
+        } else {
+            format!("t.{}", name)
+        }
+    };
+    let param = |offset: usize| format!("${}", first_param + offset);
+    // The priority operations are always successful.
+    let success = if with_priority_ops {
+        "COALESCE(t.success, true)"
+    } else {
+        "t.success"
+    };
+
+    let mut conditions = Vec::new();
+    if filters.tx_type.is_some() {
+        let tx_type = if with_priority_ops {
+            "COALESCE(t.tx->>'type', p.operation->>'type')"
+        } else {
+            "t.tx->>'type'"
+        };
+        conditions.push(format!("{} = {}", tx_type, param(0)));
+    }
+    match filters.tx_direction {
+        Some(AccountTxDirection::Incoming) => {
+            conditions.push(format!("{} = $1", column("to_account")))
+        }
+        Some(AccountTxDirection::Outgoing) => {
+            conditions.push(format!("{} = $1", column("from_account")))
+        }
+        None => {}
+    }
+    match filters.status {
+        Some(TxInBlockStatus::Committed) => conditions.push(format!(
+            "{} AND {} > {}",
+            success,
+            column("block_number"),
+            param(1)
+        )),
+        Some(TxInBlockStatus::Finalized) => conditions.push(format!(
+            "{} AND {} <= {}",
+            success,
+            column("block_number"),
+            param(1)
+        )),
+        Some(TxInBlockStatus::Rejected) => conditions.push(format!("NOT {}", success)),
+        // The executed transactions are never queued.
+        Some(TxInBlockStatus::Queued) | Some(TxInBlockStatus::Scheduled) => {
+            conditions.push("FALSE".to_string())
+        }
+        None => {}
+    }
+    if filters.created_after.is_some() {
+        conditions.push(format!("{} >= {}", column("created_at"), param(2)));
+    }
+    if filters.created_before.is_some() {
+        conditions.push(format!("{} < {}", column("created_at"), param(3)));
+    }
+
+    conditions
+        .into_iter()
+        .map(|condition| format!(" AND {}", condition))
+        .collect()
+}
+
+/// Binds the values of the filters referred by the `filters_conditions`.
+fn bind_filters<'q, O>(
+    query: QueryAs<'q, Postgres, O, PgArguments>,
+    filters: &AccountTxsFilters,
+    last_finalized: BlockNumber,
+) -> QueryAs<'q, Postgres, O, PgArguments> {
+    query
+        .bind(filters.tx_type.clone())
+        .bind(i64::from(*last_finalized))
+        .bind(filters.created_after)
+        .bind(filters.created_before)
+}
+
 /// `OperationsExt` schema is a logical extension for an `Operations` schema,
 /// which provides more getters for transactions.
 /// While `Operations` getters are very basic, `OperationsExt` schema can transform
@@ -103,7 +205,8 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                         success,
                         fail_reason,
                         Null::bigint as eth_block,
-                        Null::bigint as priority_op_serialid
+                        Null::bigint as priority_op_serialid,
+                        Null::jsonb as op
                     FROM executed_transactions
                     WHERE tx_hash = $1
                 ), priority_op AS (
@@ -113,7 +216,8 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                         true as success,
                         Null as fail_reason,
                         eth_block,
-                        priority_op_serialid
+                        priority_op_serialid,
+                        Null::jsonb as op
                     FROM executed_priority_operations
                     WHERE tx_hash = $1 OR eth_hash = $1
                 ), mempool_tx AS (
@@ -123,7 +227,8 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                         Null::boolean as success,
                         Null as fail_reason,
                         Null::bigint as eth_block,
-                        Null::bigint as priority_op_serialid
+                        Null::bigint as priority_op_serialid,
+                        tx as op
                     FROM mempool_txs
                     WHERE tx_hash = $2
                 ),
@@ -140,7 +245,8 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                     success as "success?",
                     fail_reason as "fail_reason?",
                     eth_block as "eth_block?",
-                    priority_op_serialid as "priority_op_serialid?"
+                    priority_op_serialid as "priority_op_serialid?",
+                    op as "op?"
                 FROM everything
             "#,
             hash,
@@ -152,10 +258,20 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         let result = if let Some(receipt) = receipt {
             let is_block_finalized =
                 is_block_finalized(&mut transaction, receipt.block_number).await?;
+            // The rejected transactions aren't committed on L1 along with their block.
+            let l1_details = match receipt.block_number {
+                Some(block_number) if receipt.success == Some(true) => {
+                    OperationsExtSchema(&mut transaction)
+                        .load_block_l1_details(BlockNumber(block_number as u32))
+                        .await?
+                }
+                _ => L1Details::default(),
+            };
 
             Some(StorageTxReceipt::receipt_from_storage_receipt(
                 receipt,
                 is_block_finalized,
+                l1_details,
             ))
         } else {
             None
@@ -169,6 +285,64 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         Ok(result)
     }
 
+    /// Loads the confirmed Ethereum transactions committing and executing the block,
+    /// as they are recorded by the Ethereum sender.
+    pub async fn load_block_l1_details(
+        &mut self,
+        block_number: BlockNumber,
+    ) -> QueryResult<L1Details> {
+        let start = Instant::now();
+        let records = sqlx::query!(
+            r#"
+                SELECT
+                    aggregate_operations.action_type,
+                    eth_operations.final_hash,
+                    eth_operations.confirmed_at,
+                    eth_operations.confirmed_eth_block
+                FROM aggregate_operations
+                    INNER JOIN eth_aggregated_ops_binding ON aggregate_operations.id = eth_aggregated_ops_binding.op_id
+                    INNER JOIN eth_operations ON eth_operations.id = eth_aggregated_ops_binding.eth_op_id
+                WHERE aggregate_operations.confirmed = true AND eth_operations.confirmed = true
+                    AND $1 BETWEEN aggregate_operations.from_block AND aggregate_operations.to_block
+                    AND aggregate_operations.action_type = ANY($2)
+            "#,
+            i64::from(*block_number),
+            &[
+                AggregatedActionType::CommitBlocks.to_string(),
+                AggregatedActionType::ExecuteBlocks.to_string(),
+            ]
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        let mut details = L1Details::default();
+        for record in records {
+            let tx_hash = record.final_hash.map(|hash| H256::from_slice(&hash));
+            let eth_block = record
+                .confirmed_eth_block
+                .map(|block| EthBlockId(block as u64));
+            match AggregatedActionType::from_str(&record.action_type) {
+                Ok(AggregatedActionType::CommitBlocks) => {
+                    details.commit_tx_hash = tx_hash;
+                    details.commit_eth_block = eth_block;
+                    details.committed_at = record.confirmed_at;
+                }
+                Ok(AggregatedActionType::ExecuteBlocks) => {
+                    details.verify_tx_hash = tx_hash;
+                    details.verify_eth_block = eth_block;
+                    details.finalized_at = record.confirmed_at;
+                }
+                _ => {}
+            }
+        }
+
+        metrics::histogram!(
+            "sql.chain.operations_ext.load_block_l1_details",
+            start.elapsed()
+        );
+        Ok(details)
+    }
+
     pub async fn tx_data_by_block_and_index_api_v02(
         &mut self,
         block_number: BlockNumber,
@@ -380,6 +554,81 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         result
     }
 
+    /// Loads the lifecycle of the priority operation: the operation is looked up among
+    /// the executed ones first, and then among the ones waiting in the mempool after
+    /// they are seen on L1 by the Ethereum watcher.
+    pub async fn get_priority_op_lifecycle(
+        &mut self,
+        id: PriorityOpId,
+    ) -> QueryResult<Option<PriorityOpLifecycle>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let executed_op = match id {
+            PriorityOpId::SerialId(serial_id) => match u32::try_from(serial_id) {
+                Ok(serial_id) => {
+                    OperationsSchema(&mut transaction)
+                        .get_executed_priority_operation(serial_id)
+                        .await?
+                }
+                Err(_) => None,
+            },
+            PriorityOpId::EthHash(eth_hash) => {
+                OperationsSchema(&mut transaction)
+                    .get_executed_priority_operation_by_eth_hash(eth_hash.as_bytes())
+                    .await?
+            }
+        };
+
+        let lifecycle = if let Some(executed_op) = executed_op {
+            let block_number = executed_op.block_number;
+            let block_index = executed_op.block_index as u32;
+            let executed_at = executed_op.created_at;
+            let mut lifecycle =
+                priority_op_lifecycle(executed_op.into(), PriorityOpStage::IncludedInBlock, true);
+            lifecycle.rollup_block = Some(BlockNumber(block_number as u32));
+            lifecycle.block_index = Some(block_index);
+            lifecycle.executed_at = Some(executed_at);
+
+            // The details are found only once the block is committed on L1.
+            let details = transaction
+                .chain()
+                .block_schema()
+                .find_block_by_height_or_hash(block_number.to_string())
+                .await;
+            if let Some(details) = details {
+                lifecycle.stage = if details.is_verified() {
+                    PriorityOpStage::Finalized
+                } else {
+                    PriorityOpStage::Committed
+                };
+                lifecycle.commit_tx_hash =
+                    details.commit_tx_hash.map(|hash| H256::from_slice(&hash));
+                lifecycle.committed_at = Some(details.committed_at);
+                lifecycle.verify_tx_hash =
+                    details.verify_tx_hash.map(|hash| H256::from_slice(&hash));
+                lifecycle.finalized_at = details.verified_at;
+            }
+            Some(lifecycle)
+        } else {
+            transaction
+                .chain()
+                .mempool_schema()
+                .get_pending_priority_op(id)
+                .await?
+                .map(|(op, confirmed)| {
+                    priority_op_lifecycle(op, PriorityOpStage::SeenOnL1, confirmed)
+                })
+        };
+        transaction.commit().await?;
+
+        metrics::histogram!(
+            "sql.chain.operations_ext.get_priority_op_lifecycle",
+            start.elapsed()
+        );
+        Ok(lifecycle)
+    }
+
     pub async fn get_tx_by_hash(&mut self, hash: &[u8]) -> QueryResult<Option<TxByHashResponse>> {
         let start = Instant::now();
 
@@ -1034,101 +1283,193 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                         query.from.address,
                         address,
                         query.from.token,
+                        &query.from.filters,
                         i64::from(query.limit),
                         id_from,
                         query.direction,
                     )
                     .await?
             } else {
-                let mut priority_seq_numbers = vec![];
-                let mut executed_sequence_numbers = vec![];
-                transaction
+                let records = transaction
                     .chain()
                     .operations_ext_schema()
                     .get_tx_seq_numbers_for_account(
                         query.from.address,
                         query.from.token,
+                        &query.from.filters,
                         i64::from(query.limit),
                         id_from,
                         query.direction,
                     )
+                    .await?;
+                transaction
+                    .chain()
+                    .operations_ext_schema()
+                    .get_txs_by_seq_numbers(records, query.direction)
                     .await?
-                    .iter()
-                    .for_each(|record| {
-                        if record.is_priority {
-                            priority_seq_numbers.push(record.sequence_number)
-                        } else {
-                            executed_sequence_numbers.push(record.sequence_number)
-                        }
-                    });
+            };
 
-                let mut txs = transaction
+            Some(
+                transaction
                     .chain()
                     .operations_ext_schema()
-                    .get_executed_txs_for_account(executed_sequence_numbers)
-                    .await?;
+                    .api_transactions(raw_txs)
+                    .await?,
+            )
+        } else {
+            None
+        };
+        transaction.commit().await?;
 
-                txs.append(
-                    &mut transaction
-                        .chain()
-                        .operations_ext_schema()
-                        .get_priority_operations_for_account(priority_seq_numbers)
-                        .await?,
-                );
-
-                txs.into_iter()
-                    .sorted_by(|tx1, tx2| match query.direction {
-                        PaginationDirection::Newer => tx1.created_at.cmp(&tx2.created_at),
-                        PaginationDirection::Older => tx2.created_at.cmp(&tx1.created_at),
-                    })
-                    .collect()
-            };
+        metrics::histogram!(
+            "sql.chain.operations_ext.get_account_transactions",
+            start.elapsed()
+        );
+        Ok(txs)
+    }
 
-            let last_finalized = transaction
+    /// Returns the page of the transactions that moved the token, i.e. the ones
+    /// which have it among the tokens of their affected accounts, including the fees.
+    pub async fn get_token_transactions(
+        &mut self,
+        query: &PaginationQuery<TokenTxsRequest>,
+    ) -> QueryResult<Option<Vec<Transaction>>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        let tx_hash = match query.from.tx_hash.inner {
+            Either::Left(tx_hash) => tx_hash,
+            Either::Right(_) => {
+                if let Some(tx_hash) = transaction
+                    .chain()
+                    .operations_ext_schema()
+                    .get_token_last_tx_hash(query.from.token)
+                    .await?
+                {
+                    tx_hash
+                } else {
+                    return Ok(Some(Vec::new()));
+                }
+            }
+        };
+        let sequence_number = transaction
+            .chain()
+            .operations_ext_schema()
+            .get_tx_sequence_number(tx_hash)
+            .await?;
+
+        let txs = if let Some(id_from) = sequence_number {
+            let records = transaction
                 .chain()
-                .block_schema()
-                .get_last_verified_confirmed_block()
+                .operations_ext_schema()
+                .get_tx_seq_numbers_for_token(
+                    query.from.token,
+                    i64::from(query.limit),
+                    id_from,
+                    query.direction,
+                )
                 .await?;
-            let txs: Vec<Transaction> = raw_txs
-                .into_iter()
-                .map(|tx| {
-                    if tx.block_number as u32 <= *last_finalized {
-                        TransactionItem::transaction_from_item(tx, true)
-                    } else {
-                        TransactionItem::transaction_from_item(tx, false)
-                    }
-                })
-                .collect();
-            Some(txs)
+            let raw_txs = transaction
+                .chain()
+                .operations_ext_schema()
+                .get_txs_by_seq_numbers(records, query.direction)
+                .await?;
+            Some(
+                transaction
+                    .chain()
+                    .operations_ext_schema()
+                    .api_transactions(raw_txs)
+                    .await?,
+            )
         } else {
             None
         };
         transaction.commit().await?;
 
         metrics::histogram!(
-            "sql.chain.operations_ext.get_account_transactions",
+            "sql.chain.operations_ext.get_token_transactions",
             start.elapsed()
         );
         Ok(txs)
     }
 
+    /// Loads the executed transactions and priority operations by their sequence numbers,
+    /// ordered in the pagination direction.
+    async fn get_txs_by_seq_numbers(
+        &mut self,
+        records: Vec<SequenceNumberRecord>,
+        direction: PaginationDirection,
+    ) -> QueryResult<Vec<TransactionItem>> {
+        let mut priority_seq_numbers = vec![];
+        let mut executed_sequence_numbers = vec![];
+        records.iter().for_each(|record| {
+            if record.is_priority {
+                priority_seq_numbers.push(record.sequence_number)
+            } else {
+                executed_sequence_numbers.push(record.sequence_number)
+            }
+        });
+
+        let mut txs = self
+            .get_executed_txs_for_account(executed_sequence_numbers)
+            .await?;
+        txs.append(
+            &mut self
+                .get_priority_operations_for_account(priority_seq_numbers)
+                .await?,
+        );
+
+        Ok(txs
+            .into_iter()
+            .sorted_by(|tx1, tx2| match direction {
+                PaginationDirection::Newer => tx1.created_at.cmp(&tx2.created_at),
+                PaginationDirection::Older => tx2.created_at.cmp(&tx1.created_at),
+            })
+            .collect())
+    }
+
+    /// Converts the stored transactions into the API ones, marking the ones of the
+    /// verified blocks as finalized.
+    async fn api_transactions(
+        &mut self,
+        raw_txs: Vec<TransactionItem>,
+    ) -> QueryResult<Vec<Transaction>> {
+        let last_finalized = self
+            .0
+            .chain()
+            .block_schema()
+            .get_last_verified_confirmed_block()
+            .await?;
+        Ok(raw_txs
+            .into_iter()
+            .map(|tx| {
+                if tx.block_number as u32 <= *last_finalized {
+                    TransactionItem::transaction_from_item(tx, true)
+                } else {
+                    TransactionItem::transaction_from_item(tx, false)
+                }
+            })
+            .collect())
+    }
+
+    #[allow(clippy::too_many_arguments)]
     async fn get_executed_transactions_for_two_accounts(
         &mut self,
         address: Address,
         second_address: Address,
         token: Option<TokenId>,
+        filters: &AccountTxsFilters,
         limit: i64,
         id_from: i64,
         direction: PaginationDirection,
     ) -> QueryResult<Vec<TransactionItem>> {
         let query_direction = match direction {
             PaginationDirection::Newer => {
-                "WHERE sequence_number >= $4 
-                ORDER BY sequence_number 
+                "AND sequence_number >= $4
+                ORDER BY sequence_number
                 LIMIT $5"
             }
             PaginationDirection::Older => {
-                "WHERE sequence_number <= $4
+                "AND sequence_number <= $4
                 ORDER BY sequence_number DESC
                 LIMIT $5"
             }
@@ -1144,13 +1485,13 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
             r#"
                 WITH tx_hashes AS (
                     SELECT DISTINCT tx_hash FROM tx_filters
-                    WHERE address = $1 {} 
+                    WHERE address = $1 {}
                     INTERSECT
                     SELECT DISTINCT tx_hash FROM tx_filters
                     WHERE address = $2 {}
                 )
-                SELECT                     
-                    executed_transactions.tx_hash,
+                SELECT
+                    t.tx_hash,
                     sequence_number,
                     tx as op,
                     block_number,
@@ -1161,60 +1502,123 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                     Null::bigint as priority_op_serialid,
                     block_index,
                     batch_id
-                FROM tx_hashes INNER JOIN executed_transactions 
-                    ON tx_hashes.tx_hash = executed_transactions.tx_hash
-                {}
-                
+                FROM tx_hashes INNER JOIN executed_transactions t
+                    ON tx_hashes.tx_hash = t.tx_hash
+                WHERE TRUE {} {}
             "#,
-            token_query, token_query, query_direction
+            token_query,
+            token_query,
+            filters_conditions(filters, false, 6),
+            query_direction
         );
 
-        Ok(sqlx::query_as(&query)
+        let last_finalized = self.last_finalized_for_filters(filters).await?;
+        let query = sqlx::query_as(&query)
             .bind(address.as_bytes())
             .bind(&second_address.as_bytes())
             .bind(token.unwrap_or_default().0 as i32)
             .bind(id_from)
-            .bind(limit)
+            .bind(limit);
+        Ok(bind_filters(query, filters, last_finalized)
             .fetch_all(self.0.conn())
             .await?)
     }
 
+    /// Returns the last finalized block if it's needed to filter the transactions by their status.
+    async fn last_finalized_for_filters(
+        &mut self,
+        filters: &AccountTxsFilters,
+    ) -> QueryResult<BlockNumber> {
+        if filters.status.is_some() {
+            self.0
+                .chain()
+                .block_schema()
+                .get_last_verified_confirmed_block()
+                .await
+        } else {
+            Ok(BlockNumber(0))
+        }
+    }
+
     async fn get_tx_seq_numbers_for_account(
         &mut self,
         address: Address,
         token: Option<TokenId>,
+        filters: &AccountTxsFilters,
         limit: i64,
         id_from: i64,
         direction: PaginationDirection,
     ) -> QueryResult<Vec<SequenceNumberRecord>> {
         let query_direction = match direction {
             PaginationDirection::Newer => {
-                "AND sequence_number  >= $3
-                ORDER BY sequence_number
+                "AND f.sequence_number >= $3
+                ORDER BY f.sequence_number
                 LIMIT $4"
             }
             PaginationDirection::Older => {
-                "AND sequence_number <= $3
-                ORDER BY sequence_number DESC
+                "AND f.sequence_number <= $3
+                ORDER BY f.sequence_number DESC
                 LIMIT $4"
             }
         };
 
         let token_query = if token.is_some() {
-            "AND token = $2"
+            "AND f.token = $2"
         } else {
             ""
         };
+        // The transactions are joined only if they are filtered.
+        let (joins, conditions) = if filters.is_empty() {
+            ("", String::new())
+        } else {
+            (FILTERS_JOINS, filters_conditions(filters, true, 5))
+        };
 
         let query = format!(
-            "SELECT DISTINCT sequence_number, is_priority FROM tx_filters WHERE address = $1 {} {}",
-            token_query, query_direction
+            "SELECT DISTINCT f.sequence_number, f.is_priority FROM tx_filters f {}
+            WHERE f.address = $1 {} {} {}",
+            joins, token_query, conditions, query_direction
         );
 
-        Ok(sqlx::query_as(&query)
+        let last_finalized = self.last_finalized_for_filters(filters).await?;
+        let query = sqlx::query_as(&query)
             .bind(address.as_bytes())
             .bind(token.unwrap_or_default().0 as i32)
             .bind(id_from)
+            .bind(limit);
+        Ok(bind_filters(query, filters, last_finalized)
+            .fetch_all(self.0.conn())
+            .await?)
+    }
+
+    async fn get_tx_seq_numbers_for_token(
+        &mut self,
+        token: TokenId,
+        limit: i64,
+        id_from: i64,
+        direction: PaginationDirection,
+    ) -> QueryResult<Vec<SequenceNumberRecord>> {
+        let query_direction = match direction {
+            PaginationDirection::Newer => {
+                "AND sequence_number >= $2
+                ORDER BY sequence_number
+                LIMIT $3"
+            }
+            PaginationDirection::Older => {
+                "AND sequence_number <= $2
+                ORDER BY sequence_number DESC
+                LIMIT $3"
+            }
+        };
+
+        let query = format!(
+            "SELECT DISTINCT sequence_number, is_priority FROM tx_filters WHERE token = $1 {}",
+            query_direction
+        );
+
+        Ok(sqlx::query_as(&query)
+            .bind(token.0 as i32)
+            .bind(id_from)
             .bind(limit)
             .fetch_all(self.0.conn())
             .await?)
@@ -1311,6 +1715,29 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         Ok(record.map(|record| TxHash::from_slice(&record.tx_hash).unwrap()))
     }
 
+    pub async fn get_token_last_tx_hash(&mut self, token: TokenId) -> QueryResult<Option<TxHash>> {
+        let start = Instant::now();
+        let record = sqlx::query!(
+            r#"
+            SELECT tx_hash as "tx_hash!"
+                FROM tx_filters
+                WHERE token = $1
+                ORDER BY sequence_number
+                DESC
+                LIMIT 1
+            "#,
+            token.0 as i32
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.chain.operations_ext.get_token_last_tx_hash",
+            start.elapsed()
+        );
+        Ok(record.map(|record| TxHash::from_slice(&record.tx_hash).unwrap()))
+    }
+
     pub async fn get_block_last_tx_hash(
         &mut self,
         block_number: BlockNumber,
@@ -1402,6 +1829,182 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         Ok(count as u32)
     }
 
+    /// Returns the number of the account transactions matching the filters.
+    pub async fn get_filtered_account_transactions_count(
+        &mut self,
+        address: Address,
+        token: Option<TokenId>,
+        second_address: Option<Address>,
+        filters: &AccountTxsFilters,
+    ) -> QueryResult<u32> {
+        if filters.is_empty() {
+            return self
+                .get_account_transactions_count(address, token, second_address)
+                .await;
+        }
+        let start = Instant::now();
+        let last_finalized = self.last_finalized_for_filters(filters).await?;
+
+        let (count,): (i64,) = if let Some(second_address) = second_address {
+            let query = format!(
+                r#"
+                WITH tx_hashes AS (
+                    SELECT DISTINCT tx_hash FROM tx_filters
+                    WHERE address = $1 AND ($2::boolean OR token = $3)
+                    INTERSECT
+                    SELECT DISTINCT tx_hash FROM tx_filters
+                    WHERE address = $4 AND ($2::boolean OR token = $3)
+                )
+                SELECT COUNT(*) FROM tx_hashes INNER JOIN executed_transactions t
+                    ON tx_hashes.tx_hash = t.tx_hash
+                WHERE TRUE {}
+                "#,
+                filters_conditions(filters, false, 5)
+            );
+            let query = sqlx::query_as(&query)
+                .bind(address.as_bytes())
+                .bind(token.is_none())
+                .bind(token.unwrap_or_default().0 as i32)
+                .bind(second_address.as_bytes());
+            bind_filters(query, filters, last_finalized)
+                .fetch_one(self.0.conn())
+                .await?
+        } else {
+            let query = format!(
+                "SELECT COUNT(DISTINCT f.sequence_number) FROM tx_filters f {}
+                WHERE f.address = $1 AND ($2::boolean OR f.token = $3) {}",
+                FILTERS_JOINS,
+                filters_conditions(filters, true, 4)
+            );
+            let query = sqlx::query_as(&query)
+                .bind(address.as_bytes())
+                .bind(token.is_none())
+                .bind(token.unwrap_or_default().0 as i32);
+            bind_filters(query, filters, last_finalized)
+                .fetch_one(self.0.conn())
+                .await?
+        };
+
+        metrics::histogram!(
+            "sql.chain.operations_ext.get_filtered_account_transactions_count",
+            start.elapsed()
+        );
+        Ok(count as u32)
+    }
+
+    /// Declares the `account_txs_export` cursor over the account transactions matching
+    /// the filters, optionally only the ones with the second account, from the oldest one.
+    /// The transactions are fetched from the cursor by `fetch_account_txs_export`, so the
+    /// whole history is exported without loading it into memory at once.
+    ///
+    /// The cursor lives until the end of the database transaction, which this method must be
+    /// called within.
+    pub async fn declare_account_txs_export(
+        &mut self,
+        address: Address,
+        token: Option<TokenId>,
+        second_address: Option<Address>,
+        filters: &AccountTxsFilters,
+    ) -> QueryResult<()> {
+        anyhow::ensure!(
+            self.0.in_transaction(),
+            "The export cursor must be declared within the database transaction"
+        );
+        let start = Instant::now();
+        let query = format!(
+            r#"
+            DECLARE account_txs_export NO SCROLL CURSOR FOR
+            SELECT
+                f.sequence_number,
+                COALESCE(t.tx_hash, p.tx_hash) AS "tx_hash",
+                COALESCE(t.block_number, p.block_number) AS "block_number",
+                COALESCE(t.created_at, p.created_at) AS "created_at",
+                COALESCE(t.tx->>'type', p.operation->>'type') AS "tx_type",
+                COALESCE(t.from_account, p.from_account) AS "from_account",
+                COALESCE(t.to_account, p.to_account) AS "to_account",
+                (
+                    SELECT symbol FROM tokens WHERE id = COALESCE(
+                        t.tx->>'token', p.operation->'priority_op'->>'token'
+                    )::INTEGER
+                ) AS "token",
+                COALESCE(t.tx->>'amount', p.operation->'priority_op'->>'amount') AS "amount",
+                (
+                    SELECT symbol FROM tokens
+                    WHERE id = COALESCE(t.tx->>'feeToken', t.tx->>'token')::INTEGER
+                ) AS "fee_token",
+                t.tx->>'fee' AS "fee",
+                COALESCE(t.success, true) AS "success",
+                t.fail_reason
+            FROM (
+                SELECT DISTINCT sequence_number, is_priority FROM tx_filters
+                WHERE address = $1 AND ($2::boolean OR token = $3) AND (
+                    $4::bytea IS NULL
+                    OR sequence_number IN (SELECT sequence_number FROM tx_filters WHERE address = $4)
+                )
+            ) f {}
+            WHERE TRUE {}
+            ORDER BY f.sequence_number
+            "#,
+            FILTERS_JOINS,
+            filters_conditions(filters, true, 5)
+        );
+
+        let last_finalized = self.last_finalized_for_filters(filters).await?;
+        // The declaration doesn't return any rows, `query_as` is used to bind the filters.
+        let query = sqlx::query_as::<_, (i64,)>(&query)
+            .bind(address.as_bytes())
+            .bind(token.is_none())
+            .bind(token.unwrap_or_default().0 as i32)
+            .bind(second_address.map(|address| address.as_bytes().to_vec()));
+        bind_filters(query, filters, last_finalized)
+            .fetch_optional(self.0.conn())
+            .await?;
+
+        metrics::histogram!(
+            "sql.chain.operations_ext.declare_account_txs_export",
+            start.elapsed()
+        );
+        Ok(())
+    }
+
+    /// Fetches the next `limit` transactions from the cursor declared by
+    /// `declare_account_txs_export`, the empty list means that all the transactions are fetched.
+    pub async fn fetch_account_txs_export(
+        &mut self,
+        limit: u32,
+    ) -> QueryResult<Vec<AccountTxExportRecord>> {
+        let start = Instant::now();
+        let query = format!("FETCH FORWARD {} FROM account_txs_export", limit);
+        let records = sqlx::query_as(&query).fetch_all(self.0.conn()).await?;
+
+        metrics::histogram!(
+            "sql.chain.operations_ext.fetch_account_txs_export",
+            start.elapsed()
+        );
+        Ok(records)
+    }
+
+    pub async fn get_token_transactions_count(&mut self, token: TokenId) -> QueryResult<u32> {
+        let start = Instant::now();
+        let count = sqlx::query!(
+            r#"
+            SELECT COUNT(DISTINCT tx_hash) as "count!"
+            FROM tx_filters
+            WHERE token = $1
+            "#,
+            token.0 as i32
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .count;
+
+        metrics::histogram!(
+            "sql.chain.operations_ext.get_token_transactions_count",
+            start.elapsed()
+        );
+        Ok(count as u32)
+    }
+
     /// Returns `created_at` for `block_number` fields for transaction with given hash.
     pub async fn get_tx_sequence_number_for_block(
         &mut self,
@@ -2002,6 +2605,31 @@ async fn complete_withdrawals_tx_hash(
     Ok(result)
 }
 
+/// Creates the lifecycle of the priority operation without the data of the block.
+fn priority_op_lifecycle(
+    op: PriorityOp,
+    stage: PriorityOpStage,
+    confirmed_on_l1: bool,
+) -> PriorityOpLifecycle {
+    PriorityOpLifecycle {
+        serial_id: op.serial_id,
+        tx_hash: op.tx_hash(),
+        eth_hash: op.eth_hash,
+        op_type: op.data.variance_name(),
+        stage,
+        eth_block: EthBlockId(op.eth_block),
+        eth_block_index: op.eth_block_index,
+        confirmed_on_l1,
+        rollup_block: None,
+        block_index: None,
+        executed_at: None,
+        commit_tx_hash: None,
+        committed_at: None,
+        verify_tx_hash: None,
+        finalized_at: None,
+    }
+}
+
 async fn is_block_finalized(
     transaction: &mut StorageProcessor<'_>,
     block_number: Option<i64>,
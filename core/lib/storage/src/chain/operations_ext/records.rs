@@ -8,6 +8,8 @@ use serde::{Deserialize, Serialize};
 use serde_json::value::Value;
 use sqlx::FromRow;
 // Workspace imports
+use zksync_api_types::v02::account::AccountTxExport;
+use zksync_types::{tx::TxHash, Address, BlockNumber};
 // Local imports
 use crate::prover::records::ProverRun;
 
@@ -159,6 +161,8 @@ pub struct StorageTxReceipt {
     pub fail_reason: Option<String>,
     pub eth_block: Option<i64>,
     pub priority_op_serialid: Option<i64>,
+    /// Transaction itself, it's only loaded for the mempool transactions.
+    pub op: Option<Value>,
 }
 
 #[derive(Debug, FromRow, PartialEq)]
@@ -204,3 +208,43 @@ pub struct SequenceNumberRecord {
     pub sequence_number: i64,
     pub is_priority: bool,
 }
+
+/// A single account transaction of the history export, see [`declare_account_txs_export`].
+///
+/// [`declare_account_txs_export`]: super::OperationsExtSchema::declare_account_txs_export()
+#[derive(Debug, FromRow, Clone, PartialEq)]
+pub struct AccountTxExportRecord {
+    pub sequence_number: i64,
+    pub tx_hash: Vec<u8>,
+    pub block_number: i64,
+    pub created_at: DateTime<Utc>,
+    pub tx_type: String,
+    pub from_account: Vec<u8>,
+    pub to_account: Option<Vec<u8>>,
+    pub token: Option<String>,
+    pub amount: Option<String>,
+    pub fee_token: Option<String>,
+    pub fee: Option<String>,
+    pub success: bool,
+    pub fail_reason: Option<String>,
+}
+
+impl From<AccountTxExportRecord> for AccountTxExport {
+    fn from(val: AccountTxExportRecord) -> Self {
+        Self {
+            // The priority operations executed before their hashes were stored have the empty ones.
+            tx_hash: TxHash::from_slice(&val.tx_hash).unwrap_or_default(),
+            block_number: BlockNumber(val.block_number as u32),
+            created_at: val.created_at,
+            tx_type: val.tx_type,
+            from: Address::from_slice(&val.from_account),
+            to: val.to_account.map(|to| Address::from_slice(&to)),
+            token: val.token,
+            amount: val.amount,
+            fee_token: val.fee_token,
+            fee: val.fee,
+            success: val.success,
+            fail_reason: val.fail_reason,
+        }
+    }
+}
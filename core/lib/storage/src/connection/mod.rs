@@ -1,14 +1,24 @@
 // Built-in deps
-use std::{fmt, time::Duration, time::Instant};
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+    time::Duration,
+    time::Instant,
+};
 // External imports
 use async_trait::async_trait;
-use deadpool::managed::{Manager, PoolConfig, RecycleResult, Timeouts};
+use deadpool::managed::{Manager, PoolConfig, PoolError, RecycleResult, TimeoutType, Timeouts};
 use deadpool::Runtime;
-use sqlx::{Connection, Error as SqlxError, PgConnection};
+use log::LevelFilter;
+use sqlx::{
+    postgres::PgConnectOptions, ConnectOptions, Connection, Error as SqlxError, PgConnection,
+};
 use tokio::time;
 // Local imports
 // use self::recoverable_connection::RecoverableConnection;
-use crate::{get_database_replica_url, get_database_url, StorageProcessor};
+use crate::{
+    get_database_replica_url, get_database_url, get_slow_query_threshold, StorageProcessor,
+};
 use zksync_utils::parse_env;
 
 pub mod holder;
@@ -18,43 +28,135 @@ type Pool = deadpool::managed::Pool<DbPool>;
 pub type PooledConnection = deadpool::managed::Object<DbPool>;
 
 pub const DB_CONNECTION_RETRIES: u32 = 3;
+/// How often the connections in use are checked while the pool is being closed.
+const CLOSE_POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// How long the primary database is used instead of the replica once it has failed.
+const REPLICA_RETRY_INTERVAL: Duration = Duration::from_secs(10);
+/// How long the connection to the replica is established or checked before the replica is
+/// considered unavailable, so the requests aren't stalled by the unreachable replica.
+const REPLICA_CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+/// How long the connection is waited for once all the connections of the pool are in use.
+const POOL_WAIT_TIMEOUT: Duration = Duration::from_secs(20);
 
 #[derive(Clone)]
 pub struct DbPool {
     url: String,
+    slow_query_threshold: Duration,
 }
 
 impl DbPool {
     fn create(url: impl Into<String>, max_size: usize) -> Pool {
+        Self::create_with_timeouts(
+            url,
+            max_size,
+            Timeouts {
+                wait: Some(POOL_WAIT_TIMEOUT),
+                create: None,
+                recycle: None,
+            },
+        )
+    }
+
+    fn create_replica(url: impl Into<String>, max_size: usize) -> Pool {
+        Self::create_with_timeouts(
+            url,
+            max_size,
+            Timeouts {
+                wait: Some(POOL_WAIT_TIMEOUT),
+                create: Some(REPLICA_CONNECT_TIMEOUT),
+                recycle: Some(REPLICA_CONNECT_TIMEOUT),
+            },
+        )
+    }
+
+    fn create_with_timeouts(url: impl Into<String>, max_size: usize, timeouts: Timeouts) -> Pool {
         let pool_config = PoolConfig {
             max_size,
-            timeouts: Timeouts::wait_millis(20_000), // wait 20 seconds before returning error
+            timeouts,
             runtime: Runtime::Tokio1,
         };
-        Pool::from_config(DbPool { url: url.into() }, pool_config)
+        let manager = DbPool {
+            url: url.into(),
+            slow_query_threshold: get_slow_query_threshold(),
+        };
+        Pool::from_config(manager, pool_config)
     }
 }
 
+/// Returns the options of the connection to the database, logging every statement at the `trace`
+/// level of the `sqlx::query` target and the ones executed longer than `slow_query_threshold`
+/// at the `warn` level. Only the SQL of the statements is logged, the bound parameters are not,
+/// and the events are emitted within the span of the caller, e.g. of the API request.
+pub(crate) fn connect_options(
+    url: &str,
+    slow_query_threshold: Duration,
+) -> Result<PgConnectOptions, SqlxError> {
+    let mut options: PgConnectOptions = url.parse()?;
+    options
+        .log_statements(LevelFilter::Trace)
+        .log_slow_statements(LevelFilter::Warn, slow_query_threshold);
+    Ok(options)
+}
+
 #[async_trait]
 impl Manager for DbPool {
     type Type = PgConnection;
     type Error = SqlxError;
     async fn create(&self) -> Result<PgConnection, SqlxError> {
-        PgConnection::connect(&self.url).await
+        connect_options(&self.url, self.slow_query_threshold)?
+            .connect()
+            .await
     }
     async fn recycle(&self, obj: &mut PgConnection) -> RecycleResult<SqlxError> {
         Ok(obj.ping().await?)
     }
 }
 
+/// Pool of the connections to the replica of the database, which is not used
+/// for `REPLICA_RETRY_INTERVAL` once it fails to connect within `REPLICA_CONNECT_TIMEOUT`.
+#[derive(Clone)]
+struct ReplicaPool {
+    pool: Pool,
+    unavailable_until: Arc<Mutex<Option<Instant>>>,
+}
+
+impl ReplicaPool {
+    fn is_available(&self) -> bool {
+        match *self.unavailable_until.lock().unwrap() {
+            Some(until) => Instant::now() >= until,
+            None => true,
+        }
+    }
+
+    fn set_available(&self, available: bool) {
+        let mut unavailable_until = self.unavailable_until.lock().unwrap();
+        if available {
+            if unavailable_until.take().is_some() {
+                vlog::info!("Database replica is available again");
+            }
+        } else {
+            *unavailable_until = Some(Instant::now() + REPLICA_RETRY_INTERVAL);
+        }
+    }
+}
+
 /// `ConnectionPool` is a wrapper over a `diesel`s `Pool`, encapsulating
 /// the fixed size pool of connection to the database.
 ///
 /// The size of the pool and the database URL are configured via environment
 /// variables `DATABASE_POOL_SIZE` and `DATABASE_URL` respectively.
+///
+/// The pool may also hold the connections to the replica of the database set by
+/// `DATABASE_REPLICA_URL`. Such a pool is meant for the read-only access, e.g. by the API:
+/// its accessors are routed to the replica, and to the primary database while the replica
+/// is unavailable. Note that such a pool has a second pool of the same size to the primary
+/// database, so while the replica is unavailable it may open as many connections to the primary
+/// database as to the replica, doubling the connections of the process to the primary database:
+/// `max_connections` of the database should account for it.
 #[derive(Clone)]
 pub struct ConnectionPool {
     pool: Pool,
+    replica: Option<ReplicaPool>,
 }
 
 impl fmt::Debug for ConnectionPool {
@@ -73,20 +175,42 @@ impl ConnectionPool {
 
         let pool = DbPool::create(database_url, max_size as usize);
 
-        Self { pool }
+        Self {
+            pool,
+            replica: None,
+        }
     }
 
-    /// Establishes a pool of the connections to the replica of database and
-    /// creates a new `ConnectionPool` object.
-    /// pool_max_size - number of connections in pool,
-    /// if not set env variable "DATABASE_POOL_SIZE" is going to be used.
+    /// Establishes the pools of the connections to the replica of database and to the primary one,
+    /// and creates a new `ConnectionPool` object routing the accessors to the replica while it's available.
+    /// pool_max_size - number of connections in each of the pools, so up to twice as many connections
+    /// in total, if not set env variable "DATABASE_POOL_SIZE" is going to be used.
     pub fn new_readonly_pool(pool_max_size: Option<u32>) -> Self {
-        let database_url = get_database_replica_url();
         let max_size = pool_max_size.unwrap_or_else(|| parse_env("DATABASE_POOL_SIZE"));
+        Self::with_replica(
+            &get_database_url(),
+            &get_database_replica_url(),
+            max_size as usize,
+        )
+    }
 
-        let pool = DbPool::create(database_url, max_size as usize);
-
-        Self { pool }
+    /// Creates a `ConnectionPool` object over the primary database and its replica.
+    /// The replica isn't used if its URL is the same as the primary one.
+    pub fn with_replica(primary_url: &str, replica_url: &str, max_size: usize) -> Self {
+        let replica = if replica_url != primary_url {
+            Some(ReplicaPool {
+                pool: DbPool::create_replica(replica_url, max_size),
+                unavailable_until: Default::default(),
+            })
+        } else {
+            None
+        };
+        // The connections are established on demand, so the primary database isn't
+        // connected to until the replica fails.
+        Self {
+            pool: DbPool::create(primary_url, max_size),
+            replica,
+        }
     }
     /// Creates a `StorageProcessor` entity over a recoverable connection.
     /// Upon a database outage connection will block the thread until
@@ -96,14 +220,49 @@ impl ConnectionPool {
     ///
     /// This method is intended to be used in crucial contexts, where the
     /// database access is must-have (e.g. block committer).
+    #[tracing::instrument(name = "storage.access_storage", skip_all)]
     pub async fn access_storage(&self) -> Result<StorageProcessor<'_>, SqlxError> {
         let start = Instant::now();
-        let connection = self.get_pooled_connection().await;
+        let connection = match self.get_replica_connection().await {
+            Some(connection) => connection?,
+            None => self.get_pooled_connection().await,
+        };
         metrics::histogram!("sql.connection_acquire", start.elapsed());
 
         Ok(StorageProcessor::from_pool(connection))
     }
 
+    /// Returns the connection to the replica, `None` if there is no replica or it's unavailable.
+    /// Only the failure to connect to the replica is fallen back from, while the busy replica
+    /// is waited for, since the primary database would be overloaded by the load of the replica.
+    async fn get_replica_connection(&self) -> Option<Result<PooledConnection, SqlxError>> {
+        let replica = self
+            .replica
+            .as_ref()
+            .filter(|replica| replica.is_available())?;
+        match replica.pool.get().await {
+            Ok(connection) => {
+                replica.set_available(true);
+                Some(Ok(connection))
+            }
+            // All the connections to the replica are in use, so it's not marked as unavailable.
+            Err(PoolError::Timeout(TimeoutType::Wait)) => {
+                metrics::increment_counter!("sql.replica_busy");
+                Some(Err(SqlxError::PoolTimedOut))
+            }
+            Err(err) => {
+                vlog::warn!(
+                    "Failed to get connection to the db replica, the primary db is used for {:?}: {}",
+                    REPLICA_RETRY_INTERVAL,
+                    err
+                );
+                metrics::increment_counter!("sql.replica_failback", "reason" => "unavailable");
+                replica.set_available(false);
+                None
+            }
+        }
+    }
+
     async fn get_pooled_connection(&self) -> PooledConnection {
         let mut retry_count = 0;
 
@@ -125,4 +284,116 @@ impl ConnectionPool {
         // Attempting to get the pooled connection for the last time
         self.pool.get().await.unwrap()
     }
+
+    /// Returns `true` if none of the connections is in use.
+    fn is_idle(&self) -> bool {
+        let pools = std::iter::once(&self.pool).chain(self.replica.as_ref().map(|r| &r.pool));
+        for pool in pools {
+            let status = pool.status();
+            if status.available < status.size as isize {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Waits for the connections in use to be returned to the pool for `timeout` at most,
+    /// and closes all the idle connections, so that no query is interrupted when the process exits.
+    pub async fn close(&self, timeout: Duration) {
+        let started_at = Instant::now();
+        while !self.is_idle() && started_at.elapsed() < timeout {
+            time::sleep(CLOSE_POLL_INTERVAL).await;
+        }
+        if !self.is_idle() {
+            vlog::warn!(
+                "Some of the database connections are still in use after {:?}",
+                timeout
+            );
+        }
+
+        close_idle_connections(&self.pool).await;
+        if let Some(replica) = &self.replica {
+            close_idle_connections(&replica.pool).await;
+        }
+    }
+}
+
+async fn close_idle_connections(pool: &Pool) {
+    let idle = pool.status().available.max(0);
+    for _ in 0..idle {
+        match pool.try_get().await {
+            Ok(connection) => {
+                if let Err(err) = PooledConnection::take(connection).close().await {
+                    vlog::warn!("Failed to close the database connection: {}", err);
+                }
+            }
+            Err(_) => break,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replica_availability() {
+        let url = "postgres://postgres@localhost/plasma";
+        assert!(ConnectionPool::with_replica(url, url, 1).replica.is_none());
+
+        let pool = ConnectionPool::with_replica(url, "postgres://postgres@replica/plasma", 1);
+        let replica = pool.replica.expect("replica is not created");
+        assert!(replica.is_available());
+        replica.set_available(false);
+        assert!(!replica.is_available());
+        // The replica is retried once the interval has passed.
+        *replica.unavailable_until.lock().unwrap() = Some(Instant::now());
+        assert!(replica.is_available());
+        replica.set_available(true);
+        assert_eq!(*replica.unavailable_until.lock().unwrap(), None);
+    }
+
+    /// URL of the database nothing listens to, so the connections to it are refused.
+    const UNREACHABLE_URL: &str = "postgres://postgres@localhost:1/plasma";
+
+    async fn select_one(pool: &ConnectionPool) {
+        let mut storage = pool.access_storage().await.unwrap();
+        sqlx::query("SELECT 1")
+            .execute(storage.conn())
+            .await
+            .unwrap();
+    }
+
+    /// Checks that the unreachable replica is fallen back from, while the busy one is waited for.
+    #[tokio::test]
+    #[cfg_attr(not(feature = "db_test"), ignore)]
+    async fn replica_failback() {
+        let database_url = get_database_url();
+
+        let pool = ConnectionPool::with_replica(&database_url, UNREACHABLE_URL, 1);
+        let started_at = Instant::now();
+        select_one(&pool).await;
+        assert!(started_at.elapsed() < REPLICA_CONNECT_TIMEOUT * 2);
+        let replica = pool.replica.as_ref().unwrap();
+        assert!(!replica.is_available());
+        // The primary database is used while the replica is unavailable.
+        select_one(&pool).await;
+
+        // The primary database is unreachable, so the connection is taken from the replica.
+        let pool = ConnectionPool::with_replica(UNREACHABLE_URL, &database_url, 1);
+        let storage = pool.access_storage().await.unwrap();
+        // All the replica connections are in use, so the next one is waited for.
+        let waited = time::timeout(Duration::from_millis(500), pool.access_storage()).await;
+        assert!(waited.is_err());
+        assert!(pool.replica.as_ref().unwrap().is_available());
+        drop(storage);
+        select_one(&pool).await;
+    }
+
+    #[test]
+    fn invalid_url() {
+        let threshold = Duration::from_millis(100);
+        assert!(connect_options("postgres://postgres@localhost/plasma", threshold).is_ok());
+        assert!(connect_options("localhost/plasma", threshold).is_err());
+    }
 }
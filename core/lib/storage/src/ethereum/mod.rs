@@ -1,5 +1,10 @@
 // Built-in deps
-use std::{collections::VecDeque, convert::TryFrom, str::FromStr, time::Instant};
+use std::{
+    collections::VecDeque,
+    convert::TryFrom,
+    str::FromStr,
+    time::{Duration, Instant},
+};
 // External imports
 use anyhow::format_err;
 use num::{BigInt, BigUint};
@@ -7,14 +12,19 @@ use sqlx::types::BigDecimal;
 // Workspace imports
 use zksync_types::{
     aggregated_operations::{AggregatedActionType, AggregatedOperation},
-    ethereum::{ETHOperation, InsertedOperationResponse},
+    ethereum::{
+        ETHOperation, ETHTxAttempt, ETHTxAttemptReason, EthOpId, InsertedOperationResponse,
+    },
     event::{
         account::AccountStateChangeStatus, block::BlockStatus, transaction::TransactionStatus,
     },
-    BlockNumber, H256, U256,
+    Address, BlockNumber, H256, U256,
 };
 // Local imports
-use self::records::{ETHOperationData, ETHParams, ETHStats, ETHTxHash, StorageETHOperation};
+use self::records::{
+    ETHOperationData, ETHParams, ETHStats, ETHTxHash, StorageBlockVerificationCost,
+    StorageETHOperation, StorageETHTxAttempt, StorageOperatorAccount,
+};
 use crate::{chain::operations::records::StoredAggregatedOperation, QueryResult, StorageProcessor};
 use chrono::{DateTime, Utc};
 
@@ -93,19 +103,27 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
                 .expect("Stored operation type must have a valid value");
             let last_used_gas_price =
                 U256::from_str(&eth_op.last_used_gas_price.to_string()).unwrap();
+            let last_used_priority_fee = eth_op
+                .last_used_priority_fee
+                .map(|fee| U256::from_dec_str(&fee.to_string()).unwrap());
             let used_tx_hashes = eth_tx_hashes
                 .iter()
                 .map(|entry| H256::from_slice(&entry.tx_hash))
                 .collect();
             let final_hash = eth_op.final_hash.map(|hash| H256::from_slice(&hash));
+            let operator = eth_op
+                .operator_address
+                .map(|address| Address::from_slice(&address));
 
             let eth_op = ETHOperation {
                 id: eth_op.id,
                 op_type,
                 op,
                 nonce: eth_op.nonce.into(),
+                operator,
                 last_deadline_block: eth_op.last_deadline_block as u64,
                 last_used_gas_price,
+                last_used_priority_fee,
                 used_tx_hashes,
                 encoded_tx_data: eth_op.raw_tx,
                 confirmed: eth_op.confirmed,
@@ -221,6 +239,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         operation: Option<(i64, AggregatedOperation)>,
         last_deadline_block: i64,
         last_used_gas_price: BigUint,
+        last_used_priority_fee: Option<BigUint>,
         raw_tx: Vec<u8>,
     ) -> QueryResult<InsertedOperationResponse> {
         let start = Instant::now();
@@ -228,19 +247,21 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
 
         // It's important to assign nonce within the same db transaction
         // as saving the operation to avoid the state divergence.
-        let nonce = EthereumSchema(&mut transaction).get_next_nonce().await?;
+        let (operator, nonce) = EthereumSchema(&mut transaction).assign_nonce().await?;
 
         // Create and insert the operation.
 
         // Obtain the operation ID for the follow-up queried.
         let last_used_gas_price = BigDecimal::from(BigInt::from(last_used_gas_price));
+        let last_used_priority_fee =
+            last_used_priority_fee.map(|fee| BigDecimal::from(BigInt::from(fee)));
         let eth_op_id = sqlx::query!(
             "
-                INSERT INTO eth_operations (op_type, nonce, last_deadline_block, last_used_gas_price, raw_tx)
-                VALUES ($1, $2, $3, $4, $5)
+                INSERT INTO eth_operations (op_type, nonce, operator_address, last_deadline_block, last_used_gas_price, last_used_priority_fee, raw_tx)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
                 RETURNING id
             ",
-            op_type.to_string(), nonce, last_deadline_block, last_used_gas_price, raw_tx,
+            op_type.to_string(), nonce, operator.as_ref().map(|address| address.as_bytes()), last_deadline_block, last_used_gas_price, last_used_priority_fee, raw_tx,
         )
         .fetch_one(transaction.conn())
         .await?
@@ -266,6 +287,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         let response = InsertedOperationResponse {
             id: eth_op_id,
             nonce: nonce.into(),
+            operator,
         };
 
         transaction.commit().await?;
@@ -323,21 +345,24 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
     }
 
     /// Updates the Ethereum operation by adding a new tx data.
-    /// The new deadline block / gas value are placed instead of old values to the main entry.
+    /// The new deadline block / gas value / priority fee are placed instead of old values to the main entry.
     pub async fn update_eth_tx(
         &mut self,
         eth_op_id: i64,
         new_deadline_block: i64,
         new_gas_value: BigUint,
+        new_priority_fee: Option<BigUint>,
     ) -> QueryResult<()> {
         let start = Instant::now();
         // Update the stored tx.
         let new_gas_price = BigDecimal::from(BigInt::from(new_gas_value));
+        let new_priority_fee = new_priority_fee.map(|fee| BigDecimal::from(BigInt::from(fee)));
         sqlx::query!(
             "UPDATE eth_operations 
-            SET last_used_gas_price = $1, last_deadline_block = $2
-            WHERE id = $3",
+            SET last_used_gas_price = $1, last_used_priority_fee = $2, last_deadline_block = $3
+            WHERE id = $4",
             new_gas_price,
+            new_priority_fee,
             new_deadline_block,
             eth_op_id
         )
@@ -477,6 +502,81 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         Ok(params.into())
     }
 
+    /// Stores the gas used by the confirmed transaction of the aggregated operation. The gas is
+    /// amortized over the blocks of the operation, so the costs of the differently aggregated
+    /// blocks can be compared.
+    pub async fn store_block_verification_costs(
+        &mut self,
+        first_block: BlockNumber,
+        last_block: BlockNumber,
+        action_type: AggregatedActionType,
+        hash: &H256,
+        gas_used: u64,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let blocks_count = *last_block - *first_block + 1;
+        let gas_per_block = gas_used / u64::from(blocks_count);
+
+        sqlx::query!(
+            "INSERT INTO block_verification_costs
+                (block_number, action_type, eth_tx_hash, operation_gas_used, operation_blocks_count, gas_used)
+            SELECT block_number, $3, $4, $5, $6, $7
+                FROM generate_series($1::bigint, $2::bigint) AS block_number
+            ON CONFLICT (block_number, action_type) DO UPDATE
+                SET (eth_tx_hash, operation_gas_used, operation_blocks_count, gas_used, created_at) = ($4, $5, $6, $7, now())",
+            i64::from(*first_block),
+            i64::from(*last_block),
+            action_type.to_string(),
+            hash.as_bytes(),
+            gas_used as i64,
+            blocks_count as i32,
+            gas_per_block as i64
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.ethereum.store_block_verification_costs",
+            start.elapsed()
+        );
+        Ok(())
+    }
+
+    /// Loads the statistics of the gas used by the blocks confirmed within the `period`, grouped by
+    /// the operation type, the block size and the amount of blocks in the operation.
+    pub async fn load_block_verification_costs(
+        &mut self,
+        period: Duration,
+    ) -> QueryResult<Vec<StorageBlockVerificationCost>> {
+        let start = Instant::now();
+        let costs = sqlx::query_as!(
+            StorageBlockVerificationCost,
+            r#"
+                SELECT block_verification_costs.action_type, blocks.block_size,
+                    block_verification_costs.operation_blocks_count,
+                    COUNT(*) as "blocks_count!",
+                    AVG(block_verification_costs.gas_used)::float8 as "average_gas_per_block!",
+                    MAX(block_verification_costs.gas_used) as "max_gas_per_block!"
+                FROM block_verification_costs
+                INNER JOIN blocks ON blocks.number = block_verification_costs.block_number
+                WHERE block_verification_costs.created_at >= now() - make_interval(secs => $1)
+                GROUP BY block_verification_costs.action_type, blocks.block_size,
+                    block_verification_costs.operation_blocks_count
+                ORDER BY block_verification_costs.action_type, blocks.block_size,
+                    block_verification_costs.operation_blocks_count
+            "#,
+            period.as_secs_f64()
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.ethereum.load_block_verification_costs",
+            start.elapsed()
+        );
+        Ok(costs)
+    }
+
     async fn load_eth_params(&mut self) -> QueryResult<ETHParams> {
         let start = Instant::now();
         let params = sqlx::query_as!(ETHParams, "SELECT * FROM eth_parameters WHERE id = true",)
@@ -489,18 +589,29 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
     /// Marks the stored Ethereum transaction as confirmed (and thus the associated `Operation`
     /// is marked as confirmed as well).
     pub async fn confirm_eth_tx(&mut self, hash: &H256) -> QueryResult<()> {
+        self.confirm_eth_tx_in_block(hash, None).await
+    }
+
+    /// Same as `confirm_eth_tx`, but also records the Ethereum block which includes
+    /// the transaction, so it's reported in the receipts of the confirmed operations.
+    pub async fn confirm_eth_tx_in_block(
+        &mut self,
+        hash: &H256,
+        eth_block: Option<u64>,
+    ) -> QueryResult<()> {
         let start = Instant::now();
         let mut transaction = self.0.start_transaction().await?;
 
         let eth_op_id = EthereumSchema(&mut transaction).get_eth_op_id(hash).await?;
 
-        // Set the `confirmed` and `final_hash` field of the entry.
+        // Set the `confirmed` and `final_hash` field of the entry along with the confirmation details.
         sqlx::query!(
             "UPDATE eth_operations
-                SET confirmed = $1, final_hash = $2
-                WHERE id = $3",
+                SET confirmed = $1, final_hash = $2, confirmed_at = now(), confirmed_eth_block = $3
+                WHERE id = $4",
             true,
             hash.as_bytes(),
+            eth_block.map(|block| block as i64),
             eth_op_id
         )
         .execute(transaction.conn())
@@ -612,6 +723,311 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         Ok(old_nonce_value)
     }
 
+    /// Assigns the nonce for the new Ethereum transaction: the next nonce of the active operator
+    /// account, or the global one if there are no operator accounts registered.
+    async fn assign_nonce(&mut self) -> QueryResult<(Option<Address>, i64)> {
+        let start = Instant::now();
+        let account = sqlx::query!(
+            r#"
+                UPDATE eth_operator_accounts
+                SET nonce = nonce + 1
+                WHERE is_active = true
+                RETURNING address, nonce - 1 as "nonce!"
+            "#
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        let result = match account {
+            Some(account) => (Some(Address::from_slice(&account.address)), account.nonce),
+            None => (None, self.get_next_nonce().await?),
+        };
+
+        metrics::histogram!("sql.ethereum.assign_nonce", start.elapsed());
+        Ok(result)
+    }
+
+    /// Registers the operator accounts, the already registered ones are kept intact.
+    /// The main account takes over the global nonce and becomes active if there is no active
+    /// account yet, the reserve accounts start from the provided nonces.
+    pub async fn register_operator_accounts(
+        &mut self,
+        main_account: Address,
+        reserve_accounts: &[(Address, i64)],
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let params = EthereumSchema(&mut transaction).load_eth_params().await?;
+        sqlx::query!(
+            "
+                INSERT INTO eth_operator_accounts (address, nonce, is_active)
+                VALUES ($1, $2, NOT EXISTS (SELECT 1 FROM eth_operator_accounts WHERE is_active = true))
+                ON CONFLICT (address) DO NOTHING
+            ",
+            main_account.as_bytes(),
+            params.nonce
+        )
+        .execute(transaction.conn())
+        .await?;
+        for (address, nonce) in reserve_accounts {
+            sqlx::query!(
+                "
+                    INSERT INTO eth_operator_accounts (address, nonce)
+                    VALUES ($1, $2)
+                    ON CONFLICT (address) DO NOTHING
+                ",
+                address.as_bytes(),
+                nonce
+            )
+            .execute(transaction.conn())
+            .await?;
+        }
+
+        transaction.commit().await?;
+        metrics::histogram!("sql.ethereum.register_operator_accounts", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads the registered operator accounts along with their nonces and last observed balances.
+    pub async fn load_operator_accounts(&mut self) -> QueryResult<Vec<StorageOperatorAccount>> {
+        let start = Instant::now();
+        let accounts = sqlx::query_as!(
+            StorageOperatorAccount,
+            "SELECT * FROM eth_operator_accounts ORDER BY address"
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.ethereum.load_operator_accounts", start.elapsed());
+        Ok(accounts)
+    }
+
+    /// Stores the last observed balance of the operator account.
+    pub async fn update_operator_balance(
+        &mut self,
+        address: Address,
+        balance: BigUint,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let balance = BigDecimal::from(BigInt::from(balance));
+        sqlx::query!(
+            "UPDATE eth_operator_accounts SET balance = $1 WHERE address = $2",
+            balance,
+            address.as_bytes()
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.ethereum.update_operator_balance", start.elapsed());
+        Ok(())
+    }
+
+    /// Makes the operator account active, so the new Ethereum transactions are sent by it.
+    /// The nonce of the account is replaced with the provided one, since the nodes may drop
+    /// the transactions of the account which weren't mined before it was rotated.
+    pub async fn activate_operator_account(
+        &mut self,
+        address: Address,
+        nonce: i64,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let updated = sqlx::query!(
+            "
+                UPDATE eth_operator_accounts
+                SET is_active = (address = $1),
+                    nonce = CASE WHEN address = $1 THEN $2 ELSE nonce END
+                WHERE EXISTS (SELECT 1 FROM eth_operator_accounts WHERE address = $1)
+            ",
+            address.as_bytes(),
+            nonce
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+        if updated == 0 {
+            return Err(format_err!(
+                "Operator account {:#x} is not registered",
+                address
+            ));
+        }
+
+        metrics::histogram!("sql.ethereum.activate_operator_account", start.elapsed());
+        Ok(())
+    }
+
+    /// Assigns the next nonce of the active operator account to the Ethereum operation,
+    /// so the next transactions of the operation are sent by this account.
+    /// Returns the address of the account and the assigned nonce.
+    pub async fn reassign_eth_tx_operator(
+        &mut self,
+        eth_op_id: i64,
+    ) -> QueryResult<(Address, U256)> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let (operator, nonce) = EthereumSchema(&mut transaction).assign_nonce().await?;
+        let operator =
+            operator.ok_or_else(|| format_err!("There is no active operator account"))?;
+        sqlx::query!(
+            "UPDATE eth_operations SET nonce = $1, operator_address = $2 WHERE id = $3",
+            nonce,
+            operator.as_bytes(),
+            eth_op_id
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        transaction.commit().await?;
+        metrics::histogram!("sql.ethereum.reassign_eth_tx_operator", start.elapsed());
+        Ok((operator, nonce.into()))
+    }
+
+    /// Stores the sent Ethereum transaction in the attempts history of the operation.
+    pub async fn add_tx_attempt(&mut self, attempt: &ETHTxAttempt) -> QueryResult<()> {
+        let start = Instant::now();
+        let nonce = i64::try_from(attempt.nonce)
+            .map_err(|_| format_err!("Nonce {} is too big", attempt.nonce))?;
+        let gas_price = BigDecimal::from_str(&attempt.gas_price.to_string())?;
+        let priority_fee = attempt
+            .priority_fee
+            .map(|fee| BigDecimal::from_str(&fee.to_string()))
+            .transpose()?;
+        sqlx::query!(
+            "
+                INSERT INTO eth_tx_attempts (eth_op_id, tx_hash, nonce, gas_price, priority_fee, operator_address, sent_at_block, reason)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ",
+            attempt.eth_op_id,
+            attempt.tx_hash.as_bytes(),
+            nonce,
+            gas_price,
+            priority_fee,
+            attempt.operator.as_ref().map(|address| address.as_bytes()),
+            attempt.sent_at_block as i64,
+            attempt.reason.as_str()
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.ethereum.add_tx_attempt", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads the attempts history of the Ethereum operation in the order of sending.
+    pub async fn load_tx_attempts(&mut self, eth_op_id: EthOpId) -> QueryResult<Vec<ETHTxAttempt>> {
+        let start = Instant::now();
+        let attempts = sqlx::query_as!(
+            StorageETHTxAttempt,
+            "SELECT * FROM eth_tx_attempts WHERE eth_op_id = $1 ORDER BY id ASC",
+            eth_op_id
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|attempt| {
+            let reason =
+                ETHTxAttemptReason::from_str(&attempt.reason).map_err(|err| format_err!(err))?;
+            Ok(ETHTxAttempt {
+                eth_op_id: attempt.eth_op_id,
+                tx_hash: H256::from_slice(&attempt.tx_hash),
+                nonce: attempt.nonce.into(),
+                gas_price: U256::from_dec_str(&attempt.gas_price.to_string())?,
+                priority_fee: attempt
+                    .priority_fee
+                    .map(|fee| U256::from_dec_str(&fee.to_string()))
+                    .transpose()?,
+                operator: attempt
+                    .operator_address
+                    .map(|address| Address::from_slice(&address)),
+                sent_at_block: attempt.sent_at_block as u64,
+                reason,
+            })
+        })
+        .collect::<QueryResult<Vec<_>>>()?;
+
+        metrics::histogram!("sql.ethereum.load_tx_attempts", start.elapsed());
+        Ok(attempts)
+    }
+
+    /// Loads the Ethereum blocks at which the first transactions of the operations were sent.
+    /// The operations sent before the attempts were tracked are omitted.
+    pub async fn load_first_attempt_blocks(
+        &mut self,
+        eth_op_ids: &[EthOpId],
+    ) -> QueryResult<Vec<(EthOpId, u64)>> {
+        let start = Instant::now();
+        let blocks = sqlx::query!(
+            r#"
+                SELECT eth_op_id, MIN(sent_at_block) as "sent_at_block!"
+                FROM eth_tx_attempts
+                WHERE eth_op_id = ANY($1)
+                GROUP BY eth_op_id
+            "#,
+            eth_op_ids
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|record| (record.eth_op_id, record.sent_at_block as u64))
+        .collect();
+
+        metrics::histogram!("sql.ethereum.load_first_attempt_blocks", start.elapsed());
+        Ok(blocks)
+    }
+
+    /// Requests the replacement of the stuck transaction of the operation.
+    /// Returns `false` if there is no such unconfirmed operation.
+    pub async fn request_tx_bump(&mut self, eth_op_id: EthOpId) -> QueryResult<bool> {
+        let start = Instant::now();
+        let requested = sqlx::query!(
+            "
+                INSERT INTO eth_tx_bump_requests (eth_op_id)
+                SELECT id FROM eth_operations WHERE id = $1 AND confirmed = false
+                ON CONFLICT (eth_op_id) DO UPDATE SET requested_at = now()
+            ",
+            eth_op_id
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected()
+            > 0;
+
+        metrics::histogram!("sql.ethereum.request_tx_bump", start.elapsed());
+        Ok(requested)
+    }
+
+    /// Loads the operations whose transactions are requested to be replaced.
+    pub async fn load_tx_bump_requests(&mut self) -> QueryResult<Vec<EthOpId>> {
+        let start = Instant::now();
+        let eth_op_ids =
+            sqlx::query!("SELECT eth_op_id FROM eth_tx_bump_requests ORDER BY eth_op_id")
+                .fetch_all(self.0.conn())
+                .await?
+                .into_iter()
+                .map(|record| record.eth_op_id)
+                .collect();
+
+        metrics::histogram!("sql.ethereum.load_tx_bump_requests", start.elapsed());
+        Ok(eth_op_ids)
+    }
+
+    /// Removes and returns the replacement requests, so every request is handled once.
+    pub async fn take_tx_bump_requests(&mut self) -> QueryResult<Vec<EthOpId>> {
+        let start = Instant::now();
+        let mut eth_op_ids: Vec<EthOpId> =
+            sqlx::query!("DELETE FROM eth_tx_bump_requests RETURNING eth_op_id")
+                .fetch_all(self.0.conn())
+                .await?
+                .into_iter()
+                .map(|record| record.eth_op_id)
+                .collect();
+        eth_op_ids.sort_unstable();
+
+        metrics::histogram!("sql.ethereum.take_tx_bump_requests", start.elapsed());
+        Ok(eth_op_ids)
+    }
+
     /// Method that internally initializes the `eth_parameters` table.
     /// Since in db tests the database is empty, we must provide a possibility
     /// to initialize required db fields.
@@ -15,6 +15,10 @@ pub struct StorageETHOperation {
     pub last_deadline_block: i64,
     pub last_used_gas_price: BigDecimal,
     pub created_at: Option<DateTime<Utc>>,
+    pub confirmed_at: Option<DateTime<Utc>>,
+    pub confirmed_eth_block: Option<i64>,
+    pub last_used_priority_fee: Option<BigDecimal>,
+    pub operator_address: Option<Vec<u8>>,
 }
 
 #[derive(Debug, Clone, FromRow, PartialEq)]
@@ -30,6 +34,10 @@ pub struct ETHOperationData {
     pub agg_op_id: Option<i64>,
     pub arguments: Option<serde_json::Value>,
     pub created_at: Option<DateTime<Utc>>,
+    pub confirmed_at: Option<DateTime<Utc>>,
+    pub confirmed_eth_block: Option<i64>,
+    pub last_used_priority_fee: Option<BigDecimal>,
+    pub operator_address: Option<Vec<u8>>,
 }
 
 #[derive(Debug, Clone, FromRow, PartialEq)]
@@ -39,6 +47,28 @@ pub struct ETHTxHash {
     pub tx_hash: Vec<u8>,
 }
 
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StorageETHTxAttempt {
+    pub id: i64,
+    pub eth_op_id: i64,
+    pub tx_hash: Vec<u8>,
+    pub nonce: i64,
+    pub gas_price: BigDecimal,
+    pub priority_fee: Option<BigDecimal>,
+    pub operator_address: Option<Vec<u8>>,
+    pub sent_at_block: i64,
+    pub reason: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StorageOperatorAccount {
+    pub address: Vec<u8>,
+    pub nonce: i64,
+    pub balance: Option<BigDecimal>,
+    pub is_active: bool,
+}
+
 #[derive(Debug, FromRow, PartialEq)]
 pub struct ETHParams {
     pub id: bool,
@@ -58,6 +88,17 @@ pub struct ETHStats {
     pub last_executed_block: i64,
 }
 
+/// Statistics of the gas used by the blocks of the same size and aggregation, per block.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StorageBlockVerificationCost {
+    pub action_type: String,
+    pub block_size: i64,
+    pub operation_blocks_count: i32,
+    pub blocks_count: i64,
+    pub average_gas_per_block: f64,
+    pub max_gas_per_block: i64,
+}
+
 impl From<ETHParams> for ETHStats {
     fn from(params: ETHParams) -> Self {
         Self {
@@ -30,7 +30,8 @@ pub use records::{get_event_type, EventType};
 /// about it.
 ///
 /// Note, that all events should be created solely by other `storage` methods
-/// and they are persisted in the database forever.
+/// and they are persisted in the database until the history of their blocks is pruned,
+/// which happens only after they are consumed by all the registered event consumers.
 #[derive(Debug)]
 pub struct EventSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
 
@@ -108,6 +109,31 @@ impl<'a, 'c> EventSchema<'a, 'c> {
         Ok(id)
     }
 
+    /// Stores the id of the last event consumed by the consumer, so that the events
+    /// it hasn't consumed yet are not pruned.
+    pub async fn store_consumer_progress(
+        &mut self,
+        consumer: &str,
+        last_event_id: EventId,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            INSERT INTO event_consumers ( name, last_event_id )
+            VALUES ( $1, $2 )
+            ON CONFLICT (name) DO UPDATE
+            SET last_event_id = EXCLUDED.last_event_id, updated_at = now()
+            "#,
+            consumer,
+            *last_event_id as i64
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.event.store_consumer_progress", start.elapsed());
+        Ok(())
+    }
+
     /// Create new block event and store it in the database.
     /// This method relies on the `load_block_range` which may return `None`
     /// if there're no Ethereum transactions featuring this block (`Committed` or `Executed`).
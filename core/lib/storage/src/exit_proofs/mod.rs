@@ -0,0 +1,153 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+use zksync_types::{
+    exit_proof::{ExitProofData, ExitProofRequest},
+    AccountId, BlockNumber, TokenId,
+};
+// Local imports
+use self::records::StoredExitProof;
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Exit proofs schema handles the `exit_proofs` table, storing the requests for the exit
+/// proofs along with the proofs generated for them.
+///
+/// There is at most one request for the balance of the account in the token, the request
+/// for the later block replaces the previous one, since the proof is only usable for the
+/// last verified block.
+#[derive(Debug)]
+pub struct ExitProofsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> ExitProofsSchema<'a, 'c> {
+    /// Stores the request for the exit proof at the block, unless there is already
+    /// a request for the same or a later block.
+    /// Returns `false` if the request isn't stored for that reason.
+    pub async fn store_request(
+        &mut self,
+        account_id: AccountId,
+        token_id: TokenId,
+        block_number: BlockNumber,
+    ) -> QueryResult<bool> {
+        let start = Instant::now();
+        let stored = sqlx::query!(
+            r#"
+            INSERT INTO exit_proofs ( account_id, token_id, block_number )
+            VALUES ( $1, $2, $3 )
+            ON CONFLICT (account_id, token_id) DO UPDATE
+            SET block_number = EXCLUDED.block_number, proof = NULL, error = NULL, created_at = now(), generated_at = NULL
+            WHERE exit_proofs.block_number < EXCLUDED.block_number
+            "#,
+            i64::from(*account_id),
+            *token_id as i32,
+            i64::from(*block_number)
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected()
+            > 0;
+
+        metrics::histogram!("sql.exit_proofs.store_request", start.elapsed());
+        Ok(stored)
+    }
+
+    /// Loads the request for the exit proof along with the proof, if it's already generated.
+    pub async fn load_request(
+        &mut self,
+        account_id: AccountId,
+        token_id: TokenId,
+    ) -> QueryResult<Option<ExitProofRequest>> {
+        let start = Instant::now();
+        let request = sqlx::query_as!(
+            StoredExitProof,
+            r#"
+            SELECT * FROM exit_proofs
+            WHERE account_id = $1 AND token_id = $2
+            "#,
+            i64::from(*account_id),
+            *token_id as i32
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(ExitProofRequest::from);
+
+        metrics::histogram!("sql.exit_proofs.load_request", start.elapsed());
+        Ok(request)
+    }
+
+    /// Loads the oldest request whose proof isn't generated yet and didn't fail.
+    pub async fn load_pending_request(&mut self) -> QueryResult<Option<ExitProofRequest>> {
+        let start = Instant::now();
+        let request = sqlx::query_as!(
+            StoredExitProof,
+            r#"
+            SELECT * FROM exit_proofs
+            WHERE proof IS NULL AND error IS NULL
+            ORDER BY created_at
+            LIMIT 1
+            "#
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(ExitProofRequest::from);
+
+        metrics::histogram!("sql.exit_proofs.load_pending_request", start.elapsed());
+        Ok(request)
+    }
+
+    /// Stores the exit proof for the request at the block of the proof.
+    /// Returns `false` if there is no such request, e.g. it was replaced by the request
+    /// for a later block while the proof was being generated.
+    pub async fn store_proof(&mut self, proof_data: &ExitProofData) -> QueryResult<bool> {
+        let start = Instant::now();
+        let proof = serde_json::to_value(proof_data)?;
+        let stored = sqlx::query!(
+            r#"
+            UPDATE exit_proofs
+            SET proof = $4, generated_at = now()
+            WHERE account_id = $1 AND token_id = $2 AND block_number = $3
+            "#,
+            i64::from(*proof_data.account_id),
+            *proof_data.token_id as i32,
+            i64::from(*proof_data.stored_block_info.block_number),
+            proof
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected()
+            > 0;
+
+        metrics::histogram!("sql.exit_proofs.store_proof", start.elapsed());
+        Ok(stored)
+    }
+
+    /// Stores the error of the proof generation for the request at the block, so the request
+    /// isn't processed again until it's replaced by the request for a later block.
+    pub async fn store_error(
+        &mut self,
+        account_id: AccountId,
+        token_id: TokenId,
+        block_number: BlockNumber,
+        error: &str,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            UPDATE exit_proofs
+            SET error = $4, generated_at = now()
+            WHERE account_id = $1 AND token_id = $2 AND block_number = $3
+            "#,
+            i64::from(*account_id),
+            *token_id as i32,
+            i64::from(*block_number),
+            error
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.exit_proofs.store_error", start.elapsed());
+        Ok(())
+    }
+}
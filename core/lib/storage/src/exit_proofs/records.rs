@@ -0,0 +1,32 @@
+// External imports
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+// Workspace imports
+use zksync_types::{exit_proof::ExitProofRequest, AccountId, BlockNumber, TokenId};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredExitProof {
+    pub account_id: i64,
+    pub token_id: i32,
+    pub block_number: i64,
+    pub proof: Option<Value>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub generated_at: Option<DateTime<Utc>>,
+}
+
+impl From<StoredExitProof> for ExitProofRequest {
+    fn from(stored: StoredExitProof) -> Self {
+        Self {
+            account_id: AccountId(stored.account_id as u32),
+            token_id: TokenId(stored.token_id as u32),
+            block_number: BlockNumber(stored.block_number as u32),
+            created_at: stored.created_at,
+            generated_at: stored.generated_at,
+            proof_data: stored
+                .proof
+                .map(|proof| serde_json::from_value(proof).expect("Invalid stored exit proof")),
+            error: stored.error,
+        }
+    }
+}
@@ -0,0 +1,78 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+use zksync_types::{Address, TokenId};
+// Local imports
+use self::records::StoredFeeDiscount;
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Fee discounts schema handles the `fee_discounts` table, storing the percentage discounts
+/// of the transaction fees for the selected tokens, transaction types and addresses.
+#[derive(Debug)]
+pub struct FeeDiscountsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> FeeDiscountsSchema<'a, 'c> {
+    /// Stores the new discount. The criteria set to `None` match any transaction.
+    pub async fn store_fee_discount(
+        &mut self,
+        token_id: Option<TokenId>,
+        tx_type: Option<&str>,
+        address: Option<Address>,
+        discount_percent: u32,
+        description: &str,
+    ) -> QueryResult<StoredFeeDiscount> {
+        let start = Instant::now();
+        let discount = sqlx::query_as!(
+            StoredFeeDiscount,
+            r#"
+            INSERT INTO fee_discounts ( token_id, tx_type, address, discount_percent, description )
+            VALUES ( $1, $2, $3, $4, $5 )
+            RETURNING id, token_id, tx_type, address, discount_percent, description, created_at
+            "#,
+            token_id.map(|token_id| *token_id as i32),
+            tx_type,
+            address.as_ref().map(|address| address.as_bytes()),
+            discount_percent as i32,
+            description
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.fee_discounts.store_fee_discount", start.elapsed());
+        Ok(discount)
+    }
+
+    /// Removes the discount, returns `false` if there is no such discount.
+    pub async fn remove_fee_discount(&mut self, id: i64) -> QueryResult<bool> {
+        let start = Instant::now();
+        let removed = sqlx::query!("DELETE FROM fee_discounts WHERE id = $1", id)
+            .execute(self.0.conn())
+            .await?
+            .rows_affected()
+            > 0;
+
+        metrics::histogram!("sql.fee_discounts.remove_fee_discount", start.elapsed());
+        Ok(removed)
+    }
+
+    /// Loads all the discounts, the oldest ones go first.
+    pub async fn load_fee_discounts(&mut self) -> QueryResult<Vec<StoredFeeDiscount>> {
+        let start = Instant::now();
+        let discounts = sqlx::query_as!(
+            StoredFeeDiscount,
+            r#"
+            SELECT id, token_id, tx_type, address, discount_percent, description, created_at
+            FROM fee_discounts
+            ORDER BY id
+            "#
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.fee_discounts.load_fee_discounts", start.elapsed());
+        Ok(discounts)
+    }
+}
@@ -0,0 +1,13 @@
+// External imports
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredFeeDiscount {
+    pub id: i64,
+    pub token_id: Option<i32>,
+    pub tx_type: Option<String>,
+    pub address: Option<Vec<u8>>,
+    pub discount_percent: i32,
+    pub description: String,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,83 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use chrono::{DateTime, Utc};
+use sqlx::types::BigDecimal;
+// Workspace imports
+use zksync_types::{Address, TokenId};
+// Local imports
+use self::records::StoredFeeQuote;
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Fee quotes schema handles the `fee_quotes` table, storing the fees quoted by the API.
+#[derive(Debug)]
+pub struct FeeQuotesSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> FeeQuotesSchema<'a, 'c> {
+    /// Stores the fee quoted for the transaction of the given type.
+    pub async fn store_fee_quote(
+        &mut self,
+        tx_type: &str,
+        token_id: TokenId,
+        address: Address,
+        gas_fee: BigDecimal,
+        zkp_fee: BigDecimal,
+        total_fee: BigDecimal,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            INSERT INTO fee_quotes ( tx_type, token_id, address, gas_fee, zkp_fee, total_fee )
+            VALUES ( $1, $2, $3, $4, $5, $6 )
+            "#,
+            tx_type,
+            *token_id as i32,
+            address.as_bytes(),
+            gas_fee,
+            zkp_fee,
+            total_fee
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.fee_quotes.store_fee_quote", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads the latest fee quotes in the token quoted within the given time range,
+    /// optionally only for the given transaction type. The oldest quotes go first.
+    pub async fn load_fee_quotes(
+        &mut self,
+        token_id: TokenId,
+        tx_type: Option<&str>,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        limit: u32,
+    ) -> QueryResult<Vec<StoredFeeQuote>> {
+        let start = Instant::now();
+        let mut quotes = sqlx::query_as!(
+            StoredFeeQuote,
+            r#"
+            SELECT id, tx_type, token_id, address, gas_fee, zkp_fee, total_fee, created_at
+            FROM fee_quotes
+            WHERE token_id = $1 AND ($2::TEXT IS NULL OR tx_type = $2)
+                AND created_at >= $3 AND created_at <= $4
+            ORDER BY created_at DESC, id DESC
+            LIMIT $5
+            "#,
+            *token_id as i32,
+            tx_type,
+            from,
+            to,
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+        quotes.reverse();
+
+        metrics::histogram!("sql.fee_quotes.load_fee_quotes", start.elapsed());
+        Ok(quotes)
+    }
+}
@@ -0,0 +1,15 @@
+// External imports
+use chrono::{DateTime, Utc};
+use sqlx::types::BigDecimal;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredFeeQuote {
+    pub id: i64,
+    pub tx_type: String,
+    pub token_id: i32,
+    pub address: Vec<u8>,
+    pub gas_fee: BigDecimal,
+    pub zkp_fee: BigDecimal,
+    pub total_fee: BigDecimal,
+    pub created_at: DateTime<Utc>,
+}
@@ -11,17 +11,19 @@ use zksync_types::forced_exit_requests::{
     ForcedExitRequest, ForcedExitRequestId, SaveForcedExitRequestQuery,
 };
 
-use zksync_types::tx::TxHash;
+use zksync_types::{tx::TxHash, Address, TokenId};
 
 pub mod records;
 
 mod utils;
 
-use records::DbForcedExitRequest;
+use records::{DbForcedExitRequest, ForcedExitAutomationStatus, StoredForcedExitAutomationRequest};
 
 use crate::utils::address_to_stored_string;
 
 /// ForcedExitRequests schema handles the `forced_exit_requests` table, providing methods to
+/// store the paid requests, and the `forced_exit_automation_requests` table with the requests
+/// submitted by the operator in batches.
 #[derive(Debug)]
 pub struct ForcedExitRequestsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
 
@@ -217,4 +219,133 @@ impl<'a, 'c> ForcedExitRequestsSchema<'a, 'c> {
 
         Ok(())
     }
+
+    /// Stores the request of the ForcedExit of the token by the automation. If there is
+    /// a pending request for the token already, its estimated fee is updated instead.
+    pub async fn store_automation_request(
+        &mut self,
+        target: Address,
+        token_id: TokenId,
+        estimated_fee: BigDecimal,
+    ) -> QueryResult<StoredForcedExitAutomationRequest> {
+        let start = Instant::now();
+        let request = sqlx::query_as!(
+            StoredForcedExitAutomationRequest,
+            r#"
+            INSERT INTO forced_exit_automation_requests ( target, token_id, estimated_fee )
+            VALUES ( $1, $2, $3 )
+            ON CONFLICT (target, token_id) WHERE status = 'pending'
+            DO UPDATE SET estimated_fee = $3
+            RETURNING *
+            "#,
+            target.as_bytes(),
+            *token_id as i32,
+            estimated_fee
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.forced_exit_requests.store_automation_request",
+            start.elapsed()
+        );
+        Ok(request)
+    }
+
+    pub async fn get_automation_request(
+        &mut self,
+        id: i64,
+    ) -> QueryResult<Option<StoredForcedExitAutomationRequest>> {
+        let start = Instant::now();
+        let request = sqlx::query_as!(
+            StoredForcedExitAutomationRequest,
+            "SELECT * FROM forced_exit_automation_requests WHERE id = $1",
+            id
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.forced_exit_requests.get_automation_request",
+            start.elapsed()
+        );
+        Ok(request)
+    }
+
+    /// Loads up to `limit` pending requests of the automation, the oldest requests go first.
+    pub async fn load_pending_automation_requests(
+        &mut self,
+        limit: u32,
+    ) -> QueryResult<Vec<StoredForcedExitAutomationRequest>> {
+        let start = Instant::now();
+        let requests = sqlx::query_as!(
+            StoredForcedExitAutomationRequest,
+            r#"
+            SELECT * FROM forced_exit_automation_requests
+            WHERE status = $1
+            ORDER BY id
+            LIMIT $2
+            "#,
+            ForcedExitAutomationStatus::Pending.as_str(),
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.forced_exit_requests.load_pending_automation_requests",
+            start.elapsed()
+        );
+        Ok(requests)
+    }
+
+    /// Marks the request of the automation as submitted by the transaction.
+    pub async fn set_automation_request_submitted(
+        &mut self,
+        id: i64,
+        tx_hash: TxHash,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            UPDATE forced_exit_automation_requests
+                SET (status, tx_hash, processed_at) = ($2, $3, now())
+                WHERE id = $1
+            "#,
+            id,
+            ForcedExitAutomationStatus::Submitted.as_str(),
+            tx_hash.as_ref()
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.forced_exit_requests.set_automation_request_submitted",
+            start.elapsed()
+        );
+        Ok(())
+    }
+
+    /// Marks the request of the automation as failed for the reason.
+    pub async fn set_automation_request_failed(&mut self, id: i64, error: &str) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            UPDATE forced_exit_automation_requests
+                SET (status, error, processed_at) = ($2, $3, now())
+                WHERE id = $1
+            "#,
+            id,
+            ForcedExitAutomationStatus::Failed.as_str(),
+            error
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.forced_exit_requests.set_automation_request_failed",
+            start.elapsed()
+        );
+        Ok(())
+    }
 }
@@ -63,3 +63,36 @@ impl From<DbForcedExitRequest> for ForcedExitRequest {
         }
     }
 }
+
+/// Status of the ForcedExit request submitted by the automation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForcedExitAutomationStatus {
+    /// The request awaits for the next batch.
+    Pending,
+    Submitted,
+    /// The ForcedExit isn't possible anymore, the reason is in the `error` of the request.
+    Failed,
+}
+
+impl ForcedExitAutomationStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Submitted => "submitted",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredForcedExitAutomationRequest {
+    pub id: i64,
+    pub target: Vec<u8>,
+    pub token_id: i32,
+    pub estimated_fee: BigDecimal,
+    pub status: String,
+    pub tx_hash: Option<Vec<u8>>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub processed_at: Option<DateTime<Utc>>,
+}
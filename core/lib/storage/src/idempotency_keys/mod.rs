@@ -0,0 +1,142 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+// Workspace imports
+use zksync_types::AccountId;
+// Local imports
+use self::records::StoredIdempotentResponse;
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Idempotency keys schema handles the `idempotency_keys` table, storing the responses
+/// of the transaction submissions by the keys provided by the clients.
+#[derive(Debug)]
+pub struct IdempotencyKeysSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> IdempotencyKeysSchema<'a, 'c> {
+    /// Reserves the key of the account for the request, replacing the one reserved before
+    /// the given time. Returns `false` if the key is already reserved, so the request
+    /// is submitted only by the one reserving the key.
+    pub async fn reserve_key(
+        &mut self,
+        account_id: AccountId,
+        key: &str,
+        request_hash: &[u8],
+        stored_after: DateTime<Utc>,
+    ) -> QueryResult<bool> {
+        let start = Instant::now();
+        let reserved = sqlx::query!(
+            r#"
+            INSERT INTO idempotency_keys ( account_id, key, request_hash )
+            VALUES ( $1, $2, $3 )
+            ON CONFLICT (account_id, key) DO UPDATE
+            SET request_hash = EXCLUDED.request_hash, response = NULL, created_at = now()
+            WHERE idempotency_keys.created_at <= $4
+            "#,
+            i64::from(*account_id),
+            key,
+            request_hash,
+            stored_after
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected()
+            > 0;
+
+        metrics::histogram!("sql.idempotency_keys.reserve_key", start.elapsed());
+        Ok(reserved)
+    }
+
+    /// Loads the key of the account reserved after the given time along with the stored response.
+    pub async fn load_response(
+        &mut self,
+        account_id: AccountId,
+        key: &str,
+        stored_after: DateTime<Utc>,
+    ) -> QueryResult<Option<StoredIdempotentResponse>> {
+        let start = Instant::now();
+        let response = sqlx::query_as!(
+            StoredIdempotentResponse,
+            r#"
+            SELECT account_id, key, request_hash, response, created_at
+            FROM idempotency_keys
+            WHERE account_id = $1 AND key = $2 AND created_at > $3
+            "#,
+            i64::from(*account_id),
+            key,
+            stored_after
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.idempotency_keys.load_response", start.elapsed());
+        Ok(response)
+    }
+
+    /// Stores the response of the request submitted by the reserved key.
+    pub async fn store_response(
+        &mut self,
+        account_id: AccountId,
+        key: &str,
+        response: Value,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            UPDATE idempotency_keys SET response = $3
+            WHERE account_id = $1 AND key = $2
+            "#,
+            i64::from(*account_id),
+            key,
+            response
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.idempotency_keys.store_response", start.elapsed());
+        Ok(())
+    }
+
+    /// Removes the reservation of the key without the response, so the failed request
+    /// can be retried with the same key.
+    pub async fn release_key(&mut self, account_id: AccountId, key: &str) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            DELETE FROM idempotency_keys
+            WHERE account_id = $1 AND key = $2 AND response IS NULL
+            "#,
+            i64::from(*account_id),
+            key
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.idempotency_keys.release_key", start.elapsed());
+        Ok(())
+    }
+
+    /// Removes the responses stored before the given time, returns the number of the removed ones.
+    pub async fn remove_responses_before(
+        &mut self,
+        stored_before: DateTime<Utc>,
+    ) -> QueryResult<u64> {
+        let start = Instant::now();
+        let removed = sqlx::query!(
+            "DELETE FROM idempotency_keys WHERE created_at <= $1",
+            stored_before
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!(
+            "sql.idempotency_keys.remove_responses_before",
+            start.elapsed()
+        );
+        Ok(removed)
+    }
+}
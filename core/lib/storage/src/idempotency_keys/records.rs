@@ -0,0 +1,13 @@
+// External imports
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredIdempotentResponse {
+    pub account_id: i64,
+    pub key: String,
+    pub request_hash: Vec<u8>,
+    /// The response is empty while the request is being submitted.
+    pub response: Option<Value>,
+    pub created_at: DateTime<Utc>,
+}
@@ -75,9 +75,9 @@
 #![allow(clippy::toplevel_ref_arg, clippy::suspicious_else_formatting)]
 
 // Built-in deps
-use std::env;
+use std::{env, time::Duration};
 // External imports
-use sqlx::{postgres::Postgres, Connection, PgConnection, Transaction};
+use sqlx::{postgres::Postgres, ConnectOptions, Connection, PgConnection, Transaction};
 // Workspace imports
 use zksync_types::{ActionType, BlockNumber};
 // Local imports
@@ -87,6 +87,7 @@ use crate::connection::{holder::ConnectionHolder, PooledConnection};
 #[cfg(test)]
 mod tests;
 
+pub mod api_keys;
 pub mod chain;
 pub mod config;
 pub mod connection;
@@ -94,13 +95,21 @@ pub mod data_restore;
 pub mod diff;
 pub mod ethereum;
 pub mod event;
+pub mod exit_proofs;
+pub mod fee_discounts;
+pub mod fee_quotes;
 pub mod forced_exit_requests;
+pub mod idempotency_keys;
 pub mod listener;
 pub mod misc;
+pub mod network_stats;
 pub mod prover;
+pub mod pruning;
+pub mod read_only_mode;
 pub mod test_data;
 pub mod tokens;
 pub mod utils;
+pub mod webhooks;
 
 use forced_exit_requests::ForcedExitRequestsSchema;
 
@@ -112,6 +121,8 @@ pub type QueryResult<T, E = anyhow::Error> = Result<T, E>;
 pub const MAX_BLOCK_NUMBER: BlockNumber = BlockNumber(u32::MAX);
 /// The maximum possible index value in block in the storage.
 pub const MAX_BLOCK_INDEX: u32 = i32::MAX as u32;
+/// The queries executed longer than this are logged, unless `DATABASE_SLOW_QUERY_THRESHOLD_MS` is set.
+pub const DEFAULT_SLOW_QUERY_THRESHOLD_MS: u64 = 500;
 
 /// Obtains the database URL from the environment variable.
 pub fn get_database_replica_url() -> String {
@@ -123,6 +134,15 @@ pub fn get_database_url() -> String {
     env::var("DATABASE_URL").expect("DATABASE_URL must be set")
 }
 
+/// Obtains the duration of the queries which are logged as slow from the environment variable.
+pub fn get_slow_query_threshold() -> Duration {
+    let millis = env::var("DATABASE_SLOW_QUERY_THRESHOLD_MS")
+        .ok()
+        .and_then(|millis| millis.parse().ok())
+        .unwrap_or(DEFAULT_SLOW_QUERY_THRESHOLD_MS);
+    Duration::from_millis(millis)
+}
+
 /// Storage processor is the main storage interaction point.
 /// It holds down the connection (either direct or pooled) to the database
 /// and provide methods to obtain different storage schemas.
@@ -152,7 +172,9 @@ impl<'a> StorageProcessor<'a> {
     /// Creates a `StorageProcessor` using an unique sole connection to the database.
     pub async fn establish_connection<'b>() -> QueryResult<StorageProcessor<'b>> {
         let database_url = get_database_url();
-        let connection = PgConnection::connect(&database_url).await?;
+        let connection = connection::connect_options(&database_url, get_slow_query_threshold())?
+            .connect()
+            .await?;
         Ok(StorageProcessor {
             conn: ConnectionHolder::Direct(connection),
             in_transaction: false,
@@ -191,6 +213,12 @@ impl<'a> StorageProcessor<'a> {
         }
     }
 
+    /// Checks that the database is reachable over the connection.
+    pub async fn ping(&mut self) -> QueryResult<()> {
+        self.conn().ping().await?;
+        Ok(())
+    }
+
     /// Creates a `StorageProcessor` using a pool of connections.
     /// This method borrows one of the connections from the pool, and releases it
     /// after `drop`.
@@ -201,6 +229,11 @@ impl<'a> StorageProcessor<'a> {
         }
     }
 
+    /// Gains access to the `ApiKeys` schema.
+    pub fn api_keys_schema(&mut self) -> api_keys::ApiKeysSchema<'_, 'a> {
+        api_keys::ApiKeysSchema(self)
+    }
+
     /// Gains access to the `Chain` schemas.
     pub fn chain(&mut self) -> chain::ChainIntermediator<'_, 'a> {
         chain::ChainIntermediator(self)
@@ -221,16 +254,56 @@ impl<'a> StorageProcessor<'a> {
         ethereum::EthereumSchema(self)
     }
 
+    /// Gains access to the `ExitProofs` schema.
+    pub fn exit_proofs_schema(&mut self) -> exit_proofs::ExitProofsSchema<'_, 'a> {
+        exit_proofs::ExitProofsSchema(self)
+    }
+
+    /// Gains access to the `FeeDiscounts` schema.
+    pub fn fee_discounts_schema(&mut self) -> fee_discounts::FeeDiscountsSchema<'_, 'a> {
+        fee_discounts::FeeDiscountsSchema(self)
+    }
+
+    /// Gains access to the `FeeQuotes` schema.
+    pub fn fee_quotes_schema(&mut self) -> fee_quotes::FeeQuotesSchema<'_, 'a> {
+        fee_quotes::FeeQuotesSchema(self)
+    }
+
+    /// Gains access to the `IdempotencyKeys` schema.
+    pub fn idempotency_keys_schema(&mut self) -> idempotency_keys::IdempotencyKeysSchema<'_, 'a> {
+        idempotency_keys::IdempotencyKeysSchema(self)
+    }
+
+    /// Gains access to the `NetworkStats` schema.
+    pub fn network_stats_schema(&mut self) -> network_stats::NetworkStatsSchema<'_, 'a> {
+        network_stats::NetworkStatsSchema(self)
+    }
+
     /// Gains access to the `Prover` schema.
     pub fn prover_schema(&mut self) -> prover::ProverSchema<'_, 'a> {
         prover::ProverSchema(self)
     }
 
+    /// Gains access to the `Pruning` schema.
+    pub fn pruning_schema(&mut self) -> pruning::PruningSchema<'_, 'a> {
+        pruning::PruningSchema(self)
+    }
+
+    /// Gains access to the `ReadOnlyMode` schema.
+    pub fn read_only_mode_schema(&mut self) -> read_only_mode::ReadOnlyModeSchema<'_, 'a> {
+        read_only_mode::ReadOnlyModeSchema(self)
+    }
+
     /// Gains access to the `Tokens` schema.
     pub fn tokens_schema(&mut self) -> tokens::TokensSchema<'_, 'a> {
         tokens::TokensSchema(self)
     }
 
+    /// Gains access to the `Webhooks` schema.
+    pub fn webhooks_schema(&mut self) -> webhooks::WebhooksSchema<'_, 'a> {
+        webhooks::WebhooksSchema(self)
+    }
+
     pub fn forced_exit_requests_schema(&mut self) -> ForcedExitRequestsSchema<'_, 'a> {
         ForcedExitRequestsSchema(self)
     }
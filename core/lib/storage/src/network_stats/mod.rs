@@ -0,0 +1,139 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use chrono::{DateTime, Utc};
+// Workspace imports
+// Local imports
+use self::records::{StoredNetworkStats, StoredTokenStats};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Network stats schema handles the `network_stats` and `network_token_stats` tables,
+/// storing the statistics aggregated over the executed transactions and the balances.
+///
+/// The statistics are recalculated from scratch by every update, so the API only loads
+/// the stored ones and never scans the transactions itself.
+#[derive(Debug)]
+pub struct NetworkStatsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> NetworkStatsSchema<'a, 'c> {
+    /// Recalculates the statistics over the transactions executed since `since`
+    /// and replaces the stored ones within one database transaction.
+    ///
+    /// The volume is the sum of the amounts of the transfers, the withdrawals and the deposits,
+    /// the average fee doesn't take into account the transactions whose fee is paid by
+    /// another transaction of the batch. The active accounts are the distinct initiators
+    /// of the L2 transactions and the priority operations.
+    pub async fn update_network_stats(&mut self, since: DateTime<Utc>) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO network_stats ( tx_count_24h, active_accounts_24h, updated_at )
+            SELECT
+                (SELECT COUNT(*) FROM executed_transactions WHERE success AND created_at >= $1)
+                    + (SELECT COUNT(*) FROM executed_priority_operations WHERE created_at >= $1),
+                (SELECT COUNT(*) FROM (
+                    SELECT from_account FROM executed_transactions WHERE success AND created_at >= $1
+                    UNION
+                    SELECT from_account FROM executed_priority_operations WHERE created_at >= $1
+                ) AS accounts),
+                now()
+            ON CONFLICT (id) DO UPDATE
+            SET tx_count_24h = EXCLUDED.tx_count_24h,
+                active_accounts_24h = EXCLUDED.active_accounts_24h,
+                updated_at = EXCLUDED.updated_at
+            "#,
+            since
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        sqlx::query!("DELETE FROM network_token_stats")
+            .execute(transaction.conn())
+            .await?;
+        sqlx::query!(
+            r#"
+            WITH tvl AS (
+                SELECT coin_id AS token_id, SUM(balance) AS tvl FROM balances
+                GROUP BY coin_id
+            ),
+            volumes AS (
+                SELECT token_id, SUM(amount) AS volume FROM (
+                    SELECT (tx->>'token')::INTEGER AS token_id, (tx->>'amount')::NUMERIC AS amount
+                    FROM executed_transactions
+                    WHERE success AND created_at >= $1 AND tx->>'type' IN ('Transfer', 'Withdraw')
+                    UNION ALL
+                    SELECT (operation->'priority_op'->>'token')::INTEGER,
+                        (operation->'priority_op'->>'amount')::NUMERIC
+                    FROM executed_priority_operations
+                    WHERE created_at >= $1 AND operation->>'type' = 'Deposit'
+                ) AS amounts
+                GROUP BY token_id
+            ),
+            fees AS (
+                SELECT COALESCE(tx->>'feeToken', tx->>'token')::INTEGER AS token_id,
+                    TRUNC(AVG((tx->>'fee')::NUMERIC)) AS average_fee
+                FROM executed_transactions
+                WHERE success AND created_at >= $1 AND (tx->>'fee')::NUMERIC > 0
+                GROUP BY 1
+            )
+            INSERT INTO network_token_stats ( token_id, tvl, volume_24h, average_fee_24h )
+            SELECT tokens.id, COALESCE(tvl.tvl, 0), COALESCE(volumes.volume, 0), fees.average_fee
+            FROM tokens
+            LEFT JOIN tvl ON tvl.token_id = tokens.id
+            LEFT JOIN volumes ON volumes.token_id = tokens.id
+            LEFT JOIN fees ON fees.token_id = tokens.id
+            WHERE NOT tokens.is_nft
+                AND (tvl.tvl > 0 OR volumes.volume > 0 OR fees.average_fee IS NOT NULL)
+            "#,
+            since
+        )
+        .execute(transaction.conn())
+        .await?;
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.network_stats.update_network_stats", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads the stored statistics along with the statistics of the tokens ordered by their ids,
+    /// `None` if the statistics weren't calculated yet.
+    pub async fn load_network_stats(
+        &mut self,
+    ) -> QueryResult<Option<(StoredNetworkStats, Vec<StoredTokenStats>)>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        let stats = sqlx::query_as!(
+            StoredNetworkStats,
+            "SELECT tx_count_24h, active_accounts_24h, updated_at FROM network_stats"
+        )
+        .fetch_optional(transaction.conn())
+        .await?;
+        let stats = match stats {
+            Some(stats) => stats,
+            None => {
+                transaction.commit().await?;
+                return Ok(None);
+            }
+        };
+        let tokens = sqlx::query_as!(
+            StoredTokenStats,
+            r#"
+            SELECT network_token_stats.token_id, tokens.symbol, network_token_stats.tvl,
+                network_token_stats.volume_24h, network_token_stats.average_fee_24h
+            FROM network_token_stats
+            INNER JOIN tokens ON tokens.id = network_token_stats.token_id
+            ORDER BY network_token_stats.token_id
+            "#
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.network_stats.load_network_stats", start.elapsed());
+        Ok(Some((stats, tokens)))
+    }
+}
@@ -0,0 +1,50 @@
+// External imports
+use chrono::{DateTime, Utc};
+use num::{bigint::ToBigInt, BigUint};
+use sqlx::types::BigDecimal;
+// Workspace imports
+use zksync_api_types::v02::status::{NetworkStats, TokenStats};
+use zksync_types::TokenId;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredNetworkStats {
+    pub tx_count_24h: i64,
+    pub active_accounts_24h: i64,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl StoredNetworkStats {
+    pub fn into_api(self, tokens: Vec<StoredTokenStats>) -> NetworkStats {
+        NetworkStats {
+            tx_count_24h: self.tx_count_24h as u64,
+            active_accounts_24h: self.active_accounts_24h as u64,
+            tokens: tokens.into_iter().map(TokenStats::from).collect(),
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredTokenStats {
+    pub token_id: i32,
+    pub symbol: String,
+    pub tvl: BigDecimal,
+    pub volume_24h: BigDecimal,
+    pub average_fee_24h: Option<BigDecimal>,
+}
+
+fn to_biguint(amount: BigDecimal) -> BigUint {
+    amount.to_bigint().unwrap().to_biguint().unwrap()
+}
+
+impl From<StoredTokenStats> for TokenStats {
+    fn from(val: StoredTokenStats) -> Self {
+        Self {
+            token_id: TokenId(val.token_id as u32),
+            symbol: val.symbol,
+            tvl: to_biguint(val.tvl),
+            volume_24h: to_biguint(val.volume_24h),
+            average_fee_24h: val.average_fee_24h.map(|fee| to_biguint(fee).into()),
+        }
+    }
+}
@@ -1,11 +1,14 @@
 // Built-in deps
-use std::time::Instant;
+use std::time::{Duration, Instant};
 // External imports
 use anyhow::format_err;
 // Workspace imports
 use zksync_types::BlockNumber;
 // Local imports
-use self::records::{StorageProverJobQueue, StoredAggregatedProof, StoredProof};
+use self::records::{
+    StorageConnectedProver, StorageJobQueueDepth, StorageProofGenerationTime,
+    StorageProverJobQueue, StorageProvingTime, StoredAggregatedProof, StoredProof,
+};
 use crate::chain::operations::OperationsSchema;
 use crate::prover::records::StorageBlockWitness;
 use crate::{QueryResult, StorageProcessor};
@@ -47,10 +50,13 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         job_type: ProverJobType,
     ) -> QueryResult<()> {
         let start = Instant::now();
+        // The priority set for any of the job blocks overrides the default one.
         sqlx::query!(
         "
           WITH job_values as (
-            SELECT $1::int4, $2::int4, $3::text, 'server_add_job', $4::int8, $5::int8, $6::jsonb
+            SELECT $1::int4,
+              COALESCE((SELECT MIN(job_priority) FROM prover_block_priorities WHERE block_number BETWEEN $4 AND $5), $2::int4),
+              $3::text, 'server_add_job', $4::int8, $5::int8, $6::jsonb
             WHERE NOT EXISTS (SELECT * FROM prover_job_queue WHERE first_block = $4 and last_block = $5 and job_type = $3 LIMIT 1)
           )
           INSERT INTO prover_job_queue (job_status, job_priority, job_type, updated_by, first_block, last_block, job_data)
@@ -68,22 +74,30 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         Ok(())
     }
 
-    pub async fn mark_stale_jobs_as_idle(&mut self) -> QueryResult<()> {
+    /// Returns the jobs whose leases have expired back to the queue, so they are leased to the other provers.
+    pub async fn release_expired_prover_job_leases(&mut self) -> QueryResult<()> {
         let start = Instant::now();
         let result = sqlx::query!(
-            "UPDATE prover_job_queue SET (job_status, updated_at, updated_by) = ($1, now(), 'server_clean_idle')
-            WHERE job_status = $2 AND (now() - INTERVAL '120 seconds') >= updated_at RETURNING id",
+            "UPDATE prover_job_queue
+            SET (job_status, updated_at, updated_by, leased_by, lease_expires_at) = ($1, now(), 'server_release_lease', NULL, NULL)
+            WHERE job_status = $2 AND (lease_expires_at IS NULL OR lease_expires_at <= now()) RETURNING id",
             ProverJobStatus::Idle.to_number(),
             ProverJobStatus::InProgress.to_number(),
         )
         .fetch_all(self.0.conn())
         .await?;
         metrics::counter!("stale_jobs", result.len() as u64);
-        metrics::histogram!("sql", start.elapsed(), "prover" => "mark_stale_jobs_as_idle");
+        metrics::histogram!("sql", start.elapsed(), "prover" => "release_expired_prover_job_leases");
         Ok(())
     }
 
-    pub async fn get_idle_prover_job_from_job_queue(&mut self) -> QueryResult<Option<ProverJob>> {
+    /// Leases the idle job with the highest priority (the lowest `job_priority`) to the prover.
+    /// The prover has to renew the lease before it expires, otherwise the job is leased to another prover.
+    pub async fn lease_prover_job(
+        &mut self,
+        prover_name: &str,
+        lease_duration: Duration,
+    ) -> QueryResult<Option<ProverJob>> {
         let start = Instant::now();
         // Select the block to prove.
         let mut transaction = self.0.start_transaction().await?;
@@ -108,10 +122,13 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
             sqlx::query!(
                 r#"
                 UPDATE prover_job_queue
-                SET (job_status, updated_at, updated_by) = ($1, now(), 'server_give_job')
-                WHERE id = $2;
+                SET (job_status, updated_at, updated_by, leased_by, leased_at, lease_expires_at, lease_count)
+                    = ($1, now(), 'server_give_job', $2, now(), now() + make_interval(secs => $3), lease_count + 1)
+                WHERE id = $4;
             "#,
                 ProverJobStatus::InProgress.to_number(),
+                prover_name,
+                lease_duration.as_secs_f64(),
                 job.id,
             )
             .execute(transaction.conn())
@@ -127,49 +144,206 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
             None
         };
         transaction.commit().await?;
-        metrics::histogram!("sql", start.elapsed(), "prover" => "get_idle_prover_job_from_job_queue");
+        metrics::histogram!("sql", start.elapsed(), "prover" => "lease_prover_job");
         Ok(prover_job)
     }
 
-    /// Updates the state of ongoing prover job.
-    pub async fn record_prover_is_working(
+    /// Renews the lease of the ongoing prover job. Returns `false` if the job isn't leased
+    /// to the prover anymore, e.g. because the lease has expired and the job was given to another prover.
+    pub async fn renew_prover_job_lease(
         &mut self,
         job_id: i32,
         prover_name: &str,
-    ) -> QueryResult<()> {
+        lease_duration: Duration,
+    ) -> QueryResult<bool> {
         let start = Instant::now();
-        sqlx::query!(
+        let updated_rows = sqlx::query!(
             "UPDATE prover_job_queue
-            SET (updated_at, updated_by) = (now(), $1)
-            WHERE id = $2",
-            prover_name.to_string(),
+            SET (updated_at, updated_by, lease_expires_at) = (now(), $1, now() + make_interval(secs => $2))
+            WHERE id = $3 AND leased_by = $1 AND job_status = $4",
+            prover_name,
+            lease_duration.as_secs_f64(),
             job_id,
+            ProverJobStatus::InProgress.to_number(),
         )
         .execute(self.0.conn())
-        .await?;
+        .await?
+        .rows_affected();
 
-        metrics::histogram!("sql", start.elapsed(), "prover" => "record_prover_is_working");
-        Ok(())
+        metrics::histogram!("sql", start.elapsed(), "prover" => "renew_prover_job_lease");
+        Ok(updated_rows == 1)
     }
 
-    /// Marks the prover as stopped.
+    /// Marks the prover as stopped, returning the jobs leased to it back to the queue.
     pub async fn record_prover_stop(&mut self, prover_name: &str) -> QueryResult<()> {
         let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
         sqlx::query!(
             "UPDATE prover_job_queue
-            SET (updated_at, job_status) = (now(), $1)
-            WHERE updated_by = $2 and job_status = $3",
+            SET (updated_at, job_status, leased_by, lease_expires_at) = (now(), $1, NULL, NULL)
+            WHERE leased_by = $2 and job_status = $3",
             ProverJobStatus::Idle.to_number(),
             prover_name,
             ProverJobStatus::InProgress.to_number()
         )
-        .execute(self.0.conn())
+        .execute(transaction.conn())
+        .await?;
+
+        sqlx::query!(
+            "DELETE FROM prover_heartbeats WHERE prover_name = $1",
+            prover_name
+        )
+        .execute(transaction.conn())
         .await?;
+        transaction.commit().await?;
 
         metrics::histogram!("sql", start.elapsed(), "prover" => "record_prover_stop");
         Ok(())
     }
 
+    /// Sets the priority of the prover jobs of the block, including the jobs created later.
+    /// The job covering several blocks takes the highest priority (the lowest value) set for them.
+    pub async fn set_block_job_priority(
+        &mut self,
+        block_number: BlockNumber,
+        job_priority: i32,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        sqlx::query!(
+            "INSERT INTO prover_block_priorities (block_number, job_priority)
+            VALUES ($1, $2)
+            ON CONFLICT (block_number)
+            DO UPDATE SET job_priority = $2, updated_at = now()",
+            i64::from(*block_number),
+            job_priority
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        sqlx::query!(
+            "UPDATE prover_job_queue
+            SET job_priority = (
+                SELECT MIN(job_priority) FROM prover_block_priorities
+                WHERE block_number BETWEEN prover_job_queue.first_block AND prover_job_queue.last_block
+            )
+            WHERE first_block <= $1 AND last_block >= $1 AND job_status != $2",
+            i64::from(*block_number),
+            ProverJobStatus::Done.to_number()
+        )
+        .execute(transaction.conn())
+        .await?;
+        transaction.commit().await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "set_block_job_priority");
+        Ok(())
+    }
+
+    /// Records that the prover has reached the server.
+    pub async fn record_prover_heartbeat(&mut self, prover_name: &str) -> QueryResult<()> {
+        let start = Instant::now();
+        // The provers poll the server for the jobs often, so the record is only updated once a second.
+        sqlx::query!(
+            "INSERT INTO prover_heartbeats (prover_name, last_seen_at)
+            VALUES ($1, now())
+            ON CONFLICT (prover_name)
+            DO UPDATE SET last_seen_at = now()
+            WHERE prover_heartbeats.last_seen_at < now() - INTERVAL '1 second'",
+            prover_name
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "record_prover_heartbeat");
+        Ok(())
+    }
+
+    /// Loads the provers that have reached the server within the `timeout` along with their current jobs.
+    pub async fn load_connected_provers(
+        &mut self,
+        timeout: Duration,
+    ) -> QueryResult<Vec<StorageConnectedProver>> {
+        let start = Instant::now();
+        let provers = sqlx::query_as!(
+            StorageConnectedProver,
+            r#"
+                SELECT prover_heartbeats.prover_name, prover_heartbeats.last_seen_at,
+                    prover_job_queue.id as "job_id?", prover_job_queue.job_type as "job_type?",
+                    prover_job_queue.first_block as "first_block?", prover_job_queue.last_block as "last_block?",
+                    prover_job_queue.leased_at as "leased_at?"
+                FROM prover_heartbeats
+                LEFT JOIN prover_job_queue
+                    ON prover_job_queue.leased_by = prover_heartbeats.prover_name AND prover_job_queue.job_status = $1
+                WHERE prover_heartbeats.last_seen_at >= now() - make_interval(secs => $2)
+                ORDER BY prover_heartbeats.prover_name
+            "#,
+            ProverJobStatus::InProgress.to_number(),
+            timeout.as_secs_f64()
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "load_connected_provers");
+        Ok(provers)
+    }
+
+    /// Loads the statistics of the times of the single block proofs created within the `period`
+    /// grouped by the block size. The proving time is measured since the last lease of the job.
+    pub async fn load_proving_times(
+        &mut self,
+        period: Duration,
+    ) -> QueryResult<Vec<StorageProvingTime>> {
+        let start = Instant::now();
+        let proving_times = sqlx::query_as!(
+            StorageProvingTime,
+            r#"
+                SELECT blocks.block_size, COUNT(*) as "proofs_count!",
+                    AVG(EXTRACT(EPOCH FROM prover_job_queue.updated_at - prover_job_queue.leased_at))::float8 as "average_secs!",
+                    MAX(EXTRACT(EPOCH FROM prover_job_queue.updated_at - prover_job_queue.leased_at))::float8 as "max_secs!"
+                FROM prover_job_queue
+                INNER JOIN blocks ON blocks.number = prover_job_queue.first_block
+                WHERE prover_job_queue.job_type = $1 AND prover_job_queue.job_status = $2
+                    AND prover_job_queue.leased_at IS NOT NULL
+                    AND prover_job_queue.updated_at >= now() - make_interval(secs => $3)
+                GROUP BY blocks.block_size
+                ORDER BY blocks.block_size
+            "#,
+            ProverJobType::SingleProof.to_string(),
+            ProverJobStatus::Done.to_number(),
+            period.as_secs_f64()
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "load_proving_times");
+        Ok(proving_times)
+    }
+
+    /// Loads the amounts of the idle and in-progress jobs of every job type.
+    pub async fn load_job_queue_depth(&mut self) -> QueryResult<Vec<StorageJobQueueDepth>> {
+        let start = Instant::now();
+        let queue_depth = sqlx::query_as!(
+            StorageJobQueueDepth,
+            r#"
+                SELECT job_type,
+                    COUNT(*) FILTER (WHERE job_status = $1) as "idle_jobs!",
+                    COUNT(*) FILTER (WHERE job_status = $2) as "in_progress_jobs!"
+                FROM prover_job_queue
+                WHERE job_status != $3
+                GROUP BY job_type
+                ORDER BY job_type
+            "#,
+            ProverJobStatus::Idle.to_number(),
+            ProverJobStatus::InProgress.to_number(),
+            ProverJobStatus::Done.to_number()
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "load_job_queue_depth");
+        Ok(queue_depth)
+    }
+
     /// Stores the proof for a block.
     pub async fn store_proof(
         &mut self,
@@ -194,6 +368,10 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         if updated_rows != 1 {
             return Err(format_err!("Missing job for stored proof"));
         }
+        transaction
+            .prover_schema()
+            .record_proof_generation_time(job_id)
+            .await?;
 
         sqlx::query!(
             "INSERT INTO proofs (block_number, proof)
@@ -213,6 +391,58 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         Ok(())
     }
 
+    /// Records the wall time of the finished job since its last lease, so the proving times
+    /// outlive the jobs in the queue.
+    async fn record_proof_generation_time(&mut self, job_id: i32) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "INSERT INTO proof_generation_times (job_id, job_type, first_block, last_block, prover_name, proving_time_ms)
+            SELECT id, job_type, first_block, last_block, COALESCE(leased_by, updated_by),
+                (EXTRACT(EPOCH FROM now() - leased_at) * 1000)::bigint
+            FROM prover_job_queue
+            WHERE id = $1 AND leased_at IS NOT NULL
+            ON CONFLICT (job_id) DO NOTHING",
+            job_id
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "record_proof_generation_time");
+        Ok(())
+    }
+
+    /// Loads the statistics of the wall times of the proofs created within the `period`, grouped by
+    /// the job type, the block size and the amount of blocks in the proof.
+    pub async fn load_proof_generation_times(
+        &mut self,
+        period: Duration,
+    ) -> QueryResult<Vec<StorageProofGenerationTime>> {
+        let start = Instant::now();
+        let times = sqlx::query_as!(
+            StorageProofGenerationTime,
+            r#"
+                SELECT proof_generation_times.job_type, blocks.block_size,
+                    proof_generation_times.last_block - proof_generation_times.first_block + 1 as "blocks_count!",
+                    COUNT(*) as "proofs_count!",
+                    AVG(proof_generation_times.proving_time_ms)::float8 as "average_ms!",
+                    MAX(proof_generation_times.proving_time_ms) as "max_ms!"
+                FROM proof_generation_times
+                INNER JOIN blocks ON blocks.number = proof_generation_times.first_block
+                WHERE proof_generation_times.created_at >= now() - make_interval(secs => $1)
+                GROUP BY proof_generation_times.job_type, blocks.block_size,
+                    proof_generation_times.last_block - proof_generation_times.first_block
+                ORDER BY proof_generation_times.job_type, blocks.block_size,
+                    proof_generation_times.last_block - proof_generation_times.first_block
+            "#,
+            period.as_secs_f64()
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "load_proof_generation_times");
+        Ok(times)
+    }
+
     // Set metrics about stages in block processing
     async fn set_block_processing_metrics(
         &mut self,
@@ -264,6 +494,10 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         if updated_rows != 1 {
             return Err(format_err!("Missing job for stored aggregated proof"));
         }
+        transaction
+            .prover_schema()
+            .record_proof_generation_time(job_id)
+            .await?;
 
         sqlx::query!(
             "INSERT INTO aggregated_proofs (first_block, last_block, proof)
@@ -408,15 +642,65 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         Ok(result)
     }
 
+    /// Loads the last block up to which all the witnesses of the witness generator shard are generated.
+    pub async fn load_witness_generator_progress(
+        &mut self,
+        shard_index: u32,
+    ) -> QueryResult<Option<BlockNumber>> {
+        let start = Instant::now();
+        let last_block = sqlx::query!(
+            "SELECT last_block FROM witness_generator_progress WHERE shard_index = $1",
+            shard_index as i32
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|record| BlockNumber(record.last_block as u32));
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "load_witness_generator_progress");
+        Ok(last_block)
+    }
+
+    /// Stores the last block up to which all the witnesses of the witness generator shard are generated.
+    pub async fn store_witness_generator_progress(
+        &mut self,
+        shard_index: u32,
+        last_block: BlockNumber,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "INSERT INTO witness_generator_progress (shard_index, last_block)
+            VALUES ($1, $2)
+            ON CONFLICT (shard_index)
+            DO UPDATE SET last_block = $2, updated_at = now()",
+            shard_index as i32,
+            i64::from(*last_block)
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "store_witness_generator_progress");
+        Ok(())
+    }
+
     // Removes witnesses for blocks with number greater than `last_block`
     pub async fn remove_witnesses(&mut self, last_block: BlockNumber) -> QueryResult<()> {
         let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
         sqlx::query!(
             "DELETE FROM block_witness WHERE block > $1",
             *last_block as i64
         )
-        .execute(self.0.conn())
+        .execute(transaction.conn())
+        .await?;
+
+        // The witnesses of the removed blocks are generated again.
+        sqlx::query!(
+            "UPDATE witness_generator_progress SET last_block = $1, updated_at = now() WHERE last_block > $1",
+            *last_block as i64
+        )
+        .execute(transaction.conn())
         .await?;
+        transaction.commit().await?;
 
         metrics::histogram!("sql", start.elapsed(), "prover" => "remove_witnesses");
         Ok(())
@@ -467,6 +751,20 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         )
         .execute(transaction.conn())
         .await?;
+
+        sqlx::query!(
+            "DELETE FROM prover_block_priorities WHERE block_number > $1",
+            *last_block as i64
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        sqlx::query!(
+            "DELETE FROM proof_generation_times WHERE last_block > $1",
+            *last_block as i64
+        )
+        .execute(transaction.conn())
+        .await?;
         transaction.commit().await?;
 
         metrics::histogram!("sql", start.elapsed(), "prover" => "remove_prover_jobs");
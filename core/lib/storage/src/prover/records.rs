@@ -68,4 +68,45 @@ pub struct StorageProverJobQueue {
     pub first_block: i64,
     pub last_block: i64,
     pub job_data: serde_json::Value,
+    pub leased_by: Option<String>,
+    pub lease_expires_at: Option<DateTime<Utc>>,
+    pub lease_count: i32,
+    pub leased_at: Option<DateTime<Utc>>,
+}
+
+/// Prover that has recently reached the server along with its current job, if any.
+#[derive(Debug, Clone, FromRow)]
+pub struct StorageConnectedProver {
+    pub prover_name: String,
+    pub last_seen_at: DateTime<Utc>,
+    pub job_id: Option<i32>,
+    pub job_type: Option<String>,
+    pub first_block: Option<i64>,
+    pub last_block: Option<i64>,
+    pub leased_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StorageProvingTime {
+    pub block_size: i64,
+    pub proofs_count: i64,
+    pub average_secs: f64,
+    pub max_secs: f64,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StorageProofGenerationTime {
+    pub job_type: String,
+    pub block_size: i64,
+    pub blocks_count: i64,
+    pub proofs_count: i64,
+    pub average_ms: f64,
+    pub max_ms: i64,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StorageJobQueueDepth {
+    pub job_type: String,
+    pub idle_jobs: i64,
+    pub in_progress_jobs: i64,
 }
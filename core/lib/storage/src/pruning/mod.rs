@@ -0,0 +1,208 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+use zksync_types::BlockNumber;
+// Local imports
+use crate::{QueryResult, StorageProcessor};
+
+/// Amounts of the rows deleted by the pruning of the blocks.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PrunedRows {
+    /// The last block whose history is pruned, it's before the requested one
+    /// if the events of the later blocks aren't consumed yet.
+    pub last_pruned_block: BlockNumber,
+    pub balance_updates: u64,
+    pub pubkey_updates: u64,
+    pub events: u64,
+    pub mempool_txs: u64,
+    pub mempool_priority_ops: u64,
+}
+
+impl PrunedRows {
+    pub fn total(&self) -> u64 {
+        self.balance_updates
+            + self.pubkey_updates
+            + self.events
+            + self.mempool_txs
+            + self.mempool_priority_ops
+    }
+}
+
+/// Pruning schema deletes the history of the old blocks which isn't needed to serve
+/// the current state and the proofs, and keeps the progress of the pruning in the
+/// `pruning_checkpoint` table.
+///
+/// The following rows of the pruned blocks are deleted:
+/// - the balance and public key updates superseded by the newer updates of the same pruned
+///   blocks, so that the latest update of every balance and public key is kept;
+/// - the events, only the blocks whose events are consumed by all the event consumers are pruned;
+/// - the executed transactions and priority operations left in the mempool.
+///
+/// The account creations and the NFT mints are never deleted, since the API and the state
+/// keeper look them up regardless of the block.
+#[derive(Debug)]
+pub struct PruningSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> PruningSchema<'a, 'c> {
+    /// Loads the last block whose history is pruned, `0` if nothing was pruned yet.
+    pub async fn load_last_pruned_block(&mut self) -> QueryResult<BlockNumber> {
+        let start = Instant::now();
+        let last_pruned_block = sqlx::query!("SELECT last_pruned_block FROM pruning_checkpoint")
+            .fetch_optional(self.0.conn())
+            .await?
+            .map(|record| BlockNumber(record.last_pruned_block as u32))
+            .unwrap_or_default();
+
+        metrics::histogram!("sql.pruning.load_last_pruned_block", start.elapsed());
+        Ok(last_pruned_block)
+    }
+
+    /// Returns the first block with the events not consumed by all the event consumers yet,
+    /// `None` if there are no such events or no consumers registered.
+    async fn first_unconsumed_block(&mut self) -> QueryResult<Option<BlockNumber>> {
+        let block_number = sqlx::query!(
+            r#"
+            SELECT MIN(block_number) AS "block_number"
+            FROM events
+            WHERE id > (SELECT MIN(last_event_id) FROM event_consumers)
+            "#
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .block_number
+        .map(|block_number| BlockNumber(block_number as u32));
+        Ok(block_number)
+    }
+
+    /// Prunes the history of the blocks after the last pruned one up to `last_block` inclusive
+    /// and stores the last pruned block as the checkpoint within the same database transaction.
+    /// The blocks with the events not consumed yet and the later ones are kept.
+    ///
+    /// The caller is responsible for `last_block` being old enough: its state diffs must not be
+    /// needed to restore the state from the account tree cache or the verified state.
+    pub async fn prune_blocks(&mut self, last_block: BlockNumber) -> QueryResult<PrunedRows> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let from_block = PruningSchema(&mut transaction)
+            .load_last_pruned_block()
+            .await?;
+        let last_block = match PruningSchema(&mut transaction)
+            .first_unconsumed_block()
+            .await?
+        {
+            Some(unconsumed_block) => {
+                last_block.min(BlockNumber(unconsumed_block.saturating_sub(1)))
+            }
+            None => last_block,
+        };
+        if last_block <= from_block {
+            transaction.commit().await?;
+            return Ok(PrunedRows {
+                last_pruned_block: from_block,
+                ..PrunedRows::default()
+            });
+        }
+        let from_block = i64::from(*from_block);
+        let to_block = i64::from(*last_block);
+
+        // The updates are deleted once they are superseded by the update of the pruned blocks,
+        // so the updates of the earlier blocks are deleted as well.
+        let balance_updates = sqlx::query!(
+            r#"
+            DELETE FROM account_balance_updates AS old
+            WHERE old.block_number <= $2 AND EXISTS (
+                SELECT 1 FROM account_balance_updates AS new
+                WHERE new.account_id = old.account_id AND new.coin_id = old.coin_id
+                    AND new.block_number > $1 AND new.block_number <= $2
+                    AND (new.block_number, new.update_order_id) > (old.block_number, old.update_order_id)
+            )
+            "#,
+            from_block,
+            to_block
+        )
+        .execute(transaction.conn())
+        .await?
+        .rows_affected();
+
+        let pubkey_updates = sqlx::query!(
+            r#"
+            DELETE FROM account_pubkey_updates AS old
+            WHERE old.block_number <= $2 AND EXISTS (
+                SELECT 1 FROM account_pubkey_updates AS new
+                WHERE new.account_id = old.account_id
+                    AND new.block_number > $1 AND new.block_number <= $2
+                    AND (new.block_number, new.update_order_id) > (old.block_number, old.update_order_id)
+            )
+            "#,
+            from_block,
+            to_block
+        )
+        .execute(transaction.conn())
+        .await?
+        .rows_affected();
+
+        let events = sqlx::query!(
+            "DELETE FROM events WHERE block_number > $1 AND block_number <= $2",
+            from_block,
+            to_block
+        )
+        .execute(transaction.conn())
+        .await?
+        .rows_affected();
+
+        let mempool_txs = sqlx::query!(
+            r#"
+            DELETE FROM mempool_txs
+            WHERE tx_hash IN (
+                SELECT encode(tx_hash, 'hex') FROM executed_transactions
+                WHERE block_number > $1 AND block_number <= $2
+            )
+            "#,
+            from_block,
+            to_block
+        )
+        .execute(transaction.conn())
+        .await?
+        .rows_affected();
+
+        let mempool_priority_ops = sqlx::query!(
+            r#"
+            DELETE FROM mempool_priority_operations
+            WHERE serial_id IN (
+                SELECT priority_op_serialid FROM executed_priority_operations
+                WHERE block_number > $1 AND block_number <= $2
+            )
+            "#,
+            from_block,
+            to_block
+        )
+        .execute(transaction.conn())
+        .await?
+        .rows_affected();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO pruning_checkpoint ( last_pruned_block )
+            VALUES ( $1 )
+            ON CONFLICT (id) DO UPDATE
+            SET last_pruned_block = EXCLUDED.last_pruned_block, updated_at = now()
+            "#,
+            to_block
+        )
+        .execute(transaction.conn())
+        .await?;
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.pruning.prune_blocks", start.elapsed());
+        Ok(PrunedRows {
+            last_pruned_block: last_block,
+            balance_updates,
+            pubkey_updates,
+            events,
+            mempool_txs,
+            mempool_priority_ops,
+        })
+    }
+}
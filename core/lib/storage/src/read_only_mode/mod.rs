@@ -0,0 +1,56 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+// Local imports
+use self::records::StoredReadOnlyMode;
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Read-only mode schema handles the `api_read_only_mode` table, storing the read-only mode
+/// of the API servers set by the admin API.
+#[derive(Debug)]
+pub struct ReadOnlyModeSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> ReadOnlyModeSchema<'a, 'c> {
+    /// Loads the read-only mode, returns `None` if it was never set.
+    pub async fn load_read_only_mode(&mut self) -> QueryResult<Option<StoredReadOnlyMode>> {
+        let start = Instant::now();
+        let mode = sqlx::query_as!(
+            StoredReadOnlyMode,
+            "SELECT enabled, reason, updated_at FROM api_read_only_mode"
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.read_only_mode.load_read_only_mode", start.elapsed());
+        Ok(mode)
+    }
+
+    /// Enables or disables the read-only mode, the reason is shown to the clients.
+    pub async fn set_read_only_mode(
+        &mut self,
+        enabled: bool,
+        reason: Option<&str>,
+    ) -> QueryResult<StoredReadOnlyMode> {
+        let start = Instant::now();
+        let mode = sqlx::query_as!(
+            StoredReadOnlyMode,
+            r#"
+            INSERT INTO api_read_only_mode ( enabled, reason )
+            VALUES ( $1, $2 )
+            ON CONFLICT (id) DO UPDATE
+            SET enabled = EXCLUDED.enabled, reason = EXCLUDED.reason, updated_at = now()
+            RETURNING enabled, reason, updated_at
+            "#,
+            enabled,
+            reason
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.read_only_mode.set_read_only_mode", start.elapsed());
+        Ok(mode)
+    }
+}
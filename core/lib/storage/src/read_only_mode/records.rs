@@ -0,0 +1,9 @@
+// External imports
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredReadOnlyMode {
+    pub enabled: bool,
+    pub reason: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
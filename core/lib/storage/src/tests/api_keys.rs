@@ -0,0 +1,72 @@
+// Workspace imports
+// Local imports
+use crate::tests::db_test;
+use crate::{api_keys::ApiKeysSchema, QueryResult, StorageProcessor};
+
+/// Checks that the API key quotas are stored, updated and revoked.
+#[db_test]
+async fn stored_api_keys(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let quota = ApiKeysSchema(&mut storage).get_api_key_quota("key").await?;
+    assert_eq!(quota, None);
+
+    let stored_key = ApiKeysSchema(&mut storage)
+        .store_api_key("key", 100, "partner")
+        .await?;
+    assert_eq!(stored_key.requests_per_second, 100);
+    assert_eq!(stored_key.description, "partner");
+    assert!(stored_key.revoked_at.is_none());
+    let quota = ApiKeysSchema(&mut storage).get_api_key_quota("key").await?;
+    assert_eq!(quota, Some(100));
+
+    // The quota of the existing key is replaced.
+    ApiKeysSchema(&mut storage)
+        .store_api_key("key", 500, "partner")
+        .await?;
+    let quota = ApiKeysSchema(&mut storage).get_api_key_quota("key").await?;
+    assert_eq!(quota, Some(500));
+
+    let quota = ApiKeysSchema(&mut storage)
+        .get_api_key_quota("other")
+        .await?;
+    assert_eq!(quota, None);
+
+    // The revoked key has no quota, but is still listed.
+    assert!(ApiKeysSchema(&mut storage).revoke_api_key("key").await?);
+    assert!(!ApiKeysSchema(&mut storage).revoke_api_key("key").await?);
+    assert!(!ApiKeysSchema(&mut storage).revoke_api_key("other").await?);
+    let quota = ApiKeysSchema(&mut storage).get_api_key_quota("key").await?;
+    assert_eq!(quota, None);
+
+    let keys = ApiKeysSchema(&mut storage).load_api_keys().await?;
+    assert_eq!(keys.len(), 1);
+    assert_eq!(keys[0].api_key, "key");
+    assert!(keys[0].revoked_at.is_some());
+
+    Ok(())
+}
+
+/// Checks that the requests made with the API key are summed up per day.
+#[db_test]
+async fn api_key_usage(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    ApiKeysSchema(&mut storage)
+        .store_api_key("key", 100, "partner")
+        .await?;
+    let usage = ApiKeysSchema(&mut storage)
+        .load_api_key_usage("key")
+        .await?;
+    assert!(usage.is_empty());
+
+    ApiKeysSchema(&mut storage)
+        .record_api_key_usage("key", 10)
+        .await?;
+    ApiKeysSchema(&mut storage)
+        .record_api_key_usage("key", 15)
+        .await?;
+    let usage = ApiKeysSchema(&mut storage)
+        .load_api_key_usage("key")
+        .await?;
+    assert_eq!(usage.len(), 1);
+    assert_eq!(usage[0].requests, 25);
+
+    Ok(())
+}
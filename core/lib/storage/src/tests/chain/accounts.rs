@@ -3,8 +3,8 @@ use num::{BigUint, Zero};
 // Workspace imports
 use zksync_crypto::params::{MIN_NFT_TOKEN_ID, NFT_TOKEN_ID};
 use zksync_types::{
-    aggregated_operations::AggregatedActionType, helpers::apply_updates, AccountId, AccountMap,
-    AccountUpdate, Address, BlockNumber, Nonce, Token, TokenId, TokenKind,
+    aggregated_operations::AggregatedActionType, helpers::apply_updates, Account, AccountId,
+    AccountMap, AccountUpdate, Address, BlockNumber, Nonce, Token, TokenId, TokenKind,
 };
 // Local imports
 use super::block::apply_random_updates;
@@ -323,6 +323,79 @@ async fn test_get_balance(mut storage: StorageProcessor<'_>) -> QueryResult<()>
     Ok(())
 }
 
+/// Checks that the state of the account is replayed up to the requested block.
+#[db_test]
+async fn test_account_state_at_block(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let _lock = ACCOUNT_MUTEX.lock().await;
+    let address = Address::random();
+    let updates1 = vec![
+        (
+            AccountId(1),
+            AccountUpdate::Create {
+                address,
+                nonce: Nonce(0),
+            },
+        ),
+        (
+            AccountId(1),
+            AccountUpdate::UpdateBalance {
+                old_nonce: Nonce(0),
+                new_nonce: Nonce(1),
+                balance_update: (TokenId(0), BigUint::zero(), BigUint::from(100u32)),
+            },
+        ),
+    ];
+    let updates2 = vec![(
+        AccountId(1),
+        AccountUpdate::UpdateBalance {
+            old_nonce: Nonce(1),
+            new_nonce: Nonce(2),
+            balance_update: (TokenId(0), BigUint::from(100u32), BigUint::from(50u32)),
+        },
+    )];
+    storage
+        .chain()
+        .state_schema()
+        .commit_state_update(BlockNumber(1), &updates1, 0)
+        .await?;
+    storage
+        .chain()
+        .state_schema()
+        .commit_state_update(BlockNumber(2), &updates2, 0)
+        .await?;
+
+    // The account didn't exist before its creation.
+    assert!(AccountSchema(&mut storage)
+        .account_state_at_block(AccountId(1), BlockNumber(0))
+        .await?
+        .is_none());
+
+    let expected = |nonce, balance: u32| {
+        let mut account = Account::default_with_address(&address);
+        account.nonce = Nonce(nonce);
+        account.set_balance(TokenId(0), BigUint::from(balance));
+        account
+    };
+    let (last_block, account) = AccountSchema(&mut storage)
+        .account_state_at_block(AccountId(1), BlockNumber(1))
+        .await?
+        .unwrap();
+    assert_eq!(last_block, BlockNumber(1));
+    assert_eq!(account, expected(1, 100));
+
+    // The blocks without the updates of the account keep its state.
+    for block in 2..=3 {
+        let (last_block, account) = AccountSchema(&mut storage)
+            .account_state_at_block(AccountId(1), BlockNumber(block))
+            .await?
+            .unwrap();
+        assert_eq!(last_block, BlockNumber(2));
+        assert_eq!(account, expected(2, 50));
+    }
+
+    Ok(())
+}
+
 #[db_test]
 async fn test_get_account_nft_balance(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     let address = Address::random();
@@ -1,12 +1,13 @@
 // External imports
 use chrono::Utc;
 // Workspace imports
+use zksync_api_types::v02::transaction::TxInBlockStatus;
 use zksync_crypto::rand::{Rng, SeedableRng, XorShiftRng};
 use zksync_types::{
-    block::{Block, ExecutedOperations},
+    block::{Block, ExecutedOperations, PendingBlock},
     mempool::SignedTxVariant,
-    priority_ops::FullExit,
-    tx::{ChangePubKey, Transfer, TxHash, Withdraw},
+    priority_ops::{Deposit, FullExit},
+    tx::{ChangePubKey, TimeRange, Transfer, TxHash, Withdraw},
     AccountId, Address, BlockNumber, ExecutedPriorityOp, ExecutedTx, FullExitOp, Nonce, PriorityOp,
     SignedZkSyncTx, TokenId, ZkSyncOp, ZkSyncPriorityOp, ZkSyncTx, H256,
 };
@@ -15,6 +16,7 @@ use crate::test_data::gen_eth_sign_data;
 use crate::tests::db_test;
 use crate::{
     chain::{
+        block::BlockSchema,
         mempool::MempoolSchema,
         operations::{
             records::{NewExecutedPriorityOperation, NewExecutedTransaction},
@@ -512,3 +514,283 @@ async fn test_return_executed_txs_to_mempool(mut storage: StorageProcessor<'_>)
     assert_eq!(block_tx.variance_name(), "FullExit");
     Ok(())
 }
+
+/// Checks that the pending transactions of the account are found by its id and address.
+#[db_test]
+async fn account_pending_txs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let txs = zksync_txs();
+    for tx in &txs {
+        MempoolSchema(&mut storage).insert_tx(tx).await?;
+    }
+    let hashes = |txs: Vec<SignedZkSyncTx>| txs.iter().map(|tx| tx.hash()).collect::<Vec<_>>();
+
+    // The transaction sent by the account and the one transferring funds to its address.
+    let recipient = match &txs[1].tx {
+        ZkSyncTx::Transfer(transfer) => transfer.to,
+        _ => unreachable!(),
+    };
+    let pending_txs = MempoolSchema(&mut storage)
+        .get_account_pending_txs(AccountId(42), recipient)
+        .await?;
+    assert_eq!(hashes(pending_txs), vec![txs[0].hash(), txs[1].hash()]);
+
+    let pending_txs = MempoolSchema(&mut storage)
+        .get_account_pending_txs(AccountId(123), Address::random())
+        .await?;
+    assert_eq!(hashes(pending_txs), vec![txs[3].hash()]);
+
+    let pending_txs = MempoolSchema(&mut storage)
+        .get_account_pending_txs(AccountId(1), Address::random())
+        .await?;
+    assert!(pending_txs.is_empty());
+
+    Ok(())
+}
+
+/// Checks that the pending transaction is found by its nonce and can be replaced.
+#[db_test]
+async fn replace_tx_by_nonce(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let txs = zksync_txs();
+    for tx in &txs {
+        MempoolSchema(&mut storage).insert_tx(tx).await?;
+    }
+
+    let (pending_tx, batch_id) = MempoolSchema(&mut storage)
+        .get_pending_tx_by_nonce(AccountId(42), Nonce(10))
+        .await?
+        .expect("Pending transaction is not found");
+    assert_eq!(pending_tx.hash(), txs[0].hash());
+    assert_eq!(batch_id, None);
+    assert!(MempoolSchema(&mut storage)
+        .get_pending_tx_by_nonce(AccountId(42), Nonce(11))
+        .await?
+        .is_none());
+
+    // The replacement is placed at the end of the queue, the replaced transaction is removed.
+    let replacement = gen_transfers(43).pop().unwrap();
+    assert!(
+        MempoolSchema(&mut storage)
+            .replace_tx(txs[0].hash(), &replacement)
+            .await?
+    );
+    assert!(
+        !MempoolSchema(&mut storage)
+            .contains_tx(txs[0].hash())
+            .await?
+    );
+    let queue = MempoolSchema(&mut storage).load_txs(&[]).await?;
+    let last_tx = unwrap_tx(queue.back().cloned().unwrap());
+    assert_eq!(last_tx.hash(), replacement.hash());
+
+    // Transactions of the batches are reported along with the batch ID.
+    let batch = gen_transfers(2);
+    let batch_id = MempoolSchema(&mut storage)
+        .insert_batch(&batch, Vec::new())
+        .await?;
+    let (_, pending_batch_id) = MempoolSchema(&mut storage)
+        .get_pending_tx_by_nonce(AccountId(1), Nonce(10))
+        .await?
+        .unwrap();
+    assert_eq!(pending_batch_id, Some(batch_id));
+
+    Ok(())
+}
+
+/// Checks that the pending transaction executed in the pending block isn't replaced.
+#[db_test]
+async fn replace_executed_tx(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let txs = zksync_txs();
+    for tx in &txs {
+        MempoolSchema(&mut storage).insert_tx(tx).await?;
+    }
+    // The transactions stay in the mempool until their block is committed.
+    let executed_tx = ExecutedOperations::Tx(Box::new(ExecutedTx {
+        signed_tx: txs[0].clone(),
+        success: true,
+        op: None,
+        fail_reason: None,
+        block_index: Some(0),
+        created_at: Utc::now(),
+        batch_id: None,
+    }));
+    BlockSchema(&mut storage)
+        .save_pending_block(PendingBlock {
+            number: BlockNumber(1),
+            chunks_left: 10,
+            unprocessed_priority_op_before: 0,
+            pending_block_iteration: 1,
+            success_operations: vec![executed_tx],
+            failed_txs: Vec::new(),
+            timestamp: 0,
+        })
+        .await?;
+
+    let (pending_tx, _) = MempoolSchema(&mut storage)
+        .get_pending_tx_by_nonce(AccountId(42), Nonce(10))
+        .await?
+        .expect("Pending transaction is not found");
+    assert_eq!(pending_tx.hash(), txs[0].hash());
+
+    let replacement = gen_transfers(43).pop().unwrap();
+    assert!(
+        !MempoolSchema(&mut storage)
+            .replace_tx(txs[0].hash(), &replacement)
+            .await?
+    );
+    assert!(
+        MempoolSchema(&mut storage)
+            .contains_tx(txs[0].hash())
+            .await?
+    );
+    assert!(
+        !MempoolSchema(&mut storage)
+            .contains_tx(replacement.hash())
+            .await?
+    );
+
+    // The transaction which isn't executed yet is still replaced.
+    let (pending_tx, _) = MempoolSchema(&mut storage)
+        .get_pending_tx_by_nonce(AccountId(123), Nonce(13))
+        .await?
+        .expect("Pending transaction is not found");
+    assert_eq!(pending_tx.hash(), txs[3].hash());
+    assert!(
+        MempoolSchema(&mut storage)
+            .replace_tx(txs[3].hash(), &replacement)
+            .await?
+    );
+
+    Ok(())
+}
+
+/// Checks the mempool statistics and the queue positions of the account transactions.
+#[db_test]
+async fn mempool_queue_stats(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let stats = MempoolSchema(&mut storage).get_mempool_stats().await?;
+    assert_eq!(stats.txs_count, 0);
+    assert_eq!(stats.oldest_tx_created_at, None);
+
+    let txs = zksync_txs();
+    for tx in &txs {
+        MempoolSchema(&mut storage).insert_tx(tx).await?;
+    }
+    let batch = gen_transfers(2);
+    let batch_id = MempoolSchema(&mut storage)
+        .insert_batch(&batch, Vec::new())
+        .await?;
+
+    let stats = MempoolSchema(&mut storage).get_mempool_stats().await?;
+    assert_eq!(stats.txs_count, 6);
+    assert_eq!(stats.batches_count, 1);
+    assert_eq!(stats.reverted_txs_count, 0);
+    assert_eq!(stats.priority_ops_count, 0);
+    assert!(stats.oldest_tx_created_at.is_some());
+
+    let queued_txs = MempoolSchema(&mut storage)
+        .get_account_queued_txs(AccountId(33))
+        .await?;
+    assert_eq!(queued_txs.len(), 1);
+    assert_eq!(queued_txs[0].tx_hash, txs[2].hash());
+    assert_eq!(queued_txs[0].nonce, Nonce(12));
+    assert_eq!(queued_txs[0].batch_id, None);
+    assert_eq!(queued_txs[0].queue_position, 2);
+
+    let queued_txs = MempoolSchema(&mut storage)
+        .get_account_queued_txs(AccountId(1))
+        .await?;
+    assert_eq!(queued_txs.len(), 1);
+    assert_eq!(queued_txs[0].batch_id, Some(batch_id as u64));
+    assert_eq!(queued_txs[0].queue_position, 5);
+
+    assert!(MempoolSchema(&mut storage)
+        .get_account_queued_txs(AccountId(7))
+        .await?
+        .is_empty());
+
+    Ok(())
+}
+
+/// Checks that the mempool transactions which aren't valid yet are reported as scheduled.
+#[db_test]
+async fn scheduled_tx_status(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut txs = gen_transfers(2);
+    let valid_from = Utc::now().timestamp() as u64 + 3600;
+    if let ZkSyncTx::Transfer(transfer) = &mut txs[1].tx {
+        transfer.time_range = Some(TimeRange::new(valid_from, u64::MAX));
+    }
+    for tx in &txs {
+        MempoolSchema(&mut storage).insert_tx(tx).await?;
+    }
+
+    for (tx, expected_status) in txs
+        .iter()
+        .zip(vec![TxInBlockStatus::Queued, TxInBlockStatus::Scheduled])
+    {
+        let receipt = OperationsExtSchema(&mut storage)
+            .tx_receipt_api_v02(tx.hash().as_ref())
+            .await?
+            .expect("mempool tx receipt");
+        assert_eq!(receipt.status(), expected_status);
+
+        let data = OperationsExtSchema(&mut storage)
+            .tx_data_api_v02(tx.hash().as_ref())
+            .await?
+            .expect("mempool tx data");
+        assert_eq!(data.tx.status, expected_status);
+    }
+
+    Ok(())
+}
+
+/// Checks that only the deposits without enough confirmations are reported as unconfirmed,
+/// and that the last block processed by the eth watcher is stored.
+#[db_test]
+async fn unconfirmed_deposits(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let address = Address::repeat_byte(0x12);
+    let deposit = |serial_id: u64, eth_block: u64| PriorityOp {
+        serial_id,
+        data: ZkSyncPriorityOp::Deposit(Deposit {
+            from: Address::repeat_byte(0x34),
+            token: TokenId(0),
+            amount: 100u32.into(),
+            to: address,
+        }),
+        deadline_block: 1000,
+        eth_hash: H256::from_low_u64_be(serial_id),
+        eth_block,
+        eth_block_index: Some(1),
+    };
+    MempoolSchema(&mut storage)
+        .insert_priority_ops(&[deposit(0, 10)], true)
+        .await?;
+    MempoolSchema(&mut storage)
+        .insert_priority_ops(&[deposit(1, 20), deposit(2, 21)], false)
+        .await?;
+
+    let deposits = MempoolSchema(&mut storage)
+        .get_unconfirmed_deposits(address)
+        .await?;
+    let serial_ids: Vec<_> = deposits.iter().map(|op| op.serial_id).collect();
+    assert_eq!(serial_ids, vec![1, 2]);
+    assert!(MempoolSchema(&mut storage)
+        .get_unconfirmed_deposits(Address::repeat_byte(0x34))
+        .await?
+        .is_empty());
+
+    assert_eq!(
+        MempoolSchema(&mut storage).last_watched_eth_block().await?,
+        None
+    );
+    MempoolSchema(&mut storage)
+        .update_last_watched_eth_block(21)
+        .await?;
+    MempoolSchema(&mut storage)
+        .update_last_watched_eth_block(22)
+        .await?;
+    assert_eq!(
+        MempoolSchema(&mut storage).last_watched_eth_block().await?,
+        Some(22)
+    );
+
+    Ok(())
+}
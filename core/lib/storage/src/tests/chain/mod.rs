@@ -2,7 +2,7 @@ mod accounts;
 mod block;
 mod mempool;
 mod operations;
-mod operations_ext;
+pub(crate) mod operations_ext;
 mod state;
 mod tree_cache;
 
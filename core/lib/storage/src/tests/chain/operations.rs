@@ -563,3 +563,43 @@ async fn test_remove_eth_unprocessed_aggregated_ops(
 
     Ok(())
 }
+
+/// Checks that the recent confirmed aggregated operations are loaded, the latest one first.
+#[db_test]
+async fn recent_confirmed_aggregated_actions(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let action_type = AggregatedActionType::CommitBlocks;
+    for block_number in 1..=3 {
+        OperationsSchema(&mut storage)
+            .store_aggregated_action(gen_unique_aggregated_operation(
+                BlockNumber(block_number),
+                action_type,
+                100,
+            ))
+            .await?;
+    }
+    OperationsSchema(&mut storage)
+        .confirm_aggregated_operations(BlockNumber(1), BlockNumber(2), action_type)
+        .await?;
+
+    let blocks = |actions: Vec<(BlockNumber, _)>| {
+        actions
+            .into_iter()
+            .map(|(block_number, _)| block_number)
+            .collect::<Vec<_>>()
+    };
+    // The unconfirmed operation isn't loaded.
+    let actions = OperationsSchema(&mut storage)
+        .load_recent_confirmed_aggregated_actions(action_type, 10)
+        .await?;
+    assert_eq!(blocks(actions), vec![BlockNumber(2), BlockNumber(1)]);
+    let actions = OperationsSchema(&mut storage)
+        .load_recent_confirmed_aggregated_actions(action_type, 1)
+        .await?;
+    assert_eq!(blocks(actions), vec![BlockNumber(2)]);
+    let actions = OperationsSchema(&mut storage)
+        .load_recent_confirmed_aggregated_actions(AggregatedActionType::ExecuteBlocks, 10)
+        .await?;
+    assert!(actions.is_empty());
+
+    Ok(())
+}
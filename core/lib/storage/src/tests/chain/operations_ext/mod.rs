@@ -1,17 +1,22 @@
 // Built-in imports
 use std::collections::HashMap;
+use std::str::FromStr;
 // External imports
 // Workspace imports
 use zksync_api_types::v02::{
-    pagination::{AccountTxsRequest, ApiEither, PaginationDirection, PaginationQuery},
-    transaction::{Receipt, TxInBlockStatus},
+    account::AccountTxExport,
+    pagination::{
+        AccountTxsFilters, AccountTxsRequest, ApiEither, PaginationDirection, PaginationQuery,
+        TokenTxsRequest,
+    },
+    transaction::{PriorityOpId, PriorityOpStage, Receipt, TxInBlockStatus},
 };
 use zksync_crypto::{franklin_crypto::bellman::pairing::ff::Field, Fr};
 use zksync_types::{
     aggregated_operations::{AggregatedActionType, AggregatedOperation},
     block::Block,
     tx::TxHash,
-    AccountId, AccountUpdate, BlockNumber, ExecutedOperations, Nonce, ZkSyncOp, H256,
+    AccountId, AccountUpdate, BlockNumber, EthBlockId, ExecutedOperations, Nonce, ZkSyncOp, H256,
 };
 // Local imports
 use self::setup::TransactionsHistoryTestSetup;
@@ -110,7 +115,14 @@ async fn confirm_eth_op(
     let eth_tx_hash = dummy_ethereum_tx_hash(op.0);
     let response = storage
         .ethereum_schema()
-        .save_new_eth_tx(op_type, Some(op), 100, 100u32.into(), Default::default())
+        .save_new_eth_tx(
+            op_type,
+            Some(op),
+            100,
+            100u32.into(),
+            None,
+            Default::default(),
+        )
         .await?;
     storage
         .ethereum_schema()
@@ -386,6 +398,7 @@ async fn get_account_transactions(mut storage: StorageProcessor<'_>) -> QueryRes
                 tx_hash: ApiEither::from(setup.get_tx_hash(0, 0)),
                 token: None,
                 second_address: None,
+                filters: Default::default(),
             },
             limit: 1,
             direction: PaginationDirection::Newer,
@@ -514,6 +527,7 @@ async fn get_account_transactions(mut storage: StorageProcessor<'_>) -> QueryRes
                     tx_hash: ApiEither::from(request.tx_hash),
                     token: None,
                     second_address: None,
+                    filters: Default::default(),
                 },
                 limit: request.limit,
                 direction: request.direction,
@@ -533,6 +547,7 @@ async fn get_account_transactions(mut storage: StorageProcessor<'_>) -> QueryRes
                 tx_hash: ApiEither::from(setup.get_tx_hash(1, 2)),
                 token: None,
                 second_address: None,
+                filters: Default::default(),
             },
             limit: 1,
             direction: PaginationDirection::Newer,
@@ -551,6 +566,7 @@ async fn get_account_transactions(mut storage: StorageProcessor<'_>) -> QueryRes
                 tx_hash: ApiEither::from(setup.get_tx_hash(0, 9)),
                 token: None,
                 second_address: None,
+                filters: Default::default(),
             },
             limit: 2,
             direction: PaginationDirection::Newer,
@@ -570,6 +586,7 @@ async fn get_account_transactions(mut storage: StorageProcessor<'_>) -> QueryRes
                 tx_hash: ApiEither::from(setup.get_tx_hash(0, 2)),
                 token: None,
                 second_address: None,
+                filters: Default::default(),
             },
             limit: 1,
             direction: PaginationDirection::Newer,
@@ -585,6 +602,7 @@ async fn get_account_transactions(mut storage: StorageProcessor<'_>) -> QueryRes
                 tx_hash: ApiEither::from(setup.get_tx_hash(0, 2)),
                 token: None,
                 second_address: None,
+                filters: Default::default(),
             },
             limit: 1,
             direction: PaginationDirection::Newer,
@@ -825,6 +843,311 @@ async fn account_last_tx_hash(mut storage: StorageProcessor<'_>) -> QueryResult<
     Ok(())
 }
 
+/// Test the filters of `get_account_transactions` and `get_filtered_account_transactions_count` methods
+#[db_test]
+async fn account_transactions_filters(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    let address = setup.from_zksync_account.address;
+    setup.add_block(1);
+    commit_schema_data(&mut storage, &setup).await?;
+    commit_block(&mut storage, BlockNumber(1)).await?;
+
+    let test_data = vec![
+        (
+            "Filter by the transaction type.",
+            AccountTxsFilters {
+                tx_type: Some("Transfer".to_string()),
+                ..Default::default()
+            },
+            vec![setup.get_tx_hash(0, 2), setup.get_tx_hash(0, 1)],
+        ),
+        (
+            "Filter the priority operations by the type and the status.",
+            AccountTxsFilters {
+                tx_type: Some("Deposit".to_string()),
+                status: Some(TxInBlockStatus::Committed),
+                ..Default::default()
+            },
+            vec![setup.get_tx_hash(0, 0)],
+        ),
+        (
+            "The block isn't verified, so there are no finalized transactions.",
+            AccountTxsFilters {
+                status: Some(TxInBlockStatus::Finalized),
+                ..Default::default()
+            },
+            vec![],
+        ),
+        (
+            "Filter by the creation time.",
+            AccountTxsFilters {
+                tx_type: Some("Withdraw".to_string()),
+                created_before: Some(chrono::Utc::now() + chrono::Duration::days(1)),
+                ..Default::default()
+            },
+            vec![setup.get_tx_hash(0, 5)],
+        ),
+        (
+            "No transactions are created in the future.",
+            AccountTxsFilters {
+                created_after: Some(chrono::Utc::now() + chrono::Duration::days(1)),
+                ..Default::default()
+            },
+            vec![],
+        ),
+    ];
+
+    for (msg, filters, expected_tx_hashes) in test_data {
+        let txs = storage
+            .chain()
+            .operations_ext_schema()
+            .get_account_transactions(&PaginationQuery {
+                from: AccountTxsRequest {
+                    address,
+                    tx_hash: ApiEither::from_str("latest").unwrap(),
+                    token: None,
+                    second_address: None,
+                    filters: filters.clone(),
+                },
+                limit: 10,
+                direction: PaginationDirection::Older,
+            })
+            .await?
+            .unwrap();
+        let tx_hashes: Vec<_> = txs.iter().map(|tx| tx.tx_hash).collect();
+        assert_eq!(tx_hashes, expected_tx_hashes, "{}", msg);
+
+        let count = storage
+            .chain()
+            .operations_ext_schema()
+            .get_filtered_account_transactions_count(address, None, None, &filters)
+            .await?;
+        assert_eq!(count as usize, expected_tx_hashes.len(), "{}", msg);
+    }
+
+    Ok(())
+}
+
+/// Test `declare_account_txs_export` and `fetch_account_txs_export` methods
+#[db_test]
+async fn account_transactions_export(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    let address = setup.from_zksync_account.address;
+    setup.add_block(1);
+    commit_schema_data(&mut storage, &setup).await?;
+
+    let filters = AccountTxsFilters::default();
+    let mut expected_tx_hashes: Vec<_> = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_transactions(&PaginationQuery {
+            from: AccountTxsRequest {
+                address,
+                tx_hash: ApiEither::from_str("latest").unwrap(),
+                token: None,
+                second_address: None,
+                filters: filters.clone(),
+            },
+            limit: 100,
+            direction: PaginationDirection::Older,
+        })
+        .await?
+        .unwrap()
+        .into_iter()
+        .map(|tx| tx.tx_hash)
+        .collect();
+    expected_tx_hashes.reverse();
+
+    // The cursor can't outlive the statement outside of the transaction.
+    assert!(storage
+        .chain()
+        .operations_ext_schema()
+        .declare_account_txs_export(address, None, None, &filters)
+        .await
+        .is_err());
+
+    let mut transaction = storage.start_transaction().await?;
+    transaction
+        .chain()
+        .operations_ext_schema()
+        .declare_account_txs_export(address, None, None, &filters)
+        .await?;
+    let mut tx_hashes = Vec::new();
+    loop {
+        let batch = transaction
+            .chain()
+            .operations_ext_schema()
+            .fetch_account_txs_export(4)
+            .await?;
+        if batch.is_empty() {
+            break;
+        }
+        assert!(batch.len() <= 4);
+        tx_hashes.extend(
+            batch
+                .into_iter()
+                .map(|record| AccountTxExport::from(record).tx_hash),
+        );
+    }
+    transaction.commit().await?;
+    assert_eq!(tx_hashes, expected_tx_hashes);
+
+    Ok(())
+}
+
+/// Test `get_priority_op_lifecycle` method
+#[db_test]
+async fn priority_op_lifecycle(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    let (deposit, full_exit) = match (
+        &setup.blocks[0].block_transactions[0],
+        &setup.blocks[0].block_transactions[9],
+    ) {
+        (ExecutedOperations::PriorityOp(deposit), ExecutedOperations::PriorityOp(full_exit)) => {
+            (deposit.priority_op.clone(), full_exit.priority_op.clone())
+        }
+        _ => panic!("Priority operations are expected"),
+    };
+
+    // The operation seen by the Ethereum watcher waits for the confirmations in the mempool.
+    let mut pending_op = deposit.clone();
+    pending_op.serial_id = 100;
+    pending_op.eth_hash = H256::repeat_byte(0x11);
+    for &confirmed in &[false, true] {
+        storage
+            .chain()
+            .mempool_schema()
+            .insert_priority_ops(&[pending_op.clone()], confirmed)
+            .await?;
+        let lifecycle = storage
+            .chain()
+            .operations_ext_schema()
+            .get_priority_op_lifecycle(PriorityOpId::EthHash(pending_op.eth_hash))
+            .await?
+            .unwrap();
+        assert_eq!(lifecycle.serial_id, 100);
+        assert_eq!(lifecycle.tx_hash, pending_op.tx_hash());
+        assert_eq!(lifecycle.stage, PriorityOpStage::SeenOnL1);
+        assert_eq!(lifecycle.confirmed_on_l1, confirmed);
+        assert!(lifecycle.rollup_block.is_none());
+    }
+
+    commit_schema_data(&mut storage, &setup).await?;
+    let lifecycle = storage
+        .chain()
+        .operations_ext_schema()
+        .get_priority_op_lifecycle(PriorityOpId::SerialId(deposit.serial_id))
+        .await?
+        .unwrap();
+    assert_eq!(lifecycle.stage, PriorityOpStage::IncludedInBlock);
+    assert_eq!(lifecycle.op_type, "Deposit");
+    assert_eq!(lifecycle.eth_hash, deposit.eth_hash);
+    assert_eq!(lifecycle.rollup_block, Some(BlockNumber(1)));
+    assert_eq!(lifecycle.block_index, Some(0));
+    assert!(lifecycle.committed_at.is_none());
+
+    commit_block(&mut storage, BlockNumber(1)).await?;
+    let lifecycle = storage
+        .chain()
+        .operations_ext_schema()
+        .get_priority_op_lifecycle(PriorityOpId::EthHash(full_exit.eth_hash))
+        .await?
+        .unwrap();
+    assert_eq!(lifecycle.stage, PriorityOpStage::Committed);
+    assert_eq!(lifecycle.serial_id, full_exit.serial_id);
+    assert_eq!(lifecycle.op_type, "FullExit");
+    assert!(lifecycle.commit_tx_hash.is_some());
+    assert!(lifecycle.finalized_at.is_none());
+
+    verify_block(&mut storage, BlockNumber(1)).await?;
+    let lifecycle = storage
+        .chain()
+        .operations_ext_schema()
+        .get_priority_op_lifecycle(PriorityOpId::SerialId(deposit.serial_id))
+        .await?
+        .unwrap();
+    assert_eq!(lifecycle.stage, PriorityOpStage::Finalized);
+    assert!(lifecycle.verify_tx_hash.is_some());
+    assert!(lifecycle.finalized_at.is_some());
+
+    let lifecycle = storage
+        .chain()
+        .operations_ext_schema()
+        .get_priority_op_lifecycle(PriorityOpId::SerialId(1000))
+        .await?;
+    assert!(lifecycle.is_none());
+
+    Ok(())
+}
+
+/// Test `get_token_transactions`, `get_token_transactions_count` and `get_token_last_tx_hash` methods
+#[db_test]
+async fn token_transactions(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    // The withdrawal token is used only by the withdrawal and the full exit.
+    let token = setup.tokens[2].id;
+    let last_tx_hash = storage
+        .chain()
+        .operations_ext_schema()
+        .get_token_last_tx_hash(token)
+        .await?;
+    assert!(last_tx_hash.is_none());
+
+    setup.add_block(1);
+    commit_schema_data(&mut storage, &setup).await?;
+
+    let last_tx_hash = storage
+        .chain()
+        .operations_ext_schema()
+        .get_token_last_tx_hash(token)
+        .await?;
+    assert_eq!(last_tx_hash, Some(setup.get_tx_hash(0, 9)));
+    let count = storage
+        .chain()
+        .operations_ext_schema()
+        .get_token_transactions_count(token)
+        .await?;
+    assert_eq!(count, 2);
+
+    let txs = storage
+        .chain()
+        .operations_ext_schema()
+        .get_token_transactions(&PaginationQuery {
+            from: TokenTxsRequest {
+                token,
+                tx_hash: ApiEither::from_str("latest").unwrap(),
+            },
+            limit: 10,
+            direction: PaginationDirection::Older,
+        })
+        .await?
+        .unwrap();
+    let tx_hashes: Vec<_> = txs.iter().map(|tx| tx.tx_hash).collect();
+    assert_eq!(
+        tx_hashes,
+        vec![setup.get_tx_hash(0, 9), setup.get_tx_hash(0, 5)]
+    );
+
+    let txs = storage
+        .chain()
+        .operations_ext_schema()
+        .get_token_transactions(&PaginationQuery {
+            from: TokenTxsRequest {
+                token,
+                tx_hash: ApiEither::from(setup.get_tx_hash(0, 5)),
+            },
+            limit: 10,
+            direction: PaginationDirection::Newer,
+        })
+        .await?
+        .unwrap();
+    assert_eq!(txs.len(), 2);
+    assert_eq!(txs[0].tx_hash, setup.get_tx_hash(0, 5));
+
+    Ok(())
+}
+
 /// Test `get_block_last_tx_hash` method
 #[db_test]
 async fn block_last_tx_hash(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -943,6 +1266,75 @@ async fn tx_receipt(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     Ok(())
 }
 
+/// Checks that the L1 details of the block are loaded from the confirmed Ethereum transactions.
+#[db_test]
+async fn block_l1_details(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let block_number = BlockNumber(1);
+    let details = storage
+        .chain()
+        .operations_ext_schema()
+        .load_block_l1_details(block_number)
+        .await?;
+    assert_eq!(details, Default::default());
+
+    commit_block(&mut storage, block_number).await?;
+    let details = storage
+        .chain()
+        .operations_ext_schema()
+        .load_block_l1_details(block_number)
+        .await?;
+    assert!(details.commit_tx_hash.is_some());
+    assert!(details.committed_at.is_some());
+    assert!(details.commit_eth_block.is_none());
+    assert!(details.verify_tx_hash.is_none());
+    assert!(details.finalized_at.is_none());
+
+    // The executing transaction is confirmed along with the Ethereum block which includes it.
+    OperationsSchema(&mut storage)
+        .store_aggregated_action(gen_unique_aggregated_operation(
+            block_number,
+            AggregatedActionType::ExecuteBlocks,
+            BLOCK_SIZE_CHUNKS,
+        ))
+        .await?;
+    let (id, op) = OperationsSchema(&mut storage)
+        .get_aggregated_op_that_affects_block(AggregatedActionType::ExecuteBlocks, block_number)
+        .await?
+        .unwrap();
+    let eth_tx_hash = dummy_ethereum_tx_hash(id);
+    let response = storage
+        .ethereum_schema()
+        .save_new_eth_tx(
+            AggregatedActionType::ExecuteBlocks,
+            Some((id, op)),
+            100,
+            100u32.into(),
+            None,
+            Default::default(),
+        )
+        .await?;
+    storage
+        .ethereum_schema()
+        .add_hash_entry(response.id, &eth_tx_hash)
+        .await?;
+    storage
+        .ethereum_schema()
+        .confirm_eth_tx_in_block(&eth_tx_hash, Some(42))
+        .await?;
+
+    let details = storage
+        .chain()
+        .operations_ext_schema()
+        .load_block_l1_details(block_number)
+        .await?;
+    assert!(details.commit_tx_hash.is_some());
+    assert_eq!(details.verify_tx_hash, Some(eth_tx_hash));
+    assert_eq!(details.verify_eth_block, Some(EthBlockId(42)));
+    assert!(details.finalized_at.is_some());
+
+    Ok(())
+}
+
 /// Test `tx_data_api_v02` method
 #[db_test]
 async fn tx_data(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -1234,6 +1626,7 @@ async fn test_getting_swap_for_acc(mut storage: StorageProcessor<'_>) -> QueryRe
                     tx_hash: ApiEither::from(tx_hash),
                     token: None,
                     second_address: None,
+                    filters: Default::default(),
                 },
                 limit: 1,
                 direction: PaginationDirection::Older,
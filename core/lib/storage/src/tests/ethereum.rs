@@ -4,8 +4,8 @@ use std::str::FromStr;
 // Workspace imports
 use zksync_types::{
     aggregated_operations::{AggregatedActionType, AggregatedOperation},
-    ethereum::ETHOperation,
-    BlockNumber, H256, U256,
+    ethereum::{ETHOperation, ETHTxAttempt, ETHTxAttemptReason},
+    Address, BlockNumber, H256, U256,
 };
 // Local imports
 use crate::test_data::{gen_unique_aggregated_operation, BLOCK_SIZE_CHUNKS};
@@ -51,8 +51,10 @@ impl EthereumTxParams {
             op_type,
             op: self.op.clone(),
             nonce: nonce.into(),
+            operator: None,
             last_deadline_block: self.deadline_block,
             last_used_gas_price,
+            last_used_priority_fee: None,
             used_tx_hashes,
             encoded_tx_data: self.raw_tx.clone(),
             confirmed: false,
@@ -116,6 +118,7 @@ async fn ethereum_storage(mut storage: StorageProcessor<'_>) -> QueryResult<()>
             params.op.clone(),
             params.deadline_block as i64,
             params.gas_price.clone(),
+            None,
             params.raw_tx.clone(),
         )
         .await?;
@@ -156,6 +159,7 @@ async fn ethereum_storage(mut storage: StorageProcessor<'_>) -> QueryResult<()>
             params_2.op.clone(),
             params_2.deadline_block as i64,
             params_2.gas_price.clone(),
+            None,
             params_2.raw_tx.clone(),
         )
         .await?;
@@ -270,6 +274,7 @@ async fn ethereum_unprocessed(mut storage: StorageProcessor<'_>) -> QueryResult<
             params.op.clone(),
             params.deadline_block as i64,
             params.gas_price.clone(),
+            None,
             params.raw_tx.clone(),
         )
         .await?;
@@ -328,6 +333,7 @@ async fn ethereum_unprocessed(mut storage: StorageProcessor<'_>) -> QueryResult<
             verify_params.op,
             verify_params.deadline_block as i64,
             verify_params.gas_price.clone(),
+            None,
             verify_params.raw_tx.clone(),
         )
         .await?;
@@ -358,6 +364,264 @@ async fn ethereum_unprocessed(mut storage: StorageProcessor<'_>) -> QueryResult<
     Ok(())
 }
 
+/// Checks that the priority fee of the EIP-1559 transactions is stored and updated
+/// along with the gas price.
+#[db_test]
+async fn ethereum_priority_fee(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    EthereumSchema(&mut storage).initialize_eth_data().await?;
+
+    let block_number = BlockNumber(1);
+    OperationsSchema(&mut storage)
+        .store_aggregated_action(gen_unique_aggregated_operation(
+            block_number,
+            AggregatedActionType::CommitBlocks,
+            BLOCK_SIZE_CHUNKS,
+        ))
+        .await?;
+    let op = OperationsSchema(&mut storage)
+        .get_aggregated_op_that_affects_block(AggregatedActionType::CommitBlocks, block_number)
+        .await?;
+
+    let params = EthereumTxParams::new("CommitBlocks".into(), op);
+    let response = EthereumSchema(&mut storage)
+        .save_new_eth_tx(
+            AggregatedActionType::CommitBlocks,
+            params.op.clone(),
+            params.deadline_block as i64,
+            params.gas_price.clone(),
+            Some(10u32.into()),
+            params.raw_tx.clone(),
+        )
+        .await?;
+    EthereumSchema(&mut storage)
+        .add_hash_entry(response.id, &params.hash)
+        .await?;
+
+    let unconfirmed_operations = EthereumSchema(&mut storage)
+        .load_unconfirmed_operations()
+        .await?;
+    assert_eq!(
+        unconfirmed_operations[0].last_used_priority_fee,
+        Some(U256::from(10))
+    );
+
+    // The replacement transaction increases both the max fee and the priority fee.
+    EthereumSchema(&mut storage)
+        .update_eth_tx(
+            response.id,
+            params.deadline_block as i64 + 10,
+            2000u32.into(),
+            Some(12u32.into()),
+        )
+        .await?;
+    let unconfirmed_operations = EthereumSchema(&mut storage)
+        .load_unconfirmed_operations()
+        .await?;
+    assert_eq!(
+        unconfirmed_operations[0].last_used_priority_fee,
+        Some(U256::from(12))
+    );
+    assert_eq!(
+        unconfirmed_operations[0].last_deadline_block,
+        params.deadline_block + 10
+    );
+
+    Ok(())
+}
+
+/// Checks that the nonces are assigned by the active operator account, and the operation
+/// is moved to the new active account along with its nonce.
+#[db_test]
+async fn ethereum_operator_accounts(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    EthereumSchema(&mut storage).initialize_eth_data().await?;
+    let main_account = Address::repeat_byte(1);
+    let reserve_account = Address::repeat_byte(2);
+    EthereumSchema(&mut storage)
+        .register_operator_accounts(main_account, &[(reserve_account, 5)])
+        .await?;
+
+    let accounts = EthereumSchema(&mut storage)
+        .load_operator_accounts()
+        .await?;
+    assert_eq!(accounts.len(), 2);
+    assert_eq!(accounts[0].address, main_account.as_bytes());
+    assert_eq!(accounts[0].nonce, 0);
+    assert!(accounts[0].is_active);
+    assert_eq!(accounts[1].nonce, 5);
+    assert!(!accounts[1].is_active);
+
+    let block_number = BlockNumber(1);
+    OperationsSchema(&mut storage)
+        .store_aggregated_action(gen_unique_aggregated_operation(
+            block_number,
+            AggregatedActionType::CommitBlocks,
+            BLOCK_SIZE_CHUNKS,
+        ))
+        .await?;
+    let op = OperationsSchema(&mut storage)
+        .get_aggregated_op_that_affects_block(AggregatedActionType::CommitBlocks, block_number)
+        .await?;
+    let params = EthereumTxParams::new("CommitBlocks".into(), op);
+    let response = EthereumSchema(&mut storage)
+        .save_new_eth_tx(
+            AggregatedActionType::CommitBlocks,
+            params.op.clone(),
+            params.deadline_block as i64,
+            params.gas_price.clone(),
+            None,
+            params.raw_tx.clone(),
+        )
+        .await?;
+    EthereumSchema(&mut storage)
+        .add_hash_entry(response.id, &params.hash)
+        .await?;
+    assert_eq!(response.operator, Some(main_account));
+    assert_eq!(response.nonce, U256::from(0));
+
+    // The main account runs low on funds, so the reserve one becomes active.
+    EthereumSchema(&mut storage)
+        .update_operator_balance(main_account, 10u32.into())
+        .await?;
+    EthereumSchema(&mut storage)
+        .activate_operator_account(reserve_account, 7)
+        .await?;
+    let (operator, nonce) = EthereumSchema(&mut storage)
+        .reassign_eth_tx_operator(response.id)
+        .await?;
+    assert_eq!(operator, reserve_account);
+    assert_eq!(nonce, U256::from(7));
+
+    let unconfirmed_operations = EthereumSchema(&mut storage)
+        .load_unconfirmed_operations()
+        .await?;
+    assert_eq!(unconfirmed_operations[0].operator, Some(reserve_account));
+    assert_eq!(unconfirmed_operations[0].nonce, U256::from(7));
+
+    // The registered accounts are kept intact.
+    EthereumSchema(&mut storage)
+        .register_operator_accounts(main_account, &[(reserve_account, 5)])
+        .await?;
+    let accounts = EthereumSchema(&mut storage)
+        .load_operator_accounts()
+        .await?;
+    assert_eq!(accounts[0].balance, Some(10.into()));
+    assert!(!accounts[0].is_active);
+    assert_eq!(accounts[1].nonce, 8);
+    assert!(accounts[1].is_active);
+
+    // The unknown account can't be activated.
+    assert!(EthereumSchema(&mut storage)
+        .activate_operator_account(Address::repeat_byte(3), 0)
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+/// Checks the attempts history of the Ethereum transactions and the manual replacement requests.
+#[db_test]
+async fn ethereum_tx_attempts(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    EthereumSchema(&mut storage).initialize_eth_data().await?;
+    let block_number = BlockNumber(1);
+    OperationsSchema(&mut storage)
+        .store_aggregated_action(gen_unique_aggregated_operation(
+            block_number,
+            AggregatedActionType::CommitBlocks,
+            BLOCK_SIZE_CHUNKS,
+        ))
+        .await?;
+    let op = OperationsSchema(&mut storage)
+        .get_aggregated_op_that_affects_block(AggregatedActionType::CommitBlocks, block_number)
+        .await?;
+    let params = EthereumTxParams::new("CommitBlocks".into(), op);
+    let response = EthereumSchema(&mut storage)
+        .save_new_eth_tx(
+            AggregatedActionType::CommitBlocks,
+            params.op.clone(),
+            params.deadline_block as i64,
+            params.gas_price.clone(),
+            None,
+            params.raw_tx.clone(),
+        )
+        .await?;
+    EthereumSchema(&mut storage)
+        .add_hash_entry(response.id, &params.hash)
+        .await?;
+
+    let initial = ETHTxAttempt {
+        eth_op_id: response.id,
+        tx_hash: params.hash,
+        nonce: response.nonce,
+        gas_price: 1000.into(),
+        priority_fee: None,
+        operator: None,
+        sent_at_block: 10,
+        reason: ETHTxAttemptReason::Initial,
+    };
+    let bump = ETHTxAttempt {
+        tx_hash: H256::repeat_byte(0x11),
+        gas_price: 1150.into(),
+        priority_fee: Some(2.into()),
+        operator: Some(Address::repeat_byte(1)),
+        sent_at_block: 15,
+        reason: ETHTxAttemptReason::ManualBump,
+        ..initial.clone()
+    };
+    EthereumSchema(&mut storage)
+        .add_tx_attempt(&initial)
+        .await?;
+    EthereumSchema(&mut storage).add_tx_attempt(&bump).await?;
+
+    let attempts = EthereumSchema(&mut storage)
+        .load_tx_attempts(response.id)
+        .await?;
+    assert_eq!(attempts, vec![initial, bump]);
+    let first_blocks = EthereumSchema(&mut storage)
+        .load_first_attempt_blocks(&[response.id, response.id + 1])
+        .await?;
+    assert_eq!(first_blocks, vec![(response.id, 10)]);
+
+    // Only the existing unconfirmed operations can be bumped, every request is taken once.
+    assert!(
+        EthereumSchema(&mut storage)
+            .request_tx_bump(response.id)
+            .await?
+    );
+    assert!(
+        EthereumSchema(&mut storage)
+            .request_tx_bump(response.id)
+            .await?
+    );
+    assert!(
+        !EthereumSchema(&mut storage)
+            .request_tx_bump(response.id + 1)
+            .await?
+    );
+    assert_eq!(
+        EthereumSchema(&mut storage).load_tx_bump_requests().await?,
+        vec![response.id]
+    );
+    assert_eq!(
+        EthereumSchema(&mut storage).take_tx_bump_requests().await?,
+        vec![response.id]
+    );
+    assert!(EthereumSchema(&mut storage)
+        .take_tx_bump_requests()
+        .await?
+        .is_empty());
+
+    EthereumSchema(&mut storage)
+        .confirm_eth_tx(&params.hash)
+        .await?;
+    assert!(
+        !EthereumSchema(&mut storage)
+            .request_tx_bump(response.id)
+            .await?
+    );
+
+    Ok(())
+}
+
 /// Simple test for store/load of (average) gas price.
 #[db_test]
 async fn ethereum_gas_update(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -72,6 +72,7 @@ async fn store_operation(
             Some((id, op)),
             100,
             100u32.into(),
+            None,
             Default::default(),
         )
         .await?;
@@ -0,0 +1,116 @@
+// External imports
+use num::BigUint;
+// Workspace imports
+use zksync_crypto::proof::EncodedSingleProof;
+use zksync_types::{
+    exit_proof::{ExitProofData, StoredBlockInfo},
+    AccountId, Address, BlockNumber, TokenId, H256,
+};
+// Local imports
+use crate::tests::db_test;
+use crate::{exit_proofs::ExitProofsSchema, QueryResult, StorageProcessor};
+
+fn exit_proof(block_number: BlockNumber) -> ExitProofData {
+    ExitProofData {
+        stored_block_info: StoredBlockInfo {
+            block_number,
+            priority_operations: 0,
+            pending_onchain_operations_hash: H256::zero(),
+            timestamp: 0,
+            state_hash: H256::repeat_byte(1),
+            commitment: H256::repeat_byte(2),
+        },
+        owner: Address::repeat_byte(3),
+        account_id: AccountId(1),
+        token_id: TokenId(0),
+        amount: BigUint::from(100u32).into(),
+        nft_creator_id: AccountId(0),
+        nft_creator_address: Address::zero(),
+        nft_serial_id: 0,
+        nft_content_hash: H256::zero(),
+        proof: EncodedSingleProof::default(),
+        token_address: Address::zero(),
+    }
+}
+
+/// Checks that the exit proofs and the errors are stored for the requests
+/// and the later requests replace them.
+#[db_test]
+async fn exit_proof_requests(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let (account_id, token_id) = (AccountId(1), TokenId(0));
+    assert!(ExitProofsSchema(&mut storage)
+        .load_pending_request()
+        .await?
+        .is_none());
+    assert!(
+        ExitProofsSchema(&mut storage)
+            .store_request(account_id, token_id, BlockNumber(2))
+            .await?
+    );
+    // The request for the earlier block doesn't replace the stored one.
+    assert!(
+        !ExitProofsSchema(&mut storage)
+            .store_request(account_id, token_id, BlockNumber(1))
+            .await?
+    );
+    let pending = ExitProofsSchema(&mut storage)
+        .load_pending_request()
+        .await?
+        .unwrap();
+    assert_eq!(
+        (pending.account_id, pending.token_id, pending.block_number),
+        (account_id, token_id, BlockNumber(2))
+    );
+    assert!(pending.proof_data.is_none());
+
+    // The proof for another block isn't stored.
+    assert!(
+        !ExitProofsSchema(&mut storage)
+            .store_proof(&exit_proof(BlockNumber(1)))
+            .await?
+    );
+    assert!(
+        ExitProofsSchema(&mut storage)
+            .store_proof(&exit_proof(BlockNumber(2)))
+            .await?
+    );
+    assert!(ExitProofsSchema(&mut storage)
+        .load_pending_request()
+        .await?
+        .is_none());
+    let request = ExitProofsSchema(&mut storage)
+        .load_request(account_id, token_id)
+        .await?
+        .unwrap();
+    assert_eq!(request.proof_data, Some(exit_proof(BlockNumber(2))));
+    assert!(request.generated_at.is_some());
+
+    // The request for the later block resets the proof.
+    assert!(
+        ExitProofsSchema(&mut storage)
+            .store_request(account_id, token_id, BlockNumber(3))
+            .await?
+    );
+    let request = ExitProofsSchema(&mut storage)
+        .load_request(account_id, token_id)
+        .await?
+        .unwrap();
+    assert_eq!(request.block_number, BlockNumber(3));
+    assert!(request.proof_data.is_none());
+
+    // The failed request isn't processed again.
+    ExitProofsSchema(&mut storage)
+        .store_error(account_id, token_id, BlockNumber(3), "Account not found")
+        .await?;
+    assert!(ExitProofsSchema(&mut storage)
+        .load_pending_request()
+        .await?
+        .is_none());
+    let request = ExitProofsSchema(&mut storage)
+        .load_request(account_id, token_id)
+        .await?
+        .unwrap();
+    assert_eq!(request.error.as_deref(), Some("Account not found"));
+
+    Ok(())
+}
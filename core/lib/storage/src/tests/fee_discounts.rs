@@ -0,0 +1,45 @@
+// Workspace imports
+use zksync_types::{Address, TokenId};
+// Local imports
+use crate::tests::db_test;
+use crate::{fee_discounts::FeeDiscountsSchema, QueryResult, StorageProcessor};
+
+/// Checks that the fee discounts are stored, loaded and removed.
+#[db_test]
+async fn stored_fee_discounts(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let address = Address::repeat_byte(0x11);
+    let token_discount = FeeDiscountsSchema(&mut storage)
+        .store_fee_discount(Some(TokenId(1)), None, None, 50, "token promo")
+        .await?;
+    let address_discount = FeeDiscountsSchema(&mut storage)
+        .store_fee_discount(None, Some("Transfer"), Some(address), 100, "partner")
+        .await?;
+    assert_eq!(token_discount.token_id, Some(1));
+    assert_eq!(token_discount.tx_type, None);
+    assert_eq!(token_discount.address, None);
+    assert_eq!(address_discount.tx_type.as_deref(), Some("Transfer"));
+    assert_eq!(address_discount.address, Some(address.as_bytes().to_vec()));
+    assert_eq!(address_discount.discount_percent, 100);
+
+    let discounts = FeeDiscountsSchema(&mut storage)
+        .load_fee_discounts()
+        .await?;
+    assert_eq!(discounts, vec![token_discount.clone(), address_discount]);
+
+    assert!(
+        FeeDiscountsSchema(&mut storage)
+            .remove_fee_discount(discounts[1].id)
+            .await?
+    );
+    assert!(
+        !FeeDiscountsSchema(&mut storage)
+            .remove_fee_discount(discounts[1].id)
+            .await?
+    );
+    let discounts = FeeDiscountsSchema(&mut storage)
+        .load_fee_discounts()
+        .await?;
+    assert_eq!(discounts, vec![token_discount]);
+
+    Ok(())
+}
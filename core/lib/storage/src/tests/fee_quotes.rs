@@ -0,0 +1,65 @@
+// External imports
+use chrono::{Duration, Utc};
+use sqlx::types::BigDecimal;
+// Workspace imports
+use zksync_types::{Address, TokenId};
+// Local imports
+use crate::tests::db_test;
+use crate::{fee_quotes::FeeQuotesSchema, QueryResult, StorageProcessor};
+
+/// Checks that the fee quotes are stored and loaded by the token and the transaction type.
+#[db_test]
+async fn stored_fee_quotes(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let address = Address::repeat_byte(0x11);
+    for (tx_type, token_id, total_fee) in &[
+        ("Transfer", 1, 10u32),
+        ("Withdraw", 1, 20),
+        ("Transfer", 2, 30),
+        ("Transfer", 1, 40),
+    ] {
+        FeeQuotesSchema(&mut storage)
+            .store_fee_quote(
+                tx_type,
+                TokenId(*token_id),
+                address,
+                BigDecimal::from(*total_fee - 1),
+                BigDecimal::from(1),
+                BigDecimal::from(*total_fee),
+            )
+            .await?;
+    }
+    let from = Utc::now() - Duration::hours(1);
+    let to = Utc::now() + Duration::hours(1);
+
+    let quotes = FeeQuotesSchema(&mut storage)
+        .load_fee_quotes(TokenId(1), None, from, to, 10)
+        .await?;
+    let total_fees: Vec<_> = quotes.iter().map(|quote| quote.total_fee.clone()).collect();
+    assert_eq!(
+        total_fees,
+        vec![
+            BigDecimal::from(10),
+            BigDecimal::from(20),
+            BigDecimal::from(40)
+        ]
+    );
+    assert_eq!(quotes[0].address, address.as_bytes().to_vec());
+    assert_eq!(quotes[0].gas_fee, BigDecimal::from(9));
+
+    let quotes = FeeQuotesSchema(&mut storage)
+        .load_fee_quotes(TokenId(1), Some("Transfer"), from, to, 10)
+        .await?;
+    assert_eq!(quotes.len(), 2);
+    // Only the latest quotes are loaded.
+    let quotes = FeeQuotesSchema(&mut storage)
+        .load_fee_quotes(TokenId(1), None, from, to, 2)
+        .await?;
+    assert_eq!(quotes[0].total_fee, BigDecimal::from(20));
+    assert_eq!(quotes.len(), 2);
+    let quotes = FeeQuotesSchema(&mut storage)
+        .load_fee_quotes(TokenId(1), None, to, to + Duration::hours(1), 10)
+        .await?;
+    assert!(quotes.is_empty());
+
+    Ok(())
+}
@@ -3,12 +3,13 @@ use std::{
     str::FromStr,
 };
 
-use crate::forced_exit_requests::ForcedExitRequestsSchema;
+use crate::forced_exit_requests::{records::ForcedExitAutomationStatus, ForcedExitRequestsSchema};
 use crate::tests::db_test;
 use crate::QueryResult;
 use crate::StorageProcessor;
 use chrono::{Duration, Timelike, Utc};
 use num::{BigUint, FromPrimitive};
+use sqlx::types::BigDecimal;
 use zksync_types::{
     forced_exit_requests::{ForcedExitRequest, SaveForcedExitRequestQuery},
     tx::TxHash,
@@ -201,3 +202,68 @@ async fn delete_old_requests(mut storage: StorageProcessor<'_>) -> QueryResult<(
 
     Ok(())
 }
+
+/// Checks that the requests of the automation are stored once per token and processed.
+#[db_test]
+async fn forced_exit_automation_requests(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let target = Address::from_low_u64_be(1);
+    let first = ForcedExitRequestsSchema(&mut storage)
+        .store_automation_request(target, TokenId(1), BigDecimal::from(10))
+        .await?;
+    assert_eq!(first.status, ForcedExitAutomationStatus::Pending.as_str());
+    // The pending request of the same token is updated rather than duplicated.
+    let updated = ForcedExitRequestsSchema(&mut storage)
+        .store_automation_request(target, TokenId(1), BigDecimal::from(20))
+        .await?;
+    assert_eq!(updated.id, first.id);
+    assert_eq!(updated.estimated_fee, BigDecimal::from(20));
+    let second = ForcedExitRequestsSchema(&mut storage)
+        .store_automation_request(target, TokenId(2), BigDecimal::from(10))
+        .await?;
+
+    let pending = ForcedExitRequestsSchema(&mut storage)
+        .load_pending_automation_requests(10)
+        .await?;
+    let pending_ids: Vec<_> = pending.iter().map(|request| request.id).collect();
+    assert_eq!(pending_ids, vec![first.id, second.id]);
+
+    let tx_hash = TxHash::from_str(
+        "sync-tx:796018689b3e323894f44fb0093856ec3832908c626dea357a9bd1b25f9d11bf",
+    )
+    .unwrap();
+    ForcedExitRequestsSchema(&mut storage)
+        .set_automation_request_submitted(first.id, tx_hash)
+        .await?;
+    ForcedExitRequestsSchema(&mut storage)
+        .set_automation_request_failed(second.id, "Target account has a signing key")
+        .await?;
+    assert!(ForcedExitRequestsSchema(&mut storage)
+        .load_pending_automation_requests(10)
+        .await?
+        .is_empty());
+
+    let submitted = ForcedExitRequestsSchema(&mut storage)
+        .get_automation_request(first.id)
+        .await?
+        .unwrap();
+    assert_eq!(
+        submitted.status,
+        ForcedExitAutomationStatus::Submitted.as_str()
+    );
+    assert_eq!(submitted.tx_hash, Some(tx_hash.as_ref().to_vec()));
+    assert!(submitted.processed_at.is_some());
+    let failed = ForcedExitRequestsSchema(&mut storage)
+        .get_automation_request(second.id)
+        .await?
+        .unwrap();
+    assert_eq!(failed.status, ForcedExitAutomationStatus::Failed.as_str());
+    assert!(failed.error.is_some());
+
+    // The token may be requested once again after the previous request is processed.
+    let next = ForcedExitRequestsSchema(&mut storage)
+        .store_automation_request(target, TokenId(1), BigDecimal::from(10))
+        .await?;
+    assert_ne!(next.id, first.id);
+
+    Ok(())
+}
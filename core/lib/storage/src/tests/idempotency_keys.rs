@@ -0,0 +1,108 @@
+// External imports
+use chrono::{Duration, Utc};
+use serde_json::json;
+// Workspace imports
+use zksync_types::AccountId;
+// Local imports
+use crate::tests::db_test;
+use crate::{idempotency_keys::IdempotencyKeysSchema, QueryResult, StorageProcessor};
+
+/// Checks that the keys are reserved by the accounts, and the reservations are replaced
+/// only after they expire.
+#[db_test]
+async fn stored_idempotent_responses(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    const ACCOUNT_ID: AccountId = AccountId(1);
+    let hour_ago = Utc::now() - Duration::hours(1);
+    let response = json!({ "txHash": "sync-tx:01" });
+
+    assert!(IdempotencyKeysSchema(&mut storage)
+        .load_response(ACCOUNT_ID, "key", hour_ago)
+        .await?
+        .is_none());
+    assert!(
+        IdempotencyKeysSchema(&mut storage)
+            .reserve_key(ACCOUNT_ID, "key", &[1], hour_ago)
+            .await?
+    );
+    // The key is reserved only once, while the request is being submitted.
+    assert!(
+        !IdempotencyKeysSchema(&mut storage)
+            .reserve_key(ACCOUNT_ID, "key", &[1], hour_ago)
+            .await?
+    );
+    let reserved = IdempotencyKeysSchema(&mut storage)
+        .load_response(ACCOUNT_ID, "key", hour_ago)
+        .await?
+        .unwrap();
+    assert_eq!(reserved.request_hash, vec![1]);
+    assert_eq!(reserved.response, None);
+
+    IdempotencyKeysSchema(&mut storage)
+        .store_response(ACCOUNT_ID, "key", response.clone())
+        .await?;
+    let stored = IdempotencyKeysSchema(&mut storage)
+        .load_response(ACCOUNT_ID, "key", hour_ago)
+        .await?
+        .unwrap();
+    assert_eq!(stored.response, Some(response));
+    // The stored response is not released.
+    IdempotencyKeysSchema(&mut storage)
+        .release_key(ACCOUNT_ID, "key")
+        .await?;
+    assert!(IdempotencyKeysSchema(&mut storage)
+        .load_response(ACCOUNT_ID, "key", hour_ago)
+        .await?
+        .is_some());
+
+    // The same key of another account is independent.
+    assert!(IdempotencyKeysSchema(&mut storage)
+        .load_response(AccountId(2), "key", hour_ago)
+        .await?
+        .is_none());
+    assert!(
+        IdempotencyKeysSchema(&mut storage)
+            .reserve_key(AccountId(2), "key", &[3], hour_ago)
+            .await?
+    );
+    // The reservation without the response is released.
+    IdempotencyKeysSchema(&mut storage)
+        .release_key(AccountId(2), "key")
+        .await?;
+    assert!(IdempotencyKeysSchema(&mut storage)
+        .load_response(AccountId(2), "key", hour_ago)
+        .await?
+        .is_none());
+
+    // The expired reservation is neither loaded nor kept.
+    let future = Utc::now() + Duration::hours(1);
+    assert!(IdempotencyKeysSchema(&mut storage)
+        .load_response(ACCOUNT_ID, "key", future)
+        .await?
+        .is_none());
+    assert!(
+        IdempotencyKeysSchema(&mut storage)
+            .reserve_key(ACCOUNT_ID, "key", &[2], future)
+            .await?
+    );
+    let reserved = IdempotencyKeysSchema(&mut storage)
+        .load_response(ACCOUNT_ID, "key", hour_ago)
+        .await?
+        .unwrap();
+    assert_eq!(reserved.request_hash, vec![2]);
+    assert_eq!(reserved.response, None);
+
+    assert_eq!(
+        IdempotencyKeysSchema(&mut storage)
+            .remove_responses_before(hour_ago)
+            .await?,
+        0
+    );
+    assert_eq!(
+        IdempotencyKeysSchema(&mut storage)
+            .remove_responses_before(future)
+            .await?,
+        1
+    );
+
+    Ok(())
+}
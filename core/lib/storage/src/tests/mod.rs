@@ -23,15 +23,24 @@ use tokio::sync::Mutex;
 // Workspace imports
 use zksync_crypto::rand::{SeedableRng, XorShiftRng};
 
+mod api_keys;
 pub(crate) mod chain;
 mod config;
 mod data_restore;
 mod ethereum;
 mod event;
+mod exit_proofs;
+mod fee_discounts;
+mod fee_quotes;
 mod forced_exit_requests;
+mod idempotency_keys;
 mod misc;
+mod network_stats;
 mod prover;
+mod pruning;
+mod read_only_mode;
 mod tokens;
+mod webhooks;
 
 pub use db_test_macro::test as db_test;
 
@@ -0,0 +1,79 @@
+// External imports
+use chrono::{Duration, Utc};
+use sqlx::types::BigDecimal;
+// Workspace imports
+// Local imports
+use crate::tests::{
+    chain::operations_ext::{commit_schema_data, setup::TransactionsHistoryTestSetup},
+    db_test,
+};
+use crate::{
+    network_stats::{
+        records::{StoredNetworkStats, StoredTokenStats},
+        NetworkStatsSchema,
+    },
+    QueryResult, StorageProcessor,
+};
+
+async fn update_and_load(
+    storage: &mut StorageProcessor<'_>,
+) -> QueryResult<(StoredNetworkStats, Vec<StoredTokenStats>)> {
+    let since = Utc::now() - Duration::hours(24);
+    NetworkStatsSchema(storage)
+        .update_network_stats(since)
+        .await?;
+    Ok(NetworkStatsSchema(storage)
+        .load_network_stats()
+        .await?
+        .expect("The stats are stored"))
+}
+
+fn volume(tokens: &[StoredTokenStats], token_id: i32) -> BigDecimal {
+    tokens
+        .iter()
+        .find(|token| token.token_id == token_id)
+        .map(|token| token.volume_24h.clone())
+        .unwrap_or_default()
+}
+
+/// Checks that the stats are aggregated over the executed transactions and replaced by the updates.
+#[db_test]
+async fn network_stats(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let (stats_before, tokens_before) = update_and_load(&mut storage).await?;
+
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    commit_schema_data(&mut storage, &setup).await?;
+
+    let (stats, tokens) = update_and_load(&mut storage).await?;
+    // All the transactions of the block are successful and initiated by one account.
+    assert_eq!(stats.tx_count_24h, stats_before.tx_count_24h + 10);
+    assert_eq!(
+        stats.active_accounts_24h,
+        stats_before.active_accounts_24h + 1
+    );
+    // The deposit, the transfers and the withdrawal of the amount of `1`.
+    for (token_id, volume_delta) in vec![(0, 1u32), (1, 2), (2, 1)] {
+        assert_eq!(
+            volume(&tokens, token_id),
+            volume(&tokens_before, token_id) + BigDecimal::from(volume_delta)
+        );
+    }
+    // The NFTs aren't included.
+    assert!(tokens
+        .iter()
+        .all(|token| setup.tokens[3].id.0 as i32 != token.token_id));
+
+    // The transactions are too old for the stats since now.
+    NetworkStatsSchema(&mut storage)
+        .update_network_stats(Utc::now() + Duration::hours(1))
+        .await?;
+    let (stats, _) = NetworkStatsSchema(&mut storage)
+        .load_network_stats()
+        .await?
+        .unwrap();
+    assert_eq!(stats.tx_count_24h, 0);
+    assert_eq!(stats.active_accounts_24h, 0);
+
+    Ok(())
+}
@@ -1,11 +1,14 @@
+// Built-in imports
+use std::time::Duration;
 // External imports
 use anyhow::format_err;
 use once_cell::sync::Lazy;
 use tokio::sync::Mutex;
 // Workspace imports
 use zksync_types::{
+    aggregated_operations::AggregatedActionType,
     prover::{ProverJob, ProverJobType},
-    BlockNumber,
+    BlockNumber, H256,
 };
 // Local imports
 use crate::test_data::{gen_sample_block, get_sample_aggregated_proof, get_sample_single_proof};
@@ -14,9 +17,11 @@ use crate::{prover::ProverSchema, QueryResult, StorageProcessor};
 
 static MUTEX: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
 
+const LEASE_DURATION: Duration = Duration::from_secs(60);
+
 async fn get_idle_job_from_queue(storage: &mut StorageProcessor<'_>) -> QueryResult<ProverJob> {
     let job = ProverSchema(storage)
-        .get_idle_prover_job_from_job_queue()
+        .lease_prover_job("test_prover", LEASE_DURATION)
         .await?;
 
     job.ok_or_else(|| format_err!("expect idle job from job queue"))
@@ -35,6 +40,114 @@ async fn test_prover_job_queue(mut storage: StorageProcessor<'_>) -> QueryResult
     Ok(())
 }
 
+/// Checks that the expired leases of the prover jobs are re-assigned to the other provers.
+#[db_test]
+async fn test_prover_job_leases(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let _lock = MUTEX.lock().await;
+
+    ProverSchema(&mut storage)
+        .add_prover_job_to_job_queue(
+            BlockNumber(1),
+            BlockNumber(1),
+            Default::default(),
+            1,
+            ProverJobType::SingleProof,
+        )
+        .await?;
+
+    // The lease with zero duration expires at once, since `now()` is fixed within the test transaction.
+    let job = ProverSchema(&mut storage)
+        .lease_prover_job("first_prover", Duration::from_secs(0))
+        .await?
+        .expect("expect idle job from job queue");
+    assert!(ProverSchema(&mut storage)
+        .lease_prover_job("second_prover", LEASE_DURATION)
+        .await?
+        .is_none());
+    // Only the prover holding the lease can renew it.
+    assert!(
+        !ProverSchema(&mut storage)
+            .renew_prover_job_lease(job.job_id, "second_prover", LEASE_DURATION)
+            .await?
+    );
+
+    ProverSchema(&mut storage)
+        .release_expired_prover_job_leases()
+        .await?;
+    let reassigned_job = ProverSchema(&mut storage)
+        .lease_prover_job("second_prover", LEASE_DURATION)
+        .await?
+        .expect("expect the expired job to be re-assigned");
+    assert_eq!(reassigned_job.job_id, job.job_id);
+    assert!(
+        !ProverSchema(&mut storage)
+            .renew_prover_job_lease(job.job_id, "first_prover", LEASE_DURATION)
+            .await?
+    );
+    assert!(
+        ProverSchema(&mut storage)
+            .renew_prover_job_lease(job.job_id, "second_prover", LEASE_DURATION)
+            .await?
+    );
+
+    // The renewed lease doesn't expire.
+    ProverSchema(&mut storage)
+        .release_expired_prover_job_leases()
+        .await?;
+    assert!(ProverSchema(&mut storage)
+        .lease_prover_job("first_prover", LEASE_DURATION)
+        .await?
+        .is_none());
+
+    Ok(())
+}
+
+/// Checks that the priorities set for the blocks affect the order of the prover jobs.
+#[db_test]
+async fn test_block_job_priority(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let _lock = MUTEX.lock().await;
+
+    for block in 1..=2 {
+        ProverSchema(&mut storage)
+            .add_prover_job_to_job_queue(
+                BlockNumber(block),
+                BlockNumber(block),
+                Default::default(),
+                1,
+                ProverJobType::SingleProof,
+            )
+            .await?;
+    }
+    // The priority applies both to the existing jobs and to the ones created later.
+    ProverSchema(&mut storage)
+        .set_block_job_priority(BlockNumber(2), 0)
+        .await?;
+    ProverSchema(&mut storage)
+        .set_block_job_priority(BlockNumber(3), -1)
+        .await?;
+    ProverSchema(&mut storage)
+        .add_prover_job_to_job_queue(
+            BlockNumber(3),
+            BlockNumber(3),
+            Default::default(),
+            1,
+            ProverJobType::SingleProof,
+        )
+        .await?;
+
+    let leased_blocks = vec![
+        get_idle_job_from_queue(&mut storage).await?.first_block,
+        get_idle_job_from_queue(&mut storage).await?.first_block,
+        get_idle_job_from_queue(&mut storage).await?.first_block,
+    ];
+    assert_eq!(
+        leased_blocks,
+        vec![BlockNumber(3), BlockNumber(2), BlockNumber(1)]
+    );
+
+    Ok(())
+}
+
 /// Checks that the single and aggregated proof can be stored and loaded.
 async fn test_store_proof(storage: &mut StorageProcessor<'_>) -> QueryResult<()> {
     // Attempt to load the proof that was not stored should result in None.
@@ -182,15 +295,21 @@ async fn pending_jobs_count(storage: &mut StorageProcessor<'_>) -> QueryResult<(
     assert_eq!(jobs_count, 3);
 
     // Record prover is working and stopped it.
-    ProverSchema(storage)
-        .record_prover_is_working(first_job.job_id, "test_prover")
-        .await?;
-    ProverSchema(storage)
-        .record_prover_is_working(second_job.job_id, "test_prover")
-        .await?;
-    ProverSchema(storage)
-        .record_prover_is_working(third_job.job_id, "test_prover")
-        .await?;
+    assert!(
+        ProverSchema(storage)
+            .renew_prover_job_lease(first_job.job_id, "test_prover", LEASE_DURATION)
+            .await?
+    );
+    assert!(
+        ProverSchema(storage)
+            .renew_prover_job_lease(second_job.job_id, "test_prover", LEASE_DURATION)
+            .await?
+    );
+    assert!(
+        ProverSchema(storage)
+            .renew_prover_job_lease(third_job.job_id, "test_prover", LEASE_DURATION)
+            .await?
+    );
 
     // Store one proof and then turn off the prover.
     ProverSchema(storage)
@@ -213,6 +332,168 @@ async fn pending_jobs_count(storage: &mut StorageProcessor<'_>) -> QueryResult<(
     Ok(())
 }
 
+/// Checks that the connected provers, their jobs and the queue depth are reported.
+#[db_test]
+async fn test_prover_fleet_status(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let _lock = MUTEX.lock().await;
+
+    storage
+        .chain()
+        .block_schema()
+        .save_full_block(gen_sample_block(BlockNumber(1), 10, Default::default()))
+        .await?;
+    for block in 1..=2 {
+        ProverSchema(&mut storage)
+            .add_prover_job_to_job_queue(
+                BlockNumber(block),
+                BlockNumber(block),
+                Default::default(),
+                1,
+                ProverJobType::SingleProof,
+            )
+            .await?;
+    }
+    for prover in &["first_prover", "second_prover"] {
+        ProverSchema(&mut storage)
+            .record_prover_heartbeat(prover)
+            .await?;
+    }
+    let job = ProverSchema(&mut storage)
+        .lease_prover_job("first_prover", LEASE_DURATION)
+        .await?
+        .expect("expect idle job from job queue");
+
+    let provers = ProverSchema(&mut storage)
+        .load_connected_provers(LEASE_DURATION)
+        .await?;
+    assert_eq!(provers.len(), 2);
+    assert_eq!(provers[0].prover_name, "first_prover");
+    assert_eq!(provers[0].job_id, Some(job.job_id));
+    assert_eq!(provers[0].first_block, Some(1));
+    assert_eq!(provers[1].prover_name, "second_prover");
+    assert_eq!(provers[1].job_id, None);
+
+    let queue_depth = ProverSchema(&mut storage).load_job_queue_depth().await?;
+    assert_eq!(queue_depth.len(), 1);
+    assert_eq!(
+        queue_depth[0].job_type,
+        ProverJobType::SingleProof.to_string()
+    );
+    assert_eq!(queue_depth[0].idle_jobs, 1);
+    assert_eq!(queue_depth[0].in_progress_jobs, 1);
+
+    // The proving time is measured since the lease of the job.
+    ProverSchema(&mut storage)
+        .store_proof(job.job_id, job.first_block, &get_sample_single_proof())
+        .await?;
+    let proving_times = ProverSchema(&mut storage)
+        .load_proving_times(Duration::from_secs(3600))
+        .await?;
+    assert_eq!(proving_times.len(), 1);
+    assert_eq!(proving_times[0].block_size, 10);
+    assert_eq!(proving_times[0].proofs_count, 1);
+
+    // The stopped prover isn't reported anymore.
+    ProverSchema(&mut storage)
+        .record_prover_stop("second_prover")
+        .await?;
+    let provers = ProverSchema(&mut storage)
+        .load_connected_provers(LEASE_DURATION)
+        .await?;
+    assert_eq!(provers.len(), 1);
+    assert_eq!(provers[0].job_id, None);
+
+    Ok(())
+}
+
+/// Checks that the proving times and the L1 costs of the blocks are recorded and aggregated.
+#[db_test]
+async fn test_costs_stats(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let _lock = MUTEX.lock().await;
+    const PERIOD: Duration = Duration::from_secs(3600);
+
+    for block in 1..=2 {
+        storage
+            .chain()
+            .block_schema()
+            .save_full_block(gen_sample_block(BlockNumber(block), 10, Default::default()))
+            .await?;
+        ProverSchema(&mut storage)
+            .add_prover_job_to_job_queue(
+                BlockNumber(block),
+                BlockNumber(block),
+                Default::default(),
+                1,
+                ProverJobType::SingleProof,
+            )
+            .await?;
+    }
+
+    // Only the proofs of the leased jobs have the measured proving time.
+    let job = get_idle_job_from_queue(&mut storage).await?;
+    ProverSchema(&mut storage)
+        .store_proof(job.job_id, job.first_block, &get_sample_single_proof())
+        .await?;
+    let proof_times = ProverSchema(&mut storage)
+        .load_proof_generation_times(PERIOD)
+        .await?;
+    assert_eq!(proof_times.len(), 1);
+    assert_eq!(
+        proof_times[0].job_type,
+        ProverJobType::SingleProof.to_string()
+    );
+    assert_eq!(proof_times[0].block_size, 10);
+    assert_eq!(proof_times[0].blocks_count, 1);
+    assert_eq!(proof_times[0].proofs_count, 1);
+
+    // The gas of the operation is amortized over its blocks.
+    let action_type = AggregatedActionType::PublishProofBlocksOnchain;
+    storage
+        .ethereum_schema()
+        .store_block_verification_costs(
+            BlockNumber(1),
+            BlockNumber(2),
+            action_type,
+            &H256::repeat_byte(1),
+            1_000,
+        )
+        .await?;
+    let costs = storage
+        .ethereum_schema()
+        .load_block_verification_costs(PERIOD)
+        .await?;
+    assert_eq!(costs.len(), 1);
+    assert_eq!(costs[0].action_type, action_type.to_string());
+    assert_eq!(costs[0].block_size, 10);
+    assert_eq!(costs[0].operation_blocks_count, 2);
+    assert_eq!(costs[0].blocks_count, 2);
+    assert_eq!(costs[0].average_gas_per_block, 500.0);
+    assert_eq!(costs[0].max_gas_per_block, 500);
+
+    // The costs of the block confirmed once again are overwritten.
+    storage
+        .ethereum_schema()
+        .store_block_verification_costs(
+            BlockNumber(2),
+            BlockNumber(2),
+            action_type,
+            &H256::repeat_byte(2),
+            800,
+        )
+        .await?;
+    let costs = storage
+        .ethereum_schema()
+        .load_block_verification_costs(PERIOD)
+        .await?;
+    assert_eq!(costs.len(), 2);
+    assert_eq!(costs[0].operation_blocks_count, 1);
+    assert_eq!(costs[0].max_gas_per_block, 800);
+    assert_eq!(costs[1].operation_blocks_count, 2);
+    assert_eq!(costs[1].blocks_count, 1);
+
+    Ok(())
+}
+
 /// Checks that the witness can be stored and loaded.
 #[db_test]
 async fn test_store_witness(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -312,6 +593,60 @@ async fn test_remove_witnesses(mut storage: StorageProcessor<'_>) -> QueryResult
     Ok(())
 }
 
+/// Checks that the progress of the witness generator shards is stored and reverted along with the witnesses.
+#[db_test]
+async fn witness_generator_progress(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    assert_eq!(
+        storage
+            .prover_schema()
+            .load_witness_generator_progress(0)
+            .await?,
+        None
+    );
+
+    storage
+        .prover_schema()
+        .store_witness_generator_progress(0, BlockNumber(5))
+        .await?;
+    storage
+        .prover_schema()
+        .store_witness_generator_progress(1, BlockNumber(2))
+        .await?;
+    storage
+        .prover_schema()
+        .store_witness_generator_progress(0, BlockNumber(7))
+        .await?;
+    assert_eq!(
+        storage
+            .prover_schema()
+            .load_witness_generator_progress(0)
+            .await?,
+        Some(BlockNumber(7))
+    );
+
+    // Only the progress beyond the last kept witness is reverted.
+    storage
+        .prover_schema()
+        .remove_witnesses(BlockNumber(3))
+        .await?;
+    assert_eq!(
+        storage
+            .prover_schema()
+            .load_witness_generator_progress(0)
+            .await?,
+        Some(BlockNumber(3))
+    );
+    assert_eq!(
+        storage
+            .prover_schema()
+            .load_witness_generator_progress(1)
+            .await?,
+        Some(BlockNumber(2))
+    );
+
+    Ok(())
+}
+
 /// Checks that block proofs are removed correctly.
 #[db_test]
 async fn test_remove_proofs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -0,0 +1,208 @@
+// External imports
+use num::BigUint;
+// Workspace imports
+use zksync_types::{
+    event::{account::AccountStateChangeStatus, EventId},
+    AccountId, AccountUpdate, Address, BlockNumber, Nonce, TokenId,
+};
+// Local imports
+use crate::tests::{db_test, ACCOUNT_MUTEX};
+use crate::{pruning::PruningSchema, QueryResult, StorageProcessor};
+
+fn balance_update(nonce: u32, old_balance: u32, new_balance: u32) -> (AccountId, AccountUpdate) {
+    (
+        AccountId(1),
+        AccountUpdate::UpdateBalance {
+            old_nonce: Nonce(nonce),
+            new_nonce: Nonce(nonce + 1),
+            balance_update: (
+                TokenId(0),
+                BigUint::from(old_balance),
+                BigUint::from(new_balance),
+            ),
+        },
+    )
+}
+
+async fn balance(
+    storage: &mut StorageProcessor<'_>,
+    address: Address,
+    block: u32,
+) -> QueryResult<BigUint> {
+    storage
+        .chain()
+        .account_schema()
+        .get_account_balance_for_block(address, BlockNumber(block), TokenId(0))
+        .await
+}
+
+/// Commits the blocks `1..=3` updating the balance of the account with the address,
+/// the balance is `100`, `300` and `400` after them.
+async fn commit_balance_updates(
+    storage: &mut StorageProcessor<'_>,
+    address: Address,
+) -> QueryResult<()> {
+    let updates = vec![
+        vec![
+            (
+                AccountId(1),
+                AccountUpdate::Create {
+                    address,
+                    nonce: Nonce(0),
+                },
+            ),
+            balance_update(0, 0, 100),
+        ],
+        vec![balance_update(1, 100, 200), balance_update(2, 200, 300)],
+        vec![balance_update(3, 300, 400)],
+    ];
+    for (block, updates) in (1..).zip(&updates) {
+        storage
+            .chain()
+            .state_schema()
+            .commit_state_update(BlockNumber(block), updates, 0)
+            .await?;
+        storage
+            .event_schema()
+            .store_state_updated_event(BlockNumber(block), AccountStateChangeStatus::Committed)
+            .await?;
+    }
+    Ok(())
+}
+
+/// Returns the ids and the blocks of the stored events.
+async fn events(storage: &mut StorageProcessor<'_>) -> QueryResult<Vec<(EventId, u32)>> {
+    let events = storage
+        .event_schema()
+        .fetch_new_events(EventId(0))
+        .await?
+        .into_iter()
+        .map(|event| (EventId(event.id as u64), event.block_number as u32))
+        .collect();
+    Ok(events)
+}
+
+/// Checks that the superseded balance updates are pruned, while the latest balance is kept.
+#[db_test]
+async fn prune_balance_updates(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let _lock = ACCOUNT_MUTEX.lock().await;
+    let address = Address::random();
+    commit_balance_updates(&mut storage, address).await?;
+    assert_eq!(
+        PruningSchema(&mut storage).load_last_pruned_block().await?,
+        BlockNumber(0)
+    );
+    let pruned = PruningSchema(&mut storage)
+        .prune_blocks(BlockNumber(2))
+        .await?;
+    assert_eq!(pruned.balance_updates, 2);
+    assert_eq!(pruned.last_pruned_block, BlockNumber(2));
+    assert_eq!(
+        PruningSchema(&mut storage).load_last_pruned_block().await?,
+        BlockNumber(2)
+    );
+    // The latest balance of the pruned blocks is kept.
+    assert_eq!(
+        balance(&mut storage, address, 2).await?,
+        BigUint::from(300u32)
+    );
+    assert_eq!(
+        balance(&mut storage, address, 3).await?,
+        BigUint::from(400u32)
+    );
+
+    // The already pruned blocks are skipped.
+    let pruned = PruningSchema(&mut storage)
+        .prune_blocks(BlockNumber(1))
+        .await?;
+    assert_eq!(pruned.total(), 0);
+
+    // The update kept by the previous pruning is deleted once it's superseded.
+    let pruned = PruningSchema(&mut storage)
+        .prune_blocks(BlockNumber(3))
+        .await?;
+    assert_eq!(pruned.balance_updates, 1);
+    assert_eq!(
+        balance(&mut storage, address, 3).await?,
+        BigUint::from(400u32)
+    );
+
+    Ok(())
+}
+
+/// Checks that the events are pruned only once they are consumed by all the event consumers,
+/// and the blocks with the unconsumed events are kept.
+#[db_test]
+async fn prune_consumed_events(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let _lock = ACCOUNT_MUTEX.lock().await;
+    let address = Address::random();
+    commit_balance_updates(&mut storage, address).await?;
+    let stored_events = events(&mut storage).await?;
+    let last_event_of_block = |block| {
+        stored_events
+            .iter()
+            .filter(|(_, event_block)| *event_block == block)
+            .map(|(id, _)| *id)
+            .max()
+            .unwrap()
+    };
+
+    // The listener has consumed the events of the first block only.
+    storage
+        .event_schema()
+        .store_consumer_progress("event_listener", last_event_of_block(1))
+        .await?;
+    // Another consumer is ahead of it.
+    storage
+        .event_schema()
+        .store_consumer_progress("other_consumer", last_event_of_block(3))
+        .await?;
+    let pruned = PruningSchema(&mut storage)
+        .prune_blocks(BlockNumber(3))
+        .await?;
+    assert_eq!(pruned.last_pruned_block, BlockNumber(1));
+    assert!(pruned.events > 0);
+    assert_eq!(
+        PruningSchema(&mut storage).load_last_pruned_block().await?,
+        BlockNumber(1)
+    );
+    // The unconsumed events and the balance updates of their blocks are kept.
+    let kept_events = events(&mut storage).await?;
+    assert!(kept_events.iter().all(|(_, block)| *block > 1));
+    assert_eq!(
+        kept_events,
+        stored_events
+            .iter()
+            .copied()
+            .filter(|(_, block)| *block > 1)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        balance(&mut storage, address, 2).await?,
+        BigUint::from(300u32)
+    );
+
+    // Nothing is pruned until the listener consumes more events.
+    let pruned = PruningSchema(&mut storage)
+        .prune_blocks(BlockNumber(3))
+        .await?;
+    assert_eq!(pruned.total(), 0);
+    assert_eq!(pruned.last_pruned_block, BlockNumber(1));
+
+    storage
+        .event_schema()
+        .store_consumer_progress("event_listener", last_event_of_block(3))
+        .await?;
+    let pruned = PruningSchema(&mut storage)
+        .prune_blocks(BlockNumber(3))
+        .await?;
+    assert_eq!(pruned.last_pruned_block, BlockNumber(3));
+    assert_eq!(pruned.events as usize, kept_events.len());
+    assert!(events(&mut storage).await?.is_empty());
+    assert_eq!(
+        balance(&mut storage, address, 3).await?,
+        BigUint::from(400u32)
+    );
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+// External imports
+// Workspace imports
+// Local imports
+use crate::tests::db_test;
+use crate::{read_only_mode::ReadOnlyModeSchema, QueryResult, StorageProcessor};
+
+/// Checks that the read-only mode is enabled and disabled.
+#[db_test]
+async fn read_only_mode(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    assert!(ReadOnlyModeSchema(&mut storage)
+        .load_read_only_mode()
+        .await?
+        .is_none());
+
+    let mode = ReadOnlyModeSchema(&mut storage)
+        .set_read_only_mode(true, Some("Migration"))
+        .await?;
+    assert!(mode.enabled);
+    assert_eq!(
+        ReadOnlyModeSchema(&mut storage)
+            .load_read_only_mode()
+            .await?,
+        Some(mode)
+    );
+
+    // The mode is replaced rather than added.
+    ReadOnlyModeSchema(&mut storage)
+        .set_read_only_mode(false, None)
+        .await?;
+    let mode = ReadOnlyModeSchema(&mut storage)
+        .load_read_only_mode()
+        .await?
+        .unwrap();
+    assert!(!mode.enabled);
+    assert_eq!(mode.reason, None);
+
+    Ok(())
+}
@@ -1,9 +1,17 @@
 // Built-in imports
+use std::collections::HashSet;
 use std::str::FromStr;
 // External imports
-use chrono::Utc;
+use chrono::{TimeZone, Utc};
 use num::{rational::Ratio, BigUint};
 // Workspace imports
+use zksync_api_types::{
+    v02::{
+        pagination::{ApiEither, Latest, PaginationDirection, PaginationQuery, TokensRequest},
+        token::{TokenFilter, TokenMetadata, TokenSortOrder},
+    },
+    Either,
+};
 use zksync_test_account::ZkSyncAccount;
 use zksync_types::{
     tokens::TokenMarketVolume, AccountId, Address, BlockNumber, ExecutedOperations, ExecutedTx,
@@ -15,19 +23,21 @@ use crate::tests::db_test;
 use crate::{
     chain::account::records::StorageMintNFTUpdate,
     diff::StorageAccountDiff,
-    tokens::{TokensSchema, STORED_USD_PRICE_PRECISION},
+    tokens::{
+        memory::MemoryTokensStorage, records::StoredPriceProviderHealth, TokensSchema,
+        TokensStorage, STORED_USD_PRICE_PRECISION,
+    },
     QueryResult, StorageProcessor,
 };
 use zksync_crypto::params::MIN_NFT_TOKEN_ID;
 
 /// Verifies the token save & load mechanism.
-#[db_test]
-async fn tokens_storage(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+async fn tokens_storage(storage: &mut impl TokensStorage) -> QueryResult<()> {
     // There should be only Ethereum main token by default.
-    assert_eq!(storage.tokens_schema().get_count().await?, 1);
-    assert_eq!(storage.tokens_schema().get_max_token_id().await?, 0);
-    assert_eq!(storage.tokens_schema().get_max_erc20_token_id().await?, 0);
-    let tokens = TokensSchema(&mut storage)
+    assert_eq!(storage.get_count().await?, 1);
+    assert_eq!(storage.get_max_token_id().await?, 0);
+    assert_eq!(storage.get_max_erc20_token_id().await?, 0);
+    let tokens = storage
         .load_tokens()
         .await
         .expect("Load tokens query failed");
@@ -68,26 +78,26 @@ async fn tokens_storage(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
         is_nft: true,
     };
 
-    TokensSchema(&mut storage)
+    storage
         .store_or_update_token(nft.clone())
         .await
         .expect("Store tokens query failed");
 
-    TokensSchema(&mut storage)
+    storage
         .store_or_update_token(token_a.clone())
         .await
         .expect("Store tokens query failed");
-    TokensSchema(&mut storage)
+    storage
         .store_or_update_token(token_b.clone())
         .await
         .expect("Store tokens query failed");
     // The count is updated.
-    assert_eq!(storage.tokens_schema().get_count().await?, 2);
-    assert_eq!(storage.tokens_schema().get_max_token_id().await?, 2);
-    assert_eq!(storage.tokens_schema().get_max_erc20_token_id().await?, 1);
+    assert_eq!(storage.get_count().await?, 2);
+    assert_eq!(storage.get_max_token_id().await?, 2);
+    assert_eq!(storage.get_max_erc20_token_id().await?, 1);
 
     // Load tokens again.
-    let tokens = TokensSchema(&mut storage)
+    let tokens = storage
         .load_tokens()
         .await
         .expect("Load tokens query failed");
@@ -97,21 +107,21 @@ async fn tokens_storage(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     assert_eq!(tokens[&eth_token.id], eth_token);
     assert_eq!(tokens[&token_a.id], token_a);
 
-    let token_b_by_id = TokensSchema(&mut storage)
+    let token_b_by_id = storage
         .get_token(TokenLike::Id(token_b.id))
         .await
         .expect("get token query failed")
         .expect("token by id not found");
     assert_eq!(token_b, token_b_by_id);
 
-    let token_b_by_address = TokensSchema(&mut storage)
+    let token_b_by_address = storage
         .get_token(TokenLike::Address(token_b.address))
         .await
         .expect("get token query failed")
         .expect("token by address not found");
     assert_eq!(token_b, token_b_by_address);
 
-    let token_b_by_symbol = TokensSchema(&mut storage)
+    let token_b_by_symbol = storage
         .get_token(TokenLike::Symbol(token_b.symbol.clone()))
         .await
         .expect("get token query failed")
@@ -119,22 +129,47 @@ async fn tokens_storage(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     assert_eq!(token_b, token_b_by_symbol);
 
     // Try case-insensitive search
-    let token_b_by_symbol_case_insensitive = TokensSchema(&mut storage)
+    let token_b_by_symbol_case_insensitive = storage
         .get_token(TokenLike::Symbol(token_b.symbol.to_lowercase()))
         .await
         .expect("get token query failed")
         .expect("token by symbol not found");
     assert_eq!(token_b, token_b_by_symbol_case_insensitive);
 
-    let db_nft_token = TokensSchema(&mut storage)
+    let db_nft_token = storage
         .get_token(TokenLike::Id(nft.id))
         .await
         .expect("Get nft failed")
         .expect("Token not found");
     assert_eq!(db_nft_token, nft);
+
+    // The tokens requested in the different forms are loaded at once,
+    // the unknown ones are skipped.
+    let mut tokens = storage
+        .get_tokens(&[
+            TokenLike::Id(token_a.id),
+            TokenLike::Address(token_b.address),
+            TokenLike::Symbol(nft.symbol.to_lowercase()),
+            TokenLike::Symbol("UNKNOWN".into()),
+            TokenLike::Id(TokenId(3)),
+        ])
+        .await
+        .expect("get tokens query failed");
+    tokens.sort_by_key(|token| token.id);
+    assert_eq!(tokens, vec![token_a, token_b, nft]);
     Ok(())
 }
 
+#[db_test]
+async fn test_tokens_storage(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    tokens_storage(&mut storage).await
+}
+
+#[tokio::test]
+async fn test_tokens_storage_in_memory() -> QueryResult<()> {
+    tokens_storage(&mut MemoryTokensStorage::new()).await
+}
+
 /// Checks the store/load routine for `ticker_price` table.
 #[db_test]
 async fn test_ticker_price(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -176,12 +211,130 @@ async fn test_ticker_price(mut storage: StorageProcessor<'_>) -> QueryResult<()>
         price.last_updated.timestamp()
     );
 
+    // The tokens without the price are skipped by the batch load.
+    let prices = storage
+        .tokens_schema()
+        .load_historical_ticker_prices(&[TOKEN_ID, TokenId(1)])
+        .await?;
+    assert_eq!(prices.len(), 1);
+    assert_eq!(prices[&TOKEN_ID].usd_price, expected_price);
+
     Ok(())
 }
 
-/// Checks the store/load routine for `ticker_market_volume` table and load tokens by market volume.
+/// Checks the store/load routine for `price_provider_health` table.
 #[db_test]
-async fn test_market_volume(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+async fn test_price_providers_health(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let updated_at = Utc.timestamp(1_600_000_000, 0);
+    let provider = |name: &str, priority: i32| StoredPriceProviderHealth {
+        provider: name.to_string(),
+        priority,
+        last_success_at: Some(updated_at),
+        last_failure_at: None,
+        last_error: None,
+        consecutive_failures: 0,
+        updated_at,
+    };
+    let failed_provider = StoredPriceProviderHealth {
+        last_failure_at: Some(updated_at),
+        last_error: Some("API request failed".to_string()),
+        consecutive_failures: 3,
+        ..provider("CoinMarketCap", 0)
+    };
+
+    storage
+        .tokens_schema()
+        .store_price_providers_health(&[provider("CoinGecko", 1), failed_provider.clone()])
+        .await?;
+    let loaded = storage
+        .tokens_schema()
+        .load_price_providers_health()
+        .await?;
+    assert_eq!(loaded, vec![failed_provider, provider("CoinGecko", 1)]);
+
+    // The stored health is replaced.
+    storage
+        .tokens_schema()
+        .store_price_providers_health(&[provider("UniswapV3", 0)])
+        .await?;
+    let loaded = storage
+        .tokens_schema()
+        .load_price_providers_health()
+        .await?;
+    assert_eq!(loaded, vec![provider("UniswapV3", 0)]);
+
+    Ok(())
+}
+
+/// Checks the store/load routine for `ticker_price_history` table.
+#[db_test]
+async fn test_ticker_price_history(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    const TOKEN_ID: TokenId = TokenId(0);
+
+    let start = Utc.timestamp(1_600_000_000, 0);
+    // Two snapshots fall into the first hour, and one into the second.
+    for (offset_secs, usd_price) in &[(0, 1u32), (1800, 3), (3600, 10)] {
+        let price = TokenPrice {
+            usd_price: Ratio::from_integer(BigUint::from(*usd_price)),
+            last_updated: start + chrono::Duration::seconds(*offset_secs),
+        };
+        storage
+            .tokens_schema()
+            .store_ticker_price_snapshot(TOKEN_ID, price)
+            .await?;
+    }
+
+    let end = start + chrono::Duration::hours(2);
+    let history = storage
+        .tokens_schema()
+        .load_ticker_price_history(TOKEN_ID, start, end, 3600)
+        .await?;
+    let history: Vec<_> = history
+        .into_iter()
+        .map(|price| (price.last_updated.timestamp(), price.usd_price))
+        .collect();
+    assert_eq!(
+        history,
+        vec![
+            (start.timestamp(), Ratio::from_integer(BigUint::from(2u32))),
+            (
+                start.timestamp() + 3600,
+                Ratio::from_integer(BigUint::from(10u32))
+            ),
+        ]
+    );
+
+    // Snapshots outside of the range are not loaded.
+    let history = storage
+        .tokens_schema()
+        .load_ticker_price_history(TOKEN_ID, start + chrono::Duration::seconds(1), end, 60)
+        .await?;
+    assert_eq!(history.len(), 2);
+
+    // Only the snapshots created before the given time are removed.
+    let removed = storage
+        .tokens_schema()
+        .remove_ticker_price_history(start + chrono::Duration::seconds(1800))
+        .await?;
+    assert_eq!(removed, 1);
+    let history = storage
+        .tokens_schema()
+        .load_ticker_price_history(TOKEN_ID, start, end, 60)
+        .await?;
+    let history: Vec<_> = history
+        .into_iter()
+        .map(|price| price.last_updated.timestamp())
+        .collect();
+    assert_eq!(
+        history,
+        vec![start.timestamp() + 1800, start.timestamp() + 3600]
+    );
+
+    Ok(())
+}
+
+/// Checks the store/load routine for `ticker_market_volume` table and load tokens by market volume.
+async fn market_volume(storage: &mut impl TokensStorage) -> QueryResult<()> {
     const TOKEN_ID: TokenId = TokenId(0);
 
     let market_volume = TokenMarketVolume {
@@ -190,12 +343,10 @@ async fn test_market_volume(mut storage: StorageProcessor<'_>) -> QueryResult<()
     };
 
     storage
-        .tokens_schema()
         .update_token_market_volume(TOKEN_ID, market_volume.clone())
         .await?;
 
     let loaded = storage
-        .tokens_schema()
         .get_token_market_volume(TOKEN_ID)
         .await?
         .expect("couldn't load market volume");
@@ -207,21 +358,387 @@ async fn test_market_volume(mut storage: StorageProcessor<'_>) -> QueryResult<()
         market_volume.last_updated.timestamp()
     );
 
-    let tokens = TokensSchema(&mut storage)
+    let tokens = storage
         .load_tokens_by_market_volume(Ratio::new(BigUint::from(3u32), BigUint::from(5u32)))
         .await
         .expect("Load tokens by market volume query failed");
     assert_eq!(tokens.len(), 0);
 
-    let tokens = TokensSchema(&mut storage)
+    let tokens = storage
         .load_tokens_by_market_volume(Ratio::new(BigUint::from(2u32), BigUint::from(5u32)))
         .await
         .expect("Load tokens by market volume query failed");
     assert_eq!(tokens.len(), 1);
 
+    // Tokens without the market volume data are skipped.
+    let volumes = storage
+        .load_token_market_volumes(&[TOKEN_ID, TokenId(1)])
+        .await?;
+    assert_eq!(volumes.len(), 1);
+    assert_eq!(
+        volumes[&TOKEN_ID].market_volume,
+        market_volume.market_volume
+    );
+
     Ok(())
 }
 
+#[db_test]
+async fn test_market_volume(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    market_volume(&mut storage).await
+}
+
+#[tokio::test]
+async fn test_market_volume_in_memory() -> QueryResult<()> {
+    market_volume(&mut MemoryTokensStorage::new()).await
+}
+
+/// Checks the store/load routine for the `token_metadata` table.
+#[db_test]
+async fn test_token_metadata(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    const TOKEN_ID: TokenId = TokenId(0);
+
+    let metadata = TokenMetadata {
+        logo_uri: Some("https://example.com/eth.svg".to_string()),
+        project_url: None,
+        display_name: Some("Ether".to_string()),
+    };
+    storage
+        .tokens_schema()
+        .store_token_metadata(TOKEN_ID, &metadata)
+        .await?;
+    let loaded = storage
+        .tokens_schema()
+        .load_token_metadata(&[TOKEN_ID, TokenId(1)])
+        .await?;
+    assert_eq!(loaded.len(), 1);
+    assert_eq!(loaded[&TOKEN_ID], metadata);
+
+    // The stored metadata is replaced.
+    let metadata = TokenMetadata {
+        project_url: Some("https://ethereum.org".to_string()),
+        ..Default::default()
+    };
+    storage
+        .tokens_schema()
+        .store_token_metadata(TOKEN_ID, &metadata)
+        .await?;
+    let loaded = storage
+        .tokens_schema()
+        .load_token_metadata(&[TOKEN_ID])
+        .await?;
+    assert_eq!(loaded[&TOKEN_ID], metadata);
+
+    Ok(())
+}
+
+/// Checks the tokens denylisted by the admin API and by the addresses.
+#[db_test]
+async fn test_token_denylist(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    for id in 1..=3 {
+        let token = Token::new(
+            TokenId(id),
+            Address::from_low_u64_be(id as u64),
+            &format!("TOKEN{}", id),
+            18,
+            TokenKind::ERC20,
+        );
+        storage.tokens_schema().store_token(token).await?;
+    }
+
+    storage
+        .tokens_schema()
+        .add_token_to_denylist(TokenId(1), "Scam")
+        .await?;
+    // The reason of the denylisted token is replaced.
+    storage
+        .tokens_schema()
+        .add_token_to_denylist(TokenId(1), "Fake USDC")
+        .await?;
+    let denylist = storage.tokens_schema().load_token_denylist().await?;
+    assert_eq!(denylist.len(), 1);
+    assert_eq!(denylist[0].token_id, 1);
+    assert_eq!(denylist[0].reason, "Fake USDC");
+
+    let denylisted = storage
+        .tokens_schema()
+        .load_denylisted_token_ids(&[Address::from_low_u64_be(2), Address::repeat_byte(0x11)])
+        .await?;
+    assert_eq!(
+        denylisted,
+        [TokenId(1), TokenId(2)].iter().copied().collect()
+    );
+
+    // The denylisted tokens are skipped by the token pages.
+    let query = PaginationQuery {
+        from: TokensRequest {
+            token_id: ApiEither {
+                inner: Either::Left(TokenId(0)),
+            },
+            filter: TokenFilter::default(),
+            min_market_volume: Ratio::from_integer(BigUint::from(5u32)),
+            denylisted_tokens: denylisted.into_iter().collect(),
+        },
+        limit: 10,
+        direction: PaginationDirection::Newer,
+    };
+    let tokens = storage
+        .tokens_schema()
+        .load_filtered_token_page(&query)
+        .await?;
+    let ids: Vec<u32> = tokens.into_iter().map(|token| *token.id).collect();
+    assert_eq!(ids, vec![0, 3]);
+    assert_eq!(
+        storage
+            .tokens_schema()
+            .get_filtered_count(
+                &TokenFilter::default(),
+                &query.from.min_market_volume,
+                &query.from.denylisted_tokens
+            )
+            .await?,
+        2
+    );
+
+    assert!(
+        storage
+            .tokens_schema()
+            .remove_token_from_denylist(TokenId(1))
+            .await?
+    );
+    assert!(
+        !storage
+            .tokens_schema()
+            .remove_token_from_denylist(TokenId(1))
+            .await?
+    );
+    assert!(storage
+        .tokens_schema()
+        .load_token_denylist()
+        .await?
+        .is_empty());
+
+    Ok(())
+}
+
+async fn enabled_tokens(
+    storage: &mut impl TokensStorage,
+    min_market_volume: &Ratio<BigUint>,
+) -> QueryResult<HashSet<TokenId>> {
+    storage
+        .filter_tokens_by_market_volume(vec![TokenId(1), TokenId(2)], min_market_volume)
+        .await
+}
+
+/// Checks that the fee eligibility set by the admin takes precedence over the market volume.
+async fn fee_eligibility_override(storage: &mut impl TokensStorage) -> QueryResult<()> {
+    let min_market_volume = Ratio::from_integer(BigUint::from(5u32));
+    for (id, market_volume) in &[(1, 10u32), (2, 1)] {
+        let token = Token::new(
+            TokenId(*id),
+            Address::from_low_u64_be(*id as u64),
+            &format!("TOKEN{}", id),
+            18,
+            TokenKind::ERC20,
+        );
+        storage.store_token(token).await?;
+        let market_volume = TokenMarketVolume {
+            market_volume: Ratio::from_integer(BigUint::from(*market_volume)),
+            last_updated: Utc::now(),
+        };
+        storage
+            .update_token_market_volume(TokenId(*id), market_volume)
+            .await?;
+    }
+    assert_eq!(
+        enabled_tokens(storage, &min_market_volume).await?,
+        vec![TokenId(1)].into_iter().collect()
+    );
+
+    assert!(
+        storage
+            .set_fee_eligibility_override(TokenId(1), Some(false))
+            .await?
+    );
+    assert!(
+        storage
+            .set_fee_eligibility_override(TokenId(2), Some(true))
+            .await?
+    );
+    assert_eq!(
+        storage.get_fee_eligibility_override(TokenId(1)).await?,
+        Some(false)
+    );
+    assert_eq!(
+        enabled_tokens(storage, &min_market_volume).await?,
+        vec![TokenId(2)].into_iter().collect()
+    );
+
+    // After the reset the eligibility is decided by the market volume again.
+    storage
+        .set_fee_eligibility_override(TokenId(1), None)
+        .await?;
+    assert_eq!(
+        storage.get_fee_eligibility_override(TokenId(1)).await?,
+        None
+    );
+    let tokens = storage
+        .load_tokens_by_market_volume(min_market_volume.clone())
+        .await?;
+    let mut ids: Vec<_> = tokens.keys().copied().collect();
+    ids.sort();
+    assert_eq!(ids, vec![TokenId(1), TokenId(2)]);
+
+    // There is no such token.
+    assert!(
+        !storage
+            .set_fee_eligibility_override(TokenId(100), Some(true))
+            .await?
+    );
+
+    Ok(())
+}
+
+/// Loads the ids of the filtered token page with the market volume threshold of 5.
+async fn load_filtered_token_ids(
+    storage: &mut impl TokensStorage,
+    from: Option<u32>,
+    direction: PaginationDirection,
+    filter: TokenFilter,
+) -> QueryResult<Vec<u32>> {
+    let query = PaginationQuery {
+        from: TokensRequest {
+            token_id: ApiEither {
+                inner: from.map_or(Either::Right(Latest), |id| Either::Left(TokenId(id))),
+            },
+            filter,
+            min_market_volume: Ratio::from_integer(BigUint::from(5u32)),
+            denylisted_tokens: Vec::new(),
+        },
+        limit: 10,
+        direction,
+    };
+    let tokens = storage.load_filtered_token_page(&query).await?;
+    Ok(tokens.into_iter().map(|token| *token.id).collect())
+}
+
+/// Checks the filtering and the sorting of the token pages.
+async fn filtered_token_page(storage: &mut impl TokensStorage) -> QueryResult<()> {
+    let tokens = [
+        (1, "ABC", Some(10u32)),
+        (2, "ABD", Some(1)),
+        (3, "A_C", None),
+    ];
+    for (id, symbol, market_volume) in tokens.iter() {
+        let token = Token::new(
+            TokenId(*id),
+            Address::from_low_u64_be(*id as u64),
+            symbol,
+            18,
+            TokenKind::ERC20,
+        );
+        storage.store_token(token).await?;
+        if let Some(market_volume) = market_volume {
+            let market_volume = TokenMarketVolume {
+                market_volume: Ratio::from_integer(BigUint::from(*market_volume)),
+                last_updated: Utc::now(),
+            };
+            storage
+                .update_token_market_volume(TokenId(*id), market_volume)
+                .await?;
+        }
+    }
+    let min_market_volume = Ratio::from_integer(BigUint::from(5u32));
+
+    let ids = load_filtered_token_ids(
+        storage,
+        None,
+        PaginationDirection::Older,
+        TokenFilter::default(),
+    )
+    .await?;
+    assert_eq!(ids, vec![3, 2, 1, 0]);
+
+    // ETH is always enabled for fees, the token without the volume never is.
+    let enabled = TokenFilter {
+        enabled_for_fees: Some(true),
+        ..Default::default()
+    };
+    let ids = load_filtered_token_ids(
+        storage,
+        Some(0),
+        PaginationDirection::Newer,
+        enabled.clone(),
+    )
+    .await?;
+    assert_eq!(ids, vec![0, 1]);
+    assert_eq!(
+        storage
+            .get_filtered_count(&enabled, &min_market_volume, &[])
+            .await?,
+        2
+    );
+    let disabled = TokenFilter {
+        enabled_for_fees: Some(false),
+        ..Default::default()
+    };
+    let ids =
+        load_filtered_token_ids(storage, Some(0), PaginationDirection::Newer, disabled).await?;
+    assert_eq!(ids, vec![2, 3]);
+
+    // The prefix is case-insensitive and the wildcards in it are matched literally.
+    let prefix = |prefix: &str| TokenFilter {
+        symbol_prefix: Some(prefix.to_string()),
+        ..Default::default()
+    };
+    let ids =
+        load_filtered_token_ids(storage, None, PaginationDirection::Older, prefix("ab")).await?;
+    assert_eq!(ids, vec![2, 1]);
+    let ids =
+        load_filtered_token_ids(storage, None, PaginationDirection::Older, prefix("A_")).await?;
+    assert_eq!(ids, vec![3]);
+    assert_eq!(
+        storage
+            .get_last_filtered_token_id(&prefix("X"), &min_market_volume, &[])
+            .await?,
+        None
+    );
+
+    // Tokens with the same market volume are ordered by id.
+    let by_volume = TokenFilter {
+        sort: TokenSortOrder::MarketVolume,
+        ..Default::default()
+    };
+    let ids = load_filtered_token_ids(storage, None, PaginationDirection::Older, by_volume.clone())
+        .await?;
+    assert_eq!(ids, vec![1, 2, 3, 0]);
+    let ids =
+        load_filtered_token_ids(storage, Some(3), PaginationDirection::Newer, by_volume).await?;
+    assert_eq!(ids, vec![3, 2, 1]);
+
+    Ok(())
+}
+
+#[db_test]
+async fn test_fee_eligibility_override(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    fee_eligibility_override(&mut storage).await
+}
+
+#[tokio::test]
+async fn test_fee_eligibility_override_in_memory() -> QueryResult<()> {
+    fee_eligibility_override(&mut MemoryTokensStorage::new()).await
+}
+
+#[db_test]
+async fn test_filtered_token_page(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    filtered_token_page(&mut storage).await
+}
+
+#[tokio::test]
+async fn test_filtered_token_page_in_memory() -> QueryResult<()> {
+    filtered_token_page(&mut MemoryTokensStorage::new()).await
+}
+
 /// Checks the store/load factories for nft
 #[db_test]
 async fn test_nfts_with_factories(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
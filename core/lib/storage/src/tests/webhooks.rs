@@ -0,0 +1,131 @@
+// Built-in imports
+use std::time::Duration;
+// External imports
+use serde_json::json;
+// Workspace imports
+use zksync_types::event::EventId;
+// Local imports
+use crate::tests::db_test;
+use crate::{
+    webhooks::{records::WebhookDeliveryStatus, WebhooksSchema},
+    QueryResult, StorageProcessor,
+};
+
+const LEASE_DURATION: Duration = Duration::from_secs(60);
+
+/// Checks that the webhooks are registered and removed.
+#[db_test]
+async fn stored_webhooks(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let filter = json!({ "status": "finalized" });
+    let first = WebhooksSchema(&mut storage)
+        .register_webhook("https://example.com/first", "secret", &filter, "partner")
+        .await?;
+    assert_eq!(first.url, "https://example.com/first");
+    assert_eq!(first.filter, filter);
+    assert!(first.removed_at.is_none());
+    let second = WebhooksSchema(&mut storage)
+        .register_webhook("https://example.com/second", "secret", &json!({}), "")
+        .await?;
+
+    let webhooks = WebhooksSchema(&mut storage).load_webhooks().await?;
+    assert_eq!(webhooks, vec![first.clone(), second.clone()]);
+
+    // The removed webhook isn't listed anymore.
+    assert!(
+        WebhooksSchema(&mut storage)
+            .remove_webhook(first.id)
+            .await?
+    );
+    assert!(
+        !WebhooksSchema(&mut storage)
+            .remove_webhook(first.id)
+            .await?
+    );
+    let webhooks = WebhooksSchema(&mut storage).load_webhooks().await?;
+    assert_eq!(webhooks, vec![second]);
+
+    Ok(())
+}
+
+/// Checks that the deliveries are leased, retried and logged.
+#[db_test]
+async fn webhook_deliveries(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let webhook = WebhooksSchema(&mut storage)
+        .register_webhook("https://example.com", "secret", &json!({}), "")
+        .await?;
+    let removed = WebhooksSchema(&mut storage)
+        .register_webhook("https://example.com/removed", "secret", &json!({}), "")
+        .await?;
+    WebhooksSchema(&mut storage)
+        .schedule_deliveries(&[
+            (webhook.id, EventId(1), json!({ "event": 1 })),
+            (webhook.id, EventId(2), json!({ "event": 2 })),
+            (removed.id, EventId(1), json!({ "event": 1 })),
+        ])
+        .await?;
+    // The event already scheduled for the webhook is ignored.
+    WebhooksSchema(&mut storage)
+        .schedule_deliveries(&[(webhook.id, EventId(1), json!({ "event": 1 }))])
+        .await?;
+    WebhooksSchema(&mut storage)
+        .remove_webhook(removed.id)
+        .await?;
+
+    // The deliveries of the removed webhooks aren't leased.
+    let deliveries = WebhooksSchema(&mut storage)
+        .lease_due_deliveries(1, LEASE_DURATION)
+        .await?;
+    assert_eq!(deliveries.len(), 1);
+    assert_eq!(deliveries[0].event_id, 1);
+    assert_eq!(deliveries[0].url, webhook.url);
+    assert_eq!(deliveries[0].attempts, 0);
+    let first_id = deliveries[0].id;
+    let deliveries = WebhooksSchema(&mut storage)
+        .lease_due_deliveries(10, LEASE_DURATION)
+        .await?;
+    assert_eq!(deliveries.len(), 1);
+    assert_eq!(deliveries[0].event_id, 2);
+    let second_id = deliveries[0].id;
+    // The leased deliveries aren't leased once again.
+    assert!(WebhooksSchema(&mut storage)
+        .lease_due_deliveries(10, LEASE_DURATION)
+        .await?
+        .is_empty());
+
+    WebhooksSchema(&mut storage)
+        .record_delivery_success(first_id, 200)
+        .await?;
+    WebhooksSchema(&mut storage)
+        .record_delivery_failure(second_id, Some(500), "server error", Some(Duration::ZERO))
+        .await?;
+    // The failed delivery is retried.
+    let deliveries = WebhooksSchema(&mut storage)
+        .lease_due_deliveries(10, LEASE_DURATION)
+        .await?;
+    assert_eq!(deliveries.len(), 1);
+    assert_eq!(deliveries[0].id, second_id);
+    assert_eq!(deliveries[0].attempts, 1);
+    WebhooksSchema(&mut storage)
+        .record_delivery_failure(second_id, None, "timeout", None)
+        .await?;
+
+    let log = WebhooksSchema(&mut storage)
+        .load_deliveries(webhook.id, 10)
+        .await?;
+    assert_eq!(log.len(), 2);
+    assert_eq!(log[0].id, second_id);
+    assert_eq!(log[0].status, WebhookDeliveryStatus::Failed.as_str());
+    assert_eq!(log[0].attempts, 2);
+    assert_eq!(log[0].last_response_code, None);
+    assert_eq!(log[0].last_error.as_deref(), Some("timeout"));
+    assert_eq!(log[1].id, first_id);
+    assert_eq!(log[1].status, WebhookDeliveryStatus::Delivered.as_str());
+    assert_eq!(log[1].last_response_code, Some(200));
+    // The failed delivery isn't retried anymore.
+    assert!(WebhooksSchema(&mut storage)
+        .lease_due_deliveries(10, LEASE_DURATION)
+        .await?
+        .is_empty());
+
+    Ok(())
+}
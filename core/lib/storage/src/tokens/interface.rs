@@ -0,0 +1,250 @@
+//! Interface of the token storage, implemented by the database and by the in-memory
+//! storage of the tests, so the logic on top of the token pages can be tested without
+//! the database.
+
+// Built-in deps
+use std::collections::{HashMap, HashSet};
+// External imports
+use num::{rational::Ratio, BigUint};
+// Workspace imports
+use zksync_api_types::v02::{
+    pagination::{PaginationQuery, TokensRequest},
+    token::{TokenFilter, TokenMetadata},
+};
+use zksync_types::{tokens::TokenMarketVolume, Token, TokenId, TokenLike};
+// Local imports
+use super::StoreTokenError;
+use crate::{QueryResult, StorageProcessor};
+
+/// Operations on the tokens, see the methods of `TokensSchema` with the same names.
+#[async_trait::async_trait]
+pub trait TokensStorage: Send {
+    async fn store_token(&mut self, token: Token) -> Result<(), StoreTokenError>;
+
+    async fn store_or_update_token(&mut self, token: Token) -> QueryResult<()>;
+
+    async fn load_tokens(&mut self) -> QueryResult<HashMap<TokenId, Token>>;
+
+    async fn get_token(&mut self, token_like: TokenLike) -> QueryResult<Option<Token>>;
+
+    async fn get_tokens(&mut self, token_likes: &[TokenLike]) -> QueryResult<Vec<Token>>;
+
+    async fn get_count(&mut self) -> QueryResult<u32>;
+
+    async fn get_max_erc20_token_id(&mut self) -> QueryResult<u32>;
+
+    async fn get_max_token_id(&mut self) -> QueryResult<u32>;
+
+    async fn get_token_market_volume(
+        &mut self,
+        token_id: TokenId,
+    ) -> QueryResult<Option<TokenMarketVolume>>;
+
+    async fn load_token_market_volumes(
+        &mut self,
+        token_ids: &[TokenId],
+    ) -> QueryResult<HashMap<TokenId, TokenMarketVolume>>;
+
+    async fn update_token_market_volume(
+        &mut self,
+        token_id: TokenId,
+        market_volume: TokenMarketVolume,
+    ) -> QueryResult<()>;
+
+    async fn set_fee_eligibility_override(
+        &mut self,
+        token_id: TokenId,
+        enabled: Option<bool>,
+    ) -> QueryResult<bool>;
+
+    async fn get_fee_eligibility_override(
+        &mut self,
+        token_id: TokenId,
+    ) -> QueryResult<Option<bool>>;
+
+    async fn store_token_metadata(
+        &mut self,
+        token_id: TokenId,
+        metadata: &TokenMetadata,
+    ) -> QueryResult<()>;
+
+    async fn load_token_metadata(
+        &mut self,
+        token_ids: &[TokenId],
+    ) -> QueryResult<HashMap<TokenId, TokenMetadata>>;
+
+    async fn load_tokens_by_market_volume(
+        &mut self,
+        min_market_volume: Ratio<BigUint>,
+    ) -> QueryResult<HashMap<TokenId, Token>>;
+
+    async fn filter_tokens_by_market_volume(
+        &mut self,
+        tokens_to_check: Vec<TokenId>,
+        min_market_volume: &Ratio<BigUint>,
+    ) -> QueryResult<HashSet<TokenId>>;
+
+    async fn load_filtered_token_page(
+        &mut self,
+        query: &PaginationQuery<TokensRequest>,
+    ) -> QueryResult<Vec<Token>>;
+
+    async fn get_last_filtered_token_id(
+        &mut self,
+        filter: &TokenFilter,
+        min_market_volume: &Ratio<BigUint>,
+        denylisted_tokens: &[TokenId],
+    ) -> QueryResult<Option<TokenId>>;
+
+    async fn get_filtered_count(
+        &mut self,
+        filter: &TokenFilter,
+        min_market_volume: &Ratio<BigUint>,
+        denylisted_tokens: &[TokenId],
+    ) -> QueryResult<u32>;
+}
+
+#[async_trait::async_trait]
+impl TokensStorage for StorageProcessor<'_> {
+    async fn store_token(&mut self, token: Token) -> Result<(), StoreTokenError> {
+        self.tokens_schema().store_token(token).await
+    }
+
+    async fn store_or_update_token(&mut self, token: Token) -> QueryResult<()> {
+        self.tokens_schema().store_or_update_token(token).await
+    }
+
+    async fn load_tokens(&mut self) -> QueryResult<HashMap<TokenId, Token>> {
+        self.tokens_schema().load_tokens().await
+    }
+
+    async fn get_token(&mut self, token_like: TokenLike) -> QueryResult<Option<Token>> {
+        self.tokens_schema().get_token(token_like).await
+    }
+
+    async fn get_tokens(&mut self, token_likes: &[TokenLike]) -> QueryResult<Vec<Token>> {
+        self.tokens_schema().get_tokens(token_likes).await
+    }
+
+    async fn get_count(&mut self) -> QueryResult<u32> {
+        self.tokens_schema().get_count().await
+    }
+
+    async fn get_max_erc20_token_id(&mut self) -> QueryResult<u32> {
+        self.tokens_schema().get_max_erc20_token_id().await
+    }
+
+    async fn get_max_token_id(&mut self) -> QueryResult<u32> {
+        self.tokens_schema().get_max_token_id().await
+    }
+
+    async fn get_token_market_volume(
+        &mut self,
+        token_id: TokenId,
+    ) -> QueryResult<Option<TokenMarketVolume>> {
+        self.tokens_schema().get_token_market_volume(token_id).await
+    }
+
+    async fn load_token_market_volumes(
+        &mut self,
+        token_ids: &[TokenId],
+    ) -> QueryResult<HashMap<TokenId, TokenMarketVolume>> {
+        self.tokens_schema()
+            .load_token_market_volumes(token_ids)
+            .await
+    }
+
+    async fn update_token_market_volume(
+        &mut self,
+        token_id: TokenId,
+        market_volume: TokenMarketVolume,
+    ) -> QueryResult<()> {
+        self.tokens_schema()
+            .update_token_market_volume(token_id, market_volume)
+            .await
+    }
+
+    async fn set_fee_eligibility_override(
+        &mut self,
+        token_id: TokenId,
+        enabled: Option<bool>,
+    ) -> QueryResult<bool> {
+        self.tokens_schema()
+            .set_fee_eligibility_override(token_id, enabled)
+            .await
+    }
+
+    async fn get_fee_eligibility_override(
+        &mut self,
+        token_id: TokenId,
+    ) -> QueryResult<Option<bool>> {
+        self.tokens_schema()
+            .get_fee_eligibility_override(token_id)
+            .await
+    }
+
+    async fn store_token_metadata(
+        &mut self,
+        token_id: TokenId,
+        metadata: &TokenMetadata,
+    ) -> QueryResult<()> {
+        self.tokens_schema()
+            .store_token_metadata(token_id, metadata)
+            .await
+    }
+
+    async fn load_token_metadata(
+        &mut self,
+        token_ids: &[TokenId],
+    ) -> QueryResult<HashMap<TokenId, TokenMetadata>> {
+        self.tokens_schema().load_token_metadata(token_ids).await
+    }
+
+    async fn load_tokens_by_market_volume(
+        &mut self,
+        min_market_volume: Ratio<BigUint>,
+    ) -> QueryResult<HashMap<TokenId, Token>> {
+        self.tokens_schema()
+            .load_tokens_by_market_volume(min_market_volume)
+            .await
+    }
+
+    async fn filter_tokens_by_market_volume(
+        &mut self,
+        tokens_to_check: Vec<TokenId>,
+        min_market_volume: &Ratio<BigUint>,
+    ) -> QueryResult<HashSet<TokenId>> {
+        self.tokens_schema()
+            .filter_tokens_by_market_volume(tokens_to_check, min_market_volume)
+            .await
+    }
+
+    async fn load_filtered_token_page(
+        &mut self,
+        query: &PaginationQuery<TokensRequest>,
+    ) -> QueryResult<Vec<Token>> {
+        self.tokens_schema().load_filtered_token_page(query).await
+    }
+
+    async fn get_last_filtered_token_id(
+        &mut self,
+        filter: &TokenFilter,
+        min_market_volume: &Ratio<BigUint>,
+        denylisted_tokens: &[TokenId],
+    ) -> QueryResult<Option<TokenId>> {
+        self.tokens_schema()
+            .get_last_filtered_token_id(filter, min_market_volume, denylisted_tokens)
+            .await
+    }
+
+    async fn get_filtered_count(
+        &mut self,
+        filter: &TokenFilter,
+        min_market_volume: &Ratio<BigUint>,
+        denylisted_tokens: &[TokenId],
+    ) -> QueryResult<u32> {
+        self.tokens_schema()
+            .get_filtered_count(filter, min_market_volume, denylisted_tokens)
+            .await
+    }
+}
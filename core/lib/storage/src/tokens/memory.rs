@@ -0,0 +1,418 @@
+//! In-memory token storage with the same semantics as the database, available in the tests
+//! and with the `memory_storage` feature.
+
+// Built-in deps
+use std::collections::{BTreeMap, HashMap, HashSet};
+// External imports
+use num::{rational::Ratio, BigUint, Zero};
+// Workspace imports
+use zksync_api_types::{
+    v02::{
+        pagination::{ApiEither, Latest, PaginationDirection, PaginationQuery, TokensRequest},
+        token::{TokenFilter, TokenMetadata, TokenSortOrder},
+    },
+    Either,
+};
+use zksync_types::{tokens::TokenMarketVolume, Address, Token, TokenId, TokenKind, TokenLike};
+// Local imports
+use super::{interface::TokensStorage, StoreTokenError};
+use crate::QueryResult;
+
+/// Tokens kept in memory, the storage contains ETH after the creation like the database
+/// after the migrations.
+#[derive(Debug, Clone)]
+pub struct MemoryTokensStorage {
+    tokens: BTreeMap<TokenId, Token>,
+    market_volumes: HashMap<TokenId, TokenMarketVolume>,
+    fee_eligibility_overrides: HashMap<TokenId, bool>,
+    metadata: HashMap<TokenId, TokenMetadata>,
+    /// Number of the `get_token` and `get_tokens` calls, so that the tests can check
+    /// how many lookups the request takes.
+    token_lookups: usize,
+}
+
+impl Default for MemoryTokensStorage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MemoryTokensStorage {
+    pub fn new() -> Self {
+        let eth = Token::new(TokenId(0), Address::zero(), "ETH", 18, TokenKind::ERC20);
+        Self {
+            tokens: std::iter::once((eth.id, eth)).collect(),
+            market_volumes: HashMap::new(),
+            fee_eligibility_overrides: HashMap::new(),
+            metadata: HashMap::new(),
+            token_lookups: 0,
+        }
+    }
+
+    /// Returns the number of the token lookups made so far.
+    pub fn token_lookups(&self) -> usize {
+        self.token_lookups
+    }
+
+    /// Stores the tokens, replacing the stored ones with the same ids.
+    pub fn with_tokens(mut self, tokens: impl IntoIterator<Item = Token>) -> Self {
+        self.tokens
+            .extend(tokens.into_iter().map(|token| (token.id, token)));
+        self
+    }
+
+    pub fn with_market_volumes(
+        mut self,
+        market_volumes: impl IntoIterator<Item = (TokenId, TokenMarketVolume)>,
+    ) -> Self {
+        self.market_volumes.extend(market_volumes);
+        self
+    }
+
+    pub fn with_fee_eligibility_overrides(
+        mut self,
+        overrides: impl IntoIterator<Item = (TokenId, bool)>,
+    ) -> Self {
+        self.fee_eligibility_overrides.extend(overrides);
+        self
+    }
+
+    fn market_volume(&self, token_id: TokenId) -> Ratio<BigUint> {
+        self.market_volumes
+            .get(&token_id)
+            .map(|volume| volume.market_volume.clone())
+            .unwrap_or_else(Ratio::zero)
+    }
+
+    fn max_token_id(&self, filter: impl Fn(&Token) -> bool) -> u32 {
+        self.tokens
+            .values()
+            .filter(|token| filter(token))
+            .map(|token| *token.id)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The eligibility set by the admin takes precedence over the market volume,
+    /// the tokens without the market volume aren't eligible.
+    fn is_eligible_for_fees(&self, token_id: TokenId, min_market_volume: &Ratio<BigUint>) -> bool {
+        match self.fee_eligibility_overrides.get(&token_id) {
+            Some(enabled) => *enabled,
+            None => self
+                .market_volumes
+                .get(&token_id)
+                .map_or(false, |volume| &volume.market_volume >= min_market_volume),
+        }
+    }
+
+    fn matches_filter(
+        &self,
+        token: &Token,
+        filter: &TokenFilter,
+        min_market_volume: &Ratio<BigUint>,
+        denylisted_tokens: &[TokenId],
+    ) -> bool {
+        if token.kind != TokenKind::ERC20 || denylisted_tokens.contains(&token.id) {
+            return false;
+        }
+        // ETH always has enough market volume.
+        let enabled_for_fees =
+            token.id == TokenId(0) || self.is_eligible_for_fees(token.id, min_market_volume);
+        if filter
+            .enabled_for_fees
+            .map_or(false, |enabled| enabled != enabled_for_fees)
+        {
+            return false;
+        }
+        filter.symbol_prefix.as_ref().map_or(true, |prefix| {
+            token
+                .symbol
+                .to_lowercase()
+                .starts_with(&prefix.to_lowercase())
+        })
+    }
+
+    fn sort_key(&self, token_id: TokenId, sort: &TokenSortOrder) -> (Ratio<BigUint>, TokenId) {
+        match sort {
+            TokenSortOrder::Id => (Ratio::zero(), token_id),
+            TokenSortOrder::MarketVolume => (self.market_volume(token_id), token_id),
+        }
+    }
+
+    fn filtered_tokens(
+        &self,
+        filter: &TokenFilter,
+        min_market_volume: &Ratio<BigUint>,
+        denylisted_tokens: &[TokenId],
+    ) -> impl Iterator<Item = &Token> {
+        let filter = filter.clone();
+        let min_market_volume = min_market_volume.clone();
+        let denylisted_tokens = denylisted_tokens.to_vec();
+        self.tokens.values().filter(move |token| {
+            self.matches_filter(token, &filter, &min_market_volume, &denylisted_tokens)
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl TokensStorage for MemoryTokensStorage {
+    async fn store_token(&mut self, token: Token) -> Result<(), StoreTokenError> {
+        let existing = self.tokens.values().find(|stored| {
+            stored.id == token.id
+                || stored.address == token.address
+                || stored.symbol == token.symbol
+        });
+        if let Some(existing) = existing {
+            let mut matched_parameters = Vec::new();
+            if existing.id == token.id {
+                matched_parameters.push(format!("id = {}", token.id));
+            }
+            if existing.symbol == token.symbol {
+                matched_parameters.push(format!("symbol = {}", token.symbol));
+            }
+            if existing.address == token.address {
+                matched_parameters.push(format!("address = {}", token.address));
+            }
+            return Err(StoreTokenError::TokenAlreadyExistsError(format!(
+                "tokens with such parameters already exist: {:#?}",
+                matched_parameters
+            )));
+        }
+        self.tokens.insert(token.id, token);
+        Ok(())
+    }
+
+    async fn store_or_update_token(&mut self, token: Token) -> QueryResult<()> {
+        self.tokens.insert(token.id, token);
+        Ok(())
+    }
+
+    async fn load_tokens(&mut self) -> QueryResult<HashMap<TokenId, Token>> {
+        Ok(self
+            .tokens
+            .values()
+            .filter(|token| token.kind == TokenKind::ERC20)
+            .map(|token| (token.id, token.clone()))
+            .collect())
+    }
+
+    async fn get_token(&mut self, token_like: TokenLike) -> QueryResult<Option<Token>> {
+        self.token_lookups += 1;
+        Ok(self
+            .tokens
+            .values()
+            .find(|token| token_matches(token, &token_like))
+            .cloned())
+    }
+
+    async fn get_tokens(&mut self, token_likes: &[TokenLike]) -> QueryResult<Vec<Token>> {
+        self.token_lookups += 1;
+        Ok(self
+            .tokens
+            .values()
+            .filter(|token| {
+                token_likes
+                    .iter()
+                    .any(|token_like| token_matches(token, token_like))
+            })
+            .cloned()
+            .collect())
+    }
+
+    async fn get_count(&mut self) -> QueryResult<u32> {
+        Ok(self
+            .tokens
+            .values()
+            .filter(|token| token.kind == TokenKind::ERC20)
+            .count() as u32)
+    }
+
+    async fn get_max_erc20_token_id(&mut self) -> QueryResult<u32> {
+        Ok(self.max_token_id(|token| token.kind == TokenKind::ERC20))
+    }
+
+    async fn get_max_token_id(&mut self) -> QueryResult<u32> {
+        Ok(self.max_token_id(|token| token.kind != TokenKind::NFT))
+    }
+
+    async fn get_token_market_volume(
+        &mut self,
+        token_id: TokenId,
+    ) -> QueryResult<Option<TokenMarketVolume>> {
+        Ok(self.market_volumes.get(&token_id).cloned())
+    }
+
+    async fn load_token_market_volumes(
+        &mut self,
+        token_ids: &[TokenId],
+    ) -> QueryResult<HashMap<TokenId, TokenMarketVolume>> {
+        Ok(token_ids
+            .iter()
+            .filter_map(|id| {
+                self.market_volumes
+                    .get(id)
+                    .map(|volume| (*id, volume.clone()))
+            })
+            .collect())
+    }
+
+    async fn update_token_market_volume(
+        &mut self,
+        token_id: TokenId,
+        market_volume: TokenMarketVolume,
+    ) -> QueryResult<()> {
+        self.market_volumes.insert(token_id, market_volume);
+        Ok(())
+    }
+
+    async fn set_fee_eligibility_override(
+        &mut self,
+        token_id: TokenId,
+        enabled: Option<bool>,
+    ) -> QueryResult<bool> {
+        if !self.tokens.contains_key(&token_id) {
+            return Ok(false);
+        }
+        match enabled {
+            Some(enabled) => self.fee_eligibility_overrides.insert(token_id, enabled),
+            None => self.fee_eligibility_overrides.remove(&token_id),
+        };
+        Ok(true)
+    }
+
+    async fn get_fee_eligibility_override(
+        &mut self,
+        token_id: TokenId,
+    ) -> QueryResult<Option<bool>> {
+        Ok(self.fee_eligibility_overrides.get(&token_id).copied())
+    }
+
+    async fn store_token_metadata(
+        &mut self,
+        token_id: TokenId,
+        metadata: &TokenMetadata,
+    ) -> QueryResult<()> {
+        self.metadata.insert(token_id, metadata.clone());
+        Ok(())
+    }
+
+    async fn load_token_metadata(
+        &mut self,
+        token_ids: &[TokenId],
+    ) -> QueryResult<HashMap<TokenId, TokenMetadata>> {
+        Ok(token_ids
+            .iter()
+            .filter_map(|id| {
+                self.metadata
+                    .get(id)
+                    .map(|metadata| (*id, metadata.clone()))
+            })
+            .collect())
+    }
+
+    async fn load_tokens_by_market_volume(
+        &mut self,
+        min_market_volume: Ratio<BigUint>,
+    ) -> QueryResult<HashMap<TokenId, Token>> {
+        Ok(self
+            .tokens
+            .values()
+            .filter(|token| {
+                token.kind == TokenKind::ERC20
+                    && self.is_eligible_for_fees(token.id, &min_market_volume)
+            })
+            .map(|token| (token.id, token.clone()))
+            .collect())
+    }
+
+    async fn filter_tokens_by_market_volume(
+        &mut self,
+        tokens_to_check: Vec<TokenId>,
+        min_market_volume: &Ratio<BigUint>,
+    ) -> QueryResult<HashSet<TokenId>> {
+        Ok(tokens_to_check
+            .into_iter()
+            .filter(|id| {
+                // ETH always has enough market volume.
+                *id == TokenId(0)
+                    || (self.tokens.contains_key(id)
+                        && self.is_eligible_for_fees(*id, min_market_volume))
+            })
+            .collect())
+    }
+
+    async fn load_filtered_token_page(
+        &mut self,
+        query: &PaginationQuery<TokensRequest>,
+    ) -> QueryResult<Vec<Token>> {
+        let request = &query.from;
+        let sort = &request.filter.sort;
+        let mut tokens: Vec<&Token> = self
+            .filtered_tokens(
+                &request.filter,
+                &request.min_market_volume,
+                &request.denylisted_tokens,
+            )
+            .collect();
+        tokens.sort_by_key(|token| self.sort_key(token.id, sort));
+        if query.direction == PaginationDirection::Older {
+            tokens.reverse();
+        }
+        if let Either::Left(from) = request.token_id.inner {
+            let from = self.sort_key(from, sort);
+            tokens.retain(|token| {
+                let key = self.sort_key(token.id, sort);
+                match query.direction {
+                    PaginationDirection::Newer => key >= from,
+                    PaginationDirection::Older => key <= from,
+                }
+            });
+        }
+        Ok(tokens
+            .into_iter()
+            .take(query.limit as usize)
+            .cloned()
+            .collect())
+    }
+
+    async fn get_last_filtered_token_id(
+        &mut self,
+        filter: &TokenFilter,
+        min_market_volume: &Ratio<BigUint>,
+        denylisted_tokens: &[TokenId],
+    ) -> QueryResult<Option<TokenId>> {
+        let query = PaginationQuery {
+            from: TokensRequest {
+                token_id: ApiEither {
+                    inner: Either::Right(Latest),
+                },
+                filter: filter.clone(),
+                min_market_volume: min_market_volume.clone(),
+                denylisted_tokens: denylisted_tokens.to_vec(),
+            },
+            limit: 1,
+            direction: PaginationDirection::Older,
+        };
+        let tokens = self.load_filtered_token_page(&query).await?;
+        Ok(tokens.first().map(|token| token.id))
+    }
+
+    async fn get_filtered_count(
+        &mut self,
+        filter: &TokenFilter,
+        min_market_volume: &Ratio<BigUint>,
+        denylisted_tokens: &[TokenId],
+    ) -> QueryResult<u32> {
+        Ok(self
+            .filtered_tokens(filter, min_market_volume, denylisted_tokens)
+            .count() as u32)
+    }
+}
+
+fn token_matches(token: &Token, token_like: &TokenLike) -> bool {
+    match token_like {
+        TokenLike::Id(id) => token.id == *id,
+        TokenLike::Address(address) => token.address == *address,
+        TokenLike::Symbol(symbol) => token.symbol.to_lowercase() == symbol.to_lowercase(),
+    }
+}
@@ -2,23 +2,35 @@
 use std::collections::{HashMap, HashSet};
 use std::time::Instant;
 // External imports
+use chrono::{DateTime, Utc};
 use num::{rational::Ratio, BigUint};
 
 use thiserror::Error;
 // Workspace imports
-use zksync_api_types::v02::{
-    pagination::{PaginationDirection, PaginationQuery},
-    token::ApiNFT,
+use zksync_api_types::{
+    v02::{
+        pagination::{ApiEither, Latest, PaginationDirection, PaginationQuery, TokensRequest},
+        token::{ApiNFT, TokenFilter, TokenMetadata, TokenSortOrder},
+    },
+    Either,
 };
 use zksync_types::{AccountId, Address, Token, TokenId, TokenLike, TokenPrice, NFT};
 use zksync_utils::ratio_to_big_decimal;
 // Local imports
-use self::records::{DBMarketVolume, DbTickerPrice, DbToken, StorageApiNFT, StorageNFT, TokenKind};
+use self::records::{
+    DBMarketVolume, DbTickerPrice, DbToken, StorageApiNFT, StorageNFT, StoredDenylistedToken,
+    StoredPriceProviderHealth, StoredTokenMetadata, TokenKind,
+};
 
 use crate::utils::address_to_stored_string;
 use crate::{QueryResult, StorageProcessor};
 use zksync_types::tokens::TokenMarketVolume;
 
+pub use self::interface::TokensStorage;
+
+mod interface;
+#[cfg(any(test, feature = "memory_storage"))]
+pub mod memory;
 pub mod records;
 
 /// Precision of the USD price per token
@@ -203,6 +215,126 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         Ok(tokens)
     }
 
+    /// Loads the page of ERC20 tokens matching the filter of the request,
+    /// the denylisted tokens of the request are skipped.
+    /// If the request starts from the latest token, the page starts from the end
+    /// of the sort order.
+    pub async fn load_filtered_token_page(
+        &mut self,
+        query: &PaginationQuery<TokensRequest>,
+    ) -> QueryResult<Vec<Token>> {
+        let start = Instant::now();
+        let filter = &query.from.filter;
+        let (cmp, order) = match query.direction {
+            PaginationDirection::Newer => (">=", "ASC"),
+            PaginationDirection::Older => ("<=", "DESC"),
+        };
+        let (from, cursor) = match query.from.token_id.inner {
+            Either::Left(token_id) => {
+                let cursor = match filter.sort {
+                    TokenSortOrder::Id => format!("AND tokens.id {} $1", cmp),
+                    TokenSortOrder::MarketVolume => format!(
+                        "AND (COALESCE(ticker_market_volume.market_volume, 0), tokens.id) {} \
+                        (COALESCE((SELECT market_volume FROM ticker_market_volume WHERE token_id = $1), 0), $1)",
+                        cmp
+                    ),
+                };
+                (*token_id as i32, cursor)
+            }
+            Either::Right(_) => (0, String::new()),
+        };
+        let order_by = match filter.sort {
+            TokenSortOrder::Id => format!("tokens.id {}", order),
+            TokenSortOrder::MarketVolume => format!(
+                "COALESCE(ticker_market_volume.market_volume, 0) {}, tokens.id {}",
+                order, order
+            ),
+        };
+        let sql = format!(
+            r#"
+            SELECT tokens.id, tokens.address, tokens.decimals, tokens.kind, tokens.symbol
+            FROM tokens
+            LEFT JOIN ticker_market_volume
+            ON tokens.id = ticker_market_volume.token_id
+            WHERE tokens.kind = 'ERC20'::token_kind {} {}
+            ORDER BY {}
+            LIMIT $2
+            "#,
+            cursor,
+            token_filter_conditions(filter, 3, 4, 5),
+            order_by
+        );
+        let tokens: Vec<DbToken> = sqlx::query_as(&sql)
+            .bind(from)
+            .bind(i64::from(query.limit))
+            .bind(ratio_to_big_decimal(
+                &query.from.min_market_volume,
+                STORED_USD_PRICE_PRECISION,
+            ))
+            .bind(symbol_prefix_pattern(filter))
+            .bind(denylisted_token_ids(&query.from.denylisted_tokens))
+            .fetch_all(self.0.conn())
+            .await?;
+
+        let result = tokens.into_iter().map(Token::from).collect();
+        metrics::histogram!("sql.token.load_filtered_token_page", start.elapsed());
+        Ok(result)
+    }
+
+    /// Returns the id of the last ERC20 token in the sort order of the filter.
+    pub async fn get_last_filtered_token_id(
+        &mut self,
+        filter: &TokenFilter,
+        min_market_volume: &Ratio<BigUint>,
+        denylisted_tokens: &[TokenId],
+    ) -> QueryResult<Option<TokenId>> {
+        let query = PaginationQuery {
+            from: TokensRequest {
+                token_id: ApiEither {
+                    inner: Either::Right(Latest),
+                },
+                filter: filter.clone(),
+                min_market_volume: min_market_volume.clone(),
+                denylisted_tokens: denylisted_tokens.to_vec(),
+            },
+            limit: 1,
+            direction: PaginationDirection::Older,
+        };
+        let tokens = self.load_filtered_token_page(&query).await?;
+        Ok(tokens.first().map(|token| token.id))
+    }
+
+    /// Get the number of ERC20 tokens matching the filter, except for the denylisted ones.
+    pub async fn get_filtered_count(
+        &mut self,
+        filter: &TokenFilter,
+        min_market_volume: &Ratio<BigUint>,
+        denylisted_tokens: &[TokenId],
+    ) -> QueryResult<u32> {
+        let start = Instant::now();
+        let sql = format!(
+            r#"
+            SELECT COUNT(*) FROM tokens
+            LEFT JOIN ticker_market_volume
+            ON tokens.id = ticker_market_volume.token_id
+            WHERE tokens.kind = 'ERC20'::token_kind {}
+            "#,
+            token_filter_conditions(filter, 1, 2, 3)
+        );
+        let (count,): (i64,) = sqlx::query_as(&sql)
+            .bind(ratio_to_big_decimal(
+                min_market_volume,
+                STORED_USD_PRICE_PRECISION,
+            ))
+            .bind(symbol_prefix_pattern(filter))
+            .bind(denylisted_token_ids(denylisted_tokens))
+            .fetch_one(self.0.conn())
+            .await?;
+
+        metrics::histogram!("sql.token.get_filtered_count", start.elapsed());
+        Ok(count as u32)
+    }
+
     /// Loads all finalized NFTs.
     pub async fn load_nfts(&mut self) -> QueryResult<HashMap<TokenId, NFT>> {
         let start = Instant::now();
@@ -231,7 +363,8 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
     }
 
     /// Loads all the stored tokens, which have market_volume (ticker_market_volume table)
-    /// not less than parameter (min_market_volume)
+    /// not less than parameter (min_market_volume), or are enabled for fees by the admin.
+    /// The tokens disabled by the admin are not loaded.
     pub async fn load_tokens_by_market_volume(
         &mut self,
         min_market_volume: Ratio<BigUint>,
@@ -242,9 +375,9 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
             r#"
             SELECT id, address, decimals, kind as "kind: _", symbol
             FROM tokens
-            INNER JOIN ticker_market_volume
+            LEFT JOIN ticker_market_volume
             ON tokens.id = ticker_market_volume.token_id
-            WHERE ticker_market_volume.market_volume >= $1
+            WHERE COALESCE(tokens.fee_eligibility_override, ticker_market_volume.market_volume >= $1, false)
             AND kind = 'ERC20'::token_kind
             ORDER BY id ASC
             "#,
@@ -266,6 +399,7 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
     }
 
     /// Filters out tokens whose market volume is less than the specified limit (min_market_volume).
+    /// The eligibility set by the admin takes precedence over the market volume.
     pub async fn filter_tokens_by_market_volume(
         &mut self,
         tokens_to_check: Vec<TokenId>,
@@ -275,9 +409,12 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         let tokens_to_check: Vec<i32> = tokens_to_check.into_iter().map(|id| *id as i32).collect();
         let tokens = sqlx::query!(
             r#"
-            SELECT token_id
-            FROM ticker_market_volume
-            WHERE token_id = ANY($1) AND market_volume >= $2
+            SELECT tokens.id AS token_id
+            FROM tokens
+            LEFT JOIN ticker_market_volume
+            ON tokens.id = ticker_market_volume.token_id
+            WHERE tokens.id = ANY($1)
+            AND COALESCE(tokens.fee_eligibility_override, ticker_market_volume.market_volume >= $2, false)
             "#,
             &tokens_to_check,
             ratio_to_big_decimal(min_market_volume, STORED_USD_PRICE_PRECISION)
@@ -457,6 +594,37 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         Ok(db_token.map(|t| t.into()))
     }
 
+    /// Loads the tokens matching any of the given ids, addresses or symbols at once.
+    /// The symbols are matched case-insensitively, the same as in `get_token`.
+    pub async fn get_tokens(&mut self, token_likes: &[TokenLike]) -> QueryResult<Vec<Token>> {
+        let start = Instant::now();
+        let mut ids = Vec::new();
+        let mut addresses = Vec::new();
+        let mut symbols = Vec::new();
+        for token_like in token_likes {
+            match token_like {
+                TokenLike::Id(token_id) => ids.push(**token_id as i32),
+                TokenLike::Address(address) => addresses.push(address_to_stored_string(address)),
+                TokenLike::Symbol(symbol) => symbols.push(symbol.to_lowercase()),
+            }
+        }
+        let db_tokens = sqlx::query_as!(
+            DbToken,
+            r#"
+            SELECT id, address, decimals, kind as "kind: _", symbol FROM tokens
+            WHERE id = ANY($1) OR address = ANY($2) OR lower(symbol) = ANY($3)
+            "#,
+            &ids,
+            &addresses,
+            &symbols
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.get_tokens", start.elapsed());
+        Ok(db_tokens.into_iter().map(|token| token.into()).collect())
+    }
+
     pub async fn get_token_market_volume(
         &mut self,
         token_id: TokenId,
@@ -478,6 +646,206 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         Ok(db_market_volume.map(|p| p.into()))
     }
 
+    /// Sets the eligibility of the token for paying fees, `None` resets it to be decided
+    /// by the market volume. Returns `false` if there is no such token.
+    pub async fn set_fee_eligibility_override(
+        &mut self,
+        token_id: TokenId,
+        enabled: Option<bool>,
+    ) -> QueryResult<bool> {
+        let start = Instant::now();
+        let result = sqlx::query!(
+            "UPDATE tokens SET fee_eligibility_override = $2 WHERE id = $1",
+            *token_id as i32,
+            enabled
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.set_fee_eligibility_override", start.elapsed());
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Returns the eligibility of the token for paying fees set by the admin, if any.
+    pub async fn get_fee_eligibility_override(
+        &mut self,
+        token_id: TokenId,
+    ) -> QueryResult<Option<bool>> {
+        let start = Instant::now();
+        let record = sqlx::query!(
+            "SELECT fee_eligibility_override FROM tokens WHERE id = $1",
+            *token_id as i32
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.get_fee_eligibility_override", start.elapsed());
+        Ok(record.and_then(|record| record.fee_eligibility_override))
+    }
+
+    /// Stores the metadata of the token, replacing the previously stored one.
+    pub async fn store_token_metadata(
+        &mut self,
+        token_id: TokenId,
+        metadata: &TokenMetadata,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            INSERT INTO token_metadata ( token_id, logo_uri, project_url, display_name )
+            VALUES ( $1, $2, $3, $4 )
+            ON CONFLICT (token_id)
+            DO
+              UPDATE SET logo_uri = $2, project_url = $3, display_name = $4, updated_at = now()
+            "#,
+            *token_id as i32,
+            metadata.logo_uri,
+            metadata.project_url,
+            metadata.display_name
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.store_token_metadata", start.elapsed());
+        Ok(())
+    }
+
+    /// Given the list of token ids, returns the stored metadata of those tokens.
+    /// Tokens without the metadata are not included in the result.
+    pub async fn load_token_metadata(
+        &mut self,
+        token_ids: &[TokenId],
+    ) -> QueryResult<HashMap<TokenId, TokenMetadata>> {
+        let start = Instant::now();
+        let token_ids: Vec<i32> = token_ids.iter().map(|id| **id as i32).collect();
+        let metadata = sqlx::query_as!(
+            StoredTokenMetadata,
+            r#"
+            SELECT * FROM token_metadata
+            WHERE token_id = ANY($1)
+            "#,
+            &token_ids
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        let result = metadata
+            .into_iter()
+            .map(|metadata| (TokenId(metadata.token_id as u32), metadata.into()))
+            .collect();
+
+        metrics::histogram!("sql.token.load_token_metadata", start.elapsed());
+        Ok(result)
+    }
+
+    /// Adds the token to the denylist, or replaces the reason if it's already there.
+    pub async fn add_token_to_denylist(
+        &mut self,
+        token_id: TokenId,
+        reason: &str,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            INSERT INTO token_denylist ( token_id, reason )
+            VALUES ( $1, $2 )
+            ON CONFLICT (token_id)
+            DO
+              UPDATE SET reason = $2
+            "#,
+            *token_id as i32,
+            reason
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.add_token_to_denylist", start.elapsed());
+        Ok(())
+    }
+
+    /// Removes the token from the denylist. Returns `false` if the token wasn't denylisted.
+    pub async fn remove_token_from_denylist(&mut self, token_id: TokenId) -> QueryResult<bool> {
+        let start = Instant::now();
+        let result = sqlx::query!(
+            "DELETE FROM token_denylist WHERE token_id = $1",
+            *token_id as i32
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.remove_token_from_denylist", start.elapsed());
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Loads the tokens denylisted by the admin API.
+    pub async fn load_token_denylist(&mut self) -> QueryResult<Vec<StoredDenylistedToken>> {
+        let start = Instant::now();
+        let tokens = sqlx::query_as!(
+            StoredDenylistedToken,
+            "SELECT * FROM token_denylist ORDER BY token_id"
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.load_token_denylist", start.elapsed());
+        Ok(tokens)
+    }
+
+    /// Returns the ids of the tokens denylisted by the admin API
+    /// together with the ones having the given addresses.
+    pub async fn load_denylisted_token_ids(
+        &mut self,
+        addresses: &[Address],
+    ) -> QueryResult<HashSet<TokenId>> {
+        let start = Instant::now();
+        let addresses: Vec<String> = addresses.iter().map(address_to_stored_string).collect();
+        let records = sqlx::query!(
+            r#"
+            SELECT token_id AS "token_id!" FROM token_denylist
+            UNION
+            SELECT id AS "token_id!" FROM tokens WHERE address = ANY($1)
+            "#,
+            &addresses
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        let result = records
+            .into_iter()
+            .map(|record| TokenId(record.token_id as u32))
+            .collect();
+        metrics::histogram!("sql.token.load_denylisted_token_ids", start.elapsed());
+        Ok(result)
+    }
+
+    /// Given the list of token ids, returns the stored market volumes of those tokens.
+    /// Tokens without the market volume data are not included in the result.
+    pub async fn load_token_market_volumes(
+        &mut self,
+        token_ids: &[TokenId],
+    ) -> QueryResult<HashMap<TokenId, TokenMarketVolume>> {
+        let start = Instant::now();
+        let token_ids: Vec<i32> = token_ids.iter().map(|id| **id as i32).collect();
+        let db_market_volumes = sqlx::query_as!(
+            DBMarketVolume,
+            r#"
+            SELECT * FROM ticker_market_volume
+            WHERE token_id = ANY($1)
+            "#,
+            &token_ids
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        let result = db_market_volumes
+            .into_iter()
+            .map(|volume| (TokenId(volume.token_id as u32), volume.into()))
+            .collect();
+
+        metrics::histogram!("sql.token.load_market_volumes", start.elapsed());
+        Ok(result)
+    }
+
     pub async fn update_token_market_volume(
         &mut self,
         token_id: TokenId,
@@ -506,6 +874,7 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
     }
 
     /// Given token id, returns its price in USD and a timestamp of the last update.
+    #[tracing::instrument(name = "storage.tokens.get_historical_ticker_price", skip_all)]
     pub async fn get_historical_ticker_price(
         &mut self,
         token_id: TokenId,
@@ -527,10 +896,40 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         Ok(db_price.map(|p| p.into()))
     }
 
+    /// Given the list of token ids, returns the stored USD prices of those tokens.
+    /// Tokens without the stored price are not included in the result.
+    #[tracing::instrument(name = "storage.tokens.load_historical_ticker_prices", skip_all)]
+    pub async fn load_historical_ticker_prices(
+        &mut self,
+        token_ids: &[TokenId],
+    ) -> QueryResult<HashMap<TokenId, TokenPrice>> {
+        let start = Instant::now();
+        let token_ids: Vec<i32> = token_ids.iter().map(|id| **id as i32).collect();
+        let db_prices = sqlx::query_as!(
+            DbTickerPrice,
+            r#"
+            SELECT * FROM ticker_price
+            WHERE token_id = ANY($1)
+            "#,
+            &token_ids
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        let result = db_prices
+            .into_iter()
+            .map(|price| (TokenId(price.token_id as u32), price.into()))
+            .collect();
+
+        metrics::histogram!("sql.token.load_historical_ticker_prices", start.elapsed());
+        Ok(result)
+    }
+
     /// Updates price in USD for the given token.
     ///
     /// Note, that the price precision cannot be greater than `STORED_USD_PRICE_PRECISION`,
     /// so the number might get rounded.
+    #[tracing::instrument(name = "storage.tokens.update_historical_ticker_price", skip_all)]
     pub async fn update_historical_ticker_price(
         &mut self,
         token_id: TokenId,
@@ -557,6 +956,149 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         Ok(())
     }
 
+    /// Stores the snapshot of the token price in USD to the price history.
+    ///
+    /// Note, that the price precision cannot be greater than `STORED_USD_PRICE_PRECISION`,
+    /// so the number might get rounded.
+    pub async fn store_ticker_price_snapshot(
+        &mut self,
+        token_id: TokenId,
+        price: TokenPrice,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let usd_price_rounded = ratio_to_big_decimal(&price.usd_price, STORED_USD_PRICE_PRECISION);
+        sqlx::query!(
+            r#"
+            INSERT INTO ticker_price_history ( token_id, usd_price, created_at )
+            VALUES ( $1, $2, $3 )
+            ON CONFLICT ( token_id, created_at )
+            DO NOTHING
+            "#,
+            *token_id as i32,
+            usd_price_rounded,
+            price.last_updated
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.store_ticker_price_snapshot", start.elapsed());
+        Ok(())
+    }
+
+    /// Removes the snapshots of the token prices created before the given time.
+    /// Returns the number of the removed snapshots.
+    pub async fn remove_ticker_price_history(
+        &mut self,
+        created_before: DateTime<Utc>,
+    ) -> QueryResult<u64> {
+        let start = Instant::now();
+        let removed = sqlx::query!(
+            "DELETE FROM ticker_price_history WHERE created_at < $1",
+            created_before
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!("sql.token.remove_ticker_price_history", start.elapsed());
+        Ok(removed)
+    }
+
+    /// Loads the history of the token price in USD within the given time range.
+    ///
+    /// Snapshots are grouped into the intervals of `resolution_secs` seconds, and
+    /// every returned price is the average over the interval starting at `last_updated`.
+    pub async fn load_ticker_price_history(
+        &mut self,
+        token_id: TokenId,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        resolution_secs: u32,
+    ) -> QueryResult<Vec<TokenPrice>> {
+        let start = Instant::now();
+        let db_prices = sqlx::query_as!(
+            DbTickerPrice,
+            r#"
+            SELECT
+                token_id,
+                AVG(usd_price) AS "usd_price!",
+                to_timestamp(
+                    (extract(epoch FROM created_at)::BIGINT / $4 * $4)::DOUBLE PRECISION
+                ) AS "last_updated!"
+            FROM ticker_price_history
+            WHERE token_id = $1 AND created_at >= $2 AND created_at <= $3
+            GROUP BY token_id, 3
+            ORDER BY 3
+            "#,
+            *token_id as i32,
+            from,
+            to,
+            resolution_secs as i64
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.load_ticker_price_history", start.elapsed());
+        Ok(db_prices.into_iter().map(|price| price.into()).collect())
+    }
+
+    /// Replaces the stored health of the price providers with the given one.
+    pub async fn store_price_providers_health(
+        &mut self,
+        providers: &[StoredPriceProviderHealth],
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        // The providers which are not used anymore are removed.
+        sqlx::query!("DELETE FROM price_provider_health")
+            .execute(transaction.conn())
+            .await?;
+        for provider in providers {
+            sqlx::query!(
+                r#"
+                INSERT INTO price_provider_health (
+                    provider, priority, last_success_at, last_failure_at,
+                    last_error, consecutive_failures, updated_at
+                )
+                VALUES ( $1, $2, $3, $4, $5, $6, $7 )
+                "#,
+                provider.provider,
+                provider.priority,
+                provider.last_success_at,
+                provider.last_failure_at,
+                provider.last_error,
+                provider.consecutive_failures,
+                provider.updated_at
+            )
+            .execute(transaction.conn())
+            .await?;
+        }
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.token.store_price_providers_health", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads the health of the price providers, in the order of their priority.
+    pub async fn load_price_providers_health(
+        &mut self,
+    ) -> QueryResult<Vec<StoredPriceProviderHealth>> {
+        let start = Instant::now();
+        let providers = sqlx::query_as!(
+            StoredPriceProviderHealth,
+            r#"
+            SELECT * FROM price_provider_health
+            ORDER BY priority
+            "#
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.load_price_providers_health", start.elapsed());
+        Ok(providers)
+    }
+
     pub async fn store_nft_factory(
         &mut self,
         creator_id: AccountId,
@@ -583,3 +1125,43 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         Ok(())
     }
 }
+
+/// Builds the SQL conditions of the token filter. The market volume and the symbol pattern
+/// are expected to be bound to the parameters with the given indices.
+fn token_filter_conditions(
+    filter: &TokenFilter,
+    volume_param: usize,
+    symbol_param: usize,
+    denylist_param: usize,
+) -> String {
+    let mut conditions = format!(" AND NOT (tokens.id = ANY(${}))", denylist_param);
+    // ETH always has enough market volume, tokens without the volume are enabled only by the admin.
+    let enabled_for_fees = format!(
+        "(tokens.id = 0 OR COALESCE(tokens.fee_eligibility_override, ticker_market_volume.market_volume >= ${}, false))",
+        volume_param
+    );
+    match filter.enabled_for_fees {
+        Some(true) => conditions.push_str(&format!(" AND {}", enabled_for_fees)),
+        Some(false) => conditions.push_str(&format!(" AND NOT {}", enabled_for_fees)),
+        None => {}
+    }
+    if filter.symbol_prefix.is_some() {
+        conditions.push_str(&format!(" AND tokens.symbol ILIKE ${}", symbol_param));
+    }
+    conditions
+}
+
+fn denylisted_token_ids(denylisted_tokens: &[TokenId]) -> Vec<i32> {
+    denylisted_tokens.iter().map(|id| **id as i32).collect()
+}
+
+/// Returns the `ILIKE` pattern matching the symbols with the prefix of the filter.
+fn symbol_prefix_pattern(filter: &TokenFilter) -> Option<String> {
+    filter.symbol_prefix.as_ref().map(|prefix| {
+        let escaped = prefix
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+        format!("{}%", escaped)
+    })
+}
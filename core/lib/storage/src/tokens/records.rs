@@ -7,7 +7,10 @@ use sqlx::{types::BigDecimal, FromRow};
 // Local imports
 use crate::utils::{address_to_stored_string, stored_str_address_to_address};
 use chrono::{DateTime, Utc};
-use zksync_api_types::v02::token::ApiNFT;
+use zksync_api_types::v02::{
+    status::PriceProviderHealth,
+    token::{ApiNFT, TokenMetadata},
+};
 use zksync_types::{
     tokens::{TokenMarketVolume, TokenPrice},
     AccountId, Address, Token, TokenId, H256, NFT,
@@ -168,3 +171,54 @@ impl From<DBMarketVolume> for TokenMarketVolume {
         }
     }
 }
+
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct StoredTokenMetadata {
+    pub token_id: i32,
+    pub logo_uri: Option<String>,
+    pub project_url: Option<String>,
+    pub display_name: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<StoredTokenMetadata> for TokenMetadata {
+    fn from(val: StoredTokenMetadata) -> Self {
+        Self {
+            logo_uri: val.logo_uri,
+            project_url: val.project_url,
+            display_name: val.display_name,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct StoredDenylistedToken {
+    pub token_id: i32,
+    pub reason: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct StoredPriceProviderHealth {
+    pub provider: String,
+    pub priority: i32,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub last_failure_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub consecutive_failures: i32,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<StoredPriceProviderHealth> for PriceProviderHealth {
+    fn from(val: StoredPriceProviderHealth) -> Self {
+        Self {
+            provider: val.provider,
+            healthy: val.consecutive_failures == 0,
+            last_success_at: val.last_success_at,
+            last_failure_at: val.last_failure_at,
+            last_error: val.last_error,
+            consecutive_failures: val.consecutive_failures as u32,
+            updated_at: val.updated_at,
+        }
+    }
+}
@@ -0,0 +1,234 @@
+// Built-in deps
+use std::time::{Duration, Instant};
+// External imports
+use serde_json::Value;
+// Workspace imports
+use zksync_types::event::EventId;
+// Local imports
+use self::records::{
+    StoredDueDelivery, StoredWebhook, StoredWebhookDelivery, WebhookDeliveryStatus,
+};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Webhooks schema handles the `webhooks` and `webhook_deliveries` tables, storing the callback URLs
+/// notified about the zkSync events by the event listener and the log of the deliveries of the events.
+#[derive(Debug)]
+pub struct WebhooksSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> WebhooksSchema<'a, 'c> {
+    /// Stores the new webhook notified about the events matching the `filter`.
+    pub async fn register_webhook(
+        &mut self,
+        url: &str,
+        secret: &str,
+        filter: &Value,
+        description: &str,
+    ) -> QueryResult<StoredWebhook> {
+        let start = Instant::now();
+        let webhook = sqlx::query_as!(
+            StoredWebhook,
+            r#"
+            INSERT INTO webhooks ( url, secret, filter, description )
+            VALUES ( $1, $2, $3, $4 )
+            RETURNING *
+            "#,
+            url,
+            secret,
+            filter,
+            description
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.webhooks.register_webhook", start.elapsed());
+        Ok(webhook)
+    }
+
+    /// Removes the webhook, returns `false` if there is no such active webhook.
+    /// The pending deliveries of the removed webhook are not attempted anymore.
+    pub async fn remove_webhook(&mut self, id: i32) -> QueryResult<bool> {
+        let start = Instant::now();
+        let removed = sqlx::query!(
+            "UPDATE webhooks SET removed_at = now() WHERE id = $1 AND removed_at IS NULL",
+            id
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected()
+            > 0;
+
+        metrics::histogram!("sql.webhooks.remove_webhook", start.elapsed());
+        Ok(removed)
+    }
+
+    /// Loads the active webhooks, the oldest webhooks go first.
+    pub async fn load_webhooks(&mut self) -> QueryResult<Vec<StoredWebhook>> {
+        let start = Instant::now();
+        let webhooks = sqlx::query_as!(
+            StoredWebhook,
+            "SELECT * FROM webhooks WHERE removed_at IS NULL ORDER BY id"
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.webhooks.load_webhooks", start.elapsed());
+        Ok(webhooks)
+    }
+
+    /// Schedules the deliveries of the serialized events to the webhooks. The event already
+    /// scheduled for the webhook isn't delivered once again.
+    pub async fn schedule_deliveries(
+        &mut self,
+        deliveries: &[(i32, EventId, Value)],
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        if deliveries.is_empty() {
+            return Ok(());
+        }
+
+        let mut webhook_ids = Vec::with_capacity(deliveries.len());
+        let mut event_ids = Vec::with_capacity(deliveries.len());
+        let mut payloads = Vec::with_capacity(deliveries.len());
+        for (webhook_id, event_id, payload) in deliveries {
+            webhook_ids.push(*webhook_id);
+            event_ids.push(**event_id as i64);
+            payloads.push(payload.clone());
+        }
+        sqlx::query!(
+            "INSERT INTO webhook_deliveries (webhook_id, event_id, payload)
+            SELECT u.webhook_id, u.event_id, u.payload
+                FROM UNNEST ($1::int4[], $2::int8[], $3::jsonb[])
+                AS u(webhook_id, event_id, payload)
+            ON CONFLICT (webhook_id, event_id) DO NOTHING",
+            &webhook_ids,
+            &event_ids,
+            &payloads
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.webhooks.schedule_deliveries", start.elapsed());
+        Ok(())
+    }
+
+    /// Leases up to `limit` pending deliveries of the active webhooks whose next attempt is due.
+    /// The leased deliveries aren't returned again for the `lease_duration`, so the delivery
+    /// interrupted without its outcome recorded is attempted once again after it.
+    pub async fn lease_due_deliveries(
+        &mut self,
+        limit: u32,
+        lease_duration: Duration,
+    ) -> QueryResult<Vec<StoredDueDelivery>> {
+        let start = Instant::now();
+        let deliveries = sqlx::query_as!(
+            StoredDueDelivery,
+            r#"
+            UPDATE webhook_deliveries SET next_attempt_at = now() + make_interval(secs => $3)
+            FROM webhooks
+            WHERE webhooks.id = webhook_deliveries.webhook_id AND webhook_deliveries.id IN (
+                SELECT webhook_deliveries.id FROM webhook_deliveries
+                INNER JOIN webhooks ON webhooks.id = webhook_deliveries.webhook_id
+                WHERE webhook_deliveries.status = $1 AND webhooks.removed_at IS NULL
+                    AND webhook_deliveries.next_attempt_at <= now()
+                ORDER BY webhook_deliveries.next_attempt_at, webhook_deliveries.id
+                LIMIT $2
+                FOR UPDATE OF webhook_deliveries SKIP LOCKED
+            )
+            RETURNING webhook_deliveries.id, webhook_deliveries.webhook_id, webhooks.url, webhooks.secret,
+                webhook_deliveries.event_id, webhook_deliveries.payload, webhook_deliveries.attempts
+            "#,
+            WebhookDeliveryStatus::Pending.as_str(),
+            i64::from(limit),
+            lease_duration.as_secs_f64()
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.webhooks.lease_due_deliveries", start.elapsed());
+        Ok(deliveries)
+    }
+
+    /// Records the successful attempt of the delivery.
+    pub async fn record_delivery_success(
+        &mut self,
+        id: i64,
+        response_code: u16,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "UPDATE webhook_deliveries
+            SET (status, attempts, last_response_code, last_error, last_attempt_at) = ($2, attempts + 1, $3, NULL, now())
+            WHERE id = $1",
+            id,
+            WebhookDeliveryStatus::Delivered.as_str(),
+            i32::from(response_code)
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.webhooks.record_delivery_success", start.elapsed());
+        Ok(())
+    }
+
+    /// Records the failed attempt of the delivery. The delivery is attempted once again
+    /// after `retry_in`, or is considered failed if it's `None`.
+    pub async fn record_delivery_failure(
+        &mut self,
+        id: i64,
+        response_code: Option<u16>,
+        error: &str,
+        retry_in: Option<Duration>,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let status = if retry_in.is_some() {
+            WebhookDeliveryStatus::Pending
+        } else {
+            WebhookDeliveryStatus::Failed
+        };
+        sqlx::query!(
+            "UPDATE webhook_deliveries
+            SET (status, attempts, last_response_code, last_error, last_attempt_at, next_attempt_at)
+                = ($2, attempts + 1, $3, $4, now(), now() + make_interval(secs => $5))
+            WHERE id = $1",
+            id,
+            status.as_str(),
+            response_code.map(i32::from),
+            error,
+            retry_in.unwrap_or_default().as_secs_f64()
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.webhooks.record_delivery_failure", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads up to `limit` latest deliveries of the webhook, the latest deliveries go first.
+    pub async fn load_deliveries(
+        &mut self,
+        webhook_id: i32,
+        limit: u32,
+    ) -> QueryResult<Vec<StoredWebhookDelivery>> {
+        let start = Instant::now();
+        let deliveries = sqlx::query_as!(
+            StoredWebhookDelivery,
+            r#"
+            SELECT id, webhook_id, event_id, status, attempts, last_response_code, last_error,
+                created_at, last_attempt_at, next_attempt_at
+            FROM webhook_deliveries
+            WHERE webhook_id = $1
+            ORDER BY id DESC
+            LIMIT $2
+            "#,
+            webhook_id,
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.webhooks.load_deliveries", start.elapsed());
+        Ok(deliveries)
+    }
+}
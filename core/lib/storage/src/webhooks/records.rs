@@ -0,0 +1,61 @@
+// External imports
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+/// Status of the delivery of the event to the webhook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookDeliveryStatus {
+    /// The event awaits for the next attempt.
+    Pending,
+    Delivered,
+    /// All the attempts have failed.
+    Failed,
+}
+
+impl WebhookDeliveryStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Delivered => "delivered",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredWebhook {
+    pub id: i32,
+    pub url: String,
+    pub secret: String,
+    pub filter: Value,
+    pub description: String,
+    pub created_at: DateTime<Utc>,
+    pub removed_at: Option<DateTime<Utc>>,
+}
+
+/// Delivery leased for the next attempt along with its webhook.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredDueDelivery {
+    pub id: i64,
+    pub webhook_id: i32,
+    pub url: String,
+    pub secret: String,
+    pub event_id: i64,
+    pub payload: Value,
+    /// Attempts made before this one.
+    pub attempts: i32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredWebhookDelivery {
+    pub id: i64,
+    pub webhook_id: i32,
+    pub event_id: i64,
+    pub status: String,
+    pub attempts: i32,
+    pub last_response_code: Option<i32>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_attempt_at: Option<DateTime<Utc>>,
+    pub next_attempt_at: DateTime<Utc>,
+}
@@ -0,0 +1,78 @@
+//! Denylist of the tokens, shared by the API servers and the mempool,
+//! so that the denylisted tokens are rejected everywhere in the same way.
+//!
+//! The tokens are denylisted either by their addresses in the config, or by the admin API.
+//! The set of the denylisted tokens is reloaded from the database once it gets older
+//! than `DENYLIST_REFRESH_INTERVAL`, the tokens of the reloaded config are used since then as well.
+
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock as SyncRwLock};
+use std::time::{Duration, Instant};
+
+use tokio::sync::RwLock;
+
+use zksync_storage::ConnectionPool;
+use zksync_types::{Address, TokenId};
+
+/// How long the loaded denylist is used before being reloaded from the database.
+const DENYLIST_REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone)]
+pub struct TokenDenylist {
+    pool: ConnectionPool,
+    /// Addresses of the tokens denylisted by the config.
+    config_tokens: Arc<SyncRwLock<Arc<Vec<Address>>>>,
+    cache: Arc<RwLock<Option<(Arc<HashSet<TokenId>>, Instant)>>>,
+}
+
+impl TokenDenylist {
+    pub fn new(pool: ConnectionPool, config_tokens: Vec<Address>) -> Self {
+        Self {
+            pool,
+            config_tokens: Arc::new(SyncRwLock::new(Arc::new(config_tokens))),
+            cache: Default::default(),
+        }
+    }
+
+    /// Replaces the tokens denylisted by the config, e.g. once the config is reloaded.
+    pub fn set_config_tokens(&self, config_tokens: Vec<Address>) {
+        *self.config_tokens.write().unwrap() = Arc::new(config_tokens);
+    }
+
+    /// Returns the ids of all the denylisted tokens.
+    pub async fn denylisted_tokens(&self) -> anyhow::Result<Arc<HashSet<TokenId>>> {
+        if let Some((tokens, loaded_at)) = self.cache.read().await.as_ref() {
+            if loaded_at.elapsed() < DENYLIST_REFRESH_INTERVAL {
+                return Ok(tokens.clone());
+            }
+        }
+
+        let config_tokens = self.config_tokens.read().unwrap().clone();
+        let tokens = self
+            .pool
+            .access_storage()
+            .await?
+            .tokens_schema()
+            .load_denylisted_token_ids(&config_tokens)
+            .await?;
+        let tokens = Arc::new(tokens);
+        *self.cache.write().await = Some((tokens.clone(), Instant::now()));
+        metrics::gauge!("token_denylist.size", tokens.len() as f64);
+        Ok(tokens)
+    }
+
+    pub async fn is_denylisted(&self, token_id: TokenId) -> anyhow::Result<bool> {
+        Ok(self.denylisted_tokens().await?.contains(&token_id))
+    }
+
+    /// Returns the first of the tokens that is denylisted, if any.
+    pub async fn find_denylisted(
+        &self,
+        tokens: impl IntoIterator<Item = TokenId>,
+    ) -> anyhow::Result<Option<TokenId>> {
+        let denylisted = self.denylisted_tokens().await?;
+        Ok(tokens
+            .into_iter()
+            .find(|token_id| denylisted.contains(token_id)))
+    }
+}
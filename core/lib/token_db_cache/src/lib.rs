@@ -1,27 +1,81 @@
+//! Cache of the tokens loaded from the database.
+//!
+//! The number of the cached tokens is bounded: the least recently used ones are evicted
+//! once the capacity is reached, and every token is reloaded from the database after
+//! the configured time-to-live. The token is cached under every alias it was requested by,
+//! so `invalidate` removes all of them.
+
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
-use std::{collections::HashMap, sync::Arc};
 
-use tokio::sync::RwLock;
+use lru_cache::LruCache;
 
-use zksync_storage::StorageProcessor;
+use zksync_storage::{tokens::TokensStorage, StorageProcessor};
 use zksync_types::tokens::TokenMarketVolume;
 use zksync_types::{Token, TokenId, TokenLike, NFT};
 
-#[derive(Debug, Clone, Default)]
+pub use crate::denylist::TokenDenylist;
+
+mod denylist;
+
+/// Default number of the cached token aliases, enough to keep every alias of ten thousand tokens.
+pub const DEFAULT_TOKEN_CACHE_CAPACITY: usize = 30_000;
+/// Default number of the cached NFTs.
+pub const DEFAULT_NFT_CACHE_CAPACITY: usize = 10_000;
+
+#[derive(Debug, Clone)]
 pub struct TokenDBCache {
-    cache: Arc<RwLock<HashMap<TokenLike, (Token, Instant)>>>,
-    nft_tokens: Arc<RwLock<HashMap<TokenId, NFT>>>,
+    cache: Arc<Mutex<LruCache<TokenLike, (Token, Instant)>>>,
+    nft_tokens: Arc<Mutex<LruCache<TokenId, NFT>>>,
     token_invalidate_cache: Duration,
 }
 
 impl TokenDBCache {
     pub fn new(token_invalidate_cache: Duration) -> Self {
+        Self::with_capacity(
+            token_invalidate_cache,
+            DEFAULT_TOKEN_CACHE_CAPACITY,
+            DEFAULT_NFT_CACHE_CAPACITY,
+        )
+    }
+
+    pub fn with_capacity(
+        token_invalidate_cache: Duration,
+        tokens_capacity: usize,
+        nft_capacity: usize,
+    ) -> Self {
         Self {
+            cache: Arc::new(Mutex::new(LruCache::new(tokens_capacity))),
+            nft_tokens: Arc::new(Mutex::new(LruCache::new(nft_capacity))),
             token_invalidate_cache,
-            ..Default::default()
         }
     }
 
+    /// Returns the cached token if it's not expired, the expired one is removed from the cache.
+    fn get_cached(&self, token_query: &TokenLike) -> Option<Token> {
+        let key = token_query.to_lowercase();
+        let mut cache = self.cache.lock().expect("poisoned token cache");
+        let token = match cache.get_mut(&key) {
+            Some((token, update_time)) if update_time.elapsed() < self.token_invalidate_cache => {
+                Some(token.clone())
+            }
+            Some(_) => {
+                cache.remove(&key);
+                None
+            }
+            None => None,
+        };
+        let access_type = if token.is_some() { "hit" } else { "miss" };
+        metrics::increment_counter!("token_db_cache.access", "type" => access_type);
+        token
+    }
+
+    fn insert_cached(&self, token_query: &TokenLike, token: &Token) {
+        let mut cache = self.cache.lock().expect("poisoned token cache");
+        cache.insert(token_query.to_lowercase(), (token.clone(), Instant::now()));
+        metrics::gauge!("token_db_cache.size", cache.len() as f64);
+    }
+
     /// Version of `get_token` that only attempts to find the token in the cache.
     /// This method should be used in places that don't require the DB connection itself,
     /// so taking a connection from the pool is avoided.
@@ -33,15 +87,7 @@ impl TokenDBCache {
         &self,
         token_query: impl Into<TokenLike>,
     ) -> Option<Token> {
-        let token_query = token_query.into();
-        // Just return token from cache.
-        if let Some((token, update_time)) = self.cache.read().await.get(&token_query.to_lowercase())
-        {
-            if update_time.elapsed() < self.token_invalidate_cache {
-                return Some(token.clone());
-            }
-        }
-        None
+        self.get_cached(&token_query.into())
     }
 
     /// Performs case-insensitive token search.
@@ -52,11 +98,8 @@ impl TokenDBCache {
     ) -> anyhow::Result<Option<Token>> {
         let token_query = token_query.into();
         // Just return token from cache.
-        if let Some((token, update_time)) = self.cache.read().await.get(&token_query.to_lowercase())
-        {
-            if update_time.elapsed() < self.token_invalidate_cache {
-                return Ok(Some(token.clone()));
-            }
+        if let Some(token) = self.get_cached(&token_query) {
+            return Ok(Some(token));
         }
         // Tries to fetch token from the underlying database.
         let token = {
@@ -67,15 +110,68 @@ impl TokenDBCache {
         };
         // Stores received token into the local cache.
         if let Some(token) = &token {
-            self.cache
-                .write()
-                .await
-                .insert(token_query.to_lowercase(), (token.clone(), Instant::now()));
+            self.insert_cached(&token_query, token);
         }
 
         Ok(token)
     }
 
+    /// Performs case-insensitive search of the tokens, in the order of the queries.
+    /// The tokens missing in the cache are loaded from the storage with one lookup.
+    pub async fn get_tokens<S: TokensStorage>(
+        &self,
+        storage: &mut S,
+        token_queries: &[TokenLike],
+    ) -> anyhow::Result<Vec<Option<Token>>> {
+        let mut tokens: Vec<Option<Token>> = token_queries
+            .iter()
+            .map(|token_query| self.get_cached(token_query))
+            .collect();
+        let missing: Vec<TokenLike> = token_queries
+            .iter()
+            .zip(&tokens)
+            .filter(|(_, token)| token.is_none())
+            .map(|(token_query, _)| token_query.clone())
+            .collect();
+        if missing.is_empty() {
+            return Ok(tokens);
+        }
+
+        let loaded = storage.get_tokens(&missing).await?;
+        for (token_query, token) in token_queries.iter().zip(tokens.iter_mut()) {
+            if token.is_some() {
+                continue;
+            }
+            *token = loaded
+                .iter()
+                .find(|loaded| token_matches(loaded, token_query))
+                .cloned();
+            if let Some(token) = token {
+                self.insert_cached(token_query, token);
+            }
+        }
+        Ok(tokens)
+    }
+
+    /// Removes the token from the cache under all of its aliases, so it's loaded
+    /// from the database on the next request. Should be called whenever the properties
+    /// of the token are changed.
+    pub async fn invalidate(&self, token_query: impl Into<TokenLike>) {
+        let token_query = token_query.into();
+        let mut cache = self.cache.lock().expect("poisoned token cache");
+        let mut keys: Vec<_> = cache
+            .iter()
+            .filter(|(_, (token, _))| token_matches(token, &token_query))
+            .map(|(key, _)| key.clone())
+            .collect();
+        keys.push(token_query.to_lowercase());
+        for key in keys {
+            cache.remove(&key);
+        }
+        metrics::increment_counter!("token_db_cache.invalidations");
+        metrics::gauge!("token_db_cache.size", cache.len() as f64);
+    }
+
     pub async fn token_symbol(
         &self,
         storage: &mut StorageProcessor<'_>,
@@ -90,8 +186,14 @@ impl TokenDBCache {
         storage: &mut StorageProcessor<'_>,
         token_id: TokenId,
     ) -> anyhow::Result<Option<NFT>> {
-        if let Some(nft) = self.nft_tokens.read().await.get(&token_id) {
-            return Ok(Some(nft.clone()));
+        let cached = self
+            .nft_tokens
+            .lock()
+            .expect("poisoned NFT cache")
+            .get_mut(&token_id)
+            .cloned();
+        if let Some(nft) = cached {
+            return Ok(Some(nft));
         }
         // It's safe to get from `mint_nft_updates` because the availability of token in balance is regulated
         // by the balance of this token.
@@ -102,8 +204,8 @@ impl TokenDBCache {
             .await?
         {
             self.nft_tokens
-                .write()
-                .await
+                .lock()
+                .expect("poisoned NFT cache")
                 .insert(token_id, token.clone());
             return Ok(Some(token));
         }
@@ -112,14 +214,10 @@ impl TokenDBCache {
 
     pub async fn fill_token_cache(&mut self, storage: &mut StorageProcessor<'_>) {
         let tokens = Self::get_all_tokens(storage).await.unwrap();
-        let mut cache = self.cache.write().await;
         for token in tokens {
-            let symbol = TokenLike::Symbol(token.symbol.clone());
-            let token_id = TokenLike::Id(token.id);
-            let address = TokenLike::Address(token.address);
-            cache.insert(symbol.to_lowercase(), (token.clone(), Instant::now()));
-            cache.insert(token_id.to_lowercase(), (token.clone(), Instant::now()));
-            cache.insert(address.to_lowercase(), (token.clone(), Instant::now()));
+            self.insert_cached(&TokenLike::Symbol(token.symbol.clone()), &token);
+            self.insert_cached(&TokenLike::Id(token.id), &token);
+            self.insert_cached(&TokenLike::Address(token.address), &token);
         }
     }
 
@@ -152,3 +250,98 @@ impl TokenDBCache {
             .await?)
     }
 }
+
+fn token_matches(token: &Token, token_query: &TokenLike) -> bool {
+    match token_query {
+        TokenLike::Id(token_id) => token.id == *token_id,
+        TokenLike::Address(address) => token.address == *address,
+        TokenLike::Symbol(symbol) => token.symbol.to_lowercase() == symbol.to_lowercase(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_storage::tokens::memory::MemoryTokensStorage;
+    use zksync_types::{Address, TokenKind};
+
+    fn token(id: u32, symbol: &str) -> Token {
+        Token::new(
+            TokenId(id),
+            Address::repeat_byte(id as u8),
+            symbol,
+            18,
+            TokenKind::ERC20,
+        )
+    }
+
+    fn cache_aliases(cache: &TokenDBCache, token: &Token) {
+        cache.insert_cached(&TokenLike::Symbol(token.symbol.clone()), token);
+        cache.insert_cached(&TokenLike::Id(token.id), token);
+        cache.insert_cached(&TokenLike::Address(token.address), token);
+    }
+
+    #[tokio::test]
+    async fn token_cache_invalidation() {
+        let cache = TokenDBCache::with_capacity(Duration::from_secs(60), 4, 1);
+        let (dai, usdc) = (token(1, "DAI"), token(2, "USDC"));
+        cache_aliases(&cache, &dai);
+        assert_eq!(
+            cache.try_get_token_from_cache("dai").await,
+            Some(dai.clone())
+        );
+
+        // The least recently used aliases are evicted once the capacity is reached.
+        cache_aliases(&cache, &usdc);
+        assert_eq!(cache.try_get_token_from_cache(dai.id).await, None);
+        assert_eq!(
+            cache.try_get_token_from_cache("DAI").await,
+            Some(dai.clone())
+        );
+        assert_eq!(
+            cache.try_get_token_from_cache(usdc.id).await,
+            Some(usdc.clone())
+        );
+
+        // The token is invalidated under all of its aliases.
+        cache.invalidate(usdc.address).await;
+        assert_eq!(cache.try_get_token_from_cache(usdc.id).await, None);
+        assert_eq!(cache.try_get_token_from_cache("USDC").await, None);
+        assert_eq!(cache.try_get_token_from_cache("DAI").await, Some(dai));
+
+        // The expired tokens are not returned.
+        let cache = TokenDBCache::with_capacity(Duration::from_secs(0), 4, 1);
+        cache_aliases(&cache, &usdc);
+        assert_eq!(cache.try_get_token_from_cache(usdc.id).await, None);
+    }
+
+    #[tokio::test]
+    async fn batch_token_lookup() -> anyhow::Result<()> {
+        let (dai, usdc) = (token(1, "DAI"), token(2, "USDC"));
+        let mut storage = MemoryTokensStorage::new().with_tokens(vec![dai.clone(), usdc.clone()]);
+        let cache = TokenDBCache::new(Duration::from_secs(60));
+
+        // The tokens requested in the different forms are loaded with one lookup.
+        let queries = vec![
+            TokenLike::Id(dai.id),
+            TokenLike::Symbol("usdc".to_string()),
+            TokenLike::Address(dai.address),
+            TokenLike::Symbol("UNKNOWN".to_string()),
+        ];
+        let tokens = cache.get_tokens(&mut storage, &queries).await?;
+        assert_eq!(
+            tokens,
+            vec![Some(dai.clone()), Some(usdc.clone()), Some(dai), None]
+        );
+        assert_eq!(storage.token_lookups(), 1);
+
+        // The found tokens are cached, only the unknown one is looked up again.
+        let tokens = cache.get_tokens(&mut storage, &queries[1..]).await?;
+        assert_eq!(tokens[0], Some(usdc));
+        assert_eq!(storage.token_lookups(), 2);
+        cache.get_tokens(&mut storage, &queries[..3]).await?;
+        assert_eq!(storage.token_lookups(), 2);
+
+        Ok(())
+    }
+}
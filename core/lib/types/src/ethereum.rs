@@ -1,10 +1,11 @@
 //! Common primitives for the Ethereum network interaction.
 // Built-in deps
 // External uses
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 // Local uses
 use crate::aggregated_operations::{AggregatedActionType, AggregatedOperation};
-use zksync_basic_types::{H256, U256};
+use zksync_basic_types::{Address, H256, U256};
 
 /// Numerical identifier of the Ethereum operation.
 pub type EthOpId = i64;
@@ -18,12 +19,19 @@ pub struct ETHOperation {
     pub op_type: AggregatedActionType,
     /// Optional ZKSync operation associated with Ethereum operation.
     pub op: Option<(i64, AggregatedOperation)>,
-    /// Used nonce (fixed for all the sent transactions).
+    /// Used nonce (fixed for all the sent transactions, unless the operation is moved
+    /// to another operator account).
     pub nonce: U256,
+    /// Operator account sending the transactions, `None` for the operations sent by the main
+    /// operator account before the operator accounts were tracked.
+    pub operator: Option<Address>,
     /// Deadline block of the last sent transaction.
     pub last_deadline_block: u64,
-    /// Gas price used in the last sent transaction.
+    /// Gas price used in the last sent transaction, it's the max fee per gas
+    /// for the EIP-1559 transactions.
     pub last_used_gas_price: U256,
+    /// Priority fee per gas used in the last sent transaction, `None` for the legacy transactions.
+    pub last_used_priority_fee: Option<U256>,
     /// Hashes of all the sent transactions.
     pub used_tx_hashes: Vec<H256>,
     /// Tx payload (not signed).
@@ -49,6 +57,7 @@ impl ETHOperation {
     pub fn complete(&mut self, inserted_data: InsertedOperationResponse) {
         self.id = inserted_data.id;
         self.nonce = inserted_data.nonce;
+        self.operator = inserted_data.operator;
     }
 }
 
@@ -59,8 +68,10 @@ impl PartialEq for ETHOperation {
         // However, the volatile fields (e.g. `used_tx_hashes` and `confirmed`) may vary
         // for the same operation in different states, so we compare them as well.
         (self.id == other.id)
+            && (self.operator == other.operator)
             && (self.last_deadline_block == other.last_deadline_block)
             && (self.last_used_gas_price == other.last_used_gas_price)
+            && (self.last_used_priority_fee == other.last_used_priority_fee)
             && (self.used_tx_hashes == other.used_tx_hashes)
             && (self.confirmed == other.confirmed)
             && (self.final_hash == other.final_hash)
@@ -76,8 +87,68 @@ pub struct InsertedOperationResponse {
     /// Nonce assigned for the Ethereum operation. Meant to be used for all the
     /// transactions sent within one particular Ethereum operation.
     pub nonce: U256,
+    /// Operator account the nonce is assigned for, `None` if the operator accounts aren't tracked.
+    pub operator: Option<Address>,
 }
 
 #[derive(Debug, Error, PartialEq)]
 #[error("Unknown type of operation: {0}")]
 pub struct UnknownOperationType(pub String);
+
+/// Reason of sending the Ethereum transaction of the operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ETHTxAttemptReason {
+    /// The first transaction of the operation.
+    Initial,
+    /// Replacement of the stuck transaction.
+    Stuck,
+    /// The operation is moved to another operator account.
+    Rotation,
+    /// Replacement of the stuck transaction requested by the admin.
+    ManualBump,
+}
+
+impl ETHTxAttemptReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Initial => "Initial",
+            Self::Stuck => "Stuck",
+            Self::Rotation => "Rotation",
+            Self::ManualBump => "ManualBump",
+        }
+    }
+}
+
+impl std::str::FromStr for ETHTxAttemptReason {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Initial" => Ok(Self::Initial),
+            "Stuck" => Ok(Self::Stuck),
+            "Rotation" => Ok(Self::Rotation),
+            "ManualBump" => Ok(Self::ManualBump),
+            _ => Err("Incorrect Ethereum transaction attempt reason".to_owned()),
+        }
+    }
+}
+
+/// Ethereum transaction sent for the operation. The attempts of every operation are kept,
+/// so the history of the stuck operations can be inspected.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ETHTxAttempt {
+    pub eth_op_id: EthOpId,
+    pub tx_hash: H256,
+    pub nonce: U256,
+    /// Gas price of the transaction, it's the max fee per gas for the EIP-1559 transactions.
+    pub gas_price: U256,
+    /// Priority fee per gas, `None` for the legacy transactions.
+    pub priority_fee: Option<U256>,
+    /// Operator account which sent the transaction, `None` if the operator accounts aren't tracked.
+    pub operator: Option<Address>,
+    /// Ethereum block at which the transaction was sent.
+    pub sent_at_block: u64,
+    pub reason: ETHTxAttemptReason,
+}
@@ -10,7 +10,9 @@ pub use crate::EventId;
 
 pub mod account;
 pub mod block;
+pub mod stream;
 pub mod transaction;
+pub mod webhook;
 
 pub mod test_data;
 
@@ -0,0 +1,155 @@
+// Built-in uses
+// External uses
+use serde::{Deserialize, Serialize};
+// Workspace uses
+// Local uses
+use super::{
+    block::{BlockDetails, BlockStatus},
+    transaction::{TransactionEvent, TransactionStatus},
+    EventData, EventId, ZkSyncEvent,
+};
+use crate::BlockNumber;
+
+/// Version of the schema of the events published to the event stream. Incremented
+/// on every change of the schema which isn't backward compatible.
+pub const STREAM_SCHEMA_VERSION: u32 = 1;
+
+/// Types of the events published to the event stream, every type has its own topic.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StreamEventType {
+    BlockCommitted,
+    BlockFinalized,
+    /// The transaction is executed in the committed block, either successfully or not.
+    TxExecuted,
+}
+
+impl StreamEventType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::BlockCommitted => "block_committed",
+            Self::BlockFinalized => "block_finalized",
+            Self::TxExecuted => "tx_executed",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum StreamEventData {
+    Transaction(TransactionEvent),
+    Block(BlockDetails),
+}
+
+/// The event published to the event stream.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamEvent {
+    pub schema_version: u32,
+    /// Id of the event, increasing with every next event. The consumers may use it
+    /// to skip the events published more than once.
+    pub event_id: EventId,
+    pub event_type: StreamEventType,
+    pub block_number: BlockNumber,
+    pub data: StreamEventData,
+}
+
+impl StreamEvent {
+    /// Returns the stream event of the zkSync event, or `None` if the event isn't published.
+    pub fn from_event(event: &ZkSyncEvent) -> Option<Self> {
+        let (event_type, data) = match &event.data {
+            EventData::Block(block_event) => {
+                let event_type = match block_event.status {
+                    BlockStatus::Committed => StreamEventType::BlockCommitted,
+                    BlockStatus::Finalized => StreamEventType::BlockFinalized,
+                    BlockStatus::Reverted => return None,
+                };
+                (
+                    event_type,
+                    StreamEventData::Block(block_event.block_details.clone()),
+                )
+            }
+            EventData::Transaction(tx_event) => match tx_event.status {
+                TransactionStatus::Committed | TransactionStatus::Rejected => (
+                    StreamEventType::TxExecuted,
+                    StreamEventData::Transaction(tx_event.clone()),
+                ),
+                TransactionStatus::Queued | TransactionStatus::Finalized => return None,
+            },
+            EventData::Account(_) => return None,
+        };
+
+        Some(Self {
+            schema_version: STREAM_SCHEMA_VERSION,
+            event_id: event.id,
+            event_type,
+            block_number: event.block_number,
+            data,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        event::{
+            account::AccountStateChangeStatus,
+            test_data::{get_account_event, get_block_event, get_transaction_event},
+            transaction::TransactionType,
+        },
+        AccountId, TokenId,
+    };
+
+    #[test]
+    fn test_stream_events() {
+        let event_type = |event: &ZkSyncEvent| StreamEvent::from_event(event).map(|e| e.event_type);
+
+        assert_eq!(
+            event_type(&get_block_event(BlockStatus::Committed)),
+            Some(StreamEventType::BlockCommitted)
+        );
+        assert_eq!(
+            event_type(&get_block_event(BlockStatus::Finalized)),
+            Some(StreamEventType::BlockFinalized)
+        );
+        assert_eq!(event_type(&get_block_event(BlockStatus::Reverted)), None);
+
+        let tx_event = |status| {
+            get_transaction_event(TransactionType::Transfer, AccountId(1), TokenId(0), status)
+        };
+        assert_eq!(
+            event_type(&tx_event(TransactionStatus::Committed)),
+            Some(StreamEventType::TxExecuted)
+        );
+        assert_eq!(
+            event_type(&tx_event(TransactionStatus::Rejected)),
+            Some(StreamEventType::TxExecuted)
+        );
+        assert_eq!(event_type(&tx_event(TransactionStatus::Queued)), None);
+        assert_eq!(event_type(&tx_event(TransactionStatus::Finalized)), None);
+
+        let account_event =
+            get_account_event(AccountId(1), None, AccountStateChangeStatus::Committed);
+        assert_eq!(event_type(&account_event), None);
+    }
+
+    #[test]
+    fn test_stream_event_schema() {
+        let event = StreamEvent::from_event(&get_transaction_event(
+            TransactionType::Transfer,
+            AccountId(1),
+            TokenId(0),
+            TransactionStatus::Committed,
+        ))
+        .unwrap();
+        let value = serde_json::to_value(&event).unwrap();
+        assert_eq!(value["schema_version"], STREAM_SCHEMA_VERSION);
+        assert_eq!(value["event_type"], "tx_executed");
+        assert_eq!(value["data"]["status"], "committed");
+
+        // The consumers are able to parse the published events.
+        let parsed: StreamEvent = serde_json::from_value(value).unwrap();
+        assert_eq!(parsed.event_type, StreamEventType::TxExecuted);
+        assert!(matches!(parsed.data, StreamEventData::Transaction(_)));
+    }
+}
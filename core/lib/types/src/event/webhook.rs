@@ -0,0 +1,144 @@
+// Built-in uses
+use std::collections::HashSet;
+// External uses
+use serde::Deserialize;
+use serde_json::Value;
+// Workspace uses
+// Local uses
+use super::{
+    transaction::{TransactionStatus, TransactionType},
+    EventData, ZkSyncEvent,
+};
+use crate::{Address, TokenId};
+
+/// Fields of the transactions and their priority operations holding the addresses
+/// of the involved accounts.
+const ADDRESS_FIELDS: &[&str] = &[
+    "from",
+    "to",
+    "account",
+    "target",
+    "recipient",
+    "creatorAddress",
+    "eth_address",
+];
+
+/// Filter of the events delivered to the webhook. The webhooks are notified about
+/// the transaction events only, every unset field of the filter matches any event.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookFilter {
+    /// Addresses of the L1 or L2 accounts involved into the transaction.
+    pub addresses: Option<HashSet<Address>>,
+    pub tokens: Option<HashSet<TokenId>>,
+    pub types: Option<HashSet<TransactionType>>,
+    pub status: Option<TransactionStatus>,
+}
+
+impl WebhookFilter {
+    pub fn matches(&self, event: &ZkSyncEvent) -> bool {
+        let tx_event = match &event.data {
+            EventData::Transaction(tx_event) => tx_event,
+            _ => return false,
+        };
+        if let Some(status) = &self.status {
+            if tx_event.status != *status {
+                return false;
+            }
+        }
+        if let Some(tx_types) = &self.types {
+            if !tx_types.contains(&tx_event.tx_type()) {
+                return false;
+            }
+        }
+        if let Some(token_ids) = &self.tokens {
+            if !token_ids.contains(&tx_event.token_id) {
+                return false;
+            }
+        }
+        if let Some(addresses) = &self.addresses {
+            if !involved_addresses(&tx_event.tx).any(|address| addresses.contains(&address)) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Returns the addresses found in the serialized transaction. The priority operations
+/// hold the addresses in their `priority_op` field.
+fn involved_addresses(tx: &Value) -> impl Iterator<Item = Address> + '_ {
+    vec![tx, &tx["priority_op"]].into_iter().flat_map(|object| {
+        ADDRESS_FIELDS
+            .iter()
+            .filter_map(move |field| serde_json::from_value(object.get(field)?.clone()).ok())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{event::test_data::get_transaction_event, AccountId};
+
+    fn transaction_event(
+        tx_type: TransactionType,
+        status: TransactionStatus,
+        tx: Value,
+    ) -> ZkSyncEvent {
+        let mut event = get_transaction_event(tx_type, AccountId(1), TokenId(0), status);
+        if let EventData::Transaction(tx_event) = &mut event.data {
+            tx_event.tx = tx;
+        }
+        event
+    }
+
+    #[test]
+    fn test_webhook_filter() {
+        let sender = Address::repeat_byte(1);
+        let recipient = Address::repeat_byte(2);
+        let transfer = transaction_event(
+            TransactionType::Transfer,
+            TransactionStatus::Finalized,
+            json!({ "type": "Transfer", "from": sender, "to": recipient }),
+        );
+        let deposit = transaction_event(
+            TransactionType::Deposit,
+            TransactionStatus::Committed,
+            json!({ "type": "Deposit", "priority_op": { "from": sender, "to": sender } }),
+        );
+
+        // The empty filter matches every transaction.
+        let mut filter: WebhookFilter = serde_json::from_str("{}").unwrap();
+        assert!(filter.matches(&transfer));
+        assert!(filter.matches(&deposit));
+
+        // The addresses are looked up in the priority operations too.
+        filter.addresses = Some(vec![recipient].into_iter().collect());
+        assert!(filter.matches(&transfer));
+        assert!(!filter.matches(&deposit));
+        filter.addresses = Some(vec![sender].into_iter().collect());
+        assert!(filter.matches(&transfer));
+        assert!(filter.matches(&deposit));
+
+        filter.status = Some(TransactionStatus::Finalized);
+        assert!(filter.matches(&transfer));
+        assert!(!filter.matches(&deposit));
+
+        filter.status = None;
+        filter.types = Some(
+            vec![TransactionType::Deposit, TransactionType::Withdraw]
+                .into_iter()
+                .collect(),
+        );
+        assert!(!filter.matches(&transfer));
+        assert!(filter.matches(&deposit));
+
+        filter.tokens = Some(vec![TokenId(1)].into_iter().collect());
+        assert!(!filter.matches(&deposit));
+
+        // Unknown fields are rejected.
+        assert!(serde_json::from_str::<WebhookFilter>(r#"{"accounts": [1]}"#).is_err());
+    }
+}
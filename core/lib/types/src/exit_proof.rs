@@ -0,0 +1,69 @@
+//! Exit proofs used to withdraw the funds from the contract in the exodus mode.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use zksync_basic_types::{AccountId, Address, BlockNumber, TokenId, H256};
+use zksync_crypto::proof::EncodedSingleProof;
+use zksync_utils::BigUintSerdeWrapper;
+
+use crate::block::Block;
+
+/// Information about the block as it's stored by the contract,
+/// the exit transaction refers to the last verified block with it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredBlockInfo {
+    pub block_number: BlockNumber,
+    pub priority_operations: u64,
+    pub pending_onchain_operations_hash: H256,
+    pub timestamp: u64,
+    pub state_hash: H256,
+    pub commitment: H256,
+}
+
+impl StoredBlockInfo {
+    pub fn from_block(block: &Block) -> Self {
+        Self {
+            block_number: block.block_number,
+            priority_operations: block.number_of_processed_prior_ops(),
+            pending_onchain_operations_hash: block.get_onchain_operations_block_info().1,
+            timestamp: block.timestamp,
+            state_hash: block.get_eth_encoded_root(),
+            commitment: block.block_commitment,
+        }
+    }
+}
+
+/// Inputs of the exit transaction of the contract.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExitProofData {
+    pub stored_block_info: StoredBlockInfo,
+    pub owner: Address,
+    pub account_id: AccountId,
+    pub token_id: TokenId,
+    pub amount: BigUintSerdeWrapper,
+    pub nft_creator_id: AccountId,
+    pub nft_creator_address: Address,
+    pub nft_serial_id: u32,
+    pub nft_content_hash: H256,
+    pub proof: EncodedSingleProof,
+    pub token_address: Address,
+}
+
+/// Request for the exit proof of the account balance in the token.
+///
+/// The proof is generated for the verified state of the requested block,
+/// it's `None` until the proof generator processes the request or if it has failed.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExitProofRequest {
+    pub account_id: AccountId,
+    pub token_id: TokenId,
+    pub block_number: BlockNumber,
+    pub created_at: DateTime<Utc>,
+    pub generated_at: Option<DateTime<Utc>>,
+    pub proof_data: Option<ExitProofData>,
+    /// The reason why the proof can't be generated.
+    pub error: Option<String>,
+}
@@ -41,6 +41,7 @@ pub mod aggregated_operations;
 pub mod block;
 pub mod ethereum;
 pub mod event;
+pub mod exit_proof;
 pub mod fee;
 pub mod forced_exit_requests;
 pub mod gas_counter;
@@ -4,6 +4,7 @@ use thiserror::Error;
 use crate::tx::{
     change_pubkey, close, forced_exit, mint_nft, swap, transfer, withdraw, withdraw_nft,
 };
+use crate::TokenId;
 #[derive(Debug, Error, PartialEq)]
 pub enum ChangePubkeySignedDataError {
     #[error("Change pubkey signed message does not match in size. Actual: {actual}, expected: {expected}")]
@@ -57,6 +58,27 @@ pub enum TxAddError {
 
     #[error("Too many Ethereum signatures provided")]
     EthSignaturesLimitExceeded,
+
+    #[error("Token {0} is not supported")]
+    TokenDenylisted(TokenId),
+
+    #[error("Nonces of the account transactions in the batch are not consecutive")]
+    BatchNonceGap,
+
+    #[error("Fee of the replacement transaction is too low, it should be at least {0}% higher than the pending one")]
+    ReplacementFeeTooLow(u64),
+
+    #[error("Replacement transaction should pay the fee in the same token as the pending one")]
+    ReplacementFeeTokenMismatch,
+
+    #[error("Pending transaction with the same nonce is a part of a batch and can't be replaced")]
+    ReplacedTxInBatch,
+
+    #[error("Pending transaction with the same nonce is already executed and can't be replaced")]
+    ReplacedTxExecuted,
+
+    #[error("Transaction is expired, its validity period is over")]
+    TxExpired,
 }
 
 #[derive(Error, Debug, Copy, Clone, Serialize, Deserialize)]
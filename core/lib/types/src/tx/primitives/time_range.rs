@@ -39,6 +39,16 @@ impl TimeRange {
     pub fn intersects(&self, other: Self) -> bool {
         self.valid_from <= other.valid_until && other.valid_from <= self.valid_until
     }
+
+    /// Returns `true` if the transaction can't be executed yet at the given timestamp.
+    pub fn is_scheduled(&self, timestamp: u64) -> bool {
+        timestamp < self.valid_from
+    }
+
+    /// Returns `true` if the transaction can't be executed anymore at the given timestamp.
+    pub fn is_expired(&self, timestamp: u64) -> bool {
+        self.valid_until < timestamp
+    }
 }
 
 impl Default for TimeRange {
@@ -0,0 +1,89 @@
+//! Authorization by the JSON web tokens signed by the shared secret.
+//!
+//! The tokens are issued by the provers and the admin clients, and checked by the
+//! prover server and the admin API.
+
+use std::time::{Duration, UNIX_EPOCH};
+
+use jsonwebtoken::{
+    decode, encode, errors::Result as JwtResult, DecodingKey, EncodingKey, Header, Validation,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PayloadAuthToken {
+    /// Subject (whom auth token refers to).
+    sub: String,
+    /// Expiration time (as UTC timestamp).
+    exp: usize,
+}
+
+/// Encodes the auth token signed by the secret, the token is valid for the given period.
+pub fn encode_auth_token(secret: &str, period_availability: Duration) -> JwtResult<String> {
+    // Time (Unix Timestamp) until which the token will be valid
+    let exp = UNIX_EPOCH.elapsed().expect("failed to get system time") + period_availability;
+
+    encode(
+        &Header::default(),
+        &PayloadAuthToken {
+            sub: "Authorization".to_string(),
+            exp: exp.as_secs() as usize,
+        },
+        &EncodingKey::from_secret(secret.as_ref()),
+    )
+}
+
+/// The structure that stores the secret key for checking JsonWebToken matching.
+pub struct AuthTokenValidator<'a> {
+    decoding_key: DecodingKey<'a>,
+}
+
+impl<'a> AuthTokenValidator<'a> {
+    pub fn new(secret: &'a str) -> Self {
+        Self {
+            decoding_key: DecodingKey::from_secret(secret.as_ref()),
+        }
+    }
+
+    /// Checks whether the secret key and the authorization token match.
+    pub fn validate_auth_token(&self, token: &str) -> JwtResult<()> {
+        decode::<PayloadAuthToken>(token, &self.decoding_key, &Validation::default())?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expired_auth_token(secret: &str) -> String {
+        let exp = UNIX_EPOCH.elapsed().unwrap() - Duration::from_secs(3600);
+        encode(
+            &Header::default(),
+            &PayloadAuthToken {
+                sub: "Authorization".to_string(),
+                exp: exp.as_secs() as usize,
+            },
+            &EncodingKey::from_secret(secret.as_ref()),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn auth_token_validation() {
+        let validator = AuthTokenValidator::new("secret");
+        let period = Duration::from_secs(3600);
+
+        assert!(validator
+            .validate_auth_token(&encode_auth_token("secret", period).unwrap())
+            .is_ok());
+        assert!(validator
+            .validate_auth_token(&encode_auth_token("other", period).unwrap())
+            .is_err());
+        // The expired token is rejected.
+        assert!(validator
+            .validate_auth_token(&expired_auth_token("secret"))
+            .is_err());
+    }
+}
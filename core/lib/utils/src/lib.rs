@@ -1,5 +1,6 @@
 //! Various helpers used in the zkSync stack.
 
+pub mod auth;
 mod convert;
 mod env_tools;
 mod format;
@@ -11,15 +11,24 @@
 //! Integration with sentry for catching errors and react on them immediately
 //! https://docs.sentry.io/platforms/rust/
 //!
+//! If the `MISC_OTLP_ENDPOINT` env variable is set, the spans are exported over OTLP as well,
+//! so that the traces can be seen in Jaeger, Tempo, etc.
+//!
 
 use std::{borrow::Cow, str::FromStr};
 
+use opentelemetry::{sdk::Resource, KeyValue};
+
 pub use sentry;
 use sentry::{types::Dsn, ClientInitGuard};
 
 pub use tracing as __tracing;
 pub use tracing::{debug, info, log, trace};
 use tracing_appender::non_blocking::WorkerGuard;
+use tracing_opentelemetry::OpenTelemetryLayer;
+use tracing_subscriber::{
+    fmt::format::FmtSpan, layer::SubscriberExt, registry::LookupSpan, util::SubscriberInitExt,
+};
 
 #[macro_export]
 macro_rules! warn {
@@ -49,6 +58,16 @@ macro_rules! error {
 pub struct VlogGuard {
     _sentry_guard: Option<ClientInitGuard>,
     _logger_guard: WorkerGuard,
+    _otlp_guard: Option<OtlpGuard>,
+}
+
+/// Exports the remaining spans when dropped.
+struct OtlpGuard;
+
+impl Drop for OtlpGuard {
+    fn drop(&mut self) {
+        opentelemetry::global::shutdown_tracer_provider();
+    }
 }
 
 fn get_sentry_url() -> Option<Dsn> {
@@ -60,6 +79,56 @@ fn get_sentry_url() -> Option<Dsn> {
     None
 }
 
+fn get_otlp_endpoint() -> Option<String> {
+    match std::env::var("MISC_OTLP_ENDPOINT") {
+        Ok(endpoint) if !endpoint.is_empty() && endpoint != "unset" => Some(endpoint),
+        _ => None,
+    }
+}
+
+/// Name of the service in the exported traces, which is the name of the binary.
+fn service_name() -> String {
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| {
+            exe.file_stem()
+                .map(|name| name.to_string_lossy().into_owned())
+        })
+        .unwrap_or_else(|| "zksync".to_string())
+}
+
+/// Builds the layer exporting the spans to the OTLP collector,
+/// the spans are exported in batches by the tokio task.
+///
+/// Returns an error instead if there is no tokio runtime to spawn the task on
+/// or the exporter can't be installed.
+fn otlp_layer<S>(
+    endpoint: String,
+) -> Result<OpenTelemetryLayer<S, opentelemetry::sdk::trace::Tracer>, String>
+where
+    S: tracing::Subscriber + for<'span> LookupSpan<'span>,
+{
+    if tokio::runtime::Handle::try_current().is_err() {
+        return Err("the logger isn't initialized within the tokio runtime".to_string());
+    }
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(endpoint),
+        )
+        .with_trace_config(
+            opentelemetry::sdk::trace::config().with_resource(Resource::new(vec![KeyValue::new(
+                "service.name",
+                service_name(),
+            )])),
+        )
+        .install_batch(opentelemetry::runtime::Tokio)
+        .map_err(|err| format!("failed to install the OTLP exporter: {}", err))?;
+    Ok(tracing_opentelemetry::layer().with_tracer(tracer))
+}
+
 /// Initialize logging with non blocking tracing and set up log format
 ///
 /// If the sentry URL is provided via an environment variable, this function will also initialize sentry.
@@ -68,14 +137,33 @@ fn get_sentry_url() -> Option<Dsn> {
 /// The full description can be found in the official documentation:
 /// https://docs.sentry.io/platforms/rust/#configure
 /// https://docs.rs/tracing-appender/0.2.2/tracing_appender/non_blocking/index.html
+///
+/// If the OTLP endpoint is provided, the function should be called within the tokio runtime,
+/// otherwise the spans aren't exported.
 pub fn init() -> VlogGuard {
     let log_format = std::env::var("MISC_LOG_FORMAT").unwrap_or_else(|_| "plain".to_string());
     let (non_blocking, _logger_guard) = tracing_appender::non_blocking(std::io::stdout());
+    // The closed spans are logged with their durations, e.g. to find out which part
+    // of the API request handling is slow.
+    let span_events = match std::env::var("MISC_LOG_SPAN_TIMINGS").as_deref() {
+        Ok("true") => FmtSpan::CLOSE,
+        _ => FmtSpan::NONE,
+    };
+    let otlp_endpoint = get_otlp_endpoint();
+    let otlp_enabled = otlp_endpoint.is_some();
+    let mut otlp_error = None;
     match log_format.as_str() {
         "plain" => {
             tracing_subscriber::fmt::Subscriber::builder()
                 .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
                 .with_writer(non_blocking)
+                .with_span_events(span_events)
+                .finish()
+                .with(otlp_endpoint.and_then(|endpoint| {
+                    otlp_layer(endpoint)
+                        .map_err(|err| otlp_error = Some(err))
+                        .ok()
+                }))
                 .init();
         }
         "json" => {
@@ -83,12 +171,23 @@ pub fn init() -> VlogGuard {
             tracing_subscriber::fmt::Subscriber::builder()
                 .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
                 .with_writer(non_blocking)
+                .with_span_events(span_events)
                 .with_timer(timer)
                 .json()
+                .finish()
+                .with(otlp_endpoint.and_then(|endpoint| {
+                    otlp_layer(endpoint)
+                        .map_err(|err| otlp_error = Some(err))
+                        .ok()
+                }))
                 .init();
         }
         _ => panic!("MISC_LOG_FORMAT has an unexpected value {}", log_format),
     };
+    if let Some(err) = &otlp_error {
+        tracing::warn!("The spans aren't exported over OTLP, {}", err);
+    }
+    let _otlp_guard = (otlp_enabled && otlp_error.is_none()).then(|| OtlpGuard);
 
     let _sentry_guard = get_sentry_url().map(|sentry_url| {
         sentry::init((
@@ -106,5 +205,6 @@ pub fn init() -> VlogGuard {
     VlogGuard {
         _sentry_guard,
         _logger_guard,
+        _otlp_guard,
     }
 }
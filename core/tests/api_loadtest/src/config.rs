@@ -0,0 +1,55 @@
+use serde::Deserialize;
+
+/// Configuration of the API loadtest.
+///
+/// Unlike `loadnext`, the test doesn't need funded accounts: it measures the latency of the
+/// API endpoints and doesn't check whether the submitted transactions are executed.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApiLoadtestConfig {
+    /// Address of the zkSync REST API.
+    pub api_url: String,
+
+    /// Amount of the requests which are sent concurrently.
+    pub concurrency: usize,
+    /// Duration of the test in seconds.
+    pub duration_secs: u64,
+    /// Optional limit of the requests amount, the test stops after sending this amount
+    /// of the requests even if its duration hasn't passed.
+    pub requests_limit: Option<u64>,
+
+    /// Relative weight of the token USD price requests in the mix.
+    pub token_price_weight: u32,
+    /// Relative weight of the account state requests in the mix.
+    pub account_state_weight: u32,
+    /// Relative weight of the transaction submissions in the mix.
+    pub tx_submission_weight: u32,
+
+    /// Symbols, addresses or ids of the tokens which prices are requested.
+    pub tokens: Vec<String>,
+    /// Amount of the randomly generated accounts, which states are requested and
+    /// which sign the submitted transactions.
+    pub accounts_amount: usize,
+}
+
+impl ApiLoadtestConfig {
+    pub fn from_env() -> envy::Result<Self> {
+        envy::prefixed("API_LOADTEST_").from_env()
+    }
+}
+
+impl Default for ApiLoadtestConfig {
+    fn default() -> Self {
+        // Values which correspond to the local development deployment.
+        Self {
+            api_url: "http://127.0.0.1:3001".into(),
+            concurrency: 16,
+            duration_secs: 60,
+            requests_limit: None,
+            token_price_weight: 5,
+            account_state_weight: 3,
+            tx_submission_weight: 2,
+            tokens: vec!["ETH".into(), "DAI".into()],
+            accounts_amount: 10,
+        }
+    }
+}
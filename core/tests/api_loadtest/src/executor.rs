@@ -0,0 +1,74 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::{
+    config::ApiLoadtestConfig,
+    metrics::LoadtestReport,
+    request::{RequestMix, RequestSender},
+};
+
+/// Sends the mix of the requests from the concurrent workers until the duration
+/// of the test passes or the limit of the requests is reached.
+#[derive(Debug)]
+pub struct Executor {
+    config: ApiLoadtestConfig,
+    mix: RequestMix,
+    sender: RequestSender,
+    sent_requests: AtomicU64,
+}
+
+impl Executor {
+    pub fn new(config: ApiLoadtestConfig) -> anyhow::Result<Self> {
+        anyhow::ensure!(config.concurrency > 0, "Concurrency must be positive");
+
+        Ok(Self {
+            mix: RequestMix::from_config(&config)?,
+            sender: RequestSender::new(&config)?,
+            config,
+            sent_requests: AtomicU64::new(0),
+        })
+    }
+
+    pub async fn start(&self) -> LoadtestReport {
+        vlog::info!(
+            "Sending requests to {} from {} workers for {} seconds",
+            self.config.api_url,
+            self.config.concurrency,
+            self.config.duration_secs
+        );
+        let start = Instant::now();
+        let deadline = start + Duration::from_secs(self.config.duration_secs);
+
+        let workers = (0..self.config.concurrency).map(|_| self.run_worker(deadline));
+        let mut report = LoadtestReport::default();
+        for worker_report in futures::future::join_all(workers).await {
+            report.merge(worker_report);
+        }
+        report.elapsed = start.elapsed();
+        report
+    }
+
+    async fn run_worker(&self, deadline: Instant) -> LoadtestReport {
+        let mut rng = StdRng::from_entropy();
+        let mut report = LoadtestReport::default();
+        while Instant::now() < deadline && self.take_request() {
+            let kind = self.mix.pick(&mut rng);
+            let start = Instant::now();
+            let outcome = self.sender.send(kind, &mut rng).await;
+            report.add(kind, outcome, start.elapsed());
+        }
+        report
+    }
+
+    /// Returns `false` if the limit of the requests is reached.
+    fn take_request(&self) -> bool {
+        let sent_requests = self.sent_requests.fetch_add(1, Ordering::Relaxed);
+        self.config
+            .requests_limit
+            .map_or(true, |limit| sent_requests < limit)
+    }
+}
@@ -0,0 +1,4 @@
+pub mod config;
+pub mod executor;
+pub mod metrics;
+pub mod request;
@@ -0,0 +1,33 @@
+//! API loadtest: an utility to measure the latency of the zkSync REST API under load.
+//!
+//! It sends the configurable mix of the token price, account state and transaction submission
+//! requests, and reports the latency percentiles of each kind. For the configuration,
+//! see `README.md`. Without the variables provided, the local development server is tested.
+
+use api_loadtest::{config::ApiLoadtestConfig, executor::Executor};
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let _vlog_guard = vlog::init();
+
+    let config = ApiLoadtestConfig::from_env().unwrap_or_else(|err| {
+        vlog::warn!(
+            "Loading the config from the environment variables failed: {:?}",
+            err
+        );
+        vlog::warn!("Using the hard-coded config, assuming it's the development run");
+        ApiLoadtestConfig::default()
+    });
+
+    let executor = Executor::new(config)?;
+    let report = executor.start().await;
+    report.report();
+
+    let failures = report.failures();
+    if failures > 0 {
+        vlog::error!("{} requests didn't reach the API", failures);
+        return Err(anyhow::anyhow!("Test failed"));
+    }
+    vlog::info!("Test passed");
+    Ok(())
+}
@@ -0,0 +1,173 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use crate::request::{RequestKind, RequestOutcome};
+
+/// Latencies and outcomes of the requests of one kind.
+///
+/// All the latencies are kept, so the percentiles are exact rather than the histogram windows
+/// of `loadnext`: the regressions we look for are much smaller than its 100 ms windows.
+#[derive(Debug, Clone, Default)]
+pub struct LatencyStats {
+    latencies: Vec<Duration>,
+    successes: usize,
+    api_errors: usize,
+    failures: usize,
+}
+
+impl LatencyStats {
+    pub fn add(&mut self, outcome: RequestOutcome, latency: Duration) {
+        match outcome {
+            RequestOutcome::Success => self.successes += 1,
+            RequestOutcome::ApiError => self.api_errors += 1,
+            // The failed requests are counted, but their latency is the one of the network
+            // error rather than of the API.
+            RequestOutcome::Failed => {
+                self.failures += 1;
+                return;
+            }
+        }
+        self.latencies.push(latency);
+    }
+
+    pub fn merge(&mut self, other: Self) {
+        self.latencies.extend(other.latencies);
+        self.successes += other.successes;
+        self.api_errors += other.api_errors;
+        self.failures += other.failures;
+    }
+
+    pub fn total(&self) -> usize {
+        self.successes + self.api_errors + self.failures
+    }
+
+    /// Returns the latency below which the `percentile` percent of the responses were received,
+    /// `None` if there are no responses.
+    pub fn percentile(&self, percentile: u64) -> Option<Duration> {
+        if self.latencies.is_empty() {
+            return None;
+        }
+        let mut latencies = self.latencies.clone();
+        latencies.sort_unstable();
+        // Nearest-rank method: the smallest latency with at least `percentile` percent
+        // of the latencies less or equal to it.
+        let rank = (latencies.len() as u64 * percentile.min(100) + 99) / 100;
+        let idx = rank.saturating_sub(1) as usize;
+        Some(latencies[idx])
+    }
+}
+
+/// Statistics of the requests of each kind.
+#[derive(Debug, Clone, Default)]
+pub struct LoadtestReport {
+    pub stats: BTreeMap<RequestKind, LatencyStats>,
+    pub elapsed: Duration,
+}
+
+impl LoadtestReport {
+    pub fn add(&mut self, kind: RequestKind, outcome: RequestOutcome, latency: Duration) {
+        self.stats.entry(kind).or_default().add(outcome, latency);
+    }
+
+    pub fn merge(&mut self, other: Self) {
+        for (kind, stats) in other.stats {
+            self.stats.entry(kind).or_default().merge(stats);
+        }
+        self.elapsed = self.elapsed.max(other.elapsed);
+    }
+
+    /// Amount of the requests which didn't reach the API.
+    pub fn failures(&self) -> usize {
+        self.stats.values().map(|stats| stats.failures).sum()
+    }
+
+    pub fn report(&self) {
+        let total: usize = self.stats.values().map(LatencyStats::total).sum();
+        vlog::info!(
+            "Sent {} requests in {:?} ({:.1} rps)",
+            total,
+            self.elapsed,
+            total as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+        );
+        vlog::info!(
+            "Request: [ok, api errors, failed] [50 percentile, 90 percentile, 99 percentile, max]"
+        );
+        let millis = |latency: Option<Duration>| {
+            latency.map_or_else(
+                || "-".to_string(),
+                |latency| format!("{}ms", latency.as_millis()),
+            )
+        };
+        for (kind, stats) in self.stats.iter() {
+            vlog::info!(
+                "{:?}: [{} {} {}] [{} {} {} {}]",
+                kind,
+                stats.successes,
+                stats.api_errors,
+                stats.failures,
+                millis(stats.percentile(50)),
+                millis(stats.percentile(90)),
+                millis(stats.percentile(99)),
+                millis(stats.percentile(100)),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latency_percentiles() {
+        let mut stats = LatencyStats::default();
+        assert_eq!(stats.percentile(50), None);
+
+        for millis in (1..=100).rev() {
+            stats.add(RequestOutcome::Success, Duration::from_millis(millis));
+        }
+        assert_eq!(stats.percentile(0), Some(Duration::from_millis(1)));
+        assert_eq!(stats.percentile(50), Some(Duration::from_millis(50)));
+        assert_eq!(stats.percentile(99), Some(Duration::from_millis(99)));
+        assert_eq!(stats.percentile(100), Some(Duration::from_millis(100)));
+
+        // The rejected requests are responses of the API, the failed ones aren't.
+        stats.add(RequestOutcome::ApiError, Duration::from_millis(1000));
+        stats.add(RequestOutcome::Failed, Duration::from_secs(30));
+        assert_eq!(stats.total(), 102);
+        assert_eq!(stats.percentile(100), Some(Duration::from_millis(1000)));
+    }
+
+    #[test]
+    fn report_merge() {
+        let mut report = LoadtestReport::default();
+        report.add(
+            RequestKind::TokenPrice,
+            RequestOutcome::Success,
+            Duration::from_millis(10),
+        );
+        let mut other = LoadtestReport {
+            elapsed: Duration::from_secs(1),
+            ..Default::default()
+        };
+        other.add(
+            RequestKind::TokenPrice,
+            RequestOutcome::Success,
+            Duration::from_millis(30),
+        );
+        other.add(
+            RequestKind::TxSubmission,
+            RequestOutcome::Failed,
+            Duration::from_millis(5),
+        );
+
+        report.merge(other);
+        let token_prices = &report.stats[&RequestKind::TokenPrice];
+        assert_eq!(token_prices.total(), 2);
+        assert_eq!(
+            token_prices.percentile(100),
+            Some(Duration::from_millis(30))
+        );
+        assert_eq!(report.failures(), 1);
+        assert_eq!(report.elapsed, Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,195 @@
+use num::BigUint;
+use rand::{
+    distributions::{Distribution, WeightedIndex},
+    seq::SliceRandom,
+    Rng,
+};
+
+use zksync_api_client::rest::client::{Client, Result as ClientResult};
+use zksync_api_types::v02::{Response, ResultStatus};
+use zksync_test_account::ZkSyncAccount;
+use zksync_types::{
+    tx::{TimeRange, TxEthSignature, TxEthSignatureVariant},
+    AccountId, TokenId, TokenLike, ZkSyncTx,
+};
+
+use crate::config::ApiLoadtestConfig;
+
+/// Kind of the request sent to the API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum RequestKind {
+    /// USD price of the token, served by the fee ticker.
+    TokenPrice,
+    /// Committed state of the account, loaded from the storage.
+    AccountState,
+    /// Submission of the transfer, checked by the storage and the fee ticker.
+    TxSubmission,
+}
+
+impl RequestKind {
+    pub fn all() -> Vec<Self> {
+        vec![Self::TokenPrice, Self::AccountState, Self::TxSubmission]
+    }
+}
+
+/// Outcome of the request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestOutcome {
+    /// The API returned the result.
+    Success,
+    /// The API returned the error, e.g. it rejected the transaction.
+    ApiError,
+    /// The request didn't reach the API or its response is malformed.
+    Failed,
+}
+
+impl From<ClientResult<Response>> for RequestOutcome {
+    fn from(response: ClientResult<Response>) -> Self {
+        match response {
+            Ok(response) => match response.status {
+                ResultStatus::Success => Self::Success,
+                ResultStatus::Error => Self::ApiError,
+            },
+            Err(err) if err.api_error_code().is_some() => Self::ApiError,
+            Err(err) => {
+                vlog::debug!("Request failed: {}", err);
+                Self::Failed
+            }
+        }
+    }
+}
+
+/// Weighted mix of the request kinds.
+#[derive(Debug, Clone)]
+pub struct RequestMix {
+    kinds: Vec<RequestKind>,
+    weights: WeightedIndex<u32>,
+}
+
+impl RequestMix {
+    pub fn new(weights: &[(RequestKind, u32)]) -> anyhow::Result<Self> {
+        let index = WeightedIndex::new(weights.iter().map(|(_, weight)| *weight))
+            .map_err(|err| anyhow::anyhow!("Invalid weights of the request mix: {}", err))?;
+        Ok(Self {
+            kinds: weights.iter().map(|(kind, _)| *kind).collect(),
+            weights: index,
+        })
+    }
+
+    pub fn from_config(config: &ApiLoadtestConfig) -> anyhow::Result<Self> {
+        Self::new(&[
+            (RequestKind::TokenPrice, config.token_price_weight),
+            (RequestKind::AccountState, config.account_state_weight),
+            (RequestKind::TxSubmission, config.tx_submission_weight),
+        ])
+    }
+
+    pub fn pick(&self, rng: &mut impl Rng) -> RequestKind {
+        self.kinds[self.weights.sample(rng)]
+    }
+}
+
+/// Sends the requests of each kind with the randomly chosen parameters.
+#[derive(Debug)]
+pub struct RequestSender {
+    client: Client,
+    tokens: Vec<TokenLike>,
+    accounts: Vec<ZkSyncAccount>,
+}
+
+impl RequestSender {
+    pub fn new(config: &ApiLoadtestConfig) -> anyhow::Result<Self> {
+        anyhow::ensure!(!config.tokens.is_empty(), "No tokens to request prices of");
+        anyhow::ensure!(config.accounts_amount > 0, "No accounts to send requests");
+
+        let accounts = (0..config.accounts_amount)
+            .map(|idx| {
+                let account = ZkSyncAccount::rand();
+                // The accounts don't exist, so any id is fine for signing.
+                account.set_account_id(Some(AccountId(idx as u32 + 1)));
+                account
+            })
+            .collect();
+        Ok(Self {
+            client: Client::new(config.api_url.clone()),
+            tokens: config
+                .tokens
+                .iter()
+                .map(|token| TokenLike::parse(token))
+                .collect(),
+            accounts,
+        })
+    }
+
+    pub async fn send(&self, kind: RequestKind, rng: &mut impl Rng) -> RequestOutcome {
+        match kind {
+            RequestKind::TokenPrice => {
+                let token = self.tokens.choose(rng).expect("tokens are not empty");
+                self.client.token_price(token, "usd").await.into()
+            }
+            RequestKind::AccountState => {
+                let account = self.accounts.choose(rng).expect("accounts are not empty");
+                let address = format!("{:?}", account.address);
+                self.client.account_info(&address, "committed").await.into()
+            }
+            RequestKind::TxSubmission => {
+                let from = self.accounts.choose(rng).expect("accounts are not empty");
+                let to = self.accounts.choose(rng).expect("accounts are not empty");
+                // The zero fee is rejected only after the fee ticker is requested for the required
+                // fee, so the submission goes through the same checks as the real transactions.
+                let (transfer, eth_signature) = from.sign_transfer(
+                    TokenId(0),
+                    "ETH",
+                    BigUint::from(rng.gen_range(1u32..1000)),
+                    BigUint::from(0u32),
+                    &to.address,
+                    None,
+                    false,
+                    TimeRange::default(),
+                );
+                let signature = TxEthSignatureVariant::Single(
+                    eth_signature.map(TxEthSignature::EthereumSignature),
+                );
+                self.client
+                    .submit_tx(ZkSyncTx::Transfer(Box::new(transfer)), signature)
+                    .await
+                    .into()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::collections::HashMap;
+
+    #[test]
+    fn request_mix() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mix = RequestMix::new(&[
+            (RequestKind::TokenPrice, 3),
+            (RequestKind::AccountState, 1),
+            (RequestKind::TxSubmission, 0),
+        ])
+        .unwrap();
+
+        let mut picked = HashMap::new();
+        for _ in 0..4000 {
+            *picked.entry(mix.pick(&mut rng)).or_insert(0) += 1;
+        }
+        // The kinds with the zero weight are never picked.
+        assert!(!picked.contains_key(&RequestKind::TxSubmission));
+        let token_prices = picked[&RequestKind::TokenPrice];
+        assert!((2800..3200).contains(&token_prices), "{}", token_prices);
+
+        // The mix must contain at least one kind of the requests.
+        assert!(RequestMix::new(&[
+            (RequestKind::TokenPrice, 0),
+            (RequestKind::AccountState, 0),
+            (RequestKind::TxSubmission, 0),
+        ])
+        .is_err());
+    }
+}
@@ -7,7 +7,7 @@ use tokio::runtime::Runtime;
 use zksync_core::{
     committer::CommitRequest,
     state_keeper::{
-        start_root_hash_calculator, StateKeeperTestkitRequest, ZkSyncStateInitParams,
+        start_root_hash_calculator, SealPolicy, StateKeeperTestkitRequest, ZkSyncStateInitParams,
         ZkSyncStateKeeper,
     },
     tx_event_emitter::ProcessedOperations,
@@ -72,6 +72,7 @@ pub fn spawn_state_keeper(
         block_chunks_sizes,
         max_miniblock_iterations,
         max_miniblock_iterations,
+        SealPolicy::default(),
         processed_tx_events_sender,
     );
 
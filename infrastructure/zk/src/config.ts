@@ -23,7 +23,8 @@ const CONFIG_FILES = [
     'private.toml',
     'forced_exit_requests.toml',
     'token_handler.toml',
-    'nft_factory.toml'
+    'nft_factory.toml',
+    'network_profile.toml'
 ];
 
 async function getEnvironment(): Promise<string> {
@@ -39,6 +39,11 @@ export async function check(expectedHash: string) {
     );
 }
 
+export async function verify() {
+    await db.reset();
+    await utils.spawn('cargo run --bin zksync_data_restore --release -- --genesis --finite --verify_state_roots');
+}
+
 export async function checkExisting() {
     const expectedHash = await rootHash();
     await check(expectedHash);
@@ -55,6 +60,11 @@ command
     .description('wipe the database, run the data restore in finite mode and check the root hash')
     .action(check);
 
+command
+    .command('verify')
+    .description('wipe the database, run the data restore in finite mode and check the state roots with the contract')
+    .action(verify);
+
 command
     .command('check-existing')
     .description(`like "check", but instead hash is loaded from the database before wiping it`)